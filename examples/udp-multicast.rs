@@ -0,0 +1,75 @@
+//! A UDP multicast listener printing the datagrams sent to a multicast group.
+//!
+//! The listener joins the group given as the first argument, which defaults
+//! to 239.255.42.98:8080, on all interfaces. You can test this out by in one
+//! terminal executing:
+//!
+//!     cargo run --example udp-multicast
+//!
+//! and in another terminal, executing:
+//!
+//!     cargo run --example udp-multicast -- 239.255.42.98:8080 hello
+//!
+//! Each datagram sent to the group is received and printed by every listener
+//! that joined it, including the ones running on the same host.
+
+#![deny(warnings)]
+
+extern crate tokio;
+extern crate tokio_codec;
+
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use tokio::net::{UdpFramed, UdpSocket};
+use tokio::prelude::*;
+use tokio_codec::BytesCodec;
+
+fn main() -> Result<(), Box<std::error::Error>> {
+    let group = env::args()
+        .nth(1)
+        .unwrap_or("239.255.42.98:8080".to_string());
+    let group = group.parse::<SocketAddr>()?;
+
+    let multiaddr = match group.ip() {
+        IpAddr::V4(ip) if ip.is_multicast() => ip,
+        _ => return Err("the group must be an IPv4 multicast address".into()),
+    };
+
+    // With a message to send, act as the sender instead.
+    if let Some(message) = env::args().nth(2) {
+        let any = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+        let socket = UdpSocket::bind(&any)?;
+
+        // Let the datagram reach listeners on the local host, but not leave
+        // the local network.
+        socket.set_multicast_loop_v4(true)?;
+        socket.set_multicast_ttl_v4(1)?;
+
+        let send = socket
+            .send_dgram(message.into_bytes(), &group)
+            .map(move |_| println!("Sent message to {}", group))
+            .map_err(|e| println!("error = {:?}", e));
+
+        tokio::run(send);
+        return Ok(());
+    }
+
+    // Bind the port of the group on all addresses, so that the datagrams sent
+    // to the group are received, then join the group on any interface.
+    let addr = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), group.port());
+    let socket = UdpSocket::bind(&addr)?;
+    socket.join_multicast_v4(&multiaddr, &Ipv4Addr::UNSPECIFIED)?;
+    println!("Listening to {}", group);
+
+    let listener = UdpFramed::new(socket, BytesCodec::new())
+        .for_each(|(msg, addr)| {
+            println!("{}: {}", addr, String::from_utf8_lossy(&msg));
+            Ok(())
+        })
+        .map_err(|e| println!("error = {:?}", e));
+
+    // The group is left when the socket is closed.
+    tokio::run(listener);
+    Ok(())
+}
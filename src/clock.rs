@@ -7,9 +7,80 @@
 //!
 //! Note that, because the source of time is configurable, it is possible to
 //! observe non-monotonic behavior when calling [`now`][n] from different
-//! executors.
+//! executors. For this reason [`now`][n] is deprecated in favor of
+//! [`timer::Handle::now`], which reads the clock of the runtime's timer.
+//!
+//! Time can also be paused on a [current thread runtime] built with
+//! [`enable_pause`], in order to test logic depending on timeouts without
+//! waiting for them to actually elapse. See [`pause`] and [`advance`].
 //!
 //! [n]: fn.now.html
 //! [`tokio-timer`]: https://docs.rs/tokio-timer/0.2/tokio_timer/clock/index.html
+//! [`timer::Handle::now`]: ../timer/struct.Handle.html#method.now
+//! [current thread runtime]: ../runtime/current_thread/index.html
+//! [`enable_pause`]: ../runtime/current_thread/struct.Builder.html#method.enable_pause
+//! [`pause`]: fn.pause.html
+//! [`advance`]: fn.advance.html
 
+#[allow(deprecated)]
 pub use tokio_timer::clock::now;
+
+#[cfg(feature = "rt-full")]
+use runtime::current_thread::Pause;
+#[cfg(feature = "rt-full")]
+use std::time::Duration;
+
+/// Pause time on the current runtime.
+///
+/// Once paused, [`now`] keeps returning the same instant until time is moved
+/// forward, either explicitly with [`advance`] or automatically: whenever the
+/// runtime has no futures ready to run, time jumps to the next pending
+/// timeout. Pausing time that is already paused has no effect.
+///
+/// # Panics
+///
+/// This function panics if it is not called from a [current thread runtime]
+/// built with [`enable_pause`].
+///
+/// [`now`]: fn.now.html
+/// [`advance`]: fn.advance.html
+/// [current thread runtime]: ../runtime/current_thread/index.html
+/// [`enable_pause`]: ../runtime/current_thread/struct.Builder.html#method.enable_pause
+#[cfg(feature = "rt-full")]
+pub fn pause() {
+    Pause::with_current(|pause| pause.pause())
+}
+
+/// Resume time on the current runtime.
+///
+/// Time continues to pass from the instant it was paused at, at the rate of
+/// the runtime's clock. Resuming time that is not paused has no effect.
+///
+/// # Panics
+///
+/// This function panics if it is not called from a [current thread runtime]
+/// built with [`enable_pause`].
+///
+/// [current thread runtime]: ../runtime/current_thread/index.html
+/// [`enable_pause`]: ../runtime/current_thread/struct.Builder.html#method.enable_pause
+#[cfg(feature = "rt-full")]
+pub fn resume() {
+    Pause::with_current(|pause| pause.resume())
+}
+
+/// Move paused time forward by `duration`.
+///
+/// Timeouts that expire as a result fire the next time the runtime turns its
+/// timer, at the latest once the currently running future yields.
+///
+/// # Panics
+///
+/// This function panics if time is not paused, or if it is not called from a
+/// [current thread runtime] built with [`enable_pause`].
+///
+/// [current thread runtime]: ../runtime/current_thread/index.html
+/// [`enable_pause`]: ../runtime/current_thread/struct.Builder.html#method.enable_pause
+#[cfg(feature = "rt-full")]
+pub fn advance(duration: Duration) {
+    Pause::with_current(|pause| pause.advance(duration))
+}
@@ -312,6 +312,51 @@
 //! anywhere because it already is factored into the total frame length that
 //! is read from the byte stream.
 //!
+//! # Oversized frames
+//!
+//! A frame whose length exceeds the max frame length fails decoding with an
+//! `io::Error` wrapping a [`FrameTooBig`], which carries the offending
+//! length. As the stream is then left in the middle of the frame, it cannot
+//! be decoded any further. Using [`Builder::discard_oversized_frames`], such
+//! frames are instead skipped as their bytes arrive and decoding resumes with
+//! the next frame.
+//!
+//! # Length field mask
+//!
+//! When some bits of the length field hold flags, such as the type of the
+//! frame, [`Builder::length_field_mask`] keeps the bits of the length only.
+//! The mask is applied to the length field before it is checked against the
+//! max frame length and adjusted.
+//!
+//! # Variable length field
+//!
+//! Some protocols, such as streams of length delimited protobuf messages,
+//! encode the length field as a varint instead of a fixed width integer.
+//! [`Builder::varint`] configures the codec to read and write such a length
+//! field, which makes the head of each frame as long as needed to hold the
+//! length. Other encodings can be plugged in with
+//! [`LengthDelimitedCodec::set_length_codec`].
+//!
+//! ```
+//! # extern crate tokio;
+//! # use tokio::io::AsyncRead;
+//! # use tokio::codec::length_delimited;
+//! # fn bind_read<T: AsyncRead>(io: T) {
+//! length_delimited::Builder::new()
+//!     .varint()
+//!     .new_read(io);
+//! # }
+//! # pub fn main() {}
+//! ```
+//!
+//! A payload of `hello world` is then delimited as:
+//!
+//! ```text
+//! +- len -+---- data ----+
+//! | \x0b  |  hello world |
+//! +-------+--------------+
+//! ```
+//!
 //! # Encoding
 //!
 //! [`FramedWrite`] adapts an [`AsyncWrite`] into a `Sink` of [`BytesMut`],
@@ -347,6 +392,11 @@
 //! ```
 //!
 //! [`LengthDelimitedCodec::new()`]: struct.LengthDelimitedCodec.html#method.new
+//! [`FrameTooBig`]: struct.FrameTooBig.html
+//! [`Builder::discard_oversized_frames`]: struct.Builder.html#method.discard_oversized_frames
+//! [`Builder::length_field_mask`]: struct.Builder.html#method.length_field_mask
+//! [`Builder::varint`]: struct.Builder.html#method.varint
+//! [`LengthDelimitedCodec::set_length_codec`]: struct.LengthDelimitedCodec.html#method.set_length_codec
 //! [`FramedRead`]: struct.FramedRead.html
 //! [`FramedWrite`]: struct.FramedWrite.html
 //! [`AsyncRead`]: ../../trait.AsyncRead.html
@@ -355,14 +405,15 @@
 //! [`BytesMut`]: https://docs.rs/bytes/0.4/bytes/struct.BytesMut.html
 
 use {
-    codec::{Decoder, Encoder, Framed, FramedRead, FramedWrite},
+    codec::{Decoder, EncodeRef, Encoder, Framed, FramedRead, FramedWrite},
     io::{AsyncRead, AsyncWrite},
 };
 
-use bytes::{Buf, BufMut, Bytes, BytesMut, IntoBuf};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use std::error::Error as StdError;
 use std::io::{self, Cursor};
+use std::sync::Arc;
 use std::{cmp, fmt};
 
 /// Configure length delimited `LengthDelimitedCodec`s.
@@ -390,11 +441,46 @@ pub struct Builder {
 
     // Length field byte order (little or big endian)
     length_field_is_big_endian: bool,
+
+    // Encode the length field as a varint instead of a fixed width integer
+    varint: bool,
+
+    // Bits of the length field holding the length
+    length_field_mask: u64,
+
+    // Skip frames exceeding the max frame length instead of failing
+    discard_oversized_frames: bool,
+}
+
+/// Encoding of the length field of a frame head.
+///
+/// By default, the length field is a fixed width integer. Implementing this
+/// trait allows using another encoding, possibly of variable width, with
+/// [`LengthDelimitedCodec::set_length_codec`].
+///
+/// [`LengthDelimitedCodec::set_length_codec`]: struct.LengthDelimitedCodec.html#method.set_length_codec
+pub trait LengthCodec: fmt::Debug + Send + Sync {
+    /// Decode a length field from the start of `src`.
+    ///
+    /// Returns the length along with the number of bytes of the field, or
+    /// `None` if `src` does not hold the whole field yet.
+    fn decode(&self, src: &[u8]) -> io::Result<Option<(u64, usize)>>;
+
+    /// Append the length field representing `len` to `dst`.
+    fn encode(&self, len: u64, dst: &mut BytesMut) -> io::Result<()>;
 }
 
+/// Unsigned LEB128 length field, as used by protobuf.
+#[derive(Debug)]
+struct Varint;
+
+/// Maximum number of bytes of a varint encoded `u64`
+const MAX_VARINT_LEN: usize = 10;
+
 /// An error when the number of bytes read is more than max frame length.
 pub struct FrameTooBig {
-    _priv: (),
+    len: u64,
+    max: usize,
 }
 
 /// A codec for frames delimited by a frame head specifying their lengths.
@@ -410,6 +496,10 @@ pub struct LengthDelimitedCodec {
     // Configuration values
     builder: Builder,
 
+    // Encoding of the length field, if not set, a fixed width integer of
+    // `builder.length_field_len` bytes
+    length_codec: Option<Arc<LengthCodec>>,
+
     // Read state
     state: DecodeState,
 }
@@ -418,6 +508,11 @@ pub struct LengthDelimitedCodec {
 enum DecodeState {
     Head,
     Data(usize),
+    Discard(usize),
+    // The head of an oversized frame is left in the buffer after an error
+    Oversized,
+    // As `Head`, discarding the frame if it is oversized
+    SkipHead,
 }
 
 // ===== impl LengthDelimitedCodec ======
@@ -427,6 +522,7 @@ impl LengthDelimitedCodec {
     pub fn new() -> Self {
         Self {
             builder: Builder::new(),
+            length_codec: None,
             state: DecodeState::Head,
         }
     }
@@ -449,36 +545,143 @@ impl LengthDelimitedCodec {
         self.builder.max_frame_length(val);
     }
 
-    fn decode_head(&mut self, src: &mut BytesMut) -> io::Result<Option<usize>> {
-        let head_len = self.builder.num_head_bytes();
-        let field_len = self.builder.length_field_len;
+    /// Encodes the length field with a custom `LengthCodec`.
+    ///
+    /// This replaces the fixed width or varint length field configured with
+    /// the `Builder`, whose byte order and length field length are then
+    /// ignored. When decoding, the default number of bytes to skip is
+    /// `length_field_offset` plus the length of the field as returned by the
+    /// codec.
+    ///
+    /// This applies to both encoding and decoding, and should be set before
+    /// the codec is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate bytes;
+    /// # use tokio::io::AsyncRead;
+    /// use tokio::codec::FramedRead;
+    /// use tokio::codec::length_delimited::{Builder, LengthCodec};
+    /// use bytes::{BufMut, BytesMut};
+    /// use std::io;
+    ///
+    /// /// Length as ASCII decimal digits, terminated by `:`
+    /// #[derive(Debug)]
+    /// struct Decimal;
+    ///
+    /// impl LengthCodec for Decimal {
+    ///     fn decode(&self, src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+    ///         let end = match src.iter().position(|b| *b == b':') {
+    ///             Some(end) => end,
+    ///             None => return Ok(None),
+    ///         };
+    ///
+    ///         std::str::from_utf8(&src[..end])
+    ///             .ok()
+    ///             .and_then(|s| s.parse().ok())
+    ///             .map(|n| Some((n, end + 1)))
+    ///             .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid length"))
+    ///     }
+    ///
+    ///     fn encode(&self, len: u64, dst: &mut BytesMut) -> io::Result<()> {
+    ///         let len = format!("{}:", len);
+    ///         dst.reserve(len.len());
+    ///         dst.put_slice(len.as_bytes());
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// let mut codec = Builder::new().new_codec();
+    /// codec.set_length_codec(Decimal);
+    ///
+    /// FramedRead::new(io, codec);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn set_length_codec<C>(&mut self, codec: C)
+    where
+        C: LengthCodec + 'static,
+    {
+        self.length_codec = Some(Arc::new(codec));
+    }
+
+    fn num_head_bytes(&self) -> usize {
+        // A variable length field takes at least one byte
+        let field_len = match self.length_codec {
+            Some(_) => 1,
+            None => self.builder.length_field_len,
+        };
+        let num = self.builder.length_field_offset + field_len;
+        cmp::max(num, self.builder.num_skip.unwrap_or(0))
+    }
+
+    fn decode_head(&mut self, src: &mut BytesMut, discard: bool) -> io::Result<Option<usize>> {
+        let head_len = self.num_head_bytes();
+        let mut field_len = self.builder.length_field_len;
 
         if src.len() < head_len {
             // Not enough data
             return Ok(None);
         }
 
+        let mut oversized = false;
+
         let n = {
-            let mut src = Cursor::new(&mut *src);
+            let n = match self.length_codec {
+                Some(ref codec) => {
+                    let offset = self.builder.length_field_offset;
+
+                    match try!(codec.decode(&src[offset..])) {
+                        Some((n, len)) => {
+                            field_len = len;
+                            n
+                        }
+                        None => return Ok(None),
+                    }
+                }
+                None => {
+                    let mut src = Cursor::new(&mut *src);
 
-            // Skip the required bytes
-            src.advance(self.builder.length_field_offset);
+                    // Skip the required bytes
+                    src.advance(self.builder.length_field_offset);
 
-            // match endianess
-            let n = if self.builder.length_field_is_big_endian {
-                src.get_uint_be(field_len)
-            } else {
-                src.get_uint_le(field_len)
+                    // match endianess
+                    if self.builder.length_field_is_big_endian {
+                        src.get_uint_be(field_len)
+                    } else {
+                        src.get_uint_le(field_len)
+                    }
+                }
             };
 
+            let n = n & self.builder.length_field_mask;
+
             if n > self.builder.max_frame_len as u64 {
+                let err = FrameTooBig {
+                    len: n,
+                    max: self.builder.max_frame_len,
+                };
+
+                if !discard {
+                    self.state = DecodeState::Oversized;
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+                }
+
+                oversized = true;
+            }
+
+            // Lengths of oversized frames are only needed to discard them, in
+            // which case they have to fit in memory
+            if n > usize::max_value() as u64 {
                 return Err(io::Error::new(
                     io::ErrorKind::InvalidData,
-                    FrameTooBig { _priv: () },
+                    "provided length does not fit in a usize",
                 ));
             }
 
-            // The check above ensures there is no overflow
             let n = n as usize;
 
             // Adjust `n` with bounds checking
@@ -500,12 +703,25 @@ impl LengthDelimitedCodec {
             }
         };
 
-        let num_skip = self.builder.get_num_skip();
+        let num_skip = self
+            .builder
+            .num_skip
+            .unwrap_or(self.builder.length_field_offset + field_len);
+
+        if src.len() < num_skip {
+            // The length field ends after the configured head
+            return Ok(None);
+        }
 
         if num_skip > 0 {
             let _ = src.split_to(num_skip);
         }
 
+        if oversized {
+            self.state = DecodeState::Discard(n);
+            return Ok(None);
+        }
+
         // Ensure that the buffer has enough space to read the incoming
         // payload
         src.reserve(n);
@@ -522,6 +738,21 @@ impl LengthDelimitedCodec {
 
         Ok(Some(src.split_to(n)))
     }
+
+    /// Drop up to `n` bytes of an oversized frame, returning `true` once the
+    /// whole frame has been dropped.
+    fn discard_data(&mut self, n: usize, src: &mut BytesMut) -> bool {
+        let len = cmp::min(n, src.len());
+        let _ = src.split_to(len);
+
+        if len < n {
+            self.state = DecodeState::Discard(n - len);
+            false
+        } else {
+            self.state = DecodeState::Head;
+            true
+        }
+    }
 }
 
 impl Decoder for LengthDelimitedCodec {
@@ -529,28 +760,60 @@ impl Decoder for LengthDelimitedCodec {
     type Error = io::Error;
 
     fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
-        let n = match self.state {
-            DecodeState::Head => match try!(self.decode_head(src)) {
-                Some(n) => {
-                    self.state = DecodeState::Data(n);
-                    n
+        loop {
+            let n = match self.state {
+                DecodeState::Head | DecodeState::Oversized | DecodeState::SkipHead => {
+                    let discard = match self.state {
+                        DecodeState::SkipHead => true,
+                        _ => self.builder.discard_oversized_frames,
+                    };
+
+                    match try!(self.decode_head(src, discard)) {
+                        Some(n) => {
+                            self.state = DecodeState::Data(n);
+                            n
+                        }
+                        None => match self.state {
+                            // The head of an oversized frame was read
+                            DecodeState::Discard(_) => continue,
+                            _ => return Ok(None),
+                        },
+                    }
                 }
-                None => return Ok(None),
-            },
-            DecodeState::Data(n) => n,
-        };
+                DecodeState::Data(n) => n,
+                DecodeState::Discard(n) => {
+                    if self.discard_data(n, src) {
+                        continue;
+                    }
+
+                    return Ok(None);
+                }
+            };
 
-        match try!(self.decode_data(n, src)) {
-            Some(data) => {
-                // Update the decode state
-                self.state = DecodeState::Head;
+            return match try!(self.decode_data(n, src)) {
+                Some(data) => {
+                    // Update the decode state
+                    self.state = DecodeState::Head;
 
-                // Make sure the buffer has enough space to read the next head
-                src.reserve(self.builder.num_head_bytes());
+                    // Make sure the buffer has enough space to read the next head
+                    src.reserve(self.num_head_bytes());
 
-                Ok(Some(data))
+                    Ok(Some(data))
+                }
+                None => Ok(None),
+            };
+        }
+    }
+
+    fn recover(&mut self, _src: &mut BytesMut) -> bool {
+        // Only oversized frames can be skipped, other errors leave the stream
+        // without a valid length.
+        match self.state {
+            DecodeState::Oversized => {
+                self.state = DecodeState::SkipHead;
+                true
             }
-            None => Ok(None),
+            _ => false,
         }
     }
 }
@@ -560,12 +823,21 @@ impl Encoder for LengthDelimitedCodec {
     type Error = io::Error;
 
     fn encode(&mut self, data: Bytes, dst: &mut BytesMut) -> Result<(), io::Error> {
-        let n = (&data).into_buf().remaining();
+        self.encode_ref(&data, dst)
+    }
+}
+
+impl EncodeRef for LengthDelimitedCodec {
+    fn encode_ref(&mut self, data: &Bytes, dst: &mut BytesMut) -> Result<(), io::Error> {
+        let n = data.len();
 
         if n > self.builder.max_frame_len {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
-                FrameTooBig { _priv: () },
+                FrameTooBig {
+                    len: n as u64,
+                    max: self.builder.max_frame_len,
+                },
             ));
         }
 
@@ -583,14 +855,29 @@ impl Encoder for LengthDelimitedCodec {
             )
         })?;
 
-        // Reserve capacity in the destination buffer to fit the frame and
-        // length field (plus adjustment).
-        dst.reserve(self.builder.length_field_len + n);
+        if n as u64 & !self.builder.length_field_mask != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "provided length does not fit in the length field mask",
+            ));
+        }
 
-        if self.builder.length_field_is_big_endian {
-            dst.put_uint_be(n as u64, self.builder.length_field_len);
-        } else {
-            dst.put_uint_le(n as u64, self.builder.length_field_len);
+        match self.length_codec {
+            Some(ref codec) => {
+                try!(codec.encode(n as u64, dst));
+                dst.reserve(n);
+            }
+            None => {
+                // Reserve capacity in the destination buffer to fit the frame
+                // and length field (plus adjustment).
+                dst.reserve(self.builder.length_field_len + n);
+
+                if self.builder.length_field_is_big_endian {
+                    dst.put_uint_be(n as u64, self.builder.length_field_len);
+                } else {
+                    dst.put_uint_le(n as u64, self.builder.length_field_len);
+                }
+            }
         }
 
         // Write the frame to the buffer
@@ -642,6 +929,15 @@ impl Builder {
 
             // Default to reading the length field in network (big) endian.
             length_field_is_big_endian: true,
+
+            // Default to a fixed width length field
+            varint: false,
+
+            // Default to the whole length field holding the length
+            length_field_mask: !0,
+
+            // Default to failing on oversized frames
+            discard_oversized_frames: false,
         }
     }
 
@@ -729,12 +1025,13 @@ impl Builder {
     /// default value is 8MB.
     ///
     /// When decoding, the length field read from the byte stream is checked
-    /// against this setting **before** any adjustments are applied. When
-    /// encoding, the length of the submitted payload is checked against this
-    /// setting.
+    /// against this setting after the [`length_field_mask`] is applied, but
+    /// **before** any adjustments are applied. When encoding, the length of
+    /// the submitted payload is checked against this setting.
     ///
     /// When frames exceed the max length, an `io::Error` with the custom value
-    /// of the `FrameTooBig` type will be returned.
+    /// of the `FrameTooBig` type will be returned, unless decoding with
+    /// [`discard_oversized_frames`] set.
     ///
     /// # Examples
     ///
@@ -750,15 +1047,87 @@ impl Builder {
     /// # }
     /// # pub fn main() {}
     /// ```
+    ///
+    /// [`length_field_mask`]: #method.length_field_mask
+    /// [`discard_oversized_frames`]: #method.discard_oversized_frames
     pub fn max_frame_length(&mut self, val: usize) -> &mut Self {
         self.max_frame_len = val;
         self
     }
 
+    /// Skip frames exceeding the max frame length instead of failing
+    ///
+    /// When set, the head of an oversized frame is consumed and its payload
+    /// is discarded as it is read from the byte stream, without buffering it.
+    /// Decoding then resumes with the next frame. Otherwise, decoding fails
+    /// with a `FrameTooBig` error and the stream cannot be decoded any
+    /// further.
+    ///
+    /// The default value is `false`.
+    ///
+    /// This configuration option only applies to decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # use tokio::io::AsyncRead;
+    /// use tokio::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .max_frame_length(8 * 1024)
+    ///     .discard_oversized_frames(true)
+    ///     .new_read(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn discard_oversized_frames(&mut self, val: bool) -> &mut Self {
+        self.discard_oversized_frames = val;
+        self
+    }
+
+    /// Sets the bits of the length field holding the length
+    ///
+    /// The other bits of the length field are cleared before the length is
+    /// checked against the max frame length and adjusted by the
+    /// [`length_adjustment`]. This allows flags to be stored in the length
+    /// field.
+    ///
+    /// The default value is `!0`, using the whole length field.
+    ///
+    /// When encoding, frames whose adjusted length does not fit in the mask are
+    /// rejected. The bits outside the mask are written as zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # use tokio::io::AsyncRead;
+    /// use tokio::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// // The most significant bit of the length field is a flag
+    /// Builder::new()
+    ///     .length_field_mask(0x7fff_ffff)
+    ///     .new_read(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    ///
+    /// [`length_adjustment`]: #method.length_adjustment
+    pub fn length_field_mask(&mut self, val: u64) -> &mut Self {
+        self.length_field_mask = val;
+        self
+    }
+
     /// Sets the number of bytes used to represent the length field
     ///
     /// The default value is `4`. The max value is `8`.
     ///
+    /// This switches back to a fixed width length field if [`varint`] was
+    /// set.
+    ///
     /// This configuration option applies to both encoding and decoding.
     ///
     /// # Examples
@@ -775,9 +1144,43 @@ impl Builder {
     /// # }
     /// # pub fn main() {}
     /// ```
+    ///
+    /// [`varint`]: #method.varint
     pub fn length_field_length(&mut self, val: usize) -> &mut Self {
         assert!(val > 0 && val <= 8, "invalid length field length");
         self.length_field_len = val;
+        self.varint = false;
+        self
+    }
+
+    /// Encode the length field as an unsigned varint
+    ///
+    /// Each byte of the field holds 7 bits of the length, least significant
+    /// first, and has its high bit set if more bytes follow. This is the
+    /// encoding used to delimit protobuf messages.
+    ///
+    /// The length field is as long as needed to hold the length, up to 10
+    /// bytes. When decoding, the default number of bytes to skip is
+    /// `length_field_offset` plus the length of the varint.
+    ///
+    /// This configuration option applies to both encoding and decoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # use tokio::io::AsyncRead;
+    /// use tokio::codec::length_delimited::Builder;
+    ///
+    /// # fn bind_read<T: AsyncRead>(io: T) {
+    /// Builder::new()
+    ///     .varint()
+    ///     .new_read(io);
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    pub fn varint(&mut self) -> &mut Self {
+        self.varint = true;
         self
     }
 
@@ -869,8 +1272,15 @@ impl Builder {
     /// # }
     /// ```
     pub fn new_codec(&self) -> LengthDelimitedCodec {
+        let length_codec: Option<Arc<LengthCodec>> = if self.varint {
+            Some(Arc::new(Varint))
+        } else {
+            None
+        };
+
         LengthDelimitedCodec {
             builder: *self,
+            length_codec,
             state: DecodeState::Head,
         }
     }
@@ -949,29 +1359,76 @@ impl Builder {
     {
         Framed::new(inner, self.new_codec())
     }
+}
 
-    fn num_head_bytes(&self) -> usize {
-        let num = self.length_field_offset + self.length_field_len;
-        cmp::max(num, self.num_skip.unwrap_or(0))
+// ===== impl Varint =====
+
+impl LengthCodec for Varint {
+    fn decode(&self, src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+        let mut n = 0;
+
+        for (i, &b) in src.iter().enumerate() {
+            // The last byte only holds the most significant bit of a `u64`
+            if i == MAX_VARINT_LEN - 1 && b > 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "varint length field overflows a u64",
+                ));
+            }
+
+            n |= u64::from(b & 0x7f) << (7 * i);
+
+            if b & 0x80 == 0 {
+                return Ok(Some((n, i + 1)));
+            }
+        }
+
+        Ok(None)
     }
 
-    fn get_num_skip(&self) -> usize {
-        self.num_skip
-            .unwrap_or(self.length_field_offset + self.length_field_len)
+    fn encode(&self, mut len: u64, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(MAX_VARINT_LEN);
+
+        while len >= 0x80 {
+            dst.put_u8(len as u8 | 0x80);
+            len >>= 7;
+        }
+
+        dst.put_u8(len as u8);
+
+        Ok(())
     }
 }
 
 // ===== impl FrameTooBig =====
 
+impl FrameTooBig {
+    /// Returns the length of the frame.
+    ///
+    /// When decoding, this is the length read from the length field, before
+    /// any adjustments are applied.
+    pub fn frame_length(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns the max frame length the frame was checked against.
+    pub fn max_frame_length(&self) -> usize {
+        self.max
+    }
+}
+
 impl fmt::Debug for FrameTooBig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("FrameTooBig").finish()
+        f.debug_struct("FrameTooBig")
+            .field("len", &self.len)
+            .field("max", &self.max)
+            .finish()
     }
 }
 
 impl fmt::Display for FrameTooBig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(self.description())
+        write!(f, "frame of {} bytes exceeds max of {}", self.len, self.max)
     }
 }
 
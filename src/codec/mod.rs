@@ -11,7 +11,8 @@
 //! [transports]: https://tokio.rs/docs/going-deeper/frames/
 
 pub use tokio_codec::{
-    BytesCodec, Decoder, Encoder, Framed, FramedParts, FramedRead, FramedWrite, LinesCodec,
+    AnyDelimiterCodec, BytesCodec, CobsCodec, DecodeRecovery, Decoder, EncodeRef, Encoder, Framed,
+    FramedParts, FramedRead, FramedWrite, LengthError, LinesCodec, NetstringCodec, SlipCodec,
 };
 
 pub mod length_delimited;
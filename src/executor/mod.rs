@@ -61,6 +61,8 @@ pub mod thread_pool {
 }
 
 pub use tokio_executor::{Executor, DefaultExecutor, SpawnError};
+pub use tokio_executor::{AbortHandle, JoinError, JoinHandle, SpawnWithHandle};
+pub use tokio_executor::park;
 
 use futures::{Future, IntoFuture};
 use futures::future::{self, FutureResult};
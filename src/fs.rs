@@ -7,9 +7,17 @@
 //! the context of the Tokio runtime as they require Tokio specific features to
 //! function.
 
-pub use tokio_fs::OpenOptions;
 pub use tokio_fs::{
-    create_dir, create_dir_all, file, hard_link, metadata, os, read_dir, read_link,
+    canonicalize, copy, create_dir, create_dir_all, file, hard_link, limit, metadata, os, read_dir,
+    read_link,
 };
+pub use tokio_fs::{copy_with_progress, CopyOptions, CopyWithProgress, OpenOptions};
 pub use tokio_fs::{read, write, ReadFile, WriteFile};
-pub use tokio_fs::{remove_dir, remove_file, rename, set_permissions, symlink_metadata, File};
+pub use tokio_fs::{
+    remove_dir, remove_file, rename, set_permissions, set_times, symlink_metadata, File,
+};
+pub use tokio_fs::{temp, tempdir, tempfile, NamedTempFile, TempDir};
+pub use tokio_fs::{walk_dir, WalkDir, WalkEntry};
+#[cfg(feature = "fs-watch")]
+pub use tokio_fs::{watch, Watch};
+pub use tokio_fs::{DirEntry, ReadDir};
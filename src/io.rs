@@ -45,7 +45,7 @@
 //! [`ErrorKind`]: enum.ErrorKind.html
 //! [`Result`]: type.Result.html
 
-pub use tokio_io::{AsyncRead, AsyncWrite};
+pub use tokio_io::{AsyncBufRead, AsyncRead, AsyncSeek, AsyncWrite, IoVec};
 
 // standard input, output, and error
 #[cfg(feature = "fs")]
@@ -53,8 +53,10 @@ pub use tokio_fs::{stderr, stdin, stdout, Stderr, Stdin, Stdout};
 
 // Utils
 pub use tokio_io::io::{
-    copy, flush, lines, read, read_exact, read_to_end, read_until, shutdown, write_all, Copy,
-    Flush, Lines, ReadExact, ReadHalf, ReadToEnd, ReadUntil, Shutdown, WriteAll, WriteHalf,
+    chain, copy, copy_bidirectional, copy_buf, flush, lines, read, read_exact, read_line,
+    read_to_end, read_until, seek, shutdown, take, write_all, BufReader, BufWriter,
+    Chain, Copy, CopyBidirectional, CopyBuf, Flush, HalfClose, LimitExceeded, Lines, ReadExact,
+    ReadHalf, ReadLine, ReadToEnd, ReadUntil, Seek, Shutdown, Take, WriteAll, WriteHalf,
 };
 
 // Re-export io::Error so that users don't have to deal
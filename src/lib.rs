@@ -113,6 +113,8 @@ extern crate tokio_async_await;
 
 #[cfg(all(unix, feature = "uds"))]
 extern crate tokio_uds;
+#[cfg(all(windows, feature = "named-pipes"))]
+extern crate tokio_named_pipes;
 
 #[cfg(feature = "timer")]
 pub mod clock;
@@ -122,7 +124,12 @@ pub mod codec;
 pub mod fs;
 #[cfg(feature = "io")]
 pub mod io;
-#[cfg(any(feature = "tcp", feature = "udp", feature = "uds"))]
+#[cfg(any(
+    feature = "tcp",
+    feature = "udp",
+    feature = "uds",
+    feature = "named-pipes"
+))]
 pub mod net;
 pub mod prelude;
 #[cfg(feature = "reactor")]
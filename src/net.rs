@@ -11,7 +11,12 @@
 //! Unix Domain Stream Socket **(available on Unix only)**
 //! * [`UnixDatagram`] and [`UnixDatagramFramed`] provide functionality for communication
 //! over Unix Domain Datagram Socket **(available on Unix only)**
-
+//! * [`NamedPipeServer`] and [`NamedPipe`] provide functionality for communication
+//! over Windows named pipes **(available on Windows only)**
+//! * [`dns`] resolves host names, and [`tcp::connect`] connects to a host name
+//! * [`Listener`] and [`Connection`] abstract over the listeners and the connections
+//! they accept
+//! * [`proxy`] opens tunnels through SOCKS5 and HTTP proxies
 //!
 //! [`TcpListener`]: struct.TcpListener.html
 //! [`TcpStream`]: struct.TcpStream.html
@@ -21,6 +26,24 @@
 //! [`UnixStream`]: struct.UnixStream.html
 //! [`UnixDatagram`]: struct.UnixDatagram.html
 //! [`UnixDatagramFramed`]: struct.UnixDatagramFramed.html
+//! [`NamedPipeServer`]: windows/struct.NamedPipeServer.html
+//! [`NamedPipe`]: windows/struct.NamedPipe.html
+//! [`dns`]: dns/index.html
+//! [`Listener`]: listener/trait.Listener.html
+//! [`Connection`]: listener/trait.Connection.html
+//! [`proxy`]: proxy/index.html
+//! [`tcp::connect`]: tcp/fn.connect.html
+
+#[cfg(all(feature = "tcp", feature = "rt-full"))]
+mod connect;
+#[cfg(feature = "rt-full")]
+pub mod dns;
+#[cfg(feature = "io")]
+pub mod listener;
+#[cfg(feature = "io")]
+pub use self::listener::{Connection, Listener};
+#[cfg(feature = "io")]
+pub mod proxy;
 
 #[cfg(feature = "tcp")]
 pub mod tcp {
@@ -41,7 +64,25 @@ pub mod tcp {
     //! [`TcpListener`]: struct.TcpListener.html
     //! [incoming_method]: struct.TcpListener.html#method.incoming
     //! [`Incoming`]: struct.Incoming.html
-    pub use tokio_tcp::{ConnectFuture, Incoming, TcpListener, TcpStream};
+    //!
+    //! Host names can be resolved and connected to with the [`connect`]
+    //! function.
+    //!
+    //! [`connect`]: fn.connect.html
+    #[cfg(feature = "rt-full")]
+    pub use super::connect::{connect, connect_with, Connect};
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    ))]
+    pub use tokio_tcp::TcpInfo;
+    pub use tokio_tcp::{
+        accept, peek, Accept, ConnectAny, ConnectFuture, ConnectTimeout, HappyEyeballs, Incoming,
+        OwnedReadHalf, OwnedWriteHalf, Peek, ReuniteError,
+        TcpBuilder, TcpKeepalive, TcpListener, TcpStream,
+    };
 }
 #[cfg(feature = "tcp")]
 pub use self::tcp::{TcpListener, TcpStream};
@@ -71,7 +112,7 @@ pub mod udp {
     //! [`SendDgram`]: struct.SendDgram.html
     //! [`UdpFramed`]: struct.UdpFramed.html
     //! [`framed`]: struct.UdpSocket.html#method.framed
-    pub use tokio_udp::{RecvDgram, SendDgram, UdpFramed, UdpSocket};
+    pub use tokio_udp::{RecvDgram, RecvMeta, SendDgram, UdpBuilder, UdpFramed, UdpSocket};
 }
 #[cfg(feature = "udp")]
 pub use self::udp::{UdpFramed, UdpSocket};
@@ -90,9 +131,16 @@ pub mod unix {
     //! Unix domain socket bindings for `tokio` (only available on unix systems).
 
     pub use tokio_uds::{
-        ConnectFuture, Incoming, RecvDgram, SendDgram, UCred, UnixDatagram, UnixDatagramFramed,
-        UnixListener, UnixStream,
+        ConnectFuture, Incoming, OwnedReadHalf, OwnedWriteHalf, RecvDgram, ReuniteError, SendDgram,
+        UCred, UnixDatagram, UnixDatagramFramed, UnixListener, UnixSocketAddr, UnixStream,
     };
 }
 #[cfg(all(unix, feature = "uds"))]
 pub use self::unix::{UnixDatagram, UnixDatagramFramed, UnixListener, UnixStream};
+
+#[cfg(all(windows, feature = "named-pipes"))]
+pub mod windows {
+    //! Named pipe bindings for `tokio` (only available on Windows).
+
+    pub use tokio_named_pipes::{Builder, ConnectFuture, Incoming, NamedPipe, NamedPipeServer};
+}
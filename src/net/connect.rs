@@ -0,0 +1,78 @@
+use super::dns::{BlockingResolver, Resolve, Resolving, ToSocketAddrs};
+use tokio_tcp::{ConnectAny, TcpStream};
+
+use std::io;
+
+use futures::{Future, Poll};
+
+/// Resolves `addr` with the default resolver and connects to the resolved
+/// addresses, returning a future resolving to the connected `TcpStream`.
+///
+/// When `addr` resolves to several addresses, they are raced as described
+/// by RFC 8305, see [`HappyEyeballs`]. The returned future must be polled
+/// from within the threadpool of a [`Runtime`].
+///
+/// [`HappyEyeballs`]: struct.HappyEyeballs.html
+/// [`Runtime`]: ../../runtime/struct.Runtime.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::net::tcp;
+/// use futures::Future;
+///
+/// let task = tcp::connect("tokio.rs:80")
+///     .map(|stream| println!("connected to {}", stream.peer_addr().unwrap()))
+///     .map_err(|e| eprintln!("failed to connect; error = {:?}", e));
+///
+/// tokio::run(task);
+/// ```
+pub fn connect<A: ToSocketAddrs>(addr: A) -> Connect {
+    connect_with(addr, &BlockingResolver::new())
+}
+
+/// Resolves `addr` with the given resolver and connects to the resolved
+/// addresses, returning a future resolving to the connected `TcpStream`.
+///
+/// See [`connect`] for more details.
+///
+/// [`connect`]: fn.connect.html
+pub fn connect_with<A: ToSocketAddrs>(addr: A, resolver: &Resolve) -> Connect {
+    Connect {
+        state: State::Resolving(addr.to_socket_addrs(resolver)),
+    }
+}
+
+/// Future returned by the [`connect`] and [`connect_with`] functions.
+///
+/// [`connect`]: fn.connect.html
+/// [`connect_with`]: fn.connect_with.html
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Connect {
+    state: State,
+}
+
+#[derive(Debug)]
+enum State {
+    Resolving(Resolving),
+    Connecting(ConnectAny),
+}
+
+impl Future for Connect {
+    type Item = TcpStream;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<TcpStream, io::Error> {
+        loop {
+            let addrs = match self.state {
+                State::Resolving(ref mut resolving) => try_ready!(resolving.poll()),
+                State::Connecting(ref mut connecting) => return connecting.poll(),
+            };
+
+            self.state = State::Connecting(TcpStream::connect_any(addrs));
+        }
+    }
+}
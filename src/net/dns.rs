@@ -0,0 +1,255 @@
+//! Asynchronous resolution of host names.
+//!
+//! Host names are resolved by a [`Resolve`] implementation. The
+//! [`BlockingResolver`] used by default resolves them with the system
+//! resolver, through the standard library, on the runtime's blocking
+//! threads. Other resolvers, such as a caching resolver or a resolver
+//! speaking the DNS protocol asynchronously, can be used by implementing
+//! [`Resolve`].
+//!
+//! The addresses to resolve are given as a [`ToSocketAddrs`] value, which
+//! can be a `"host:port"` string, a `(host, port)` pair or a socket address.
+//! Addresses which are already IP addresses are not passed to the resolver.
+//!
+//! [`Resolve`]: trait.Resolve.html
+//! [`BlockingResolver`]: struct.BlockingResolver.html
+//! [`ToSocketAddrs`]: trait.ToSocketAddrs.html
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate tokio;
+//! # extern crate futures;
+//! use tokio::net::dns;
+//! use futures::Future;
+//!
+//! let task = dns::lookup_host("localhost:8080")
+//!     .map(|addrs| {
+//!         for addr in addrs {
+//!             println!("localhost resolved to {}", addr);
+//!         }
+//!     })
+//!     .map_err(|e| eprintln!("failed to resolve; error = {:?}", e));
+//!
+//! tokio::run(task);
+//! ```
+
+use std::fmt;
+use std::io;
+use std::net::{self, IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use futures::future::{self, poll_fn};
+use futures::{Async, Future, Poll};
+use tokio_threadpool;
+
+/// Resolves host names to IP addresses.
+///
+/// Implementations are used by [`lookup_host_with`] and by
+/// [`tcp::connect_with`]. Other functions taking a socket address, such as
+/// `TcpStream::connect`, do not resolve host names and never use a
+/// `Resolve` implementation.
+///
+/// [`lookup_host_with`]: fn.lookup_host_with.html
+/// [`tcp::connect_with`]: ../tcp/fn.connect_with.html
+pub trait Resolve {
+    /// Resolves `host`, returning a future resolving to the socket addresses
+    /// with the given `port`.
+    fn resolve(&self, host: &str, port: u16) -> Resolving;
+}
+
+/// Future returned by a [`Resolve`] implementation, resolving to the
+/// addresses of a host.
+///
+/// [`Resolve`]: trait.Resolve.html
+#[must_use = "futures do nothing unless polled"]
+pub struct Resolving {
+    inner: Box<Future<Item = Vec<SocketAddr>, Error = io::Error> + Send>,
+}
+
+/// The default resolver, resolving host names with the system resolver.
+///
+/// Resolving a host name blocks the calling thread, so this is done with
+/// [`blocking`], which requires the future to be polled from within the
+/// threadpool of a [`Runtime`].
+///
+/// [`blocking`]: https://docs.rs/tokio-threadpool/0.1/tokio_threadpool/fn.blocking.html
+/// [`Runtime`]: ../../runtime/struct.Runtime.html
+#[derive(Debug, Clone, Default)]
+pub struct BlockingResolver {
+    _priv: (),
+}
+
+/// A value which can be resolved to socket addresses, similar to
+/// `std::net::ToSocketAddrs`.
+pub trait ToSocketAddrs {
+    /// Resolves this value to socket addresses, using `resolver` if it is a
+    /// host name.
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving;
+}
+
+/// Resolves `addr` to socket addresses with the default resolver.
+///
+/// See [`BlockingResolver`] for more details.
+///
+/// [`BlockingResolver`]: struct.BlockingResolver.html
+pub fn lookup_host<A: ToSocketAddrs>(addr: A) -> Resolving {
+    lookup_host_with(addr, &BlockingResolver::new())
+}
+
+/// Resolves `addr` to socket addresses with the given resolver.
+pub fn lookup_host_with<A: ToSocketAddrs>(addr: A, resolver: &Resolve) -> Resolving {
+    addr.to_socket_addrs(resolver)
+}
+
+// ===== impl Resolving =====
+
+impl Resolving {
+    /// Creates a new `Resolving` future from a future resolving to the
+    /// addresses of a host.
+    pub fn new<F>(future: F) -> Resolving
+    where
+        F: Future<Item = Vec<SocketAddr>, Error = io::Error> + Send + 'static,
+    {
+        Resolving {
+            inner: Box::new(future),
+        }
+    }
+
+    /// Creates a new `Resolving` future which is immediately ready with the
+    /// given addresses.
+    pub fn ready(addrs: Vec<SocketAddr>) -> Resolving {
+        Resolving::new(future::ok(addrs))
+    }
+}
+
+impl Future for Resolving {
+    type Item = Vec<SocketAddr>;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Vec<SocketAddr>, io::Error> {
+        self.inner.poll()
+    }
+}
+
+impl fmt::Debug for Resolving {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Resolving").finish()
+    }
+}
+
+// ===== impl BlockingResolver =====
+
+impl BlockingResolver {
+    /// Returns a new `BlockingResolver`.
+    pub fn new() -> BlockingResolver {
+        BlockingResolver { _priv: () }
+    }
+}
+
+impl Resolve for BlockingResolver {
+    fn resolve(&self, host: &str, port: u16) -> Resolving {
+        let host = host.to_string();
+
+        Resolving::new(poll_fn(move || {
+            let res = tokio_threadpool::blocking(|| {
+                net::ToSocketAddrs::to_socket_addrs(&(&host[..], port)).map(Iterator::collect)
+            });
+
+            match res {
+                Ok(Async::Ready(Ok(addrs))) => Ok(Async::Ready(addrs)),
+                Ok(Async::Ready(Err(e))) => Err(e),
+                Ok(Async::NotReady) => Ok(Async::NotReady),
+                Err(_) => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "`BlockingResolver` must be used from the context of the Tokio runtime.",
+                )),
+            }
+        }))
+    }
+}
+
+// ===== impl ToSocketAddrs =====
+
+impl ToSocketAddrs for SocketAddr {
+    fn to_socket_addrs(&self, _: &Resolve) -> Resolving {
+        Resolving::ready(vec![*self])
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV4 {
+    fn to_socket_addrs(&self, _: &Resolve) -> Resolving {
+        Resolving::ready(vec![SocketAddr::V4(*self)])
+    }
+}
+
+impl ToSocketAddrs for SocketAddrV6 {
+    fn to_socket_addrs(&self, _: &Resolve) -> Resolving {
+        Resolving::ready(vec![SocketAddr::V6(*self)])
+    }
+}
+
+impl ToSocketAddrs for (IpAddr, u16) {
+    fn to_socket_addrs(&self, _: &Resolve) -> Resolving {
+        Resolving::ready(vec![SocketAddr::new(self.0, self.1)])
+    }
+}
+
+impl<'a> ToSocketAddrs for (&'a str, u16) {
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving {
+        let (host, port) = *self;
+
+        match host.parse::<IpAddr>() {
+            Ok(ip) => Resolving::ready(vec![SocketAddr::new(ip, port)]),
+            Err(_) => resolver.resolve(host, port),
+        }
+    }
+}
+
+impl ToSocketAddrs for (String, u16) {
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving {
+        (&self.0[..], self.1).to_socket_addrs(resolver)
+    }
+}
+
+impl ToSocketAddrs for str {
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving {
+        if let Ok(addr) = self.parse::<SocketAddr>() {
+            return Resolving::ready(vec![addr]);
+        }
+
+        let invalid = || {
+            Resolving::new(future::err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid socket address",
+            )))
+        };
+
+        let (host, port) = match self.rfind(':') {
+            Some(i) => (&self[..i], &self[i + 1..]),
+            None => return invalid(),
+        };
+
+        // An IPv6 address must be bracketed, otherwise the port cannot be
+        // told apart from the address.
+        if host.contains(':') {
+            return invalid();
+        }
+
+        match port.parse::<u16>() {
+            Ok(port) => (host, port).to_socket_addrs(resolver),
+            Err(_) => invalid(),
+        }
+    }
+}
+
+impl ToSocketAddrs for String {
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving {
+        self[..].to_socket_addrs(resolver)
+    }
+}
+
+impl<'a, T: ToSocketAddrs + ?Sized> ToSocketAddrs for &'a T {
+    fn to_socket_addrs(&self, resolver: &Resolve) -> Resolving {
+        (**self).to_socket_addrs(resolver)
+    }
+}
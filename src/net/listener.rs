@@ -0,0 +1,469 @@
+//! Traits abstracting over the kinds of listeners.
+//!
+//! A [`Listener`] accepts [`Connection`]s, which are byte streams
+//! implementing `AsyncRead` and `AsyncWrite` and know the address of their
+//! peer. They are implemented by the TCP and Unix listeners and streams, and
+//! by Windows named pipes, so that accept loops, connection limits or TLS
+//! acceptors can be written once for all of them.
+//!
+//! [`Listener`]: trait.Listener.html
+//! [`Connection`]: trait.Connection.html
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate tokio;
+//! # extern crate futures;
+//! use tokio::net::listener::{Connection, Listener};
+//! use tokio::net::TcpListener;
+//! use tokio::prelude::*;
+//!
+//! fn serve<L>(listener: L) -> impl Future<Item = (), Error = ()>
+//! where
+//!     L: Listener,
+//!     L::Connection: Send + 'static,
+//! {
+//!     listener
+//!         .incoming()
+//!         .for_each(|(conn, addr)| {
+//!             println!("accepted connection from {:?}", addr);
+//!             let (reader, writer) = conn.split();
+//!             let echo = tokio::io::copy(reader, writer)
+//!                 .map(|_| ())
+//!                 .map_err(|e| eprintln!("echo failed; error = {:?}", e));
+//!             tokio::spawn(echo);
+//!             Ok(())
+//!         })
+//!         .map_err(|e| eprintln!("accept failed; error = {:?}", e))
+//! }
+//!
+//! # fn main() {
+//! let addr = "127.0.0.1:0".parse().unwrap();
+//! let listener = TcpListener::bind(&addr).unwrap();
+//! # drop(listener.local_addr());
+//! # if false {
+//! tokio::run(serve(listener));
+//! # }
+//! # }
+//! ```
+
+use tokio_io::{AsyncRead, AsyncWrite, IoVec};
+
+use futures::task::AtomicTask;
+use futures::{Async, Poll, Stream};
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A connected byte stream accepted from a [`Listener`].
+///
+/// [`Listener`]: trait.Listener.html
+pub trait Connection: AsyncRead + AsyncWrite {
+    /// The type of the addresses of the connection's ends.
+    type Addr: fmt::Debug;
+
+    /// Returns the address of the local end of the connection.
+    fn local_addr(&self) -> io::Result<Self::Addr>;
+
+    /// Returns the address of the remote end of the connection.
+    fn peer_addr(&self) -> io::Result<Self::Addr>;
+}
+
+/// A listener accepting connections.
+pub trait Listener {
+    /// The type of the accepted connections.
+    type Connection: Connection;
+
+    /// Attempts to accept a connection, returning it with the address of the
+    /// peer.
+    ///
+    /// If no connection is pending, `Async::NotReady` is returned and the
+    /// current task is notified once one is.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from outside of a task context.
+    fn poll_accept(
+        &mut self,
+    ) -> Poll<(Self::Connection, <Self::Connection as Connection>::Addr), io::Error>;
+
+    /// Returns the local address that this listener is bound to.
+    fn local_addr(&self) -> io::Result<<Self::Connection as Connection>::Addr>;
+
+    /// Consumes this listener, returning a stream of the connections it
+    /// accepts, along with the addresses of their peers.
+    fn incoming(self) -> Incoming<Self>
+    where
+        Self: Sized,
+    {
+        Incoming { listener: self }
+    }
+
+    /// Consumes this listener, returning a stream of the connections it
+    /// accepts, with at most `max_connections` of them open at the same
+    /// time.
+    ///
+    /// Once `max_connections` connections yielded by the stream are still
+    /// alive, the stream stops accepting connections until one of them is
+    /// dropped. See [`LimitedIncoming`] for more details.
+    ///
+    /// [`LimitedIncoming`]: struct.LimitedIncoming.html
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `max_connections` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::net::{Listener, TcpListener};
+    /// use tokio::io;
+    /// use futures::{Future, Stream};
+    /// use std::net::SocketAddr;
+    ///
+    /// # fn main() -> Result<(), Box<std::error::Error>> {
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+    /// let listener = TcpListener::bind(&addr)?;
+    ///
+    /// listener.incoming_limited(1024)
+    ///     .map_err(|e| eprintln!("failed to accept stream; error = {:?}", e))
+    ///     .for_each(|(conn, _)| {
+    ///         let (reader, writer) = io::AsyncRead::split(conn);
+    ///         tokio::spawn(io::copy(reader, writer).map(drop).map_err(drop));
+    ///         Ok(())
+    ///     });
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn incoming_limited(self, max_connections: usize) -> LimitedIncoming<Self>
+    where
+        Self: Sized,
+    {
+        LimitedIncoming::new(self, max_connections)
+    }
+}
+
+/// Stream returned by [`Listener::incoming`], yielding the connections
+/// accepted by a listener along with the addresses of their peers.
+///
+/// [`Listener::incoming`]: trait.Listener.html#method.incoming
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct Incoming<L> {
+    listener: L,
+}
+
+impl<L> Incoming<L> {
+    /// Returns a reference to the underlying listener.
+    pub fn get_ref(&self) -> &L {
+        &self.listener
+    }
+
+    /// Returns a mutable reference to the underlying listener.
+    pub fn get_mut(&mut self) -> &mut L {
+        &mut self.listener
+    }
+
+    /// Consumes this stream, returning the underlying listener.
+    pub fn into_inner(self) -> L {
+        self.listener
+    }
+}
+
+impl<L: Listener> Stream for Incoming<L> {
+    type Item = (L::Connection, <L::Connection as Connection>::Addr);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, io::Error> {
+        let accepted = try_ready!(self.listener.poll_accept());
+        Ok(Async::Ready(Some(accepted)))
+    }
+}
+
+/// Stream returned by [`Listener::incoming_limited`], yielding the
+/// connections accepted by a listener, with a limited number of them open at
+/// the same time.
+///
+/// Each accepted connection is yielded as a [`LimitedStream`], which counts
+/// as an active connection until it is dropped. Once the limit is reached, no
+/// connections are accepted until one of the active connections is closed,
+/// leaving the pending connections in the listener's backlog.
+///
+/// [`Listener::incoming_limited`]: trait.Listener.html#method.incoming_limited
+/// [`LimitedStream`]: struct.LimitedStream.html
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct LimitedIncoming<L> {
+    listener: L,
+    shared: Arc<Shared>,
+}
+
+/// A connection accepted by [`LimitedIncoming`].
+///
+/// The connection is no longer counted as active once this is dropped.
+///
+/// [`LimitedIncoming`]: struct.LimitedIncoming.html
+#[derive(Debug)]
+pub struct LimitedStream<C> {
+    inner: C,
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    max: usize,
+    active: AtomicUsize,
+
+    // The task accepting connections, notified when a connection closes.
+    task: AtomicTask,
+}
+
+impl<L> LimitedIncoming<L> {
+    fn new(listener: L, max: usize) -> LimitedIncoming<L> {
+        assert!(max > 0, "the connection limit must be greater than zero");
+
+        LimitedIncoming {
+            listener,
+            shared: Arc::new(Shared {
+                max,
+                active: AtomicUsize::new(0),
+                task: AtomicTask::new(),
+            }),
+        }
+    }
+
+    /// Returns the number of accepted connections which are still open.
+    pub fn active_connections(&self) -> usize {
+        self.shared.active.load(Ordering::SeqCst)
+    }
+
+    /// Returns the maximum number of connections open at the same time.
+    pub fn max_connections(&self) -> usize {
+        self.shared.max
+    }
+
+    /// Returns a reference to the underlying listener.
+    pub fn get_ref(&self) -> &L {
+        &self.listener
+    }
+
+    /// Consumes this stream, returning the underlying listener.
+    ///
+    /// The connections already accepted are not affected.
+    pub fn into_inner(self) -> L {
+        self.listener
+    }
+}
+
+impl<L: Listener> Stream for LimitedIncoming<L> {
+    type Item = (
+        LimitedStream<L::Connection>,
+        <L::Connection as Connection>::Addr,
+    );
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, io::Error> {
+        if self.active_connections() >= self.shared.max {
+            self.shared.task.register();
+
+            // A connection may have closed before the task was registered.
+            if self.active_connections() >= self.shared.max {
+                return Ok(Async::NotReady);
+            }
+        }
+
+        let (conn, addr) = try_ready!(self.listener.poll_accept());
+
+        // Only this stream increments the count, so it cannot go over the
+        // limit.
+        self.shared.active.fetch_add(1, Ordering::SeqCst);
+
+        let conn = LimitedStream {
+            inner: conn,
+            shared: self.shared.clone(),
+        };
+
+        Ok(Async::Ready(Some((conn, addr))))
+    }
+}
+
+impl<C> LimitedStream<C> {
+    /// Returns a reference to the underlying connection.
+    pub fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying connection.
+    pub fn get_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+}
+
+impl<C: Connection> Connection for LimitedStream<C> {
+    type Addr = C::Addr;
+
+    fn local_addr(&self) -> io::Result<C::Addr> {
+        self.inner.local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<C::Addr> {
+        self.inner.peer_addr()
+    }
+}
+
+impl<C: Read> Read for LimitedStream<C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<C: AsyncRead> AsyncRead for LimitedStream<C> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.inner.poll_read_vectored(bufs)
+    }
+}
+
+impl<C: Write> Write for LimitedStream<C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<C: AsyncWrite> AsyncWrite for LimitedStream<C> {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.inner.poll_write_vectored(bufs)
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<C> Drop for LimitedStream<C> {
+    fn drop(&mut self) {
+        self.shared.active.fetch_sub(1, Ordering::SeqCst);
+        self.shared.task.notify();
+    }
+}
+
+#[cfg(feature = "tcp")]
+mod tcp {
+    use super::{Connection, Listener};
+
+    use tokio_tcp::{TcpListener, TcpStream};
+
+    use futures::Poll;
+
+    use std::io;
+    use std::net::SocketAddr;
+
+    impl Connection for TcpStream {
+        type Addr = SocketAddr;
+
+        fn local_addr(&self) -> io::Result<SocketAddr> {
+            TcpStream::local_addr(self)
+        }
+
+        fn peer_addr(&self) -> io::Result<SocketAddr> {
+            TcpStream::peer_addr(self)
+        }
+    }
+
+    impl Listener for TcpListener {
+        type Connection = TcpStream;
+
+        fn poll_accept(&mut self) -> Poll<(TcpStream, SocketAddr), io::Error> {
+            TcpListener::poll_accept(self)
+        }
+
+        fn local_addr(&self) -> io::Result<SocketAddr> {
+            TcpListener::local_addr(self)
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "uds"))]
+mod unix {
+    use super::{Connection, Listener};
+
+    use tokio_uds::{UnixListener, UnixStream};
+
+    use futures::Poll;
+
+    use std::io;
+    use std::os::unix::net::SocketAddr;
+
+    impl Connection for UnixStream {
+        type Addr = SocketAddr;
+
+        fn local_addr(&self) -> io::Result<SocketAddr> {
+            UnixStream::local_addr(self)
+        }
+
+        fn peer_addr(&self) -> io::Result<SocketAddr> {
+            UnixStream::peer_addr(self)
+        }
+    }
+
+    impl Listener for UnixListener {
+        type Connection = UnixStream;
+
+        fn poll_accept(&mut self) -> Poll<(UnixStream, SocketAddr), io::Error> {
+            UnixListener::poll_accept(self)
+        }
+
+        fn local_addr(&self) -> io::Result<SocketAddr> {
+            UnixListener::local_addr(self)
+        }
+    }
+}
+
+#[cfg(all(windows, feature = "named-pipes"))]
+mod windows {
+    use super::{Connection, Listener};
+
+    use tokio_named_pipes::{NamedPipe, NamedPipeServer};
+
+    use futures::Poll;
+
+    use std::io;
+
+    /// Named pipes have no addresses, the ends of a pipe are only known by
+    /// the name of the pipe.
+    impl Connection for NamedPipe {
+        type Addr = ();
+
+        fn local_addr(&self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn peer_addr(&self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Listener for NamedPipeServer {
+        type Connection = NamedPipe;
+
+        fn poll_accept(&mut self) -> Poll<(NamedPipe, ()), io::Error> {
+            let pipe = try_ready!(NamedPipeServer::poll_accept(self));
+            Ok((pipe, ()).into())
+        }
+
+        fn local_addr(&self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,670 @@
+//! Connecting through SOCKS5 and HTTP proxies.
+//!
+//! The [`socks5`] and [`http_connect`] functions perform the handshake
+//! asking a proxy to open a tunnel to a target address, over a stream
+//! already connected to the proxy. They resolve to the same stream, which
+//! then carries the data exchanged with the target.
+//!
+//! The stream to the proxy can be any `AsyncRead + AsyncWrite` type,
+//! usually a [`TcpStream`], but also for example a TLS stream to an HTTPS
+//! proxy. Proxies requiring authentication are supported with
+//! [`Credentials`]: the username/password method for SOCKS5, and the basic
+//! scheme for HTTP.
+//!
+//! [`socks5`]: fn.socks5.html
+//! [`http_connect`]: fn.http_connect.html
+//! [`TcpStream`]: ../struct.TcpStream.html
+//! [`Credentials`]: struct.Credentials.html
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # extern crate tokio;
+//! # extern crate futures;
+//! use tokio::net::{proxy, TcpStream};
+//! use tokio::prelude::*;
+//!
+//! let proxy_addr = "127.0.0.1:1080".parse().unwrap();
+//!
+//! let task = TcpStream::connect(&proxy_addr)
+//!     .and_then(|stream| proxy::socks5(stream, ("tokio.rs", 80)))
+//!     .and_then(|stream| tokio::io::write_all(stream, b"GET / HTTP/1.0\r\n\r\n"))
+//!     .and_then(|(stream, _)| tokio::io::read_to_end(stream, vec![]))
+//!     .map(|(_, response)| println!("{}", String::from_utf8_lossy(&response)))
+//!     .map_err(|e| eprintln!("request failed; error = {:?}", e));
+//!
+//! tokio::run(task);
+//! ```
+
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use futures::{Async, Future, Poll};
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+
+/// The address a proxy is asked to open a tunnel to.
+///
+/// Host names are resolved by the proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TargetAddr {
+    /// An IP address and port.
+    Ip(SocketAddr),
+    /// A host name and port.
+    Domain(String, u16),
+}
+
+/// A username and password to authenticate to a proxy with.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+/// Future returned by the [`socks5`] and [`socks5_with_auth`] functions.
+///
+/// [`socks5`]: fn.socks5.html
+/// [`socks5_with_auth`]: fn.socks5_with_auth.html
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Socks5Connect<S> {
+    io: Handshake<S>,
+    target: TargetAddr,
+    credentials: Option<Credentials>,
+    step: Socks5Step,
+}
+
+/// Future returned by the [`http_connect`] and [`http_connect_with_auth`]
+/// functions.
+///
+/// [`http_connect`]: fn.http_connect.html
+/// [`http_connect_with_auth`]: fn.http_connect_with_auth.html
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct HttpConnect<S> {
+    io: Handshake<S>,
+    step: HttpStep,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Socks5Step {
+    Greeting,
+    Method,
+    Auth,
+    AuthStatus,
+    Request,
+    ReplyHeader,
+    ReplyDomainLen,
+    ReplyAddr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HttpStep {
+    InvalidHost,
+    Request,
+    Response,
+}
+
+/// The stream to the proxy, with the bytes being written or read.
+#[derive(Debug)]
+struct Handshake<S> {
+    stream: Option<S>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+const SOCKS5_VERSION: u8 = 5;
+const SOCKS5_AUTH_VERSION: u8 = 1;
+const SOCKS5_NO_AUTH: u8 = 0;
+const SOCKS5_USERNAME_PASSWORD: u8 = 2;
+const SOCKS5_NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const SOCKS5_CONNECT: u8 = 1;
+const SOCKS5_IPV4: u8 = 1;
+const SOCKS5_DOMAIN: u8 = 3;
+const SOCKS5_IPV6: u8 = 4;
+
+/// The maximum size of the response of an HTTP proxy, up to the end of its
+/// headers.
+const MAX_HTTP_RESPONSE_LEN: usize = 8 * 1024;
+
+/// Asks the SOCKS5 proxy connected to with `stream` to open a tunnel to
+/// `target`, returning a future resolving to the tunneled stream.
+///
+/// The proxy must accept clients without authentication, see
+/// [`socks5_with_auth`] otherwise.
+///
+/// [`socks5_with_auth`]: fn.socks5_with_auth.html
+pub fn socks5<S, A>(stream: S, target: A) -> Socks5Connect<S>
+where
+    S: AsyncRead + AsyncWrite,
+    A: Into<TargetAddr>,
+{
+    Socks5Connect::new(stream, target.into(), None)
+}
+
+/// Asks the SOCKS5 proxy connected to with `stream` to open a tunnel to
+/// `target`, authenticating with `credentials` if the proxy requires it.
+///
+/// See [`socks5`] for more details.
+///
+/// [`socks5`]: fn.socks5.html
+pub fn socks5_with_auth<S, A>(stream: S, target: A, credentials: &Credentials) -> Socks5Connect<S>
+where
+    S: AsyncRead + AsyncWrite,
+    A: Into<TargetAddr>,
+{
+    Socks5Connect::new(stream, target.into(), Some(credentials.clone()))
+}
+
+/// Asks the HTTP proxy connected to with `stream` to open a tunnel to
+/// `target` with a `CONNECT` request, returning a future resolving to the
+/// tunneled stream.
+///
+/// The future fails if the proxy responds with another status than 2xx.
+pub fn http_connect<S, A>(stream: S, target: A) -> HttpConnect<S>
+where
+    S: AsyncRead + AsyncWrite,
+    A: Into<TargetAddr>,
+{
+    HttpConnect::new(stream, target.into(), None)
+}
+
+/// Asks the HTTP proxy connected to with `stream` to open a tunnel to
+/// `target`, authenticating with `credentials` using the basic scheme.
+///
+/// See [`http_connect`] for more details.
+///
+/// [`http_connect`]: fn.http_connect.html
+pub fn http_connect_with_auth<S, A>(
+    stream: S,
+    target: A,
+    credentials: &Credentials,
+) -> HttpConnect<S>
+where
+    S: AsyncRead + AsyncWrite,
+    A: Into<TargetAddr>,
+{
+    HttpConnect::new(stream, target.into(), Some(credentials))
+}
+
+// ===== impl TargetAddr =====
+
+impl fmt::Display for TargetAddr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TargetAddr::Ip(ref addr) => addr.fmt(fmt),
+            TargetAddr::Domain(ref host, port) => write!(fmt, "{}:{}", host, port),
+        }
+    }
+}
+
+impl From<SocketAddr> for TargetAddr {
+    fn from(addr: SocketAddr) -> TargetAddr {
+        TargetAddr::Ip(addr)
+    }
+}
+
+impl<'a> From<(&'a str, u16)> for TargetAddr {
+    fn from((host, port): (&'a str, u16)) -> TargetAddr {
+        TargetAddr::Domain(host.to_string(), port)
+    }
+}
+
+impl From<(String, u16)> for TargetAddr {
+    fn from((host, port): (String, u16)) -> TargetAddr {
+        TargetAddr::Domain(host, port)
+    }
+}
+
+// ===== impl Credentials =====
+
+impl Credentials {
+    /// Returns credentials made of `username` and `password`.
+    pub fn new<U, P>(username: U, password: P) -> Credentials
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        Credentials {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Returns the username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"..")
+            .finish()
+    }
+}
+
+// ===== impl Socks5Connect =====
+
+impl<S> Socks5Connect<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    fn new(stream: S, target: TargetAddr, credentials: Option<Credentials>) -> Socks5Connect<S> {
+        let mut greeting = vec![SOCKS5_VERSION, 1, SOCKS5_NO_AUTH];
+        if credentials.is_some() {
+            greeting[1] = 2;
+            greeting.push(SOCKS5_USERNAME_PASSWORD);
+        }
+
+        Socks5Connect {
+            io: Handshake::new(stream, greeting),
+            target,
+            credentials,
+            step: Socks5Step::Greeting,
+        }
+    }
+
+    fn auth_request(&self) -> io::Result<Vec<u8>> {
+        let credentials = match self.credentials {
+            Some(ref credentials) => credentials,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "SOCKS5 proxy requires authentication",
+                ))
+            }
+        };
+
+        let username = credentials.username.as_bytes();
+        let password = credentials.password.as_bytes();
+        if username.len() > 255 || password.len() > 255 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "SOCKS5 username or password is too long",
+            ));
+        }
+
+        let mut request = vec![SOCKS5_AUTH_VERSION, username.len() as u8];
+        request.extend_from_slice(username);
+        request.push(password.len() as u8);
+        request.extend_from_slice(password);
+        Ok(request)
+    }
+
+    fn connect_request(&self) -> io::Result<Vec<u8>> {
+        let mut request = vec![SOCKS5_VERSION, SOCKS5_CONNECT, 0];
+
+        let port = match self.target {
+            TargetAddr::Ip(SocketAddr::V4(ref addr)) => {
+                request.push(SOCKS5_IPV4);
+                request.extend_from_slice(&addr.ip().octets());
+                addr.port()
+            }
+            TargetAddr::Ip(SocketAddr::V6(ref addr)) => {
+                request.push(SOCKS5_IPV6);
+                request.extend_from_slice(&addr.ip().octets());
+                addr.port()
+            }
+            TargetAddr::Domain(ref host, port) => {
+                if host.len() > 255 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "host name is too long",
+                    ));
+                }
+
+                request.push(SOCKS5_DOMAIN);
+                request.push(host.len() as u8);
+                request.extend_from_slice(host.as_bytes());
+                port
+            }
+        };
+
+        request.push((port >> 8) as u8);
+        request.push(port as u8);
+        Ok(request)
+    }
+}
+
+impl<S> Future for Socks5Connect<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    type Item = S;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<S, io::Error> {
+        loop {
+            match self.step {
+                Socks5Step::Greeting => {
+                    try_ready!(self.io.poll_write());
+                    self.io.read(2);
+                    self.step = Socks5Step::Method;
+                }
+                Socks5Step::Method => {
+                    try_ready!(self.io.poll_read());
+                    check_version(self.io.buf[0], SOCKS5_VERSION)?;
+
+                    match self.io.buf[1] {
+                        SOCKS5_NO_AUTH => {
+                            let request = self.connect_request()?;
+                            self.io.write(request);
+                            self.step = Socks5Step::Request;
+                        }
+                        SOCKS5_USERNAME_PASSWORD => {
+                            let request = self.auth_request()?;
+                            self.io.write(request);
+                            self.step = Socks5Step::Auth;
+                        }
+                        SOCKS5_NO_ACCEPTABLE_METHOD => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::PermissionDenied,
+                                "SOCKS5 proxy accepts none of the authentication methods",
+                            ));
+                        }
+                        _ => return Err(invalid_data("SOCKS5 proxy chose an unknown method")),
+                    }
+                }
+                Socks5Step::Auth => {
+                    try_ready!(self.io.poll_write());
+                    self.io.read(2);
+                    self.step = Socks5Step::AuthStatus;
+                }
+                Socks5Step::AuthStatus => {
+                    try_ready!(self.io.poll_read());
+                    check_version(self.io.buf[0], SOCKS5_AUTH_VERSION)?;
+
+                    if self.io.buf[1] != 0 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::PermissionDenied,
+                            "SOCKS5 proxy rejected the credentials",
+                        ));
+                    }
+
+                    let request = self.connect_request()?;
+                    self.io.write(request);
+                    self.step = Socks5Step::Request;
+                }
+                Socks5Step::Request => {
+                    try_ready!(self.io.poll_write());
+                    self.io.read(4);
+                    self.step = Socks5Step::ReplyHeader;
+                }
+                Socks5Step::ReplyHeader => {
+                    try_ready!(self.io.poll_read());
+                    check_version(self.io.buf[0], SOCKS5_VERSION)?;
+                    check_reply(self.io.buf[1])?;
+
+                    // The address the proxy bound to, followed by its port,
+                    // is not used.
+                    match self.io.buf[3] {
+                        SOCKS5_IPV4 => self.io.read(4 + 2),
+                        SOCKS5_IPV6 => self.io.read(16 + 2),
+                        SOCKS5_DOMAIN => {
+                            self.io.read(1);
+                            self.step = Socks5Step::ReplyDomainLen;
+                            continue;
+                        }
+                        _ => return Err(invalid_data("SOCKS5 proxy replied an unknown address")),
+                    }
+                    self.step = Socks5Step::ReplyAddr;
+                }
+                Socks5Step::ReplyDomainLen => {
+                    try_ready!(self.io.poll_read());
+                    let len = self.io.buf[0] as usize;
+                    self.io.read(len + 2);
+                    self.step = Socks5Step::ReplyAddr;
+                }
+                Socks5Step::ReplyAddr => {
+                    try_ready!(self.io.poll_read());
+                    return Ok(Async::Ready(self.io.take_stream()));
+                }
+            }
+        }
+    }
+}
+
+fn check_version(version: u8, expected: u8) -> io::Result<()> {
+    if version != expected {
+        return Err(invalid_data("SOCKS5 proxy replied an unknown version"));
+    }
+    Ok(())
+}
+
+fn check_reply(reply: u8) -> io::Result<()> {
+    let (kind, msg) = match reply {
+        0 => return Ok(()),
+        1 => (io::ErrorKind::Other, "general SOCKS server failure"),
+        2 => (
+            io::ErrorKind::PermissionDenied,
+            "connection not allowed by ruleset",
+        ),
+        3 => (io::ErrorKind::Other, "network unreachable"),
+        4 => (io::ErrorKind::Other, "host unreachable"),
+        5 => (io::ErrorKind::ConnectionRefused, "connection refused"),
+        6 => (io::ErrorKind::TimedOut, "TTL expired"),
+        7 => (io::ErrorKind::Other, "command not supported"),
+        8 => (io::ErrorKind::Other, "address type not supported"),
+        _ => (io::ErrorKind::Other, "unknown SOCKS5 proxy failure"),
+    };
+    Err(io::Error::new(kind, msg))
+}
+
+// ===== impl HttpConnect =====
+
+impl<S> HttpConnect<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    fn new(stream: S, target: TargetAddr, credentials: Option<&Credentials>) -> HttpConnect<S> {
+        // The host is written as is in the request, it must not be able to
+        // end the request line or a header.
+        if let TargetAddr::Domain(ref host, _) = target {
+            if host.is_empty() || host.chars().any(|c| c == ' ' || c.is_control()) {
+                return HttpConnect {
+                    io: Handshake::new(stream, vec![]),
+                    step: HttpStep::InvalidHost,
+                };
+            }
+        }
+
+        let mut request = format!(
+            "CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n",
+            target = target
+        );
+        if let Some(credentials) = credentials {
+            let userpass = format!("{}:{}", credentials.username, credentials.password);
+            request.push_str("Proxy-Authorization: Basic ");
+            request.push_str(&base64(userpass.as_bytes()));
+            request.push_str("\r\n");
+        }
+        request.push_str("\r\n");
+
+        HttpConnect {
+            io: Handshake::new(stream, request.into_bytes()),
+            step: HttpStep::Request,
+        }
+    }
+}
+
+impl<S> Future for HttpConnect<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    type Item = S;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<S, io::Error> {
+        if self.step == HttpStep::InvalidHost {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid host name",
+            ));
+        }
+
+        if self.step == HttpStep::Request {
+            try_ready!(self.io.poll_write());
+            self.io.buf.clear();
+            self.step = HttpStep::Response;
+        }
+
+        // The response is read a byte at a time, so that no data sent by the
+        // target after the headers is consumed.
+        while !self.io.buf.ends_with(b"\r\n\r\n") {
+            if self.io.buf.len() == MAX_HTTP_RESPONSE_LEN {
+                return Err(invalid_data("HTTP proxy response is too long"));
+            }
+
+            let mut byte = [0];
+            if try_ready!(self.io.stream_mut().poll_read(&mut byte)) == 0 {
+                return Err(unexpected_eof());
+            }
+            self.io.buf.push(byte[0]);
+        }
+
+        check_status(&self.io.buf)?;
+        Ok(Async::Ready(self.io.take_stream()))
+    }
+}
+
+fn check_status(response: &[u8]) -> io::Result<()> {
+    let response = String::from_utf8_lossy(response);
+    let status_line = response.lines().next().unwrap_or("");
+    let mut parts = status_line.splitn(3, ' ');
+
+    let version = parts.next().unwrap_or("");
+    let status = parts.next().and_then(|status| status.parse::<u16>().ok());
+
+    match status {
+        Some(status) if version.starts_with("HTTP/1.") => {
+            if status >= 200 && status < 300 {
+                return Ok(());
+            }
+
+            let kind = match status {
+                401 | 403 | 407 => io::ErrorKind::PermissionDenied,
+                _ => io::ErrorKind::Other,
+            };
+            Err(io::Error::new(
+                kind,
+                format!("HTTP proxy responded with {:?}", status_line),
+            ))
+        }
+        _ => Err(invalid_data("invalid HTTP proxy response")),
+    }
+}
+
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity((input.len() + 2) / 3 * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+
+        output.push(ALPHABET[n >> 18 & 63] as char);
+        output.push(ALPHABET[n >> 12 & 63] as char);
+        if chunk.len() > 1 {
+            output.push(ALPHABET[n >> 6 & 63] as char);
+        } else {
+            output.push('=');
+        }
+        if chunk.len() > 2 {
+            output.push(ALPHABET[n & 63] as char);
+        } else {
+            output.push('=');
+        }
+    }
+    output
+}
+
+// ===== impl Handshake =====
+
+impl<S> Handshake<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    fn new(stream: S, buf: Vec<u8>) -> Handshake<S> {
+        Handshake {
+            stream: Some(stream),
+            buf,
+            pos: 0,
+        }
+    }
+
+    fn stream_mut(&mut self) -> &mut S {
+        self.stream.as_mut().expect("polled after completion")
+    }
+
+    fn take_stream(&mut self) -> S {
+        self.stream.take().expect("polled after completion")
+    }
+
+    /// Starts writing `buf`.
+    fn write(&mut self, buf: Vec<u8>) {
+        self.buf = buf;
+        self.pos = 0;
+    }
+
+    /// Starts reading `n` bytes.
+    fn read(&mut self, n: usize) {
+        self.buf.clear();
+        self.buf.resize(n, 0);
+        self.pos = 0;
+    }
+
+    fn poll_write(&mut self) -> Poll<(), io::Error> {
+        while self.pos < self.buf.len() {
+            let n = try_ready!(self
+                .stream
+                .as_mut()
+                .expect("polled after completion")
+                .poll_write(&self.buf[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write to the proxy",
+                ));
+            }
+            self.pos += n;
+        }
+
+        try_ready!(self.stream_mut().poll_flush());
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_read(&mut self) -> Poll<(), io::Error> {
+        while self.pos < self.buf.len() {
+            let n = try_ready!(self
+                .stream
+                .as_mut()
+                .expect("polled after completion")
+                .poll_read(&mut self.buf[self.pos..]));
+            if n == 0 {
+                return Err(unexpected_eof());
+            }
+            self.pos += n;
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "proxy closed the connection during the handshake",
+    )
+}
@@ -1,9 +1,6 @@
-use executor::current_thread::CurrentThread;
-use runtime::current_thread::Runtime;
+use runtime::current_thread::{Driver, Runtime};
 
-use tokio_reactor::Reactor;
 use tokio_timer::clock::Clock;
-use tokio_timer::timer::Timer;
 
 use std::io;
 
@@ -42,6 +39,15 @@ use std::io;
 pub struct Builder {
     /// The clock to use
     clock: Clock,
+
+    /// Whether the driver includes a reactor
+    enable_io: bool,
+
+    /// Whether the driver includes a timer
+    enable_time: bool,
+
+    /// Whether time can be paused
+    enable_pause: bool,
 }
 
 impl Builder {
@@ -52,6 +58,9 @@ impl Builder {
     pub fn new() -> Builder {
         Builder {
             clock: Clock::new(),
+            enable_io: true,
+            enable_time: true,
+            enable_pause: false,
         }
     }
 
@@ -61,28 +70,95 @@ impl Builder {
         self
     }
 
-    /// Create the configured `Runtime`.
-    pub fn build(&mut self) -> io::Result<Runtime> {
-        // We need a reactor to receive events about IO objects from kernel
-        let reactor = Reactor::new()?;
-        let reactor_handle = reactor.handle();
+    /// Set whether the runtime's driver includes a reactor.
+    ///
+    /// When disabled, I/O resources used on the runtime are driven by the
+    /// fallback reactor, which runs on a background thread.
+    ///
+    /// The default value is `true`.
+    pub fn enable_io(&mut self, val: bool) -> &mut Self {
+        self.enable_io = val;
+        self
+    }
 
-        // Place a timer wheel on top of the reactor. If there are no timeouts to fire, it'll let the
-        // reactor pick up some new external events.
-        let timer = Timer::new_with_now(reactor, self.clock.clone());
-        let timer_handle = timer.handle();
+    /// Set whether the runtime's driver includes a timer.
+    ///
+    /// When disabled, no default timer is set on the runtime and creating a
+    /// `Delay` there results in an error.
+    ///
+    /// The default value is `true`.
+    pub fn enable_time(&mut self, val: bool) -> &mut Self {
+        self.enable_time = val;
+        self
+    }
 
-        // And now put a single-threaded executor on top of the timer. When there are no futures ready
-        // to do something, it'll let the timer or the reactor to generate some new stimuli for the
-        // futures to continue in their life.
-        let executor = CurrentThread::new_with_park(timer);
+    /// Set whether time can be paused on the runtime.
+    ///
+    /// This is intended for tests of time related logic. When enabled, the
+    /// futures running on the runtime can stop time with [`clock::pause`] and
+    /// move it forward with [`clock::advance`]. While time is paused and the
+    /// runtime has no futures ready to run, time automatically jumps to the
+    /// next pending timeout instead of blocking the thread, so that tests
+    /// involving long timeouts complete instantly.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::clock;
+    /// use tokio::prelude::*;
+    /// use tokio::runtime::current_thread::Builder;
+    /// use tokio::timer::{Delay, Handle};
+    ///
+    /// use std::time::Duration;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = Builder::new().enable_pause(true).build().unwrap();
+    ///
+    /// rt.block_on(future::lazy(|| {
+    ///     clock::pause();
+    ///
+    ///     // Completes without waiting for an hour
+    ///     Delay::new(Handle::current().now() + Duration::from_secs(3600))
+    /// })).unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`clock::pause`]: ../../clock/fn.pause.html
+    /// [`clock::advance`]: ../../clock/fn.advance.html
+    pub fn enable_pause(&mut self, val: bool) -> &mut Self {
+        self.enable_pause = val;
+        self
+    }
 
-        let runtime = Runtime::new2(
-            reactor_handle,
-            timer_handle,
+    /// Create the configured `Driver` without an executor on top of it.
+    ///
+    /// This is useful to drive a `CurrentThread` executor, or another `Park`
+    /// consumer, from an existing event loop. See [`Driver`] for more details.
+    ///
+    /// [`Driver`]: struct.Driver.html
+    pub fn build_driver(&mut self) -> io::Result<Driver> {
+        // The reactor receives events about IO objects from the kernel. A
+        // timer wheel is placed on top of it: if there are no timeouts to
+        // fire, it'll let the reactor pick up some new external events.
+        Driver::new(
+            self.enable_io,
+            self.enable_time,
+            self.enable_pause,
             self.clock.clone(),
-            executor);
+        )
+    }
+
+    /// Create the configured `Runtime`.
+    pub fn build(&mut self) -> io::Result<Runtime> {
+        let driver = self.build_driver()?;
 
-        Ok(runtime)
+        // Put a single-threaded executor on top of the driver. When there are
+        // no futures ready to do something, it'll let the timer or the reactor
+        // generate some new stimuli for the futures to continue in their life.
+        Ok(Runtime::new2(driver))
     }
 }
@@ -0,0 +1,268 @@
+use runtime::current_thread::pause::{Pause, PausePark};
+
+use tokio_executor::park::{Park, ParkThread, Unpark};
+use tokio_executor::Enter;
+use tokio_reactor::{self, Reactor};
+use tokio_timer::clock::{self, Clock};
+use tokio_timer::timer::{self, Timer};
+
+use std::io;
+use std::time::Duration;
+
+/// The I/O and time driver of a single-threaded runtime.
+///
+/// A `Driver` is the [`Park`] implementation that the [`CurrentThread`]
+/// executor blocks on when it has no more futures ready to run. It is made of
+/// the following layers, each one parking on the one below it:
+///
+/// * A [timer] wheel. When parked, it fires any expired timeouts and parks the
+///   layer below for no longer than the time until the next timeout.
+/// * A [reactor]. When parked, it waits for I/O events from the operating
+///   system and dispatches them to the registered I/O resources.
+///
+/// If the timer is disabled, the executor parks directly on the reactor. If
+/// the reactor is disabled, the bottom layer is a plain [`ParkThread`] that
+/// puts the thread to sleep until it is unparked. In all cases, the reactor,
+/// the timer, and the executor are all driven from the thread that parks the
+/// driver; no background threads are spawned.
+///
+/// A `Driver` is usually created as part of a [`Runtime`], but it can also be
+/// created on its own with [`Builder::build_driver`] in order to drive a
+/// `CurrentThread` executor, or any other `Park` consumer, embedded in an
+/// existing event loop.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate tokio_current_thread;
+/// # extern crate tokio_executor;
+/// # extern crate futures;
+/// use tokio::runtime::current_thread::Builder;
+/// use tokio_current_thread::CurrentThread;
+/// use tokio::prelude::*;
+///
+/// # pub fn main() {
+/// let driver = Builder::new().build_driver().unwrap();
+/// let handles = driver.clone_handles();
+///
+/// let mut executor = CurrentThread::new_with_park(driver);
+/// let mut enter = tokio_executor::enter().unwrap();
+///
+/// handles.with_default(&mut enter, |enter| {
+///     executor.enter(enter).block_on(future::lazy(|| {
+///         // The reactor and timer of `driver` are the defaults here.
+///         Ok::<_, ()>(())
+///     })).unwrap();
+/// });
+/// # }
+/// ```
+///
+/// [`Park`]: ../../executor/park/trait.Park.html
+/// [`ParkThread`]: ../../executor/park/struct.ParkThread.html
+/// [`CurrentThread`]: ../../executor/current_thread/struct.CurrentThread.html
+/// [timer]: ../../timer/index.html
+/// [reactor]: ../../reactor/struct.Reactor.html
+/// [`Runtime`]: struct.Runtime.html
+/// [`Builder::build_driver`]: struct.Builder.html#method.build_driver
+#[derive(Debug)]
+pub struct Driver {
+    inner: TimeDriver,
+    handles: DriverHandles,
+}
+
+/// Handles to the resources of a [`Driver`].
+///
+/// The handles can be installed as the defaults for the current thread in
+/// order for I/O resources and timeouts created there to be registered with
+/// the driver.
+///
+/// [`Driver`]: struct.Driver.html
+#[derive(Debug, Clone)]
+pub struct DriverHandles {
+    reactor: Option<tokio_reactor::Handle>,
+    timer: Option<timer::Handle>,
+    clock: Clock,
+    pause: Option<Pause>,
+}
+
+#[derive(Debug)]
+enum TimeDriver {
+    Enabled(Timer<PausePark<IoDriver>>),
+    Disabled(IoDriver),
+}
+
+#[derive(Debug)]
+enum IoDriver {
+    Enabled(Reactor),
+    Disabled(ParkThread),
+}
+
+impl Driver {
+    /// Build the driver layers bottom up.
+    pub(super) fn new(
+        enable_io: bool,
+        enable_time: bool,
+        enable_pause: bool,
+        clock: Clock,
+    ) -> io::Result<Driver> {
+        let (io, reactor) = if enable_io {
+            let reactor = Reactor::new()?;
+            let handle = reactor.handle();
+            (IoDriver::Enabled(reactor), Some(handle))
+        } else {
+            (IoDriver::Disabled(ParkThread::new()), None)
+        };
+
+        // When pausing is enabled, the runtime's clock is wrapped so that time
+        // can be stopped and advanced.
+        let (clock, pause) = if enable_pause {
+            let pause = Pause::new(clock);
+            (Clock::new_with_now(pause.clone()), Some(pause))
+        } else {
+            (clock, None)
+        };
+
+        let (inner, timer) = if enable_time {
+            let io = PausePark::new(io, pause.clone());
+            let timer = Timer::new_with_now(io, clock.clone());
+            let handle = timer.handle();
+            (TimeDriver::Enabled(timer), Some(handle))
+        } else {
+            (TimeDriver::Disabled(io), None)
+        };
+
+        Ok(Driver {
+            inner,
+            handles: DriverHandles {
+                reactor,
+                timer,
+                clock,
+                pause,
+            },
+        })
+    }
+
+    /// Returns the handle to the reactor, if I/O is enabled.
+    pub fn reactor_handle(&self) -> Option<&tokio_reactor::Handle> {
+        self.handles.reactor_handle()
+    }
+
+    /// Returns the handle to the timer, if time is enabled.
+    pub fn timer_handle(&self) -> Option<&timer::Handle> {
+        self.handles.timer_handle()
+    }
+
+    /// Returns the clock used by the driver.
+    pub fn clock(&self) -> &Clock {
+        self.handles.clock()
+    }
+
+    /// Returns a copy of the handles to the driver's resources.
+    pub fn clone_handles(&self) -> DriverHandles {
+        self.handles.clone()
+    }
+}
+
+impl Park for Driver {
+    type Unpark = Box<Unpark>;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        match self.inner {
+            TimeDriver::Enabled(ref timer) => timer.unpark(),
+            TimeDriver::Disabled(ref io) => io.unpark(),
+        }
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        match self.inner {
+            TimeDriver::Enabled(ref mut timer) => timer.park(),
+            TimeDriver::Disabled(ref mut io) => io.park(),
+        }
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        match self.inner {
+            TimeDriver::Enabled(ref mut timer) => timer.park_timeout(duration),
+            TimeDriver::Disabled(ref mut io) => io.park_timeout(duration),
+        }
+    }
+}
+
+impl Park for IoDriver {
+    type Unpark = Box<Unpark>;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        match *self {
+            IoDriver::Enabled(ref reactor) => Box::new(reactor.unpark()),
+            IoDriver::Disabled(ref park) => Box::new(park.unpark()),
+        }
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        match *self {
+            IoDriver::Enabled(ref mut reactor) => reactor.park(),
+            IoDriver::Disabled(ref mut park) => park.park().map_err(park_error),
+        }
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        match *self {
+            IoDriver::Enabled(ref mut reactor) => reactor.park_timeout(duration),
+            IoDriver::Disabled(ref mut park) => {
+                park.park_timeout(duration).map_err(park_error)
+            }
+        }
+    }
+}
+
+fn park_error<E: ::std::fmt::Debug>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("failed to park thread: {:?}", e))
+}
+
+impl DriverHandles {
+    /// Returns the handle to the reactor, if I/O is enabled.
+    pub fn reactor_handle(&self) -> Option<&tokio_reactor::Handle> {
+        self.reactor.as_ref()
+    }
+
+    /// Returns the handle to the timer, if time is enabled.
+    pub fn timer_handle(&self) -> Option<&timer::Handle> {
+        self.timer.as_ref()
+    }
+
+    /// Returns the clock used by the driver.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Set the driver's reactor, timer, and clock as the defaults for the
+    /// duration of the closure.
+    ///
+    /// Resources that are disabled on the driver are left untouched.
+    pub fn with_default<F, R>(&self, enter: &mut Enter, f: F) -> R
+    where
+        F: FnOnce(&mut Enter) -> R,
+    {
+        match self.reactor {
+            Some(ref reactor) => {
+                tokio_reactor::with_default(reactor, enter, |enter| self.with_time(enter, f))
+            }
+            None => self.with_time(enter, f),
+        }
+    }
+
+    fn with_time<F, R>(&self, enter: &mut Enter, f: F) -> R
+    where
+        F: FnOnce(&mut Enter) -> R,
+    {
+        clock::with_default(&self.clock, enter, |enter| {
+            Pause::with_default(self.pause.as_ref(), || match self.timer {
+                Some(ref timer) => timer::with_default(timer, enter, f),
+                None => f(enter),
+            })
+        })
+    }
+}
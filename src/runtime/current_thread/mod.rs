@@ -15,6 +15,22 @@
 //! Note that [`current_thread::Runtime`][rt] does not implement `Send` itself
 //! and cannot be safely moved to other threads.
 //!
+//! # Driver
+//!
+//! All of these components are driven from the thread running the runtime;
+//! no background threads are used. When the executor has no futures ready to
+//! run, it parks on a [`Driver`], which is layered as follows:
+//!
+//! ```text
+//! executor -> timer -> reactor
+//! ```
+//!
+//! The timer fires expired timeouts and parks the reactor until the next
+//! timeout is due, and the reactor waits for I/O events. The timer and the
+//! reactor can be disabled with [`Builder::enable_time`] and
+//! [`Builder::enable_io`], and [`Builder::build_driver`] builds a standalone
+//! driver that can be embedded in a custom event loop.
+//!
 //! # Spawning from other threads
 //!
 //! While [`current_thread::Runtime`][rt] does not implement `Send` and cannot
@@ -60,6 +76,10 @@
 //! ```
 //!
 //! [rt]: struct.Runtime.html
+//! [`Driver`]: struct.Driver.html
+//! [`Builder::enable_time`]: struct.Builder.html#method.enable_time
+//! [`Builder::enable_io`]: struct.Builder.html#method.enable_io
+//! [`Builder::build_driver`]: struct.Builder.html#method.build_driver
 //! [concurrent-rt]: ../struct.Runtime.html
 //! [chan]: https://docs.rs/futures/0.1/futures/sync/mpsc/fn.channel.html
 //! [reactor]: ../../reactor/struct.Reactor.html
@@ -67,10 +87,14 @@
 //! [timer]: ../../timer/index.html
 
 mod builder;
+mod driver;
+mod pause;
 mod runtime;
 
+pub(crate) use self::pause::Pause;
 pub use self::builder::Builder;
-pub use self::runtime::{Runtime, Handle};
+pub use self::driver::{Driver, DriverHandles};
+pub use self::runtime::{Runtime, Handle, RunUntilError};
 pub use tokio_current_thread::spawn;
 pub use tokio_current_thread::TaskExecutor;
 
@@ -0,0 +1,130 @@
+use tokio_executor::park::Park;
+use tokio_timer::clock::{Clock, MockNow, Now};
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// Source of time of a runtime built with [`Builder::enable_pause`].
+///
+/// While running, time follows the clock the runtime was configured with.
+/// While paused, time only moves forward when it is advanced.
+///
+/// [`Builder::enable_pause`]: struct.Builder.html#method.enable_pause
+#[derive(Debug, Clone)]
+pub(crate) struct Pause {
+    now: MockNow,
+}
+
+/// `Park` implementation that advances paused time instead of blocking for a
+/// timeout.
+///
+/// Placed below the timer, this lets time jump to the next pending timeout
+/// when the runtime has nothing else to do.
+#[derive(Debug)]
+pub(super) struct PausePark<P> {
+    inner: P,
+    pause: Option<Pause>,
+}
+
+thread_local!(static CURRENT: RefCell<Option<Pause>> = RefCell::new(None));
+
+impl Pause {
+    pub fn new(clock: Clock) -> Pause {
+        Pause {
+            now: MockNow::new(clock, false),
+        }
+    }
+
+    /// Set `pause` as the current one for the duration of the closure.
+    pub fn with_default<F, R>(pause: Option<&Pause>, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        struct Reset(Option<Pause>);
+
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                let prev = self.0.take();
+                CURRENT.with(|current| *current.borrow_mut() = prev);
+            }
+        }
+
+        let prev = CURRENT.with(|current| current.replace(pause.cloned()));
+        let _reset = Reset(prev);
+
+        f()
+    }
+
+    /// Calls `f` with the pause of the runtime running on this thread.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the current runtime was not built with pausing
+    /// enabled.
+    pub fn with_current<F, R>(f: F) -> R
+    where
+        F: FnOnce(&Pause) -> R,
+    {
+        CURRENT.with(|current| match *current.borrow() {
+            Some(ref pause) => f(pause),
+            None => panic!(
+                "time can only be paused on a current thread runtime built \
+                 with `enable_pause`"
+            ),
+        })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.now.is_paused()
+    }
+
+    pub fn pause(&self) {
+        self.now.pause()
+    }
+
+    pub fn resume(&self) {
+        self.now.resume()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.advance(duration)
+    }
+}
+
+impl Now for Pause {
+    fn now(&self) -> Instant {
+        self.now.now()
+    }
+}
+
+impl<P: Park> PausePark<P> {
+    pub fn new(inner: P, pause: Option<Pause>) -> PausePark<P> {
+        PausePark { inner, pause }
+    }
+}
+
+impl<P: Park> Park for PausePark<P> {
+    type Unpark = P::Unpark;
+    type Error = P::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        self.inner.unpark()
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        self.inner.park()
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        match self.pause {
+            Some(ref pause) if pause.is_paused() => {
+                // Pick up pending events without blocking, then jump to the
+                // timeout the timer is waiting for.
+                self.inner.park_timeout(Duration::from_millis(0))?;
+                pause.advance(duration);
+                Ok(())
+            }
+            _ => self.inner.park_timeout(duration),
+        }
+    }
+}
@@ -1,10 +1,7 @@
 use tokio_current_thread::{self as current_thread, CurrentThread};
 use tokio_current_thread::Handle as ExecutorHandle;
-use runtime::current_thread::Builder;
+use runtime::current_thread::{Builder, Driver, DriverHandles};
 
-use tokio_reactor::{self, Reactor};
-use tokio_timer::clock::{self, Clock};
-use tokio_timer::timer::{self, Timer};
 use tokio_executor;
 
 use futures::{future, Future};
@@ -16,15 +13,17 @@ use std::io;
 /// Single-threaded runtime provides a way to start reactor
 /// and executor on the current thread.
 ///
+/// The executor parks on a [`Driver`] when it has no futures ready to run,
+/// which in turn drives the timer and the reactor.
+///
 /// See [module level][mod] documentation for more details.
 ///
 /// [mod]: index.html
+/// [`Driver`]: struct.Driver.html
 #[derive(Debug)]
 pub struct Runtime {
-    reactor_handle: tokio_reactor::Handle,
-    timer_handle: timer::Handle,
-    clock: Clock,
-    executor: CurrentThread<Timer<Reactor>>,
+    handles: DriverHandles,
+    executor: CurrentThread<Driver>,
 }
 
 /// Handle to spawn a future on the corresponding `CurrentThread` runtime instance
@@ -101,26 +100,64 @@ impl Error for RunError {
     }
 }
 
+/// Error returned by the `run_until` function.
+#[derive(Debug)]
+pub struct RunUntilError<E> {
+    /// The error yielded by the future, `None` if the runtime failed.
+    inner: Option<E>,
+}
+
+impl<E> RunUntilError<E> {
+    /// Returns `true` if the error was yielded by the future being run.
+    pub fn is_inner(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Returns the error yielded by the future being run, or `None` if the
+    /// runtime's driver failed.
+    pub fn into_inner(self) -> Option<E> {
+        self.inner
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RunUntilError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            Some(ref e) => write!(fmt, "future failed: {}", e),
+            None => write!(fmt, "runtime driver failed"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for RunUntilError<E> {
+    fn description(&self) -> &str {
+        match self.inner {
+            Some(_) => "future failed",
+            None => "runtime driver failed",
+        }
+    }
+}
+
 impl Runtime {
     /// Returns a new runtime initialized with default configuration values.
     pub fn new() -> io::Result<Runtime> {
         Builder::new().build()
     }
 
-    pub(super) fn new2(
-        reactor_handle: tokio_reactor::Handle,
-        timer_handle: timer::Handle,
-        clock: Clock,
-        executor: CurrentThread<Timer<Reactor>>) -> Runtime
-    {
+    pub(super) fn new2(driver: Driver) -> Runtime {
+        let handles = driver.clone_handles();
+
         Runtime {
-            reactor_handle,
-            timer_handle,
-            clock,
-            executor,
+            handles,
+            executor: CurrentThread::new_with_park(driver),
         }
     }
 
+    /// Returns a reference to the driver the runtime's executor parks on.
+    pub fn driver(&self) -> &Driver {
+        self.executor.get_park()
+    }
+
     /// Get a new handle to spawn futures on the single-threaded Tokio runtime
     ///
     /// Different to the runtime itself, the handle can be sent to different
@@ -173,7 +210,8 @@ impl Runtime {
     /// This function can be used to synchronously block the current thread
     /// until the provided `future` has resolved either successfully or with an
     /// error. The result of the future is then returned from this function
-    /// call.
+    /// call. The future does not need to be `'static`, so it may borrow from
+    /// the caller's stack.
     ///
     /// Note that this function will **also** execute any spawned futures on the
     /// current thread, but will **not** block until these other spawned futures
@@ -183,13 +221,63 @@ impl Runtime {
     ///
     /// The caller is responsible for ensuring that other spawned futures
     /// complete execution by calling `block_on` or `run`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the runtime's driver fails, use [`run_until`]
+    /// to handle that case. A panic raised while polling the provided future
+    /// or any of the spawned futures is propagated to the caller.
+    ///
+    /// [`run_until`]: #method.run_until
     pub fn block_on<F>(&mut self, f: F) -> Result<F::Item, F::Error>
         where F: Future
+    {
+        self.run_until(f)
+            .map_err(|e| e.into_inner().expect("unexpected execution error"))
+    }
+
+    /// Drives the runtime until the provided future completes.
+    ///
+    /// Spawned futures are executed on the current thread while waiting for
+    /// `future` to complete, same as with [`block_on`]. The future does not
+    /// need to be `'static`.
+    ///
+    /// Unlike [`block_on`], a failure of the runtime's driver is returned as
+    /// an error instead of panicking. [`RunUntilError::into_inner`] tells the
+    /// two failure cases apart.
+    ///
+    /// # Panics
+    ///
+    /// A panic raised while polling the provided future or any of the spawned
+    /// futures is propagated to the caller.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::runtime::current_thread::Runtime;
+    /// use tokio::prelude::*;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = Runtime::new().unwrap();
+    /// let name = String::from("borrowed");
+    ///
+    /// let len = rt.run_until(future::lazy(|| Ok::<_, ()>(name.len())));
+    /// assert_eq!(len.unwrap(), 8);
+    /// # }
+    /// ```
+    ///
+    /// [`block_on`]: #method.block_on
+    /// [`RunUntilError::into_inner`]: struct.RunUntilError.html#method.into_inner
+    pub fn run_until<F>(&mut self, future: F) -> Result<F::Item, RunUntilError<F::Error>>
+        where F: Future
     {
         self.enter(|executor| {
-            // Run the provided future
-            let ret = executor.block_on(f);
-            ret.map_err(|e| e.into_inner().expect("unexpected execution error"))
+            executor.block_on(future)
+                .map_err(|e| RunUntilError {
+                    inner: e.into_inner(),
+                })
         })
     }
 
@@ -203,12 +291,12 @@ impl Runtime {
     }
 
     fn enter<F, R>(&mut self, f: F) -> R
-    where F: FnOnce(&mut current_thread::Entered<Timer<Reactor>>) -> R
+    where F: FnOnce(&mut current_thread::Entered<Driver>) -> R
     {
+        let handle = ::runtime::Handle::current_thread(self.handle(), &self.handles);
+
         let Runtime {
-            ref reactor_handle,
-            ref timer_handle,
-            ref clock,
+            ref handles,
             ref mut executor,
             ..
         } = *self;
@@ -216,21 +304,19 @@ impl Runtime {
         // Binds an executor to this thread
         let mut enter = tokio_executor::enter().expect("Multiple executors at once");
 
-        // This will set the default handle and timer to use inside the closure
-        // and run the future.
-        tokio_reactor::with_default(&reactor_handle, &mut enter, |enter| {
-            clock::with_default(clock, enter, |enter| {
-                timer::with_default(&timer_handle, enter, |enter| {
-                    // The TaskExecutor is a fake executor that looks into the
-                    // current single-threaded executor when used. This is a trick,
-                    // because we need two mutable references to the executor (one
-                    // to run the provided future, another to install as the default
-                    // one). We use the fake one here as the default one.
-                    let mut default_executor = current_thread::TaskExecutor::current();
-                    tokio_executor::with_default(&mut default_executor, enter, |enter| {
-                        let mut executor = executor.enter(enter);
-                        f(&mut executor)
-                    })
+        // This will set the default reactor, clock and timer to use inside the
+        // closure and run the future.
+        handles.with_default(&mut enter, |enter| {
+            // The TaskExecutor is a fake executor that looks into the
+            // current single-threaded executor when used. This is a trick,
+            // because we need two mutable references to the executor (one
+            // to run the provided future, another to install as the default
+            // one). We use the fake one here as the default one.
+            let mut default_executor = current_thread::TaskExecutor::current();
+            tokio_executor::with_default(&mut default_executor, enter, |enter| {
+                handle.with_current(|| {
+                    let mut executor = executor.enter(enter);
+                    f(&mut executor)
                 })
             })
         })
@@ -0,0 +1,305 @@
+use runtime::current_thread;
+use runtime::registry;
+
+use tokio_executor::{self, Enter, Executor, SpawnError};
+use tokio_reactor;
+use tokio_threadpool::Sender;
+use tokio_timer::clock::{self, Clock};
+use tokio_timer::timer;
+
+use futures::Future;
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+/// Handle to the runtime the current task is running on.
+///
+/// A `Handle` is obtained from within a runtime with [`Handle::current`]. It
+/// gives access to the runtime's executor, reactor, timer, and clock without
+/// having to pass them through every layer of an application. The handle can
+/// be sent to other threads, for example to spawn tasks onto the runtime from
+/// outside of it.
+///
+/// The runtime sets the current handle on each of its worker threads, as well
+/// as on the thread running a [`current_thread::Runtime`] for the duration of
+/// `block_on` and `run`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::runtime::Handle;
+/// use tokio::prelude::*;
+///
+/// # pub fn main() {
+/// tokio::run(future::lazy(|| {
+///     let handle = Handle::current();
+///
+///     handle.spawn(future::lazy(|| {
+///         println!("spawned from a handle");
+///         Ok(())
+///     })).unwrap();
+///
+///     Ok(())
+/// }));
+/// # }
+/// ```
+///
+/// [`Handle::current`]: #method.current
+/// [`current_thread::Runtime`]: current_thread/struct.Runtime.html
+#[derive(Debug, Clone)]
+pub struct Handle {
+    spawner: Spawner,
+    reactor: Option<tokio_reactor::Handle>,
+    timer: Option<timer::Handle>,
+    clock: Clock,
+}
+
+#[derive(Debug, Clone)]
+enum Spawner {
+    ThreadPool(Sender),
+    CurrentThread(current_thread::Handle),
+}
+
+/// Error returned by [`Handle::try_current`] when not called from within a
+/// runtime.
+///
+/// [`Handle::try_current`]: struct.Handle.html#method.try_current
+#[derive(Debug)]
+pub struct TryCurrentError {
+    _p: (),
+}
+
+thread_local! {
+    /// Handle to the runtime running on the current thread.
+    static CURRENT: RefCell<Option<Handle>> = RefCell::new(None)
+}
+
+impl Handle {
+    pub(crate) fn thread_pool(
+        sender: Sender,
+        reactor: tokio_reactor::Handle,
+        timer: timer::Handle,
+        clock: Clock,
+    ) -> Handle {
+        Handle {
+            spawner: Spawner::ThreadPool(sender),
+            reactor: Some(reactor),
+            timer: Some(timer),
+            clock,
+        }
+    }
+
+    pub(crate) fn current_thread(
+        handle: current_thread::Handle,
+        driver: &current_thread::DriverHandles,
+    ) -> Handle {
+        Handle {
+            spawner: Spawner::CurrentThread(handle),
+            reactor: driver.reactor_handle().cloned(),
+            timer: driver.timer_handle().cloned(),
+            clock: driver.clock().clone(),
+        }
+    }
+
+    /// Returns a handle to the runtime the current thread is running on.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is not called from within a runtime, use
+    /// [`try_current`] to handle that case.
+    ///
+    /// [`try_current`]: #method.try_current
+    pub fn current() -> Handle {
+        match Handle::try_current() {
+            Ok(handle) => handle,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Returns a handle to the runtime the current thread is running on, or
+    /// an error if it is not called from within a runtime.
+    pub fn try_current() -> Result<Handle, TryCurrentError> {
+        CURRENT.with(|current| {
+            current.borrow().clone().ok_or(TryCurrentError { _p: () })
+        })
+    }
+
+    /// Returns a handle to the runtime registered under `name`, or `None` if
+    /// no runtime is registered under that name.
+    ///
+    /// Runtimes are registered with [`Runtime::register`], and unregistered
+    /// when they shut down.
+    ///
+    /// [`Runtime::register`]: struct.Runtime.html#method.register
+    pub fn named(name: &str) -> Option<Handle> {
+        registry::get(name)
+    }
+
+    /// Spawn a future onto the runtime.
+    ///
+    /// This function may be called from any thread.
+    pub fn spawn<F>(&self, future: F) -> Result<(), SpawnError>
+    where F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        match self.spawner {
+            Spawner::ThreadPool(ref sender) => sender.spawn(future),
+            Spawner::CurrentThread(ref handle) => handle.spawn(future),
+        }
+    }
+
+    /// Returns the handle to the runtime's reactor, if the runtime has one.
+    ///
+    /// The runtime's thread pool runs one reactor per worker thread, in which
+    /// case this is the reactor of the worker the handle was obtained on.
+    pub fn reactor(&self) -> Option<&tokio_reactor::Handle> {
+        self.reactor.as_ref()
+    }
+
+    /// Returns the handle to the runtime's timer, if the runtime has one.
+    ///
+    /// The runtime's thread pool runs one timer per worker thread, in which
+    /// case this is the timer of the worker the handle was obtained on. The
+    /// handle of the runtime itself, obtained outside of the worker threads,
+    /// uses a timer shared by all the threads that are not workers.
+    pub fn timer(&self) -> Option<&timer::Handle> {
+        self.timer.as_ref()
+    }
+
+    /// Returns the runtime's clock.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Set the runtime's resources as the defaults for the current thread for
+    /// the duration of the closure.
+    ///
+    /// Resources such as TCP streams and timeouts register with the default
+    /// reactor and timer of the thread they are created on, which are only set
+    /// when running on the runtime. Creating them on any other thread panics or
+    /// fails. Within `enter`, the runtime's reactor, timer, and clock are the
+    /// defaults, so such resources can be created on any thread and then used
+    /// on the runtime. [`tokio::spawn`] spawns onto the runtime and
+    /// [`Handle::current`] returns `self`.
+    ///
+    /// The runtime's thread pool runs one reactor and one timer per worker
+    /// thread. The resources created within `enter` are driven by the worker
+    /// the handle was obtained on. For the handle of the runtime itself, I/O
+    /// resources are driven by the first worker, and timeouts by a timer shared
+    /// by all the threads that are not workers.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is called from within an execution context,
+    /// for example from a task running on a runtime. In that case, the
+    /// runtime's resources already are the defaults. The closure must not
+    /// block on futures either, as that would run an executor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::runtime::{Handle, Runtime};
+    /// use tokio::timer::Delay;
+    /// use tokio::prelude::*;
+    ///
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # pub fn main() {
+    /// let mut rt = Runtime::new().unwrap();
+    /// let handle = rt.block_on(future::lazy(|| Ok::<_, ()>(Handle::current()))).unwrap();
+    ///
+    /// // Create a timeout outside of the runtime
+    /// let delay = handle.enter(|| {
+    ///     let delay = Delay::new(Instant::now() + Duration::from_millis(10));
+    ///     tokio::spawn(future::lazy(|| Ok(())));
+    ///     delay
+    /// });
+    ///
+    /// rt.block_on(delay).unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`tokio::spawn`]: ../executor/fn.spawn.html
+    /// [`Handle::current`]: #method.current
+    pub fn enter<F, R>(&self, f: F) -> R
+    where F: FnOnce() -> R,
+    {
+        let mut enter = tokio_executor::enter()
+            .expect("attempted to enter a runtime from within an execution context");
+
+        let mut spawner = self.spawner.clone();
+
+        self.with_drivers(&mut enter, |enter| {
+            tokio_executor::with_default(&mut spawner, enter, |_| self.with_current(f))
+        })
+    }
+
+    /// Set the runtime's reactor, clock, and timer as the defaults for the
+    /// duration of the closure.
+    fn with_drivers<F, R>(&self, enter: &mut Enter, f: F) -> R
+    where F: FnOnce(&mut Enter) -> R,
+    {
+        let with_time = |enter: &mut Enter| {
+            clock::with_default(&self.clock, enter, |enter| match self.timer {
+                Some(ref timer) => timer::with_default(timer, enter, f),
+                None => f(enter),
+            })
+        };
+
+        match self.reactor {
+            Some(ref reactor) => tokio_reactor::with_default(reactor, enter, with_time),
+            None => with_time(enter),
+        }
+    }
+
+    /// Set `self` as the current handle for the duration of the closure.
+    pub(crate) fn with_current<F, R>(&self, f: F) -> R
+    where F: FnOnce() -> R,
+    {
+        struct Reset(Option<Handle>);
+
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                let prev = self.0.take();
+                CURRENT.with(|current| *current.borrow_mut() = prev);
+            }
+        }
+
+        let prev = CURRENT.with(|current| {
+            mem::replace(&mut *current.borrow_mut(), Some(self.clone()))
+        });
+
+        let _reset = Reset(prev);
+
+        f()
+    }
+}
+
+impl Executor for Spawner {
+    fn spawn(
+        &mut self,
+        future: Box<Future<Item = (), Error = ()> + Send>,
+    ) -> Result<(), SpawnError> {
+        match *self {
+            Spawner::ThreadPool(ref sender) => sender.spawn(future),
+            Spawner::CurrentThread(ref handle) => handle.spawn(future),
+        }
+    }
+}
+
+impl fmt::Display for TryCurrentError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description())
+    }
+}
+
+impl Error for TryCurrentError {
+    fn description(&self) -> &str {
+        "not currently running inside a Tokio runtime"
+    }
+}
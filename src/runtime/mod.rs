@@ -113,13 +113,23 @@
 //! [`Timer`]: https://docs.rs/tokio-timer/0.2/tokio_timer/timer/struct.Timer.html
 
 pub mod current_thread;
+mod handle;
+mod registry;
+pub mod simulation;
 mod threadpool;
 
+pub use self::handle::{Handle, TryCurrentError};
+pub use self::registry::RegisterError;
+
 pub use self::threadpool::{
     Builder,
     Runtime,
     Shutdown,
     TaskExecutor,
+    WorkerPark,
     run,
 };
 
+pub use tokio_threadpool::{BlockingPolicy, BlockingStats, WorkerContext, WorkerStats};
+pub use tokio_threadpool::{Dump, SpawnLocation, TaskDump, TaskState};
+
@@ -0,0 +1,99 @@
+use runtime::Handle;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Mutex, Once, ONCE_INIT};
+
+/// Names a runtime registered itself under, removed from the process-level
+/// registry when dropped.
+#[derive(Debug)]
+pub(crate) struct Registration {
+    names: Mutex<Vec<String>>,
+}
+
+/// Error returned by [`Runtime::register`] when another runtime is already
+/// registered under the requested name.
+///
+/// [`Runtime::register`]: struct.Runtime.html#method.register
+#[derive(Debug)]
+pub struct RegisterError {
+    name: String,
+}
+
+type Registry = Mutex<HashMap<String, Handle>>;
+
+fn registry() -> &'static Registry {
+    static INIT: Once = ONCE_INIT;
+    static mut REGISTRY: *const Registry = 0 as *const _;
+
+    unsafe {
+        INIT.call_once(|| {
+            REGISTRY = Box::into_raw(Box::new(Mutex::new(HashMap::new())));
+        });
+
+        &*REGISTRY
+    }
+}
+
+/// Returns the handle registered under `name`, if any.
+pub(crate) fn get(name: &str) -> Option<Handle> {
+    registry().lock().unwrap().get(name).cloned()
+}
+
+impl Registration {
+    pub fn new() -> Registration {
+        Registration {
+            names: Mutex::new(vec![]),
+        }
+    }
+
+    /// Register `handle` under `name` until `self` is dropped.
+    pub fn register(&self, name: String, handle: &Handle) -> Result<(), RegisterError> {
+        let mut registry = registry().lock().unwrap();
+
+        if registry.contains_key(&name) {
+            return Err(RegisterError { name });
+        }
+
+        registry.insert(name.clone(), handle.clone());
+        self.names.lock().unwrap().push(name);
+
+        Ok(())
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let names = self.names.get_mut().unwrap();
+
+        if names.is_empty() {
+            return;
+        }
+
+        let mut registry = registry().lock().unwrap();
+
+        for name in names.drain(..) {
+            registry.remove(&name);
+        }
+    }
+}
+
+impl RegisterError {
+    /// Returns the name that is already registered.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "a runtime is already registered as {:?}", self.name)
+    }
+}
+
+impl Error for RegisterError {
+    fn description(&self) -> &str {
+        "a runtime is already registered under that name"
+    }
+}
@@ -0,0 +1,75 @@
+//! A deterministic runtime for testing.
+//!
+//! The [`Runtime`] in this module runs all tasks on the current thread, like
+//! the [`current_thread`] runtime, but with two important differences:
+//!
+//! * The order in which ready tasks are polled is picked by a scheduler seeded
+//!   with a user provided value. Running the same program twice with the same
+//!   seed polls the tasks in the same order.
+//! * Time is virtual. The runtime's [clock] does not advance while tasks are
+//!   running. When no task is ready, the clock jumps straight to the next
+//!   pending timeout instead of sleeping until it fires.
+//!
+//! This makes it possible to reproduce a concurrency bug that only shows up
+//! with a specific interleaving of tasks: run the test with a number of
+//! different seeds and, once one fails, keep the seed around to replay the
+//! exact same execution.
+//!
+//! The tasks can also be driven by hand. [`Runtime::ready_tasks`] lists the
+//! tasks that are ready to be polled and [`Runtime::step`] polls a specific
+//! one, which allows exploring specific interleavings.
+//!
+//! The simulation runtime does not include a reactor, so it can only be used
+//! to run code that does not perform I/O, or that performs it through an
+//! in-memory abstraction. Deadlines should be computed from
+//! [`timer::Handle::now`] instead of `Instant::now` in order to use virtual
+//! time.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate tokio;
+//! # extern crate futures;
+//! use tokio::runtime::simulation::Runtime;
+//! use tokio::timer::{Delay, Handle};
+//! use tokio::prelude::*;
+//!
+//! use std::sync::{Arc, Mutex};
+//! use std::time::Duration;
+//!
+//! # pub fn main() {
+//! fn run(seed: u64) -> Vec<usize> {
+//!     let order = Arc::new(Mutex::new(vec![]));
+//!     let mut rt = Runtime::new(seed);
+//!
+//!     for i in 0..4 {
+//!         let order = order.clone();
+//!         rt.spawn(future::lazy(move || {
+//!             order.lock().unwrap().push(i);
+//!             Ok(())
+//!         }));
+//!     }
+//!
+//!     // An hour long delay completes immediately
+//!     let when = Handle::current().now() + Duration::from_secs(3600);
+//!     rt.block_on(Delay::new(when)).unwrap();
+//!
+//!     let order = order.lock().unwrap().clone();
+//!     order
+//! }
+//!
+//! assert_eq!(run(7), run(7));
+//! # }
+//! ```
+//!
+//! [`Runtime`]: struct.Runtime.html
+//! [`Runtime::ready_tasks`]: struct.Runtime.html#method.ready_tasks
+//! [`Runtime::step`]: struct.Runtime.html#method.step
+//! [`current_thread`]: ../current_thread/index.html
+//! [clock]: ../../clock/index.html
+//! [`timer::Handle::now`]: ../../timer/struct.Handle.html#method.now
+
+mod runtime;
+mod time;
+
+pub use self::runtime::{Runtime, TaskId};
@@ -0,0 +1,411 @@
+use super::time::{VirtualNow, VirtualPark};
+
+use tokio_executor::{self, Executor, SpawnError};
+use tokio_timer::clock::{self, Clock};
+use tokio_timer::timer::{self, Timer};
+
+use futures::executor::{self, Notify, NotifyHandle, Spawn};
+use futures::{Async, Future};
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Single-threaded runtime with a seeded scheduler and virtual time.
+///
+/// See the [module level][mod] documentation for more details.
+///
+/// [mod]: index.html
+pub struct Runtime {
+    /// The seed the scheduler was created with
+    seed: u64,
+
+    /// Picks the next task to poll among the ready ones
+    rng: XorShift,
+
+    /// Spawned tasks that have not completed yet
+    tasks: HashMap<usize, Task>,
+
+    /// Identifier of the future passed to `block_on`, if any
+    main: Option<usize>,
+
+    /// Identifier assigned to the next task
+    next_id: usize,
+
+    /// Tracks the tasks that have been notified
+    notifier: Arc<Notifier>,
+
+    /// Timer driven by virtual time
+    timer: Timer<VirtualPark, Clock>,
+
+    /// Virtual clock
+    clock: Clock,
+
+    /// Source of virtual time of `clock`
+    now: VirtualNow,
+}
+
+/// Identifies a task spawned onto a simulation [`Runtime`].
+///
+/// Identifiers are assigned in increasing order as tasks are spawned, so they
+/// are the same across runs using the same seed.
+///
+/// [`Runtime`]: struct.Runtime.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(usize);
+
+type Task = Spawn<Box<Future<Item = (), Error = ()>>>;
+
+#[derive(Debug)]
+struct Notifier {
+    /// Identifiers of the notified tasks, ordered to keep the scheduling
+    /// deterministic.
+    ready: Mutex<BTreeSet<usize>>,
+}
+
+/// Collects the futures spawned with `tokio::spawn` while a task is polled.
+#[derive(Default)]
+struct Spawner {
+    spawned: Vec<Box<Future<Item = (), Error = ()> + Send>>,
+}
+
+/// xorshift64* pseudo random number generator.
+#[derive(Debug)]
+struct XorShift(u64);
+
+impl Runtime {
+    /// Create a new simulation runtime whose scheduler is seeded with `seed`.
+    pub fn new(seed: u64) -> Runtime {
+        let now = VirtualNow::new();
+        let clock = Clock::new_with_now(now.clone());
+        let timer = Timer::new_with_now(VirtualPark::new(now.clone()), clock.clone());
+
+        Runtime {
+            seed,
+            rng: XorShift::new(seed),
+            tasks: HashMap::new(),
+            main: None,
+            next_id: 0,
+            notifier: Arc::new(Notifier {
+                ready: Mutex::new(BTreeSet::new()),
+            }),
+            timer,
+            clock,
+            now,
+        }
+    }
+
+    /// Returns the seed the scheduler was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the virtual clock of the runtime.
+    ///
+    /// The clock is set as the default clock while tasks are polled.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Returns the current virtual time.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Spawn a future onto the runtime, returning the identifier of the new
+    /// task.
+    ///
+    /// The task is ready to be polled but is not polled until the runtime is
+    /// driven.
+    pub fn spawn<F>(&mut self, future: F) -> TaskId
+    where
+        F: Future<Item = (), Error = ()> + 'static,
+    {
+        TaskId(self.insert(Box::new(future)))
+    }
+
+    /// Returns the identifiers of the tasks that are ready to be polled, in
+    /// increasing order.
+    ///
+    /// Timeouts that have expired at the current virtual time are fired
+    /// first, which makes the tasks waiting on them ready.
+    pub fn ready_tasks(&mut self) -> Vec<TaskId> {
+        self.fire_timers();
+
+        let ready = self.notifier.ready.lock().unwrap();
+
+        ready
+            .iter()
+            .filter(|id| self.tasks.contains_key(id))
+            .map(|&id| TaskId(id))
+            .collect()
+    }
+
+    /// Poll the task identified by `task`, returning `true` if it completed.
+    ///
+    /// The task does not need to be ready. Polling a task that was not
+    /// notified is the same as a spurious wakeup.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the task has already completed, or if it is
+    /// called from within an execution context.
+    pub fn step(&mut self, task: TaskId) -> bool {
+        assert!(self.tasks.contains_key(&task.0), "no task with id {:?}", task);
+
+        self.notifier.ready.lock().unwrap().remove(&task.0);
+        self.poll_task(task.0)
+    }
+
+    /// Poll a single ready task, picked by the seeded scheduler.
+    ///
+    /// Returns the identifier of the polled task, or `None` if no task is
+    /// ready. Virtual time is not advanced.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is called from within an execution context.
+    pub fn tick(&mut self) -> Option<TaskId> {
+        let id = self.next_ready()?;
+        self.poll_task(id);
+        Some(TaskId(id))
+    }
+
+    /// Poll ready tasks until none is left, without advancing virtual time.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is called from within an execution context.
+    pub fn run_until_stalled(&mut self) -> &mut Self {
+        while self.tick().is_some() {}
+        self
+    }
+
+    /// Advance virtual time by `duration`, firing the timeouts that expire.
+    ///
+    /// The tasks waiting on the fired timeouts become ready but are not
+    /// polled.
+    pub fn advance(&mut self, duration: Duration) -> &mut Self {
+        self.now.advance(duration);
+        self.fire_timers();
+        self
+    }
+
+    /// Run the runtime until all spawned tasks have completed.
+    ///
+    /// Whenever no task is ready, virtual time jumps to the next pending
+    /// timeout.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the tasks cannot make progress anymore, i.e.
+    /// no task is ready and there is no pending timeout, or if it is called
+    /// from within an execution context.
+    pub fn run(&mut self) -> &mut Self {
+        while !self.tasks.is_empty() {
+            match self.next_ready() {
+                Some(id) => {
+                    self.poll_task(id);
+                }
+                None => self.park(),
+            }
+        }
+
+        self
+    }
+
+    /// Run the provided future to completion on the runtime, along with the
+    /// tasks spawned onto it.
+    ///
+    /// The future is scheduled like any other task. Tasks that are still
+    /// running when the future completes are left on the runtime.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future cannot make progress anymore, i.e.
+    /// no task is ready and there is no pending timeout, or if it is called
+    /// from within an execution context.
+    pub fn block_on<F>(&mut self, future: F) -> Result<F::Item, F::Error>
+    where
+        F: Future,
+    {
+        let id = self.next_id();
+        let mut future = executor::spawn(future);
+
+        self.main = Some(id);
+        self.notifier.notify(id);
+
+        loop {
+            match self.next_ready() {
+                Some(next) if next == id => {
+                    let res = self.enter(|notify| future.poll_future_notify(notify, id));
+
+                    match res {
+                        Ok(Async::Ready(v)) => {
+                            self.main = None;
+                            return Ok(v);
+                        }
+                        Ok(Async::NotReady) => {}
+                        Err(e) => {
+                            self.main = None;
+                            return Err(e);
+                        }
+                    }
+                }
+                Some(next) => {
+                    self.poll_task(next);
+                }
+                None => self.park(),
+            }
+        }
+    }
+
+    fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn insert(&mut self, future: Box<Future<Item = (), Error = ()>>) -> usize {
+        let id = self.next_id();
+        self.tasks.insert(id, executor::spawn(future));
+        self.notifier.notify(id);
+        id
+    }
+
+    /// Pick the next task to poll among the ready ones.
+    fn next_ready(&mut self) -> Option<usize> {
+        self.fire_timers();
+
+        let mut ready = self.notifier.ready.lock().unwrap();
+
+        // Completed tasks may still be notified, skip them.
+        let tasks = &self.tasks;
+        let main = self.main;
+        ready.retain(|id| tasks.contains_key(id) || main == Some(*id));
+
+        if ready.is_empty() {
+            return None;
+        }
+
+        let n = (self.rng.next() % ready.len() as u64) as usize;
+        let id = *ready.iter().nth(n).unwrap();
+        ready.remove(&id);
+
+        Some(id)
+    }
+
+    /// Poll the task identified by `id`, returning `true` if it completed.
+    fn poll_task(&mut self, id: usize) -> bool {
+        let mut task = match self.tasks.remove(&id) {
+            Some(task) => task,
+            None => return true,
+        };
+
+        match self.enter(|notify| task.poll_future_notify(notify, id)) {
+            Ok(Async::NotReady) => {
+                self.tasks.insert(id, task);
+                false
+            }
+            Ok(Async::Ready(())) | Err(()) => true,
+        }
+    }
+
+    /// Fire the timeouts that have expired at the current virtual time.
+    fn fire_timers(&mut self) {
+        self.timer
+            .turn(Some(Duration::from_secs(0)))
+            .expect("failed to turn the timer");
+    }
+
+    /// Advance virtual time to the next pending timeout.
+    fn park(&mut self) {
+        let now = self.now();
+
+        self.timer.turn(None).expect("failed to turn the timer");
+
+        if self.now() == now && self.notifier.ready.lock().unwrap().is_empty() {
+            panic!(
+                "simulation cannot make progress, no task is ready and there is no \
+                 pending timeout (seed = {})",
+                self.seed
+            );
+        }
+    }
+
+    /// Set the runtime as the default execution context while calling `f`.
+    fn enter<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&NotifyHandle) -> R,
+    {
+        let mut enter = tokio_executor::enter()
+            .expect("attempted to run a simulation from within an execution context");
+
+        let notify = NotifyHandle::from(self.notifier.clone());
+        let timer = self.timer.handle();
+        let mut spawner = Spawner::default();
+
+        let ret = clock::with_default(&self.clock, &mut enter, |enter| {
+            timer::with_default(&timer, enter, |enter| {
+                tokio_executor::with_default(&mut spawner, enter, |_| f(&notify))
+            })
+        });
+
+        for future in spawner.spawned {
+            self.insert(future);
+        }
+
+        ret
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Runtime")
+            .field("seed", &self.seed)
+            .field("tasks", &self.tasks.len())
+            .field("now", &self.now())
+            .finish()
+    }
+}
+
+impl Notify for Notifier {
+    fn notify(&self, id: usize) {
+        self.ready.lock().unwrap().insert(id);
+    }
+}
+
+impl Executor for Spawner {
+    fn spawn(
+        &mut self,
+        future: Box<Future<Item = (), Error = ()> + Send>,
+    ) -> Result<(), SpawnError> {
+        self.spawned.push(future);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Spawner {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Spawner")
+            .field("spawned", &self.spawned.len())
+            .finish()
+    }
+}
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // The state must never be zero
+        XorShift(seed ^ 0x9E37_79B9_7F4A_7C15 | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
@@ -0,0 +1,71 @@
+use tokio_executor::park::{Park, Unpark};
+use tokio_timer::clock::Now;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of virtual time shared by the simulation's clock and timer.
+#[derive(Debug, Clone)]
+pub(super) struct VirtualNow {
+    now: Arc<Mutex<Instant>>,
+}
+
+/// `Park` implementation advancing virtual time instead of blocking.
+///
+/// The timer parks for exactly the time remaining until its next timeout, so
+/// advancing the clock by the requested duration fires that timeout.
+#[derive(Debug)]
+pub(super) struct VirtualPark {
+    now: VirtualNow,
+}
+
+#[derive(Debug)]
+pub(super) struct NoopUnpark;
+
+impl VirtualNow {
+    pub fn new() -> VirtualNow {
+        VirtualNow {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Now for VirtualNow {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+}
+
+impl VirtualPark {
+    pub fn new(now: VirtualNow) -> VirtualPark {
+        VirtualPark { now }
+    }
+}
+
+impl Park for VirtualPark {
+    type Unpark = NoopUnpark;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        NoopUnpark
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        // There is no pending timeout, nothing would ever wake the thread up.
+        Ok(())
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        self.now.advance(duration);
+        Ok(())
+    }
+}
+
+impl Unpark for NoopUnpark {
+    fn unpark(&self) {}
+}
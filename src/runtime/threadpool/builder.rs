@@ -1,14 +1,24 @@
 use super::{Inner, Runtime};
+use super::park::{NewWorkerPark, WorkerPark};
+use super::timer::SharedTimer;
+use runtime::Handle;
+use runtime::registry::Registration;
 
 use reactor::Reactor;
 
+use std::error::Error;
+use std::fmt;
 use std::io;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use num_cpus;
+#[cfg(feature = "fs")]
+use tokio_fs::limit::{self as fs_limit, Limit as FsLimit};
+use tokio_executor::park::Park;
 use tokio_reactor;
 use tokio_threadpool::Builder as ThreadPoolBuilder;
+use tokio_threadpool::{BlockingPolicy, WorkerContext, WorkerId};
 use tokio_timer::clock::{self, Clock};
 use tokio_timer::timer::{self, Timer};
 
@@ -51,7 +61,6 @@ use tokio_timer::timer::{self, Timer};
 ///     // use runtime ...
 /// }
 /// ```
-#[derive(Debug)]
 pub struct Builder {
     /// Thread pool specific builder
     threadpool_builder: ThreadPoolBuilder,
@@ -59,8 +68,22 @@ pub struct Builder {
     /// The number of worker threads
     core_threads: usize,
 
+    /// The number of worker threads the runtime can be resized to
+    max_core_threads: usize,
+
     /// The clock to use
     clock: Clock,
+
+    /// How long the thread pool must stay idle before `shutdown_on_idle`
+    /// shuts the runtime down
+    idle_grace_period: Option<Duration>,
+
+    /// Wraps the `Park` implementation of each worker
+    custom_park: Option<Arc<Fn(&mut ThreadPoolBuilder, NewWorkerPark) + Send + Sync>>,
+
+    /// Limits the blocking threads used by filesystem operations
+    #[cfg(feature = "fs")]
+    fs_limit: Option<FsLimit>,
 }
 
 impl Builder {
@@ -78,7 +101,12 @@ impl Builder {
         Builder {
             threadpool_builder,
             core_threads,
+            max_core_threads: 0,
             clock: Clock::new(),
+            idle_grace_period: None,
+            custom_park: None,
+            #[cfg(feature = "fs")]
+            fs_limit: None,
         }
     }
 
@@ -127,11 +155,45 @@ impl Builder {
         self
     }
 
+    /// Set the maximum number of worker threads the `Runtime` can be resized
+    /// to with [`Runtime::resize`].
+    ///
+    /// Each potential worker gets its own reactor and timer, which are created
+    /// when the runtime is built. Values smaller than `core_threads` are
+    /// ignored. By default, the runtime can only be resized up to
+    /// `core_threads`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = runtime::Builder::new()
+    ///     .core_threads(2)
+    ///     .max_core_threads(8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.resize(4);
+    /// # }
+    /// ```
+    ///
+    /// [`Runtime::resize`]: struct.Runtime.html#method.resize
+    pub fn max_core_threads(&mut self, val: usize) -> &mut Self {
+        self.max_core_threads = val;
+        self.threadpool_builder.max_pool_size(val);
+        self
+    }
+
     /// Set the maximum number of concurrent blocking sections in the `Runtime`'s
     /// thread pool.
     ///
     /// When the maximum concurrent `blocking` calls is reached, any further
-    /// calls to `blocking` will return `NotReady` and the task is notified once
+    /// calls to `blocking` are handled according to the [`blocking_policy`].
+    /// By default, they return `NotReady` and the task is notified once
     /// previously in-flight calls to `blocking` return.
     ///
     /// This must be a number between 1 and 32,768 though it is advised to keep
@@ -139,6 +201,8 @@ impl Builder {
     ///
     /// The default value is 100.
     ///
+    /// [`blocking_policy`]: #method.blocking_policy
+    ///
     /// # Examples
     ///
     /// ```
@@ -157,13 +221,110 @@ impl Builder {
         self
     }
 
+    /// Set the policy applied to `blocking` calls made while all blocking
+    /// threads of the `Runtime`'s thread pool are in use.
+    ///
+    /// With [`BlockingPolicy::Queue`], the task waits until a blocking thread
+    /// frees up. With [`BlockingPolicy::FailFast`], `blocking` returns an
+    /// error right away so the caller can shed load or fall back.
+    ///
+    /// The default value is [`BlockingPolicy::Queue`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    /// use tokio::runtime::BlockingPolicy;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = runtime::Builder::new()
+    ///     .blocking_threads(16)
+    ///     .blocking_policy(BlockingPolicy::FailFast)
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`BlockingPolicy::Queue`]: enum.BlockingPolicy.html#variant.Queue
+    /// [`BlockingPolicy::FailFast`]: enum.BlockingPolicy.html#variant.FailFast
+    pub fn blocking_policy(&mut self, val: BlockingPolicy) -> &mut Self {
+        self.threadpool_builder.blocking_policy(val);
+        self
+    }
+
+    /// Limit the number of blocking threads used by filesystem operations at
+    /// once.
+    ///
+    /// Filesystem operations share the [`blocking_threads`] with any other
+    /// `blocking` call, so a slow filesystem may use up all of them. With a
+    /// [`Limit`], the filesystem operations run on the workers of the
+    /// `Runtime` wait for it instead, leaving the other blocking threads to
+    /// the rest of the application. The operations still run on the
+    /// [`blocking_threads`], no thread is dedicated to them.
+    ///
+    /// This method is only available with the `fs` feature enabled.
+    ///
+    /// By default, filesystem operations are only limited by the
+    /// [`blocking_threads`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # use tokio::runtime;
+    /// use tokio::fs::limit;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = runtime::Builder::new()
+    ///     .blocking_threads(100)
+    ///     .fs_limit(limit::Builder::new().concurrency(8).queue_depth(256).build())
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`blocking_threads`]: #method.blocking_threads
+    /// [`Limit`]: ../fs/limit/struct.Limit.html
+    #[cfg(feature = "fs")]
+    pub fn fs_limit(&mut self, val: FsLimit) -> &mut Self {
+        self.fs_limit = Some(val);
+        self
+    }
+
+    /// Set the maximum number of spawned futures that have not yet started
+    /// executing on the `Runtime`'s thread pool.
+    ///
+    /// Once this many futures are queued, spawning fails with an "at capacity"
+    /// error until a worker picks up one of the queued futures.
+    ///
+    /// By default, the number of queued futures is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = runtime::Builder::new()
+    ///     .max_queued(1_024)
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn max_queued(&mut self, val: usize) -> &mut Self {
+        self.threadpool_builder.max_queued(val);
+        self
+    }
+
     /// Set the worker thread keep alive duration for threads in the `Runtime`'s
     /// thread pool.
     ///
     /// If set, a worker thread will wait for up to the specified duration for
     /// work, at which point the thread will shutdown. When work becomes
     /// available, a new thread will eventually be spawned to replace the one
-    /// that shut down.
+    /// that shut down. This also applies to threads that are left idle after
+    /// running a `blocking` section.
     ///
     /// When the value is `None`, the thread will wait for work forever.
     ///
@@ -188,6 +349,67 @@ impl Builder {
         self
     }
 
+    /// Set how long the `Runtime`'s thread pool must stay without tasks before
+    /// [`Runtime::shutdown_on_idle`] shuts the runtime down.
+    ///
+    /// Without a grace period, the runtime shuts down the moment the thread
+    /// pool is momentarily empty. Servers that go briefly quiet can use a
+    /// grace period to only shut down once no tasks have been running for the
+    /// whole duration.
+    ///
+    /// The default value is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    /// use std::time::Duration;
+    ///
+    /// # pub fn main() {
+    /// let mut rt = runtime::Builder::new()
+    ///     .idle_grace_period(Some(Duration::from_secs(30)))
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`Runtime::shutdown_on_idle`]: struct.Runtime.html#method.shutdown_on_idle
+    pub fn idle_grace_period(&mut self, val: Option<Duration>) -> &mut Self {
+        self.idle_grace_period = val;
+        self
+    }
+
+    /// Track the live tasks of the `Runtime` so that they can be dumped with
+    /// [`Runtime::dump`].
+    ///
+    /// Tracking tasks adds some overhead to spawning and completing tasks.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let rt = runtime::Builder::new()
+    ///     .task_dump(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// println!("{}", rt.dump());
+    /// # }
+    /// ```
+    ///
+    /// [`Runtime::dump`]: struct.Runtime.html#method.dump
+    pub fn task_dump(&mut self, val: bool) -> &mut Self {
+        self.threadpool_builder.task_dump(val);
+        self
+    }
+
     /// Set name prefix of threads spawned by the `Runtime`'s thread pool.
     ///
     /// Thread name prefix is used for generating thread names. For example, if
@@ -293,6 +515,214 @@ impl Builder {
         self
     }
 
+    /// Execute function `f` after each thread is started but before it starts
+    /// doing work, passing it the identity of the worker being started.
+    ///
+    /// Only one start callback is kept, so calling this function replaces any
+    /// callback previously set with [`after_start`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = runtime::Builder::new()
+    ///     .on_worker_start(|cx| {
+    ///         println!("worker {} started", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`after_start`]: #method.after_start
+    pub fn on_worker_start<F>(&mut self, f: F) -> &mut Self
+        where F: Fn(&WorkerContext) + Send + Sync + 'static
+    {
+        self.threadpool_builder.on_worker_start(f);
+        self
+    }
+
+    /// Execute function `f` before each thread stops, passing it the identity
+    /// of the last worker that ran on the thread.
+    ///
+    /// Only one stop callback is kept, so calling this function replaces any
+    /// callback previously set with [`before_stop`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = runtime::Builder::new()
+    ///     .on_worker_stop(|cx| {
+    ///         println!("worker {} stopping", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`before_stop`]: #method.before_stop
+    pub fn on_worker_stop<F>(&mut self, f: F) -> &mut Self
+        where F: Fn(&WorkerContext) + Send + Sync + 'static
+    {
+        self.threadpool_builder.on_worker_stop(f);
+        self
+    }
+
+    /// Execute function `f` each time a worker runs out of work and is about
+    /// to go to sleep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = runtime::Builder::new()
+    ///     .on_park(|cx| {
+    ///         println!("worker {} parking", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn on_park<F>(&mut self, f: F) -> &mut Self
+        where F: Fn(&WorkerContext) + Send + Sync + 'static
+    {
+        self.threadpool_builder.on_park(f);
+        self
+    }
+
+    /// Execute function `f` each time a sleeping worker is woken up to process
+    /// work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = runtime::Builder::new()
+    ///     .on_unpark(|cx| {
+    ///         println!("worker {} unparked", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn on_unpark<F>(&mut self, f: F) -> &mut Self
+        where F: Fn(&WorkerContext) + Send + Sync + 'static
+    {
+        self.threadpool_builder.on_unpark(f);
+        self
+    }
+
+    /// Pin worker threads to CPU cores.
+    ///
+    /// The provided closure `f` returns the index of the core that the worker
+    /// should run on, or `None` to let the worker run on any core. Threads
+    /// running blocking sections are kept off of the cores assigned to
+    /// workers.
+    ///
+    /// Thread affinity is supported on Linux and Windows. On other platforms,
+    /// the assignment is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use tokio::runtime;
+    ///
+    /// # pub fn main() {
+    /// let rt = runtime::Builder::new()
+    ///     .core_threads(2)
+    ///     .core_affinity(|id| Some(id.to_usize()))
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn core_affinity<F>(&mut self, f: F) -> &mut Self
+        where F: Fn(&WorkerId) -> Option<usize> + 'static
+    {
+        self.threadpool_builder.core_affinity(f);
+        self
+    }
+
+    /// Customize the `Park` instance used by each worker thread.
+    ///
+    /// The provided closure `f` is called once per worker with the worker's
+    /// default [`WorkerPark`], which drives the worker's timer and reactor. The
+    /// returned `Park` instance is used by the worker to put itself to sleep,
+    /// which allows interposing on parking, for example to drive virtual time
+    /// or an external event loop. The returned instance must still park the
+    /// provided `WorkerPark` for the worker's timer and reactor to make
+    /// progress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # use tokio::runtime;
+    /// use tokio::executor::park::Park;
+    /// use tokio::runtime::WorkerPark;
+    ///
+    /// use std::io;
+    /// use std::time::Duration;
+    ///
+    /// struct Logged(WorkerPark);
+    ///
+    /// impl Park for Logged {
+    ///     type Unpark = <WorkerPark as Park>::Unpark;
+    ///     type Error = io::Error;
+    ///
+    ///     fn unpark(&self) -> Self::Unpark {
+    ///         self.0.unpark()
+    ///     }
+    ///
+    ///     fn park(&mut self) -> io::Result<()> {
+    ///         println!("parking");
+    ///         self.0.park()
+    ///     }
+    ///
+    ///     fn park_timeout(&mut self, duration: Duration) -> io::Result<()> {
+    ///         println!("parking for {:?}", duration);
+    ///         self.0.park_timeout(duration)
+    ///     }
+    /// }
+    ///
+    /// # pub fn main() {
+    /// let rt = runtime::Builder::new()
+    ///     .custom_park(|_, park| Logged(park))
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`WorkerPark`]: struct.WorkerPark.html
+    pub fn custom_park<F, P>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerId, WorkerPark) -> P + Send + Sync + 'static,
+        P: Park + Send + 'static,
+        P::Error: Error,
+    {
+        let f = Arc::new(f);
+
+        // The thread pool boxes the returned `Park` itself, so it is handed
+        // the concrete type.
+        self.custom_park = Some(Arc::new(move |pool, new_park| {
+            let f = f.clone();
+            pool.custom_park(move |id| f(id, new_park(id)));
+        }));
+        self
+    }
+
     /// Create the configured `Runtime`.
     ///
     /// The returned `ThreadPool` instance is ready to spawn tasks.
@@ -316,7 +746,7 @@ impl Builder {
         let mut timer_handles = Vec::new();
         let mut timers = Vec::new();
 
-        for _ in 0..self.core_threads {
+        for _ in 0..self.core_threads.max(self.max_core_threads) {
             // Create a new reactor.
             let reactor = Reactor::new()?;
             reactor_handles.push(reactor.handle());
@@ -330,28 +760,72 @@ impl Builder {
         // Get a handle to the clock for the runtime.
         let clock = self.clock.clone();
 
-        let pool = self.threadpool_builder
+        // I/O resources created from outside of the runtime are driven by the
+        // first worker, timeouts by a timer shared by all the threads that are
+        // not workers.
+        let enter_reactor = reactor_handles[0].clone();
+        let shared_timer = SharedTimer::new(&self.clock);
+        let enter_timer = shared_timer.handle().clone();
+
+        #[cfg(feature = "fs")]
+        let fs_limit = self.fs_limit.clone();
+
+        self.threadpool_builder
             .around_worker(move |w, enter| {
                 let index = w.id().to_usize();
 
-                tokio_reactor::with_default(&reactor_handles[index], enter, |enter| {
-                    clock::with_default(&clock, enter, |enter| {
-                        timer::with_default(&timer_handles[index], enter, |_| {
-                            w.run();
-                        });
+                let handle = Handle::thread_pool(
+                    w.sender(),
+                    reactor_handles[index].clone(),
+                    timer_handles[index].clone(),
+                    clock.clone());
+
+                let run = |enter: &mut _| {
+                    tokio_reactor::with_default(&reactor_handles[index], enter, |enter| {
+                        clock::with_default(&clock, enter, |enter| {
+                            timer::with_default(&timer_handles[index], enter, |_| {
+                                handle.with_current(|| w.run());
+                            });
+                        })
                     })
-                });
-            })
-            .custom_park(move |worker_id| {
-                let index = worker_id.to_usize();
-
-                timers[index]
-                    .lock()
-                    .unwrap()
-                    .take()
-                    .unwrap()
-            })
-            .build();
+                };
+
+                #[cfg(feature = "fs")]
+                {
+                    if let Some(ref limit) = fs_limit {
+                        return fs_limit::with_default(limit, enter, run);
+                    }
+                }
+
+                run(enter)
+            });
+
+        let new_park: NewWorkerPark = Arc::new(move |worker_id| {
+            let index = worker_id.to_usize();
+
+            let timer = timers[index]
+                .lock()
+                .unwrap()
+                .take()
+                .unwrap();
+
+            WorkerPark::new(timer)
+        });
+
+        match self.custom_park {
+            Some(ref custom_park) => custom_park(&mut self.threadpool_builder, new_park),
+            None => {
+                self.threadpool_builder.custom_park(move |id| new_park(id));
+            }
+        }
+
+        let pool = self.threadpool_builder.build();
+
+        let handle = Handle::thread_pool(
+            pool.sender().clone(),
+            enter_reactor,
+            enter_timer,
+            self.clock.clone());
 
         // To support deprecated `reactor()` function
         let reactor = Reactor::new()?;
@@ -362,7 +836,23 @@ impl Builder {
                 reactor_handle,
                 reactor: Mutex::new(Some(reactor)),
                 pool,
+                handle,
+                shared_timer,
+                idle_grace_period: self.idle_grace_period,
+                registration: Registration::new(),
             }),
         })
     }
 }
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Builder")
+            .field("threadpool_builder", &self.threadpool_builder)
+            .field("core_threads", &self.core_threads)
+            .field("max_core_threads", &self.max_core_threads)
+            .field("clock", &self.clock)
+            .field("idle_grace_period", &self.idle_grace_period)
+            .finish()
+    }
+}
@@ -1,18 +1,26 @@
 mod builder;
+mod park;
 mod shutdown;
 mod task_executor;
+mod timer;
 
 pub use self::builder::Builder;
+pub use self::park::WorkerPark;
 pub use self::shutdown::Shutdown;
 pub use self::task_executor::TaskExecutor;
 
 use reactor::{Handle, Reactor};
+use runtime::registry::Registration;
+use self::timer::SharedTimer;
+use runtime::RegisterError;
 
 use std::io;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use tokio_executor::enter;
 use tokio_threadpool as threadpool;
+use tokio_threadpool::{BlockingStats, Dump, SpawnLocation, WorkerStats};
 
 use futures;
 use futures::future::Future;
@@ -46,6 +54,20 @@ struct Inner {
 
     /// Task execution pool.
     pool: threadpool::ThreadPool,
+
+    /// Handle used to enter the runtime from other threads.
+    handle: ::runtime::Handle,
+
+    /// Timer driving the timeouts created from other threads.
+    shared_timer: SharedTimer,
+
+    /// How long the thread pool must stay idle before `shutdown_on_idle`
+    /// shuts the runtime down.
+    idle_grace_period: Option<Duration>,
+
+    /// Names the runtime is registered under, unregistered once the runtime
+    /// shuts down.
+    registration: Registration,
 }
 
 // ===== impl Runtime =====
@@ -200,6 +222,167 @@ impl Runtime {
         TaskExecutor { inner }
     }
 
+    /// Change the number of worker threads of the runtime.
+    ///
+    /// New workers start processing tasks right away. Retired workers finish
+    /// the task they are currently polling and hand the rest of their queued
+    /// tasks over to the remaining workers before stopping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Builder;
+    ///
+    /// let rt = Builder::new()
+    ///     .core_threads(1)
+    ///     .max_core_threads(4)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.resize(4);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `core_threads` is zero or greater than the
+    /// value set with [`Builder::max_core_threads`].
+    ///
+    /// [`Builder::max_core_threads`]: struct.Builder.html#method.max_core_threads
+    pub fn resize(&self, core_threads: usize) {
+        self.inner().pool.resize(core_threads);
+    }
+
+    /// Returns a snapshot of the usage of the runtime's blocking threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// let rt = Runtime::new().unwrap();
+    ///
+    /// let stats = rt.blocking_stats();
+    /// assert_eq!(stats.in_use(), 0);
+    /// ```
+    pub fn blocking_stats(&self) -> BlockingStats {
+        self.inner().pool.blocking_stats()
+    }
+
+    /// Set the runtime's resources as the defaults for the current thread for
+    /// the duration of the closure.
+    ///
+    /// This allows creating I/O resources and timeouts outside of the
+    /// runtime, to be used on the runtime later. I/O resources are driven by
+    /// the runtime's first worker thread, and timeouts by a timer shared by
+    /// all the threads that are not workers of the runtime. See
+    /// [`Handle::enter`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::runtime::Runtime;
+    /// use tokio::timer::Delay;
+    ///
+    /// use std::time::{Duration, Instant};
+    ///
+    /// # pub fn main() {
+    /// let mut rt = Runtime::new().unwrap();
+    ///
+    /// let delay = rt.enter(|| {
+    ///     Delay::new(Instant::now() + Duration::from_millis(10))
+    /// });
+    ///
+    /// rt.block_on(delay).unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it is called from within an execution context.
+    ///
+    /// [`Handle::enter`]: struct.Handle.html#method.enter
+    pub fn enter<F, R>(&self, f: F) -> R
+    where F: FnOnce() -> R,
+    {
+        self.inner().handle.enter(f)
+    }
+
+    /// Register the runtime under `name` in the process-level registry.
+    ///
+    /// Applications running several runtimes, for example one for I/O and one
+    /// for CPU bound work, can then obtain a handle to a specific runtime from
+    /// anywhere with [`Handle::named`], in order to spawn tasks onto it.
+    ///
+    /// A runtime may be registered under several names. The names are
+    /// unregistered as soon as the runtime starts shutting down, whether with
+    /// [`shutdown_on_idle`], [`shutdown_now`] or by being dropped, after which
+    /// they can be registered again. Handles obtained before that remain
+    /// valid, but spawning through them fails once the runtime has shut down.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a runtime is already registered under `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::runtime::{Handle, Runtime};
+    /// use tokio::prelude::*;
+    ///
+    /// # pub fn main() {
+    /// let rt = Runtime::new().unwrap();
+    /// rt.register("docs-io").unwrap();
+    ///
+    /// // Somewhere else in the application
+    /// Handle::named("docs-io")
+    ///     .unwrap()
+    ///     .spawn(future::lazy(|| {
+    ///         println!("running on the I/O runtime");
+    ///         Ok(())
+    ///     }))
+    ///     .unwrap();
+    ///
+    /// rt.shutdown_on_idle().wait().unwrap();
+    /// assert!(Handle::named("docs-io").is_none());
+    /// # }
+    /// ```
+    ///
+    /// [`Handle::named`]: struct.Handle.html#method.named
+    /// [`shutdown_on_idle`]: #method.shutdown_on_idle
+    /// [`shutdown_now`]: #method.shutdown_now
+    pub fn register<N>(&self, name: N) -> Result<(), RegisterError>
+    where N: Into<String>,
+    {
+        let inner = self.inner();
+        inner.registration.register(name.into(), &inner.handle)
+    }
+
+    /// Returns a snapshot of the scheduling counters of each of the runtime's
+    /// worker threads.
+    ///
+    /// See [`ThreadPool::worker_stats`] for more details.
+    ///
+    /// [`ThreadPool::worker_stats`]: https://docs.rs/tokio-threadpool/0.1/tokio_threadpool/struct.ThreadPool.html#method.worker_stats
+    pub fn worker_stats(&self) -> Vec<WorkerStats> {
+        self.inner().pool.worker_stats()
+    }
+
+    /// Returns a snapshot of the tasks that are live on the runtime.
+    ///
+    /// Tasks are only tracked when enabled with [`Builder::task_dump`],
+    /// otherwise the dump is always empty. See [`ThreadPool::dump`] for more
+    /// details.
+    ///
+    /// [`Builder::task_dump`]: struct.Builder.html#method.task_dump
+    /// [`ThreadPool::dump`]: https://docs.rs/tokio-threadpool/0.1/tokio_threadpool/struct.ThreadPool.html#method.dump
+    pub fn dump(&self) -> Dump {
+        self.inner().pool.dump()
+    }
+
     /// Spawn a future onto the Tokio runtime.
     ///
     /// This spawns the given future onto the runtime's executor, usually a
@@ -242,6 +425,85 @@ impl Runtime {
         self
     }
 
+    /// Spawn a future onto the Tokio runtime, giving the task a name.
+    ///
+    /// This behaves like [`spawn`], except that `name` is attached to the
+    /// spawned task. The name is included in the runtime's diagnostics about
+    /// the task, such as log and trace events emitted by the thread pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// # use futures::{future, Future, Stream};
+    /// use tokio::runtime::Runtime;
+    ///
+    /// # fn dox() {
+    /// let mut rt = Runtime::new().unwrap();
+    ///
+    /// rt.spawn_named("metrics reporter", future::lazy(|| {
+    ///     println!("reporting metrics");
+    ///     Ok(())
+    /// }));
+    /// # }
+    /// # pub fn main() {}
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`spawn`]: #method.spawn
+    pub fn spawn_named<N, F>(&mut self, name: N, future: F) -> &mut Self
+    where N: Into<String>,
+          F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner_mut().pool.sender().spawn_named(name, future).unwrap();
+        self
+    }
+
+    /// Spawn a future onto the Tokio runtime, recording where it was spawned
+    /// from.
+    ///
+    /// The location is reported by [`dump`]. This is usually called through
+    /// the `tokio_threadpool::spawn!` macro, which captures the location of
+    /// the call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`dump`]: #method.dump
+    pub fn spawn_at<F>(&mut self, location: SpawnLocation, future: F) -> &mut Self
+    where F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner_mut().pool.sender().spawn_at(location, future).unwrap();
+        self
+    }
+
+    /// Spawn a future onto the Tokio runtime, giving the task a name and
+    /// recording where it was spawned from.
+    ///
+    /// See [`spawn_named`] and [`spawn_at`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`spawn_named`]: #method.spawn_named
+    /// [`spawn_at`]: #method.spawn_at
+    pub fn spawn_named_at<N, F>(&mut self, location: SpawnLocation, name: N, future: F) -> &mut Self
+    where N: Into<String>,
+          F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner_mut().pool.sender().spawn_named_at(location, name, future).unwrap();
+        self
+    }
+
     /// Run a future to completion on the Tokio runtime.
     ///
     /// This runs the given future on the runtime, blocking until it is
@@ -311,6 +573,10 @@ impl Runtime {
     ///   spawned have completed.
     /// * The reactor is not managing any I/O resources.
     ///
+    /// If an idle grace period is set with [`Builder::idle_grace_period`], the
+    /// thread pool must stay without tasks for that long before the runtime
+    /// shuts down.
+    ///
     /// See [module level][mod] documentation for more details.
     ///
     /// # Examples
@@ -330,10 +596,10 @@ impl Runtime {
     /// ```
     ///
     /// [mod]: index.html
+    /// [`Builder::idle_grace_period`]: struct.Builder.html#method.idle_grace_period
     pub fn shutdown_on_idle(mut self) -> Shutdown {
         let inner = self.inner.take().unwrap();
-        let inner = inner.pool.shutdown_on_idle();
-        Shutdown { inner }
+        Shutdown::shutdown_on_idle(inner)
     }
 
     /// Signals the runtime to shutdown immediately.
@@ -0,0 +1,57 @@
+use reactor::{Handle, Reactor};
+
+use tokio_executor::park::Park;
+use tokio_threadpool::WorkerId;
+use tokio_timer::timer::{self, Timer};
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The default `Park` implementation used by the runtime's worker threads.
+///
+/// A `WorkerPark` drives the worker's timer, which in turn drives the worker's
+/// reactor. It is handed to the closure passed to [`Builder::custom_park`],
+/// which may wrap it in order to interpose on the worker going to sleep.
+///
+/// [`Builder::custom_park`]: struct.Builder.html#method.custom_park
+#[derive(Debug)]
+pub struct WorkerPark {
+    inner: Timer<Reactor>,
+}
+
+/// Creates the default `WorkerPark` of a worker.
+pub(super) type NewWorkerPark = Arc<Fn(&WorkerId) -> WorkerPark>;
+
+impl WorkerPark {
+    pub(super) fn new(inner: Timer<Reactor>) -> WorkerPark {
+        WorkerPark { inner }
+    }
+
+    /// Returns a handle to the worker's reactor.
+    pub fn reactor_handle(&self) -> Handle {
+        self.inner.get_park().handle()
+    }
+
+    /// Returns a handle to the worker's timer.
+    pub fn timer_handle(&self) -> timer::Handle {
+        self.inner.handle()
+    }
+}
+
+impl Park for WorkerPark {
+    type Unpark = <Timer<Reactor> as Park>::Unpark;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        self.inner.unpark()
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        self.inner.park()
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        self.inner.park_timeout(duration)
+    }
+}
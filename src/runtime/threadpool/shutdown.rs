@@ -1,19 +1,62 @@
+use super::timer::SharedTimer;
 use super::Inner;
 use tokio_threadpool as threadpool;
+use tokio_timer::Delay;
 
 use std::fmt;
+use std::time::{Duration, Instant};
 
-use futures::{Future, Poll};
+use futures::{Async, Future, Poll};
 
 /// A future that resolves when the Tokio `Runtime` is shut down.
 pub struct Shutdown {
-    pub(super) inner: threadpool::Shutdown,
+    state: State,
+
+    /// Kept running until the thread pool has shut down, as tasks may still be
+    /// waiting on timeouts created from other threads.
+    _shared_timer: SharedTimer,
+}
+
+enum State {
+    /// Waiting for the thread pool to stay idle for the grace period.
+    Grace {
+        pool: Option<threadpool::ThreadPool>,
+        grace: Duration,
+        delay: Delay,
+    },
+
+    /// Waiting for the thread pool to shut down.
+    Pool(threadpool::Shutdown),
 }
 
 impl Shutdown {
+    pub(super) fn shutdown_on_idle(inner: Inner) -> Self {
+        let state = match inner.idle_grace_period {
+            Some(grace) => {
+                // The grace period is tracked by the runtime's timer, the
+                // deadline is reset once the pool is known to be idle.
+                let delay = inner.shared_timer.handle().delay(Instant::now() + grace);
+
+                State::Grace {
+                    pool: Some(inner.pool),
+                    grace,
+                    delay,
+                }
+            }
+            None => State::Pool(inner.pool.shutdown_on_idle()),
+        };
+
+        Shutdown {
+            state,
+            _shared_timer: inner.shared_timer,
+        }
+    }
+
     pub(super) fn shutdown_now(inner: Inner) -> Self {
-        let inner = inner.pool.shutdown_now();
-        Shutdown { inner }
+        Shutdown {
+            state: State::Pool(inner.pool.shutdown_now()),
+            _shared_timer: inner.shared_timer,
+        }
     }
 }
 
@@ -22,8 +65,45 @@ impl Future for Shutdown {
     type Error = ();
 
     fn poll(&mut self) -> Poll<(), ()> {
-        try_ready!(self.inner.poll());
-        Ok(().into())
+        loop {
+            let shutdown = match self.state {
+                State::Grace {
+                    ref mut pool,
+                    grace,
+                    ref mut delay,
+                } => {
+                    let since = match pool.as_ref().unwrap().poll_idle() {
+                        Async::Ready(since) => since,
+                        Async::NotReady => return Ok(Async::NotReady),
+                    };
+
+                    if delay.deadline() != since + grace {
+                        delay.reset(since + grace);
+                    }
+
+                    // The shared timer outlives this future, so an error can
+                    // only mean it is gone and there is nothing to wait for.
+                    if let Ok(Async::NotReady) = delay.poll() {
+                        return Ok(Async::NotReady);
+                    }
+
+                    // Futures may have been spawned while the delay was
+                    // pending, restarting the grace period.
+                    match pool.as_ref().unwrap().poll_idle() {
+                        Async::Ready(now) if now == since => {}
+                        _ => continue,
+                    }
+
+                    pool.take().unwrap().shutdown_on_idle()
+                }
+                State::Pool(ref mut shutdown) => {
+                    try_ready!(shutdown.poll());
+                    return Ok(().into());
+                }
+            };
+
+            self.state = State::Pool(shutdown);
+        }
     }
 }
 
@@ -1,5 +1,5 @@
 
-use tokio_threadpool::Sender;
+use tokio_threadpool::{BlockingStats, Dump, Sender, SpawnLocation, WorkerStats};
 
 use futures::future::{self, Future};
 
@@ -56,6 +56,95 @@ impl TaskExecutor {
     {
         self.inner.spawn(future).unwrap();
     }
+
+    /// Spawn a future onto the Tokio runtime, giving the task a name.
+    ///
+    /// See [`Runtime::spawn_named`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`Runtime::spawn_named`]: struct.Runtime.html#method.spawn_named
+    pub fn spawn_named<N, F>(&self, name: N, future: F)
+    where N: Into<String>,
+          F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner.spawn_named(name, future).unwrap();
+    }
+
+    /// Spawn a future onto the Tokio runtime, recording where it was spawned
+    /// from.
+    ///
+    /// See [`Runtime::spawn_at`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`Runtime::spawn_at`]: struct.Runtime.html#method.spawn_at
+    pub fn spawn_at<F>(&self, location: SpawnLocation, future: F)
+    where F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner.spawn_at(location, future).unwrap();
+    }
+
+    /// Spawn a future onto the Tokio runtime, giving the task a name and
+    /// recording where it was spawned from.
+    ///
+    /// See [`Runtime::spawn_named_at`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Failure occurs if the executor
+    /// is currently at capacity and is unable to spawn a new future.
+    ///
+    /// [`Runtime::spawn_named_at`]: struct.Runtime.html#method.spawn_named_at
+    pub fn spawn_named_at<N, F>(&self, location: SpawnLocation, name: N, future: F)
+    where N: Into<String>,
+          F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.inner.spawn_named_at(location, name, future).unwrap();
+    }
+
+    /// Change the number of worker threads of the runtime.
+    ///
+    /// See [`Runtime::resize`] for more details.
+    ///
+    /// [`Runtime::resize`]: struct.Runtime.html#method.resize
+    pub fn resize(&self, core_threads: usize) {
+        self.inner.resize(core_threads);
+    }
+
+    /// Returns a snapshot of the usage of the runtime's blocking threads.
+    ///
+    /// See [`Runtime::blocking_stats`] for more details.
+    ///
+    /// [`Runtime::blocking_stats`]: struct.Runtime.html#method.blocking_stats
+    pub fn blocking_stats(&self) -> BlockingStats {
+        self.inner.blocking_stats()
+    }
+
+    /// Returns a snapshot of the scheduling counters of each of the runtime's
+    /// worker threads.
+    ///
+    /// See [`Runtime::worker_stats`] for more details.
+    ///
+    /// [`Runtime::worker_stats`]: struct.Runtime.html#method.worker_stats
+    pub fn worker_stats(&self) -> Vec<WorkerStats> {
+        self.inner.worker_stats()
+    }
+
+    /// Returns a snapshot of the tasks that are live on the runtime.
+    ///
+    /// See [`Runtime::dump`] for more details.
+    ///
+    /// [`Runtime::dump`]: struct.Runtime.html#method.dump
+    pub fn dump(&self) -> Dump {
+        self.inner.dump()
+    }
 }
 
 impl<T> future::Executor<T> for TaskExecutor
@@ -0,0 +1,175 @@
+use tokio_executor::park::{Park, Unpark};
+use tokio_threadpool::park::{DefaultPark, DefaultUnpark, ParkError};
+use tokio_timer::clock::Clock;
+use tokio_timer::timer::{self, Timer};
+
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Timer shared by the threads that are not workers of the runtime.
+///
+/// Each worker drives its own timer, so timeouts created by tasks are owned by
+/// the worker they are created on and never contend with other workers.
+/// Timeouts created from outside of the runtime, for example within
+/// [`Runtime::enter`], are registered with this timer instead. It is driven
+/// by a dedicated thread, which is only started once the first of these
+/// timeouts is registered, and runs until the runtime shuts down.
+///
+/// [`Runtime::enter`]: struct.Runtime.html#method.enter
+#[derive(Debug)]
+pub(super) struct SharedTimer {
+    /// Handle to the timer
+    handle: timer::Handle,
+
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+
+    /// Set to stop the timer thread
+    shutdown: AtomicBool,
+
+    /// Wakes up the timer thread
+    unpark: DefaultUnpark,
+}
+
+#[derive(Debug)]
+enum State {
+    /// No timeout was registered, so the timer is not driven yet.
+    Idle(Timer<TimerPark, Clock>),
+
+    /// The timer is driven by this thread.
+    Running(JoinHandle<()>),
+
+    /// The timer was dropped.
+    Done,
+}
+
+/// Parks the timer thread.
+#[derive(Debug)]
+struct TimerPark {
+    park: DefaultPark,
+    shared: Arc<Shared>,
+}
+
+/// Starts the timer thread if needed, then unparks it.
+///
+/// The timer unparks its thread whenever a timeout is registered from another
+/// thread.
+#[derive(Debug)]
+struct TimerUnpark {
+    shared: Arc<Shared>,
+}
+
+impl SharedTimer {
+    /// Create the timer, without starting its thread.
+    pub fn new(clock: &Clock) -> SharedTimer {
+        let park = DefaultPark::new();
+
+        let shared = Arc::new(Shared {
+            // Set to `Idle` once the timer is created.
+            state: Mutex::new(State::Done),
+            shutdown: AtomicBool::new(false),
+            unpark: park.unpark(),
+        });
+
+        let park = TimerPark {
+            park,
+            shared: shared.clone(),
+        };
+
+        let timer = Timer::new_with_now(park, clock.clone());
+        let handle = timer.handle();
+
+        *shared.state.lock().unwrap() = State::Idle(timer);
+
+        SharedTimer { handle, shared }
+    }
+
+    /// Returns a handle to the timer.
+    pub fn handle(&self) -> &timer::Handle {
+        &self.handle
+    }
+}
+
+impl Drop for SharedTimer {
+    fn drop(&mut self) {
+        let state = mem::replace(&mut *self.shared.state.lock().unwrap(), State::Done);
+
+        // Dropping the timer errors the timeouts that are still pending.
+        match state {
+            State::Idle(timer) => drop(timer),
+            State::Running(thread) => {
+                self.shared.shutdown.store(true, SeqCst);
+                self.shared.unpark.unpark();
+
+                let _ = thread.join();
+            }
+            State::Done => {}
+        }
+    }
+}
+
+impl Shared {
+    fn start(shared: &Arc<Shared>) {
+        let mut state = shared.state.lock().unwrap();
+
+        let mut timer = match mem::replace(&mut *state, State::Done) {
+            State::Idle(timer) => timer,
+            other => {
+                *state = other;
+                return;
+            }
+        };
+
+        let shared2 = shared.clone();
+
+        // If the thread cannot be spawned, the timer is dropped along with the
+        // closure, which errors the timeouts.
+        let res = thread::Builder::new()
+            .name("tokio-runtime-timer".to_string())
+            .spawn(move || {
+                while !shared2.shutdown.load(SeqCst) {
+                    if timer.turn(None).is_err() {
+                        break;
+                    }
+                }
+            });
+
+        if let Ok(thread) = res {
+            *state = State::Running(thread);
+        }
+    }
+}
+
+impl Park for TimerPark {
+    type Unpark = TimerUnpark;
+    type Error = ParkError;
+
+    fn unpark(&self) -> Self::Unpark {
+        TimerUnpark {
+            shared: self.shared.clone(),
+        }
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        self.park.park()
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        self.park.park_timeout(duration)
+    }
+}
+
+impl Unpark for TimerUnpark {
+    fn unpark(&self) {
+        Shared::start(&self.shared);
+        self.shared.unpark.unpark();
+    }
+}
@@ -82,7 +82,10 @@
 //! [Interval]: struct.Interval.html
 //! [`DelayQueue`]: struct.DelayQueue.html
 
-pub use tokio_timer::{delay_queue, timeout, Delay, DelayQueue, Error, Interval, Timeout};
+pub use tokio_timer::timer::Handle;
+pub use tokio_timer::{
+    delay_queue, timeout, Delay, DelayQueue, Error, Interval, MissedTickBehavior, Timeout,
+};
 
 #[deprecated(since = "0.1.8", note = "use Timeout instead")]
 #[allow(deprecated)]
@@ -62,3 +62,77 @@ fn clock_and_timer_single_threaded() {
     })
     .unwrap();
 }
+
+#[test]
+fn paused_time_auto_advances() {
+    let _ = env_logger::try_init();
+
+    let mut rt = current_thread::Builder::new()
+        .enable_pause(true)
+        .build()
+        .unwrap();
+
+    let real = Instant::now();
+
+    let elapsed = rt
+        .block_on(future::lazy(|| {
+            tokio::clock::pause();
+
+            let start = Handle::current().now();
+            let when = start + Duration::from_secs(3600);
+
+            Delay::new(when).map(move |_| Handle::current().now() - start)
+        }))
+        .unwrap();
+
+    assert!(elapsed >= Duration::from_secs(3600));
+    assert!(real.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn advance_paused_time() {
+    use std::thread;
+
+    let _ = env_logger::try_init();
+
+    let mut rt = current_thread::Builder::new()
+        .enable_pause(true)
+        .build()
+        .unwrap();
+
+    rt.block_on(future::lazy(|| {
+        tokio::clock::pause();
+
+        let start = Handle::current().now();
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(Handle::current().now(), start);
+
+        let mut delay = Delay::new(start + Duration::from_millis(100));
+        assert!(delay.poll().unwrap().is_not_ready());
+
+        tokio::clock::advance(Duration::from_millis(100));
+        assert_eq!(Handle::current().now(), start + Duration::from_millis(100));
+
+        delay.map(move |_| {
+            let paused = Handle::current().now();
+            assert!(paused - start < Duration::from_millis(110));
+
+            tokio::clock::resume();
+            thread::sleep(Duration::from_millis(10));
+            assert!(Handle::current().now() > paused);
+        })
+    }))
+    .unwrap();
+}
+
+#[test]
+#[should_panic]
+fn pause_requires_enable_pause() {
+    let mut rt = current_thread::Runtime::new().unwrap();
+
+    rt.block_on(future::lazy(|| {
+        tokio::clock::pause();
+        Ok::<_, ()>(())
+    }))
+    .unwrap();
+}
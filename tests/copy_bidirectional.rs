@@ -0,0 +1,65 @@
+extern crate env_logger;
+extern crate futures;
+extern crate tokio;
+
+use std::io::{Read, Write};
+use std::net::{self, Shutdown};
+use std::thread;
+
+use futures::Future;
+use tokio::io::copy_bidirectional;
+use tokio::net::TcpStream;
+use tokio::reactor::Handle;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+#[test]
+fn proxy_half_close() {
+    drop(env_logger::try_init());
+
+    let front = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let front_addr = t!(front.local_addr());
+    let back = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let back_addr = t!(back.local_addr());
+
+    let client = thread::spawn(move || {
+        let mut s = t!(net::TcpStream::connect(&front_addr));
+        t!(s.write_all(b"ping"));
+        t!(s.shutdown(Shutdown::Write));
+
+        let mut buf = vec![];
+        t!(s.read_to_end(&mut buf));
+        buf
+    });
+
+    let server = thread::spawn(move || {
+        let mut s = t!(back.accept()).0;
+
+        // Only completes if the proxy forwards the EOF of the client
+        let mut buf = vec![];
+        t!(s.read_to_end(&mut buf));
+
+        t!(s.write_all(b"pong"));
+        buf
+    });
+
+    let a = t!(front.accept()).0;
+    let b = t!(net::TcpStream::connect(&back_addr));
+
+    let a = t!(TcpStream::from_std(a, &Handle::default()));
+    let b = t!(TcpStream::from_std(b, &Handle::default()));
+
+    let (a_to_b, b_to_a, _, _) = t!(copy_bidirectional(a, b).wait());
+
+    assert_eq!(a_to_b, 4);
+    assert_eq!(b_to_a, 4);
+    assert_eq!(t!(server.join()), b"ping");
+    assert_eq!(t!(client.join()), b"pong");
+}
@@ -0,0 +1,88 @@
+extern crate env_logger;
+extern crate futures;
+extern crate tokio;
+
+use std::io;
+use std::net::{self, SocketAddr};
+use std::thread;
+
+use futures::future;
+use tokio::net::dns::{self, Resolve, Resolving};
+use tokio::net::tcp;
+use tokio::runtime::Runtime;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+#[test]
+fn lookup_host() {
+    drop(env_logger::try_init());
+    let mut rt = t!(Runtime::new());
+
+    let addrs = t!(rt.block_on(dns::lookup_host("127.0.0.1:80")));
+    assert_eq!(addrs, vec![t!("127.0.0.1:80".parse())]);
+
+    let addrs = t!(rt.block_on(dns::lookup_host(("::1", 80))));
+    assert_eq!(addrs, vec![t!("[::1]:80".parse())]);
+
+    let addrs = t!(rt.block_on(dns::lookup_host("localhost:80")));
+    assert!(addrs
+        .iter()
+        .all(|addr| addr.ip().is_loopback() && addr.port() == 80));
+
+    let err = rt.block_on(dns::lookup_host("localhost")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn lookup_host_unbracketed_ipv6() {
+    drop(env_logger::try_init());
+    let mut rt = t!(Runtime::new());
+
+    let addrs = t!(rt.block_on(dns::lookup_host("[::1]:80")));
+    assert_eq!(addrs, vec![t!("[::1]:80".parse())]);
+
+    let err = rt.block_on(dns::lookup_host("::1:80")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+struct StaticResolver(SocketAddr);
+
+impl Resolve for StaticResolver {
+    fn resolve(&self, host: &str, port: u16) -> Resolving {
+        if host == "tokio.test" {
+            Resolving::ready(vec![SocketAddr::new(self.0.ip(), port)])
+        } else {
+            Resolving::new(future::err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "unknown host",
+            )))
+        }
+    }
+}
+
+#[test]
+fn connect_with_resolver() {
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || t!(srv.accept()).0);
+
+    let mut rt = t!(Runtime::new());
+    let resolver = StaticResolver(addr);
+
+    let host = format!("tokio.test:{}", addr.port());
+    let mine = t!(rt.block_on(tcp::connect_with(&host, &resolver)));
+    let theirs = t.join().unwrap();
+    assert_eq!(t!(mine.local_addr()), t!(theirs.peer_addr()));
+
+    let connect = tcp::connect_with(("unknown.test", addr.port()), &resolver);
+    let err = rt.block_on(connect).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
@@ -229,6 +229,70 @@ fn read_max_frame_len() {
     assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
 }
 
+#[test]
+fn read_max_frame_len_error() {
+    let mut io = length_delimited::Builder::new()
+        .max_frame_length(5)
+        .new_read(mock! {
+            Ok(b"\x00\x00\x00\x09abcdefghi"[..].into()),
+        });
+
+    let err = io.poll().unwrap_err();
+    let err = err
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<length_delimited::FrameTooBig>()
+        .unwrap();
+    assert_eq!(err.frame_length(), 9);
+    assert_eq!(err.max_frame_length(), 5);
+}
+
+#[test]
+fn read_discard_oversized_frames() {
+    let mut io = length_delimited::Builder::new()
+        .max_frame_length(5)
+        .discard_oversized_frames(true)
+        .new_read(mock! {
+            Ok(b"\x00\x00\x00\x09abcd"[..].into()),
+            Ok(b"efghi\x00\x00\x00\x03123"[..].into()),
+            Ok(b"\x00\x00\x00\x06abcdef"[..].into()),
+            Ok(b"\x00\x00\x00\x0512345"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"123"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"12345"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_skip_oversized_frames() {
+    let mut io = length_delimited::Builder::new()
+        .max_frame_length(5)
+        .new_read(mock! {
+            Ok(b"\x00\x00\x00\x09abcd"[..].into()),
+            Ok(b"efghi\x00\x00\x00\x03123"[..].into()),
+        });
+    io.set_decode_recovery(DecodeRecovery::Skip);
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"123"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_length_field_mask() {
+    let mut io = length_delimited::Builder::new()
+        .length_field_mask(0x7fff_ffff)
+        .length_adjustment(-4)
+        .new_read(mock! {
+            Ok(b"\x80\x00\x00\x07abc"[..].into()),
+            Ok(b"\x00\x00\x00\x06de"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abc"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"de"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
 #[test]
 fn read_update_max_frame_len_at_rest() {
     let mut io = length_delimited::Builder::new().new_read(mock! {
@@ -523,6 +587,25 @@ fn write_update_max_frame_len_in_flight() {
     assert!(io.get_ref().calls.is_empty());
 }
 
+#[test]
+fn write_length_field_mask() {
+    let mut io = length_delimited::Builder::new()
+        .length_field_length(1)
+        .length_field_mask(0x0f)
+        .new_write(mock! {
+            Ok(b"\x03abc"[..].into()),
+            Ok(Flush),
+        });
+
+    assert!(io.start_send(Bytes::from("abc")).unwrap().is_ready());
+    assert_eq!(
+        io.start_send(Bytes::from(vec![0; 16])).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert!(io.poll_complete().unwrap().is_ready());
+    assert!(io.get_ref().calls.is_empty());
+}
+
 #[test]
 fn write_zero() {
     let mut io = length_delimited::Builder::new().new_write(mock! {});
@@ -549,6 +632,127 @@ fn encode_overflow() {
     codec.encode(Bytes::from("hello"), &mut buf).unwrap();
 }
 
+#[test]
+fn read_varint_frames() {
+    let mut data: Vec<u8> = vec![];
+    data.extend_from_slice(b"\x09abcdefghi");
+    data.extend_from_slice(b"\x00");
+    data.extend_from_slice(b"\xac\x02");
+    data.extend_from_slice(&[b'a'; 300][..]);
+
+    let mut io = length_delimited::Builder::new().varint().new_read(mock! {
+        Ok(data[..12].into()),
+        Ok(data[12..].into()),
+    });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abcdefghi"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(b""[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(Some(vec![b'a'; 300][..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_varint_with_offset() {
+    let mut io = length_delimited::Builder::new()
+        .varint()
+        .length_field_offset(1)
+        .new_read(mock! {
+            Ok(b"\xca\x03abc"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap(), Ready(Some(b"abc"[..].into())));
+    assert_eq!(io.poll().unwrap(), Ready(None));
+}
+
+#[test]
+fn read_varint_overflow() {
+    let mut io = length_delimited::Builder::new().varint().new_read(mock! {
+        Ok(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..].into()),
+    });
+
+    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_varint_max_frame_len() {
+    let mut io = length_delimited::Builder::new()
+        .varint()
+        .max_frame_length(100)
+        .new_read(mock! {
+            Ok(b"\xac\x02"[..].into()),
+        });
+
+    assert_eq!(io.poll().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn write_varint_frames() {
+    let mut io = length_delimited::Builder::new().varint().new_write(mock! {
+        Ok(b"\x09abcdefghi"[..].into()),
+        Ok(b"\xac\x02"[..].into()),
+        Ok(vec![b'a'; 300].into()),
+        Ok(Flush),
+    });
+
+    assert!(io.start_send(Bytes::from("abcdefghi")).unwrap().is_ready());
+    assert!(io
+        .start_send(Bytes::from(vec![b'a'; 300]))
+        .unwrap()
+        .is_ready());
+    assert!(io.poll_complete().unwrap().is_ready());
+    assert!(io.get_ref().calls.is_empty());
+}
+
+#[test]
+fn custom_length_codec() {
+    // Length field of one byte, counting in units configured at runtime
+    #[derive(Debug)]
+    struct Scaled {
+        unit: u64,
+    }
+
+    impl length_delimited::LengthCodec for Scaled {
+        fn decode(&self, src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+            Ok(src.first().map(|b| (u64::from(*b) * self.unit, 1)))
+        }
+
+        fn encode(&self, len: u64, dst: &mut BytesMut) -> io::Result<()> {
+            dst.reserve(1);
+            dst.put_u8((len / self.unit) as u8);
+            Ok(())
+        }
+    }
+
+    let unit = "2".parse().unwrap();
+    let mut codec = length_delimited::Builder::new().new_codec();
+    codec.set_length_codec(Scaled { unit });
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("abcd"), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"\x02abcd");
+
+    buf.extend_from_slice(b"\x01ef");
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"abcd"[..].into()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"ef"[..].into()));
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+}
+
+#[test]
+fn builder_is_copy() {
+    let builder = *length_delimited::Builder::new().varint();
+    let mut buf = BytesMut::new();
+
+    builder
+        .new_codec()
+        .encode(Bytes::from("a"), &mut buf)
+        .unwrap();
+    builder
+        .new_codec()
+        .encode(Bytes::from("b"), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..], b"\x01a\x01b");
+}
+
 // ===== Test utils =====
 
 fn would_block() -> io::Error {
@@ -0,0 +1,106 @@
+extern crate env_logger;
+extern crate futures;
+extern crate tokio;
+
+use futures::{Future, Stream};
+use tokio::io::{self, AsyncRead};
+use tokio::net::listener::{Connection, Listener};
+use tokio::net::TcpListener;
+use tokio::runtime::current_thread::Runtime;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+/// Accepts one connection on `listener` and echoes a message sent by the
+/// client returned by `connect`.
+fn echo_once<L, C, F>(listener: L, connect: F)
+where
+    L: Listener + 'static,
+    L::Connection: 'static,
+    C: Connection + 'static,
+    F: Future<Item = C, Error = io::Error>,
+{
+    let mut rt = t!(Runtime::new());
+
+    let server = listener
+        .incoming()
+        .into_future()
+        .map_err(|(e, _)| e)
+        .and_then(|(accepted, _)| {
+            let (conn, peer) = accepted.unwrap();
+            assert_eq!(format!("{:?}", t!(conn.peer_addr())), format!("{:?}", peer));
+            let (reader, writer) = conn.split();
+            io::copy(reader, writer)
+        });
+    rt.spawn(server.map(|_| ()).map_err(|e| panic!("err={:?}", e)));
+
+    let client = t!(rt.block_on(connect));
+    let (client, _) = t!(rt.block_on(io::write_all(client, b"hello")));
+    let (_, buf) = t!(rt.block_on(io::read_exact(client, [0; 5])));
+    assert_eq!(&buf, b"hello");
+}
+
+#[test]
+fn tcp_listener() {
+    drop(env_logger::try_init());
+
+    let listener = t!(TcpListener::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(Listener::local_addr(&listener));
+    echo_once(listener, tokio::net::TcpStream::connect(&addr));
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_listener() {
+    use tokio::net::{UnixListener, UnixStream};
+
+    drop(env_logger::try_init());
+
+    let path = std::env::temp_dir().join(format!("tokio-listener-{}.sock", std::process::id()));
+    drop(std::fs::remove_file(&path));
+
+    let listener = t!(UnixListener::bind(&path));
+    echo_once(listener, UnixStream::connect(&path));
+
+    drop(std::fs::remove_file(&path));
+}
+
+#[test]
+fn incoming_limited() {
+    use futures::future;
+
+    drop(env_logger::try_init());
+    let mut rt = t!(Runtime::new());
+
+    let listener = t!(TcpListener::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(Listener::local_addr(&listener));
+    let mut incoming = listener.incoming_limited(1);
+    assert_eq!(incoming.max_connections(), 1);
+
+    let _first = t!(std::net::TcpStream::connect(&addr));
+    let _second = t!(std::net::TcpStream::connect(&addr));
+
+    let (first, mut incoming) = t!(rt.block_on(incoming.into_future().map_err(|(e, _)| e)));
+    let (first, peer) = first.unwrap();
+    assert_eq!(t!(first.peer_addr()), peer);
+    assert_eq!(incoming.active_connections(), 1);
+
+    // The limit is reached, the second connection has to wait
+    let mut incoming = t!(rt.block_on(future::lazy(move || {
+        assert!(t!(incoming.poll()).is_not_ready());
+        Ok::<_, ()>(incoming)
+    })));
+
+    drop(first);
+    assert_eq!(incoming.active_connections(), 0);
+
+    let (second, incoming) = t!(rt.block_on(incoming.into_future().map_err(|(e, _)| e)));
+    assert!(second.is_some());
+    assert_eq!(incoming.active_connections(), 1);
+}
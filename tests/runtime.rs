@@ -1,6 +1,9 @@
 extern crate env_logger;
+#[macro_use]
 extern crate futures;
 extern crate tokio;
+#[macro_use]
+extern crate tokio_threadpool;
 
 use futures::sync::oneshot;
 use std::sync::{atomic, Arc, Mutex};
@@ -79,6 +82,65 @@ fn runtime_single_threaded_block_on() {
     tokio::runtime::current_thread::block_on_all(create_client_server_future()).unwrap();
 }
 
+#[test]
+fn runtime_single_threaded_driver_layers() {
+    use std::time::{Duration, Instant};
+    use tokio::runtime::current_thread::Builder;
+    use tokio::timer::Delay;
+
+    let _ = env_logger::try_init();
+
+    let mut runtime = Builder::new().enable_time(false).build().unwrap();
+    assert!(runtime.driver().reactor_handle().is_some());
+    assert!(runtime.driver().timer_handle().is_none());
+
+    // Without a timer, delays fail
+    let res = runtime.block_on(Delay::new(Instant::now() + Duration::from_millis(10)));
+    assert!(res.unwrap_err().is_shutdown());
+
+    // Without a reactor, the executor still parks and runs timeouts
+    let mut runtime = Builder::new().enable_io(false).build().unwrap();
+    assert!(runtime.driver().reactor_handle().is_none());
+
+    runtime
+        .block_on(Delay::new(Instant::now() + Duration::from_millis(10)))
+        .unwrap();
+}
+
+#[test]
+fn runtime_single_threaded_run_until() {
+    use futures::sync::oneshot;
+    use std::panic::{self, AssertUnwindSafe};
+
+    let _ = env_logger::try_init();
+
+    let mut runtime = tokio::runtime::current_thread::Runtime::new().unwrap();
+
+    // Spawned tasks are driven while waiting on a future borrowing locals
+    let (tx, rx) = oneshot::channel();
+    runtime.spawn(future::lazy(move || {
+        tx.send(1).unwrap();
+        Ok(())
+    }));
+
+    let offset = 41;
+    let v = runtime.run_until(rx.map(|v| v + offset)).unwrap();
+    assert_eq!(v, 42);
+
+    // Errors from the future are returned as the inner error
+    let err = runtime.run_until(future::err::<(), _>("boom")).unwrap_err();
+    assert!(err.is_inner());
+    assert_eq!(err.into_inner(), Some("boom"));
+
+    // Panics in spawned tasks reach the caller
+    runtime.spawn(future::lazy(|| -> Result<(), ()> { panic!("spawned") }));
+
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        runtime.block_on(future::empty::<(), ()>())
+    }));
+    assert!(res.is_err());
+}
+
 mod runtime_single_threaded_block_on_all {
     use super::*;
 
@@ -530,3 +592,474 @@ fn after_start_and_before_stop_is_called() {
     assert!(after_start.load(atomic::Ordering::Relaxed) > 0);
     assert!(before_stop.load(atomic::Ordering::Relaxed) > 0);
 }
+
+#[test]
+fn resize_runtime() {
+    use std::collections::HashSet;
+    use std::time::{Duration, Instant};
+
+    let _ = env_logger::try_init();
+
+    let started = Arc::new(Mutex::new(HashSet::new()));
+    let started_inner = started.clone();
+
+    let runtime = tokio::runtime::Builder::new()
+        .core_threads(1)
+        .max_core_threads(3)
+        .on_worker_start(move |cx| {
+            started_inner.lock().unwrap().insert(cx.worker_id().to_usize());
+        })
+        .build()
+        .unwrap();
+
+    runtime.executor().resize(3);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    // Worker 0 is only started once there is work for it.
+    let expected: HashSet<_> = [1, 2].iter().cloned().collect();
+
+    while !started.lock().unwrap().is_superset(&expected) {
+        assert!(Instant::now() < deadline, "workers were not started");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    // The new workers have a reactor and a timer
+    runtime.block_on_all(create_client_server_future()).unwrap();
+}
+
+#[test]
+fn handle_current() {
+    use std::sync::mpsc;
+    use tokio::runtime::Handle;
+
+    let _ = env_logger::try_init();
+
+    let err = Handle::try_current().unwrap_err();
+    assert_eq!(err.to_string(), "not currently running inside a Tokio runtime");
+
+    let (tx, rx) = mpsc::channel();
+
+    let mut runtime = Runtime::new().unwrap();
+    runtime.spawn(future::lazy(move || {
+        let handle = Handle::current();
+        assert!(handle.reactor().is_some());
+        assert!(handle.timer().is_some());
+
+        // The handle can spawn from outside of the runtime
+        thread::spawn(move || {
+            handle.spawn(future::lazy(move || {
+                tx.send(()).unwrap();
+                Ok(())
+            })).unwrap();
+        });
+
+        Ok(())
+    }));
+
+    rx.recv().unwrap();
+    runtime.shutdown_on_idle().wait().unwrap();
+
+    let mut runtime = tokio::runtime::current_thread::Runtime::new().unwrap();
+    runtime.block_on(future::lazy(|| {
+        let handle = Handle::current();
+        handle.spawn(future::lazy(|| Ok(()))).unwrap();
+        Ok::<_, ()>(())
+    })).unwrap();
+    runtime.run().unwrap();
+
+    assert!(Handle::try_current().is_err());
+}
+
+#[test]
+fn runtime_pool_is_freed() {
+    use std::time::Duration;
+    use tokio::runtime::Handle;
+
+    let _ = env_logger::try_init();
+
+    // The pool's configuration holds the callback, which is released along
+    // with the pool.
+    let marker = Arc::new(());
+    let marker2 = marker.clone();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .after_start(move || {
+            let _ = &marker2;
+        })
+        .build()
+        .unwrap();
+
+    runtime.block_on(future::lazy(|| {
+        Handle::current();
+        Ok::<_, ()>(())
+    })).unwrap();
+
+    runtime.shutdown_on_idle().wait().unwrap();
+
+    // The worker threads may still be releasing the pool.
+    for _ in 0..100 {
+        if Arc::strong_count(&marker) == 1 {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    panic!("the pool was not freed");
+}
+
+#[test]
+fn shutdown_waits_for_idle_grace_period() {
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    let _ = env_logger::try_init();
+
+    let grace = Duration::from_millis(200);
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .core_threads(2)
+        .idle_grace_period(Some(grace))
+        .build()
+        .unwrap();
+
+    let executor = runtime.executor();
+    let (tx, rx) = mpsc::channel();
+
+    let tx2 = tx.clone();
+    runtime.spawn(lazy(move || {
+        tx2.send(()).unwrap();
+        Ok(())
+    }));
+
+    rx.recv().unwrap();
+
+    let shutdown = runtime.shutdown_on_idle();
+
+    // The pool went idle, but keeps accepting tasks during the grace period
+    thread::sleep(Duration::from_millis(50));
+
+    executor.spawn(lazy(move || {
+        tx.send(()).unwrap();
+        Ok(())
+    }));
+
+    rx.recv().unwrap();
+    let idle = Instant::now();
+
+    shutdown.wait().unwrap();
+    assert!(idle.elapsed() >= grace);
+
+    assert!(executor.execute(lazy(|| Ok(()))).is_err());
+}
+
+#[test]
+fn blocking_fail_fast_runtime() {
+    use futures::future::poll_fn;
+    use std::sync::mpsc;
+    use tokio::runtime::BlockingPolicy;
+    use tokio_threadpool::blocking;
+
+    let _ = env_logger::try_init();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .core_threads(2)
+        .blocking_threads(1)
+        .blocking_policy(BlockingPolicy::FailFast)
+        .build()
+        .unwrap();
+
+    let (started_tx, started_rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+    let unblock_rx = Mutex::new(unblock_rx);
+
+    runtime.spawn(poll_fn(move || {
+        try_ready!(blocking(|| {
+            started_tx.send(()).unwrap();
+            unblock_rx.lock().unwrap().recv().unwrap();
+        }).map_err(|_| panic!()));
+
+        Ok(().into())
+    }));
+
+    started_rx.recv().unwrap();
+    assert_eq!(runtime.blocking_stats().in_use(), 1);
+
+    let res = runtime
+        .block_on(poll_fn(|| Ok::<_, ()>(Async::Ready(blocking(|| ())))))
+        .unwrap();
+
+    assert!(res.unwrap_err().is_at_capacity());
+
+    unblock_tx.send(()).unwrap();
+    runtime.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn custom_park() {
+    use std::time::{Duration, Instant};
+    use tokio::executor::park::Park;
+    use tokio::runtime::WorkerPark;
+    use tokio::timer::Delay;
+
+    struct Counting {
+        inner: WorkerPark,
+        parks: Arc<atomic::AtomicUsize>,
+    }
+
+    impl Park for Counting {
+        type Unpark = <WorkerPark as Park>::Unpark;
+        type Error = io::Error;
+
+        fn unpark(&self) -> Self::Unpark {
+            self.inner.unpark()
+        }
+
+        fn park(&mut self) -> io::Result<()> {
+            self.parks.fetch_add(1, atomic::Ordering::SeqCst);
+            self.inner.park()
+        }
+
+        fn park_timeout(&mut self, duration: Duration) -> io::Result<()> {
+            self.parks.fetch_add(1, atomic::Ordering::SeqCst);
+            self.inner.park_timeout(duration)
+        }
+    }
+
+    let _ = env_logger::try_init();
+
+    let parks = Arc::new(atomic::AtomicUsize::new(0));
+    let parks2 = parks.clone();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .core_threads(2)
+        .custom_park(move |_, inner| Counting {
+            inner,
+            parks: parks2.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let when = Instant::now() + Duration::from_millis(20);
+    runtime.block_on(Delay::new(when)).unwrap();
+
+    runtime.shutdown_on_idle().wait().unwrap();
+
+    assert!(parks.load(atomic::Ordering::SeqCst) > 0);
+}
+
+#[test]
+fn runtime_task_dump() {
+    use std::time::{Duration, Instant};
+    use tokio::runtime::TaskState;
+
+    let _ = env_logger::try_init();
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .task_dump(true)
+        .build()
+        .unwrap();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    spawn!(runtime, "waiting", rx.map_err(|_| ()));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    let task = loop {
+        let dump = runtime.dump();
+
+        if let Some(task) = dump.tasks().iter().find(|task| task.polls() > 0) {
+            break task.clone();
+        }
+
+        assert!(Instant::now() < deadline, "task was never polled");
+        thread::yield_now();
+    };
+
+    assert_eq!(task.name(), Some("waiting"));
+    assert_eq!(task.state(), TaskState::Idle);
+    assert!(task.location().unwrap().file().ends_with("runtime.rs"));
+
+    tx.send(()).unwrap();
+    runtime.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn enter_runtime_from_other_thread() {
+    use std::time::{Duration, Instant};
+    use tokio::runtime::Handle;
+    use tokio::timer::Delay;
+
+    let _ = env_logger::try_init();
+
+    let mut runtime = Runtime::new().unwrap();
+
+    // Resources created by the main thread
+    let listener = runtime.enter(|| {
+        TcpListener::bind(&"127.0.0.1:0".parse().unwrap()).unwrap()
+    });
+    let addr = listener.local_addr().unwrap();
+
+    let handle = runtime
+        .block_on(lazy(|| Ok::<_, ()>(Handle::current())))
+        .unwrap();
+
+    // Resources created by another thread
+    let (delay, rx) = thread::spawn(move || {
+        handle.enter(|| {
+            assert!(Handle::try_current().is_ok());
+
+            let (tx, rx) = oneshot::channel();
+            tokio::spawn(lazy(move || {
+                tx.send(()).unwrap();
+                Ok(())
+            }));
+
+            (Delay::new(Instant::now() + Duration::from_millis(10)), rx)
+        })
+    }).join().unwrap();
+
+    runtime.spawn({
+        listener
+            .incoming()
+            .take(1)
+            .for_each(|_| Ok(()))
+            .map_err(|e| panic!("{}", e))
+    });
+
+    runtime.block_on(TcpStream::connect(&addr)).unwrap();
+    runtime.block_on(delay).unwrap();
+    runtime.block_on(rx).unwrap();
+
+    runtime.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn delay_created_outside_of_runtime() {
+    use std::time::{Duration, Instant};
+    use tokio::runtime::Handle;
+
+    let _ = env_logger::try_init();
+
+    let runtime = Runtime::new().unwrap();
+
+    let delay = |duration| {
+        runtime.enter(|| {
+            let timer = Handle::current().timer().unwrap().clone();
+            timer.delay(Instant::now() + duration)
+        })
+    };
+
+    // Driven by the shared timer, without any task running on the workers
+    delay(Duration::from_millis(10)).wait().unwrap();
+
+    let delay = delay(Duration::from_secs(60));
+
+    runtime.shutdown_now().wait().unwrap();
+
+    // The shared timer stops with the runtime
+    assert!(delay.wait().is_err());
+}
+
+mod budget {
+    use super::*;
+
+    use futures::future::poll_fn;
+    use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+    use tokio::runtime::current_thread;
+    use tokio::sync::mpsc;
+
+    // Returns a task that loops over an always-ready channel until `done` is
+    // set by another task, along with that flag.
+    fn greedy_task() -> (Arc<AtomicBool>, Box<Future<Item = (), Error = ()> + Send>) {
+        let done = Arc::new(AtomicBool::new(false));
+        let (mut tx, mut rx) = mpsc::unbounded_channel();
+
+        let done2 = done.clone();
+        let task = poll_fn(move || loop {
+            if done2.load(SeqCst) {
+                return Ok(().into());
+            }
+
+            tx.try_send(()).unwrap();
+            try_ready!(rx.poll().map_err(|_| ()));
+        });
+
+        (done, Box::new(task))
+    }
+
+    #[test]
+    fn current_thread_greedy_task_yields() {
+        let mut rt = current_thread::Runtime::new().unwrap();
+
+        let (done, task) = greedy_task();
+        rt.spawn(task);
+        rt.spawn(lazy(move || {
+            done.store(true, SeqCst);
+            Ok(())
+        }));
+
+        rt.run().unwrap();
+    }
+
+    #[test]
+    fn threadpool_greedy_task_yields() {
+        let mut rt = tokio::runtime::Builder::new()
+            .core_threads(1)
+            .build()
+            .unwrap();
+
+        let (done, task) = greedy_task();
+        rt.spawn(task);
+        rt.spawn(lazy(move || {
+            done.store(true, SeqCst);
+            Ok(())
+        }));
+
+        rt.shutdown_on_idle().wait().unwrap();
+    }
+}
+
+#[test]
+fn named_runtime_registry() {
+    use tokio::runtime::Handle;
+
+    let _ = env_logger::try_init();
+
+    let io = Runtime::new().unwrap();
+    let cpu = Runtime::new().unwrap();
+
+    assert!(Handle::named("test-io").is_none());
+
+    io.register("test-io").unwrap();
+    cpu.register("test-cpu").unwrap();
+
+    let err = cpu.register("test-io").unwrap_err();
+    assert_eq!(err.name(), "test-io");
+
+    let (tx, rx) = oneshot::channel();
+
+    Handle::named("test-io")
+        .unwrap()
+        .spawn(lazy(move || {
+            tx.send(Handle::named("test-cpu").is_some()).unwrap();
+            Ok(())
+        }))
+        .unwrap();
+
+    assert!(rx.wait().unwrap());
+
+    let handle = Handle::named("test-io").unwrap();
+    io.shutdown_now().wait().unwrap();
+
+    // The name is released on shutdown
+    assert!(Handle::named("test-io").is_none());
+    assert!(handle.spawn(lazy(|| Ok(()))).is_err());
+
+    cpu.register("test-io").unwrap();
+    assert!(Handle::named("test-io").is_some());
+
+    drop(cpu);
+    assert!(Handle::named("test-io").is_none());
+    assert!(Handle::named("test-cpu").is_none());
+}
@@ -0,0 +1,132 @@
+extern crate futures;
+extern crate tokio;
+
+use tokio::prelude::*;
+use tokio::runtime::simulation::Runtime;
+use tokio::timer::{Delay, Handle};
+
+use futures::sync::oneshot;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+fn interleaving(seed: u64) -> Vec<(usize, usize)> {
+    let log = Arc::new(Mutex::new(vec![]));
+    let mut rt = Runtime::new(seed);
+
+    for task in 0..5 {
+        let log = log.clone();
+        let mut step = 0;
+
+        rt.spawn(future::poll_fn(move || {
+            log.lock().unwrap().push((task, step));
+            step += 1;
+
+            if step == 3 {
+                return Ok(().into());
+            }
+
+            // Yield back to the scheduler
+            task::current().notify();
+            Ok(Async::NotReady)
+        }));
+    }
+
+    rt.run();
+
+    let log = log.lock().unwrap().clone();
+    log
+}
+
+#[test]
+fn same_seed_same_interleaving() {
+    let a = interleaving(42);
+    assert_eq!(a.len(), 15);
+    assert_eq!(a, interleaving(42));
+
+    // Some seed must produce a different interleaving
+    assert!((0..20).any(|seed| interleaving(seed) != a));
+}
+
+#[test]
+fn virtual_time() {
+    let mut rt = Runtime::new(0);
+    let start = rt.now();
+    let real = Instant::now();
+
+    rt.block_on(future::lazy(|| {
+        let when = Handle::current().now() + Duration::from_secs(3600);
+        Delay::new(when)
+    })).unwrap();
+
+    assert!(rt.now() - start >= Duration::from_secs(3600));
+    assert!(real.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn spawn_from_task() {
+    let mut rt = Runtime::new(1);
+
+    let res = rt.block_on(future::lazy(|| {
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(future::lazy(move || {
+            tx.send(7).unwrap();
+            Ok(())
+        }));
+
+        rx
+    })).unwrap();
+
+    assert_eq!(res, 7);
+}
+
+#[test]
+fn step_tasks_manually() {
+    let mut rt = Runtime::new(0);
+
+    let (tx, rx) = oneshot::channel::<()>();
+
+    let waiting = rt.spawn(rx.map_err(|_| ()));
+    let sending = rt.spawn(future::lazy(move || {
+        tx.send(()).unwrap();
+        Ok(())
+    }));
+
+    assert_eq!(rt.ready_tasks(), vec![waiting, sending]);
+
+    assert!(!rt.step(waiting));
+    assert_eq!(rt.ready_tasks(), vec![sending]);
+
+    assert!(rt.step(sending));
+    assert_eq!(rt.ready_tasks(), vec![waiting]);
+
+    assert_eq!(rt.tick(), Some(waiting));
+    assert_eq!(rt.tick(), None);
+}
+
+#[test]
+fn advance_fires_timeouts() {
+    let mut rt = Runtime::new(0);
+    let when = rt.now() + Duration::from_millis(100);
+
+    let task = rt.spawn(Delay::new(when).map_err(|_| ()));
+    rt.run_until_stalled();
+    assert!(rt.ready_tasks().is_empty());
+
+    rt.advance(Duration::from_millis(50));
+    assert!(rt.ready_tasks().is_empty());
+
+    rt.advance(Duration::from_millis(50));
+    assert_eq!(rt.ready_tasks(), vec![task]);
+    rt.run();
+}
+
+#[test]
+#[should_panic]
+fn deadlock_panics() {
+    let mut rt = Runtime::new(0);
+    let (_tx, rx) = oneshot::channel::<()>();
+
+    let _ = rt.block_on(rx);
+}
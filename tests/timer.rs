@@ -2,6 +2,7 @@ extern crate env_logger;
 extern crate futures;
 extern crate tokio;
 extern crate tokio_io;
+extern crate tokio_threadpool;
 
 use tokio::prelude::*;
 use tokio::timer::*;
@@ -29,6 +30,30 @@ fn timer_with_runtime() {
     rx.recv().unwrap();
 }
 
+#[test]
+fn delay_in_blocking() {
+    use futures::future::poll_fn;
+
+    let _ = env_logger::try_init();
+
+    // With a single worker, the worker's timer is handed to another thread
+    // while the delay is registered and awaited from the blocking section.
+    let mut runtime = tokio::runtime::Builder::new()
+        .core_threads(1)
+        .build()
+        .unwrap();
+
+    let when = Instant::now() + Duration::from_millis(50);
+
+    let res = runtime.block_on(poll_fn(move || {
+        tokio_threadpool::blocking(|| Delay::new(when).wait())
+            .map_err(|e| panic!("unexpected error; err={:?}", e))
+    }));
+
+    res.unwrap().unwrap();
+    assert!(Instant::now() >= when);
+}
+
 #[test]
 fn starving() {
     use futures::{task, Async, Poll};
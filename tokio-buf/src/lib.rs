@@ -15,6 +15,8 @@ extern crate either;
 #[allow(unused)]
 #[macro_use]
 extern crate futures;
+#[cfg(feature = "util")]
+extern crate tokio_io;
 
 mod never;
 mod size_hint;
@@ -1,4 +1,4 @@
-use BufStream;
+use {BufStream, SizeHint};
 
 use either::Either;
 use futures::Poll;
@@ -43,4 +43,24 @@ where
         let res = try_ready!(self.right.poll_buf());
         Ok(res.map(Either::Right).into())
     }
+
+    fn size_hint(&self) -> SizeHint {
+        let right = self.right.size_hint();
+
+        let left = match self.left {
+            Some(ref stream) => stream.size_hint(),
+            None => return right,
+        };
+
+        let mut hint = SizeHint::new();
+        hint.set_lower(left.lower().saturating_add(right.lower()));
+
+        if let (Some(l), Some(r)) = (left.upper(), right.upper()) {
+            if let Some(upper) = l.checked_add(r) {
+                hint.set_upper(upper);
+            }
+        }
+
+        hint
+    }
 }
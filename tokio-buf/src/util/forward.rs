@@ -0,0 +1,128 @@
+use BufStream;
+
+use bytes::Buf;
+use futures::{Future, Poll};
+use tokio_io::AsyncWrite;
+
+use std::io;
+
+/// Writes all data of a buf stream to an `AsyncWrite`.
+///
+/// `Forward` values are produced by the `forward_to` function on `BufStream`.
+#[derive(Debug)]
+pub struct Forward<T, W>
+where
+    T: BufStream,
+{
+    stream: Option<T>,
+    writer: Option<W>,
+    buf: Option<T::Item>,
+}
+
+/// Errors returned from `Forward` future.
+#[derive(Debug)]
+pub struct ForwardError<T> {
+    inner: Error<T>,
+}
+
+#[derive(Debug)]
+enum Error<T> {
+    Stream(T),
+    Write(io::Error),
+}
+
+impl<T, W> Forward<T, W>
+where
+    T: BufStream,
+    W: AsyncWrite,
+{
+    pub(crate) fn new(stream: T, writer: W) -> Forward<T, W> {
+        Forward {
+            stream: Some(stream),
+            writer: Some(writer),
+            buf: None,
+        }
+    }
+}
+
+impl<T, W> Future for Forward<T, W>
+where
+    T: BufStream,
+    W: AsyncWrite,
+{
+    type Item = (T, W);
+    type Error = ForwardError<T::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            if let Some(ref mut buf) = self.buf {
+                let writer = self.writer.as_mut().expect("cannot poll after done");
+
+                while buf.has_remaining() {
+                    let n = try_ready!(writer.write_buf(buf).map_err(ForwardError::write));
+
+                    if n == 0 {
+                        let err = io::Error::new(
+                            io::ErrorKind::WriteZero,
+                            "failed to write buffered data",
+                        );
+                        return Err(ForwardError::write(err));
+                    }
+                }
+            }
+
+            self.buf = None;
+
+            let res = {
+                let stream = self.stream.as_mut().expect("cannot poll after done");
+                try_ready!(stream.poll_buf().map_err(ForwardError::stream))
+            };
+
+            match res {
+                Some(buf) => self.buf = Some(buf),
+                None => {
+                    try_ready!(self
+                        .writer
+                        .as_mut()
+                        .expect("cannot poll after done")
+                        .poll_flush()
+                        .map_err(ForwardError::write));
+
+                    let stream = self.stream.take().unwrap();
+                    let writer = self.writer.take().unwrap();
+                    return Ok((stream, writer).into());
+                }
+            }
+        }
+    }
+}
+
+// ===== impl ForwardError =====
+
+impl<T> ForwardError<T> {
+    fn stream(err: T) -> ForwardError<T> {
+        let inner = Error::Stream(err);
+        ForwardError { inner }
+    }
+
+    fn write(err: io::Error) -> ForwardError<T> {
+        let inner = Error::Write(err);
+        ForwardError { inner }
+    }
+
+    /// Returns `true` if the error was caused by polling the stream.
+    pub fn is_stream_err(&self) -> bool {
+        match self.inner {
+            Error::Stream(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the error happened while writing the data.
+    pub fn is_write_err(&self) -> bool {
+        match self.inner {
+            Error::Write(_) => true,
+            _ => false,
+        }
+    }
+}
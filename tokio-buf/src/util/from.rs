@@ -1,6 +1,6 @@
 use SizeHint;
 
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use std::usize;
 
@@ -47,6 +47,12 @@ pub struct CollectVecError {
     _p: (),
 }
 
+/// Error returned from collecting into a `Bytes` or `BytesMut`
+#[derive(Debug)]
+pub struct CollectBytesError {
+    _p: (),
+}
+
 impl<T: Buf> FromBufStream<T> for Vec<u8> {
     type Builder = Vec<u8>;
     type Error = CollectVecError;
@@ -56,46 +62,38 @@ impl<T: Buf> FromBufStream<T> for Vec<u8> {
     }
 
     fn extend(builder: &mut Self, buf: &mut T, hint: &SizeHint) -> Result<(), Self::Error> {
-        let lower = hint.lower();
+        let reserve = match reserve(builder.len(), buf, hint) {
+            Some(n) => n,
+            None => return Err(CollectVecError { _p: () }),
+        };
 
-        // If the lower bound is greater than `usize::MAX` then we have a
-        // problem
-        if lower > usize::MAX as u64 {
-            return Err(CollectVecError { _p: () });
-        }
+        // Reserve space
+        builder.reserve(reserve);
 
-        let mut reserve = lower as usize;
+        // Copy the data
+        builder.put(buf);
 
-        // If `upper` is set, use this value if it is less than or equal to 64.
-        // This only really impacts the first iteration.
-        match hint.upper() {
-            Some(upper) if upper <= 64 => {
-                reserve = upper as usize;
-            }
-            _ => {}
-        }
+        Ok(())
+    }
 
-        // hint.lower() represents the minimum amount of data that will be
-        // received *after* this function call. We reserve this amount on top of
-        // the amount of data in `buf`.
-        reserve = match reserve.checked_add(buf.remaining()) {
-            Some(n) => n,
-            None => return Err(CollectVecError { _p: () }),
-        };
+    fn build(builder: Self) -> Result<Self, Self::Error> {
+        Ok(builder)
+    }
+}
 
-        // Always reserve 64 bytes the first time, unless `upper` is set and is
-        // less than 64.
-        if builder.is_empty() {
-            reserve = reserve.max(match hint.upper() {
-                Some(upper) if upper < 64 => upper as usize,
-                _ => 64,
-            });
-        }
+impl<T: Buf> FromBufStream<T> for BytesMut {
+    type Builder = BytesMut;
+    type Error = CollectBytesError;
 
-        // Make sure overflow won't happen when reserving
-        if reserve.checked_add(builder.len()).is_none() {
-            return Err(CollectVecError { _p: () });
-        }
+    fn builder(_hint: &SizeHint) -> BytesMut {
+        BytesMut::new()
+    }
+
+    fn extend(builder: &mut Self, buf: &mut T, hint: &SizeHint) -> Result<(), Self::Error> {
+        let reserve = match reserve(builder.len(), buf, hint) {
+            Some(n) => n,
+            None => return Err(CollectBytesError { _p: () }),
+        };
 
         // Reserve space
         builder.reserve(reserve);
@@ -110,3 +108,62 @@ impl<T: Buf> FromBufStream<T> for Vec<u8> {
         Ok(builder)
     }
 }
+
+impl<T: Buf> FromBufStream<T> for Bytes {
+    type Builder = BytesMut;
+    type Error = CollectBytesError;
+
+    fn builder(_hint: &SizeHint) -> BytesMut {
+        BytesMut::new()
+    }
+
+    fn extend(builder: &mut BytesMut, buf: &mut T, hint: &SizeHint) -> Result<(), Self::Error> {
+        <BytesMut as FromBufStream<T>>::extend(builder, buf, hint)
+    }
+
+    fn build(builder: BytesMut) -> Result<Self, Self::Error> {
+        Ok(builder.freeze())
+    }
+}
+
+/// Returns how much space to reserve in a builder holding `len` bytes before
+/// copying `buf` into it, or `None` if the data would not fit in memory.
+fn reserve<T: Buf>(len: usize, buf: &T, hint: &SizeHint) -> Option<usize> {
+    let lower = hint.lower();
+
+    // If the lower bound is greater than `usize::MAX` then we have a
+    // problem
+    if lower > usize::MAX as u64 {
+        return None;
+    }
+
+    let mut reserve = lower as usize;
+
+    // If `upper` is set, use this value if it is less than or equal to 64.
+    // This only really impacts the first iteration.
+    match hint.upper() {
+        Some(upper) if upper <= 64 => {
+            reserve = upper as usize;
+        }
+        _ => {}
+    }
+
+    // hint.lower() represents the minimum amount of data that will be
+    // received *after* this function call. We reserve this amount on top of
+    // the amount of data in `buf`.
+    reserve = reserve.checked_add(buf.remaining())?;
+
+    // Always reserve 64 bytes the first time, unless `upper` is set and is
+    // less than 64.
+    if len == 0 {
+        reserve = reserve.max(match hint.upper() {
+            Some(upper) if upper < 64 => upper as usize,
+            _ => 64,
+        });
+    }
+
+    // Make sure overflow won't happen when reserving
+    reserve.checked_add(len)?;
+
+    Some(reserve)
+}
@@ -0,0 +1,56 @@
+use BufStream;
+
+use bytes::IntoBuf;
+use futures::{Poll, Stream};
+
+/// Converts a `Stream` of byte buffers, such as `Bytes`, into a buf stream.
+///
+/// This is the inverse of [`IntoStream`].
+///
+/// [`IntoStream`]: struct.IntoStream.html
+#[derive(Debug)]
+pub struct FromStream<S> {
+    stream: S,
+}
+
+impl<S> FromStream<S>
+where
+    S: Stream,
+    S::Item: IntoBuf,
+{
+    /// Creates a new buf stream yielding the values of `stream`.
+    pub fn new(stream: S) -> FromStream<S> {
+        FromStream { stream }
+    }
+}
+
+impl<S> FromStream<S> {
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this `FromStream`, returning the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> BufStream for FromStream<S>
+where
+    S: Stream,
+    S::Item: IntoBuf,
+{
+    type Item = <S::Item as IntoBuf>::Buf;
+    type Error = S::Error;
+
+    fn poll_buf(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        let res = try_ready!(self.stream.poll());
+        Ok(res.map(IntoBuf::into_buf).into())
+    }
+}
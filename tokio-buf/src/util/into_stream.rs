@@ -0,0 +1,47 @@
+use BufStream;
+
+use bytes::{Buf, Bytes};
+use futures::{Poll, Stream};
+
+/// Converts a buf stream into a `Stream` of `Bytes`.
+///
+/// `IntoStream` values are produced by the `into_stream` function on
+/// `BufStream`.
+#[derive(Debug)]
+pub struct IntoStream<T> {
+    stream: T,
+}
+
+impl<T> IntoStream<T> {
+    pub(crate) fn new(stream: T) -> IntoStream<T> {
+        IntoStream { stream }
+    }
+
+    /// Gets a reference to the underlying buf stream.
+    pub fn get_ref(&self) -> &T {
+        &self.stream
+    }
+
+    /// Gets a mutable reference to the underlying buf stream.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.stream
+    }
+
+    /// Consumes this `IntoStream`, returning the underlying buf stream.
+    pub fn into_inner(self) -> T {
+        self.stream
+    }
+}
+
+impl<T> Stream for IntoStream<T>
+where
+    T: BufStream,
+{
+    type Item = Bytes;
+    type Error = T::Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, T::Error> {
+        let res = try_ready!(self.stream.poll_buf());
+        Ok(res.map(Buf::collect).into())
+    }
+}
@@ -1,8 +1,10 @@
-use BufStream;
+use {BufStream, SizeHint};
 
 use bytes::Buf;
 use futures::Poll;
 
+use std::cmp;
+
 /// Limits the stream to a maximum amount of data.
 #[derive(Debug)]
 pub struct Limit<T> {
@@ -59,6 +61,22 @@ where
 
         res
     }
+
+    fn size_hint(&self) -> SizeHint {
+        let inner = self.stream.size_hint();
+
+        // Any data past `remaining` results in an error, so the stream never
+        // yields more than that.
+        let mut hint = SizeHint::new();
+        hint.set_lower(cmp::min(inner.lower(), self.remaining));
+        hint.set_upper(
+            inner
+                .upper()
+                .map_or(self.remaining, |upper| cmp::min(upper, self.remaining)),
+        );
+
+        hint
+    }
 }
 
 // ===== impl LimitError =====
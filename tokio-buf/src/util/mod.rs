@@ -2,24 +2,33 @@
 
 mod chain;
 mod collect;
+mod forward;
 mod from;
+mod from_stream;
+mod into_stream;
 mod limit;
 
 pub use self::chain::Chain;
 pub use self::collect::Collect;
+pub use self::forward::Forward;
 pub use self::from::FromBufStream;
+pub use self::from_stream::FromStream;
+pub use self::into_stream::IntoStream;
 pub use self::limit::Limit;
 
 pub mod error {
     //! Error types
 
     pub use super::collect::CollectError;
-    pub use super::from::CollectVecError;
+    pub use super::forward::ForwardError;
+    pub use super::from::{CollectBytesError, CollectVecError};
     pub use super::limit::LimitError;
 }
 
 use BufStream;
 
+use tokio_io::AsyncWrite;
+
 impl<T> BufStreamExt for T where T: BufStream {}
 
 /// An extension trait for `BufStream`'s that provides a variety of convenient
@@ -70,4 +79,36 @@ pub trait BufStreamExt: BufStream {
     {
         Limit::new(self, amount)
     }
+
+    /// Writes all data from `self` to `writer`.
+    ///
+    /// `forward_to()` returns a future that writes each buffer yielded from
+    /// `self` to `writer`, and flushes `writer` once `self` yields `None`. The
+    /// future then completes, returning both `self` and `writer`. The writer
+    /// is not shut down, so more data may be written to it.
+    ///
+    /// The forward future will yield an error if `self` yields an error or if
+    /// writing to `writer` fails.
+    fn forward_to<W>(self, writer: W) -> Forward<Self, W>
+    where
+        Self: Sized,
+        W: AsyncWrite,
+    {
+        Forward::new(self, writer)
+    }
+
+    /// Converts `self` into a `Stream` of `Bytes`.
+    ///
+    /// `into_stream()` returns a new `Stream` value which yields the data of
+    /// each buffer yielded from `self` as `Bytes`. This allows passing the
+    /// data to APIs working with streams of `Bytes`. Use [`FromStream`] for
+    /// the opposite conversion.
+    ///
+    /// [`FromStream`]: struct.FromStream.html
+    fn into_stream(self) -> IntoStream<Self>
+    where
+        Self: Sized,
+    {
+        IntoStream::new(self)
+    }
 }
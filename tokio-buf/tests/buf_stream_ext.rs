@@ -1,12 +1,14 @@
-#![cfg(feature = "ext")]
+#![cfg(feature = "util")]
 
 extern crate bytes;
 extern crate futures;
 extern crate tokio_buf;
 
-use bytes::Buf;
+use bytes::{Buf, Bytes, BytesMut};
 use futures::Async::*;
-use futures::Future;
+use futures::{Future, Stream};
+use std::io::Cursor;
+use tokio_buf::util::FromStream;
 use tokio_buf::{BufStream, BufStreamExt};
 
 #[macro_use]
@@ -91,6 +93,69 @@ fn collect_vec() {
     assert_eq!(vec, b"hello world, one two three");
 }
 
+// ===== Test `forward_to()` =====
+
+#[test]
+fn forward_to() {
+    let bs = list(&["hello", " ", "world"]);
+
+    let (_, wr) = bs.forward_to(Cursor::new(vec![])).wait().unwrap();
+
+    assert_eq!(wr.into_inner(), b"hello world");
+
+    // Writer full
+    //
+    let mut dst = [0; 8];
+    let err = one("hello world")
+        .forward_to(Cursor::new(&mut dst[..]))
+        .wait()
+        .unwrap_err();
+
+    assert!(err.is_write_err());
+
+    // Stream error
+    //
+    let bs = new_mock(&[Ok(Ready("hello")), Err(())]);
+    let err = bs.forward_to(Cursor::new(vec![])).wait().unwrap_err();
+
+    assert!(err.is_stream_err());
+}
+
+// ===== Test `into_stream()` and `FromStream` =====
+
+#[test]
+fn into_stream() {
+    let mut s = list(&["hello", " ", "world"]).into_stream();
+
+    assert_eq!(s.poll().unwrap(), Ready(Some(Bytes::from("hello"))));
+    assert_eq!(s.poll().unwrap(), Ready(Some(Bytes::from(" "))));
+    assert_eq!(s.poll().unwrap(), Ready(Some(Bytes::from("world"))));
+    assert_eq!(s.poll().unwrap(), Ready(None));
+
+    let mut s = new_mock(&[Ok(Ready("foo")), Ok(NotReady), Err(())]).into_stream();
+
+    assert_eq!(s.poll().unwrap(), Ready(Some(Bytes::from("foo"))));
+    assert_eq!(s.poll().unwrap(), NotReady);
+    assert!(s.poll().is_err());
+}
+
+#[test]
+fn from_stream() {
+    let s = futures::stream::iter_ok::<_, ()>(vec![Bytes::from("hello"), Bytes::from(" world")]);
+    let mut bs = FromStream::new(s);
+
+    assert_buf_eq!(bs.poll_buf(), "hello");
+    assert_buf_eq!(bs.poll_buf(), " world");
+    assert_none!(bs.poll_buf());
+
+    // Round trip
+    //
+    let bs = FromStream::new(list(&["hello", " ", "world"]).into_stream());
+    let vec: Vec<u8> = bs.collect().wait().unwrap();
+
+    assert_eq!(vec, b"hello world");
+}
+
 // ===== Test limit() =====
 
 #[test]
@@ -143,3 +208,74 @@ fn limit() {
 
     assert!(bs.poll_buf().is_err());
 }
+
+#[test]
+fn chain_size_hint() {
+    let mut left = list(&["foo", "bar"]);
+    left.size_hint.set_lower(6);
+    left.size_hint.set_upper(6);
+
+    let mut right = one("baz");
+    right.size_hint.set_lower(3);
+
+    let mut bs = left.chain(right);
+
+    assert_eq!(bs.size_hint().lower(), 9);
+    assert_eq!(bs.size_hint().upper(), None);
+
+    assert_buf_eq!(bs.poll_buf(), "foo");
+    assert_buf_eq!(bs.poll_buf(), "bar");
+    assert_buf_eq!(bs.poll_buf(), "baz");
+
+    // Once the left stream is done, only the right stream is left.
+    assert_eq!(bs.size_hint().lower(), 3);
+}
+
+#[test]
+fn collect_bytes() {
+    // Collect one
+    //
+    let bs = one("hello world");
+
+    let bytes: Bytes = bs.collect().wait().unwrap();
+
+    assert_eq!(bytes, "hello world");
+
+    // Collect many, with size hint
+    //
+    let mut bs = list(&["hello", " ", "world", ", one two three"]);
+    bs.size_hint.set_lower(26);
+    bs.size_hint.set_upper(26);
+
+    let bytes: BytesMut = bs.collect().wait().unwrap();
+
+    assert_eq!(bytes, "hello world, one two three");
+
+    // Collect a chain of streams
+    //
+    let bytes: Bytes = list(&["hello", " "])
+        .chain(one("world"))
+        .collect()
+        .wait()
+        .unwrap();
+
+    assert_eq!(bytes, "hello world");
+}
+
+#[test]
+fn limit_size_hint() {
+    let bs = one("hello world").limit(100);
+
+    assert_eq!(bs.size_hint().lower(), 0);
+    assert_eq!(bs.size_hint().upper(), Some(100));
+
+    let mut bs = one("hello world");
+    bs.size_hint.set_lower(11);
+    bs.size_hint.set_upper(11);
+    let mut bs = bs.limit(100);
+
+    assert_eq!(bs.size_hint().lower(), 11);
+    assert_eq!(bs.size_hint().upper(), Some(11));
+
+    assert_buf_eq!(bs.poll_buf(), "hello world");
+}
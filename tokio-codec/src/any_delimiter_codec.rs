@@ -0,0 +1,151 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use delimited::Delimited;
+use std::{io, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
+
+/// A simple `Codec` implementation that splits up data into chunks based on
+/// any of the given delimiters.
+///
+/// When decoding, a chunk ends at the first of the seek delimiters found in
+/// the data, which is not part of the chunk. Delimiters are byte sequences,
+/// and when several of them match at the same position, the longest one is
+/// used. When encoding, each chunk is followed by the sequence writer.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bytes;
+/// # extern crate tokio_codec;
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_codec::{AnyDelimiterCodec, Decoder, Encoder};
+///
+/// # fn main() {
+/// let mut codec = AnyDelimiterCodec::new(vec![b",".to_vec(), b";\0".to_vec()], b";\0".to_vec());
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(Bytes::from("a"), &mut buf).unwrap();
+/// codec.encode(Bytes::from("b"), &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"a;\0b;\0");
+///
+/// buf.extend_from_slice(b"c,d");
+/// assert_eq!(&codec.decode(&mut buf).unwrap().unwrap()[..], b"a");
+/// assert_eq!(&codec.decode(&mut buf).unwrap().unwrap()[..], b"b");
+/// assert_eq!(&codec.decode(&mut buf).unwrap().unwrap()[..], b"c");
+/// assert_eq!(codec.decode(&mut buf).unwrap(), None);
+/// assert_eq!(&codec.decode_eof(&mut buf).unwrap().unwrap()[..], b"d");
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AnyDelimiterCodec {
+    /// Splits the decoded data at the seek delimiters.
+    chunks: Delimited,
+
+    /// The bytes written after each chunk when encoding.
+    sequence_writer: Vec<u8>,
+}
+
+impl AnyDelimiterCodec {
+    /// Returns an `AnyDelimiterCodec` for splitting up data into chunks.
+    ///
+    /// Chunks are split at any sequence of `seek_delimiters` when decoding,
+    /// and are followed by `sequence_writer` when encoding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seek_delimiters` is empty or holds an empty sequence.
+    ///
+    /// # Note
+    ///
+    /// The returned `AnyDelimiterCodec` will not have an upper bound on the
+    /// length of a buffered chunk. See the documentation for
+    /// [`new_with_max_length`] for information on why this could be a
+    /// potential security risk.
+    ///
+    /// [`new_with_max_length`]: #method.new_with_max_length
+    pub fn new(seek_delimiters: Vec<Vec<u8>>, sequence_writer: Vec<u8>) -> AnyDelimiterCodec {
+        AnyDelimiterCodec::new_with_max_length(seek_delimiters, sequence_writer, usize::MAX)
+    }
+
+    /// Returns an `AnyDelimiterCodec` with a maximum chunk length limit.
+    ///
+    /// If this is set, calls to `AnyDelimiterCodec::decode` will return an
+    /// error when a chunk exceeds the length limit. Subsequent calls will
+    /// discard the remainder of that chunk until a delimiter is reached,
+    /// returning `None` until the chunk over the limit has been fully
+    /// discarded. After that point, calls to `decode` will function as
+    /// normal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seek_delimiters` is empty or holds an empty sequence.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any
+    /// `AnyDelimiterCodec` which will be exposed to untrusted input.
+    /// Otherwise, the size of the buffer that holds the chunk currently being
+    /// read is unbounded. An attacker could exploit this unbounded buffer by
+    /// sending an unbounded amount of input without any delimiter, causing
+    /// unbounded memory consumption.
+    pub fn new_with_max_length(
+        seek_delimiters: Vec<Vec<u8>>,
+        sequence_writer: Vec<u8>,
+        max_length: usize,
+    ) -> AnyDelimiterCodec {
+        AnyDelimiterCodec {
+            chunks: Delimited::with_delimiters(seek_delimiters, max_length),
+            sequence_writer,
+        }
+    }
+
+    /// Returns the maximum chunk length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.chunks.max_length()
+    }
+
+    /// Returns the byte sequences that end a chunk when decoding.
+    pub fn seek_delimiters(&self) -> &[Vec<u8>] {
+        self.chunks.delimiters()
+    }
+
+    /// Returns the bytes written after each chunk when encoding.
+    pub fn sequence_writer(&self) -> &[u8] {
+        &self.sequence_writer
+    }
+}
+
+impl Decoder for AnyDelimiterCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        self.chunks.next_frame(buf)
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        Ok(match self.chunks.next_frame_eof(buf)? {
+            Some(frame) => Some(frame),
+            // No terminating delimiter - return remaining data, if any
+            None if buf.is_empty() => None,
+            None => Some(self.chunks.last_frame(buf)),
+        })
+    }
+}
+
+impl Encoder for AnyDelimiterCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, chunk: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&chunk, buf)
+    }
+}
+
+impl EncodeRef for AnyDelimiterCodec {
+    fn encode_ref(&mut self, chunk: &Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        buf.reserve(chunk.len() + self.sequence_writer.len());
+        buf.put(chunk);
+        buf.put_slice(&self.sequence_writer);
+        Ok(())
+    }
+}
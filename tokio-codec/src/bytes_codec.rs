@@ -1,6 +1,6 @@
 use bytes::{BufMut, Bytes, BytesMut};
 use std::io;
-use tokio_io::_tokio_codec::{Decoder, Encoder};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
 
 /// A simple `Codec` implementation that just ships bytes around.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -32,6 +32,12 @@ impl Encoder for BytesCodec {
     type Error = io::Error;
 
     fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&data, buf)
+    }
+}
+
+impl EncodeRef for BytesCodec {
+    fn encode_ref(&mut self, data: &Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
         buf.reserve(data.len());
         buf.put(data);
         Ok(())
@@ -0,0 +1,176 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use delimited::Delimited;
+use std::{io, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
+
+/// A `Codec` implementation for frames encoded with Consistent Overhead Byte
+/// Stuffing (COBS).
+///
+/// COBS removes every zero byte from the payload of a frame, at the cost of
+/// at most one byte every 254 bytes, so that frames can be terminated by a
+/// zero byte. A receiver can then resynchronize with the stream at the next
+/// zero byte after corrupted or oversized data.
+///
+/// Each frame is encoded followed by a zero byte. When decoding, empty frames,
+/// such as a zero byte sent ahead of a frame to flush line noise, are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bytes;
+/// # extern crate tokio_codec;
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_codec::{CobsCodec, Decoder, Encoder};
+///
+/// # fn main() {
+/// let mut codec = CobsCodec::new();
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(Bytes::from(&b"a\x00b"[..]), &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"\x02a\x02b\x00");
+///
+/// let frame = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(&frame[..], b"a\x00b");
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CobsCodec {
+    // Splits the stream on zero bytes.
+    frames: Delimited,
+
+    // The maximum length of a decoded frame.
+    max_length: usize,
+}
+
+impl CobsCodec {
+    /// Returns a `CobsCodec` for framing data with COBS.
+    ///
+    /// # Note
+    ///
+    /// The returned `CobsCodec` will not have an upper bound on the length of
+    /// a buffered frame. See the documentation for [`new_with_max_length`]
+    /// for information on why this could be a potential security risk.
+    ///
+    /// [`new_with_max_length`]: #method.new_with_max_length
+    pub fn new() -> CobsCodec {
+        CobsCodec::new_with_max_length(usize::MAX)
+    }
+
+    /// Returns a `CobsCodec` with a maximum frame length limit.
+    ///
+    /// If this is set, calls to `CobsCodec::decode` will return an error when
+    /// a frame exceeds the length limit. Subsequent calls will discard the
+    /// remainder of that frame until a zero byte is reached, after which
+    /// decoding resumes with the next frame.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any `CobsCodec` which
+    /// will be exposed to untrusted input. Otherwise, the size of the buffer
+    /// that holds the frame currently being read is unbounded.
+    pub fn new_with_max_length(max_length: usize) -> CobsCodec {
+        // Encoding adds one byte, plus one more every 254 bytes.
+        let max_encoded = max_length.saturating_add(max_length / 254 + 1);
+
+        CobsCodec {
+            frames: Delimited::new(0, max_encoded),
+            max_length,
+        }
+    }
+
+    /// Returns the maximum frame length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+fn invalid_frame() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid COBS frame")
+}
+
+impl Decoder for CobsCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        loop {
+            let src = match self.frames.next_frame(buf)? {
+                Some(src) => src,
+                None => return Ok(None),
+            };
+
+            if src.is_empty() {
+                continue;
+            }
+
+            let mut frame = BytesMut::with_capacity(src.len());
+            let mut pos = 0;
+
+            while pos < src.len() {
+                let code = src[pos] as usize;
+                let end = pos + code;
+
+                if code == 0 || end > src.len() {
+                    return Err(invalid_frame());
+                }
+
+                frame.put_slice(&src[pos + 1..end]);
+                pos = end;
+
+                // A full block is not followed by a zero byte
+                if code < 0xff && pos < src.len() {
+                    frame.put_u8(0);
+                }
+            }
+
+            if frame.len() > self.max_length {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "frame length limit exceeded",
+                ));
+            }
+
+            return Ok(Some(frame));
+        }
+    }
+}
+
+impl Encoder for CobsCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&data, buf)
+    }
+}
+
+impl EncodeRef for CobsCodec {
+    fn encode_ref(&mut self, data: &Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        buf.reserve(data.len() + data.len() / 254 + 2);
+
+        // Index of the code byte of the current block, written once the
+        // length of the block is known.
+        let mut code_index = buf.len();
+        let mut code = 1;
+        buf.put_u8(0);
+
+        for &b in data.iter() {
+            if b != 0 {
+                buf.put_u8(b);
+                code += 1;
+            }
+
+            if b == 0 || code == 0xff {
+                buf[code_index] = code;
+                code_index = buf.len();
+                code = 1;
+                buf.put_u8(0);
+            }
+        }
+
+        buf[code_index] = code;
+        buf.put_u8(0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use bytes::BytesMut;
+use std::{cmp, io};
+
+/// Splits a buffer into frames terminated by a delimiter, such as the frames
+/// of byte stuffing protocols.
+///
+/// A frame ends at the first delimiter found in the buffer. If several
+/// delimiters match at that position, the longest one is used.
+///
+/// A frame longer than the limit is reported once, and the remainder of it is
+/// discarded until the next delimiter is reached.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub(crate) struct Delimited {
+    // The byte sequences terminating each frame, none of which are empty.
+    delimiters: Vec<Vec<u8>>,
+
+    // Stored index of the next index to examine for a delimiter, so that
+    // bytes already searched are not searched again.
+    next_index: usize,
+
+    // The maximum length of a frame, not counting the delimiter.
+    max_length: usize,
+
+    // Are we currently discarding the remainder of a frame which was over
+    // the length limit?
+    is_discarding: bool,
+}
+
+/// Result of searching a buffer for a delimiter.
+enum Search {
+    /// A delimiter of the given length starts at the given index.
+    Found(usize, usize),
+    /// The buffer ends with the start of a delimiter at the given index.
+    Partial(usize),
+    /// No delimiter starts before the end of the searched range.
+    NotFound,
+}
+
+impl Delimited {
+    pub fn new(delimiter: u8, max_length: usize) -> Delimited {
+        Delimited::with_delimiters(vec![vec![delimiter]], max_length)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `delimiters` is empty or holds an empty sequence.
+    pub fn with_delimiters(delimiters: Vec<Vec<u8>>, max_length: usize) -> Delimited {
+        assert!(!delimiters.is_empty(), "no delimiter");
+        assert!(
+            delimiters.iter().all(|d| !d.is_empty()),
+            "delimiters must not be empty"
+        );
+
+        Delimited {
+            delimiters,
+            next_index: 0,
+            max_length,
+            is_discarding: false,
+        }
+    }
+
+    pub fn delimiters(&self) -> &[Vec<u8>] {
+        &self.delimiters
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns the next frame, without its delimiter, or `None` if the buffer
+    /// does not hold a whole frame yet.
+    pub fn next_frame(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        self.next(buf, false)
+    }
+
+    /// Returns the next frame as `next_frame`, once the stream has ended.
+    ///
+    /// The start of a delimiter at the end of the buffer is not waited for,
+    /// so that a shorter delimiter matching at the same position is used.
+    pub fn next_frame_eof(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        self.next(buf, true)
+    }
+
+    /// Returns the remaining bytes of the buffer, once the stream has ended
+    /// without a final delimiter.
+    pub fn last_frame(&mut self, buf: &mut BytesMut) -> BytesMut {
+        self.next_index = 0;
+        buf.take()
+    }
+
+    fn next(&mut self, buf: &mut BytesMut, eof: bool) -> Result<Option<BytesMut>, io::Error> {
+        if self.is_discarding {
+            let len = buf.len();
+
+            match self.search(buf, 0, len, eof) {
+                Search::Found(index, len) => {
+                    buf.advance(index + len);
+                    self.is_discarding = false;
+                }
+                Search::Partial(index) => {
+                    // Keep the start of the delimiter until it is complete
+                    buf.advance(index);
+                    return Ok(None);
+                }
+                Search::NotFound => {
+                    buf.clear();
+                    return Ok(None);
+                }
+            }
+        }
+
+        // Determine how far into the buffer a frame can start a delimiter.
+        let read_to = cmp::min(self.max_length.saturating_add(1), buf.len());
+
+        match self.search(buf, self.next_index, read_to, eof) {
+            Search::Found(index, len) => {
+                self.next_index = 0;
+
+                let mut frame = buf.split_to(index + len);
+                frame.truncate(index);
+
+                Ok(Some(frame))
+            }
+            Search::Partial(index) => {
+                self.next_index = index;
+                Ok(None)
+            }
+            Search::NotFound if buf.len() > self.max_length => {
+                // The frame is over the limit. The bytes searched so far are
+                // dropped, and the rest of the frame is discarded as it
+                // arrives.
+                buf.advance(read_to);
+                self.next_index = 0;
+                self.is_discarding = true;
+
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "frame length limit exceeded",
+                ))
+            }
+            Search::NotFound => {
+                self.next_index = read_to;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Searches for the first delimiter starting in `buf[from..to]`.
+    fn search(&self, buf: &[u8], from: usize, to: usize, eof: bool) -> Search {
+        for index in from..to {
+            let rest = &buf[index..];
+            let mut found = None;
+
+            for delimiter in &self.delimiters {
+                if rest.starts_with(delimiter) {
+                    if found.map_or(true, |len| delimiter.len() > len) {
+                        found = Some(delimiter.len());
+                    }
+                } else if !eof && delimiter.starts_with(rest) {
+                    // A longer delimiter may still match once more bytes
+                    // are read.
+                    return Search::Partial(index);
+                }
+            }
+
+            if let Some(len) = found {
+                return Search::Found(index, len);
+            }
+        }
+
+        Search::NotFound
+    }
+}
@@ -0,0 +1,174 @@
+use bytes::{BufMut, BytesMut};
+use delimited::Delimited;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use std::marker::PhantomData;
+use std::{fmt, io, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
+
+/// A `Codec` implementation for newline delimited JSON values.
+///
+/// Each line is decoded into a `T` using serde, and each value is encoded as
+/// compact JSON followed by a `\n` character. Blank lines are skipped.
+///
+/// A line which fails to parse is consumed before the error is returned, so
+/// decoding may resume with the next line.
+///
+/// This codec is only available with the `json` feature enabled.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bytes;
+/// # extern crate tokio_codec;
+/// use bytes::BytesMut;
+/// use tokio_codec::{Decoder, Encoder, Json};
+///
+/// # fn main() {
+/// let mut codec = Json::<Vec<u32>>::new();
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(vec![1, 2, 3], &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"[1,2,3]\n");
+///
+/// assert_eq!(codec.decode(&mut buf).unwrap(), Some(vec![1, 2, 3]));
+/// # }
+/// ```
+pub struct Json<T> {
+    // Splits the stream into lines.
+    lines: Delimited,
+
+    // The maximum length of a line.
+    max_length: usize,
+
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Json<T> {
+    /// Returns a `Json` codec for decoding and encoding lines of JSON.
+    ///
+    /// # Note
+    ///
+    /// The returned `Json` codec will not have an upper bound on the length
+    /// of a buffered line. See the documentation for [`new_with_max_length`]
+    /// for information on why this could be a potential security risk.
+    ///
+    /// [`new_with_max_length`]: #method.new_with_max_length
+    pub fn new() -> Json<T> {
+        Json::new_with_max_length(usize::MAX)
+    }
+
+    /// Returns a `Json` codec with a maximum line length limit.
+    ///
+    /// If this is set, calls to `Json::decode` will return an error when a
+    /// line exceeds the length limit. Subsequent calls will discard the
+    /// remainder of that line until a newline character is reached, after
+    /// which decoding resumes with the next line.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any `Json` codec which
+    /// will be exposed to untrusted input. Otherwise, the size of the buffer
+    /// that holds the line currently being read is unbounded.
+    pub fn new_with_max_length(max_length: usize) -> Json<T> {
+        Json {
+            lines: Delimited::new(b'\n', max_length),
+            max_length,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the maximum line length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+fn is_blank(line: &[u8]) -> bool {
+    line.iter()
+        .all(|b| *b == b' ' || *b == b'\t' || *b == b'\r')
+}
+
+impl<T> Decoder for Json<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        loop {
+            let line = match self.lines.next_frame(buf)? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+
+            if !is_blank(&line) {
+                return Ok(Some(serde_json::from_slice(&line)?));
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        if let Some(value) = self.decode(buf)? {
+            return Ok(Some(value));
+        }
+
+        // No terminating newline - decode the remaining data, if any
+        let line = self.lines.last_frame(buf);
+
+        if is_blank(&line) {
+            Ok(None)
+        } else {
+            Ok(Some(serde_json::from_slice(&line)?))
+        }
+    }
+}
+
+impl<T> Encoder for Json<T>
+where
+    T: Serialize,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn encode(&mut self, value: T, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&value, buf)
+    }
+}
+
+impl<T> EncodeRef for Json<T>
+where
+    T: Serialize,
+{
+    fn encode_ref(&mut self, value: &T, buf: &mut BytesMut) -> Result<(), io::Error> {
+        // Compact JSON never contains a newline character
+        let json = serde_json::to_vec(&value)?;
+
+        buf.reserve(json.len() + 1);
+        buf.put_slice(&json);
+        buf.put_u8(b'\n');
+
+        Ok(())
+    }
+}
+
+impl<T> Clone for Json<T> {
+    fn clone(&self) -> Json<T> {
+        Json {
+            lines: self.lines.clone(),
+            max_length: self.max_length,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Json<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Json")
+            .field("lines", &self.lines)
+            .field("max_length", &self.max_length)
+            .finish()
+    }
+}
@@ -14,12 +14,42 @@
 //! [transports]: #
 
 extern crate bytes;
+#[cfg(feature = "json")]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
 extern crate tokio_io;
 
+mod any_delimiter_codec;
 mod bytes_codec;
+mod cobs_codec;
+mod delimited;
+#[cfg(feature = "json")]
+mod json_codec;
 mod lines_codec;
+mod netstring_codec;
+mod slip_codec;
 
-pub use tokio_io::_tokio_codec::{Decoder, Encoder, Framed, FramedParts, FramedRead, FramedWrite};
+#[doc(hidden)]
+pub mod _datagram {
+    //! Shared by the framed datagram sockets of `tokio-udp` and `tokio-uds`,
+    //! not part of the public API.
 
+    /// Number of datagrams failing to decode which are skipped by a single
+    /// poll, before yielding so that a flood of malformed datagrams does not
+    /// monopolize the thread.
+    pub const MAX_SKIPPED_PER_POLL: usize = 32;
+}
+
+pub use tokio_io::_tokio_codec::{
+    DecodeRecovery, Decoder, EncodeRef, Encoder, Framed, FramedParts, FramedRead, FramedWrite,
+};
+
+pub use any_delimiter_codec::AnyDelimiterCodec;
 pub use bytes_codec::BytesCodec;
-pub use lines_codec::LinesCodec;
+pub use cobs_codec::CobsCodec;
+#[cfg(feature = "json")]
+pub use json_codec::Json;
+pub use lines_codec::{LengthError, LinesCodec};
+pub use netstring_codec::NetstringCodec;
+pub use slip_codec::SlipCodec;
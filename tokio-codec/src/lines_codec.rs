@@ -1,6 +1,7 @@
 use bytes::{BufMut, BytesMut};
-use std::{cmp, io, str, usize};
-use tokio_io::_tokio_codec::{Decoder, Encoder};
+use std::error::Error as StdError;
+use std::{cmp, fmt, io, str, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
 
 /// A simple `Codec` implementation that splits up data into lines.
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -20,6 +21,24 @@ pub struct LinesCodec {
     /// Are we currently discarding the remainder of a line which was over
     /// the length limit?
     is_discarding: bool,
+
+    /// Should decoding resume at the next line once a line over the length
+    /// limit has been discarded?
+    resync: bool,
+
+    /// Has a line over the length limit been read while `resync` is unset?
+    is_poisoned: bool,
+}
+
+/// An error when a line is longer than the maximum line length.
+///
+/// Returned by [`LinesCodec::decode`] wrapped in an `io::Error` of kind
+/// `Other`, and can be retrieved with `io::Error::get_ref`.
+///
+/// [`LinesCodec::decode`]: struct.LinesCodec.html#method.decode
+#[derive(Debug)]
+pub struct LengthError {
+    max_length: usize,
 }
 
 impl LinesCodec {
@@ -37,6 +56,8 @@ impl LinesCodec {
             next_index: 0,
             max_length: usize::MAX,
             is_discarding: false,
+            resync: true,
+            is_poisoned: false,
         }
     }
 
@@ -47,7 +68,7 @@ impl LinesCodec {
     /// will discard up to `limit` bytes from that line until a newline
     /// character is reached, returning `None` until the line over the limit
     /// has been fully discarded. After that point, calls to `decode` will
-    /// function as normal.
+    /// function as normal. Use [`set_resync`] to keep failing instead.
     ///
     /// # Note
     ///
@@ -57,7 +78,8 @@ impl LinesCodec {
     /// exploit this unbounded buffer by sending an unbounded amount of input
     /// without any `\n` characters, causing unbounded memory consumption.
     ///
-    /// [`LengthError`]: ../struct.LengthError
+    /// [`LengthError`]: struct.LengthError.html
+    /// [`set_resync`]: #method.set_resync
     pub fn new_with_max_length(max_length: usize) -> Self {
         LinesCodec {
             max_length,
@@ -84,6 +106,51 @@ impl LinesCodec {
         self.max_length
     }
 
+    /// Returns `true` if decoding resumes at the next line after a line over
+    /// the length limit.
+    pub fn resync(&self) -> bool {
+        self.resync
+    }
+
+    /// Sets whether decoding resumes at the next line after a line over the
+    /// length limit.
+    ///
+    /// When set, which is the default, the remainder of the line over the
+    /// limit is discarded and the following lines are decoded as usual. When
+    /// unset, every call to `decode` following a line over the limit returns
+    /// a [`LengthError`], so that the stream ends with an error instead of
+    /// silently skipping data.
+    ///
+    /// ```
+    /// # extern crate bytes;
+    /// # extern crate tokio_codec;
+    /// use bytes::BytesMut;
+    /// use tokio_codec::{Decoder, LinesCodec};
+    ///
+    /// # fn main() {
+    /// let mut codec = LinesCodec::new_with_max_length(4);
+    /// codec.set_resync(false);
+    ///
+    /// let mut buf = BytesMut::from(&b"too long\nok\n"[..]);
+    /// assert!(codec.decode(&mut buf).is_err());
+    /// assert!(codec.decode(&mut buf).is_err());
+    /// # }
+    /// ```
+    ///
+    /// [`LengthError`]: struct.LengthError.html
+    pub fn set_resync(&mut self, val: bool) {
+        self.resync = val;
+    }
+
+    fn length_error(&self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Other,
+            LengthError {
+                max_length: self.max_length,
+            },
+        )
+    }
+
     fn discard(&mut self, newline_offset: Option<usize>, read_to: usize, buf: &mut BytesMut) {
         let discard_to = if let Some(offset) = newline_offset {
             // If we found a newline, discard up to that offset and
@@ -122,6 +189,10 @@ impl Decoder for LinesCodec {
     type Error = io::Error;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, io::Error> {
+        if self.is_poisoned {
+            return Err(self.length_error());
+        }
+
         loop {
             // Determine how far into the buffer we'll search for a newline. If
             // there's no max_length set, we'll read to the end of the buffer.
@@ -147,12 +218,13 @@ impl Decoder for LinesCodec {
                 } else if buf.len() > self.max_length {
                     // Reached the maximum length without finding a
                     // newline, return an error and start discarding on the
-                    // next call.
-                    self.is_discarding = true;
-                    Err(io::Error::new(
-                        io::ErrorKind::Other,
-                        "line length limit exceeded",
-                    ))
+                    // next call, or keep failing if not resynchronizing.
+                    if self.resync {
+                        self.is_discarding = true;
+                    } else {
+                        self.is_poisoned = true;
+                    }
+                    Err(self.length_error())
                 } else {
                     // We didn't find a line or reach the length limit, so the next
                     // call will resume searching at the current offset.
@@ -180,6 +252,13 @@ impl Decoder for LinesCodec {
             }
         })
     }
+
+    fn recover(&mut self, _buf: &mut BytesMut) -> bool {
+        // Lines which are not UTF-8 have already been consumed, and the
+        // remainder of a line over the length limit is discarded by the next
+        // call to `decode` unless the codec is poisoned.
+        !self.is_poisoned
+    }
 }
 
 impl Encoder for LinesCodec {
@@ -187,9 +266,34 @@ impl Encoder for LinesCodec {
     type Error = io::Error;
 
     fn encode(&mut self, line: String, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&line, buf)
+    }
+}
+
+impl EncodeRef for LinesCodec {
+    fn encode_ref(&mut self, line: &String, buf: &mut BytesMut) -> Result<(), io::Error> {
         buf.reserve(line.len() + 1);
         buf.put(line);
         buf.put_u8(b'\n');
         Ok(())
     }
 }
+
+impl LengthError {
+    /// Returns the maximum line length that was exceeded.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl StdError for LengthError {
+    fn description(&self) -> &str {
+        "line length limit exceeded"
+    }
+}
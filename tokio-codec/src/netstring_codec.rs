@@ -0,0 +1,187 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use std::{cmp, io, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
+
+/// Maximum number of digits of the length of a netstring.
+const MAX_DIGITS: usize = 20;
+
+/// Most space reserved up front for a payload, so that a netstring announcing
+/// a huge length cannot make the buffer allocate before the data arrives.
+const MAX_RESERVE: usize = 8 * 1024;
+
+/// A `Codec` implementation for [netstrings].
+///
+/// A netstring is the decimal length of its payload, followed by `:`, the
+/// payload, and `,`. For example, `hello world!` is encoded as
+/// `12:hello world!,`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bytes;
+/// # extern crate tokio_codec;
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_codec::{Decoder, Encoder, NetstringCodec};
+///
+/// # fn main() {
+/// let mut codec = NetstringCodec::new();
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(Bytes::from("hello world!"), &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"12:hello world!,");
+///
+/// let frame = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(&frame[..], b"hello world!");
+/// # }
+/// ```
+///
+/// [netstrings]: https://cr.yp.to/proto/netstrings.txt
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct NetstringCodec {
+    // The maximum length of a payload.
+    max_length: usize,
+
+    // The length of the payload being read, once its head has been read.
+    data_length: Option<usize>,
+}
+
+impl NetstringCodec {
+    /// Returns a `NetstringCodec` for framing data as netstrings.
+    ///
+    /// # Note
+    ///
+    /// The returned `NetstringCodec` will **not** have an upper bound on the
+    /// length of a payload, so a peer can make it buffer as much data as it
+    /// sends. Use [`new_with_max_length`] for any codec that will be exposed
+    /// to untrusted input.
+    ///
+    /// [`new_with_max_length`]: #method.new_with_max_length
+    pub fn new() -> NetstringCodec {
+        NetstringCodec::new_with_max_length(usize::MAX)
+    }
+
+    /// Returns a `NetstringCodec` with a maximum payload length limit.
+    ///
+    /// If this is set, calls to `NetstringCodec::decode` will return an error
+    /// when the length of a netstring exceeds the limit, as soon as its
+    /// length is read. As the stream is then left in the middle of the
+    /// netstring, it cannot be decoded any further.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any `NetstringCodec`
+    /// which will be exposed to untrusted input. Otherwise, a peer can make
+    /// the buffer that holds the payload being read grow to any size.
+    pub fn new_with_max_length(max_length: usize) -> NetstringCodec {
+        NetstringCodec {
+            max_length,
+            data_length: None,
+        }
+    }
+
+    /// Returns the maximum payload length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Reads the length of the next netstring.
+    fn decode_head(&mut self, buf: &mut BytesMut) -> Result<Option<usize>, io::Error> {
+        let mut len = 0usize;
+
+        for (i, &b) in buf.iter().enumerate() {
+            match b {
+                b':' if i > 0 => {
+                    if len > self.max_length {
+                        return Err(length_exceeded());
+                    }
+
+                    buf.advance(i + 1);
+                    return Ok(Some(len));
+                }
+                // Leading zeros are not allowed
+                b'0'..=b'9' if i < MAX_DIGITS && (i == 0 || buf[0] != b'0') => {
+                    len = len
+                        .checked_mul(10)
+                        .and_then(|len| len.checked_add((b - b'0') as usize))
+                        .ok_or_else(length_exceeded)?;
+                }
+                _ => return Err(invalid_netstring()),
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn invalid_netstring() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid netstring")
+}
+
+fn length_exceeded() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "netstring length limit exceeded",
+    )
+}
+
+impl Decoder for NetstringCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        let len = match self.data_length {
+            Some(len) => len,
+            None => match self.decode_head(buf)? {
+                Some(len) => {
+                    self.data_length = Some(len);
+
+                    // Make room for the payload and the trailing comma, but
+                    // only allocate as much as a peer has a chance of sending
+                    // soon.
+                    let rem = len.saturating_add(1).saturating_sub(buf.len());
+                    buf.reserve(cmp::min(rem, MAX_RESERVE));
+                    len
+                }
+                None => return Ok(None),
+            },
+        };
+
+        if buf.len() <= len {
+            return Ok(None);
+        }
+
+        if buf[len] != b',' {
+            return Err(invalid_netstring());
+        }
+
+        self.data_length = None;
+
+        let data = buf.split_to(len);
+        buf.advance(1);
+
+        Ok(Some(data))
+    }
+}
+
+impl Encoder for NetstringCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&data, buf)
+    }
+}
+
+impl EncodeRef for NetstringCodec {
+    fn encode_ref(&mut self, data: &Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        let len = data.len().to_string();
+
+        buf.reserve(len.len() + data.len() + 2);
+        buf.put(len);
+        buf.put_u8(b':');
+        buf.put(data);
+        buf.put_u8(b',');
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use delimited::Delimited;
+use std::{io, usize};
+use tokio_io::_tokio_codec::{Decoder, EncodeRef, Encoder};
+
+const END: u8 = 0xc0;
+const ESC: u8 = 0xdb;
+const ESC_END: u8 = 0xdc;
+const ESC_ESC: u8 = 0xdd;
+
+/// A `Codec` implementation for frames encoded with the Serial Line Internet
+/// Protocol (SLIP), as specified by [RFC 1055].
+///
+/// Frames are terminated by an `END` byte (`0xc0`). `END` and `ESC` (`0xdb`)
+/// bytes in the payload are escaped as `ESC ESC_END` and `ESC ESC_ESC`.
+///
+/// As recommended by the RFC, each frame is encoded preceded by an `END`
+/// byte as well, which flushes any line noise received before the frame.
+/// When decoding, empty frames are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bytes;
+/// # extern crate tokio_codec;
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_codec::{Decoder, Encoder, SlipCodec};
+///
+/// # fn main() {
+/// let mut codec = SlipCodec::new();
+/// let mut buf = BytesMut::new();
+///
+/// codec.encode(Bytes::from(&b"a\xc0b"[..]), &mut buf).unwrap();
+/// assert_eq!(&buf[..], b"\xc0a\xdb\xdcb\xc0");
+///
+/// let frame = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(&frame[..], b"a\xc0b");
+/// # }
+/// ```
+///
+/// [RFC 1055]: https://tools.ietf.org/html/rfc1055
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SlipCodec {
+    // Splits the stream on `END` bytes.
+    frames: Delimited,
+
+    // The maximum length of a decoded frame.
+    max_length: usize,
+}
+
+impl SlipCodec {
+    /// Returns a `SlipCodec` for framing data with SLIP.
+    ///
+    /// # Note
+    ///
+    /// The returned `SlipCodec` will not have an upper bound on the length of
+    /// a buffered frame. See the documentation for [`new_with_max_length`]
+    /// for information on why this could be a potential security risk.
+    ///
+    /// [`new_with_max_length`]: #method.new_with_max_length
+    pub fn new() -> SlipCodec {
+        SlipCodec::new_with_max_length(usize::MAX)
+    }
+
+    /// Returns a `SlipCodec` with a maximum frame length limit.
+    ///
+    /// If this is set, calls to `SlipCodec::decode` will return an error when
+    /// a frame exceeds the length limit. Subsequent calls will discard the
+    /// remainder of that frame until an `END` byte is reached, after which
+    /// decoding resumes with the next frame.
+    ///
+    /// # Note
+    ///
+    /// Setting a length limit is highly recommended for any `SlipCodec` which
+    /// will be exposed to untrusted input. Otherwise, the size of the buffer
+    /// that holds the frame currently being read is unbounded.
+    pub fn new_with_max_length(max_length: usize) -> SlipCodec {
+        // Every byte of the frame may be escaped.
+        SlipCodec {
+            frames: Delimited::new(END, max_length.saturating_mul(2)),
+            max_length,
+        }
+    }
+
+    /// Returns the maximum frame length when decoding.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Decoder for SlipCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<BytesMut>, io::Error> {
+        loop {
+            let src = match self.frames.next_frame(buf)? {
+                Some(src) => src,
+                None => return Ok(None),
+            };
+
+            if src.is_empty() {
+                continue;
+            }
+
+            let mut frame = BytesMut::with_capacity(src.len());
+            let mut bytes = src.iter();
+
+            while let Some(&b) = bytes.next() {
+                let b = match b {
+                    ESC => match bytes.next() {
+                        Some(&ESC_END) => END,
+                        Some(&ESC_ESC) => ESC,
+                        _ => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "invalid SLIP escape sequence",
+                            ));
+                        }
+                    },
+                    b => b,
+                };
+
+                frame.put_u8(b);
+            }
+
+            if frame.len() > self.max_length {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "frame length limit exceeded",
+                ));
+            }
+
+            return Ok(Some(frame));
+        }
+    }
+}
+
+impl Encoder for SlipCodec {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn encode(&mut self, data: Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&data, buf)
+    }
+}
+
+impl EncodeRef for SlipCodec {
+    fn encode_ref(&mut self, data: &Bytes, buf: &mut BytesMut) -> Result<(), io::Error> {
+        let escaped = data.iter().filter(|b| **b == END || **b == ESC).count();
+        buf.reserve(data.len() + escaped + 2);
+
+        buf.put_u8(END);
+
+        for &b in data.iter() {
+            match b {
+                END => buf.put_slice(&[ESC, ESC_END]),
+                ESC => buf.put_slice(&[ESC, ESC_ESC]),
+                b => buf.put_u8(b),
+            }
+        }
+
+        buf.put_u8(END);
+
+        Ok(())
+    }
+}
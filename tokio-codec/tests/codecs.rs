@@ -2,7 +2,10 @@ extern crate bytes;
 extern crate tokio_codec;
 
 use bytes::{BufMut, Bytes, BytesMut};
-use tokio_codec::{BytesCodec, Decoder, Encoder, LinesCodec};
+use tokio_codec::{
+    AnyDelimiterCodec, BytesCodec, CobsCodec, Decoder, Encoder, LengthError, LinesCodec,
+    NetstringCodec, SlipCodec,
+};
 
 #[test]
 fn bytes_decoder() {
@@ -189,6 +192,36 @@ fn lines_decoder_max_length_newline_between_decodes() {
     assert_eq!("hello", codec.decode(buf).unwrap().unwrap());
 }
 
+#[test]
+fn lines_decoder_max_length_error() {
+    let mut codec = LinesCodec::new_with_max_length(4);
+    let buf = &mut BytesMut::new();
+
+    buf.put("too long\n");
+    let err = codec.decode(buf).unwrap_err();
+    let err = err
+        .get_ref()
+        .unwrap()
+        .downcast_ref::<LengthError>()
+        .unwrap();
+    assert_eq!(4, err.max_length());
+}
+
+#[test]
+fn lines_decoder_max_length_no_resync() {
+    let mut codec = LinesCodec::new_with_max_length(4);
+    codec.set_resync(false);
+    assert!(!codec.resync());
+
+    let buf = &mut BytesMut::new();
+
+    buf.put("ok\ntoo long\nok\n");
+    assert_eq!("ok", codec.decode(buf).unwrap().unwrap());
+    assert!(codec.decode(buf).is_err());
+    assert!(codec.decode(buf).is_err());
+    assert!(codec.decode_eof(buf).is_err());
+}
+
 #[test]
 fn lines_encoder() {
     let mut codec = LinesCodec::new();
@@ -200,3 +233,279 @@ fn lines_encoder() {
     codec.encode(String::from("line 2"), &mut buf).unwrap();
     assert_eq!("line 1\nline 2\n", buf);
 }
+
+#[test]
+fn any_delimiter_decoder() {
+    let mut codec = AnyDelimiterCodec::new(
+        vec![b",".to_vec(), b";".to_vec(), b"\0".to_vec()],
+        b",".to_vec(),
+    );
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("chunk 1,chunk 2;\0chunk 3\0chunk 4");
+    assert_eq!("chunk 1", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("chunk 2", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("chunk 3", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(",");
+    assert_eq!("chunk 4", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+    buf.put("tail");
+    assert_eq!("tail", codec.decode_eof(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn any_delimiter_decoder_max_length() {
+    const MAX_LENGTH: usize = 7;
+
+    let mut codec = AnyDelimiterCodec::new_with_max_length(
+        vec![b",".to_vec(), b";".to_vec()],
+        b",".to_vec(),
+        MAX_LENGTH,
+    );
+    let buf = &mut BytesMut::new();
+
+    buf.reserve(200);
+    buf.put("chunk 1 is too long;chunk 2;chunk 3 is also too long,chunk 4");
+
+    assert!(codec.decode(buf).is_err());
+    assert_eq!("chunk 2", codec.decode(buf).unwrap().unwrap());
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!("chunk 4", codec.decode_eof(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn any_delimiter_decoder_max_length_bursts() {
+    let mut codec =
+        AnyDelimiterCodec::new_with_max_length(vec![b"\0".to_vec()], b"\0".to_vec(), 10);
+    let buf = &mut BytesMut::new();
+
+    buf.reserve(200);
+    buf.put("chunk ");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("too long!");
+    assert!(codec.decode(buf).is_err());
+    buf.put("\0ok\0");
+    assert_eq!("ok", codec.decode(buf).unwrap().unwrap());
+}
+
+#[test]
+fn any_delimiter_decoder_sequences() {
+    let mut codec = AnyDelimiterCodec::new_with_max_length(
+        vec![b";".to_vec(), b";\r\n".to_vec(), b"--".to_vec()],
+        b";\r\n".to_vec(),
+        7,
+    );
+    let buf = &mut BytesMut::new();
+
+    buf.reserve(200);
+    buf.put("chunk 1;");
+    // `;\r\n` may still match
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("\r\nchunk 2-");
+    assert_eq!("chunk 1", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("-chunk 3 is too long-");
+    assert_eq!("chunk 2", codec.decode(buf).unwrap().unwrap());
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("-chunk 4;\r");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("\nchunk 5;");
+    assert_eq!("chunk 4", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!("chunk 5", codec.decode_eof(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn any_delimiter_round_trip() {
+    let mut codec = AnyDelimiterCodec::new(vec![b";\0".to_vec()], b";\0".to_vec());
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("chunk;1"), &mut buf).unwrap();
+    codec.encode(Bytes::from("chunk\02"), &mut buf).unwrap();
+    assert_eq!("chunk;1", codec.decode(&mut buf).unwrap().unwrap());
+    assert_eq!("chunk\02", codec.decode(&mut buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(&mut buf).unwrap());
+}
+
+#[test]
+fn any_delimiter_encoder() {
+    let mut codec = AnyDelimiterCodec::new(vec![b";\r\n".to_vec()], b";\r\n".to_vec());
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("chunk 1"), &mut buf).unwrap();
+    assert_eq!("chunk 1;\r\n", buf);
+
+    codec.encode(Bytes::from("chunk 2"), &mut buf).unwrap();
+    assert_eq!("chunk 1;\r\nchunk 2;\r\n", buf);
+}
+
+#[test]
+fn netstring_decoder() {
+    let mut codec = NetstringCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("5:hello,0:,1");
+    assert_eq!("hello", codec.decode(buf).unwrap().unwrap());
+    assert_eq!("", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(":a");
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(",");
+    assert_eq!("a", codec.decode(buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn netstring_decoder_invalid() {
+    for input in &["05:hello,", ":", "x:", "5:hello;", "-1:"] {
+        let mut codec = NetstringCodec::new();
+        let buf = &mut BytesMut::from(input.as_bytes());
+        assert!(codec.decode(buf).is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn netstring_decoder_max_length() {
+    let mut codec = NetstringCodec::new_with_max_length(4);
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("4:abcd,5:");
+    assert_eq!("abcd", codec.decode(buf).unwrap().unwrap());
+    assert!(codec.decode(buf).is_err());
+
+    let mut codec = NetstringCodec::new_with_max_length(4);
+    let buf = &mut BytesMut::from(&b"99999999999999999999999:"[..]);
+    assert!(codec.decode(buf).is_err());
+}
+
+#[test]
+fn netstring_decoder_huge_length() {
+    let mut codec = NetstringCodec::new();
+    let buf = &mut BytesMut::from(&b"9999999999999999999:abc"[..]);
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert!(buf.capacity() < 64 * 1024);
+}
+
+#[test]
+fn netstring_encoder() {
+    let mut codec = NetstringCodec::new();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::from("hello"), &mut buf).unwrap();
+    codec.encode(Bytes::new(), &mut buf).unwrap();
+    assert_eq!(&buf[..], b"5:hello,0:,");
+}
+
+#[test]
+fn cobs_decoder() {
+    let mut codec = CobsCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put(&b"\x00\x01\x01\x00\x03ab\x00\x02"[..]);
+    assert_eq!(&b"\x00"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert_eq!(&b"ab"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(&b"c\x00"[..]);
+    assert_eq!(&b"c"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn cobs_decoder_invalid() {
+    let mut codec = CobsCodec::new();
+    let buf = &mut BytesMut::from(&b"\x05ab\x00\x02c\x00"[..]);
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(&b"c"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn cobs_decoder_max_length() {
+    let mut codec = CobsCodec::new_with_max_length(3);
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put(&b"\x04abc\x00\x06ab"[..]);
+    assert_eq!(&b"abc"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(&b"cde"[..]);
+    assert!(codec.decode(buf).is_err());
+    buf.put(&b"f\x00\x02g\x00"[..]);
+    assert_eq!(&b"g"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn cobs_encoder() {
+    let mut codec = CobsCodec::new();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Bytes::new(), &mut buf).unwrap();
+    codec.encode(Bytes::from(&b"\x00"[..]), &mut buf).unwrap();
+    codec
+        .encode(Bytes::from(&b"ab\x00c"[..]), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..], b"\x01\x00\x01\x01\x00\x03ab\x02c\x00");
+
+    let mut buf = BytesMut::new();
+    let data = vec![1; 300];
+    codec.encode(Bytes::from(data.clone()), &mut buf).unwrap();
+    assert_eq!(buf.len(), 303);
+    assert_eq!(buf[0], 0xff);
+    assert_eq!(buf[255], 47);
+    assert_eq!(&data[..], &codec.decode(&mut buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn slip_decoder() {
+    let mut codec = SlipCodec::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put(&b"\xc0\xc0a\xdb\xdcb\xdb\xddc\xc0d"[..]);
+    assert_eq!(
+        &b"a\xc0b\xdbc"[..],
+        &codec.decode(buf).unwrap().unwrap()[..]
+    );
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put(&b"\xc0"[..]);
+    assert_eq!(&b"d"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert_eq!(None, codec.decode(buf).unwrap());
+}
+
+#[test]
+fn slip_decoder_invalid() {
+    let mut codec = SlipCodec::new();
+    let buf = &mut BytesMut::from(&b"a\xdbb\xc0c\xc0"[..]);
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(&b"c"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn slip_decoder_max_length() {
+    let mut codec = SlipCodec::new_with_max_length(2);
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put(&b"\xdb\xdc\xdb\xdd\xc0abcde"[..]);
+    assert_eq!(&b"\xc0\xdb"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+    assert!(codec.decode(buf).is_err());
+    buf.put(&b"f\xc0g\xc0"[..]);
+    assert_eq!(&b"g"[..], &codec.decode(buf).unwrap().unwrap()[..]);
+}
+
+#[test]
+fn slip_encoder() {
+    let mut codec = SlipCodec::new();
+    let mut buf = BytesMut::new();
+
+    codec
+        .encode(Bytes::from(&b"a\xc0\xdb"[..]), &mut buf)
+        .unwrap();
+    assert_eq!(&buf[..], b"\xc0a\xdb\xdc\xdb\xdd\xc0");
+}
@@ -4,9 +4,9 @@ extern crate tokio_codec;
 extern crate tokio_io;
 
 use bytes::{Buf, BufMut, BytesMut, IntoBuf};
-use futures::{Future, Stream};
-use std::io::{self, Read};
-use tokio_codec::{Decoder, Encoder, Framed, FramedParts};
+use futures::{Future, Sink, Stream};
+use std::io::{self, Cursor, Read};
+use tokio_codec::{Decoder, Encoder, Framed, FramedParts, FramedRead, FramedWrite, LinesCodec};
 use tokio_io::AsyncRead;
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -92,3 +92,77 @@ fn external_buf_does_not_shrink() {
 
     assert_eq!(read_buf.capacity(), INITIAL_CAPACITY * 2);
 }
+
+#[test]
+fn upgrade_keeps_read_buf() {
+    let io = Cursor::new(b"\x00\x00\x00\x01hello\n".to_vec());
+    let framed = Framed::from_parts(FramedParts::new(io, U32Codec));
+
+    let (num, framed) = framed.into_future().map_err(|e| e.0).wait().unwrap();
+    assert_eq!(num, Some(1));
+
+    let parts = framed.into_parts().map_codec(|_| LinesCodec::new());
+    assert_eq!(&parts.read_buf[..], b"hello\n");
+
+    let framed = Framed::from_parts(parts);
+    let lines = framed.collect().wait().unwrap();
+    assert_eq!(lines, vec!["hello".to_string()]);
+}
+
+#[test]
+fn split_reunite_keeps_buffers() {
+    let io = Cursor::new(b"\x00\x00\x00\x01\x00\x00\x00\x02".to_vec());
+    let framed = Framed::from_parts(FramedParts::new(io, U32Codec));
+    let (sink, stream) = framed.split();
+
+    let (num, stream) = stream.into_future().map_err(|e| e.0).wait().unwrap();
+    assert_eq!(num, Some(1));
+
+    let framed = stream.reunite(sink).unwrap();
+    let FramedParts { read_buf, .. } = framed.into_parts();
+    assert_eq!(&read_buf[..], b"\x00\x00\x00\x02");
+}
+
+#[test]
+fn framed_read_parts() {
+    let mut parts = FramedParts::new(DontReadIntoThis, U32Codec);
+    parts.read_buf = vec![0, 0, 0, 42, 0, 0].into();
+
+    let framed = FramedRead::from_parts(parts);
+    let (num, framed) = framed.into_future().map_err(|e| e.0).wait().unwrap();
+    assert_eq!(num, Some(42));
+
+    let parts = framed.into_parts();
+    assert_eq!(&parts.read_buf[..], &[0, 0][..]);
+    assert!(parts.write_buf.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn framed_read_parts_with_write_buf() {
+    let mut parts = FramedParts::new(DontReadIntoThis, U32Codec);
+    parts.write_buf = vec![0].into();
+
+    FramedRead::from_parts(parts);
+}
+
+#[test]
+fn framed_write_parts() {
+    let framed = FramedWrite::new(Cursor::new(vec![]), U32Codec);
+    let mut framed = framed.send(42).wait().unwrap();
+
+    // The frame is written, and the next one only buffered
+    assert!(framed.start_send(43).unwrap().is_ready());
+
+    let parts = framed.into_parts();
+    assert_eq!(&parts.io.get_ref()[..], &[0, 0, 0, 42][..]);
+    assert_eq!(&parts.write_buf[..], &[0, 0, 0, 43][..]);
+    assert!(parts.read_buf.is_empty());
+
+    let framed = FramedWrite::from_parts(parts);
+    let framed = framed.flush().wait().unwrap();
+    assert_eq!(
+        &framed.get_ref().get_ref()[..],
+        &[0, 0, 0, 42, 0, 0, 0, 43][..]
+    );
+}
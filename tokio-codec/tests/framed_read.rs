@@ -3,7 +3,7 @@ extern crate futures;
 extern crate tokio_codec;
 extern crate tokio_io;
 
-use tokio_codec::{Decoder, FramedRead};
+use tokio_codec::{DecodeRecovery, Decoder, FramedRead, LinesCodec};
 use tokio_io::AsyncRead;
 
 use bytes::{Buf, BytesMut, IntoBuf};
@@ -192,6 +192,88 @@ fn multi_frames_on_eof() {
     assert_eq!(Ready(None), framed.poll().unwrap());
 }
 
+/// Decodes `u32`s, failing on odd values once they have been consumed.
+struct EvenDecoder;
+
+impl Decoder for EvenDecoder {
+    type Item = u32;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<u32>> {
+        match U32Decoder.decode(buf)? {
+            Some(n) if n % 2 == 1 => Err(io::Error::new(io::ErrorKind::InvalidData, "odd")),
+            res => Ok(res),
+        }
+    }
+
+    fn recover(&mut self, _buf: &mut BytesMut) -> bool {
+        true
+    }
+}
+
+const EVEN_AND_ODD: &[u8] = b"\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x02";
+
+#[test]
+fn decode_recovery_continue() {
+    let mut framed = FramedRead::new(EVEN_AND_ODD, EvenDecoder);
+    assert_eq!(DecodeRecovery::Continue, framed.decode_recovery());
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+    assert!(framed.poll().is_err());
+    assert_eq!(Ready(Some(2)), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn decode_recovery_fail() {
+    let mut framed = FramedRead::new(EVEN_AND_ODD, EvenDecoder);
+    framed.set_decode_recovery(DecodeRecovery::Fail);
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+    assert!(framed.poll().is_err());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn decode_recovery_skip() {
+    let mut framed = FramedRead::new(EVEN_AND_ODD, EvenDecoder);
+    framed.set_decode_recovery(DecodeRecovery::Skip);
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+    assert_eq!(Ready(Some(2)), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn decode_recovery_skip_lines() {
+    let data = b"one\ntoo long\n\xff\nthree\n";
+
+    let mut framed = FramedRead::new(&data[..], LinesCodec::new_with_max_length(5));
+    framed.set_decode_recovery(DecodeRecovery::Skip);
+    assert_eq!(Ready(Some("one".to_string())), framed.poll().unwrap());
+    assert_eq!(Ready(Some("three".to_string())), framed.poll().unwrap());
+    assert_eq!(Ready(None), framed.poll().unwrap());
+}
+
+#[test]
+fn decode_recovery_skip_poisoned_lines() {
+    let mut codec = LinesCodec::new_with_max_length(5);
+    codec.set_resync(false);
+
+    let mut framed = FramedRead::new(&b"too long\nok\n"[..], codec);
+    framed.set_decode_recovery(DecodeRecovery::Skip);
+    assert!(framed.poll().is_err());
+    assert!(framed.poll().is_err());
+}
+
+#[test]
+fn decode_recovery_skip_unsupported() {
+    // `U32Decoder` does not implement `recover`
+    let data = [0; 5];
+
+    let mut framed = FramedRead::new(&data[..], U32Decoder);
+    framed.set_decode_recovery(DecodeRecovery::Skip);
+    assert_eq!(Ready(Some(0)), framed.poll().unwrap());
+    assert!(framed.poll().is_err());
+}
+
 // ===== Mock ======
 
 struct Mock {
@@ -3,7 +3,7 @@ extern crate futures;
 extern crate tokio_codec;
 extern crate tokio_io;
 
-use tokio_codec::{Encoder, FramedWrite};
+use tokio_codec::{EncodeRef, Encoder, FramedWrite};
 use tokio_io::AsyncWrite;
 
 use bytes::{BufMut, BytesMut};
@@ -34,6 +34,12 @@ impl Encoder for U32Encoder {
     }
 }
 
+impl EncodeRef for U32Encoder {
+    fn encode_ref(&mut self, item: &u32, dst: &mut BytesMut) -> io::Result<()> {
+        self.encode(*item, dst)
+    }
+}
+
 #[test]
 fn write_multi_frame_in_packet() {
     let mock = mock! {
@@ -54,6 +60,25 @@ fn write_multi_frame_in_packet() {
     assert_eq!(0, framed.get_ref().calls.len());
 }
 
+#[test]
+fn write_multi_frame_by_ref() {
+    let mock = mock! {
+        Ok(b"\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00".to_vec()),
+    };
+
+    let frames = [0, 1];
+
+    let mut framed = FramedWrite::new(mock, U32Encoder);
+    assert!(framed.start_send_ref(&frames[0]).unwrap().is_ready());
+    assert!(framed.start_send_ref(&frames[1]).unwrap().is_ready());
+
+    // The same frame may be sent again
+    assert!(framed.start_send_ref(&frames[0]).unwrap().is_ready());
+
+    assert!(framed.poll_complete().unwrap().is_ready());
+    assert_eq!(0, framed.get_ref().calls.len());
+}
+
 #[test]
 fn write_hits_backpressure() {
     const ITER: usize = 2 * 1024;
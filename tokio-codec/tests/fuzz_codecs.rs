@@ -0,0 +1,148 @@
+extern crate bytes;
+extern crate rand;
+extern crate tokio_codec;
+
+use bytes::{Bytes, BytesMut};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio_codec::{CobsCodec, Decoder, Encoder, NetstringCodec, SlipCodec};
+
+const ITERATIONS: usize = 200;
+
+/// Seed used unless `TOKIO_CODEC_FUZZ_SEED` is set, so that runs are
+/// reproducible.
+const DEFAULT_SEED: u64 = 0x746f_6b69_6f00;
+
+/// Returns a generator seeded from `TOKIO_CODEC_FUZZ_SEED`, which can be set to
+/// explore other inputs.
+fn rng() -> StdRng {
+    let seed = match std::env::var("TOKIO_CODEC_FUZZ_SEED") {
+        Ok(seed) => seed.parse().expect("invalid TOKIO_CODEC_FUZZ_SEED"),
+        Err(_) => DEFAULT_SEED,
+    };
+
+    StdRng::seed_from_u64(seed)
+}
+
+/// Returns a random frame, biased towards the bytes with a special meaning
+/// to the codecs.
+fn frame<R: Rng>(rng: &mut R) -> Vec<u8> {
+    const SPECIAL: &[u8] = &[0x00, 0x01, 0xff, 0xc0, 0xdb, 0xdc, 0xdd, b':', b','];
+
+    let len = match rng.gen_range(0, 4) {
+        0 => 0,
+        1 => rng.gen_range(250, 260),
+        _ => rng.gen_range(1, 600),
+    };
+
+    (0..len)
+        .map(|_| {
+            if rng.gen() {
+                SPECIAL[rng.gen_range(0, SPECIAL.len())]
+            } else {
+                rng.gen()
+            }
+        })
+        .collect()
+}
+
+/// Encodes random frames, then decodes them from chunks of random sizes.
+fn round_trip<C, F>(new_codec: F, skips_empty: bool)
+where
+    C: Decoder<Item = BytesMut, Error = std::io::Error>
+        + Encoder<Item = Bytes, Error = std::io::Error>,
+    F: Fn() -> C,
+{
+    let mut rng = rng();
+
+    for _ in 0..ITERATIONS {
+        let mut codec = new_codec();
+        let frames: Vec<_> = (0..rng.gen_range(1, 10)).map(|_| frame(&mut rng)).collect();
+
+        let mut encoded = BytesMut::new();
+        for frame in &frames {
+            codec.encode(Bytes::from(&frame[..]), &mut encoded).unwrap();
+        }
+
+        let mut buf = BytesMut::new();
+        let mut decoded = vec![];
+
+        while !encoded.is_empty() {
+            let n = rng.gen_range(1, encoded.len() + 1);
+            buf.extend_from_slice(&encoded.split_to(n));
+
+            while let Some(frame) = codec.decode(&mut buf).unwrap() {
+                decoded.push(frame.to_vec());
+            }
+        }
+
+        let expected: Vec<_> = frames
+            .into_iter()
+            .filter(|f| !skips_empty || !f.is_empty())
+            .collect();
+
+        assert!(buf.is_empty());
+        assert_eq!(decoded, expected);
+    }
+}
+
+/// Decodes random bytes, which must not panic nor buffer more than the
+/// maximum frame length.
+fn garbage<C, F>(new_codec: F, max_length: usize, resync: bool)
+where
+    C: Decoder<Item = BytesMut, Error = std::io::Error>,
+    F: Fn() -> C,
+{
+    let mut rng = rng();
+
+    for _ in 0..ITERATIONS {
+        let mut codec = new_codec();
+        let mut buf = BytesMut::new();
+
+        for _ in 0..rng.gen_range(1, 20) {
+            buf.extend_from_slice(&frame(&mut rng));
+
+            loop {
+                match codec.decode(&mut buf) {
+                    Ok(Some(frame)) => assert!(frame.len() <= max_length),
+                    Ok(None) => break,
+                    Err(_) if resync => {}
+                    Err(_) => return,
+                }
+            }
+
+            // Byte stuffing may double the length of a frame
+            assert!(buf.len() <= 2 * max_length + 2);
+        }
+    }
+}
+
+#[test]
+fn netstring_round_trip() {
+    round_trip(NetstringCodec::new, false);
+}
+
+#[test]
+fn netstring_garbage() {
+    garbage(|| NetstringCodec::new_with_max_length(64), 64, false);
+}
+
+#[test]
+fn cobs_round_trip() {
+    round_trip(CobsCodec::new, false);
+}
+
+#[test]
+fn cobs_garbage() {
+    garbage(|| CobsCodec::new_with_max_length(64), 64, true);
+}
+
+#[test]
+fn slip_round_trip() {
+    round_trip(SlipCodec::new, true);
+}
+
+#[test]
+fn slip_garbage() {
+    garbage(|| SlipCodec::new_with_max_length(64), 64, true);
+}
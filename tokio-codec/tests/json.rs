@@ -0,0 +1,70 @@
+#![cfg(feature = "json")]
+
+extern crate bytes;
+#[macro_use]
+extern crate serde_derive;
+extern crate tokio_codec;
+
+use bytes::{BufMut, BytesMut};
+use tokio_codec::{Decoder, Encoder, Json};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Message {
+    id: u32,
+    body: String,
+}
+
+fn message(id: u32, body: &str) -> Message {
+    Message {
+        id,
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn json_decoder() {
+    let mut codec = Json::<Message>::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("{\"id\":1,\"body\":\"a\"}\n\r\n{\"id\":2,");
+    assert_eq!(Some(message(1, "a")), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    buf.put("\"body\":\"b\"}\r\n{\"id\":3,\"body\":\"c\"}");
+    assert_eq!(Some(message(2, "b")), codec.decode(buf).unwrap());
+    assert_eq!(None, codec.decode(buf).unwrap());
+    assert_eq!(Some(message(3, "c")), codec.decode_eof(buf).unwrap());
+    assert_eq!(None, codec.decode_eof(buf).unwrap());
+}
+
+#[test]
+fn json_decoder_invalid() {
+    let mut codec = Json::<Message>::new();
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("{\"id\":\"x\"}\n{\"id\":1,\"body\":\"a\"}\n");
+    assert!(codec.decode(buf).is_err());
+    assert_eq!(Some(message(1, "a")), codec.decode(buf).unwrap());
+}
+
+#[test]
+fn json_decoder_max_length() {
+    let mut codec = Json::<Message>::new_with_max_length(20);
+    assert_eq!(20, codec.max_length());
+
+    let buf = &mut BytesMut::new();
+    buf.reserve(200);
+    buf.put("{\"id\":1,\"body\":\"too long\"");
+    assert!(codec.decode(buf).is_err());
+    buf.put("}\n{\"id\":2,\"body\":\"b\"}\n");
+    assert_eq!(Some(message(2, "b")), codec.decode(buf).unwrap());
+}
+
+#[test]
+fn json_encoder() {
+    let mut codec = Json::<Message>::new();
+    let mut buf = BytesMut::new();
+
+    codec.encode(message(1, "a\nb"), &mut buf).unwrap();
+    assert_eq!(&buf[..], &b"{\"id\":1,\"body\":\"a\\nb\"}\n"[..]);
+    assert_eq!(Some(message(1, "a\nb")), codec.decode(&mut buf).unwrap());
+}
@@ -32,6 +32,7 @@ mod scheduler;
 
 use self::scheduler::Scheduler;
 
+use tokio_executor::budget;
 use tokio_executor::park::{Park, ParkThread, Unpark};
 use tokio_executor::{Enter, SpawnError};
 
@@ -481,7 +482,9 @@ impl<'a, P: Park> Entered<'a, P> {
             let res = self
                 .executor
                 .borrow()
-                .enter(self.enter, || future.poll_future_notify(&notify, 0));
+                .enter(self.enter, || {
+                    budget::with_budget(|| future.poll_future_notify(&notify, 0))
+                });
 
             match res {
                 Ok(Async::Ready(e)) => return Ok(e),
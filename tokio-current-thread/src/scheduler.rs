@@ -1,4 +1,5 @@
 use super::Borrow;
+use tokio_executor::budget;
 use tokio_executor::park::Unpark;
 use tokio_executor::Enter;
 
@@ -348,7 +349,7 @@ impl<'a, U: Unpark> Scheduled<'a, U> {
     /// Polls the task, returns `true` if the task has completed.
     pub fn tick(&mut self) -> bool {
         // Tick the future
-        let ret = match self.task.0.poll_future_notify(self.notify, 0) {
+        let ret = match budget::with_budget(|| self.task.0.poll_future_notify(self.notify, 0)) {
             Ok(Async::Ready(_)) | Err(_) => true,
             Ok(Async::NotReady) => false,
         };
@@ -0,0 +1,148 @@
+//! Cooperative scheduling budget.
+//!
+//! A task that repeatedly polls a resource which is always ready (a saturated
+//! socket, a channel that is never empty, ...) never returns `NotReady` to its
+//! executor. Without intervention, such a task monopolizes the thread it runs
+//! on and starves every other task scheduled there.
+//!
+//! To prevent this, executors grant each task a fixed number of operations
+//! every time the task is polled by calling [`with_budget`]. Leaf resources
+//! (channels, I/O resources, timers) call [`poll_proceed`] before performing
+//! an operation, and only charge the budget if the operation is ready. Once
+//! the budget is exhausted, [`poll_proceed`] notifies the current task and
+//! returns `NotReady`, causing the task to yield back to the executor. The
+//! task is then scheduled again, giving other tasks a chance to run in the
+//! meantime.
+//!
+//! When no budget has been set, for example when a future is polled outside of
+//! a Tokio executor, [`poll_proceed`] always returns `Ready`.
+//!
+//! [`with_budget`]: fn.with_budget.html
+//! [`poll_proceed`]: fn.poll_proceed.html
+
+use futures::{task, Async};
+
+use std::cell::Cell;
+
+/// Number of operations a task may perform on leaf resources each time it is
+/// polled.
+///
+/// This value is large enough that well-behaved tasks rarely reach it, while
+/// still bounding the time a single misbehaving task can hold the thread.
+const INITIAL: usize = 128;
+
+thread_local!(static CURRENT: Cell<Option<usize>> = Cell::new(None));
+
+/// A unit of budget taken by [`poll_proceed`].
+///
+/// The unit is given back to the budget when the `Proceed` is dropped, unless
+/// [`made_progress`] was called, so that polling a resource which is not
+/// ready does not count as an operation.
+///
+/// [`poll_proceed`]: fn.poll_proceed.html
+/// [`made_progress`]: #method.made_progress
+#[derive(Debug)]
+#[must_use]
+pub struct Proceed {
+    // Whether the unit is to be given back to the budget when dropped.
+    refund: bool,
+}
+
+/// Run `f` with a fresh cooperative scheduling budget.
+///
+/// Executors call this function around each poll of a task. Any budget that
+/// was set before calling `with_budget` is restored once `f` returns, even if
+/// `f` panics.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_executor;
+/// use tokio_executor::budget;
+///
+/// # fn main() {
+/// // Outside of a budget, resources may always proceed.
+/// assert!(budget::has_remaining());
+///
+/// budget::with_budget(|| {
+///     assert!(budget::has_remaining());
+/// });
+/// # }
+/// ```
+pub fn with_budget<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    CURRENT.with(|cell| {
+        struct Reset<'a>(&'a Cell<Option<usize>>, Option<usize>);
+
+        impl<'a> Drop for Reset<'a> {
+            fn drop(&mut self) {
+                self.0.set(self.1);
+            }
+        }
+
+        let _reset = Reset(cell, cell.replace(Some(INITIAL)));
+
+        f()
+    })
+}
+
+/// Take one unit of the current task's budget.
+///
+/// Returns `Ready` if the caller may proceed with the operation. The caller
+/// then calls [`Proceed::made_progress`] if the operation was ready, otherwise
+/// the unit is given back when the [`Proceed`] is dropped.
+///
+/// If the budget has been exhausted, the current task is notified so that it
+/// is scheduled again and `NotReady` is returned. The caller must then return
+/// `NotReady` without performing the operation.
+///
+/// # Panics
+///
+/// This function panics if the budget is exhausted and it is called from
+/// outside of a task context.
+///
+/// [`Proceed`]: struct.Proceed.html
+/// [`Proceed::made_progress`]: struct.Proceed.html#method.made_progress
+pub fn poll_proceed() -> Async<Proceed> {
+    CURRENT.with(|cell| match cell.get() {
+        None => Async::Ready(Proceed { refund: false }),
+        Some(0) => {
+            task::current().notify();
+            Async::NotReady
+        }
+        Some(n) => {
+            cell.set(Some(n - 1));
+            Async::Ready(Proceed { refund: true })
+        }
+    })
+}
+
+/// Returns `true` if the current task has budget remaining.
+///
+/// Unlike [`poll_proceed`], this does not consume any budget.
+///
+/// [`poll_proceed`]: fn.poll_proceed.html
+pub fn has_remaining() -> bool {
+    CURRENT.with(|cell| cell.get() != Some(0))
+}
+
+impl Proceed {
+    /// Keeps the unit of budget consumed, as the operation was ready.
+    pub fn made_progress(mut self) {
+        self.refund = false;
+    }
+}
+
+impl Drop for Proceed {
+    fn drop(&mut self) {
+        if self.refund {
+            CURRENT.with(|cell| {
+                if let Some(n) = cell.get() {
+                    cell.set(Some(n + 1));
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use {Executor, SpawnError};
+
+use futures::sync::oneshot;
+use futures::task::AtomicTask;
+use futures::{Async, Future, Poll};
+
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::Arc;
+
+/// Extension trait for spawning a future and retrieving its output.
+///
+/// [`Executor`] only supports fire-and-forget spawning of futures that
+/// complete with `()`. `SpawnWithHandle` is implemented for all executors,
+/// including `Box<Executor>` and `&mut Executor` trait objects, and returns a
+/// [`JoinHandle`] that completes with the output of the spawned future. This
+/// allows code written against the `Executor` trait to await the result of a
+/// task independently of the runtime executing it.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate futures;
+/// # extern crate tokio_executor;
+/// use tokio_executor::{Executor, SpawnWithHandle};
+/// use futures::future::{Future, lazy};
+///
+/// fn compute(executor: &mut Executor) -> Box<Future<Item = u32, Error = ()> + Send> {
+///     let handle = executor.spawn_with_handle(lazy(|| Ok::<_, ()>(42)))
+///         .expect("failed to spawn");
+///
+///     Box::new(handle.map_err(|_| ()))
+/// }
+/// # fn main() {}
+/// ```
+///
+/// [`Executor`]: trait.Executor.html
+/// [`JoinHandle`]: struct.JoinHandle.html
+pub trait SpawnWithHandle: Executor {
+    /// Spawns `future` onto the executor, returning a handle that completes
+    /// with the future's output.
+    ///
+    /// The task keeps running if the returned handle is dropped. It can be
+    /// canceled with [`JoinHandle::abort`].
+    ///
+    /// # Errors
+    ///
+    /// The executor may be unable to spawn tasks, see [`Executor::spawn`].
+    ///
+    /// [`Executor::spawn`]: trait.Executor.html#tymethod.spawn
+    /// [`JoinHandle::abort`]: struct.JoinHandle.html#method.abort
+    fn spawn_with_handle<F>(&mut self, future: F) -> Result<JoinHandle<F::Item, F::Error>, SpawnError>
+    where
+        F: Future + Send + 'static,
+        F::Item: Send + 'static,
+        F::Error: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let abort = AbortHandle::new();
+
+        let task = future.then(move |res| {
+            // The handle may have been dropped, in which case there is no one
+            // interested in the result.
+            let _ = tx.send(res);
+            Ok(())
+        });
+
+        let task = Abortable {
+            future: Some(task),
+            abort: abort.clone(),
+        };
+
+        self.spawn(Box::new(task))?;
+
+        Ok(JoinHandle { rx, abort })
+    }
+}
+
+impl<E: Executor + ?Sized> SpawnWithHandle for E {}
+
+/// A future that completes with the output of a task spawned with
+/// [`SpawnWithHandle::spawn_with_handle`].
+///
+/// [`SpawnWithHandle::spawn_with_handle`]: trait.SpawnWithHandle.html#method.spawn_with_handle
+#[derive(Debug)]
+pub struct JoinHandle<T, E> {
+    rx: oneshot::Receiver<Result<T, E>>,
+    abort: AbortHandle,
+}
+
+/// A handle to cancel a task spawned with
+/// [`SpawnWithHandle::spawn_with_handle`].
+///
+/// An `AbortHandle` is obtained with [`JoinHandle::abort_handle`]. Unlike the
+/// `JoinHandle`, it can be cloned and does not need to be kept by the code
+/// waiting for the task's output.
+///
+/// [`SpawnWithHandle::spawn_with_handle`]: trait.SpawnWithHandle.html#method.spawn_with_handle
+/// [`JoinHandle::abort_handle`]: struct.JoinHandle.html#method.abort_handle
+#[derive(Clone)]
+pub struct AbortHandle {
+    inner: Arc<AbortInner>,
+}
+
+struct AbortInner {
+    /// Set once the task has been aborted
+    aborted: AtomicBool,
+
+    /// The task to notify when aborted
+    task: AtomicTask,
+}
+
+/// Wraps a spawned future, completing early when aborted.
+struct Abortable<F> {
+    /// `None` once the future has been dropped
+    future: Option<F>,
+    abort: AbortHandle,
+}
+
+/// Error returned by a [`JoinHandle`].
+///
+/// The task either completed with an error, or it was dropped before
+/// completing. The latter happens when the task is aborted, when the executor
+/// shuts down, or when the task panics.
+///
+/// [`JoinHandle`]: struct.JoinHandle.html
+#[derive(Debug)]
+pub struct JoinError<E> {
+    /// The error the task completed with, `None` if it was canceled.
+    inner: Option<E>,
+}
+
+impl<T, E> Future for JoinHandle<T, E> {
+    type Item = T;
+    type Error = JoinError<E>;
+
+    fn poll(&mut self) -> Poll<T, JoinError<E>> {
+        match self.rx.poll() {
+            Ok(Async::Ready(Ok(v))) => Ok(Async::Ready(v)),
+            Ok(Async::Ready(Err(e))) => Err(JoinError { inner: Some(e) }),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(oneshot::Canceled) => Err(JoinError { inner: None }),
+        }
+    }
+}
+
+impl<T, E> JoinHandle<T, E> {
+    /// Cancel the task.
+    ///
+    /// The task's future is dropped the next time the task is polled, which
+    /// the executor is notified to do. The future is not interrupted while it
+    /// is being polled, so it is dropped at its next yield point at the
+    /// latest. The handle then completes with an error for which
+    /// [`JoinError::is_canceled`] returns `true`, unless the task completed
+    /// before noticing the abort.
+    ///
+    /// [`JoinError::is_canceled`]: struct.JoinError.html#method.is_canceled
+    pub fn abort(&self) {
+        self.abort.abort()
+    }
+
+    /// Returns a handle that can cancel the task independently of `self`.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.abort.clone()
+    }
+}
+
+impl AbortHandle {
+    fn new() -> AbortHandle {
+        AbortHandle {
+            inner: Arc::new(AbortInner {
+                aborted: AtomicBool::new(false),
+                task: AtomicTask::new(),
+            }),
+        }
+    }
+
+    /// Cancel the task.
+    ///
+    /// See [`JoinHandle::abort`] for more details.
+    ///
+    /// [`JoinHandle::abort`]: struct.JoinHandle.html#method.abort
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, SeqCst);
+        self.inner.task.notify();
+    }
+
+    /// Returns `true` if the task has been aborted.
+    ///
+    /// The task may still be running if its future has not been dropped yet.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(SeqCst)
+    }
+}
+
+impl fmt::Debug for AbortHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("AbortHandle")
+            .field("aborted", &self.is_aborted())
+            .finish()
+    }
+}
+
+impl<F> Future for Abortable<F>
+where
+    F: Future<Item = (), Error = ()>,
+{
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        // Register before checking the flag so that an abort happening in
+        // between is not missed.
+        self.abort.inner.task.register();
+
+        if self.abort.is_aborted() {
+            // Dropping the future drops the sending half of the output
+            // channel, which cancels the `JoinHandle`.
+            self.future = None;
+            return Ok(Async::Ready(()));
+        }
+
+        match self.future {
+            Some(ref mut future) => future.poll(),
+            None => Ok(Async::Ready(())),
+        }
+    }
+}
+
+impl<E> JoinError<E> {
+    /// Returns `true` if the task was dropped before completing.
+    pub fn is_canceled(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns the error the task completed with, or `None` if the task was
+    /// dropped before completing.
+    pub fn into_inner(self) -> Option<E> {
+        self.inner
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for JoinError<E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            Some(ref e) => write!(fmt, "task failed: {}", e),
+            None => write!(fmt, "task was canceled"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for JoinError<E> {
+    fn description(&self) -> &str {
+        match self.inner {
+            Some(_) => "task failed",
+            None => "task was canceled",
+        }
+    }
+}
@@ -27,23 +27,34 @@
 //! * [`DefaultExecutor`] spawns tasks onto the default executor for the current
 //!   context.
 //!
+//! * [`SpawnWithHandle`] spawns a task onto any executor and returns a handle
+//!   to its output.
+//!
 //! * [`Park`] abstracts over blocking and unblocking the current thread.
 //!
+//! * [`budget`] limits how much work a single task may perform before yielding
+//!   back to the executor.
+//!
 //! [`Executor`]: trait.Executor.html
 //! [`enter`]: fn.enter.html
 //! [`DefaultExecutor`]: struct.DefaultExecutor.html
+//! [`SpawnWithHandle`]: trait.SpawnWithHandle.html
 //! [`Park`]: park/index.html
+//! [`budget`]: budget/index.html
 //! [`Future::poll`]: https://docs.rs/futures/0.1/futures/future/trait.Future.html#tymethod.poll
 
 extern crate crossbeam_utils;
 extern crate futures;
 
+pub mod budget;
 mod enter;
 mod global;
+mod join;
 pub mod park;
 
 pub use enter::{enter, Enter, EnterError};
 pub use global::{spawn, with_default, DefaultExecutor};
+pub use join::{AbortHandle, JoinError, JoinHandle, SpawnWithHandle};
 
 use futures::Future;
 
@@ -0,0 +1,70 @@
+extern crate futures;
+extern crate tokio_executor;
+
+use futures::future::lazy;
+use futures::{Async, Future};
+use tokio_executor::budget;
+
+#[test]
+fn unconstrained_outside_of_budget() {
+    for _ in 0..1_000 {
+        match budget::poll_proceed() {
+            Async::Ready(proceed) => proceed.made_progress(),
+            Async::NotReady => panic!("budget exhausted"),
+        }
+    }
+}
+
+#[test]
+fn exhausted_budget_notifies_and_resets() {
+    lazy(|| {
+        budget::with_budget(|| {
+            let mut n = 0;
+
+            while let Async::Ready(proceed) = budget::poll_proceed() {
+                proceed.made_progress();
+                n += 1;
+            }
+
+            assert!(n > 0);
+            assert!(!budget::has_remaining());
+            assert!(budget::poll_proceed().is_not_ready());
+
+            // A nested budget starts out fresh and restores the outer one.
+            budget::with_budget(|| assert!(budget::has_remaining()));
+            assert!(!budget::has_remaining());
+        });
+
+        // Leaving the budget removes the constraint.
+        assert!(budget::has_remaining());
+        assert!(budget::poll_proceed().is_ready());
+
+        Ok::<(), ()>(())
+    })
+    .wait()
+    .unwrap();
+}
+
+#[test]
+fn budget_is_only_charged_on_progress() {
+    lazy(|| {
+        budget::with_budget(|| {
+            // Operations which are not ready give their unit back.
+            for _ in 0..1_000 {
+                let _proceed = budget::poll_proceed();
+            }
+
+            assert!(budget::has_remaining());
+
+            while let Async::Ready(proceed) = budget::poll_proceed() {
+                proceed.made_progress();
+            }
+
+            assert!(!budget::has_remaining());
+        });
+
+        Ok::<(), ()>(())
+    })
+    .wait()
+    .unwrap();
+}
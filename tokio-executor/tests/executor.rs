@@ -26,3 +26,92 @@ mod out_of_executor_context {
         test(|f| DefaultExecutor::current().execute(f));
     }
 }
+
+mod spawn_with_handle {
+    use super::*;
+    use std::thread;
+
+    /// Runs each spawned future to completion on its own thread.
+    struct ThreadExecutor;
+
+    impl Executor for ThreadExecutor {
+        fn spawn(
+            &mut self,
+            future: Box<Future<Item = (), Error = ()> + Send>,
+        ) -> Result<(), SpawnError> {
+            thread::spawn(move || future.wait());
+            Ok(())
+        }
+    }
+
+    /// Drops spawned futures without running them.
+    struct DroppingExecutor;
+
+    impl Executor for DroppingExecutor {
+        fn spawn(
+            &mut self,
+            _future: Box<Future<Item = (), Error = ()> + Send>,
+        ) -> Result<(), SpawnError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn output_is_returned() {
+        let mut executor: Box<Executor> = Box::new(ThreadExecutor);
+
+        let handle = executor.spawn_with_handle(lazy(|| Ok::<_, ()>(42))).unwrap();
+        assert_eq!(handle.wait().unwrap(), 42);
+
+        let handle = executor.spawn_with_handle(lazy(|| Err::<(), _>("boom"))).unwrap();
+        assert_eq!(handle.wait().unwrap_err().into_inner(), Some("boom"));
+    }
+
+    #[test]
+    fn dropped_task_is_canceled() {
+        let handle = DroppingExecutor
+            .spawn_with_handle(lazy(|| Ok::<_, ()>(())))
+            .unwrap();
+
+        assert!(handle.wait().unwrap_err().is_canceled());
+    }
+
+    #[test]
+    fn abort_cancels_task() {
+        use futures::sync::oneshot;
+        use std::sync::mpsc;
+
+        struct Dropped(mpsc::Sender<()>);
+
+        impl Drop for Dropped {
+            fn drop(&mut self) {
+                self.0.send(()).unwrap();
+            }
+        }
+
+        let (dropped_tx, dropped_rx) = mpsc::channel();
+        let (_tx, rx) = oneshot::channel::<()>();
+        let dropped = Dropped(dropped_tx);
+
+        // Never completes unless aborted
+        let handle = ThreadExecutor
+            .spawn_with_handle(rx.then(move |_| {
+                drop(dropped);
+                Ok::<_, ()>(())
+            }))
+            .unwrap();
+
+        let abort = handle.abort_handle();
+        assert!(!abort.is_aborted());
+
+        abort.abort();
+        assert!(handle.wait().unwrap_err().is_canceled());
+        dropped_rx.recv().unwrap();
+    }
+
+    #[test]
+    fn spawn_error_is_returned() {
+        let res = DefaultExecutor::current().spawn_with_handle(lazy(|| Ok::<_, ()>(())));
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,100 @@
+#![feature(test)]
+#![deny(warnings)]
+
+extern crate futures;
+extern crate tempfile;
+extern crate test;
+extern crate tokio;
+extern crate tokio_fs;
+extern crate tokio_io;
+
+use futures::future::{self, Loop};
+use futures::Future;
+use tempfile::TempDir;
+use test::Bencher;
+use tokio::runtime::Runtime;
+use tokio_fs::{File, OpenOptions};
+use tokio_io::io;
+
+use std::fs;
+use std::path::PathBuf;
+
+// Run with and without `--features io-uring` to compare the io_uring backend
+// with the blocking pool.
+
+const FILE_LEN: usize = 16 * 1024 * 1024;
+const CHUNK_LEN: usize = 64 * 1024;
+
+fn setup() -> (TempDir, PathBuf, Runtime) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bench.bin");
+    fs::write(&path, vec![1; FILE_LEN]).unwrap();
+
+    (dir, path, Runtime::new().unwrap())
+}
+
+#[bench]
+fn read_chunks(b: &mut Bencher) {
+    let (_dir, path, mut rt) = setup();
+    b.bytes = FILE_LEN as u64;
+
+    b.iter(|| {
+        let task = File::open(path.clone()).and_then(|file| {
+            future::loop_fn((file, vec![0; CHUNK_LEN]), |(file, buf)| {
+                io::read(file, buf).map(|(file, buf, n)| {
+                    if n == 0 {
+                        Loop::Break(())
+                    } else {
+                        Loop::Continue((file, buf))
+                    }
+                })
+            })
+        });
+
+        rt.block_on(task).unwrap();
+    });
+}
+
+#[bench]
+fn write_chunks(b: &mut Bencher) {
+    let (_dir, path, mut rt) = setup();
+    b.bytes = FILE_LEN as u64;
+
+    b.iter(|| {
+        let task = OpenOptions::new()
+            .write(true)
+            .open(path.clone())
+            .and_then(|file| {
+                future::loop_fn((file, 0), |(file, written)| {
+                    io::write_all(file, vec![2; CHUNK_LEN]).map(move |(file, _)| {
+                        if written + CHUNK_LEN == FILE_LEN {
+                            Loop::Break(())
+                        } else {
+                            Loop::Continue((file, written + CHUNK_LEN))
+                        }
+                    })
+                })
+            });
+
+        rt.block_on(task).unwrap();
+    });
+}
+
+#[bench]
+fn read_write_concurrent(b: &mut Bencher) {
+    let (_dir, path, mut rt) = setup();
+    b.bytes = 2 * FILE_LEN as u64;
+
+    // Reads from the file while appending to another one, so that several
+    // operations are in flight at once.
+    b.iter(|| {
+        let copy = path.with_extension("copy");
+
+        let task = File::open(path.clone())
+            .join(File::create(copy))
+            .and_then(|(from, to)| io::copy(from, to))
+            .map(|_| ());
+
+        rt.block_on(task).unwrap();
+    });
+}
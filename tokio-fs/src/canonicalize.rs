@@ -0,0 +1,53 @@
+use limit::Waiter;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use futures::{Future, Poll};
+
+/// Returns the canonical, absolute form of a path with all intermediate
+/// components normalized and symbolic links resolved.
+///
+/// This is an async version of [`std::fs::canonicalize`][std]
+///
+/// [std]: https://doc.rust-lang.org/std/fs/fn.canonicalize.html
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> CanonicalizeFuture<P> {
+    CanonicalizeFuture::new(path)
+}
+
+/// Future returned by `canonicalize`.
+#[derive(Debug)]
+pub struct CanonicalizeFuture<P>
+where
+    P: AsRef<Path>,
+{
+    path: P,
+    waiter: Waiter,
+}
+
+impl<P> CanonicalizeFuture<P>
+where
+    P: AsRef<Path>,
+{
+    fn new(path: P) -> CanonicalizeFuture<P> {
+        CanonicalizeFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl<P> Future for CanonicalizeFuture<P>
+where
+    P: AsRef<Path>,
+{
+    type Item = PathBuf;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::canonicalize(&self.path)
+        })
+    }
+}
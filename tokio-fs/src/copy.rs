@@ -0,0 +1,60 @@
+use limit::Waiter;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use futures::{Future, Poll};
+
+/// Copies the contents of one file to another, along with its permission
+/// bits, returning the number of bytes copied.
+///
+/// This function will overwrite the contents of `to`.
+///
+/// This is an async version of [`std::fs::copy`][std]
+///
+/// [std]: https://doc.rust-lang.org/std/fs/fn.copy.html
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> CopyFuture<P, Q> {
+    CopyFuture::new(from, to)
+}
+
+/// Future returned by `copy`.
+#[derive(Debug)]
+pub struct CopyFuture<P, Q>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    from: P,
+    to: Q,
+    waiter: Waiter,
+}
+
+impl<P, Q> CopyFuture<P, Q>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    fn new(from: P, to: Q) -> CopyFuture<P, Q> {
+        CopyFuture {
+            from: from,
+            to: to,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl<P, Q> Future for CopyFuture<P, Q>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    type Item = u64;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::copy(&self.from, &self.to)
+        })
+    }
+}
@@ -0,0 +1,355 @@
+use limit::Waiter;
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::{cmp, fmt, mem};
+
+use futures::task;
+use futures::{Async, Future, Poll};
+
+/// Copies the contents of one file to another, along with its permission
+/// bits, reporting the progress of the copy.
+///
+/// This is a shortcut for `CopyOptions::new().copy(from, to, progress)`, see
+/// [`CopyOptions`] for more details.
+///
+/// [`CopyOptions`]: struct.CopyOptions.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// use tokio::prelude::Future;
+///
+/// fn main() {
+///     let task = tokio::fs::copy_with_progress("big.iso", "copy.iso", |copied, total| {
+///         println!("{} / {} bytes", copied, total);
+///     }).map(|copied| {
+///         println!("copied {} bytes", copied);
+///     }).map_err(|err| eprintln!("IO error: {:?}", err));
+///
+///     tokio::run(task);
+/// }
+/// ```
+pub fn copy_with_progress<P, Q, F>(from: P, to: Q, progress: F) -> CopyWithProgress<P, Q, F>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    F: FnMut(u64, u64),
+{
+    CopyOptions::new().copy(from, to, progress)
+}
+
+/// Options for copying files with progress reporting.
+///
+/// The contents are copied in chunks, each of them on the blocking pool, so
+/// that copying a large file does not hold on to a blocking thread for the
+/// whole copy. After each chunk, the task yields, and the progress callback
+/// is called with the number of bytes copied so far and the size of the
+/// source file.
+///
+/// # Platform-specific behavior
+///
+/// On Linux, the chunks are copied with `copy_file_range`, which lets the
+/// kernel, or the filesystem, copy the data without going through userspace.
+/// With [`reflink`] enabled, the destination is first made a clone of the
+/// source with the `FICLONE` ioctl, which shares the data on copy-on-write
+/// filesystems. When this is not supported, the contents are copied as
+/// usual. Note that, this [may change in the future][changes].
+///
+/// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+/// [`reflink`]: #method.reflink
+#[derive(Debug, Clone)]
+pub struct CopyOptions {
+    chunk_size: usize,
+    reflink: bool,
+}
+
+/// Future returned by `copy_with_progress` and `CopyOptions::copy`.
+pub struct CopyWithProgress<P, Q, F> {
+    from: P,
+    to: Q,
+    progress: F,
+    options: CopyOptions,
+    state: State,
+    waiter: Waiter,
+}
+
+enum State {
+    Open,
+    Copying(Copy),
+    Done,
+}
+
+struct Copy {
+    reader: File,
+    writer: File,
+    total: u64,
+    copied: u64,
+
+    // Set once `copy_file_range` turns out not to be supported.
+    #[cfg(target_os = "linux")]
+    fallback: bool,
+}
+
+impl CopyOptions {
+    /// Returns the default options.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> CopyOptions {
+        CopyOptions {
+            chunk_size: 8 * 1024 * 1024,
+            reflink: false,
+        }
+    }
+
+    /// Sets the number of bytes copied by each blocking operation.
+    ///
+    /// The default value is 8 MiB.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `chunk_size` is zero.
+    pub fn chunk_size(&mut self, chunk_size: usize) -> &mut Self {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets whether to clone the source file instead of copying its
+    /// contents, where supported.
+    ///
+    /// The default value is `false`.
+    pub fn reflink(&mut self, reflink: bool) -> &mut Self {
+        self.reflink = reflink;
+        self
+    }
+
+    /// Copies the contents of one file to another, along with its
+    /// permission bits, with these options.
+    ///
+    /// This function will overwrite the contents of `to`. The returned future
+    /// resolves to the number of bytes copied.
+    pub fn copy<P, Q, F>(&self, from: P, to: Q, progress: F) -> CopyWithProgress<P, Q, F>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: FnMut(u64, u64),
+    {
+        CopyWithProgress {
+            from,
+            to,
+            progress,
+            options: self.clone(),
+            state: State::Open,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl Default for CopyOptions {
+    fn default() -> CopyOptions {
+        CopyOptions::new()
+    }
+}
+
+impl<P, Q, F> Future for CopyWithProgress<P, Q, F>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+    F: FnMut(u64, u64),
+{
+    type Item = u64;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let n = match self.state {
+                State::Open => {
+                    let (from, to, reflink) = (&self.from, &self.to, self.options.reflink);
+                    let copy = try_ready!(::blocking_io(
+                        try_ready!(self.waiter.poll_acquire()),
+                        || Copy::open(from, to, reflink)
+                    ));
+
+                    (self.progress)(copy.copied, copy.total);
+                    self.state = State::Copying(copy);
+                    continue;
+                }
+                State::Copying(ref mut copy) => {
+                    let chunk_size = self.options.chunk_size;
+                    try_ready!(::blocking_io(
+                        try_ready!(self.waiter.poll_acquire()),
+                        || copy.copy_chunk(chunk_size)
+                    ))
+                }
+                State::Done => panic!("Cannot poll `CopyWithProgress` after it resolves"),
+            };
+
+            if n == 0 {
+                break;
+            }
+
+            let (copied, total) = match self.state {
+                State::Copying(ref mut copy) => {
+                    copy.copied += n;
+                    (copy.copied, copy.total)
+                }
+                _ => unreachable!(),
+            };
+
+            (self.progress)(copied, total);
+
+            // Let other tasks use the blocking thread before the next chunk.
+            task::current().notify();
+            return Ok(Async::NotReady);
+        }
+
+        match mem::replace(&mut self.state, State::Done) {
+            State::Copying(copy) => Ok(Async::Ready(copy.copied)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<P, Q, F> fmt::Debug for CopyWithProgress<P, Q, F>
+where
+    P: fmt::Debug,
+    Q: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("CopyWithProgress")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Copy {
+    fn open<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q, reflink: bool) -> io::Result<Copy> {
+        let reader = File::open(from)?;
+        let metadata = reader.metadata()?;
+
+        if !metadata.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the source path is not an existing regular file",
+            ));
+        }
+
+        let writer = File::create(to)?;
+        writer.set_permissions(metadata.permissions())?;
+
+        let total = metadata.len();
+        let copied = if reflink && sys::reflink(&reader, &writer) {
+            total
+        } else {
+            0
+        };
+
+        Ok(Copy {
+            reader,
+            writer,
+            total,
+            copied,
+            #[cfg(target_os = "linux")]
+            fallback: false,
+        })
+    }
+
+    /// Copies up to `chunk_size` bytes, returning the number of bytes
+    /// copied, or 0 once the whole source has been copied.
+    fn copy_chunk(&mut self, chunk_size: usize) -> io::Result<u64> {
+        if self.copied >= self.total {
+            return Ok(0);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            if !self.fallback {
+                match sys::copy_file_range(&self.reader, &self.writer, chunk_size) {
+                    Ok(n) => return Ok(n),
+                    Err(ref e) if sys::is_unsupported(e) => self.fallback = true,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        let mut buf = vec![0; cmp::min(chunk_size, 64 * 1024)];
+        let mut copied = 0;
+
+        while copied < chunk_size {
+            let len = cmp::min(buf.len(), chunk_size - copied);
+            let n = match self.reader.read(&mut buf[..len]) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            self.writer.write_all(&buf[..n])?;
+            copied += n;
+        }
+
+        Ok(copied as u64)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use libc;
+    use sys::cvt;
+
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+    use std::ptr;
+
+    // `_IOW(0x94, 9, int)`
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+
+    /// Makes `writer` a clone of `reader`, returning whether this is
+    /// supported.
+    pub fn reflink(reader: &File, writer: &File) -> bool {
+        unsafe { libc::ioctl(writer.as_raw_fd(), FICLONE as _, reader.as_raw_fd()) == 0 }
+    }
+
+    pub fn copy_file_range(reader: &File, writer: &File, len: usize) -> io::Result<u64> {
+        let ret = unsafe {
+            libc::copy_file_range(
+                reader.as_raw_fd(),
+                ptr::null_mut(),
+                writer.as_raw_fd(),
+                ptr::null_mut(),
+                len,
+                0,
+            )
+        };
+
+        cvt(ret).map(|n| n as u64)
+    }
+
+    /// Returns whether `copy_file_range` failed because it cannot be used
+    /// for these files, rather than because of an I/O error.
+    pub fn is_unsupported(err: &io::Error) -> bool {
+        match err.raw_os_error() {
+            Some(libc::ENOSYS)
+            | Some(libc::EXDEV)
+            | Some(libc::EINVAL)
+            | Some(libc::EPERM)
+            | Some(libc::EOPNOTSUPP) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::fs::File;
+
+    pub fn reflink(_: &File, _: &File) -> bool {
+        false
+    }
+}
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -20,6 +22,7 @@ where
     P: AsRef<Path>,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> CreateDirFuture<P>
@@ -27,7 +30,10 @@ where
     P: AsRef<Path>,
 {
     fn new(path: P) -> CreateDirFuture<P> {
-        CreateDirFuture { path: path }
+        CreateDirFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -39,6 +45,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::create_dir(&self.path))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::create_dir(&self.path)
+        })
     }
 }
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -21,6 +23,7 @@ where
     P: AsRef<Path>,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> CreateDirAllFuture<P>
@@ -28,7 +31,10 @@ where
     P: AsRef<Path>,
 {
     fn new(path: P) -> CreateDirAllFuture<P> {
-        CreateDirAllFuture { path: path }
+        CreateDirAllFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -40,6 +46,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::create_dir_all(&self.path))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::create_dir_all(&self.path)
+        })
     }
 }
@@ -1,4 +1,5 @@
 use super::File;
+use limit::Waiter;
 
 use futures::{Future, Poll};
 
@@ -10,6 +11,7 @@ use std::path::Path;
 #[derive(Debug)]
 pub struct CreateFuture<P> {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> CreateFuture<P>
@@ -17,7 +19,10 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     pub(crate) fn new(path: P) -> Self {
-        CreateFuture { path }
+        CreateFuture {
+            path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -29,7 +34,10 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let std = try_ready!(::blocking_io(|| StdFile::create(&self.path)));
+        let std = try_ready!(::blocking_io(
+            try_ready!(self.waiter.poll_acquire()),
+            || StdFile::create(&self.path)
+        ));
 
         let file = File::from_std(std);
         Ok(file.into())
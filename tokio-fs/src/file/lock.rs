@@ -0,0 +1,253 @@
+use super::File;
+use limit::{Permit, Waiter};
+
+use futures::{Async, Future, Poll};
+
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+/// Future returned by `File::lock_exclusive`, `File::lock_shared` and
+/// `File::try_lock`, and resolves to a [`FileLock`].
+///
+/// If the lock cannot be acquired, the file is returned along with the
+/// error.
+///
+/// # Panics
+///
+/// Will panic if polled after returning an item or error.
+///
+/// [`FileLock`]: struct.FileLock.html
+#[derive(Debug)]
+pub struct LockFuture {
+    file: Option<File>,
+    exclusive: bool,
+    block: bool,
+    waiter: Waiter,
+}
+
+/// An advisory lock held on a file.
+///
+/// The locked [`File`] can be accessed through `Deref` and `DerefMut`. The
+/// lock is released when the `FileLock` is dropped, or with [`unlock`], which
+/// gives the file back.
+///
+/// [`File`]: struct.File.html
+/// [`unlock`]: #method.unlock
+#[derive(Debug)]
+pub struct FileLock {
+    file: Option<File>,
+}
+
+/// Future returned by `FileLock::unlock`, and resolves to the unlocked
+/// `File`.
+///
+/// # Panics
+///
+/// Will panic if polled after returning an item or error.
+#[derive(Debug)]
+pub struct UnlockFuture {
+    file: Option<File>,
+    waiter: Waiter,
+}
+
+impl LockFuture {
+    pub(crate) fn new(file: File, exclusive: bool, block: bool) -> Self {
+        LockFuture {
+            file: Some(file),
+            exclusive,
+            block,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl Future for LockFuture {
+    type Item = FileLock;
+    type Error = (File, io::Error);
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (exclusive, block) = (self.exclusive, self.block);
+
+        // Waiting for a lock can take any amount of time, so it must not hold
+        // up the other operations of the limit.
+        let permit = if block {
+            Ok(Async::Ready(Permit::unlimited()))
+        } else {
+            self.waiter.poll_acquire()
+        };
+
+        let res = match permit {
+            Ok(Async::Ready(permit)) => {
+                let std = self
+                    .file
+                    .as_mut()
+                    .expect("Cannot poll `LockFuture` after it resolves")
+                    .std();
+                ::blocking_io(permit, || sys::lock(std, exclusive, block))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Err(err),
+        };
+
+        match res {
+            Ok(Async::Ready(())) => Ok(FileLock {
+                file: self.file.take(),
+            }
+            .into()),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Err((self.file.take().unwrap(), err)),
+        }
+    }
+}
+
+impl FileLock {
+    /// Releases the lock, returning the file once it is unlocked.
+    ///
+    /// Unlike dropping the `FileLock`, this runs on the blocking pool and
+    /// reports errors.
+    pub fn unlock(mut self) -> UnlockFuture {
+        UnlockFuture {
+            file: self.file.take(),
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl Deref for FileLock {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        self.file.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for FileLock {
+    fn deref_mut(&mut self) -> &mut File {
+        self.file.as_mut().unwrap()
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        if let Some(std) = self.file.as_ref().and_then(|file| file.std.as_ref()) {
+            // Releasing a lock does not wait on other lock holders, so it
+            // is done inline. Closing the file would release it anyway.
+            let _ = sys::unlock(std);
+        }
+    }
+}
+
+impl Future for UnlockFuture {
+    type Item = File;
+    type Error = (File, io::Error);
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let res = match self.waiter.poll_acquire() {
+            Ok(Async::Ready(permit)) => {
+                let std = self
+                    .file
+                    .as_mut()
+                    .expect("Cannot poll `UnlockFuture` after it resolves")
+                    .std();
+                ::blocking_io(permit, || sys::unlock(std))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Err(err),
+        };
+
+        match res {
+            Ok(Async::Ready(())) => Ok(self.file.take().unwrap().into()),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Err((self.file.take().unwrap(), err)),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use libc;
+    use sys::cvt;
+
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    pub fn lock(file: &File, exclusive: bool, block: bool) -> io::Result<()> {
+        let mut op = if exclusive {
+            libc::LOCK_EX
+        } else {
+            libc::LOCK_SH
+        };
+
+        if !block {
+            op |= libc::LOCK_NB;
+        }
+
+        flock(file, op)
+    }
+
+    pub fn unlock(file: &File) -> io::Result<()> {
+        flock(file, libc::LOCK_UN)
+    }
+
+    fn flock(file: &File, op: libc::c_int) -> io::Result<()> {
+        cvt(unsafe { libc::flock(file.as_raw_fd(), op) })?;
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use winapi::shared::minwindef::DWORD;
+    use winapi::shared::winerror::ERROR_LOCK_VIOLATION;
+    use winapi::um::fileapi::{LockFileEx, UnlockFileEx};
+    use winapi::um::minwinbase::{LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY, OVERLAPPED};
+
+    use std::fs::File;
+    use std::io;
+    use std::mem;
+    use std::os::windows::io::AsRawHandle;
+
+    pub fn lock(file: &File, exclusive: bool, block: bool) -> io::Result<()> {
+        let mut flags: DWORD = 0;
+
+        if exclusive {
+            flags |= LOCKFILE_EXCLUSIVE_LOCK;
+        }
+
+        if !block {
+            flags |= LOCKFILE_FAIL_IMMEDIATELY;
+        }
+
+        let ret = unsafe {
+            let mut overlapped: OVERLAPPED = mem::zeroed();
+            LockFileEx(file.as_raw_handle() as _, flags, 0, !0, !0, &mut overlapped)
+        };
+
+        if ret == 0 {
+            let err = io::Error::last_os_error();
+
+            // Report contention the same way as `flock` does.
+            if err.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) {
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, err));
+            }
+
+            Err(err)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn unlock(file: &File) -> io::Result<()> {
+        let ret = unsafe {
+            let mut overlapped: OVERLAPPED = mem::zeroed();
+            UnlockFileEx(file.as_raw_handle() as _, 0, !0, !0, &mut overlapped)
+        };
+
+        if ret == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
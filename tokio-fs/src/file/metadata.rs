@@ -1,4 +1,5 @@
 use super::File;
+use limit::Waiter;
 
 use futures::{Future, Poll};
 
@@ -12,11 +13,15 @@ const POLL_AFTER_RESOLVE: &str = "Cannot poll MetadataFuture after it resolves";
 #[derive(Debug)]
 pub struct MetadataFuture {
     file: Option<File>,
+    waiter: Waiter,
 }
 
 impl MetadataFuture {
     pub(crate) fn new(file: File) -> Self {
-        MetadataFuture { file: Some(file) }
+        MetadataFuture {
+            file: Some(file),
+            waiter: Waiter::new(),
+        }
     }
 
     fn std(&mut self) -> &mut StdFile {
@@ -29,7 +34,10 @@ impl Future for MetadataFuture {
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let metadata = try_ready!(::blocking_io(|| StdFile::metadata(self.std())));
+        let metadata = try_ready!(::blocking_io(
+            try_ready!(self.waiter.poll_acquire()),
+            || StdFile::metadata(self.std())
+        ));
 
         let file = self.file.take().expect(POLL_AFTER_RESOLVE);
         Ok((file, metadata).into())
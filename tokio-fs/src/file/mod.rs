@@ -4,6 +4,7 @@
 
 mod clone;
 mod create;
+mod lock;
 mod metadata;
 mod open;
 mod open_options;
@@ -11,18 +12,29 @@ mod seek;
 
 pub use self::clone::CloneFuture;
 pub use self::create::CreateFuture;
+pub use self::lock::{FileLock, LockFuture, UnlockFuture};
 pub use self::metadata::MetadataFuture;
 pub use self::open::OpenFuture;
 pub use self::open_options::OpenOptions;
 pub use self::seek::SeekFuture;
 
-use tokio_io::{AsyncRead, AsyncWrite};
+use limit::Waiter;
+
+use tokio_io::io::{lines, BufReader, Lines};
+#[cfg(unix)]
+use tokio_io::IoVec;
+use tokio_io::{AsyncRead, AsyncSeek, AsyncWrite};
 
 use futures::Poll;
 
 use std::fs::{File as StdFile, Metadata, Permissions};
-use std::io::{self, Read, Seek, Write};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawHandle, RawHandle};
 use std::path::Path;
+use std::time::SystemTime;
 
 /// A reference to an open file on the filesystem.
 ///
@@ -35,7 +47,13 @@ use std::path::Path;
 ///
 /// Files are automatically closed when they go out of scope.
 ///
+/// Reads and writes are passed to the operating system with the caller's
+/// buffers, without intermediate copies. For files opened for direct I/O,
+/// this means the buffers must meet the alignment requirements described in
+/// [`OpenOptionsExt::custom_flags`].
+///
 /// [std]: https://doc.rust-lang.org/std/fs/struct.File.html
+/// [`OpenOptionsExt::custom_flags`]: os/unix/trait.OpenOptionsExt.html#tymethod.custom_flags
 ///
 /// # Examples
 ///
@@ -79,6 +97,13 @@ use std::path::Path;
 #[derive(Debug)]
 pub struct File {
     std: Option<StdFile>,
+
+    // The io_uring operation in progress, if any.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    uring: ::uring::Pending,
+
+    // Waits for the filesystem limit before each blocking operation.
+    pub(crate) waiter: Waiter,
 }
 
 impl File {
@@ -160,6 +185,10 @@ impl File {
 
     /// Convert a [`std::fs::File`][std] to a [`tokio_fs::File`][file].
     ///
+    /// The file is used as is, so it keeps the cursor position and the
+    /// options it was opened with. The blocking operations performed on the
+    /// returned `File` run on the blocking pool, like for any other `File`.
+    ///
     /// [std]: https://doc.rust-lang.org/std/fs/struct.File.html
     /// [file]: struct.File.html
     ///
@@ -174,7 +203,12 @@ impl File {
     /// }
     /// ```
     pub fn from_std(std: StdFile) -> File {
-        File { std: Some(std) }
+        File {
+            std: Some(std),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            uring: Default::default(),
+            waiter: Waiter::new(),
+        }
     }
 
     /// Seek to an offset, in bytes, in a stream.
@@ -209,7 +243,10 @@ impl File {
     /// }
     /// ```
     pub fn poll_seek(&mut self, pos: io::SeekFrom) -> Poll<u64, io::Error> {
-        ::blocking_io(|| self.std().seek(pos))
+        try_ready!(self.poll_settle());
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().seek(pos)
+        })
     }
 
     /// Seek to an offset, in bytes, in a stream.
@@ -267,7 +304,17 @@ impl File {
     /// }
     /// ```
     pub fn poll_sync_all(&mut self) -> Poll<(), io::Error> {
-        ::blocking_io(|| self.std().sync_all())
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            let std = self.std.as_ref().expect("`File` instance already shutdown");
+            if let Some(res) = self.uring.poll_fsync(std, false) {
+                return res;
+            }
+        }
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().sync_all()
+        })
     }
 
     /// This function is similar to `poll_sync_all`, except that it may not
@@ -300,7 +347,17 @@ impl File {
     /// }
     /// ```
     pub fn poll_sync_data(&mut self) -> Poll<(), io::Error> {
-        ::blocking_io(|| self.std().sync_data())
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            let std = self.std.as_ref().expect("`File` instance already shutdown");
+            if let Some(res) = self.uring.poll_fsync(std, true) {
+                return res;
+            }
+        }
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().sync_data()
+        })
     }
 
     /// Truncates or extends the underlying file, updating the size of this file to become size.
@@ -335,7 +392,50 @@ impl File {
     /// }
     /// ```
     pub fn poll_set_len(&mut self, size: u64) -> Poll<(), io::Error> {
-        ::blocking_io(|| self.std().set_len(size))
+        try_ready!(self.poll_settle());
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().set_len(size)
+        })
+    }
+
+    /// Returns a stream over the lines of the file.
+    ///
+    /// The file is read through a [`BufReader`] with the default capacity.
+    /// Lines are yielded without their terminating newline, and a line which
+    /// is not valid UTF-8 results in an error.
+    ///
+    /// [`BufReader`]: https://docs.rs/tokio-io/0.1/tokio_io/io/struct.BufReader.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate tokio;
+    /// use tokio::prelude::{Future, Stream};
+    ///
+    /// fn main() {
+    ///     let task = tokio::fs::File::open("app.log")
+    ///         .and_then(|file| {
+    ///             file.lines().for_each(|line| {
+    ///                 println!("{}", line);
+    ///                 Ok(())
+    ///             })
+    ///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+    ///
+    ///     tokio::run(task);
+    /// }
+    /// ```
+    pub fn lines(self) -> Lines<BufReader<File>> {
+        lines(BufReader::new(self))
+    }
+
+    /// Returns a stream over the lines of the file, read through a buffer of
+    /// the given capacity.
+    ///
+    /// See [`lines`] for more details.
+    ///
+    /// [`lines`]: #method.lines
+    pub fn lines_with_capacity(self, capacity: usize) -> Lines<BufReader<File>> {
+        lines(BufReader::with_capacity(capacity, self))
     }
 
     /// Queries metadata about the underlying file.
@@ -380,7 +480,9 @@ impl File {
     /// }
     /// ```
     pub fn poll_metadata(&mut self) -> Poll<Metadata, io::Error> {
-        ::blocking_io(|| self.std().metadata())
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().metadata()
+        })
     }
 
     /// Create a new `File` instance that shares the same underlying file handle
@@ -405,7 +507,7 @@ impl File {
     /// }
     /// ```
     pub fn poll_try_clone(&mut self) -> Poll<File, io::Error> {
-        ::blocking_io(|| {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
             let std = self.std().try_clone()?;
             Ok(File::from_std(std))
         })
@@ -443,6 +545,70 @@ impl File {
         CloneFuture::new(self)
     }
 
+    /// Acquires an exclusive advisory lock on the file, waiting until it is
+    /// available.
+    ///
+    /// The returned future resolves to a [`FileLock`], which releases the
+    /// lock when dropped. Waiting for the lock occupies a thread of the
+    /// blocking pool, but is not counted against the filesystem [`Limit`].
+    ///
+    /// Advisory locks only coordinate the processes which take them, they do
+    /// not prevent other accesses to the file.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix and
+    /// the `LockFileEx` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+    /// [`FileLock`]: struct.FileLock.html
+    /// [`Limit`]: limit/struct.Limit.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate tokio;
+    /// use tokio::prelude::Future;
+    ///
+    /// fn main() {
+    ///     let task = tokio::fs::File::create("foo.pid")
+    ///         .and_then(|file| file.lock_exclusive().map_err(|(_, err)| err))
+    ///         .map(|lock| {
+    ///             // the lock is released when `lock` is dropped
+    ///             # println!("{:?}", lock);
+    ///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+    ///
+    ///     tokio::run(task);
+    /// }
+    /// ```
+    pub fn lock_exclusive(self) -> LockFuture {
+        LockFuture::new(self, true, true)
+    }
+
+    /// Acquires a shared advisory lock on the file, waiting until it is
+    /// available.
+    ///
+    /// Several shared locks may be held on a file at once, but not along
+    /// with an exclusive lock. See [`lock_exclusive`] for more details.
+    ///
+    /// [`lock_exclusive`]: #method.lock_exclusive
+    pub fn lock_shared(self) -> LockFuture {
+        LockFuture::new(self, false, true)
+    }
+
+    /// Attempts to acquire an exclusive advisory lock on the file, without
+    /// waiting.
+    ///
+    /// If the file is already locked, the future fails right away with an
+    /// error of kind `WouldBlock`, and the file is given back. See
+    /// [`lock_exclusive`] for more details.
+    ///
+    /// [`lock_exclusive`]: #method.lock_exclusive
+    pub fn try_lock(self) -> LockFuture {
+        LockFuture::new(self, true, false)
+    }
+
     /// Changes the permissions on the underlying file.
     ///
     /// # Platform-specific behavior
@@ -481,11 +647,66 @@ impl File {
     /// }
     /// ```
     pub fn poll_set_permissions(&mut self, perm: Permissions) -> Poll<(), io::Error> {
-        ::blocking_io(|| self.std().set_permissions(perm))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            self.std().set_permissions(perm)
+        })
+    }
+
+    /// Changes the last access and modification times of the underlying
+    /// file.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `futimens` function on Unix
+    /// and the `SetFileTime` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is not opened for
+    /// writing, or if the user lacks permission to change its attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate tokio;
+    /// use tokio::prelude::Future;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// fn main() {
+    ///     let task = tokio::fs::File::create("foo.txt")
+    ///         .and_then(|mut file| {
+    ///             let yesterday = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+    ///             file.poll_set_times(yesterday, yesterday)
+    ///         })
+    ///         .map(|res| {
+    ///             // handle returned result ..
+    ///             # println!("{:?}", res);
+    ///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+    ///
+    ///     tokio::run(task);
+    /// }
+    /// ```
+    pub fn poll_set_times(
+        &mut self,
+        accessed: SystemTime,
+        modified: SystemTime,
+    ) -> Poll<(), io::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            ::set_times::set_file_times(self.std(), accessed, modified)
+        })
     }
 
     /// Destructures the `tokio_fs::File` into a [`std::fs::File`][std].
     ///
+    /// Blocking operations on a `File` complete within the call which started
+    /// them, so none of them is still in flight once the `File` is given up:
+    /// the returned file reflects all the reads, writes and seeks made so far.
+    /// With the `io-uring` feature, an operation still in flight is waited
+    /// for, blocking the current thread, and undone if it is a read.
+    ///
     /// # Panics
     ///
     /// This function will panic if `shutdown` has been called.
@@ -510,17 +731,47 @@ impl File {
     /// }
     /// ```
     pub fn into_std(mut self) -> StdFile {
-        self.std.take().expect("`File` instance already shutdown")
+        let std = self.std.take().expect("`File` instance already shutdown");
+
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            // Only seeking back after an abandoned read can fail, leaving
+            // nothing more to do.
+            let _ = self.uring.settle(&std);
+        }
+
+        std
     }
 
     fn std(&mut self) -> &mut StdFile {
         self.std.as_mut().expect("`File` instance already shutdown")
     }
+
+    /// Completes the io_uring operation abandoned by its caller, if any,
+    /// before the file is used through the blocking pool.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    fn poll_settle(&mut self) -> Poll<(), io::Error> {
+        let std = self.std.as_ref().expect("`File` instance already shutdown");
+        self.uring.poll_settle(std)
+    }
+
+    #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+    fn poll_settle(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
 }
 
 impl Read for File {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        ::would_block(|| self.std().read(buf))
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            let std = self.std.as_ref().expect("`File` instance already shutdown");
+            if let Some(res) = self.uring.read(std, buf) {
+                return res;
+            }
+        }
+
+        ::would_block(self.waiter.try_acquire()?, || self.std().read(buf))
     }
 }
 
@@ -528,27 +779,101 @@ impl AsyncRead for File {
     unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
         false
     }
+
+    #[cfg(unix)]
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        try_ready!(self.poll_settle());
+        let fd = self.std().as_raw_fd();
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || unsafe {
+            let iovecs = ::iovec::unix::as_os_slice_mut(bufs);
+            let r = ::libc::readv(fd, iovecs.as_ptr(), iovecs.len() as ::libc::c_int);
+            ::sys::cvt(r).map(|n| n as usize)
+        })
+    }
+}
+
+impl Seek for File {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        if self.poll_settle()?.is_not_ready() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        ::would_block(self.waiter.try_acquire()?, || self.std().seek(pos))
+    }
+}
+
+impl AsyncSeek for File {
+    fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, io::Error> {
+        File::poll_seek(self, pos)
+    }
 }
 
 impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        ::would_block(|| self.std().write(buf))
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            let std = self.std.as_ref().expect("`File` instance already shutdown");
+            if let Some(res) = self.uring.write(std, buf) {
+                return res;
+            }
+        }
+
+        ::would_block(self.waiter.try_acquire()?, || self.std().write(buf))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        ::would_block(|| self.std().flush())
+        ::would_block(self.waiter.try_acquire()?, || self.std().flush())
     }
 }
 
 impl AsyncWrite for File {
+    #[cfg(unix)]
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        try_ready!(self.poll_settle());
+        let fd = self.std().as_raw_fd();
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || unsafe {
+            let iovecs = ::iovec::unix::as_os_slice(bufs);
+            let r = ::libc::writev(fd, iovecs.as_ptr(), iovecs.len() as ::libc::c_int);
+            ::sys::cvt(r).map(|n| n as usize)
+        })
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
-        ::blocking_io(|| {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
             self.std = None;
             Ok(())
         })
     }
 }
 
+impl From<StdFile> for File {
+    fn from(std: StdFile) -> File {
+        File::from_std(std)
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for File {
+    fn as_raw_fd(&self) -> RawFd {
+        self.std
+            .as_ref()
+            .expect("`File` instance already shutdown")
+            .as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawHandle for File {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.std
+            .as_ref()
+            .expect("`File` instance already shutdown")
+            .as_raw_handle()
+    }
+}
+
 impl Drop for File {
     fn drop(&mut self) {
         if let Some(_std) = self.std.take() {
@@ -1,4 +1,5 @@
 use super::File;
+use limit::Waiter;
 
 use futures::{Future, Poll};
 
@@ -11,6 +12,7 @@ use std::path::Path;
 pub struct OpenFuture<P> {
     options: StdOpenOptions,
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> OpenFuture<P>
@@ -18,7 +20,11 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     pub(crate) fn new(options: StdOpenOptions, path: P) -> Self {
-        OpenFuture { options, path }
+        OpenFuture {
+            options,
+            path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -30,7 +36,10 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let std = try_ready!(::blocking_io(|| self.options.open(&self.path)));
+        let std = try_ready!(::blocking_io(
+            try_ready!(self.waiter.poll_acquire()),
+            || self.options.open(&self.path)
+        ));
 
         let file = File::from_std(std);
         Ok(file.into())
@@ -9,10 +9,13 @@ use std::path::Path;
 /// This is a specialized version of [`std::fs::OpenOptions`] for usage from
 /// the Tokio runtime.
 ///
-/// `From<std::fs::OpenOptions>` is implemented for more advanced configuration
-/// than the methods provided here.
+/// Platform-specific options, such as the mode of a new file or custom flags,
+/// are set through the `OpenOptionsExt` traits of the [`os`] module.
+/// `From<std::fs::OpenOptions>` is also implemented for more advanced
+/// configuration than the methods provided here.
 ///
 /// [`std::fs::OpenOptions`]: https://doc.rust-lang.org/std/fs/struct.OpenOptions.html
+/// [`os`]: os/index.html
 #[derive(Clone, Debug)]
 pub struct OpenOptions(StdOpenOptions);
 
@@ -95,6 +98,11 @@ impl OpenOptions {
     {
         OpenFuture::new(self.0.clone(), path)
     }
+
+    /// Returns the underlying `std` options, for the OS-specific extensions.
+    pub(crate) fn as_std_mut(&mut self) -> &mut StdOpenOptions {
+        &mut self.0
+    }
 }
 
 impl From<StdOpenOptions> for OpenOptions {
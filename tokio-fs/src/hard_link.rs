@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -25,6 +27,7 @@ where
 {
     src: P,
     dst: Q,
+    waiter: Waiter,
 }
 
 impl<P, Q> HardLinkFuture<P, Q>
@@ -33,7 +36,11 @@ where
     Q: AsRef<Path>,
 {
     fn new(src: P, dst: Q) -> HardLinkFuture<P, Q> {
-        HardLinkFuture { src: src, dst: dst }
+        HardLinkFuture {
+            src: src,
+            dst: dst,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -46,6 +53,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::hard_link(&self.src, &self.dst))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::hard_link(&self.src, &self.dst)
+        })
     }
 }
@@ -24,19 +24,55 @@
 //! to a *backup* thread immediately. See [tokio-threadpool] for more details
 //! of the threading model and [`blocking`].
 //!
+//! ## io_uring
+//!
+//! On Linux, the `io-uring` feature routes the reads, writes and syncs of a
+//! [`File`] through io_uring instead of the blocking pool. The operations
+//! then do not occupy a thread while they run, and each of them costs a
+//! copy between the caller's buffer and a buffer owned by the ring. Other
+//! operations, and all operations where io_uring is not available, still
+//! use the blocking pool.
+//!
+//! Whether this is faster depends on the kernel and on the workload: data in
+//! the page cache may well be read faster by the blocking pool. The `file`
+//! benchmarks of this crate compare both, when run with and without the
+//! feature.
+//!
 //! [`blocking`]: https://docs.rs/tokio-threadpool/0.1/tokio_threadpool/fn.blocking.html
+//! [`File`]: file/struct.File.html
 //! [`AsyncRead`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncRead.html
 //! [tokio-threadpool]: https://docs.rs/tokio-threadpool/0.1/tokio_threadpool
 
 #[macro_use]
 extern crate futures;
+extern crate tempfile;
+extern crate tokio_executor;
 extern crate tokio_io;
 extern crate tokio_threadpool;
+#[cfg(feature = "watch")]
+extern crate tokio_timer;
 
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+extern crate io_uring;
+#[cfg(unix)]
+extern crate iovec;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(all(feature = "watch", target_os = "linux"))]
+extern crate mio;
+#[cfg(all(feature = "watch", target_os = "linux"))]
+extern crate tokio_reactor;
+#[cfg(windows)]
+extern crate winapi;
+
+mod canonicalize;
+mod copy;
+mod copy_with_progress;
 mod create_dir;
 mod create_dir_all;
 pub mod file;
 mod hard_link;
+pub mod limit;
 mod metadata;
 pub mod os;
 mod read;
@@ -46,12 +82,24 @@ mod remove_dir;
 mod remove_file;
 mod rename;
 mod set_permissions;
+mod set_times;
 mod stderr;
 mod stdin;
 mod stdout;
 mod symlink_metadata;
+#[cfg(unix)]
+mod sys;
+pub mod temp;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+mod uring;
+mod walk_dir;
+#[cfg(feature = "watch")]
+pub mod watch;
 mod write;
 
+pub use canonicalize::{canonicalize, CanonicalizeFuture};
+pub use copy::{copy, CopyFuture};
+pub use copy_with_progress::{copy_with_progress, CopyOptions, CopyWithProgress};
 pub use create_dir::{create_dir, CreateDirFuture};
 pub use create_dir_all::{create_dir_all, CreateDirAllFuture};
 pub use file::File;
@@ -59,16 +107,23 @@ pub use file::OpenOptions;
 pub use hard_link::{hard_link, HardLinkFuture};
 pub use metadata::{metadata, MetadataFuture};
 pub use read::{read, ReadFile};
-pub use read_dir::{read_dir, DirEntry, ReadDir, ReadDirFuture};
+pub use read_dir::{
+    read_dir, DirEntry, DirEntryFileTypeFuture, DirEntryMetadataFuture, ReadDir, ReadDirFuture,
+};
 pub use read_link::{read_link, ReadLinkFuture};
 pub use remove_dir::{remove_dir, RemoveDirFuture};
 pub use remove_file::{remove_file, RemoveFileFuture};
 pub use rename::{rename, RenameFuture};
 pub use set_permissions::{set_permissions, SetPermissionsFuture};
+pub use set_times::{set_times, SetTimesFuture};
 pub use stderr::{stderr, Stderr};
 pub use stdin::{stdin, Stdin};
 pub use stdout::{stdout, Stdout};
 pub use symlink_metadata::{symlink_metadata, SymlinkMetadataFuture};
+pub use temp::{tempdir, tempfile, NamedTempFile, TempDir};
+pub use walk_dir::{walk_dir, WalkDir, WalkEntry};
+#[cfg(feature = "watch")]
+pub use watch::{watch, Watch};
 pub use write::{write, WriteFile};
 
 use futures::Async::*;
@@ -77,10 +132,12 @@ use futures::Poll;
 use std::io;
 use std::io::ErrorKind::{Other, WouldBlock};
 
-fn blocking_io<F, T>(f: F) -> Poll<T, io::Error>
+fn blocking_io<F, T>(permit: limit::Permit, f: F) -> Poll<T, io::Error>
 where
     F: FnOnce() -> io::Result<T>,
 {
+    let _permit = permit;
+
     match tokio_threadpool::blocking(f) {
         Ok(Ready(Ok(v))) => Ok(v.into()),
         Ok(Ready(Err(err))) => Err(err),
@@ -89,10 +146,12 @@ where
     }
 }
 
-fn would_block<F, T>(f: F) -> io::Result<T>
+fn would_block<F, T>(permit: limit::Permit, f: F) -> io::Result<T>
 where
     F: FnOnce() -> io::Result<T>,
 {
+    let _permit = permit;
+
     match tokio_threadpool::blocking(f) {
         Ok(Ready(Ok(v))) => Ok(v),
         Ok(Ready(Err(err))) => {
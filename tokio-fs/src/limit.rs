@@ -0,0 +1,355 @@
+//! Limits on the filesystem operations running at once.
+//!
+//! Filesystem operations run on the blocking threads of the thread pool,
+//! which they share with any other `blocking` call. A [`Limit`] bounds the
+//! number of these threads used by filesystem operations: at most
+//! [`concurrency`] operations run at once, and at most [`queue_depth`]
+//! operations wait for one of them to complete. A slow filesystem then cannot
+//! use up all the blocking threads of the runtime.
+//!
+//! The operations still run on the blocking threads of the thread pool, a
+//! `Limit` does not spawn threads of its own.
+//!
+//! A `Limit` is selected for the current thread with [`with_default`], which
+//! the Tokio runtime does for its workers when configured with one. Without
+//! a `Limit`, filesystem operations are only limited by the thread pool.
+//!
+//! [`Limit`]: struct.Limit.html
+//! [`concurrency`]: struct.Builder.html#method.concurrency
+//! [`queue_depth`]: struct.Builder.html#method.queue_depth
+//! [`with_default`]: fn.with_default.html
+
+use futures::task::{self, Task};
+use futures::{Async, Poll};
+use tokio_executor::Enter;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+/// Builds a [`Limit`] with custom configuration values.
+///
+/// [`Limit`]: struct.Limit.html
+#[derive(Debug, Clone)]
+pub struct Builder {
+    concurrency: usize,
+    queue_depth: usize,
+}
+
+/// A limit on the filesystem operations running at once.
+///
+/// Cloning a `Limit` returns a handle to the same limit, so it can be shared
+/// by several runtimes.
+#[derive(Debug, Clone)]
+pub struct Limit {
+    inner: Arc<Inner>,
+}
+
+/// Error returned by filesystem operations when too many operations are
+/// waiting for the [`Limit`].
+///
+/// It is wrapped in an `io::Error` of kind `Other`, and can be retrieved with
+/// `io::Error::get_ref`.
+///
+/// [`Limit`]: struct.Limit.html
+#[derive(Debug)]
+pub struct QueueFull {
+    _p: (),
+}
+
+#[derive(Debug)]
+struct Inner {
+    concurrency: usize,
+    queue_depth: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    // Number of operations currently running.
+    running: usize,
+
+    // Identifies the next waiter, so that waiters are ordered by arrival.
+    next_id: usize,
+
+    // Operations waiting for another one to complete.
+    waiters: BTreeMap<usize, Waiting>,
+}
+
+#[derive(Debug)]
+struct Waiting {
+    task: Task,
+
+    // Set once an operation completed for this waiter, until it polls again.
+    notified: bool,
+}
+
+/// Waits for the current limit to allow an operation.
+///
+/// The place in the queue is given up when the waiter is dropped.
+#[derive(Debug, Default)]
+pub(crate) struct Waiter {
+    queued: Option<(Arc<Inner>, usize)>,
+}
+
+/// Allows an operation to run, until it is dropped.
+pub(crate) struct Permit {
+    inner: Option<Arc<Inner>>,
+}
+
+thread_local! {
+    /// Tracks the limit for the current execution context.
+    static CURRENT_LIMIT: RefCell<Option<Limit>> = RefCell::new(None)
+}
+
+/// Set the default limit for the duration of the closure.
+///
+/// From within the closure, the filesystem operations are limited by
+/// `limit`.
+///
+/// # Panics
+///
+/// This function panics if there already is a default limit set.
+pub fn with_default<F, R>(limit: &Limit, enter: &mut Enter, f: F) -> R
+where
+    F: FnOnce(&mut Enter) -> R,
+{
+    // Ensure that the limit is removed from the thread-local context
+    // when leaving the scope. This handles cases that involve panicking.
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CURRENT_LIMIT.with(|current| {
+                *current.borrow_mut() = None;
+            });
+        }
+    }
+
+    let _r = Reset;
+
+    CURRENT_LIMIT.with(|current| {
+        let mut current = current.borrow_mut();
+
+        assert!(
+            current.is_none(),
+            "default filesystem limit already set for execution context"
+        );
+
+        *current = Some(limit.clone());
+    });
+
+    f(enter)
+}
+
+impl Builder {
+    /// Returns a new builder with the default configuration.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> Builder {
+        Builder {
+            concurrency: 16,
+            queue_depth: 1_024,
+        }
+    }
+
+    /// Set the maximum number of filesystem operations running at once.
+    ///
+    /// The default value is 16.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `val` is zero.
+    pub fn concurrency(&mut self, val: usize) -> &mut Self {
+        assert!(val > 0, "concurrency must be greater than zero");
+        self.concurrency = val;
+        self
+    }
+
+    /// Set the maximum number of filesystem operations waiting to run.
+    ///
+    /// Once this many operations are waiting, further operations fail right
+    /// away with a [`QueueFull`] error, so that the caller can shed load.
+    ///
+    /// The default value is 1,024.
+    ///
+    /// [`QueueFull`]: struct.QueueFull.html
+    pub fn queue_depth(&mut self, val: usize) -> &mut Self {
+        self.queue_depth = val;
+        self
+    }
+
+    /// Create the configured `Limit`.
+    pub fn build(&self) -> Limit {
+        Limit {
+            inner: Arc::new(Inner {
+                concurrency: self.concurrency,
+                queue_depth: self.queue_depth,
+                state: Mutex::new(State {
+                    running: 0,
+                    next_id: 0,
+                    waiters: BTreeMap::new(),
+                }),
+            }),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Limit {
+    /// Returns a new limit with the default configuration.
+    pub fn new() -> Limit {
+        Builder::new().build()
+    }
+
+    /// Returns the maximum number of filesystem operations running at once.
+    pub fn concurrency(&self) -> usize {
+        self.inner.concurrency
+    }
+
+    /// Returns the maximum number of filesystem operations waiting to run.
+    pub fn queue_depth(&self) -> usize {
+        self.inner.queue_depth
+    }
+}
+
+impl Default for Limit {
+    fn default() -> Limit {
+        Limit::new()
+    }
+}
+
+impl QueueFull {
+    fn new() -> io::Error {
+        io::Error::new(io::ErrorKind::Other, QueueFull { _p: () })
+    }
+}
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.description())
+    }
+}
+
+impl Error for QueueFull {
+    fn description(&self) -> &str {
+        "filesystem operation queue is full"
+    }
+}
+
+impl State {
+    /// Allows the first waiter which has not been notified yet to try again.
+    fn notify_next(&mut self) {
+        if let Some(waiting) = self.waiters.values_mut().find(|w| !w.notified) {
+            waiting.notified = true;
+            waiting.task.notify();
+        }
+    }
+}
+
+impl Waiter {
+    pub fn new() -> Waiter {
+        Waiter::default()
+    }
+
+    /// Waits for the current limit, if any, to allow one more operation.
+    pub fn poll_acquire(&mut self) -> Poll<Permit, io::Error> {
+        let inner = match self.queued {
+            Some((ref inner, _)) => inner.clone(),
+            None => match CURRENT_LIMIT.with(|current| current.borrow().clone()) {
+                Some(limit) => limit.inner,
+                None => return Ok(Async::Ready(Permit { inner: None })),
+            },
+        };
+
+        let mut state = inner.state.lock().unwrap();
+
+        if state.running < inner.concurrency {
+            state.running += 1;
+
+            if let Some((_, id)) = self.queued.take() {
+                state.waiters.remove(&id);
+            }
+            drop(state);
+
+            return Ok(Async::Ready(Permit { inner: Some(inner) }));
+        }
+
+        match self.queued {
+            Some((_, id)) => {
+                // Keep the place in the queue until the next completion.
+                let waiting = state.waiters.get_mut(&id).unwrap();
+                waiting.task = task::current();
+                waiting.notified = false;
+            }
+            None => {
+                if state.waiters.len() >= inner.queue_depth {
+                    return Err(QueueFull::new());
+                }
+
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.insert(
+                    id,
+                    Waiting {
+                        task: task::current(),
+                        notified: false,
+                    },
+                );
+
+                drop(state);
+                self.queued = Some((inner, id));
+            }
+        }
+
+        Ok(Async::NotReady)
+    }
+
+    /// Returns a permit, or a `WouldBlock` error if the operation has to wait.
+    pub fn try_acquire(&mut self) -> io::Result<Permit> {
+        match self.poll_acquire()? {
+            Async::Ready(permit) => Ok(permit),
+            Async::NotReady => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        if let Some((inner, id)) = self.queued.take() {
+            let mut state = inner.state.lock().unwrap();
+
+            if let Some(waiting) = state.waiters.remove(&id) {
+                // Pass on the completion this waiter did not make use of.
+                if waiting.notified {
+                    state.notify_next();
+                }
+            }
+        }
+    }
+}
+
+impl Permit {
+    /// Returns a permit for an operation which is not subject to any limit.
+    pub fn unlimited() -> Permit {
+        Permit { inner: None }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(ref inner) = self.inner {
+            let mut state = inner.state.lock().unwrap();
+            state.running -= 1;
+            state.notify_next();
+        }
+    }
+}
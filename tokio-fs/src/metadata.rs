@@ -1,4 +1,5 @@
 use super::blocking_io;
+use limit::Waiter;
 
 use futures::{Future, Poll};
 
@@ -21,6 +22,7 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> MetadataFuture<P>
@@ -28,7 +30,10 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     pub(crate) fn new(path: P) -> Self {
-        Self { path }
+        Self {
+            path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -40,6 +45,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        blocking_io(|| fs::metadata(&self.path))
+        blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::metadata(&self.path)
+        })
     }
 }
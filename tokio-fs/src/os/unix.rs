@@ -1,10 +1,16 @@
 //! Unix-specific extensions to primitives in the `tokio_fs` module.
 
 use std::io;
-use std::os::unix::fs;
+use std::os::unix::fs::{self, OpenOptionsExt as StdOpenOptionsExt};
+use std::os::unix::io::AsRawFd;
 use std::path::Path;
 
 use futures::{Future, Poll};
+use libc;
+
+use limit::Waiter;
+use sys::{cstr, cvt};
+use {File, OpenOptions};
 
 /// Creates a new symbolic link on the filesystem.
 ///
@@ -26,6 +32,7 @@ where
 {
     src: P,
     dst: Q,
+    waiter: Waiter,
 }
 
 impl<P, Q> SymlinkFuture<P, Q>
@@ -34,7 +41,11 @@ where
     Q: AsRef<Path>,
 {
     fn new(src: P, dst: Q) -> SymlinkFuture<P, Q> {
-        SymlinkFuture { src: src, dst: dst }
+        SymlinkFuture {
+            src: src,
+            dst: dst,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -47,6 +58,185 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::symlink(&self.src, &self.dst))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::symlink(&self.src, &self.dst)
+        })
     }
 }
+
+/// Unix-specific extensions to [`OpenOptions`].
+///
+/// This mirrors [`std::os::unix::fs::OpenOptionsExt`][std].
+///
+/// [`OpenOptions`]: ../../struct.OpenOptions.html
+/// [std]: https://doc.rust-lang.org/std/os/unix/fs/trait.OpenOptionsExt.html
+pub trait OpenOptionsExt {
+    /// Sets the mode bits that a new file will be created with.
+    ///
+    /// See the underlying [`mode`] call for details.
+    ///
+    /// [`mode`]: https://doc.rust-lang.org/std/os/unix/fs/trait.OpenOptionsExt.html#tymethod.mode
+    fn mode(&mut self, mode: u32) -> &mut Self;
+
+    /// Passes custom flags to the `flags` argument of `open`.
+    ///
+    /// This allows platform-specific flags such as `O_DIRECT`, `O_TMPFILE`
+    /// or `O_NOATIME` on Linux. The bits that define the access mode are
+    /// masked out. See the underlying [`custom_flags`] call for details.
+    ///
+    /// # Direct I/O
+    ///
+    /// A file opened with `O_DIRECT` bypasses the page cache, and the kernel
+    /// then requires each read and write to be aligned: the buffer address,
+    /// the length and the file offset must all be multiples of the logical
+    /// block size of the underlying device, typically 512 or 4096 bytes.
+    /// Otherwise, the operation fails with `EINVAL`.
+    ///
+    /// [`File`] passes the buffers given to `poll_read`, `poll_write` and the
+    /// vectored variants straight to the kernel, without copying them, so
+    /// these requirements apply to the caller's buffers. A buffer obtained
+    /// from a `Vec<u8>` is not aligned in general, and adapters which
+    /// buffer internally, such as `BufReader` or `File::lines`, should not
+    /// be used with direct I/O.
+    ///
+    /// Some filesystems, such as tmpfs, do not support `O_DIRECT` at all,
+    /// in which case opening the file fails.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use tokio::fs::OpenOptions;
+    /// use tokio::fs::os::unix::OpenOptionsExt;
+    /// use tokio::prelude::Future;
+    ///
+    /// fn main() {
+    ///     let task = OpenOptions::new()
+    ///         .read(true)
+    ///         .custom_flags(libc::O_DIRECT | libc::O_NOATIME)
+    ///         .open("data.db")
+    ///         .map(|file| {
+    ///             // reads must use aligned buffers
+    ///             # drop(file);
+    ///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+    ///
+    ///     tokio::run(task);
+    /// }
+    /// ```
+    ///
+    /// [`File`]: ../../struct.File.html
+    /// [`custom_flags`]: https://doc.rust-lang.org/std/os/unix/fs/trait.OpenOptionsExt.html#tymethod.custom_flags
+    fn custom_flags(&mut self, flags: i32) -> &mut Self;
+}
+
+impl OpenOptionsExt for OpenOptions {
+    fn mode(&mut self, mode: u32) -> &mut OpenOptions {
+        self.as_std_mut().mode(mode);
+        self
+    }
+
+    fn custom_flags(&mut self, flags: i32) -> &mut OpenOptions {
+        self.as_std_mut().custom_flags(flags);
+        self
+    }
+}
+
+/// Changes the owner and the group of a file or a directory.
+///
+/// Passing `None` leaves the owner, or the group, unchanged. Symbolic links
+/// are followed, the owner of their target is changed.
+///
+/// This is an async version of the `chown` function.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// use tokio::prelude::Future;
+/// use tokio::fs::os::unix::chown;
+///
+/// fn main() {
+///     // Change the group only
+///     let task = chown("foo.txt", None, Some(100))
+///         .map_err(|err| eprintln!("IO error: {:?}", err));
+///
+///     tokio::run(task);
+/// }
+/// ```
+pub fn chown<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>) -> ChownFuture<P> {
+    ChownFuture::new(path, uid, gid)
+}
+
+/// Future returned by `chown`.
+#[derive(Debug)]
+pub struct ChownFuture<P>
+where
+    P: AsRef<Path>,
+{
+    path: P,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    waiter: Waiter,
+}
+
+impl<P> ChownFuture<P>
+where
+    P: AsRef<Path>,
+{
+    fn new(path: P, uid: Option<u32>, gid: Option<u32>) -> ChownFuture<P> {
+        ChownFuture {
+            path,
+            uid,
+            gid,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl<P> Future for ChownFuture<P>
+where
+    P: AsRef<Path>,
+{
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (uid, gid) = ids(self.uid, self.gid);
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            let path = cstr(self.path.as_ref())?;
+            cvt(unsafe { libc::chown(path.as_ptr(), uid, gid) })?;
+            Ok(())
+        })
+    }
+}
+
+/// Unix-specific extensions to [`File`].
+///
+/// [`File`]: ../../struct.File.html
+pub trait FileExt {
+    /// Changes the owner and the group of the underlying file.
+    ///
+    /// Passing `None` leaves the owner, or the group, unchanged. This
+    /// corresponds to the `fchown` function.
+    fn poll_chown(&mut self, uid: Option<u32>, gid: Option<u32>) -> Poll<(), io::Error>;
+}
+
+impl FileExt for File {
+    fn poll_chown(&mut self, uid: Option<u32>, gid: Option<u32>) -> Poll<(), io::Error> {
+        let fd = self.as_raw_fd();
+        let (uid, gid) = ids(uid, gid);
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            cvt(unsafe { libc::fchown(fd, uid, gid) })?;
+            Ok(())
+        })
+    }
+}
+
+/// Converts optional ids, `-1` leaving the id unchanged.
+fn ids(uid: Option<u32>, gid: Option<u32>) -> (libc::uid_t, libc::gid_t) {
+    (
+        uid.map_or(!0, |uid| uid as libc::uid_t),
+        gid.map_or(!0, |gid| gid as libc::gid_t),
+    )
+}
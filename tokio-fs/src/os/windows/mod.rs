@@ -1,7 +1,9 @@
 //! Windows-specific extensions for the primitives in the `tokio_fs` module.
 
+mod open_options_ext;
 mod symlink_dir;
 mod symlink_file;
 
+pub use self::open_options_ext::OpenOptionsExt;
 pub use self::symlink_dir::{symlink_dir, SymlinkDirFuture};
 pub use self::symlink_file::{symlink_file, SymlinkFileFuture};
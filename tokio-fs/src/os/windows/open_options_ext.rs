@@ -0,0 +1,76 @@
+use std::os::windows::fs::OpenOptionsExt as StdOpenOptionsExt;
+
+use OpenOptions;
+
+/// Windows-specific extensions to [`OpenOptions`].
+///
+/// This mirrors [`std::os::windows::fs::OpenOptionsExt`][std].
+///
+/// [`OpenOptions`]: ../../struct.OpenOptions.html
+/// [std]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html
+pub trait OpenOptionsExt {
+    /// Overrides the `dwDesiredAccess` argument to the call to `CreateFile`
+    /// with the specified value.
+    ///
+    /// See the underlying [`access_mode`] call for details.
+    ///
+    /// [`access_mode`]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html#tymethod.access_mode
+    fn access_mode(&mut self, access: u32) -> &mut Self;
+
+    /// Overrides the `dwShareMode` argument to the call to `CreateFile` with
+    /// the specified value.
+    ///
+    /// See the underlying [`share_mode`] call for details.
+    ///
+    /// [`share_mode`]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html#tymethod.share_mode
+    fn share_mode(&mut self, val: u32) -> &mut Self;
+
+    /// Sets extra flags for the `dwFileFlags` argument to the call to
+    /// `CreateFile2`.
+    ///
+    /// See the underlying [`custom_flags`] call for details.
+    ///
+    /// [`custom_flags`]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html#tymethod.custom_flags
+    fn custom_flags(&mut self, flags: u32) -> &mut Self;
+
+    /// Sets the `dwFileAttributes` argument to the call to `CreateFile2`.
+    ///
+    /// See the underlying [`attributes`] call for details.
+    ///
+    /// [`attributes`]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html#tymethod.attributes
+    fn attributes(&mut self, val: u32) -> &mut Self;
+
+    /// Sets the `dwSecurityQosFlags` argument to the call to `CreateFile2`.
+    ///
+    /// See the underlying [`security_qos_flags`] call for details.
+    ///
+    /// [`security_qos_flags`]: https://doc.rust-lang.org/std/os/windows/fs/trait.OpenOptionsExt.html#tymethod.security_qos_flags
+    fn security_qos_flags(&mut self, flags: u32) -> &mut Self;
+}
+
+impl OpenOptionsExt for OpenOptions {
+    fn access_mode(&mut self, access: u32) -> &mut OpenOptions {
+        self.as_std_mut().access_mode(access);
+        self
+    }
+
+    fn share_mode(&mut self, val: u32) -> &mut OpenOptions {
+        self.as_std_mut().share_mode(val);
+        self
+    }
+
+    fn custom_flags(&mut self, flags: u32) -> &mut OpenOptions {
+        self.as_std_mut().custom_flags(flags);
+        self
+    }
+
+    fn attributes(&mut self, val: u32) -> &mut OpenOptions {
+        self.as_std_mut().attributes(val);
+        self
+    }
+
+    fn security_qos_flags(&mut self, flags: u32) -> &mut OpenOptions {
+        self.as_std_mut().security_qos_flags(flags);
+        self
+    }
+}
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::io;
 use std::os::windows::fs;
 use std::path::Path;
@@ -25,6 +27,7 @@ where
 {
     src: P,
     dst: Q,
+    waiter: Waiter,
 }
 
 impl<P, Q> SymlinkDirFuture<P, Q>
@@ -33,7 +36,11 @@ where
     Q: AsRef<Path>,
 {
     fn new(src: P, dst: Q) -> SymlinkDirFuture<P, Q> {
-        SymlinkDirFuture { src: src, dst: dst }
+        SymlinkDirFuture {
+            src: src,
+            dst: dst,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -46,6 +53,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::symlink_dir(&self.src, &self.dst))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::symlink_dir(&self.src, &self.dst)
+        })
     }
 }
@@ -1,9 +1,12 @@
+use limit::Waiter;
+
 use std::ffi::OsString;
 use std::fs::{self, DirEntry as StdDirEntry, FileType, Metadata, ReadDir as StdReadDir};
 use std::io;
 #[cfg(unix)]
 use std::os::unix::fs::DirEntryExt;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use futures::{Future, Poll, Stream};
 
@@ -26,6 +29,7 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> ReadDirFuture<P>
@@ -33,7 +37,10 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     fn new(path: P) -> ReadDirFuture<P> {
-        ReadDirFuture { path: path }
+        ReadDirFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -45,7 +52,9 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, io::Error> {
-        ::blocking_io(|| Ok(ReadDir(fs::read_dir(&self.path)?)))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            Ok(ReadDir(fs::read_dir(&self.path)?, Waiter::new()))
+        })
     }
 }
 
@@ -66,16 +75,16 @@ where
 /// [`Stream`]: ../futures/stream/trait.Stream.html
 /// [`Err`]: https://doc.rust-lang.org/std/result/enum.Result.html#variant.Err
 #[derive(Debug)]
-pub struct ReadDir(StdReadDir);
+pub struct ReadDir(StdReadDir, Waiter);
 
 impl Stream for ReadDir {
     type Item = DirEntry;
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        ::blocking_io(|| match self.0.next() {
+        ::blocking_io(try_ready!(self.1.poll_acquire()), || match self.0.next() {
             Some(Err(err)) => Err(err),
-            Some(Ok(item)) => Ok(Some(DirEntry(item))),
+            Some(Ok(item)) => Ok(Some(DirEntry(item, Mutex::new(Waiter::new())))),
             None => Ok(None),
         })
     }
@@ -94,7 +103,7 @@ impl Stream for ReadDir {
 ///
 /// [std]: https://doc.rust-lang.org/std/fs/struct.DirEntry.html
 #[derive(Debug)]
-pub struct DirEntry(StdDirEntry);
+pub struct DirEntry(StdDirEntry, Mutex<Waiter>);
 
 impl DirEntry {
     /// Destructures the `tokio_fs::DirEntry` into a [`std::fs::DirEntry`][std].
@@ -195,7 +204,8 @@ impl DirEntry {
     /// }
     /// ```
     pub fn poll_metadata(&self) -> Poll<Metadata, io::Error> {
-        ::blocking_io(|| self.0.metadata())
+        let permit = try_ready!(self.1.lock().unwrap().poll_acquire());
+        ::blocking_io(permit, || self.0.metadata())
     }
 
     /// Return the file type for the file that this entry points at.
@@ -231,7 +241,64 @@ impl DirEntry {
     /// }
     /// ```
     pub fn poll_file_type(&self) -> Poll<FileType, io::Error> {
-        ::blocking_io(|| self.0.file_type())
+        let permit = try_ready!(self.1.lock().unwrap().poll_acquire());
+        ::blocking_io(permit, || self.0.file_type())
+    }
+
+    /// Queries the metadata for the file that this entry points at.
+    ///
+    /// This is the future-returning version of [`poll_metadata`], which
+    /// resolves to both the entry and its metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate tokio;
+    /// # extern crate tokio_fs;
+    /// use futures::{Future, Stream};
+    ///
+    /// fn main() {
+    ///     let fut = tokio_fs::read_dir(".").flatten_stream().for_each(|dir| {
+    ///         dir.metadata().map(|(dir, metadata)| {
+    ///             println!("{:?}: {:?}", dir.path(), metadata.len());
+    ///         })
+    ///     }).map_err(|err| { eprintln!("Error: {:?}", err); () });
+    ///     tokio::run(fut);
+    /// }
+    /// ```
+    ///
+    /// [`poll_metadata`]: #method.poll_metadata
+    pub fn metadata(self) -> DirEntryMetadataFuture {
+        DirEntryMetadataFuture { entry: Some(self) }
+    }
+
+    /// Queries the file type for the file that this entry points at.
+    ///
+    /// This is the future-returning version of [`poll_file_type`], which
+    /// resolves to both the entry and its file type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # extern crate tokio;
+    /// # extern crate tokio_fs;
+    /// use futures::{Future, Stream};
+    ///
+    /// fn main() {
+    ///     let fut = tokio_fs::read_dir(".").flatten_stream().for_each(|dir| {
+    ///         dir.file_type().map(|(dir, file_type)| {
+    ///             println!("{:?}: {:?}", dir.path(), file_type);
+    ///         })
+    ///     }).map_err(|err| { eprintln!("Error: {:?}", err); () });
+    ///     tokio::run(fut);
+    /// }
+    /// ```
+    ///
+    /// [`poll_file_type`]: #method.poll_file_type
+    pub fn file_type(self) -> DirEntryFileTypeFuture {
+        DirEntryFileTypeFuture { entry: Some(self) }
     }
 }
 
@@ -241,3 +308,51 @@ impl DirEntryExt for DirEntry {
         self.0.ino()
     }
 }
+
+const POLL_AFTER_RESOLVE: &str = "Cannot poll DirEntry future after it resolves";
+
+/// Future returned by `DirEntry::metadata` and resolves to a
+/// `(DirEntry, Metadata)` instance.
+#[derive(Debug)]
+pub struct DirEntryMetadataFuture {
+    entry: Option<DirEntry>,
+}
+
+impl Future for DirEntryMetadataFuture {
+    type Item = (DirEntry, Metadata);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let metadata = try_ready!(self
+            .entry
+            .as_ref()
+            .expect(POLL_AFTER_RESOLVE)
+            .poll_metadata());
+
+        let entry = self.entry.take().expect(POLL_AFTER_RESOLVE);
+        Ok((entry, metadata).into())
+    }
+}
+
+/// Future returned by `DirEntry::file_type` and resolves to a
+/// `(DirEntry, FileType)` instance.
+#[derive(Debug)]
+pub struct DirEntryFileTypeFuture {
+    entry: Option<DirEntry>,
+}
+
+impl Future for DirEntryFileTypeFuture {
+    type Item = (DirEntry, FileType);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let file_type = try_ready!(self
+            .entry
+            .as_ref()
+            .expect(POLL_AFTER_RESOLVE)
+            .poll_file_type());
+
+        let entry = self.entry.take().expect(POLL_AFTER_RESOLVE);
+        Ok((entry, file_type).into())
+    }
+}
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -20,6 +22,7 @@ where
     P: AsRef<Path>,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> ReadLinkFuture<P>
@@ -27,7 +30,10 @@ where
     P: AsRef<Path>,
 {
     fn new(path: P) -> ReadLinkFuture<P> {
-        ReadLinkFuture { path: path }
+        ReadLinkFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -39,6 +45,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::read_link(&self.path))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::read_link(&self.path)
+        })
     }
 }
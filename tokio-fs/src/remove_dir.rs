@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -20,6 +22,7 @@ where
     P: AsRef<Path>,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> RemoveDirFuture<P>
@@ -27,7 +30,10 @@ where
     P: AsRef<Path>,
 {
     fn new(path: P) -> RemoveDirFuture<P> {
-        RemoveDirFuture { path: path }
+        RemoveDirFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -39,6 +45,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::remove_dir(&self.path))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::remove_dir(&self.path)
+        })
     }
 }
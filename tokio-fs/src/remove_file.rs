@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -24,6 +26,7 @@ where
     P: AsRef<Path>,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> RemoveFileFuture<P>
@@ -31,7 +34,10 @@ where
     P: AsRef<Path>,
 {
     fn new(path: P) -> RemoveFileFuture<P> {
-        RemoveFileFuture { path: path }
+        RemoveFileFuture {
+            path: path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -43,6 +49,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::remove_file(&self.path))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::remove_file(&self.path)
+        })
     }
 }
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -25,6 +27,7 @@ where
 {
     from: P,
     to: Q,
+    waiter: Waiter,
 }
 
 impl<P, Q> RenameFuture<P, Q>
@@ -33,7 +36,11 @@ where
     Q: AsRef<Path>,
 {
     fn new(from: P, to: Q) -> RenameFuture<P, Q> {
-        RenameFuture { from: from, to: to }
+        RenameFuture {
+            from: from,
+            to: to,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -46,6 +53,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::rename(&self.from, &self.to))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::rename(&self.from, &self.to)
+        })
     }
 }
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -21,6 +23,7 @@ where
 {
     path: P,
     perm: fs::Permissions,
+    waiter: Waiter,
 }
 
 impl<P> SetPermissionsFuture<P>
@@ -31,6 +34,7 @@ where
         SetPermissionsFuture {
             path: path,
             perm: perm,
+            waiter: Waiter::new(),
         }
     }
 }
@@ -43,6 +47,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        ::blocking_io(|| fs::set_permissions(&self.path, self.perm.clone()))
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::set_permissions(&self.path, self.perm.clone())
+        })
     }
 }
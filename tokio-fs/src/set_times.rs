@@ -0,0 +1,206 @@
+use limit::Waiter;
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use futures::{Future, Poll};
+
+/// Changes the last access and modification times of a file or a directory.
+///
+/// Symbolic links are followed, the times of their target are changed.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `utimensat` function on Unix
+/// and the `SetFileTime` function on Windows. Note that, this
+/// [may change in the future][changes].
+///
+/// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// use tokio::prelude::Future;
+/// use std::time::SystemTime;
+///
+/// fn main() {
+///     let now = SystemTime::now();
+///     let task = tokio::fs::set_times("foo.txt", now, now)
+///         .map_err(|err| eprintln!("IO error: {:?}", err));
+///
+///     tokio::run(task);
+/// }
+/// ```
+pub fn set_times<P: AsRef<Path>>(
+    path: P,
+    accessed: SystemTime,
+    modified: SystemTime,
+) -> SetTimesFuture<P> {
+    SetTimesFuture::new(path, accessed, modified)
+}
+
+/// Future returned by `set_times`.
+#[derive(Debug)]
+pub struct SetTimesFuture<P>
+where
+    P: AsRef<Path>,
+{
+    path: P,
+    accessed: SystemTime,
+    modified: SystemTime,
+    waiter: Waiter,
+}
+
+impl<P> SetTimesFuture<P>
+where
+    P: AsRef<Path>,
+{
+    fn new(path: P, accessed: SystemTime, modified: SystemTime) -> SetTimesFuture<P> {
+        SetTimesFuture {
+            path,
+            accessed,
+            modified,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl<P> Future for SetTimesFuture<P>
+where
+    P: AsRef<Path>,
+{
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (accessed, modified) = (self.accessed, self.modified);
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            sys::set_times(self.path.as_ref(), accessed, modified)
+        })
+    }
+}
+
+/// Changes the last access and modification times of an open file.
+pub(crate) fn set_file_times(
+    file: &File,
+    accessed: SystemTime,
+    modified: SystemTime,
+) -> io::Result<()> {
+    sys::set_file_times(file, accessed, modified)
+}
+
+#[cfg(unix)]
+mod sys {
+    use libc;
+    use sys::{cstr, cvt};
+
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    pub fn set_times(path: &Path, accessed: SystemTime, modified: SystemTime) -> io::Result<()> {
+        let path = cstr(path)?;
+        let times = [timespec(accessed), timespec(modified)];
+
+        cvt(unsafe { libc::utimensat(libc::AT_FDCWD, path.as_ptr(), times.as_ptr(), 0) })?;
+        Ok(())
+    }
+
+    pub fn set_file_times(
+        file: &File,
+        accessed: SystemTime,
+        modified: SystemTime,
+    ) -> io::Result<()> {
+        let times = [timespec(accessed), timespec(modified)];
+
+        cvt(unsafe { libc::futimens(file.as_raw_fd(), times.as_ptr()) })?;
+        Ok(())
+    }
+
+    fn timespec(time: SystemTime) -> libc::timespec {
+        // Times before the epoch have a negative number of seconds, and a
+        // positive number of nanoseconds.
+        let (sec, nsec) = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i64, d.subsec_nanos() as i64),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => (-(d.as_secs() as i64), 0),
+                    n => (-(d.as_secs() as i64) - 1, 1_000_000_000 - n as i64),
+                }
+            }
+        };
+
+        libc::timespec {
+            tv_sec: sec as libc::time_t,
+            tv_nsec: nsec as _,
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::fileapi::SetFileTime;
+    use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+    use winapi::um::winnt::FILE_WRITE_ATTRIBUTES;
+
+    use std::fs::{File, OpenOptions};
+    use std::io;
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use std::path::Path;
+    use std::ptr;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    // Number of 100ns intervals between 1601-01-01 and 1970-01-01.
+    const EPOCH_INTERVALS: i64 = 116_444_736_000_000_000;
+
+    pub fn set_times(path: &Path, accessed: SystemTime, modified: SystemTime) -> io::Result<()> {
+        // Directories can only be opened with backup semantics.
+        let file = OpenOptions::new()
+            .access_mode(FILE_WRITE_ATTRIBUTES)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+            .open(path)?;
+
+        set_file_times(&file, accessed, modified)
+    }
+
+    pub fn set_file_times(
+        file: &File,
+        accessed: SystemTime,
+        modified: SystemTime,
+    ) -> io::Result<()> {
+        let accessed = filetime(accessed);
+        let modified = filetime(modified);
+
+        let ret =
+            unsafe { SetFileTime(file.as_raw_handle() as _, ptr::null(), &accessed, &modified) };
+
+        if ret == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn filetime(time: SystemTime) -> FILETIME {
+        let intervals =
+            |d: Duration| d.as_secs() as i64 * 10_000_000 + d.subsec_nanos() as i64 / 100;
+
+        let intervals = match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => EPOCH_INTERVALS + intervals(d),
+            Err(e) => EPOCH_INTERVALS - intervals(e.duration()),
+        };
+
+        FILETIME {
+            dwLowDateTime: intervals as u32,
+            dwHighDateTime: (intervals >> 32) as u32,
+        }
+    }
+}
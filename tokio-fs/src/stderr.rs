@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use tokio_io::AsyncWrite;
 
 use futures::Poll;
@@ -16,6 +18,7 @@ use std::io::{self, Stderr as StdStderr, Write};
 #[derive(Debug)]
 pub struct Stderr {
     std: StdStderr,
+    waiter: Waiter,
 }
 
 /// Constructs a new handle to the standard error of the current process.
@@ -24,16 +27,19 @@ pub struct Stderr {
 /// Tokio runtime.
 pub fn stderr() -> Stderr {
     let std = io::stderr();
-    Stderr { std }
+    Stderr {
+        std,
+        waiter: Waiter::new(),
+    }
 }
 
 impl Write for Stderr {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        ::would_block(|| self.std.write(buf))
+        ::would_block(self.waiter.try_acquire()?, || self.std.write(buf))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        ::would_block(|| self.std.flush())
+        ::would_block(self.waiter.try_acquire()?, || self.std.flush())
     }
 }
 
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use tokio_io::AsyncRead;
 
 use std::io::{self, Read, Stdin as StdStdin};
@@ -20,6 +22,7 @@ use std::io::{self, Read, Stdin as StdStdin};
 #[derive(Debug)]
 pub struct Stdin {
     std: StdStdin,
+    waiter: Waiter,
 }
 
 /// Constructs a new handle to the standard input of the current process.
@@ -28,12 +31,15 @@ pub struct Stdin {
 /// Tokio runtime.
 pub fn stdin() -> Stdin {
     let std = io::stdin();
-    Stdin { std }
+    Stdin {
+        std,
+        waiter: Waiter::new(),
+    }
 }
 
 impl Read for Stdin {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        ::would_block(|| self.std.read(buf))
+        ::would_block(self.waiter.try_acquire()?, || self.std.read(buf))
     }
 }
 
@@ -1,3 +1,5 @@
+use limit::Waiter;
+
 use tokio_io::AsyncWrite;
 
 use futures::Poll;
@@ -16,6 +18,7 @@ use std::io::{self, Stdout as StdStdout, Write};
 #[derive(Debug)]
 pub struct Stdout {
     std: StdStdout,
+    waiter: Waiter,
 }
 
 /// Constructs a new handle to the standard output of the current process.
@@ -24,16 +27,19 @@ pub struct Stdout {
 /// runtime.
 pub fn stdout() -> Stdout {
     let std = io::stdout();
-    Stdout { std }
+    Stdout {
+        std,
+        waiter: Waiter::new(),
+    }
 }
 
 impl Write for Stdout {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        ::would_block(|| self.std.write(buf))
+        ::would_block(self.waiter.try_acquire()?, || self.std.write(buf))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        ::would_block(|| self.std.flush())
+        ::would_block(self.waiter.try_acquire()?, || self.std.flush())
     }
 }
 
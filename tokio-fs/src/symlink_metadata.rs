@@ -1,4 +1,5 @@
 use super::blocking_io;
+use limit::Waiter;
 
 use futures::{Future, Poll};
 
@@ -25,6 +26,7 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     path: P,
+    waiter: Waiter,
 }
 
 impl<P> SymlinkMetadataFuture<P>
@@ -32,7 +34,10 @@ where
     P: AsRef<Path> + Send + 'static,
 {
     pub(crate) fn new(path: P) -> Self {
-        Self { path }
+        Self {
+            path,
+            waiter: Waiter::new(),
+        }
     }
 }
 
@@ -44,6 +49,8 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        blocking_io(|| fs::symlink_metadata(&self.path))
+        blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            fs::symlink_metadata(&self.path)
+        })
     }
 }
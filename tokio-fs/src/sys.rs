@@ -0,0 +1,42 @@
+//! Helpers for calling into libc, shared by the Unix implementations.
+
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// Return values of libc functions, which report errors by returning `-1`.
+pub trait IsMinusOne {
+    fn is_minus_one(&self) -> bool;
+}
+
+impl IsMinusOne for i32 {
+    fn is_minus_one(&self) -> bool {
+        *self == -1
+    }
+}
+
+impl IsMinusOne for isize {
+    fn is_minus_one(&self) -> bool {
+        *self == -1
+    }
+}
+
+/// Converts the return value of a libc function, reading `errno` on failure.
+pub fn cvt<T: IsMinusOne>(ret: T) -> io::Result<T> {
+    if ret.is_minus_one() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+/// Converts a path to a C string, failing if it contains a NUL byte.
+pub fn cstr(path: &Path) -> io::Result<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "path contains an interior nul byte",
+        )
+    })
+}
@@ -0,0 +1,255 @@
+//! Temporary files and directories.
+//!
+//! These are asynchronous versions of the helpers of the [`tempfile`] crate.
+//! Temporary files and directories are created, and removed by `close`, on
+//! the blocking pool.
+//!
+//! [`tempfile`]: https://docs.rs/tempfile/3/tempfile/
+
+use file::File;
+use limit::Waiter;
+
+use futures::{Future, Poll};
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Creates a new temporary file, which is removed once closed.
+///
+/// The file has no name, so on some platforms it may be removed right away,
+/// and the space it uses is reclaimed once the returned `File` is dropped.
+///
+/// This is an async version of [`tempfile::tempfile`][tempfile].
+///
+/// [tempfile]: https://docs.rs/tempfile/3/tempfile/fn.tempfile.html
+pub fn tempfile() -> TempFileFuture {
+    TempFileFuture {
+        waiter: Waiter::new(),
+    }
+}
+
+/// Creates a new temporary directory.
+///
+/// This is an async version of [`tempfile::tempdir`][tempdir].
+///
+/// [tempdir]: https://docs.rs/tempfile/3/tempfile/fn.tempdir.html
+pub fn tempdir() -> TempDirFuture {
+    TempDirFuture {
+        waiter: Waiter::new(),
+    }
+}
+
+/// Future returned by `tempfile`.
+#[derive(Debug)]
+pub struct TempFileFuture {
+    waiter: Waiter,
+}
+
+/// A temporary file with a path on the filesystem.
+///
+/// The file can be read and written through the `NamedTempFile` itself, which
+/// implements [`AsyncRead`] and [`AsyncWrite`], or through [`as_file_mut`].
+///
+/// The file is removed by [`close`], on the blocking pool. Dropping a
+/// `NamedTempFile` removes the file as well, but blocks the current thread
+/// while doing so.
+///
+/// Created by [`NamedTempFile::new`].
+///
+/// [`AsyncRead`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncRead.html
+/// [`AsyncWrite`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncWrite.html
+/// [`as_file_mut`]: #method.as_file_mut
+/// [`close`]: #method.close
+/// [`NamedTempFile::new`]: #method.new
+#[derive(Debug)]
+pub struct NamedTempFile {
+    file: File,
+    path: ::tempfile::TempPath,
+}
+
+/// Future returned by `NamedTempFile::new`.
+#[derive(Debug)]
+pub struct NamedTempFileFuture {
+    waiter: Waiter,
+}
+
+/// A temporary directory.
+///
+/// The directory and its contents are removed by [`close`], on the blocking
+/// pool. Dropping a `TempDir` removes them as well, but blocks the current
+/// thread while doing so.
+///
+/// Created by the [`tempdir`] function.
+///
+/// [`close`]: #method.close
+/// [`tempdir`]: fn.tempdir.html
+#[derive(Debug)]
+pub struct TempDir {
+    inner: ::tempfile::TempDir,
+}
+
+/// Future returned by `tempdir`.
+#[derive(Debug)]
+pub struct TempDirFuture {
+    waiter: Waiter,
+}
+
+/// Future returned by `NamedTempFile::close` and `TempDir::close`.
+///
+/// # Panics
+///
+/// Will panic if polled after returning an item or error.
+#[derive(Debug)]
+pub struct CloseFuture {
+    inner: Option<Temp>,
+    waiter: Waiter,
+}
+
+#[derive(Debug)]
+enum Temp {
+    File(::tempfile::TempPath),
+    Dir(::tempfile::TempDir),
+}
+
+impl Future for TempFileFuture {
+    type Item = File;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            ::tempfile::tempfile().map(File::from_std)
+        })
+    }
+}
+
+impl NamedTempFile {
+    /// Creates a new temporary file in the temporary directory of the
+    /// platform.
+    ///
+    /// This is an async version of [`tempfile::NamedTempFile::new`][new].
+    ///
+    /// [new]: https://docs.rs/tempfile/3/tempfile/struct.NamedTempFile.html#method.new
+    pub fn new() -> NamedTempFileFuture {
+        NamedTempFileFuture {
+            waiter: Waiter::new(),
+        }
+    }
+
+    /// Returns the path of the temporary file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets a reference to the underlying file.
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Gets a mutable reference to the underlying file.
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Closes and removes the temporary file.
+    pub fn close(self) -> CloseFuture {
+        CloseFuture {
+            inner: Some(Temp::File(self.path)),
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl Read for NamedTempFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl AsyncRead for NamedTempFile {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl Write for NamedTempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl AsyncWrite for NamedTempFile {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.file.shutdown()
+    }
+}
+
+impl Future for NamedTempFileFuture {
+    type Item = NamedTempFile;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            let (file, path) = ::tempfile::NamedTempFile::new()?.into_parts();
+            Ok(NamedTempFile {
+                file: File::from_std(file),
+                path,
+            })
+        })
+    }
+}
+
+impl TempDir {
+    /// Returns the path of the temporary directory.
+    pub fn path(&self) -> &Path {
+        self.inner.path()
+    }
+
+    /// Removes the temporary directory and its contents.
+    pub fn close(self) -> CloseFuture {
+        CloseFuture {
+            inner: Some(Temp::Dir(self.inner)),
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+impl Future for TempDirFuture {
+    type Item = TempDir;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            ::tempfile::tempdir().map(|inner| TempDir { inner })
+        })
+    }
+}
+
+impl Future for CloseFuture {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let inner = &mut self.inner;
+
+        ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+            match inner
+                .take()
+                .expect("Cannot poll `CloseFuture` after it resolves")
+            {
+                Temp::File(path) => path.close(),
+                Temp::Dir(dir) => dir.close(),
+            }
+        })
+    }
+}
@@ -0,0 +1,545 @@
+//! File operations backed by io_uring.
+//!
+//! When the `io-uring` feature is enabled, reads, writes and syncs of a
+//! [`File`] are submitted to an io_uring instance shared by the process,
+//! instead of occupying a thread of the blocking pool while they run. A
+//! single background thread waits for the completions and notifies the
+//! tasks.
+//!
+//! The ring is set up on first use. Where io_uring is not available, such as
+//! on kernels older than 5.6 or when the system calls are filtered, the
+//! operations fall back to the blocking pool. So do the operations on files
+//! which cannot seek, such as pipes.
+//!
+//! An operation keeps running when the call which submitted it is abandoned,
+//! for instance when a read is followed by a seek instead of being retried.
+//! Before the file is used otherwise, the abandoned operation is completed
+//! and its effect on the file position applied: an abandoned read is undone
+//! by seeking back, so that the file only reflects the calls which returned.
+//! The bytes of an abandoned write stay in the file, as those of a write
+//! which failed halfway through would.
+//!
+//! The kernel accesses the buffers of an operation after the call which
+//! submitted it returns, so operations use buffers owned by the ring: data is
+//! copied from, or to, the caller's buffer. A buffer outlives the `File`
+//! which submitted the operation, until the kernel is done with it.
+//!
+//! [`File`]: ../file/struct.File.html
+
+use io_uring::{opcode, squeue, types, IoUring};
+use libc;
+
+use futures::task::{self, Task};
+use futures::{Async, Poll};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::{Condvar, Mutex, Once};
+use std::{cmp, thread, u32, u64};
+
+/// Number of entries of the submission queue.
+const ENTRIES: u32 = 256;
+
+/// Maximum number of bytes read or written by an operation.
+const MAX_LEN: usize = 1 << 20;
+
+/// Offset telling the kernel to use, and advance, the file position.
+const CURRENT_POS: u64 = u64::MAX;
+
+static INIT: Once = Once::new();
+static mut DRIVER: Option<&'static Driver> = None;
+
+/// The shared ring, and the state of the operations submitted to it.
+struct Driver {
+    state: Mutex<State>,
+    eventfd: RawFd,
+
+    // Notified when operations complete, for the callers waiting for them
+    // outside of a task.
+    completed: Condvar,
+}
+
+struct State {
+    ring: IoUring,
+    ops: HashMap<u64, Lifecycle>,
+    next_id: u64,
+}
+
+enum Lifecycle {
+    /// The operation was submitted, and has yet to complete.
+    Submitted(Option<Task>),
+
+    /// The operation completed with the given result.
+    Completed(i32),
+
+    /// The operation was dropped before completing, its buffer is kept alive
+    /// until then.
+    #[allow(dead_code)]
+    Ignored(Vec<u8>),
+}
+
+/// An operation submitted to the ring.
+struct Op {
+    driver: &'static Driver,
+    id: u64,
+
+    // Set until the operation completes and its buffer is handed back.
+    buf: Option<Vec<u8>>,
+}
+
+/// The operation in progress on a `File`, if any.
+#[derive(Default)]
+pub(crate) struct Pending {
+    op: Option<(Kind, Op)>,
+
+    // Whether the file can seek, and so use the ring, once known.
+    seekable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Read,
+    Write,
+    Fsync,
+}
+
+/// Returns the shared ring, setting it up if needed, or `None` if io_uring
+/// is not available.
+fn driver() -> Option<&'static Driver> {
+    unsafe {
+        INIT.call_once(|| {
+            DRIVER = Driver::new().ok();
+        });
+
+        DRIVER
+    }
+}
+
+impl Driver {
+    fn new() -> io::Result<&'static Driver> {
+        let ring = IoUring::new(ENTRIES)?;
+
+        // Reads and writes rely on the kernel tracking the file position, so
+        // that they are consistent with seeks and with the blocking pool.
+        if !ring.params().is_feature_rw_cur_pos() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "io_uring does not support the file position",
+            ));
+        }
+
+        let eventfd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC) };
+        if eventfd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        if let Err(e) = ring.submitter().register_eventfd(eventfd) {
+            unsafe { libc::close(eventfd) };
+            return Err(e);
+        }
+
+        // The driver lives for the rest of the process, as the reaper thread
+        // does.
+        let driver: &'static Driver = Box::leak(Box::new(Driver {
+            state: Mutex::new(State {
+                ring,
+                ops: HashMap::new(),
+                next_id: 0,
+            }),
+            eventfd,
+            completed: Condvar::new(),
+        }));
+
+        thread::Builder::new()
+            .name("tokio-fs-uring".to_string())
+            .spawn(move || driver.run())?;
+
+        Ok(driver)
+    }
+
+    /// Submits an operation using `buf`, or returns `None` if the submission
+    /// queue is full.
+    fn submit<F>(&'static self, mut buf: Vec<u8>, build: F) -> Option<Op>
+    where
+        F: FnOnce(&mut Vec<u8>) -> squeue::Entry,
+    {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+
+        let id = state.next_id;
+        let entry = build(&mut buf).user_data(id);
+
+        unsafe {
+            if state.ring.submission().push(&entry).is_err() {
+                // Let the kernel consume the queued entries, then try again.
+                let _ = state.ring.submit();
+                if state.ring.submission().push(&entry).is_err() {
+                    return None;
+                }
+            }
+        }
+
+        state.next_id += 1;
+        state.ops.insert(id, Lifecycle::Submitted(None));
+
+        // Once queued, the entry is submitted along with the next ones if
+        // this fails, so the operation is tracked either way.
+        let _ = state.ring.submit();
+
+        // Operations on cached data often complete during the submission,
+        // they are then ready without waiting for the background thread.
+        let tasks = state.complete();
+        drop(state);
+        self.completed.notify_all();
+
+        for task in tasks {
+            task.notify();
+        }
+
+        Some(Op {
+            driver: self,
+            id,
+            buf: Some(buf),
+        })
+    }
+
+    /// Waits for completions and notifies the tasks of the completed
+    /// operations.
+    fn run(&self) {
+        let mut buf = [0u8; 8];
+
+        loop {
+            let ret = unsafe { libc::read(self.eventfd, buf.as_mut_ptr() as *mut _, buf.len()) };
+
+            if ret == -1 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                panic!("failed to wait for io_uring completions; err={:?}", err);
+            }
+
+            let tasks = self.state.lock().unwrap().complete();
+            self.completed.notify_all();
+
+            for task in tasks {
+                task.notify();
+            }
+        }
+    }
+}
+
+impl State {
+    /// Records the completions, returning the tasks to notify.
+    fn complete(&mut self) -> Vec<Task> {
+        let mut tasks = Vec::new();
+
+        for cqe in self.ring.completion() {
+            let id = cqe.user_data();
+
+            match self.ops.remove(&id) {
+                Some(Lifecycle::Submitted(task)) => {
+                    self.ops.insert(id, Lifecycle::Completed(cqe.result()));
+                    tasks.extend(task);
+                }
+                // Ignored operations are released along with their buffer.
+                _ => {}
+            }
+        }
+
+        // Submit the entries left over by a failed submission.
+        if !self.ring.submission().is_empty() {
+            let _ = self.ring.submit();
+        }
+
+        tasks
+    }
+}
+
+impl Op {
+    /// Polls the operation, returning its result and its buffer once it
+    /// completes.
+    fn poll(&mut self) -> Async<(i32, Vec<u8>)> {
+        let mut state = self.driver.state.lock().unwrap();
+
+        let res = match state.ops.get_mut(&self.id) {
+            Some(&mut Lifecycle::Submitted(ref mut task)) => {
+                if !task.as_ref().map_or(false, |t| t.will_notify_current()) {
+                    *task = Some(task::current());
+                }
+                return Async::NotReady;
+            }
+            Some(&mut Lifecycle::Completed(res)) => res,
+            _ => panic!("io_uring operation polled after completion"),
+        };
+
+        state.ops.remove(&self.id);
+        Async::Ready((res, self.buf.take().unwrap()))
+    }
+
+    /// Blocks the current thread until the operation completes, returning its
+    /// result and its buffer.
+    fn wait(&mut self) -> (i32, Vec<u8>) {
+        let mut state = self.driver.state.lock().unwrap();
+
+        loop {
+            let res = match state.ops.get(&self.id) {
+                Some(&Lifecycle::Submitted(_)) => {
+                    state = self.driver.completed.wait(state).unwrap();
+                    continue;
+                }
+                Some(&Lifecycle::Completed(res)) => res,
+                _ => panic!("io_uring operation waited for after completion"),
+            };
+
+            state.ops.remove(&self.id);
+            return (res, self.buf.take().unwrap());
+        }
+    }
+}
+
+impl Drop for Op {
+    fn drop(&mut self) {
+        let buf = match self.buf.take() {
+            Some(buf) => buf,
+            None => return,
+        };
+
+        let mut state = self.driver.state.lock().unwrap();
+
+        if let Some(Lifecycle::Submitted(_)) = state.ops.remove(&self.id) {
+            // The kernel may still access the buffer.
+            state.ops.insert(self.id, Lifecycle::Ignored(buf));
+        }
+    }
+}
+
+impl fmt::Debug for Op {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Op").field("id", &self.id).finish()
+    }
+}
+
+impl Pending {
+    /// Reads from `file` into `buf`.
+    ///
+    /// Returns `None` if the operation must go through the blocking pool
+    /// instead. An operation in progress results in an error of kind
+    /// `WouldBlock`.
+    pub fn read(&mut self, file: &File, buf: &mut [u8]) -> Option<io::Result<usize>> {
+        if buf.is_empty() {
+            return Some(Ok(0));
+        }
+
+        if !self.is_seekable(file) {
+            return None;
+        }
+
+        let fd = types::Fd(file.as_raw_fd());
+        let len = cmp::min(buf.len(), MAX_LEN);
+
+        let res = self.poll_op(file, Kind::Read, vec![0; len], |data| {
+            opcode::Read::new(fd, data.as_mut_ptr(), data.len() as u32)
+                .offset(CURRENT_POS)
+                .build()
+        })?;
+
+        Some(would_block(res).and_then(|(n, data)| {
+            let n = n as usize;
+            let copied = cmp::min(n, buf.len());
+            buf[..copied].copy_from_slice(&data[..copied]);
+
+            // The caller may have retried with a smaller buffer, the bytes
+            // which do not fit are read again by the next call.
+            seek_back(file, n - copied)?;
+
+            Ok(copied)
+        }))
+    }
+
+    /// Writes `buf` to `file`.
+    ///
+    /// As with `AsyncWrite`, a write resulting in an error of kind
+    /// `WouldBlock` must be retried with the same data: the result of the
+    /// operation in progress is returned then. See `read` for details.
+    pub fn write(&mut self, file: &File, buf: &[u8]) -> Option<io::Result<usize>> {
+        if buf.is_empty() {
+            return Some(Ok(0));
+        }
+
+        if !self.is_seekable(file) {
+            return None;
+        }
+
+        let fd = types::Fd(file.as_raw_fd());
+        let len = cmp::min(buf.len(), MAX_LEN);
+
+        let res = self.poll_op(file, Kind::Write, buf[..len].to_vec(), |data| {
+            opcode::Write::new(fd, data.as_ptr(), data.len() as u32)
+                .offset(CURRENT_POS)
+                .build()
+        })?;
+
+        Some(would_block(res).map(|(n, _)| n as usize))
+    }
+
+    /// Synchronizes `file` with the filesystem, only its data if `datasync`
+    /// is set.
+    pub fn poll_fsync(&mut self, file: &File, datasync: bool) -> Option<Poll<(), io::Error>> {
+        if !self.is_seekable(file) {
+            return None;
+        }
+
+        let fd = types::Fd(file.as_raw_fd());
+
+        let res = self.poll_op(file, Kind::Fsync, Vec::new(), |_| {
+            let flags = if datasync {
+                types::FsyncFlags::DATASYNC
+            } else {
+                types::FsyncFlags::empty()
+            };
+
+            opcode::Fsync::new(fd).flags(flags).build()
+        })?;
+
+        Some(res.map(|ready| ready.map(|_| ())))
+    }
+
+    /// Completes the operation in progress, if any, which was abandoned by
+    /// its caller.
+    ///
+    /// This must be called before operating on `file` through the blocking
+    /// pool, as the operation would otherwise race with it.
+    pub fn poll_settle(&mut self, file: &File) -> Poll<(), io::Error> {
+        let (kind, mut op) = match self.op.take() {
+            Some(op) => op,
+            None => return Ok(Async::Ready(())),
+        };
+
+        match op.poll() {
+            Async::Ready((res, _)) => {
+                abandoned(file, kind, res)?;
+                Ok(Async::Ready(()))
+            }
+            Async::NotReady => {
+                self.op = Some((kind, op));
+                Ok(Async::NotReady)
+            }
+        }
+    }
+
+    /// Like `poll_settle`, blocking the current thread until the operation
+    /// completes.
+    pub fn settle(&mut self, file: &File) -> io::Result<()> {
+        match self.op.take() {
+            Some((kind, mut op)) => {
+                let (res, _) = op.wait();
+                abandoned(file, kind, res)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether `file` can seek, which is required to undo abandoned
+    /// reads.
+    fn is_seekable(&mut self, file: &File) -> bool {
+        *self.seekable.get_or_insert_with(|| unsafe {
+            libc::lseek(file.as_raw_fd(), 0, libc::SEEK_CUR) != -1
+        })
+    }
+
+    /// Polls the operation of the given kind, submitting it if needed.
+    ///
+    /// An operation in progress belongs to the caller if it is of the same
+    /// kind and, for writes, with the same data. Otherwise its caller
+    /// abandoned it, and it is completed before `build` is submitted.
+    fn poll_op<F>(
+        &mut self,
+        file: &File,
+        kind: Kind,
+        buf: Vec<u8>,
+        build: F,
+    ) -> Option<Poll<(i32, Vec<u8>), io::Error>>
+    where
+        F: FnOnce(&mut Vec<u8>) -> squeue::Entry,
+    {
+        let mut submit = Some((buf, build));
+
+        loop {
+            match self.op.take() {
+                Some((op_kind, mut op)) => {
+                    let (res, data) = match op.poll() {
+                        Async::Ready(ready) => ready,
+                        Async::NotReady => {
+                            self.op = Some((op_kind, op));
+                            return Some(Ok(Async::NotReady));
+                        }
+                    };
+
+                    let same = op_kind == kind
+                        && match submit {
+                            Some((ref buf, _)) if kind == Kind::Write => *buf == data,
+                            _ => true,
+                        };
+
+                    if same {
+                        return Some(if res < 0 {
+                            Err(io::Error::from_raw_os_error(-res))
+                        } else {
+                            Ok(Async::Ready((res, data)))
+                        });
+                    }
+
+                    if let Err(e) = abandoned(file, op_kind, res) {
+                        return Some(Err(e));
+                    }
+                }
+                None => {
+                    let (buf, build) = submit.take()?;
+                    let op = driver()?.submit(buf, build)?;
+                    self.op = Some((kind, op));
+                }
+            }
+        }
+    }
+}
+
+/// Applies the result of an operation abandoned by its caller.
+fn abandoned(file: &File, kind: Kind, res: i32) -> io::Result<()> {
+    match kind {
+        Kind::Read if res > 0 => seek_back(file, res as usize),
+        // Failed operations have no effect, and the bytes written by an
+        // abandoned write stay in the file.
+        _ => Ok(()),
+    }
+}
+
+/// Moves the position of `file` back by `n` bytes.
+fn seek_back(file: &File, n: usize) -> io::Result<()> {
+    if n > 0 && unsafe { libc::lseek(file.as_raw_fd(), -(n as libc::off_t), libc::SEEK_CUR) } == -1
+    {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+impl fmt::Debug for Pending {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Pending")
+            .field("op", &self.op.as_ref().map(|&(kind, _)| kind))
+            .finish()
+    }
+}
+
+/// Converts a pending operation into an error of kind `WouldBlock`, as
+/// expected from `Read` and `Write`.
+fn would_block<T>(res: Poll<T, io::Error>) -> io::Result<T> {
+    match res? {
+        Async::Ready(v) => Ok(v),
+        Async::NotReady => Err(io::ErrorKind::WouldBlock.into()),
+    }
+}
@@ -0,0 +1,315 @@
+use limit::Waiter;
+use read_dir::{read_dir, ReadDir, ReadDirFuture};
+
+use std::fmt;
+use std::fs::{self, DirEntry as StdDirEntry, FileType};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::usize;
+
+use futures::{Async, Future, Poll, Stream};
+
+/// Returns a stream over the entries within a directory and all of its
+/// subdirectories, recursively.
+///
+/// The tree is walked depth-first: the entries of a directory are yielded
+/// right after the directory itself. The root directory itself is not
+/// yielded. Only one directory is read at a time, and one directory handle
+/// is kept open for each level of the tree being walked.
+///
+/// The returned [`WalkDir`] stream can be configured before it is first
+/// polled, see its documentation for the available options.
+///
+/// [`WalkDir`]: struct.WalkDir.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// use tokio::prelude::{Future, Stream};
+///
+/// fn main() {
+///     let task = tokio::fs::walk_dir("src")
+///         .max_depth(3)
+///         .filter_entry(|entry| entry.path().extension().map_or(true, |ext| ext != "tmp"))
+///         .for_each(|entry| {
+///             println!("{}", entry.path().display());
+///             Ok(())
+///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+///
+///     tokio::run(task);
+/// }
+/// ```
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir {
+        root: Some(path.as_ref().to_owned()),
+        max_depth: usize::MAX,
+        follow_links: false,
+        filter: None,
+        opening: None,
+        stack: Vec::new(),
+        ancestors: Vec::new(),
+        entry: None,
+        waiter: Waiter::new(),
+    }
+}
+
+/// Stream of the entries in a directory tree.
+///
+/// This stream is returned from the [`walk_dir`] function and yields
+/// instances of [`WalkEntry`].
+///
+/// # Errors
+///
+/// This [`Stream`] will return an [`Err`] if a directory cannot be read, or
+/// if an entry cannot be inspected. Walking the tree can then go on by
+/// polling the stream again.
+///
+/// [`walk_dir`]: fn.walk_dir.html
+/// [`WalkEntry`]: struct.WalkEntry.html
+/// [`Stream`]: ../futures/stream/trait.Stream.html
+/// [`Err`]: https://doc.rust-lang.org/std/result/enum.Result.html#variant.Err
+pub struct WalkDir {
+    // The root directory, until it is opened.
+    root: Option<PathBuf>,
+
+    max_depth: usize,
+    follow_links: bool,
+    filter: Option<Box<FnMut(&WalkEntry) -> bool + Send>>,
+
+    // The directory being opened, if any.
+    opening: Option<ReadDirFuture<PathBuf>>,
+
+    // The directories being read, from the root to the innermost one.
+    stack: Vec<ReadDir>,
+
+    // The canonical paths of the directories in `stack`, to detect loops
+    // when following symbolic links.
+    ancestors: Vec<PathBuf>,
+
+    // The entry being inspected, if any.
+    entry: Option<StdDirEntry>,
+    waiter: Waiter,
+}
+
+/// Entries returned by the [`WalkDir`] stream.
+///
+/// [`WalkDir`]: struct.WalkDir.html
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: usize,
+}
+
+impl WalkDir {
+    /// Sets the maximum depth of the yielded entries.
+    ///
+    /// The entries directly within the root directory have a depth of 1.
+    /// By default, there is no maximum depth.
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether to follow symbolic links.
+    ///
+    /// When following symbolic links, a link to a directory is walked like
+    /// the directory itself, and the yielded entry has the file type of the
+    /// target. The root directory is then followed too. A link to one of the
+    /// directories containing it results in an error, instead of walking the
+    /// same directories forever.
+    ///
+    /// By default, symbolic links are not followed.
+    pub fn follow_links(mut self, follow_links: bool) -> WalkDir {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Sets a predicate selecting the entries to yield.
+    ///
+    /// An entry for which the predicate returns `false` is not yielded, and
+    /// if it is a directory, its contents are skipped.
+    pub fn filter_entry<F>(mut self, predicate: F) -> WalkDir
+    where
+        F: FnMut(&WalkEntry) -> bool + Send + 'static,
+    {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    fn open(&mut self, path: PathBuf, canonical: Option<PathBuf>) {
+        self.ancestors.extend(canonical);
+        self.opening = Some(read_dir(path));
+    }
+}
+
+impl Stream for WalkDir {
+    type Item = WalkEntry;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<WalkEntry>, io::Error> {
+        if self.root.is_some() {
+            let follow_links = self.follow_links;
+            let res = {
+                let root = self.root.as_ref().unwrap();
+                ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+                    canonical(root, follow_links)
+                })
+            };
+
+            let canonical = match res {
+                Ok(Async::Ready(canonical)) => canonical,
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => {
+                    self.root = None;
+                    return Err(e);
+                }
+            };
+
+            let root = self.root.take().unwrap();
+
+            if self.max_depth > 0 {
+                self.open(root, canonical);
+            }
+        }
+
+        loop {
+            if let Some(mut opening) = self.opening.take() {
+                match opening.poll() {
+                    Ok(Async::Ready(read_dir)) => self.stack.push(read_dir),
+                    Ok(Async::NotReady) => {
+                        self.opening = Some(opening);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => {
+                        if self.follow_links {
+                            self.ancestors.pop();
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+
+            if let Some(entry) = self.entry.take() {
+                let follow_links = self.follow_links;
+                let res = ::blocking_io(try_ready!(self.waiter.poll_acquire()), || {
+                    inspect(&entry, follow_links)
+                })?;
+
+                let (file_type, canonical) = match res {
+                    Async::Ready(inspected) => inspected,
+                    Async::NotReady => {
+                        self.entry = Some(entry);
+                        return Ok(Async::NotReady);
+                    }
+                };
+
+                let entry = WalkEntry {
+                    path: entry.path(),
+                    file_type,
+                    depth: self.stack.len(),
+                };
+
+                if let Some(ref mut filter) = self.filter {
+                    if !filter(&entry) {
+                        continue;
+                    }
+                }
+
+                if file_type.is_dir() && entry.depth < self.max_depth {
+                    if canonical
+                        .as_ref()
+                        .map_or(false, |path| self.ancestors.contains(path))
+                    {
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("filesystem loop found at {}", entry.path.display()),
+                        ));
+                    }
+
+                    self.open(entry.path.clone(), canonical);
+                }
+
+                return Ok(Async::Ready(Some(entry)));
+            }
+
+            let next = match self.stack.last_mut() {
+                Some(read_dir) => try_ready!(read_dir.poll()),
+                None => return Ok(Async::Ready(None)),
+            };
+
+            match next {
+                Some(entry) => self.entry = Some(entry.into_std()),
+                None => {
+                    self.stack.pop();
+                    if self.follow_links {
+                        self.ancestors.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .field("stack", &self.stack)
+            .finish()
+    }
+}
+
+/// Returns the canonical path of a directory, if loops are to be detected.
+fn canonical(path: &Path, follow_links: bool) -> io::Result<Option<PathBuf>> {
+    if follow_links {
+        fs::canonicalize(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the file type of an entry, following symbolic links if needed,
+/// and the canonical path of the directory it refers to.
+fn inspect(entry: &StdDirEntry, follow_links: bool) -> io::Result<(FileType, Option<PathBuf>)> {
+    let mut file_type = entry.file_type()?;
+
+    if follow_links && file_type.is_symlink() {
+        file_type = fs::metadata(entry.path())?.file_type();
+    }
+
+    if file_type.is_dir() {
+        Ok((file_type, canonical(&entry.path(), follow_links)?))
+    } else {
+        Ok((file_type, None))
+    }
+}
+
+impl WalkEntry {
+    /// Returns the full path to the file that this entry represents.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the entry, returning its path.
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the file type of the file that this entry represents.
+    ///
+    /// When following symbolic links, this is the file type of the target.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns the depth of the entry in the tree.
+    ///
+    /// The entries directly within the root directory have a depth of 1.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
@@ -0,0 +1,297 @@
+use super::{Event, EventKind};
+use sys::{cstr, cvt};
+
+use futures::{Async, Poll};
+use libc;
+use mio::unix::EventedFd;
+use mio::{self, Evented, PollOpt, Ready, Token};
+use tokio_reactor::PollEvented;
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::RawFd;
+use std::path::{Path, PathBuf};
+use std::{io, mem, ptr};
+
+const MASK: u32 = libc::IN_CREATE
+    | libc::IN_MODIFY
+    | libc::IN_ATTRIB
+    | libc::IN_DELETE
+    | libc::IN_DELETE_SELF
+    | libc::IN_MOVED_FROM
+    | libc::IN_MOVED_TO
+    | libc::IN_MOVE_SELF;
+
+// Mask of the parent directory, while waiting for the path to be created.
+const PARENT_MASK: u32 =
+    libc::IN_CREATE | libc::IN_MOVED_TO | libc::IN_DELETE_SELF | libc::IN_MOVE_SELF;
+
+/// Reads the events of an inotify instance watching a single path.
+///
+/// While the path does not exist, its parent directory is watched instead
+/// until the path is created.
+#[derive(Debug)]
+pub struct Watcher {
+    io: PollEvented<Inotify>,
+    path: PathBuf,
+
+    // The current watch, of the path or of its parent directory. Events of
+    // previous watches are ignored.
+    wd: libc::c_int,
+    parent: bool,
+
+    // Events read from the inotify instance, and not yielded yet.
+    buf: Vec<u8>,
+    pos: usize,
+    len: usize,
+
+    // Set once the parent directory has been removed.
+    done: bool,
+}
+
+#[derive(Debug)]
+struct Inotify {
+    fd: RawFd,
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> io::Result<Watcher> {
+        let fd = cvt(unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) })?;
+        let inotify = Inotify { fd };
+
+        let mut watcher = Watcher {
+            io: PollEvented::new(inotify),
+            path: path.to_owned(),
+            wd: -1,
+            parent: false,
+            // Large enough for several events with a name of `NAME_MAX` bytes.
+            buf: vec![0; 4096],
+            pos: 0,
+            len: 0,
+            done: false,
+        };
+
+        match add_watch(fd, path, MASK)? {
+            Some(wd) => watcher.wd = wd,
+            None => {
+                watcher.watch_parent()?;
+            }
+        }
+
+        Ok(watcher)
+    }
+
+    pub fn poll_event(&mut self) -> Poll<Option<Event>, io::Error> {
+        loop {
+            while !self.done && self.pos < self.len {
+                if let Some(event) = self.next_event()? {
+                    return Ok(Async::Ready(Some(event)));
+                }
+            }
+
+            if self.done {
+                return Ok(Async::Ready(None));
+            }
+
+            try_ready!(self.io.poll_read_ready(Ready::readable()));
+
+            let fd = self.io.get_ref().fd;
+            let n = unsafe { libc::read(fd, self.buf.as_mut_ptr() as *mut _, self.buf.len()) };
+
+            if n == -1 {
+                let err = io::Error::last_os_error();
+
+                if err.kind() == io::ErrorKind::WouldBlock {
+                    self.io.clear_read_ready(Ready::readable())?;
+                    return Ok(Async::NotReady);
+                }
+
+                return Err(err);
+            }
+
+            self.pos = 0;
+            self.len = n as usize;
+        }
+    }
+
+    /// Parses the next event in the buffer, skipping the ones which are not
+    /// reported.
+    fn next_event(&mut self) -> io::Result<Option<Event>> {
+        let raw = unsafe {
+            ptr::read_unaligned(self.buf[self.pos..].as_ptr() as *const libc::inotify_event)
+        };
+
+        let start = self.pos + mem::size_of::<libc::inotify_event>();
+        let end = start + raw.len as usize;
+        self.pos = end;
+
+        if raw.mask & libc::IN_Q_OVERFLOW != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "filesystem event queue overflowed",
+            ));
+        }
+
+        if raw.wd != self.wd {
+            return Ok(None);
+        }
+
+        // The name is padded with null bytes, and is empty for the events
+        // of the watched path itself.
+        let name = &self.buf[start..end];
+        let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(name.len())];
+
+        if self.parent {
+            let is_path = Some(OsStr::from_bytes(name)) == self.path.file_name();
+            return self.next_parent_event(raw.mask, is_path);
+        }
+
+        if raw.mask & libc::IN_IGNORED != 0 {
+            // The watched path is gone, wait for it to be created again.
+            return self.watch_parent();
+        }
+
+        if raw.mask & libc::IN_MOVE_SELF != 0 {
+            // The watch follows the path where it was moved, remove it, which
+            // is then handled as if the path had been removed.
+            unsafe {
+                libc::inotify_rm_watch(self.io.get_ref().fd, self.wd);
+            }
+        }
+
+        let kind = if raw.mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+            EventKind::Create
+        } else if raw.mask & (libc::IN_MODIFY | libc::IN_ATTRIB) != 0 {
+            EventKind::Modify
+        } else if raw.mask
+            & (libc::IN_DELETE | libc::IN_DELETE_SELF | libc::IN_MOVED_FROM | libc::IN_MOVE_SELF)
+            != 0
+        {
+            EventKind::Remove
+        } else {
+            return Ok(None);
+        };
+
+        let path = if name.is_empty() {
+            self.path.clone()
+        } else {
+            self.path.join(OsStr::from_bytes(name))
+        };
+
+        Ok(Some(Event { path, kind }))
+    }
+
+    /// Handles an event of the parent directory, while waiting for the path
+    /// to be created. `is_path` is set if the event is about the path.
+    fn next_parent_event(&mut self, mask: u32, is_path: bool) -> io::Result<Option<Event>> {
+        if mask & (libc::IN_DELETE_SELF | libc::IN_MOVE_SELF | libc::IN_IGNORED) != 0 {
+            // The path cannot be created anymore.
+            self.remove_watch();
+            self.done = true;
+            return Ok(None);
+        }
+
+        if is_path && mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0 {
+            return self.watch_path();
+        }
+
+        Ok(None)
+    }
+
+    /// Watches the parent directory until the path is created, returning an
+    /// event if the path was created in the meantime.
+    fn watch_parent(&mut self) -> io::Result<Option<Event>> {
+        let parent = match self.path.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+            Some(parent) => parent,
+            None => {
+                self.done = true;
+                return Ok(None);
+            }
+        };
+
+        match add_watch(self.io.get_ref().fd, parent, PARENT_MASK)? {
+            Some(wd) => {
+                self.wd = wd;
+                self.parent = true;
+            }
+            None => {
+                self.done = true;
+                return Ok(None);
+            }
+        }
+
+        self.watch_path()
+    }
+
+    /// Watches the path again, once it has been created, returning its
+    /// creation event.
+    fn watch_path(&mut self) -> io::Result<Option<Event>> {
+        let wd = match add_watch(self.io.get_ref().fd, &self.path, MASK)? {
+            Some(wd) => wd,
+            // The path was removed again before it could be watched.
+            None => return Ok(None),
+        };
+
+        self.remove_watch();
+        self.wd = wd;
+        self.parent = false;
+
+        Ok(Some(Event {
+            path: self.path.clone(),
+            kind: EventKind::Create,
+        }))
+    }
+
+    fn remove_watch(&mut self) {
+        unsafe {
+            libc::inotify_rm_watch(self.io.get_ref().fd, self.wd);
+        }
+    }
+}
+
+/// Adds a watch, returning `None` if the path does not exist.
+fn add_watch(fd: RawFd, path: &Path, mask: u32) -> io::Result<Option<libc::c_int>> {
+    let c_path = cstr(path)?;
+
+    match cvt(unsafe { libc::inotify_add_watch(fd, c_path.as_ptr(), mask) }) {
+        Ok(wd) => Ok(Some(wd)),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+impl Evented for Inotify {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.fd).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.fd).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        EventedFd(&self.fd).deregister(poll)
+    }
+}
+
+impl Drop for Inotify {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
@@ -0,0 +1,296 @@
+//! Watching the filesystem for changes.
+//!
+//! [`watch`] returns a [`Watch`] stream, yielding an [`Event`] for each
+//! change made to a file, or to the entries of a directory. A [`Builder`]
+//! allows debouncing the events, which is useful when a single change, such
+//! as saving a file from an editor, results in several events.
+//!
+//! This module requires the `watch` feature.
+//!
+//! # Platform-specific behavior
+//!
+//! Watching is currently implemented with `inotify` on Linux, and is driven
+//! by the reactor. On other platforms, the watched path is polled for
+//! changes, see [`Builder::poll_interval`]. Note that, this [may change in
+//! the future][changes].
+//!
+//! [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+//! [`watch`]: fn.watch.html
+//! [`Watch`]: struct.Watch.html
+//! [`Event`]: struct.Event.html
+//! [`Builder`]: struct.Builder.html
+//! [`Builder::poll_interval`]: struct.Builder.html#method.poll_interval
+
+#[cfg(target_os = "linux")]
+mod linux;
+mod poll;
+
+use limit::Waiter;
+
+use futures::{Async, Future, Poll, Stream};
+use tokio_timer::timer::Handle;
+use tokio_timer::Delay;
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Watches a file or a directory for changes.
+///
+/// This is a shortcut for `Builder::new().watch(path)`, see [`Watch`] for
+/// more details.
+///
+/// [`Watch`]: struct.Watch.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio;
+/// # extern crate tokio_fs;
+/// use tokio::prelude::{Future, Stream};
+///
+/// fn main() {
+///     let task = tokio_fs::watch("config.toml")
+///         .for_each(|event| {
+///             println!("{:?}", event);
+///             Ok(())
+///         }).map_err(|err| eprintln!("IO error: {:?}", err));
+///
+///     tokio::run(task);
+/// }
+/// ```
+pub fn watch<P: AsRef<Path>>(path: P) -> Watch {
+    Builder::new().watch(path)
+}
+
+/// Configures and creates a [`Watch`].
+///
+/// [`Watch`]: struct.Watch.html
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    debounce: Option<Duration>,
+    poll_interval: Option<Duration>,
+}
+
+/// Stream of the changes made to a watched file or directory.
+///
+/// Watching a directory reports the changes made to its entries, but not to
+/// the contents of its subdirectories.
+///
+/// When the watched path itself is removed or moved away, the stream waits
+/// for it to be created again, which is reported with an event of kind
+/// `Create`. The same goes for a path which does not exist yet. The stream
+/// ends if the parent directory of the path is removed or moved away.
+///
+/// The watch is set up on the blocking pool when the stream is first polled,
+/// so changes made before that are not reported.
+#[derive(Debug)]
+pub struct Watch {
+    path: PathBuf,
+    watcher: Option<Watcher>,
+    debounce: Option<Duration>,
+    poll_interval: Option<Duration>,
+
+    // Events received during the debounce window.
+    pending: VecDeque<Event>,
+
+    // Fires once no event has been received for the debounce duration.
+    delay: Option<Delay>,
+    waiter: Waiter,
+}
+
+/// A change made to the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event {
+    path: PathBuf,
+    kind: EventKind,
+}
+
+/// The kind of change reported by an [`Event`].
+///
+/// [`Event`]: struct.Event.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// A file or a directory was created, or moved into the watched
+    /// directory.
+    Create,
+
+    /// The contents or the metadata of a file were modified.
+    Modify,
+
+    /// A file or a directory was removed, or moved away.
+    Remove,
+}
+
+#[derive(Debug)]
+enum Watcher {
+    #[cfg(target_os = "linux")]
+    Inotify(linux::Watcher),
+    Poll(poll::Watcher),
+}
+
+/// Interval at which paths are polled on platforms without native support.
+#[cfg(not(target_os = "linux"))]
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Builder {
+    /// Returns a new builder with the default configuration.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Delays the events until none has been received for `duration`.
+    ///
+    /// The events received in the meantime are then yielded together, in the
+    /// order they were received, with repeated events only yielded once.
+    ///
+    /// Debouncing relies on a timer, so the stream must be polled from within
+    /// a task running in the context of a timer, such as on the Tokio
+    /// runtime.
+    pub fn debounce(&mut self, duration: Duration) -> &mut Self {
+        self.debounce = Some(duration);
+        self
+    }
+
+    /// Polls the watched path for changes every `interval`, instead of
+    /// relying on the notifications of the operating system.
+    ///
+    /// This is useful for filesystems which do not notify changes, such as
+    /// some network filesystems. On platforms where watching is not natively
+    /// supported, paths are polled every second unless this is set.
+    ///
+    /// Polling compares the size and modification time of the path, and of
+    /// the entries of a directory, between two intervals. Changes made in
+    /// between are merged, for instance a file created and removed during an
+    /// interval is not reported at all.
+    ///
+    /// Like debouncing, polling relies on a timer.
+    pub fn poll_interval(&mut self, interval: Duration) -> &mut Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Watches a file or a directory for changes, with this configuration.
+    pub fn watch<P: AsRef<Path>>(&self, path: P) -> Watch {
+        Watch {
+            path: path.as_ref().to_owned(),
+            watcher: None,
+            debounce: self.debounce,
+            poll_interval: self.poll_interval,
+            pending: VecDeque::new(),
+            delay: None,
+            waiter: Waiter::new(),
+        }
+    }
+}
+
+impl Watch {
+    /// Returns the watched path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn poll_event(&mut self) -> Poll<Option<Event>, io::Error> {
+        if self.watcher.is_none() {
+            self.watcher = Some(try_ready!(self.new_watcher()));
+        }
+
+        match *self.watcher.as_mut().unwrap() {
+            #[cfg(target_os = "linux")]
+            Watcher::Inotify(ref mut watcher) => watcher.poll_event(),
+            Watcher::Poll(ref mut watcher) => watcher.poll_event(),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn new_watcher(&mut self) -> Poll<Watcher, io::Error> {
+        if let Some(interval) = self.poll_interval {
+            return Ok(Async::Ready(Watcher::Poll(poll::Watcher::new(
+                &self.path, interval,
+            ))));
+        }
+
+        let path = &self.path;
+        let watcher = try_ready!(::blocking_io(
+            try_ready!(self.waiter.poll_acquire()),
+            || linux::Watcher::new(path)
+        ));
+        Ok(Async::Ready(Watcher::Inotify(watcher)))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn new_watcher(&mut self) -> Poll<Watcher, io::Error> {
+        let interval = self.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL);
+        Ok(Async::Ready(Watcher::Poll(poll::Watcher::new(
+            &self.path, interval,
+        ))))
+    }
+}
+
+impl Stream for Watch {
+    type Item = Event;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Event>, io::Error> {
+        let debounce = match self.debounce {
+            Some(debounce) => debounce,
+            None => return self.poll_event(),
+        };
+
+        if self.delay.is_none() {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Async::Ready(Some(event)));
+            }
+        }
+
+        loop {
+            match self.poll_event()? {
+                Async::Ready(Some(event)) => {
+                    if !self.pending.contains(&event) {
+                        self.pending.push_back(event);
+                    }
+
+                    let deadline = Handle::default().now() + debounce;
+
+                    match self.delay {
+                        Some(ref mut delay) => delay.reset(deadline),
+                        None => self.delay = Some(Delay::new(deadline)),
+                    }
+                }
+                Async::Ready(None) => break,
+                Async::NotReady => {
+                    let poll = match self.delay {
+                        Some(ref mut delay) => delay.poll(),
+                        None => return Ok(Async::NotReady),
+                    };
+
+                    match poll {
+                        Ok(Async::Ready(())) => break,
+                        Ok(Async::NotReady) => return Ok(Async::NotReady),
+                        Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e)),
+                    }
+                }
+            }
+        }
+
+        self.delay = None;
+        Ok(Async::Ready(self.pending.pop_front()))
+    }
+}
+
+impl Event {
+    /// Returns the path of the changed file or directory.
+    ///
+    /// When watching a directory, this is the path of the changed entry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the kind of change.
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+}
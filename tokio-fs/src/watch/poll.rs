@@ -0,0 +1,185 @@
+use super::{Event, EventKind};
+use limit::Waiter;
+
+use futures::{Async, Poll, Stream};
+use tokio_timer::timer::Handle;
+use tokio_timer::Interval;
+
+use std::collections::{BTreeMap, VecDeque};
+use std::ffi::OsString;
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Reports the changes made to a path by comparing snapshots of it, taken at
+/// a fixed interval.
+#[derive(Debug)]
+pub struct Watcher {
+    path: PathBuf,
+    interval: Interval,
+
+    // The last snapshot of the path, taken when the watcher is first polled.
+    snapshot: Option<Snapshot>,
+
+    // Set once the interval elapsed, until the next snapshot is taken.
+    elapsed: bool,
+
+    // Events found by the last comparison, and not yielded yet.
+    events: VecDeque<Event>,
+    waiter: Waiter,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    // `None` if the path does not exist.
+    stat: Option<Stat>,
+
+    // Entries of the path, if it is a directory.
+    entries: BTreeMap<OsString, Stat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stat {
+    is_dir: bool,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(path: &Path, interval: Duration) -> Watcher {
+        Watcher {
+            path: path.to_owned(),
+            interval: Interval::new(Handle::default().now() + interval, interval),
+            snapshot: None,
+            elapsed: false,
+            events: VecDeque::new(),
+            waiter: Waiter::new(),
+        }
+    }
+
+    pub fn poll_event(&mut self) -> Poll<Option<Event>, io::Error> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(Async::Ready(Some(event)));
+            }
+
+            if self.snapshot.is_none() {
+                let snapshot = {
+                    let path = &self.path;
+                    try_ready!(::blocking_io(
+                        try_ready!(self.waiter.poll_acquire()),
+                        || Snapshot::take(path)
+                    ))
+                };
+
+                self.snapshot = Some(snapshot);
+            }
+
+            if !self.elapsed {
+                try_ready!(self
+                    .interval
+                    .poll()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e)));
+
+                self.elapsed = true;
+            }
+
+            let snapshot = {
+                let path = &self.path;
+                try_ready!(::blocking_io(
+                    try_ready!(self.waiter.poll_acquire()),
+                    || Snapshot::take(path)
+                ))
+            };
+
+            self.elapsed = false;
+            self.compare(snapshot);
+        }
+    }
+
+    /// Queues the events of the changes from the last snapshot to `snapshot`.
+    fn compare(&mut self, snapshot: Snapshot) {
+        let old = self.snapshot.take().unwrap();
+
+        let kind = match (old.stat, snapshot.stat) {
+            (None, Some(_)) => Some(EventKind::Create),
+            (Some(_), None) => Some(EventKind::Remove),
+            // The entries of directories are compared instead
+            (Some(a), Some(b)) if a != b && !(a.is_dir && b.is_dir) => Some(EventKind::Modify),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            self.push(self.path.clone(), kind);
+        }
+
+        if old.stat.map_or(false, |s| s.is_dir) && snapshot.stat.map_or(false, |s| s.is_dir) {
+            for name in old.entries.keys() {
+                if !snapshot.entries.contains_key(name) {
+                    self.push(self.path.join(name), EventKind::Remove);
+                }
+            }
+
+            for (name, stat) in &snapshot.entries {
+                match old.entries.get(name) {
+                    None => self.push(self.path.join(name), EventKind::Create),
+                    Some(old) if old != stat => self.push(self.path.join(name), EventKind::Modify),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        self.snapshot = Some(snapshot);
+    }
+
+    fn push(&mut self, path: PathBuf, kind: EventKind) {
+        self.events.push_back(Event { path, kind });
+    }
+}
+
+impl Snapshot {
+    fn take(path: &Path) -> io::Result<Snapshot> {
+        let mut snapshot = Snapshot {
+            stat: None,
+            entries: BTreeMap::new(),
+        };
+
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(snapshot),
+            Err(e) => return Err(e),
+        };
+
+        snapshot.stat = Some(Stat::new(&metadata));
+
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+
+                // Entries removed since they were listed are left out.
+                match entry.metadata() {
+                    Ok(metadata) => {
+                        snapshot
+                            .entries
+                            .insert(entry.file_name(), Stat::new(&metadata));
+                    }
+                    Err(ref e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+impl Stat {
+    fn new(metadata: &Metadata) -> Stat {
+        Stat {
+            is_dir: metadata.is_dir(),
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+}
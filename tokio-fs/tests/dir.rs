@@ -70,3 +70,185 @@ fn read() {
         vec!["aa".to_string(), "bb".to_string(), "cc".to_string()]
     );
 }
+
+#[test]
+fn read_metadata() {
+    let base_dir = TempDir::new("base").unwrap();
+
+    let p = base_dir.path();
+    fs::create_dir(p.join("aa")).unwrap();
+    fs::write(p.join("bb"), b"hello").unwrap();
+
+    let entries = Arc::new(Mutex::new(Vec::new()));
+
+    let e = entries.clone();
+    let p = p.to_path_buf();
+    pool::run({
+        read_dir(p)
+            .flatten_stream()
+            .and_then(|entry| entry.metadata())
+            .and_then(|(entry, metadata)| {
+                entry
+                    .file_type()
+                    .map(move |(entry, file_type)| (entry, metadata, file_type))
+            })
+            .for_each(move |(entry, metadata, file_type)| {
+                let s = entry.file_name().to_str().unwrap().to_string();
+                e.lock()
+                    .unwrap()
+                    .push((s, metadata.is_dir(), file_type.is_dir()));
+                Ok(())
+            })
+    });
+
+    let mut entries = entries.lock().unwrap();
+    entries.sort();
+    assert_eq!(
+        *entries,
+        vec![
+            ("aa".to_string(), true, true),
+            ("bb".to_string(), false, false),
+        ]
+    );
+}
+
+/// Walks the tree at `path`, returning the yielded paths, relative to
+/// `path`, with their depths.
+fn walk_tree<F>(path: &::std::path::Path, configure: F) -> Vec<(String, usize)>
+where
+    F: FnOnce(WalkDir) -> WalkDir,
+{
+    let entries = Arc::new(Mutex::new(Vec::new()));
+
+    let e = entries.clone();
+    let base = path.to_path_buf();
+    pool::run({
+        configure(walk_dir(path)).for_each(move |entry| {
+            let s = entry.path().strip_prefix(&base).unwrap();
+            let s = s.to_str().unwrap().replace('\\', "/");
+            e.lock().unwrap().push((s, entry.depth()));
+            Ok(())
+        })
+    });
+
+    let entries = entries.lock().unwrap();
+    entries.clone()
+}
+
+#[test]
+fn walk() {
+    let base_dir = TempDir::new("base").unwrap();
+
+    let p = base_dir.path();
+    fs::create_dir_all(p.join("aa").join("bb")).unwrap();
+    fs::write(p.join("aa").join("bb").join("cc"), b"hello").unwrap();
+    fs::create_dir(p.join("dd")).unwrap();
+
+    let entries = walk_tree(p, |walk| walk);
+
+    // Each directory is directly followed by its contents
+    let position = |path: &str| entries.iter().position(|e| e.0 == path).unwrap();
+    assert_eq!(position("aa/bb"), position("aa") + 1);
+    assert_eq!(position("aa/bb/cc"), position("aa") + 2);
+
+    let mut entries = entries.clone();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("aa".to_string(), 1),
+            ("aa/bb".to_string(), 2),
+            ("aa/bb/cc".to_string(), 3),
+            ("dd".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn walk_max_depth() {
+    let base_dir = TempDir::new("base").unwrap();
+
+    let p = base_dir.path();
+    fs::create_dir_all(p.join("aa").join("bb")).unwrap();
+    fs::write(p.join("aa").join("bb").join("cc"), b"hello").unwrap();
+
+    let entries = walk_tree(p, |walk| walk.max_depth(2));
+    assert_eq!(
+        entries,
+        vec![("aa".to_string(), 1), ("aa/bb".to_string(), 2)]
+    );
+
+    assert!(walk_tree(p, |walk| walk.max_depth(0)).is_empty());
+}
+
+#[test]
+fn walk_filter_entry() {
+    let base_dir = TempDir::new("base").unwrap();
+
+    let p = base_dir.path();
+    fs::create_dir_all(p.join("aa").join("bb")).unwrap();
+    fs::create_dir_all(p.join("skip").join("bb")).unwrap();
+
+    let entries = walk_tree(p, |walk| {
+        walk.filter_entry(|entry| entry.path().file_name().unwrap() != "skip")
+    });
+
+    // The contents of a skipped directory are skipped as well
+    assert_eq!(
+        entries,
+        vec![("aa".to_string(), 1), ("aa/bb".to_string(), 2)]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn walk_follow_links() {
+    use std::os::unix::fs::symlink;
+
+    let base_dir = TempDir::new("base").unwrap();
+
+    let p = base_dir.path();
+    fs::create_dir_all(p.join("aa").join("bb")).unwrap();
+    symlink(p.join("aa"), p.join("link")).unwrap();
+
+    let mut entries = walk_tree(p, |walk| walk);
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("aa".to_string(), 1),
+            ("aa/bb".to_string(), 2),
+            ("link".to_string(), 1),
+        ]
+    );
+
+    let mut entries = walk_tree(p, |walk| walk.follow_links(true));
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            ("aa".to_string(), 1),
+            ("aa/bb".to_string(), 2),
+            ("link".to_string(), 1),
+            ("link/bb".to_string(), 2),
+        ]
+    );
+
+    // A link to one of its parents is a loop
+    symlink(p, p.join("aa").join("bb").join("parent")).unwrap();
+    let error = Arc::new(Mutex::new(None));
+
+    let e = error.clone();
+    pool::run({
+        walk_dir(p)
+            .follow_links(true)
+            .filter_entry(|entry| entry.path().file_name().unwrap() != "link")
+            .for_each(|_| Ok(()))
+            .or_else(move |err| {
+                *e.lock().unwrap() = Some(err.kind());
+                Ok(())
+            })
+    });
+
+    assert_eq!(*error.lock().unwrap(), Some(::std::io::ErrorKind::Other));
+}
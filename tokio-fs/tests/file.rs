@@ -1,4 +1,7 @@
+#[macro_use]
 extern crate futures;
+#[cfg(unix)]
+extern crate libc;
 extern crate rand;
 extern crate tempfile;
 extern crate tokio_fs;
@@ -8,7 +11,7 @@ use tokio_fs::*;
 use tokio_io::io;
 
 use futures::future::poll_fn;
-use futures::Future;
+use futures::{Future, Stream};
 use rand::{distributions, thread_rng, Rng};
 use tempfile::Builder as TmpBuilder;
 
@@ -155,6 +158,37 @@ fn seek() {
     });
 }
 
+#[test]
+fn async_seek() {
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("async_seek.txt");
+
+    pool::run({
+        OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .open(file_path)
+            .and_then(|file| io::write_all(file, "Hello, world!"))
+            .and_then(|(file, _)| io::seek(file, SeekFrom::Current(-6)))
+            .and_then(|(file, pos)| {
+                assert_eq!(pos, 7);
+                io::read_to_end(file, vec![])
+            })
+            .and_then(|(_, buf)| {
+                assert_eq!(buf, b"world!");
+                Ok(())
+            })
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+}
+
 #[test]
 fn clone() {
     use std::io::prelude::*;
@@ -188,3 +222,357 @@ fn clone() {
 
     assert_eq!(dst, b"clone successful")
 }
+
+#[test]
+#[cfg(unix)]
+fn read_write_vectored() {
+    use tokio_io::{AsyncRead, AsyncWrite, IoVec};
+
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("read_write_vectored.txt");
+
+    pool::run({
+        File::create(file_path.clone())
+            .and_then(|mut file| {
+                poll_fn(move || {
+                    let bufs: [&IoVec; 2] = [b"hello "[..].into(), b"world"[..].into()];
+                    file.poll_write_vectored(&bufs)
+                })
+            })
+            .then(|res| {
+                assert_eq!(res.unwrap(), 11);
+                Ok(())
+            })
+    });
+
+    assert_eq!(fs::read(&file_path).unwrap(), b"hello world");
+
+    pool::run({
+        File::open(file_path)
+            .and_then(|mut file| {
+                poll_fn(move || {
+                    let mut a = [0; 6];
+                    let mut b = [0; 5];
+
+                    let n = {
+                        let mut bufs: [&mut IoVec; 2] = [(&mut a[..]).into(), (&mut b[..]).into()];
+                        try_ready!(file.poll_read_vectored(&mut bufs))
+                    };
+
+                    Ok((n, a, b).into())
+                })
+            })
+            .then(|res| {
+                let (n, a, b) = res.unwrap();
+                assert_eq!(n, 11);
+                assert_eq!(&a, b"hello ");
+                assert_eq!(&b, b"world");
+                Ok(())
+            })
+    });
+}
+
+#[test]
+fn open_options_append_and_create_new() {
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("append.txt");
+    fs::write(&file_path, b"Hello").unwrap();
+
+    let path = file_path.clone();
+    pool::run({
+        OpenOptions::new()
+            .append(true)
+            .open(path)
+            .and_then(|file| io::write_all(file, ", world!"))
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+
+    assert_eq!(fs::read(&file_path).unwrap(), b"Hello, world!");
+
+    pool::run({
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(file_path)
+            .then(|r| {
+                let err = r.unwrap_err();
+                assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+                Ok(())
+            })
+    });
+}
+
+#[cfg(unix)]
+#[test]
+fn open_options_mode() {
+    use std::os::unix::fs::PermissionsExt;
+    use tokio_fs::os::unix::OpenOptionsExt;
+
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("mode.txt");
+
+    let path = file_path.clone();
+    pool::run({
+        OpenOptions::new()
+            .write(true)
+            .create(true)
+            .mode(0o600)
+            .open(path)
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+
+    let mode = fs::metadata(&file_path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[cfg(unix)]
+#[test]
+fn open_options_custom_flags() {
+    use std::os::unix::fs::symlink;
+    use tokio_fs::os::unix::OpenOptionsExt;
+
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("target.txt");
+    let link_path = dir.path().join("link.txt");
+    fs::write(&file_path, b"hello").unwrap();
+    symlink(&file_path, &link_path).unwrap();
+
+    // The flags are passed to `open`, which refuses to follow the link
+    pool::run({
+        OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NOFOLLOW)
+            .open(link_path.clone())
+            .then(|r| {
+                assert_eq!(r.unwrap_err().raw_os_error(), Some(libc::ELOOP));
+                Ok(())
+            })
+    });
+
+    pool::run({
+        OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NOFOLLOW)
+            .open(file_path)
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+}
+
+#[test]
+fn lock() {
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("lock.txt");
+    fs::write(&file_path, b"").unwrap();
+
+    pool::run({
+        let path = file_path.clone();
+        File::open(file_path.clone())
+            .and_then(|file| file.lock_exclusive().map_err(|(_, e)| e))
+            .and_then(move |lock| {
+                File::open(path).and_then(move |file| {
+                    file.try_lock().then(move |res| {
+                        let (file, err) = res.unwrap_err();
+                        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+                        // Dropping the guard releases the lock
+                        drop(lock);
+                        file.try_lock().map_err(|(_, e)| e)
+                    })
+                })
+            })
+            .and_then(|lock| lock.unlock().map_err(|(_, e)| e))
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+
+    pool::run({
+        let path = file_path.clone();
+        File::open(file_path.clone())
+            .and_then(|file| file.lock_shared().map_err(|(_, e)| e))
+            .and_then(move |lock| {
+                File::open(path)
+                    .and_then(|file| file.lock_shared().map_err(|(_, e)| e))
+                    .map(move |other| (lock, other))
+            })
+            .then(|r| {
+                let _ = r.unwrap();
+                Ok(())
+            })
+    });
+}
+
+#[test]
+fn lines() {
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("lines.txt");
+    fs::write(&file_path, "hello\nworld, this is a long line\n\nlast").unwrap();
+
+    pool::run({
+        let path = file_path.clone();
+        File::open(file_path)
+            .and_then(|file| file.lines().collect())
+            .and_then(move |lines| {
+                assert_eq!(lines, ["hello", "world, this is a long line", "", "last"]);
+
+                // A buffer smaller than the lines
+                File::open(path).and_then(|file| file.lines_with_capacity(4).collect())
+            })
+            .then(|r| {
+                let lines = r.unwrap();
+                assert_eq!(lines, ["hello", "world, this is a long line", "", "last"]);
+                Ok(())
+            })
+    });
+}
+
+#[test]
+fn std_conversions() {
+    use std::io::{Read, Seek, Write};
+
+    let mut std_file = tempfile::tempfile().unwrap();
+    std_file.write_all(b"hello world").unwrap();
+    std_file.seek(SeekFrom::Start(6)).unwrap();
+
+    #[cfg(unix)]
+    let fd = {
+        use std::os::unix::io::AsRawFd;
+        std_file.as_raw_fd()
+    };
+
+    let file = File::from(std_file);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        assert_eq!(file.as_raw_fd(), fd);
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    pool::run({
+        io::read_exact(file, [0; 3]).then(move |r| {
+            let (file, buf) = r.unwrap();
+            assert_eq!(&buf, b"wor");
+            tx.send(file.into_std()).unwrap();
+            Ok(())
+        })
+    });
+
+    // The cursor position is kept across the conversions
+    let mut std_file = rx.recv().unwrap();
+    let mut rest = String::new();
+    std_file.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "ld");
+}
+
+#[test]
+fn set_times() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("times.txt");
+    fs::write(&file_path, b"hello").unwrap();
+
+    let accessed = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let modified = UNIX_EPOCH + Duration::from_secs(1_234_567_890);
+
+    let path = file_path.clone();
+    pool::run({
+        tokio_fs::set_times(path, accessed, modified).then(|r| {
+            r.unwrap();
+            Ok(())
+        })
+    });
+
+    let metadata = fs::metadata(&file_path).unwrap();
+    assert_eq!(metadata.accessed().unwrap(), accessed);
+    assert_eq!(metadata.modified().unwrap(), modified);
+
+    let modified = UNIX_EPOCH + Duration::new(1_500_000_000, 500_000_000);
+
+    let path = file_path.clone();
+    pool::run({
+        OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(move |mut file| poll_fn(move || file.poll_set_times(accessed, modified)))
+            .then(|r| {
+                r.unwrap();
+                Ok(())
+            })
+    });
+
+    let metadata = fs::metadata(&file_path).unwrap();
+    assert_eq!(metadata.modified().unwrap(), modified);
+}
+
+#[cfg(unix)]
+#[test]
+fn chown() {
+    use std::os::unix::fs::MetadataExt;
+    use tokio_fs::os::unix::{chown, FileExt};
+
+    let dir = TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap();
+    let file_path = dir.path().join("chown.txt");
+    fs::write(&file_path, b"hello").unwrap();
+
+    // Changing to the current owner and group is always allowed
+    let metadata = fs::metadata(&file_path).unwrap();
+    let (uid, gid) = (metadata.uid(), metadata.gid());
+
+    let path = file_path.clone();
+    pool::run({
+        chown(path.clone(), Some(uid), None)
+            .and_then(move |_| File::open(path))
+            .and_then(move |mut file| poll_fn(move || file.poll_chown(None, Some(gid))))
+            .then(|r| {
+                r.unwrap();
+                Ok(())
+            })
+    });
+
+    let metadata = fs::metadata(&file_path).unwrap();
+    assert_eq!((metadata.uid(), metadata.gid()), (uid, gid));
+
+    pool::run({
+        chown(dir.path().join("missing"), None, None).then(|r| {
+            assert_eq!(r.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+            Ok(())
+        })
+    });
+}
@@ -0,0 +1,147 @@
+extern crate futures;
+#[cfg(unix)]
+extern crate libc;
+extern crate tempdir;
+extern crate tokio;
+extern crate tokio_fs;
+
+use futures::future::poll_fn;
+use futures::{Async, Future};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tempdir::TempDir;
+use tokio::runtime::{Builder, Runtime};
+use tokio_fs::limit;
+use tokio_fs::*;
+
+/// Creates a FIFO, which blocks an operation opening it for reading until it
+/// is opened for writing.
+#[cfg(unix)]
+fn mkfifo(dir: &Path) -> PathBuf {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = dir.join("fifo");
+    let cpath = CString::new(path.as_os_str().as_bytes()).unwrap();
+    assert_eq!(unsafe { libc::mkfifo(cpath.as_ptr(), 0o644) }, 0);
+    path
+}
+
+/// Polls a new `metadata` future once, until it is no longer allowed to run
+/// right away.
+#[cfg(unix)]
+fn poll_until_limited(rt: &mut Runtime, path: &Path) -> Async<()> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+
+    loop {
+        let mut fut = metadata(path.to_path_buf());
+        let res = rt.block_on(poll_fn(move || fut.poll().map(Async::Ready)));
+
+        match res {
+            Ok(Async::Ready(_)) => {}
+            Ok(Async::NotReady) => return Async::NotReady,
+            Err(err) => {
+                assert_eq!(err.kind(), io::ErrorKind::Other);
+                assert!(err.get_ref().unwrap().is::<limit::QueueFull>());
+                return Async::Ready(());
+            }
+        }
+
+        assert!(Instant::now() < deadline, "operation was never limited");
+        thread::yield_now();
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn queue_full() {
+    let dir = TempDir::new("base").unwrap();
+    let fifo = mkfifo(dir.path());
+
+    let mut rt = Builder::new()
+        .fs_limit(limit::Builder::new().concurrency(1).queue_depth(0).build())
+        .build()
+        .unwrap();
+
+    // Wait for a writer, taking the only operation allowed by the limit
+    let (tx, rx) = mpsc::channel();
+    rt.spawn(File::open(fifo.clone()).then(move |res| {
+        tx.send(res.is_ok()).unwrap();
+        Ok(())
+    }));
+
+    assert_eq!(poll_until_limited(&mut rt, dir.path()), Async::Ready(()));
+
+    fs::OpenOptions::new().write(true).open(&fifo).unwrap();
+    assert!(rx.recv().unwrap());
+}
+
+#[test]
+#[cfg(unix)]
+fn dropped_waiter_leaves_queue() {
+    let dir = TempDir::new("base").unwrap();
+    let fifo = mkfifo(dir.path());
+
+    let mut rt = Builder::new()
+        .fs_limit(limit::Builder::new().concurrency(1).queue_depth(1).build())
+        .build()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let opened = tx.clone();
+    rt.spawn(File::open(fifo.clone()).then(move |res| {
+        opened.send(res.is_ok()).unwrap();
+        Ok(())
+    }));
+
+    // Take the only place in the queue, then give it up
+    assert_eq!(poll_until_limited(&mut rt, dir.path()), Async::NotReady);
+
+    let queued = tx.clone();
+    rt.spawn(metadata(dir.path().to_path_buf()).then(move |res| {
+        queued.send(res.is_ok()).unwrap();
+        Ok(())
+    }));
+
+    fs::OpenOptions::new().write(true).open(&fifo).unwrap();
+    assert!(rx.recv().unwrap());
+    assert!(rx.recv().unwrap());
+}
+
+#[test]
+fn blocking_lock_is_not_limited() {
+    let dir = TempDir::new("base").unwrap();
+    let path = dir.path().join("lock.txt");
+    fs::write(&path, b"").unwrap();
+
+    let mut rt = Builder::new()
+        .fs_limit(limit::Builder::new().concurrency(1).queue_depth(0).build())
+        .build()
+        .unwrap();
+
+    let lock = rt
+        .block_on(File::open(path.clone()).and_then(|file| file.try_lock().map_err(|(_, e)| e)))
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    rt.spawn(
+        File::open(path.clone())
+            .and_then(|file| file.lock_exclusive().map_err(|(_, e)| e))
+            .then(move |res| {
+                tx.send(res.is_ok()).unwrap();
+                Ok(())
+            }),
+    );
+
+    // Other operations run while the lock is being waited for
+    for _ in 0..10 {
+        rt.block_on(metadata(path.clone())).unwrap();
+    }
+
+    drop(lock);
+    assert!(rx.recv().unwrap());
+}
@@ -59,6 +59,67 @@ fn test_symlink() {
 
     assert!(content == "hello");
 
+    let real = fs::canonicalize(&src).unwrap();
+
     pool::run({ read_link(dst.clone()).map(move |x| assert!(x == src)) });
     pool::run({ symlink_metadata(dst.clone()).map(move |x| assert!(x.file_type().is_symlink())) });
+
+    pool::run({ canonicalize(dst).map(move |x| assert_eq!(x, real)) });
+}
+
+#[test]
+fn test_copy() {
+    let dir = TempDir::new("base").unwrap();
+    let src = dir.path().join("src.txt");
+    let dst = dir.path().join("dst.txt");
+
+    fs::write(&src, b"hello").unwrap();
+
+    pool::run({ copy(src, dst.clone()).map(|n| assert_eq!(n, 5)) });
+
+    assert_eq!(fs::read(dst).unwrap(), b"hello");
+}
+
+#[test]
+fn test_copy_with_progress() {
+    use std::sync::{Arc, Mutex};
+
+    let dir = TempDir::new("base").unwrap();
+    let src = dir.path().join("src.txt");
+    let contents: Vec<u8> = (0..10_000).map(|i| i as u8).collect();
+
+    fs::write(&src, &contents).unwrap();
+
+    for &reflink in &[false, true] {
+        let dst = dir.path().join("dst.txt");
+        let progress = Arc::new(Mutex::new(vec![]));
+        let progress2 = progress.clone();
+
+        pool::run({
+            CopyOptions::new()
+                .chunk_size(4_096)
+                .reflink(reflink)
+                .copy(src.clone(), dst.clone(), move |copied, total| {
+                    progress2.lock().unwrap().push((copied, total));
+                })
+                .map(|n| assert_eq!(n, 10_000))
+        });
+
+        assert_eq!(fs::read(dst).unwrap(), contents);
+
+        let progress = progress.lock().unwrap();
+        assert_eq!(progress.last(), Some(&(10_000, 10_000)));
+
+        if !reflink {
+            assert_eq!(
+                *progress,
+                [
+                    (0, 10_000),
+                    (4_096, 10_000),
+                    (8_192, 10_000),
+                    (10_000, 10_000)
+                ]
+            );
+        }
+    }
 }
@@ -0,0 +1,48 @@
+extern crate futures;
+extern crate tokio_fs;
+extern crate tokio_io;
+
+use futures::Future;
+use std::fs;
+use tokio_fs::*;
+use tokio_io::io::{read_to_end, write_all};
+
+mod pool;
+
+#[test]
+fn temp_file() {
+    pool::run({
+        tempfile()
+            .and_then(|file| write_all(file, b"hello"))
+            .and_then(|(mut file, _)| file.poll_seek(std::io::SeekFrom::Start(0)).map(|_| file))
+            .and_then(|file| read_to_end(file, vec![]))
+            .map(|(_, buf)| assert_eq!(buf, b"hello"))
+    });
+}
+
+#[test]
+fn named_temp_file() {
+    pool::run({
+        NamedTempFile::new()
+            .and_then(|file| write_all(file, b"hello"))
+            .and_then(|(file, _)| {
+                let path = file.path().to_owned();
+                assert_eq!(fs::read(&path).unwrap(), b"hello");
+
+                file.close().map(move |()| assert!(!path.exists()))
+            })
+    });
+}
+
+#[test]
+fn temp_dir() {
+    pool::run({
+        tempdir().and_then(|dir| {
+            let path = dir.path().to_owned();
+            assert!(path.is_dir());
+            fs::write(path.join("foo.txt"), b"hello").unwrap();
+
+            dir.close().map(move |()| assert!(!path.exists()))
+        })
+    });
+}
@@ -0,0 +1,222 @@
+#![cfg(all(feature = "io-uring", target_os = "linux"))]
+
+#[macro_use]
+extern crate futures;
+extern crate libc;
+extern crate tempfile;
+extern crate tokio_fs;
+extern crate tokio_io;
+
+use tokio_fs::*;
+use tokio_io::{io, AsyncRead, AsyncWrite};
+
+use futures::future::poll_fn;
+use futures::{Async, Future};
+use tempfile::{Builder as TmpBuilder, TempDir};
+
+use std::fs;
+use std::io::SeekFrom;
+
+mod pool;
+
+#[test]
+fn read_write() {
+    // Larger than a single operation
+    let contents: Vec<u8> = (0..3 * 1024 * 1024).map(|i| i as u8).collect();
+
+    let dir = tempdir();
+    let file_path = dir.path().join("read_write.bin");
+
+    let expected = contents.clone();
+    pool::run({
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(file_path.clone())
+            .and_then(move |file| io::write_all(file, contents))
+            .and_then(|(mut file, _)| poll_fn(move || file.poll_sync_data()).map(|_| ()))
+            .then(move |r| {
+                r.unwrap();
+                Ok(())
+            })
+    });
+
+    assert_eq!(fs::read(&file_path).unwrap(), expected);
+
+    pool::run({
+        File::open(file_path)
+            .and_then(|file| file.seek(SeekFrom::Start(1024)))
+            .and_then(|(file, _)| io::read_to_end(file, vec![]))
+            .then(move |r| {
+                let (_, buf) = r.unwrap();
+                assert_eq!(&buf[..], &expected[1024..]);
+                Ok(())
+            })
+    });
+}
+
+/// Returns a file with the given contents, opened for reading and writing,
+/// and evicted from the page cache so that reads remain in progress for a
+/// while.
+fn uncached(dir: &TempDir, name: &str, contents: &[u8]) -> File {
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+
+    let path = dir.path().join(name);
+
+    let mut file = fs::File::create(&path).unwrap();
+    file.write_all(contents).unwrap();
+    file.sync_all().unwrap();
+
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&path)
+        .unwrap();
+
+    let ret = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    assert_eq!(ret, 0);
+
+    File::from_std(file)
+}
+
+fn tempdir() -> TempDir {
+    TmpBuilder::new()
+        .prefix("tokio-fs-tests")
+        .tempdir()
+        .unwrap()
+}
+
+#[test]
+fn read_retry_smaller_buffer() {
+    let dir = tempdir();
+    let mut file = uncached(&dir, "retry.txt", b"hello world");
+    let mut buf = [0; 11];
+    let mut step = 0;
+
+    pool::run({
+        poll_fn(move || loop {
+            match step {
+                // The read is submitted with a large buffer...
+                0 => match file.poll_read(&mut buf)? {
+                    Async::Ready(n) => {
+                        // The read completed right away
+                        assert_eq!(&buf[..n], b"hello world");
+                        return Ok(Async::Ready(()));
+                    }
+                    Async::NotReady => {
+                        step = 1;
+                        return Ok(Async::NotReady);
+                    }
+                },
+                // ...and completed with a smaller one
+                1 => {
+                    let mut small = [0; 5];
+                    let n = try_ready!(file.poll_read(&mut small));
+                    assert_eq!(&small[..n], b"hello");
+                    step = 2;
+                }
+                // The rest is returned by the next call
+                _ => {
+                    let n = try_ready!(file.poll_read(&mut buf));
+                    assert_eq!(&buf[..n], b" world");
+                    return Ok(Async::Ready(()));
+                }
+            }
+        })
+        .then(|r: ::std::io::Result<()>| {
+            r.unwrap();
+            Ok(())
+        })
+    });
+}
+
+/// Submits a read the first time it is called, which is abandoned unless it
+/// completes right away, returning the number of bytes it read.
+fn read_once(file: &mut File, read: &mut Option<usize>) -> ::std::io::Result<usize> {
+    if read.is_none() {
+        let mut buf = [0; 4096];
+        *read = Some(match file.poll_read(&mut buf)? {
+            Async::Ready(n) => n,
+            Async::NotReady => 0,
+        });
+    }
+
+    Ok(read.unwrap())
+}
+
+#[test]
+fn read_then_seek() {
+    let contents: Vec<u8> = (0..64 * 1024).map(|i| i as u8).collect();
+    let dir = tempdir();
+    let mut file = Some(uncached(&dir, "read_seek.bin", &contents));
+    let mut read = None;
+
+    pool::run({
+        poll_fn(move || {
+            let n = read_once(file.as_mut().unwrap(), &mut read)?;
+
+            // The position only reflects the reads which returned
+            let pos = try_ready!(file.as_mut().unwrap().poll_seek(SeekFrom::Current(0)));
+            assert_eq!(pos, n as u64);
+
+            Ok(Async::Ready((file.take().unwrap(), n)))
+        })
+        .and_then(|(file, n)| io::read_to_end(file, vec![]).map(move |(_, buf)| (n, buf)))
+        .then(move |r| {
+            let (n, buf) = r.unwrap();
+            assert_eq!(&buf[..], &contents[n..]);
+            Ok(())
+        })
+    });
+}
+
+#[test]
+fn read_then_write() {
+    let contents: Vec<u8> = (0..64 * 1024).map(|i| i as u8).collect();
+    let dir = tempdir();
+    let path = dir.path().join("read_write.bin");
+    let mut file = uncached(&dir, "read_write.bin", &contents);
+    let mut read = None;
+
+    let (tx, rx) = ::std::sync::mpsc::channel();
+    pool::run({
+        poll_fn(move || {
+            let n = read_once(&mut file, &mut read)?;
+            try_ready!(file.poll_write(b"xyz"));
+            tx.send(n).unwrap();
+            Ok(Async::Ready(()))
+        })
+        .then(|r: ::std::io::Result<()>| {
+            r.unwrap();
+            Ok(())
+        })
+    });
+
+    // The data is written where the reads which returned left the position
+    let n = rx.recv().unwrap();
+    let mut expected = contents.clone();
+    expected[n..n + 3].copy_from_slice(b"xyz");
+    assert_eq!(fs::read(&path).unwrap(), expected);
+}
+
+#[test]
+fn drop_in_flight() {
+    let dir = tempdir();
+    let mut file = uncached(&dir, "drop.bin", &vec![0; 1024 * 1024]);
+
+    pool::run({
+        poll_fn(move || {
+            let mut buf = vec![0; 1024 * 1024];
+            let _ = file.poll_read(&mut buf)?;
+            Ok(Async::Ready(()))
+        })
+        .then(|r: ::std::io::Result<()>| {
+            r.unwrap();
+            Ok(())
+        })
+    });
+
+    // The ring keeps the buffer of the read alive until it completes
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "watch")]
+
+extern crate futures;
+extern crate tempdir;
+extern crate tokio;
+extern crate tokio_fs;
+
+use futures::future::lazy;
+use futures::Stream;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tempdir::TempDir;
+use tokio::runtime::Runtime;
+use tokio_fs::watch::{Builder, EventKind};
+
+/// Watches a new directory while `f` changes it, returning the names and
+/// kinds of the first `n` events.
+fn events<F>(builder: Builder, n: u64, f: F) -> Vec<(String, EventKind)>
+where
+    F: FnOnce(&Path) + Send + 'static,
+{
+    let dir = TempDir::new("base").unwrap();
+    let path = dir.path().to_owned();
+    let mut rt = Runtime::new().unwrap();
+
+    let events = rt
+        .block_on(lazy(move || {
+            let mut watch = builder.watch(&path);
+
+            // Set up the watch before making any change
+            assert!(watch.poll().unwrap().is_not_ready());
+            f(&path);
+
+            watch.take(n).collect()
+        }))
+        .unwrap();
+
+    events
+        .into_iter()
+        .map(|event| {
+            let name = event.path().file_name().unwrap().to_str().unwrap();
+            (name.to_string(), event.kind())
+        })
+        .collect()
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn watch_dir() {
+    let events = events(Builder::new(), 4, |path| {
+        fs::write(path.join("foo.txt"), b"hello").unwrap();
+        fs::rename(path.join("foo.txt"), path.join("bar.txt")).unwrap();
+    });
+
+    assert_eq!(
+        events,
+        vec![
+            ("foo.txt".to_string(), EventKind::Create),
+            ("foo.txt".to_string(), EventKind::Modify),
+            ("foo.txt".to_string(), EventKind::Remove),
+            ("bar.txt".to_string(), EventKind::Create),
+        ]
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn watch_debounce() {
+    let mut builder = Builder::new();
+    builder.debounce(Duration::from_millis(50));
+
+    let events = events(builder, 3, |path| {
+        fs::write(path.join("foo.txt"), b"hello").unwrap();
+        fs::write(path.join("foo.txt"), b"world").unwrap();
+        fs::remove_file(path.join("foo.txt")).unwrap();
+    });
+
+    // The second modification is merged with the first one
+    assert_eq!(
+        events,
+        vec![
+            ("foo.txt".to_string(), EventKind::Create),
+            ("foo.txt".to_string(), EventKind::Modify),
+            ("foo.txt".to_string(), EventKind::Remove),
+        ]
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn watch_recreated() {
+    let dir = TempDir::new("base").unwrap();
+    let path = dir.path().join("watched");
+    fs::create_dir(&path).unwrap();
+    let mut rt = Runtime::new().unwrap();
+
+    let events = rt
+        .block_on(lazy(move || {
+            let mut watch = tokio_fs::watch(&path);
+            assert!(watch.poll().unwrap().is_not_ready());
+
+            thread::spawn(move || {
+                fs::remove_dir(&path).unwrap();
+                thread::sleep(Duration::from_millis(100));
+                fs::create_dir(&path).unwrap();
+                thread::sleep(Duration::from_millis(100));
+                fs::write(path.join("foo.txt"), b"hello").unwrap();
+            });
+
+            // The watched directory is watched again once created
+            watch.take(4).collect()
+        }))
+        .unwrap();
+
+    let watched = dir.path().join("watched");
+    let events: Vec<_> = events
+        .iter()
+        .map(|event| (event.path().to_owned(), event.kind()))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            (watched.clone(), EventKind::Remove),
+            (watched.clone(), EventKind::Create),
+            (watched.join("foo.txt"), EventKind::Create),
+            (watched.join("foo.txt"), EventKind::Modify),
+        ]
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn watch_parent_removed() {
+    let dir = TempDir::new("base").unwrap();
+    let parent = dir.path().join("parent");
+    let path = parent.join("watched");
+    fs::create_dir(&parent).unwrap();
+    let mut rt = Runtime::new().unwrap();
+
+    let events = rt
+        .block_on(lazy(move || {
+            let mut watch = tokio_fs::watch(&path);
+            assert!(watch.poll().unwrap().is_not_ready());
+            thread::spawn(move || {
+                fs::create_dir(&path).unwrap();
+                thread::sleep(Duration::from_millis(100));
+                fs::remove_dir(&path).unwrap();
+                thread::sleep(Duration::from_millis(100));
+                fs::remove_dir(&parent).unwrap();
+            });
+
+            // The stream ends once the path cannot be created anymore
+            watch.collect()
+        }))
+        .unwrap();
+
+    let watched = dir.path().join("parent").join("watched");
+    let events: Vec<_> = events
+        .iter()
+        .map(|event| (event.path().to_owned(), event.kind()))
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            (watched.clone(), EventKind::Create),
+            (watched.clone(), EventKind::Remove),
+        ]
+    );
+}
+
+#[test]
+fn watch_poll_interval() {
+    let mut builder = Builder::new();
+    builder.poll_interval(Duration::from_millis(10));
+
+    let events = events(builder, 4, |path| {
+        let path = path.to_owned();
+        fs::write(path.join("foo.txt"), b"hello").unwrap();
+
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(200));
+            fs::write(path.join("foo.txt"), b"hello world").unwrap();
+            thread::sleep(Duration::from_millis(200));
+            fs::rename(path.join("foo.txt"), path.join("bar.txt")).unwrap();
+        });
+    });
+
+    // The rename is found as a removal and a creation
+    assert_eq!(
+        events,
+        vec![
+            ("foo.txt".to_string(), EventKind::Create),
+            ("foo.txt".to_string(), EventKind::Modify),
+            ("foo.txt".to_string(), EventKind::Remove),
+            ("bar.txt".to_string(), EventKind::Create),
+        ]
+    );
+}
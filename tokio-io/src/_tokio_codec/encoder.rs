@@ -1,3 +1,20 @@
 // For now, we need to keep the implementation of Encoder in tokio_io.
 
+use bytes::BytesMut;
+
 pub use codec::Encoder;
+
+/// Trait of `Encoder`s which can encode an item without taking ownership of
+/// it.
+///
+/// This allows a caller to keep a message after it has been sent, for
+/// example to retransmit it later, without cloning it first. Frames are sent
+/// by reference with the `start_send_ref` methods of `Framed` and
+/// `FramedWrite`.
+pub trait EncodeRef: Encoder {
+    /// Encodes a frame into the buffer provided, from a reference to the
+    /// item.
+    ///
+    /// This method behaves as `Encoder::encode` otherwise.
+    fn encode_ref(&mut self, item: &Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error>;
+}
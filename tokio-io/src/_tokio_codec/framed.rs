@@ -3,18 +3,38 @@
 use std::fmt;
 use std::io::{self, Read, Write};
 
-use super::framed_read::{framed_read2, framed_read2_with_buffer, FramedRead2};
+use super::encoder::EncodeRef;
+use super::framed_read::{framed_read2, framed_read2_with_buffer, DecodeRecovery, FramedRead2};
 use super::framed_write::{framed_write2, framed_write2_with_buffer, FramedWrite2};
 use codec::{Decoder, Encoder};
 use {AsyncRead, AsyncWrite};
 
-use bytes::BytesMut;
+use bytes::{Buf, BufMut, BytesMut};
 use futures::{Poll, Sink, StartSend, Stream};
+use iovec::IoVec;
 
 /// A unified `Stream` and `Sink` interface to an underlying I/O object, using
 /// the `Encoder` and `Decoder` traits to encode and decode frames.
 ///
 /// You can create a `Framed` instance by using the `AsyncRead::framed` adapter.
+///
+/// # Upgrading the protocol
+///
+/// The data read from the I/O object but not decoded yet, such as the start
+/// of the next protocol after an upgrade request, is kept in the read buffer
+/// of the `Framed`. [`into_parts`] hands out that buffer along with the I/O
+/// object and the codec, and [`from_parts`] creates a `Framed` which decodes
+/// the buffered data first. [`FramedParts::map_codec`] and
+/// [`FramedParts::map_io`] change the codec or wrap the I/O object in between.
+///
+/// The `Stream` and `Sink` halves returned by `Stream::split` share the
+/// `Framed`, and its buffers, until they are put back together with
+/// `SplitStream::reunite`.
+///
+/// [`into_parts`]: #method.into_parts
+/// [`from_parts`]: #method.from_parts
+/// [`FramedParts::map_codec`]: struct.FramedParts.html#method.map_codec
+/// [`FramedParts::map_io`]: struct.FramedParts.html#method.map_io
 pub struct Framed<T, U> {
     inner: FramedRead2<FramedWrite2<Fuse<T, U>>>,
 }
@@ -118,6 +138,21 @@ impl<T, U> Framed<T, U> {
         &mut self.inner.get_mut().get_mut().1
     }
 
+    /// Returns what the stream does when the decoder returns an error.
+    pub fn decode_recovery(&self) -> DecodeRecovery {
+        self.inner.decode_recovery()
+    }
+
+    /// Sets what the stream does when the decoder returns an error.
+    ///
+    /// The default is `DecodeRecovery::Continue`. See [`DecodeRecovery`] for
+    /// the available strategies.
+    ///
+    /// [`DecodeRecovery`]: enum.DecodeRecovery.html
+    pub fn set_decode_recovery(&mut self, recovery: DecodeRecovery) {
+        self.inner.set_decode_recovery(recovery);
+    }
+
     /// Consumes the `Frame`, returning its underlying I/O stream.
     ///
     /// Note that care should be taken to not tamper with the underlying stream
@@ -182,6 +217,22 @@ where
     }
 }
 
+impl<T, U> Framed<T, U>
+where
+    T: AsyncWrite,
+    U: EncodeRef,
+{
+    /// Begins the process of sending a frame, encoding it from a reference
+    /// so that the caller keeps ownership of it.
+    ///
+    /// This behaves as `Sink::start_send`, except that `Async::NotReady` is
+    /// returned when the write buffer is full, as the item does not need to
+    /// be handed back. The frame is written out by `Sink::poll_complete`.
+    pub fn start_send_ref(&mut self, item: &U::Item) -> Poll<(), U::Error> {
+        self.inner.get_mut().start_send_ref(item)
+    }
+}
+
 impl<T, U> fmt::Debug for Framed<T, U>
 where
     T: fmt::Debug,
@@ -207,6 +258,14 @@ impl<T: AsyncRead, U> AsyncRead for Fuse<T, U> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         self.0.prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.0.poll_read_vectored(bufs)
+    }
+
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        self.0.read_buf(buf)
+    }
 }
 
 impl<T: Write, U> Write for Fuse<T, U> {
@@ -220,9 +279,21 @@ impl<T: Write, U> Write for Fuse<T, U> {
 }
 
 impl<T: AsyncWrite, U> AsyncWrite for Fuse<T, U> {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.0.poll_write_vectored(bufs)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.0.poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         self.0.shutdown()
     }
+
+    fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        self.0.write_buf(buf)
+    }
 }
 
 impl<T, U: Decoder> Decoder for Fuse<T, U> {
@@ -236,6 +307,10 @@ impl<T, U: Decoder> Decoder for Fuse<T, U> {
     fn decode_eof(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         self.1.decode_eof(buffer)
     }
+
+    fn recover(&mut self, buffer: &mut BytesMut) -> bool {
+        self.1.recover(buffer)
+    }
 }
 
 impl<T, U: Encoder> Encoder for Fuse<T, U> {
@@ -247,6 +322,12 @@ impl<T, U: Encoder> Encoder for Fuse<T, U> {
     }
 }
 
+impl<T, U: EncodeRef> EncodeRef for Fuse<T, U> {
+    fn encode_ref(&mut self, item: &Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.1.encode_ref(item, dst)
+    }
+}
+
 /// `FramedParts` contains an export of the data of a Framed transport.
 /// It can be used to construct a new `Framed` with a different codec.
 /// It contains all current buffers and the inner transport.
@@ -280,4 +361,38 @@ impl<T, U> FramedParts<T, U> {
             _priv: (),
         }
     }
+
+    /// Replace the codec, keeping the buffers and the transport.
+    ///
+    /// The data in `read_buf` is decoded by the new codec, which suits
+    /// switching to another protocol after an upgrade.
+    pub fn map_codec<C, F>(self, f: F) -> FramedParts<T, C>
+    where
+        F: FnOnce(U) -> C,
+    {
+        FramedParts {
+            io: self.io,
+            codec: f(self.codec),
+            read_buf: self.read_buf,
+            write_buf: self.write_buf,
+            _priv: (),
+        }
+    }
+
+    /// Replace the transport, keeping the buffers and the codec.
+    ///
+    /// Note that the data in `read_buf` was read from the previous transport,
+    /// and the data in `write_buf` is written to the new one.
+    pub fn map_io<I, F>(self, f: F) -> FramedParts<I, U>
+    where
+        F: FnOnce(T) -> I,
+    {
+        FramedParts {
+            io: f(self.io),
+            codec: self.codec,
+            read_buf: self.read_buf,
+            write_buf: self.write_buf,
+            _priv: (),
+        }
+    }
 }
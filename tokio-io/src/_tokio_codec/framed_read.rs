@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use super::framed::Fuse;
+use super::framed::{FramedParts, Fuse};
 use codec::Decoder;
 use AsyncRead;
 
@@ -19,6 +19,42 @@ pub struct FramedRead2<T> {
     eof: bool,
     is_readable: bool,
     buffer: BytesMut,
+    recovery: DecodeRecovery,
+    failed: bool,
+}
+
+/// What a framed stream does when its decoder returns an error.
+///
+/// Set with [`FramedRead::set_decode_recovery`] or
+/// [`Framed::set_decode_recovery`].
+///
+/// Recovering from an error relies on [`Decoder::recover`] to find the next
+/// frame boundary.
+///
+/// [`Decoder::recover`]: trait.Decoder.html#method.recover
+/// [`FramedRead::set_decode_recovery`]: struct.FramedRead.html#method.set_decode_recovery
+/// [`Framed::set_decode_recovery`]: struct.Framed.html#method.set_decode_recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeRecovery {
+    /// Yield the error, and keep decoding frames if the stream is polled
+    /// again.
+    ///
+    /// Combinators usually stop at the first error, which can be avoided by
+    /// turning errors into items, using `Stream::then` for instance. This is
+    /// the default.
+    Continue,
+
+    /// Yield the error, and end the stream.
+    Fail,
+
+    /// Drop the error and decode the next frame.
+    ///
+    /// The decoder is asked to skip the malformed frame with
+    /// [`Decoder::recover`]. If it is not able to, the error is yielded as
+    /// with `Continue`.
+    ///
+    /// [`Decoder::recover`]: trait.Decoder.html#method.recover
+    Skip,
 }
 
 const INITIAL_CAPACITY: usize = 8 * 1024;
@@ -68,6 +104,36 @@ impl<T, D> FramedRead<T, D> {
         self.inner.inner.0
     }
 
+    /// Consumes the `FramedRead`, returning its underlying I/O stream, the
+    /// buffer with unprocessed data, and the decoder.
+    ///
+    /// The `write_buf` of the returned parts is empty.
+    pub fn into_parts(self) -> FramedParts<T, D> {
+        let (inner, read_buf) = self.inner.into_parts();
+
+        let mut parts = FramedParts::new(inner.0, inner.1);
+        parts.read_buf = read_buf;
+        parts
+    }
+
+    /// Creates a `FramedRead` from the parts of another framed object,
+    /// decoding the data of `read_buf` before reading from the I/O stream.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `write_buf` is not empty, as the data would
+    /// never be written.
+    pub fn from_parts(parts: FramedParts<T, D>) -> FramedRead<T, D> {
+        assert!(
+            parts.write_buf.is_empty(),
+            "cannot create a FramedRead with data to write"
+        );
+
+        FramedRead {
+            inner: framed_read2_with_buffer(Fuse(parts.io, parts.codec), parts.read_buf),
+        }
+    }
+
     /// Returns a reference to the underlying decoder.
     pub fn decoder(&self) -> &D {
         &self.inner.inner.1
@@ -77,6 +143,21 @@ impl<T, D> FramedRead<T, D> {
     pub fn decoder_mut(&mut self) -> &mut D {
         &mut self.inner.inner.1
     }
+
+    /// Returns what the stream does when the decoder returns an error.
+    pub fn decode_recovery(&self) -> DecodeRecovery {
+        self.inner.recovery
+    }
+
+    /// Sets what the stream does when the decoder returns an error.
+    ///
+    /// The default is `DecodeRecovery::Continue`. See [`DecodeRecovery`] for
+    /// the available strategies.
+    ///
+    /// [`DecodeRecovery`]: enum.DecodeRecovery.html
+    pub fn set_decode_recovery(&mut self, recovery: DecodeRecovery) {
+        self.inner.recovery = recovery;
+    }
 }
 
 impl<T, D> Stream for FramedRead<T, D>
@@ -124,6 +205,7 @@ where
             .field("eof", &self.inner.eof)
             .field("is_readable", &self.inner.is_readable)
             .field("buffer", &self.inner.buffer)
+            .field("recovery", &self.inner.recovery)
             .finish()
     }
 }
@@ -136,6 +218,8 @@ pub fn framed_read2<T>(inner: T) -> FramedRead2<T> {
         eof: false,
         is_readable: false,
         buffer: BytesMut::with_capacity(INITIAL_CAPACITY),
+        recovery: DecodeRecovery::Continue,
+        failed: false,
     }
 }
 
@@ -149,6 +233,8 @@ pub fn framed_read2_with_buffer<T>(inner: T, mut buf: BytesMut) -> FramedRead2<T
         eof: false,
         is_readable: buf.len() > 0,
         buffer: buf,
+        recovery: DecodeRecovery::Continue,
+        failed: false,
     }
 }
 
@@ -168,6 +254,14 @@ impl<T> FramedRead2<T> {
     pub fn get_mut(&mut self) -> &mut T {
         &mut self.inner
     }
+
+    pub fn decode_recovery(&self) -> DecodeRecovery {
+        self.recovery
+    }
+
+    pub fn set_decode_recovery(&mut self, recovery: DecodeRecovery) {
+        self.recovery = recovery;
+    }
 }
 
 impl<T> Stream for FramedRead2<T>
@@ -178,6 +272,10 @@ where
     type Error = T::Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if self.failed {
+            return Ok(Async::Ready(None));
+        }
+
         loop {
             // Repeatedly call `decode` or `decode_eof` as long as it is
             // "readable". Readable is defined as not having returned `None`. If
@@ -185,16 +283,31 @@ where
             // readable, it can be assumed that the decoder will never become
             // readable again, at which point the stream is terminated.
             if self.is_readable {
-                if self.eof {
-                    let frame = try!(self.inner.decode_eof(&mut self.buffer));
-                    return Ok(Async::Ready(frame));
-                }
-
-                trace!("attempting to decode a frame");
-
-                if let Some(frame) = try!(self.inner.decode(&mut self.buffer)) {
-                    trace!("frame decoded from buffer");
-                    return Ok(Async::Ready(Some(frame)));
+                let res = if self.eof {
+                    self.inner.decode_eof(&mut self.buffer)
+                } else {
+                    trace!("attempting to decode a frame");
+                    self.inner.decode(&mut self.buffer)
+                };
+
+                match res {
+                    Ok(Some(frame)) => {
+                        trace!("frame decoded from buffer");
+                        return Ok(Async::Ready(Some(frame)));
+                    }
+                    Ok(None) if self.eof => return Ok(Async::Ready(None)),
+                    Ok(None) => {}
+                    Err(e) => match self.recovery {
+                        DecodeRecovery::Skip if self.inner.recover(&mut self.buffer) => {
+                            trace!("skipping frame that failed to decode");
+                            continue;
+                        }
+                        DecodeRecovery::Fail => {
+                            self.failed = true;
+                            return Err(e);
+                        }
+                        _ => return Err(e),
+                    },
                 }
 
                 self.is_readable = false;
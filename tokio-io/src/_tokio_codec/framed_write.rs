@@ -3,12 +3,14 @@
 use std::fmt;
 use std::io::{self, Read};
 
-use super::framed::Fuse;
+use super::encoder::EncodeRef;
+use super::framed::{FramedParts, Fuse};
 use codec::{Decoder, Encoder};
 use {AsyncRead, AsyncWrite};
 
-use bytes::BytesMut;
+use bytes::{BufMut, BytesMut};
 use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use iovec::IoVec;
 
 /// A `Sink` of frames encoded to an `AsyncWrite`.
 pub struct FramedWrite<T, E> {
@@ -66,6 +68,36 @@ impl<T, E> FramedWrite<T, E> {
         self.inner.inner.0
     }
 
+    /// Consumes the `FramedWrite`, returning its underlying I/O stream, the
+    /// buffer with data which is not written yet, and the encoder.
+    ///
+    /// The `read_buf` of the returned parts is empty.
+    pub fn into_parts(self) -> FramedParts<T, E> {
+        let (inner, write_buf) = self.inner.into_parts();
+
+        let mut parts = FramedParts::new(inner.0, inner.1);
+        parts.write_buf = write_buf;
+        parts
+    }
+
+    /// Creates a `FramedWrite` from the parts of another framed object,
+    /// writing the data of `write_buf` before any new frame.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `read_buf` is not empty, as the data would
+    /// never be decoded.
+    pub fn from_parts(parts: FramedParts<T, E>) -> FramedWrite<T, E> {
+        assert!(
+            parts.read_buf.is_empty(),
+            "cannot create a FramedWrite with data to decode"
+        );
+
+        FramedWrite {
+            inner: framed_write2_with_buffer(Fuse(parts.io, parts.codec), parts.write_buf),
+        }
+    }
+
     /// Returns a reference to the underlying decoder.
     pub fn encoder(&self) -> &E {
         &self.inner.inner.1
@@ -98,6 +130,22 @@ where
     }
 }
 
+impl<T, E> FramedWrite<T, E>
+where
+    T: AsyncWrite,
+    E: EncodeRef,
+{
+    /// Begins the process of sending a frame, encoding it from a reference
+    /// so that the caller keeps ownership of it.
+    ///
+    /// This behaves as `Sink::start_send`, except that `Async::NotReady` is
+    /// returned when the write buffer is full, as the item does not need to
+    /// be handed back. The frame is written out by `Sink::poll_complete`.
+    pub fn start_send_ref(&mut self, item: &E::Item) -> Poll<(), E::Error> {
+        self.inner.start_send_ref(item)
+    }
+}
+
 impl<T, D> Stream for FramedWrite<T, D>
 where
     T: Stream,
@@ -162,24 +210,53 @@ impl<T> FramedWrite2<T> {
     }
 }
 
-impl<T> Sink for FramedWrite2<T>
+impl<T> FramedWrite2<T>
 where
     T: AsyncWrite + Encoder,
 {
-    type SinkItem = T::Item;
-    type SinkError = T::Error;
-
-    fn start_send(&mut self, item: T::Item) -> StartSend<T::Item, T::Error> {
+    /// Returns `true` if there is room in the buffer for another frame.
+    fn poll_ready(&mut self) -> Result<bool, T::Error> {
         // If the buffer is already over 8KiB, then attempt to flush it. If after flushing it's
         // *still* over 8KiB, then apply backpressure (reject the send).
         if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
             try!(self.poll_complete());
 
             if self.buffer.len() >= BACKPRESSURE_BOUNDARY {
-                return Ok(AsyncSink::NotReady(item));
+                return Ok(false);
             }
         }
 
+        Ok(true)
+    }
+}
+
+impl<T> FramedWrite2<T>
+where
+    T: AsyncWrite + EncodeRef,
+{
+    pub fn start_send_ref(&mut self, item: &T::Item) -> Poll<(), T::Error> {
+        if !try!(self.poll_ready()) {
+            return Ok(Async::NotReady);
+        }
+
+        try!(self.inner.encode_ref(item, &mut self.buffer));
+
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T> Sink for FramedWrite2<T>
+where
+    T: AsyncWrite + Encoder,
+{
+    type SinkItem = T::Item;
+    type SinkError = T::Error;
+
+    fn start_send(&mut self, item: T::Item) -> StartSend<T::Item, T::Error> {
+        if !try!(self.poll_ready()) {
+            return Ok(AsyncSink::NotReady(item));
+        }
+
         try!(self.inner.encode(item, &mut self.buffer));
 
         Ok(AsyncSink::Ready)
@@ -216,7 +293,7 @@ where
 
     fn close(&mut self) -> Poll<(), Self::SinkError> {
         try_ready!(self.poll_complete());
-        Ok(try!(self.inner.shutdown()))
+        Ok(try!(self.inner.poll_shutdown()))
     }
 }
 
@@ -231,6 +308,10 @@ impl<T: Decoder> Decoder for FramedWrite2<T> {
     fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T::Item>, T::Error> {
         self.inner.decode_eof(src)
     }
+
+    fn recover(&mut self, src: &mut BytesMut) -> bool {
+        self.inner.recover(src)
+    }
 }
 
 impl<T: Read> Read for FramedWrite2<T> {
@@ -243,4 +324,12 @@ impl<T: AsyncRead> AsyncRead for FramedWrite2<T> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         self.inner.prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.inner.poll_read_vectored(bufs)
+    }
+
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        self.inner.read_buf(buf)
+    }
 }
@@ -30,7 +30,7 @@ mod framed_read;
 mod framed_write;
 
 pub use self::decoder::Decoder;
-pub use self::encoder::Encoder;
+pub use self::encoder::{EncodeRef, Encoder};
 pub use self::framed::{Framed, FramedParts};
-pub use self::framed_read::FramedRead;
+pub use self::framed_read::{DecodeRecovery, FramedRead};
 pub use self::framed_write::FramedWrite;
@@ -0,0 +1,70 @@
+use futures::{Async, Poll};
+use std::io as std_io;
+
+use AsyncRead;
+
+/// Read bytes asynchronously through an internal buffer.
+///
+/// This trait is to `std::io::BufRead` what `AsyncRead` is to
+/// `std::io::Read`: `fill_buf` must return an error of kind `WouldBlock`
+/// instead of blocking the current thread when no data is buffered and the
+/// object is not readable.
+///
+/// Specifically, this means that the `poll_fill_buf` function will return
+/// one of the following:
+///
+/// * `Ok(Async::Ready(buf))` means that `buf` holds the buffered data, where
+///   an empty `buf` implies that EOF has been reached. The data is consumed
+///   by calling `consume`.
+///
+/// * `Ok(Async::NotReady)` means that no data is buffered and the object is
+///   not currently readable. The current task is scheduled to get unparked
+///   when the object is readable.
+///
+/// * `Err(e)` for other errors are standard I/O errors coming from the
+///   underlying object.
+///
+/// This trait is implemented for [`BufReader`], `&[u8]`, `std::io::Cursor`
+/// and the `std::io` buffered readers wrapping an `AsyncRead`. Other types
+/// implement it explicitly, which lets them override `poll_fill_buf`. It is
+/// the bound of [`read_line`], which fills the buffer with `poll_fill_buf`.
+///
+/// [`BufReader`]: io/struct.BufReader.html
+/// [`read_line`]: io/fn.read_line.html
+pub trait AsyncBufRead: std_io::BufRead + AsyncRead {
+    /// Attempt to return the contents of the internal buffer, filling it with
+    /// more data from the inner reader if it is empty.
+    ///
+    /// If no data is buffered and the object is not readable, the method
+    /// returns `Ok(Async::NotReady)` and arranges for the current task to
+    /// receive a notification when it can make progress.
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        match self.fill_buf() {
+            Ok(buf) => Ok(Async::Ready(buf)),
+            Err(ref e) if e.kind() == std_io::ErrorKind::WouldBlock => Ok(Async::NotReady),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T: ?Sized + AsyncBufRead> AsyncBufRead for Box<T> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        (**self).poll_fill_buf()
+    }
+}
+
+impl<'a, T: ?Sized + AsyncBufRead> AsyncBufRead for &'a mut T {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], std_io::Error> {
+        (**self).poll_fill_buf()
+    }
+}
+
+impl<'a> AsyncBufRead for &'a [u8] {}
+
+impl<T: AsRef<[u8]>> AsyncBufRead for std_io::Cursor<T> {}
+
+impl<T: AsyncRead> AsyncBufRead for std_io::BufReader<T> {}
+
+impl<T: AsyncBufRead> AsyncBufRead for std_io::Take<T> {}
+
+impl<T: AsyncBufRead, U: AsyncBufRead> AsyncBufRead for std_io::Chain<T, U> {}
@@ -1,5 +1,6 @@
 use bytes::BufMut;
 use futures::{Async, Poll};
+use iovec::IoVec;
 use std::io as std_io;
 
 #[allow(deprecated)]
@@ -85,6 +86,23 @@ pub trait AsyncRead: std_io::Read {
         }
     }
 
+    /// Attempt to read from the `AsyncRead` into `bufs`, filling each buffer
+    /// in order before moving to the next one.
+    ///
+    /// This behaves like [`poll_read`], but lets implementations read into
+    /// several buffers with a single operation, such as `readv`, instead of
+    /// reading into a temporary buffer and copying the data out of it.
+    ///
+    /// The default implementation reads into the first buffer only.
+    ///
+    /// [`poll_read`]: #method.poll_read
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        match bufs.first_mut() {
+            Some(buf) => self.poll_read(buf),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
     /// Pull some bytes from this source into the specified `BufMut`, returning
     /// how many bytes were read.
     ///
@@ -146,6 +164,11 @@ pub trait AsyncRead: std_io::Read {
     ///
     /// To restore this read/write object from its `ReadHalf` and `WriteHalf`
     /// use `unsplit`.
+    ///
+    /// The halves share the object through a lock taken for every read and
+    /// write. Objects which can be read from and written to concurrently,
+    /// such as `TcpStream` and `UnixStream`, also provide an `into_split`
+    /// method, which splits them into owned halves without a lock.
     fn split(self) -> (ReadHalf<Self>, WriteHalf<Self>)
     where
         Self: AsyncWrite + Sized,
@@ -158,12 +181,20 @@ impl<T: ?Sized + AsyncRead> AsyncRead for Box<T> {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_vectored(bufs)
+    }
 }
 
 impl<'a, T: ?Sized + AsyncRead> AsyncRead for &'a mut T {
     unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
         (**self).prepare_uninitialized_buffer(buf)
     }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).poll_read_vectored(bufs)
+    }
 }
 
 impl<'a> AsyncRead for &'a [u8] {
@@ -0,0 +1,57 @@
+use futures::{Async, Poll};
+use std::io as std_io;
+use std::io::SeekFrom;
+
+/// Seek bytes asynchronously.
+///
+/// This trait inherits from `std::io::Seek` and indicates that an I/O object
+/// is **non-blocking**. Seeking an object such as a file may block, in which
+/// case `seek` must return an error of kind `WouldBlock` instead of blocking
+/// the current thread.
+///
+/// Specifically, this means that the `poll_seek` function will return one of
+/// the following:
+///
+/// * `Ok(Async::Ready(pos))` means that the cursor was moved, and `pos` is the
+///   new position from the start of the stream.
+///
+/// * `Ok(Async::NotReady)` means that the cursor was not moved. The current
+///   task is scheduled to get unparked when seeking can make progress, and
+///   `poll_seek` must then be called again with the same `pos`.
+///
+/// * `Err(e)` for other errors are standard I/O errors coming from the
+///   underlying object.
+///
+/// This trait importantly means that the `seek` method only works in the
+/// context of a future's task. The object may panic if used outside of a task.
+pub trait AsyncSeek: std_io::Seek {
+    /// Attempt to seek to an offset, in bytes, in the object.
+    ///
+    /// On success, returns `Ok(Async::Ready(pos))`, where `pos` is the new
+    /// position from the start of the stream.
+    ///
+    /// If the object is not ready for seeking, the method returns
+    /// `Ok(Async::NotReady)` and arranges for the current task to receive a
+    /// notification when it can make progress.
+    fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, std_io::Error> {
+        match self.seek(pos) {
+            Ok(t) => Ok(Async::Ready(t)),
+            Err(ref e) if e.kind() == std_io::ErrorKind::WouldBlock => return Ok(Async::NotReady),
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+impl<T: ?Sized + AsyncSeek> AsyncSeek for Box<T> {
+    fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, std_io::Error> {
+        (**self).poll_seek(pos)
+    }
+}
+
+impl<'a, T: ?Sized + AsyncSeek> AsyncSeek for &'a mut T {
+    fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, std_io::Error> {
+        (**self).poll_seek(pos)
+    }
+}
+
+impl<T: AsRef<[u8]>> AsyncSeek for std_io::Cursor<T> {}
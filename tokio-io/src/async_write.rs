@@ -1,5 +1,6 @@
 use bytes::Buf;
 use futures::{Async, Poll};
+use iovec::IoVec;
 use std::io as std_io;
 
 use AsyncRead;
@@ -51,6 +52,23 @@ pub trait AsyncWrite: std_io::Write {
         }
     }
 
+    /// Attempt to write bytes from `bufs` into the object, taking the buffers
+    /// in order.
+    ///
+    /// This behaves like [`poll_write`], but lets implementations write
+    /// several buffers with a single operation, such as `writev`, instead of
+    /// copying them into a contiguous buffer first.
+    ///
+    /// The default implementation writes the first buffer only.
+    ///
+    /// [`poll_write`]: #method.poll_write
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        match bufs.first() {
+            Some(buf) => self.poll_write(buf),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
     /// Attempt to flush the object, ensuring that any buffered data reach
     /// their destination.
     ///
@@ -60,6 +78,11 @@ pub trait AsyncWrite: std_io::Write {
     /// `Ok(Async::NotReady)` and arranges for the current task (via
     /// `cx.waker()`) to receive a notification when the object can make
     /// progress towards flushing.
+    ///
+    /// Flushing never shuts down the object, which remains writable
+    /// afterwards. Writers buffering data, such as TLS streams or compression
+    /// encoders, should write out their buffered data and then call
+    /// `poll_flush` on the writer they wrap.
     fn poll_flush(&mut self) -> Poll<(), std_io::Error> {
         match self.flush() {
             Ok(t) => Ok(Async::Ready(t)),
@@ -86,7 +109,7 @@ pub trait AsyncWrite: std_io::Write {
     /// wrapping an underlying `AsyncWrite` a call to `shutdown` implies that
     /// transitively the entire stream has been shut down. After your wrapper's
     /// shutdown logic has been executed you should shut down the underlying
-    /// stream.
+    /// stream. To only write out buffered data, use [`poll_flush`] instead.
     ///
     /// Invocation of a `shutdown` implies an invocation of `flush`. Once this
     /// method returns `Ready` it implies that a flush successfully happened
@@ -126,12 +149,30 @@ pub trait AsyncWrite: std_io::Write {
     ///
     /// This function will panic if not called within the context of a future's
     /// task.
+    ///
+    /// [`poll_flush`]: #method.poll_flush
     fn shutdown(&mut self) -> Poll<(), std_io::Error>;
 
+    /// Initiates or attempts to shut down this writer.
+    ///
+    /// This is an alias of [`shutdown`], named like the other `poll_`
+    /// methods, and behaves identically. Implementers provide `shutdown`,
+    /// which this method calls.
+    ///
+    /// [`shutdown`]: #method.shutdown
+    fn poll_shutdown(&mut self) -> Poll<(), std_io::Error> {
+        self.shutdown()
+    }
+
     /// Write a `Buf` into this value, returning how many bytes were written.
     ///
     /// Note that this method will advance the `buf` provided automatically by
     /// the number of bytes written.
+    ///
+    /// When `buf` is made of several chunks, they are written with
+    /// [`poll_write_vectored`].
+    ///
+    /// [`poll_write_vectored`]: #method.poll_write_vectored
     fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, std_io::Error>
     where
         Self: Sized,
@@ -140,18 +181,44 @@ pub trait AsyncWrite: std_io::Write {
             return Ok(Async::Ready(0));
         }
 
-        let n = try_ready!(self.poll_write(buf.bytes()));
+        let n = {
+            // The `IoVec` type can't have a zero-length size, so create a dummy
+            // version from a 1-length slice which we'll overwrite with the
+            // `bytes_vec` method.
+            static DUMMY: &[u8] = &[0];
+            let iovec = <&IoVec>::from(DUMMY);
+            let mut bufs = [iovec; 64];
+            let n = buf.bytes_vec(&mut bufs);
+            try_ready!(self.poll_write_vectored(&bufs[..n]))
+        };
+
         buf.advance(n);
         Ok(Async::Ready(n))
     }
 }
 
 impl<T: ?Sized + AsyncWrite> AsyncWrite for Box<T> {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).poll_write_vectored(bufs)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), std_io::Error> {
+        (**self).poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
 }
 impl<'a, T: ?Sized + AsyncWrite> AsyncWrite for &'a mut T {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, std_io::Error> {
+        (**self).poll_write_vectored(bufs)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), std_io::Error> {
+        (**self).poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), std_io::Error> {
         (**self).shutdown()
     }
@@ -91,6 +91,21 @@ pub trait Decoder {
         }
     }
 
+    /// Prepares the decoder to resume decoding after `decode` or `decode_eof`
+    /// returned an error.
+    ///
+    /// This method is called by `FramedRead` and `Framed` when their decode
+    /// recovery is set to `DecodeRecovery::Skip`, with the buffer the failing
+    /// call was given. An implementation returns `true` if the next frame can
+    /// be decoded, after removing the malformed frame from `src` or arranging
+    /// for it to be discarded as more bytes are read. It returns `false` if
+    /// the frame boundary is lost, in which case the error is yielded.
+    ///
+    /// The default implementation returns `false`.
+    fn recover(&mut self, _src: &mut BytesMut) -> bool {
+        false
+    }
+
     /// Provides a `Stream` and `Sink` interface for reading and writing to this
     /// `Io` object, using `Decode` and `Encode` to read and write the raw data.
     ///
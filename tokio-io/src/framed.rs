@@ -207,6 +207,10 @@ impl<T: Write, U> Write for Fuse<T, U> {
 }
 
 impl<T: AsyncWrite, U> AsyncWrite for Fuse<T, U> {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.0.poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         self.0.shutdown()
     }
@@ -223,6 +227,10 @@ impl<T, U: Decoder> Decoder for Fuse<T, U> {
     fn decode_eof(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         self.1.decode_eof(buffer)
     }
+
+    fn recover(&mut self, buffer: &mut BytesMut) -> bool {
+        self.1.recover(buffer)
+    }
 }
 
 impl<T, U: Encoder> Encoder for Fuse<T, U> {
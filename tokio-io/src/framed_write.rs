@@ -220,7 +220,7 @@ where
 
     fn close(&mut self) -> Poll<(), Self::SinkError> {
         try_ready!(self.poll_complete());
-        Ok(try!(self.inner.shutdown()))
+        Ok(try!(self.inner.poll_shutdown()))
     }
 }
 
@@ -235,6 +235,10 @@ impl<T: Decoder> Decoder for FramedWrite2<T> {
     fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T::Item>, T::Error> {
         self.inner.decode_eof(src)
     }
+
+    fn recover(&mut self, src: &mut BytesMut) -> bool {
+        self.inner.recover(src)
+    }
 }
 
 impl<T: Read> Read for FramedWrite2<T> {
@@ -0,0 +1,172 @@
+use std::io::{self, BufRead, Read, Write};
+use std::{cmp, fmt};
+
+use futures::Poll;
+use iovec::IoVec;
+
+use {AsyncBufRead, AsyncRead, AsyncWrite};
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to any reader.
+///
+/// Reading from an I/O object in small chunks, such as when parsing a
+/// protocol byte by byte, issues a system call for each read. A `BufReader`
+/// instead reads large chunks into an in-memory buffer and serves small reads
+/// from it.
+///
+/// `BufReader` implements `AsyncBufRead`, so it can be used with
+/// [`read_until`], [`read_line`] and [`lines`]. Like every other read on an
+/// `AsyncRead`, `fill_buf` returns an error of kind `WouldBlock` when the
+/// inner reader is not ready, and the current task is notified once it is.
+///
+/// Buffered data that has not been consumed is lost when the `BufReader` is
+/// dropped or unwrapped with [`into_inner`].
+///
+/// If the inner reader is also a writer, writes go straight to it.
+///
+/// [`read_until`]: fn.read_until.html
+/// [`read_line`]: fn.read_line.html
+/// [`lines`]: fn.lines.html
+/// [`into_inner`]: #method.into_inner
+pub struct BufReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity, currently
+    /// 8KB.
+    pub fn new(inner: R) -> BufReader<R> {
+        BufReader::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufReader` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: R) -> BufReader<R> {
+        let buf = unsafe {
+            let mut buf = Vec::with_capacity(capacity);
+            buf.set_len(capacity);
+            inner.prepare_uninitialized_buffer(&mut buf);
+            buf.into_boxed_slice()
+        };
+
+        BufReader {
+            inner,
+            buf,
+            pos: 0,
+            cap: 0,
+        }
+    }
+}
+
+impl<R> BufReader<R> {
+    /// Gets a reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    ///
+    /// Unlike `fill_buf`, this never reads from the underlying reader.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+
+    /// Consumes this `BufReader`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for BufReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.pos == self.cap && buf.len() >= self.buf.len() {
+            return self.inner.read(buf);
+        }
+
+        let n = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead> BufRead for BufReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Only read more data from the inner reader once all the buffered data
+        // has been consumed.
+        if self.pos >= self.cap {
+            debug_assert!(self.pos == self.cap);
+            self.cap = self.inner.read(&mut self.buf)?;
+            self.pos = 0;
+        }
+
+        Ok(&self.buf[self.pos..self.cap])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.cap);
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: AsyncRead> AsyncBufRead for BufReader<R> {}
+
+impl<R: Write> Write for BufReader<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: AsyncWrite> AsyncWrite for BufReader<R> {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        self.inner.poll_write_vectored(bufs)
+    }
+
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.inner.poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for BufReader<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufReader")
+            .field("reader", &self.inner)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.cap - self.pos, self.buf.len()),
+            )
+            .finish()
+    }
+}
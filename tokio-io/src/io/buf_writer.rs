@@ -0,0 +1,167 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use futures::Poll;
+use iovec::IoVec;
+
+use {AsyncRead, AsyncWrite};
+
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to any writer.
+///
+/// Writing to an I/O object in small chunks issues a system call for each
+/// write. A `BufWriter` instead collects small writes in an in-memory buffer,
+/// and writes the buffer out in one go once it is full or when the writer is
+/// flushed. Writes at least as large as the buffer bypass it.
+///
+/// Unlike `std::io::BufWriter`, the buffer is **not** written out when the
+/// `BufWriter` is dropped, as that would require blocking. Call [`flush`] or
+/// [`shutdown`] before dropping it, otherwise the buffered data is lost.
+/// Shutting down the `BufWriter` writes out the buffer before shutting down
+/// the inner writer.
+///
+/// If the inner writer is also a reader, reads go straight to it.
+///
+/// [`flush`]: fn.flush.html
+/// [`shutdown`]: fn.shutdown.html
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity, currently
+    /// 8KB.
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner,
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Write out the buffered data, without flushing the inner writer.
+    ///
+    /// The data written before an error, including `WouldBlock`, is removed
+    /// from the buffer.
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let len = self.buf.len();
+        let mut ret = Ok(());
+
+        while written < len {
+            match self.inner.write(&self.buf[written..]) {
+                Ok(0) => {
+                    ret = Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    ret = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if written > 0 {
+            self.buf.drain(..written);
+        }
+
+        ret
+    }
+}
+
+impl<W> BufWriter<W> {
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consumes this `BufWriter`, returning the underlying writer.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.buf.capacity() {
+            self.flush_buf()?;
+        }
+
+        if buf.len() >= self.buf.capacity() {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        try_nb!(self.flush_buf());
+        self.inner.poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        try_nb!(self.flush_buf());
+        self.inner.shutdown()
+    }
+}
+
+impl<W: Read> Read for BufWriter<W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<W: AsyncRead> AsyncRead for BufWriter<W> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        self.inner.poll_read_vectored(bufs)
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufWriter")
+            .field("writer", &self.inner)
+            .field(
+                "buffer",
+                &format_args!("{}/{}", self.buf.len(), self.buf.capacity()),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,138 @@
+use std::io::{self, BufRead, Read};
+
+use futures::{Async, Poll};
+
+use {AsyncBufRead, AsyncRead};
+
+/// Reader adapter which chains two readers together.
+///
+/// Created by the [`chain`] function.
+///
+/// [`chain`]: fn.chain.html
+#[derive(Debug)]
+pub struct Chain<R1, R2> {
+    first: R1,
+    second: R2,
+    done_first: bool,
+}
+
+/// Creates a reader which reads all the data from `first`, then all the data
+/// from `second`.
+///
+/// The returned reader reports EOF once both readers have, so it can be used
+/// with combinators reading until EOF like [`read_to_end`] or [`copy`].
+///
+/// This is the async equivalent to [`Read::chain`].
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::{chain, read_to_end};
+///
+/// # fn main() {
+/// let header: &[u8] = b"hello ";
+/// let body: &[u8] = b"world";
+///
+/// let (_, buf) = read_to_end(chain(header, body), vec![]).wait().unwrap();
+/// assert_eq!(buf, b"hello world");
+/// # }
+/// ```
+///
+/// [`read_to_end`]: fn.read_to_end.html
+/// [`copy`]: fn.copy.html
+/// [`Read::chain`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.chain
+pub fn chain<R1, R2>(first: R1, second: R2) -> Chain<R1, R2>
+where
+    R1: AsyncRead,
+    R2: AsyncRead,
+{
+    Chain {
+        first,
+        second,
+        done_first: false,
+    }
+}
+
+impl<R1, R2> Chain<R1, R2> {
+    /// Gets references to the underlying readers.
+    pub fn get_ref(&self) -> (&R1, &R2) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the underlying readers.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal state of this
+    /// `Chain`.
+    pub fn get_mut(&mut self) -> (&mut R1, &mut R2) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes the `Chain`, returning the wrapped readers.
+    pub fn into_inner(self) -> (R1, R2) {
+        (self.first, self.second)
+    }
+}
+
+impl<R1: AsyncRead, R2: AsyncRead> Read for Chain<R1, R2> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.done_first {
+            match self.first.read(buf)? {
+                0 if !buf.is_empty() => self.done_first = true,
+                n => return Ok(n),
+            }
+        }
+
+        self.second.read(buf)
+    }
+}
+
+impl<R1, R2> BufRead for Chain<R1, R2>
+where
+    R1: AsyncRead + BufRead,
+    R2: AsyncRead + BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.done_first {
+            match self.first.fill_buf()? {
+                buf if buf.is_empty() => self.done_first = true,
+                buf => return Ok(buf),
+            }
+        }
+
+        self.second.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if !self.done_first {
+            self.first.consume(amt)
+        } else {
+            self.second.consume(amt)
+        }
+    }
+}
+
+impl<R1: AsyncRead, R2: AsyncRead> AsyncRead for Chain<R1, R2> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        // We don't need to execute the second initializer if the first one
+        // already zeroed the buffer out.
+        self.first.prepare_uninitialized_buffer(buf)
+            || self.second.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R1: AsyncBufRead, R2: AsyncBufRead> AsyncBufRead for Chain<R1, R2> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        if !self.done_first {
+            match try_ready!(self.first.poll_fill_buf()) {
+                buf if buf.is_empty() => self.done_first = true,
+                buf => return Ok(Async::Ready(buf)),
+            }
+        }
+
+        self.second.poll_fill_buf()
+    }
+}
@@ -0,0 +1,243 @@
+// Thin wrappers around the streaming implementations of the `flate2` and
+// `zstd` crates, which keep their state when the inner object returns
+// `WouldBlock`, so that no blocking adapter is needed.
+
+#[cfg(feature = "flate2")]
+use flate2;
+#[cfg(feature = "zstd")]
+use zstd;
+
+use std::fmt;
+#[cfg(feature = "zstd")]
+use std::io::BufReader;
+use std::io::{self, Read, Write};
+
+use futures::Poll;
+
+use {AsyncRead, AsyncWrite};
+
+macro_rules! encoder {
+    ($(#[$attr:meta])* $name:ident($inner:ty), $finish:ident) => {
+        $(#[$attr])*
+        ///
+        /// Flushing the encoder writes out all the data written so far, in a
+        /// form the decoder can decompress right away, at the cost of a lower
+        /// compression ratio. Shutting down the encoder writes the end of the
+        /// compressed stream before shutting down the inner writer, which is
+        /// needed for the peer to read the stream to completion.
+        pub struct $name<W: Write> {
+            inner: $inner,
+        }
+
+        impl<W: Write> $name<W> {
+            /// Gets a reference to the underlying writer.
+            pub fn get_ref(&self) -> &W {
+                self.inner.get_ref()
+            }
+
+            /// Gets a mutable reference to the underlying writer.
+            ///
+            /// It is inadvisable to directly write to the underlying writer,
+            /// as it would corrupt the compressed stream.
+            pub fn get_mut(&mut self) -> &mut W {
+                self.inner.get_mut()
+            }
+        }
+
+        impl<W: Write> Write for $name<W> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.inner.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.inner.flush()
+            }
+        }
+
+        impl<W: AsyncWrite> AsyncWrite for $name<W> {
+            fn shutdown(&mut self) -> Poll<(), io::Error> {
+                try_nb!(self.inner.$finish());
+                self.inner.get_mut().shutdown()
+            }
+        }
+
+        impl<W: Write + fmt::Debug> fmt::Debug for $name<W> {
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.debug_struct(stringify!($name))
+                    .field("writer", self.get_ref())
+                    .finish()
+            }
+        }
+    };
+}
+
+macro_rules! decoder {
+    ($(#[$attr:meta])* $name:ident($inner:ty)) => {
+        decoder!($(#[$attr])* $name($inner), get_ref(), get_mut());
+    };
+    ($(#[$attr:meta])* $name:ident($inner:ty), $($get_ref:ident()).+, $($get_mut:ident()).+) => {
+        $(#[$attr])*
+        pub struct $name<R: Read> {
+            inner: $inner,
+        }
+
+        impl<R: Read> $name<R> {
+            /// Gets a reference to the underlying reader.
+            pub fn get_ref(&self) -> &R {
+                self.inner$(.$get_ref())+
+            }
+
+            /// Gets a mutable reference to the underlying reader.
+            ///
+            /// It is inadvisable to directly read from the underlying reader,
+            /// as it would corrupt the compressed stream.
+            pub fn get_mut(&mut self) -> &mut R {
+                self.inner$(.$get_mut())+
+            }
+        }
+
+        impl<R: Read> Read for $name<R> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.inner.read(buf)
+            }
+        }
+
+        impl<R: AsyncRead> AsyncRead for $name<R> {}
+
+        impl<R: Read + fmt::Debug> fmt::Debug for $name<R> {
+            fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                fmt.debug_struct(stringify!($name))
+                    .field("reader", self.get_ref())
+                    .finish()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "gzip")]
+encoder! {
+    /// Compresses the data written to it in the gzip format.
+    ///
+    /// This type is only available with the `gzip` feature enabled.
+    GzipEncoder(flate2::write::GzEncoder<W>), try_finish
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> GzipEncoder<W> {
+    /// Creates a new encoder with the default compression level.
+    pub fn new(inner: W) -> GzipEncoder<W> {
+        GzipEncoder::with_level(inner, flate2::Compression::default().level())
+    }
+
+    /// Creates a new encoder with the given compression level, from 0 for
+    /// no compression to 9 for the best compression.
+    pub fn with_level(inner: W, level: u32) -> GzipEncoder<W> {
+        GzipEncoder {
+            inner: flate2::write::GzEncoder::new(inner, flate2::Compression::new(level)),
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+decoder! {
+    /// Decompresses the gzip data read from the inner reader.
+    ///
+    /// This type is only available with the `gzip` feature enabled.
+    GzipDecoder(flate2::read::GzDecoder<R>)
+}
+
+#[cfg(feature = "gzip")]
+impl<R: Read> GzipDecoder<R> {
+    /// Creates a new decoder reading gzip data from `inner`.
+    pub fn new(inner: R) -> GzipDecoder<R> {
+        GzipDecoder {
+            inner: flate2::read::GzDecoder::new(inner),
+        }
+    }
+}
+
+#[cfg(feature = "deflate")]
+encoder! {
+    /// Compresses the data written to it in the raw deflate format.
+    ///
+    /// This type is only available with the `deflate` feature enabled.
+    DeflateEncoder(flate2::write::DeflateEncoder<W>), try_finish
+}
+
+#[cfg(feature = "deflate")]
+impl<W: Write> DeflateEncoder<W> {
+    /// Creates a new encoder with the default compression level.
+    pub fn new(inner: W) -> DeflateEncoder<W> {
+        DeflateEncoder::with_level(inner, flate2::Compression::default().level())
+    }
+
+    /// Creates a new encoder with the given compression level, from 0 for
+    /// no compression to 9 for the best compression.
+    pub fn with_level(inner: W, level: u32) -> DeflateEncoder<W> {
+        DeflateEncoder {
+            inner: flate2::write::DeflateEncoder::new(inner, flate2::Compression::new(level)),
+        }
+    }
+}
+
+#[cfg(feature = "deflate")]
+decoder! {
+    /// Decompresses the raw deflate data read from the inner reader.
+    ///
+    /// This type is only available with the `deflate` feature enabled.
+    DeflateDecoder(flate2::read::DeflateDecoder<R>)
+}
+
+#[cfg(feature = "deflate")]
+impl<R: Read> DeflateDecoder<R> {
+    /// Creates a new decoder reading raw deflate data from `inner`.
+    pub fn new(inner: R) -> DeflateDecoder<R> {
+        DeflateDecoder {
+            inner: flate2::read::DeflateDecoder::new(inner),
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+encoder! {
+    /// Compresses the data written to it in the zstd format.
+    ///
+    /// This type is only available with the `zstd` feature enabled.
+    ZstdEncoder(zstd::stream::write::Encoder<W>), do_finish
+}
+
+#[cfg(feature = "zstd")]
+impl<W: Write> ZstdEncoder<W> {
+    /// Creates a new encoder with the default compression level.
+    pub fn new(inner: W) -> io::Result<ZstdEncoder<W>> {
+        ZstdEncoder::with_level(inner, 0)
+    }
+
+    /// Creates a new encoder with the given compression level, from 1 to
+    /// 21, or 0 for the default level.
+    pub fn with_level(inner: W, level: i32) -> io::Result<ZstdEncoder<W>> {
+        Ok(ZstdEncoder {
+            inner: zstd::stream::write::Encoder::new(inner, level)?,
+        })
+    }
+}
+
+#[cfg(feature = "zstd")]
+decoder! {
+    /// Decompresses the zstd data read from the inner reader.
+    ///
+    /// This type is only available with the `zstd` feature enabled.
+    ZstdDecoder(zstd::stream::read::Decoder<BufReader<R>>),
+    get_ref().get_ref(),
+    get_mut().get_mut()
+}
+
+#[cfg(feature = "zstd")]
+impl<R: Read> ZstdDecoder<R> {
+    /// Creates a new decoder reading zstd data from `inner`.
+    pub fn new(inner: R) -> io::Result<ZstdDecoder<R>> {
+        Ok(ZstdDecoder {
+            inner: zstd::stream::read::Decoder::new(inner)?,
+        })
+    }
+}
@@ -12,13 +12,33 @@ use {AsyncRead, AsyncWrite};
 /// [`copy`]: fn.copy.html
 #[derive(Debug)]
 pub struct Copy<R, W> {
+    inner: CopyBuf<R, W, Box<[u8]>>,
+}
+
+/// A future which will copy all data from a reader into a writer, through a
+/// buffer provided by the caller.
+///
+/// Created by the [`copy_buf`] function, this future will resolve to the
+/// number of bytes copied or an error if one happens.
+///
+/// [`copy_buf`]: fn.copy_buf.html
+#[derive(Debug)]
+pub struct CopyBuf<R, W, B> {
     reader: Option<R>,
-    read_done: bool,
     writer: Option<W>,
+    state: CopyState,
+    buf: Option<B>,
+}
+
+/// Progress of a copy, which is driven with the reader and the writer
+/// borrowed so that `copy_bidirectional` can run two copies between the same
+/// objects.
+#[derive(Debug)]
+pub(crate) struct CopyState {
+    read_done: bool,
     pos: usize,
     cap: usize,
     amt: u64,
-    buf: Box<[u8]>,
 }
 
 /// Creates a future which represents copying all the bytes from one object to
@@ -32,19 +52,74 @@ pub struct Copy<R, W> {
 /// On success the number of bytes is returned and the `reader` and `writer` are
 /// consumed. On error the error is returned and the I/O objects are consumed as
 /// well.
+///
+/// The data is copied through a 2KB buffer. Use [`copy_buf`] to pick the size
+/// of the buffer, or to reuse a buffer across copies.
+///
+/// [`copy_buf`]: fn.copy_buf.html
 pub fn copy<R, W>(reader: R, writer: W) -> Copy<R, W>
 where
     R: AsyncRead,
     W: AsyncWrite,
 {
     Copy {
+        inner: copy_buf(reader, writer, Box::new([0; 2048])),
+    }
+}
+
+/// Creates a future which represents copying all the bytes from one object to
+/// another, using `buf` to hold the data in transit.
+///
+/// This behaves like [`copy`], except that the data goes through `buf`
+/// instead of a buffer allocated by the future. Larger buffers reduce the
+/// number of reads and writes needed for bulk transfers. Once the copy
+/// completes, `buf` is returned along with the number of bytes copied and the
+/// I/O objects, so that it can be reused for the next copy.
+///
+/// # Panics
+///
+/// This function panics if `buf` is empty.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::copy_buf;
+///
+/// use std::io::Cursor;
+///
+/// # fn main() {
+/// let mut buf = vec![0; 64 * 1024];
+///
+/// for _ in 0..2 {
+///     let reader = Cursor::new(vec![1; 100_000]);
+///     let writer = Cursor::new(vec![]);
+///
+///     let (n, _, writer, b) = copy_buf(reader, writer, buf).wait().unwrap();
+///     assert_eq!(n, 100_000);
+///     assert_eq!(writer.get_ref().len(), 100_000);
+///
+///     buf = b;
+/// }
+/// # }
+/// ```
+///
+/// [`copy`]: fn.copy.html
+pub fn copy_buf<R, W, B>(reader: R, writer: W, mut buf: B) -> CopyBuf<R, W, B>
+where
+    R: AsyncRead,
+    W: AsyncWrite,
+    B: AsMut<[u8]>,
+{
+    assert!(!buf.as_mut().is_empty(), "copy buffer must not be empty");
+
+    CopyBuf {
         reader: Some(reader),
-        read_done: false,
         writer: Some(writer),
-        amt: 0,
-        pos: 0,
-        cap: 0,
-        buf: Box::new([0; 2048]),
+        state: CopyState::new(),
+        buf: Some(buf),
     }
 }
 
@@ -57,12 +132,64 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<(u64, R, W), io::Error> {
+        let (amt, reader, writer, _) = try_ready!(self.inner.poll());
+        Ok((amt, reader, writer).into())
+    }
+}
+
+impl<R, W, B> Future for CopyBuf<R, W, B>
+where
+    R: AsyncRead,
+    W: AsyncWrite,
+    B: AsMut<[u8]>,
+{
+    type Item = (u64, R, W, B);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, R, W, B), io::Error> {
+        let amt = try_ready!(self.state.poll_copy(
+            self.reader.as_mut().unwrap(),
+            self.writer.as_mut().unwrap(),
+            self.buf.as_mut().unwrap().as_mut(),
+        ));
+
+        let reader = self.reader.take().unwrap();
+        let writer = self.writer.take().unwrap();
+        let buf = self.buf.take().unwrap();
+        Ok((amt, reader, writer, buf).into())
+    }
+}
+
+impl CopyState {
+    pub(crate) fn new() -> CopyState {
+        CopyState {
+            read_done: false,
+            pos: 0,
+            cap: 0,
+            amt: 0,
+        }
+    }
+
+    /// Copies data from `reader` to `writer` through `buf`, returning the
+    /// number of bytes copied once `reader` hit EOF and `writer` has been
+    /// flushed.
+    ///
+    /// The same buffer must be passed on every call.
+    pub(crate) fn poll_copy<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+        buf: &mut [u8],
+    ) -> Poll<u64, io::Error>
+    where
+        R: AsyncRead,
+        W: AsyncWrite,
+    {
         loop {
             // If our buffer is empty, then we need to read some data to
             // continue.
             if self.pos == self.cap && !self.read_done {
-                let reader = self.reader.as_mut().unwrap();
-                let n = try_ready!(reader.poll_read(&mut self.buf));
+                let n = try_ready!(reader.poll_read(buf));
                 if n == 0 {
                     self.read_done = true;
                 } else {
@@ -73,8 +200,7 @@ where
 
             // If our buffer has some data, let's write it out!
             while self.pos < self.cap {
-                let writer = self.writer.as_mut().unwrap();
-                let i = try_ready!(writer.poll_write(&self.buf[self.pos..self.cap]));
+                let i = try_ready!(writer.poll_write(&buf[self.pos..self.cap]));
                 if i == 0 {
                     return Err(io::Error::new(
                         io::ErrorKind::WriteZero,
@@ -90,10 +216,8 @@ where
             // data and finish the transfer.
             // done with the entire transfer.
             if self.pos == self.cap && self.read_done {
-                try_ready!(self.writer.as_mut().unwrap().poll_flush());
-                let reader = self.reader.take().unwrap();
-                let writer = self.writer.take().unwrap();
-                return Ok((self.amt, reader, writer).into());
+                try_ready!(writer.poll_flush());
+                return Ok(self.amt.into());
             }
         }
     }
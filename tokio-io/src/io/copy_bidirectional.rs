@@ -0,0 +1,215 @@
+use std::io;
+
+use futures::{Async, Future, Poll};
+
+use io::copy::CopyState;
+use {AsyncRead, AsyncWrite};
+
+/// A future which copies data in both directions between two I/O objects.
+///
+/// Created by the [`copy_bidirectional`] function, this future will resolve to
+/// the number of bytes copied in each direction or an error if one happens.
+///
+/// [`copy_bidirectional`]: fn.copy_bidirectional.html
+#[derive(Debug)]
+pub struct CopyBidirectional<A, B> {
+    a: Option<A>,
+    b: Option<B>,
+    a_to_b: Transfer,
+    b_to_a: Transfer,
+}
+
+/// State of the copy in one direction.
+#[derive(Debug)]
+struct Transfer {
+    copy: CopyState,
+    // Set once the data is copied, while the writer is being closed.
+    copied: Option<u64>,
+    closed: bool,
+    buf: Box<[u8]>,
+}
+
+/// I/O objects which can shut down their writing side while they keep
+/// reading, such as TCP and Unix streams.
+///
+/// [`copy_bidirectional`] uses it to forward the EOF read from one object to
+/// the other, whose peer can still send data back after reading EOF.
+///
+/// [`copy_bidirectional`]: fn.copy_bidirectional.html
+pub trait HalfClose {
+    /// Attempts to shut down the writing side of this object.
+    ///
+    /// Unlike `AsyncWrite::shutdown`, this signals EOF to the peer of a
+    /// connection, and the object can still be read from afterwards.
+    fn poll_close_write(&mut self) -> Poll<(), io::Error>;
+}
+
+/// Creates a future which copies data from `a` to `b` and from `b` to `a` at
+/// the same time.
+///
+/// Each direction is copied until its reader hits EOF, at which point the
+/// writer of that direction is flushed and its writing side closed, using
+/// [`HalfClose`]. The other direction keeps going, so a peer which
+/// half-closes its side of the connection still receives the remaining data.
+/// The future completes once both directions are done. A writer which is no
+/// longer connected, as its peer closed the connection entirely, counts as
+/// closed.
+///
+/// On success the number of bytes copied from `a` to `b` and from `b` to `a`
+/// are returned, along with both I/O objects. On error the error is returned
+/// and the I/O objects are consumed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::{copy_bidirectional, HalfClose};
+///
+/// # use std::io::{self, Read, Write};
+/// # use tokio_io::{AsyncRead, AsyncWrite};
+/// # use futures::Poll;
+/// #
+/// # /// Reads `input` and records the bytes written to it.
+/// # struct Stream {
+/// #     input: io::Cursor<Vec<u8>>,
+/// #     output: Vec<u8>,
+/// # }
+/// #
+/// # impl Read for Stream {
+/// #     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+/// #         self.input.read(buf)
+/// #     }
+/// # }
+/// #
+/// # impl AsyncRead for Stream {}
+/// #
+/// # impl Write for Stream {
+/// #     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+/// #         self.output.write(buf)
+/// #     }
+/// #
+/// #     fn flush(&mut self) -> io::Result<()> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// #
+/// # impl AsyncWrite for Stream {
+/// #     fn shutdown(&mut self) -> Poll<(), io::Error> {
+/// #         Ok(().into())
+/// #     }
+/// # }
+/// #
+/// # impl HalfClose for Stream {
+/// #     fn poll_close_write(&mut self) -> Poll<(), io::Error> {
+/// #         Ok(().into())
+/// #     }
+/// # }
+/// #
+/// # fn stream(input: &[u8]) -> Stream {
+/// #     Stream { input: io::Cursor::new(input.to_vec()), output: vec![] }
+/// # }
+/// # fn main() {
+/// let client = stream(b"request");
+/// let server = stream(b"response");
+///
+/// let (sent, received, _client, _server) = copy_bidirectional(client, server)
+///     .wait()
+///     .unwrap();
+///
+/// assert_eq!(sent, 7);
+/// assert_eq!(received, 8);
+/// # }
+/// ```
+///
+/// [`HalfClose`]: trait.HalfClose.html
+pub fn copy_bidirectional<A, B>(a: A, b: B) -> CopyBidirectional<A, B>
+where
+    A: AsyncRead + AsyncWrite + HalfClose,
+    B: AsyncRead + AsyncWrite + HalfClose,
+{
+    CopyBidirectional {
+        a: Some(a),
+        b: Some(b),
+        a_to_b: Transfer::new(),
+        b_to_a: Transfer::new(),
+    }
+}
+
+impl<A, B> Future for CopyBidirectional<A, B>
+where
+    A: AsyncRead + AsyncWrite + HalfClose,
+    B: AsyncRead + AsyncWrite + HalfClose,
+{
+    type Item = (u64, u64, A, B);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(u64, u64, A, B), io::Error> {
+        let a_to_b = {
+            let a = self.a.as_mut().unwrap();
+            let b = self.b.as_mut().unwrap();
+
+            self.a_to_b.poll(a, b)?
+        };
+
+        let b_to_a = {
+            let a = self.a.as_mut().unwrap();
+            let b = self.b.as_mut().unwrap();
+
+            self.b_to_a.poll(b, a)?
+        };
+
+        match (a_to_b, b_to_a) {
+            (Async::Ready(a_to_b), Async::Ready(b_to_a)) => {
+                let a = self.a.take().unwrap();
+                let b = self.b.take().unwrap();
+                Ok((a_to_b, b_to_a, a, b).into())
+            }
+            _ => Ok(Async::NotReady),
+        }
+    }
+}
+
+impl Transfer {
+    fn new() -> Transfer {
+        Transfer {
+            copy: CopyState::new(),
+            copied: None,
+            closed: false,
+            buf: Box::new([0; 2048]),
+        }
+    }
+
+    /// Copy data from `reader` to `writer`, returning the number of bytes
+    /// copied once `reader` hit EOF and the writing side of `writer` has been
+    /// closed.
+    fn poll<R, W>(&mut self, reader: &mut R, writer: &mut W) -> Poll<u64, io::Error>
+    where
+        R: AsyncRead,
+        W: AsyncWrite + HalfClose,
+    {
+        let amt = match self.copied {
+            Some(amt) => amt,
+            None => {
+                let amt = try_ready!(self.copy.poll_copy(reader, writer, &mut self.buf));
+                self.copied = Some(amt);
+                amt
+            }
+        };
+
+        if !self.closed {
+            match writer.poll_close_write() {
+                Ok(Async::Ready(())) => {}
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                // The peer is gone, there is no one left to forward EOF to.
+                Err(ref e) if e.kind() == io::ErrorKind::NotConnected => {}
+                Err(e) => return Err(e),
+            }
+
+            self.closed = true;
+        }
+
+        Ok(amt.into())
+    }
+}
@@ -9,22 +9,48 @@
 //! [found online]: https://tokio.rs/docs/getting-started/core/
 //! [low level details]: https://tokio.rs/docs/going-deeper-tokio/core-low-level/
 
+mod buf_reader;
+mod buf_writer;
+mod chain;
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+mod compression;
 mod copy;
+mod copy_bidirectional;
 mod flush;
 mod read;
 mod read_exact;
+mod read_line;
 mod read_to_end;
 mod read_until;
+mod seek;
 mod shutdown;
+mod take;
+#[cfg(feature = "throttle")]
+mod throttle;
 mod write_all;
 
-pub use self::copy::{copy, Copy};
+pub use self::buf_reader::BufReader;
+pub use self::buf_writer::BufWriter;
+pub use self::chain::{chain, Chain};
+#[cfg(feature = "deflate")]
+pub use self::compression::{DeflateDecoder, DeflateEncoder};
+#[cfg(feature = "gzip")]
+pub use self::compression::{GzipDecoder, GzipEncoder};
+#[cfg(feature = "zstd")]
+pub use self::compression::{ZstdDecoder, ZstdEncoder};
+pub use self::copy::{copy, copy_buf, Copy, CopyBuf};
+pub use self::copy_bidirectional::{copy_bidirectional, CopyBidirectional, HalfClose};
 pub use self::flush::{flush, Flush};
 pub use self::read::{read, Read};
 pub use self::read_exact::{read_exact, ReadExact};
-pub use self::read_to_end::{read_to_end, ReadToEnd};
+pub use self::read_line::{read_line, ReadLine};
+pub use self::read_to_end::{read_to_end, LimitExceeded, ReadToEnd};
 pub use self::read_until::{read_until, ReadUntil};
+pub use self::seek::{seek, Seek};
 pub use self::shutdown::{shutdown, Shutdown};
+pub use self::take::{take, Take};
+#[cfg(feature = "throttle")]
+pub use self::throttle::{RateLimit, Throttle};
 pub use self::write_all::{write_all, WriteAll};
 pub use allow_std::AllowStdIo;
 pub use lines::{lines, Lines};
@@ -32,6 +32,15 @@ enum State<A, T> {
 /// the error yielded. In the case of success the object will be destroyed and
 /// the buffer will be returned, with all data read from the stream appended to
 /// the buffer.
+///
+/// The operation can be interrupted without losing track of the stream, for
+/// instance when it is raced against a timeout: [`ReadExact::into_inner`]
+/// returns the object, the buffer and the number of bytes already read,
+/// including after an error. The read can then be resumed by passing a
+/// [`Window`] starting at that position as the buffer.
+///
+/// [`ReadExact::into_inner`]: struct.ReadExact.html#method.into_inner
+/// [`Window`]: struct.Window.html
 pub fn read_exact<A, T>(a: A, buf: T) -> ReadExact<A, T>
 where
     A: AsyncRead,
@@ -50,6 +59,60 @@ fn eof() -> io::Error {
     io::Error::new(io::ErrorKind::UnexpectedEof, "early eof")
 }
 
+impl<A, T> ReadExact<A, T> {
+    /// Returns the number of bytes read so far.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn position(&self) -> usize {
+        match self.state {
+            State::Reading { pos, .. } => pos,
+            State::Empty => panic!("ReadExact already completed"),
+        }
+    }
+
+    /// Returns a reference to the I/O object.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn get_ref(&self) -> &A {
+        match self.state {
+            State::Reading { ref a, .. } => a,
+            State::Empty => panic!("ReadExact already completed"),
+        }
+    }
+
+    /// Returns a mutable reference to the I/O object.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn get_mut(&mut self) -> &mut A {
+        match self.state {
+            State::Reading { ref mut a, .. } => a,
+            State::Empty => panic!("ReadExact already completed"),
+        }
+    }
+
+    /// Abandons the operation, returning the I/O object, the buffer, and the
+    /// number of bytes read so far.
+    ///
+    /// This may be called after the future has returned an error, or after it
+    /// lost a race against a timeout, to learn where the stream stands.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn into_inner(self) -> (A, T, usize) {
+        match self.state {
+            State::Reading { a, buf, pos } => (a, buf, pos),
+            State::Empty => panic!("ReadExact already completed"),
+        }
+    }
+}
+
 impl<A, T> Future for ReadExact<A, T>
 where
     A: AsyncRead,
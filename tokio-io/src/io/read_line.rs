@@ -0,0 +1,122 @@
+use std::io;
+use std::mem;
+
+use futures::{Async, Future, Poll};
+
+use AsyncBufRead;
+
+/// A future which can be used to easily read a line of text from a stream.
+///
+/// Created by the [`read_line`] function.
+///
+/// [`read_line`]: fn.read_line.html
+#[derive(Debug)]
+pub struct ReadLine<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading { a: A, buf: Vec<u8> },
+    Empty,
+}
+
+/// Creates a future which will read all the bytes associated with the I/O
+/// object `A` into the string provided until a newline character is reached.
+/// This method is the async equivalent to [`BufRead::read_line`].
+///
+/// In case of an error the string and the object will be discarded, with
+/// the error yielded. In the case of success the object will be destroyed and
+/// the string will be returned, with the line appended to it, including the
+/// newline character (if found).
+///
+/// Unlike [`BufRead::read_line`], no data is lost when the object is not
+/// ready in the middle of a multi-byte character. An error of kind
+/// `InvalidData` is returned if the line is not valid UTF-8.
+///
+/// [`BufRead::read_line`]: https://doc.rust-lang.org/std/io/trait.BufRead.html#method.read_line
+pub fn read_line<A>(a: A, buf: String) -> ReadLine<A>
+where
+    A: AsyncBufRead,
+{
+    ReadLine {
+        state: State::Reading {
+            a: a,
+            buf: buf.into_bytes(),
+        },
+    }
+}
+
+impl<A> Future for ReadLine<A>
+where
+    A: AsyncBufRead,
+{
+    type Item = (A, String);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, String), io::Error> {
+        match self.state {
+            State::Reading {
+                ref mut a,
+                ref mut buf,
+            } => {
+                try_ready!(poll_read_until(a, b'\n', buf));
+            }
+            State::Empty => panic!("poll ReadLine after it's done"),
+        }
+
+        let (a, buf) = match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf } => (a, buf),
+            State::Empty => unreachable!(),
+        };
+
+        // The string passed in is valid UTF-8, so only the appended bytes may
+        // not be.
+        match String::from_utf8(buf) {
+            Ok(buf) => Ok((a, buf).into()),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+        }
+    }
+}
+
+/// Like `BufRead::read_until`, but filling the buffer with `poll_fill_buf`.
+///
+/// The bytes read before the object stops being ready are kept in `buf`.
+fn poll_read_until<A>(a: &mut A, byte: u8, buf: &mut Vec<u8>) -> Poll<usize, io::Error>
+where
+    A: AsyncBufRead + ?Sized,
+{
+    let mut read = 0;
+
+    loop {
+        let (done, used) = {
+            let available = match a.poll_fill_buf() {
+                Ok(Async::Ready(available)) => available,
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            match available.iter().position(|&b| b == byte) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..i + 1]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            }
+        };
+
+        a.consume(used);
+        read += used;
+
+        if done || used == 0 {
+            return Ok(Async::Ready(read));
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use std::io;
-use std::mem;
+use std::error::Error;
+use std::io::{self, Read};
+use std::{fmt, mem};
 
 use futures::{Future, Poll};
 
@@ -14,6 +15,12 @@ use AsyncRead;
 #[derive(Debug)]
 pub struct ReadToEnd<A> {
     state: State<A>,
+
+    /// Length of the buffer before reading
+    start: usize,
+
+    /// Maximum number of bytes to read
+    limit: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -22,18 +29,105 @@ enum State<A> {
     Empty,
 }
 
+/// Error returned by [`ReadToEnd`] when the stream is longer than the limit
+/// set with [`ReadToEnd::limit`].
+///
+/// It is wrapped in an `io::Error` of kind `InvalidData`, and can be recovered
+/// with `io::Error::get_ref` and `downcast_ref`.
+///
+/// [`ReadToEnd`]: struct.ReadToEnd.html
+/// [`ReadToEnd::limit`]: struct.ReadToEnd.html#method.limit
+#[derive(Debug)]
+pub struct LimitExceeded {
+    limit: usize,
+}
+
 /// Creates a future which will read all the bytes associated with the I/O
 /// object `A` into the buffer provided.
 ///
 /// In the case of an error the buffer and the object will be discarded, with
 /// the error yielded. In the case of success both the object and the buffer
 /// will be returned, with all data read from the stream appended to the buffer.
+///
+/// By default, the stream is read until EOF however long it is. Use
+/// [`ReadToEnd::limit`] to bound the number of bytes read from untrusted
+/// peers, and [`ReadToEnd::reserve`] to allocate the buffer up front when the
+/// length of the stream is known.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::{read_to_end, LimitExceeded};
+///
+/// use std::io;
+///
+/// # fn main() {
+/// let body: &[u8] = b"hello world";
+///
+/// let (_, buf) = read_to_end(body, vec![])
+///     .reserve(11)
+///     .limit(16)
+///     .wait()
+///     .unwrap();
+/// assert_eq!(buf, b"hello world");
+///
+/// let err = read_to_end(body, vec![]).limit(5).wait().unwrap_err();
+/// assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+///
+/// let limit = err.get_ref().unwrap().downcast_ref::<LimitExceeded>().unwrap();
+/// assert_eq!(limit.limit(), 5);
+/// # }
+/// ```
+///
+/// [`ReadToEnd::limit`]: struct.ReadToEnd.html#method.limit
+/// [`ReadToEnd::reserve`]: struct.ReadToEnd.html#method.reserve
 pub fn read_to_end<A>(a: A, buf: Vec<u8>) -> ReadToEnd<A>
 where
     A: AsyncRead,
 {
     ReadToEnd {
+        start: buf.len(),
         state: State::Reading { a: a, buf: buf },
+        limit: None,
+    }
+}
+
+impl<A> ReadToEnd<A> {
+    /// Fail once more than `limit` bytes have been read from the stream.
+    ///
+    /// The bytes already in the buffer do not count towards the limit. When
+    /// the stream is longer than `limit`, the future fails with an
+    /// `io::Error` of kind `InvalidData` wrapping a [`LimitExceeded`] error,
+    /// after reading at most one byte past the limit.
+    ///
+    /// [`LimitExceeded`]: struct.LimitExceeded.html
+    pub fn limit(mut self, limit: usize) -> ReadToEnd<A> {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Reserve capacity for at least `additional` more bytes in the buffer.
+    ///
+    /// This avoids growing the buffer repeatedly while reading streams whose
+    /// length is known in advance. The reservation is capped to the
+    /// [`limit`], if one is set, so a length announced by a peer cannot make
+    /// the buffer larger than the limit allows.
+    ///
+    /// [`limit`]: #method.limit
+    pub fn reserve(mut self, additional: usize) -> ReadToEnd<A> {
+        let additional = match self.limit {
+            Some(limit) => additional.min(limit),
+            None => additional,
+        };
+
+        if let State::Reading { ref mut buf, .. } = self.state {
+            buf.reserve(additional);
+        }
+
+        self
     }
 }
 
@@ -53,7 +147,25 @@ where
                 // If we get `Ok`, then we know the stream hit EOF and we're done. If we
                 // hit "would block" then all the read data so far is in our buffer, and
                 // otherwise we propagate errors
-                try_nb!(a.read_to_end(buf));
+                match self.limit {
+                    Some(limit) => {
+                        // Read one byte past the limit to tell a stream of
+                        // exactly `limit` bytes from a longer one.
+                        let remaining = limit - (buf.len() - self.start);
+                        let max = (remaining as u64).saturating_add(1);
+                        try_nb!(a.by_ref().take(max).read_to_end(buf));
+
+                        if buf.len() - self.start > limit {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                LimitExceeded { limit },
+                            ));
+                        }
+                    }
+                    None => {
+                        try_nb!(a.read_to_end(buf));
+                    }
+                }
             }
             State::Empty => panic!("poll ReadToEnd after it's done"),
         }
@@ -64,3 +176,22 @@ where
         }
     }
 }
+
+impl LimitExceeded {
+    /// Returns the limit that was exceeded.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "stream exceeds the limit of {} bytes", self.limit)
+    }
+}
+
+impl Error for LimitExceeded {
+    fn description(&self) -> &str {
+        "stream is longer than the limit"
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::{self, SeekFrom};
+
+use futures::{Future, Poll};
+
+use AsyncSeek;
+
+/// A future used to seek an I/O object.
+///
+/// Resolves to the I/O object and its new position once the seek operation
+/// is complete.
+///
+/// Created by the [`seek`] function.
+///
+/// [`seek`]: fn.seek.html
+#[derive(Debug)]
+pub struct Seek<A> {
+    a: Option<A>,
+    pos: SeekFrom,
+}
+
+/// Creates a future which will seek an I/O object to `pos` and then yield the
+/// object itself, along with the new position from the start of the stream.
+///
+/// This function will consume the object provided if an error happens.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::{read_to_end, seek};
+///
+/// use std::io::{Cursor, SeekFrom};
+///
+/// # fn main() {
+/// let cursor = Cursor::new(b"hello world".to_vec());
+///
+/// let (cursor, pos) = seek(cursor, SeekFrom::Start(6)).wait().unwrap();
+/// assert_eq!(pos, 6);
+///
+/// let (_, buf) = read_to_end(cursor, vec![]).wait().unwrap();
+/// assert_eq!(buf, b"world");
+/// # }
+/// ```
+pub fn seek<A>(a: A, pos: SeekFrom) -> Seek<A>
+where
+    A: AsyncSeek,
+{
+    Seek { a: Some(a), pos }
+}
+
+impl<A> Future for Seek<A>
+where
+    A: AsyncSeek,
+{
+    type Item = (A, u64);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(A, u64), io::Error> {
+        let pos = try_ready!(self
+            .a
+            .as_mut()
+            .expect("cannot poll Seek after it resolves")
+            .poll_seek(self.pos));
+        Ok((self.a.take().unwrap(), pos).into())
+    }
+}
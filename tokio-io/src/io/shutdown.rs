@@ -38,7 +38,7 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<A, io::Error> {
-        try_ready!(self.a.as_mut().unwrap().shutdown());
+        try_ready!(self.a.as_mut().unwrap().poll_shutdown());
         Ok(Async::Ready(self.a.take().unwrap()))
     }
 }
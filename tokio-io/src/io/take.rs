@@ -0,0 +1,153 @@
+use std::cmp;
+use std::io::{self, BufRead, Read};
+
+use futures::{Async, Poll};
+
+use {AsyncBufRead, AsyncRead};
+
+/// Reader adapter which limits the bytes read from an underlying reader.
+///
+/// Created by the [`take`] function.
+///
+/// [`take`]: fn.take.html
+#[derive(Debug)]
+pub struct Take<R> {
+    inner: R,
+    limit: u64,
+}
+
+/// Creates a reader which will read at most `limit` bytes from `reader`.
+///
+/// Once `limit` bytes have been read, the returned reader reports EOF, even if
+/// `reader` has more data. This makes it possible to hand a bounded part of a
+/// stream, such as a length-prefixed payload, to combinators reading until
+/// EOF like [`read_to_end`] or [`copy`], and then get the reader back with
+/// [`Take::into_inner`] to keep reading the stream.
+///
+/// This is the async equivalent to [`Read::take`].
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_io;
+/// # use futures::Future;
+/// use tokio_io::io::{read_to_end, take};
+///
+/// # fn main() {
+/// let reader: &[u8] = b"hello world";
+///
+/// let (reader, buf) = read_to_end(take(reader, 5), vec![]).wait().unwrap();
+/// assert_eq!(buf, b"hello");
+/// assert_eq!(reader.into_inner(), b" world");
+/// # }
+/// ```
+///
+/// [`read_to_end`]: fn.read_to_end.html
+/// [`copy`]: fn.copy.html
+/// [`Take::into_inner`]: struct.Take.html#method.into_inner
+/// [`Read::take`]: https://doc.rust-lang.org/std/io/trait.Read.html#method.take
+pub fn take<R>(reader: R, limit: u64) -> Take<R>
+where
+    R: AsyncRead,
+{
+    Take {
+        inner: reader,
+        limit,
+    }
+}
+
+impl<R> Take<R> {
+    /// Returns the number of bytes that can be read before this instance will
+    /// return EOF.
+    ///
+    /// Note that this may be more than the number of bytes left in the
+    /// underlying reader.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the number of bytes that can be read before this instance will
+    /// return EOF.
+    ///
+    /// This is the same as creating a new `Take` instance, so the number of
+    /// bytes already read is not taken into account.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the internal limit of this
+    /// `Take`.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `Take`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> Read for Take<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // Don't call into the inner reader at all at EOF because it may still
+        // block
+        if self.limit == 0 {
+            return Ok(0);
+        }
+
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        let n = self.inner.read(&mut buf[..max])?;
+        self.limit -= n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: AsyncRead + BufRead> BufRead for Take<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Don't call into the inner reader at all at EOF because it may still
+        // block
+        if self.limit == 0 {
+            return Ok(&[]);
+        }
+
+        let limit = self.limit;
+        let buf = self.inner.fill_buf()?;
+        let cap = cmp::min(buf.len() as u64, limit) as usize;
+        Ok(&buf[..cap])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Don't let callers reset the limit by passing an overlarge value
+        let amt = cmp::min(amt as u64, self.limit) as usize;
+        self.limit -= amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Take<R> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for Take<R> {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        if self.limit == 0 {
+            return Ok(Async::Ready(&[]));
+        }
+
+        let limit = self.limit;
+        let buf = try_ready!(self.inner.poll_fill_buf());
+        let cap = cmp::min(buf.len() as u64, limit) as usize;
+        Ok(Async::Ready(&buf[..cap]))
+    }
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+use futures::{Async, Future, Poll};
+use tokio_timer::timer::Handle;
+use tokio_timer::Delay;
+
+use {AsyncRead, AsyncWrite};
+
+/// A limit on the rate at which bytes are transferred, used by [`Throttle`].
+///
+/// Bytes are allowed through at `bytes_per_sec` on average. Up to `burst`
+/// bytes may be transferred at once after a quiet period.
+///
+/// [`Throttle`]: struct.Throttle.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    bytes_per_sec: u64,
+    burst: u64,
+}
+
+impl RateLimit {
+    /// Creates a limit of `bytes_per_sec` on average, with bursts of up to
+    /// `burst` bytes.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `bytes_per_sec` or `burst` is zero.
+    pub fn new(bytes_per_sec: u64, burst: u64) -> RateLimit {
+        assert!(bytes_per_sec > 0, "rate limit must be greater than zero");
+        assert!(burst > 0, "burst capacity must be greater than zero");
+
+        RateLimit {
+            bytes_per_sec,
+            burst,
+        }
+    }
+
+    /// Returns the average number of bytes allowed per second.
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// Returns the maximum number of bytes allowed at once.
+    pub fn burst(&self) -> u64 {
+        self.burst
+    }
+}
+
+/// Limits the rate of reads and writes of an I/O object.
+///
+/// Each direction is shaped by a token bucket holding up to
+/// [`RateLimit::burst`] bytes, refilled at [`RateLimit::bytes_per_sec`]. A
+/// read or write is shortened to the bytes available in the bucket, and once
+/// the bucket is empty, the `Throttle` returns `WouldBlock` and uses a
+/// `tokio-timer` [`Delay`] to wake up the task when bytes are available
+/// again. A direction without a limit is not throttled.
+///
+/// As it relies on a timer, a throttled `Throttle` must be used from within
+/// a task running in the context of a timer, such as on the Tokio runtime.
+///
+/// This type is only available with the `throttle` feature enabled.
+///
+/// [`RateLimit::burst`]: struct.RateLimit.html#method.burst
+/// [`RateLimit::bytes_per_sec`]: struct.RateLimit.html#method.bytes_per_sec
+/// [`Delay`]: https://docs.rs/tokio-timer/0.2/tokio_timer/struct.Delay.html
+pub struct Throttle<T> {
+    inner: T,
+    read: Option<Bucket>,
+    write: Option<Bucket>,
+}
+
+struct Bucket {
+    limit: RateLimit,
+
+    // Bytes which may be transferred now. Fractional, so that the bytes
+    // earned between two refills are not lost.
+    tokens: f64,
+
+    // When the bucket was last refilled.
+    refilled: Instant,
+
+    // Wakes up the task once the bucket holds a byte again.
+    delay: Option<Delay>,
+}
+
+impl<T> Throttle<T> {
+    /// Creates a new `Throttle` around `inner`, without any limit.
+    pub fn new(inner: T) -> Throttle<T> {
+        Throttle {
+            inner,
+            read: None,
+            write: None,
+        }
+    }
+
+    /// Returns the limit on the rate of reads.
+    pub fn read_limit(&self) -> Option<RateLimit> {
+        self.read.as_ref().map(|bucket| bucket.limit)
+    }
+
+    /// Sets the limit on the rate of reads, or removes it.
+    ///
+    /// The bucket starts out full, so up to a burst of bytes may be read
+    /// right away.
+    pub fn set_read_limit(&mut self, limit: Option<RateLimit>) {
+        self.read = limit.map(Bucket::new);
+    }
+
+    /// Returns the limit on the rate of writes.
+    pub fn write_limit(&self) -> Option<RateLimit> {
+        self.write.as_ref().map(|bucket| bucket.limit)
+    }
+
+    /// Sets the limit on the rate of writes, or removes it.
+    ///
+    /// The bucket starts out full, so up to a burst of bytes may be written
+    /// right away.
+    pub fn set_write_limit(&mut self, limit: Option<RateLimit>) {
+        self.write = limit.map(Bucket::new);
+    }
+
+    /// Gets a reference to the underlying I/O object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying I/O object.
+    ///
+    /// Data transferred directly through the underlying I/O object is not
+    /// counted against the limits.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this `Throttle`, returning the underlying I/O object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Returns how many of `len` bytes may be transferred, or `WouldBlock` if
+/// the bucket is empty.
+fn acquire(bucket: &mut Option<Bucket>, len: usize) -> io::Result<usize> {
+    match *bucket {
+        Some(ref mut bucket) if len > 0 => match bucket.poll_acquire(len)? {
+            Async::Ready(n) => Ok(n),
+            Async::NotReady => Err(io::ErrorKind::WouldBlock.into()),
+        },
+        _ => Ok(len),
+    }
+}
+
+fn consume(bucket: &mut Option<Bucket>, n: usize) {
+    if let Some(ref mut bucket) = *bucket {
+        bucket.tokens -= n as f64;
+    }
+}
+
+impl<T: Read> Read for Throttle<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = acquire(&mut self.read, buf.len())?;
+        let n = self.inner.read(&mut buf[..len])?;
+        consume(&mut self.read, n);
+        Ok(n)
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Throttle<T> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        self.inner.prepare_uninitialized_buffer(buf)
+    }
+}
+
+impl<T: Write> Write for Throttle<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = acquire(&mut self.write, buf.len())?;
+        let n = self.inner.write(&buf[..len])?;
+        consume(&mut self.write, n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Throttle<T> {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.inner.poll_flush()
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.inner.shutdown()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Throttle<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Throttle")
+            .field("inner", &self.inner)
+            .field("read_limit", &self.read_limit())
+            .field("write_limit", &self.write_limit())
+            .finish()
+    }
+}
+
+// ===== impl Bucket =====
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Bucket {
+        Bucket {
+            limit,
+            tokens: limit.burst as f64,
+            refilled: Handle::default().now(),
+            delay: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if now <= self.refilled {
+            return;
+        }
+
+        let elapsed = secs(now - self.refilled);
+        let tokens = self.tokens + elapsed * self.limit.bytes_per_sec as f64;
+
+        self.tokens = tokens.min(self.limit.burst as f64);
+        self.refilled = now;
+    }
+
+    /// Returns how many of `len` bytes may be transferred, waiting until at
+    /// least one may be.
+    fn poll_acquire(&mut self, len: usize) -> Poll<usize, io::Error> {
+        loop {
+            let now = Handle::default().now();
+            self.refill(now);
+
+            if self.tokens >= 1.0 {
+                self.delay = None;
+                return Ok(Async::Ready(self.tokens.min(len as f64) as usize));
+            }
+
+            let wait = (1.0 - self.tokens) / self.limit.bytes_per_sec as f64;
+            let deadline = now + duration(wait);
+
+            match self.delay {
+                Some(ref mut delay) => delay.reset(deadline),
+                None => self.delay = Some(Delay::new(deadline)),
+            }
+
+            let poll = self.delay.as_mut().unwrap().poll();
+            match poll {
+                Ok(Async::Ready(())) => {}
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e)),
+            }
+        }
+    }
+}
+
+fn secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1e9
+}
+
+/// Converts seconds to a `Duration`, rounding up to the next nanosecond.
+fn duration(secs: f64) -> Duration {
+    let whole = secs.trunc();
+    let nanos = ((secs - whole) * 1e9).ceil();
+
+    if nanos >= 1e9 {
+        Duration::new(whole as u64 + 1, 0)
+    } else {
+        Duration::new(whole as u64, nanos as u32)
+    }
+}
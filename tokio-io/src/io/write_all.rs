@@ -29,12 +29,18 @@ enum State<A, T> {
 /// needed).
 ///
 /// Any error which happens during writing will cause both the stream and the
-/// buffer to get destroyed.
+/// buffer to get destroyed, unless they are taken back with
+/// [`WriteAll::into_inner`]. It also returns the number of bytes already
+/// written, so that an operation which failed or lost a race against a
+/// timeout can be resumed with a [`Window`] starting at that position.
 ///
 /// The `buf` parameter here only requires the `AsRef<[u8]>` trait, which should
 /// be broadly applicable to accepting data which can be converted to a slice.
 /// The `Window` struct is also available in this crate to provide a different
 /// window into a slice if necessary.
+///
+/// [`WriteAll::into_inner`]: struct.WriteAll.html#method.into_inner
+/// [`Window`]: struct.Window.html
 pub fn write_all<A, T>(a: A, buf: T) -> WriteAll<A, T>
 where
     A: AsyncWrite,
@@ -53,6 +59,60 @@ fn zero_write() -> io::Error {
     io::Error::new(io::ErrorKind::WriteZero, "zero-length write")
 }
 
+impl<A, T> WriteAll<A, T> {
+    /// Returns the number of bytes written so far.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn position(&self) -> usize {
+        match self.state {
+            State::Writing { pos, .. } => pos,
+            State::Empty => panic!("WriteAll already completed"),
+        }
+    }
+
+    /// Returns a reference to the I/O object.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn get_ref(&self) -> &A {
+        match self.state {
+            State::Writing { ref a, .. } => a,
+            State::Empty => panic!("WriteAll already completed"),
+        }
+    }
+
+    /// Returns a mutable reference to the I/O object.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn get_mut(&mut self) -> &mut A {
+        match self.state {
+            State::Writing { ref mut a, .. } => a,
+            State::Empty => panic!("WriteAll already completed"),
+        }
+    }
+
+    /// Abandons the operation, returning the I/O object, the buffer, and the
+    /// number of bytes written so far.
+    ///
+    /// This may be called after the future has returned an error, or after it
+    /// lost a race against a timeout, to learn where the stream stands.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the future has already completed.
+    pub fn into_inner(self) -> (A, T, usize) {
+        match self.state {
+            State::Writing { a, buf, pos } => (a, buf, pos),
+            State::Empty => panic!("WriteAll already completed"),
+        }
+    }
+}
+
 impl<A, T> Future for WriteAll<A, T>
 where
     A: AsyncWrite,
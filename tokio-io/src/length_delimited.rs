@@ -275,6 +275,10 @@ impl<T: io::Write> io::Write for FramedRead<T> {
 }
 
 impl<T: AsyncWrite> AsyncWrite for FramedRead<T> {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.inner.get_mut().poll_flush()
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         self.inner.get_mut().shutdown()
     }
@@ -546,7 +550,7 @@ impl<T: AsyncWrite, B: IntoBuf> Sink for FramedWrite<T, B> {
 
     fn close(&mut self) -> Poll<(), io::Error> {
         try_ready!(self.poll_complete());
-        self.inner.shutdown()
+        self.inner.poll_shutdown()
     }
 }
 
@@ -15,6 +15,13 @@ extern crate log;
 #[macro_use]
 extern crate futures;
 extern crate bytes;
+#[cfg(feature = "flate2")]
+extern crate flate2;
+extern crate iovec;
+#[cfg(feature = "throttle")]
+extern crate tokio_timer;
+#[cfg(feature = "zstd")]
+extern crate zstd;
 
 use std::io as std_io;
 
@@ -47,10 +54,14 @@ macro_rules! try_nb {
 
 pub mod codec;
 pub mod io;
+#[cfg(feature = "mock")]
+pub mod mock;
 
 pub mod _tokio_codec;
 mod allow_std;
+mod async_buf_read;
 mod async_read;
+mod async_seek;
 mod async_write;
 mod framed;
 mod framed_read;
@@ -60,8 +71,11 @@ mod lines;
 mod split;
 mod window;
 
+pub use self::async_buf_read::AsyncBufRead;
 pub use self::async_read::AsyncRead;
+pub use self::async_seek::AsyncSeek;
 pub use self::async_write::AsyncWrite;
+pub use iovec::IoVec;
 
 fn _assert_objects() {
     fn _assert<T>() {}
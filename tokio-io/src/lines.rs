@@ -10,7 +10,10 @@ use AsyncRead;
 #[derive(Debug)]
 pub struct Lines<A> {
     io: A,
-    line: String,
+
+    // The bytes of the current line, which may end in the middle of a
+    // multi-byte character until the line is complete.
+    line: Vec<u8>,
 }
 
 /// Creates a new stream from the I/O object given representing the lines of
@@ -19,13 +22,16 @@ pub struct Lines<A> {
 /// This method takes an asynchronous I/O object, `a`, and returns a `Stream` of
 /// lines that the object contains. The returned stream will reach its end once
 /// `a` reaches EOF.
+///
+/// Each line is yielded without its trailing `\n` or `\r\n`. An error of kind
+/// `InvalidData` is yielded for a line which is not valid UTF-8.
 pub fn lines<A>(a: A) -> Lines<A>
 where
     A: AsyncRead + BufRead,
 {
     Lines {
         io: a,
-        line: String::new(),
+        line: Vec::new(),
     }
 }
 
@@ -47,16 +53,22 @@ where
     type Error = io::Error;
 
     fn poll(&mut self) -> Poll<Option<String>, io::Error> {
-        let n = try_nb!(self.io.read_line(&mut self.line));
+        let n = try_nb!(self.io.read_until(b'\n', &mut self.line));
         if n == 0 && self.line.len() == 0 {
             return Ok(None.into());
         }
-        if self.line.ends_with("\n") {
+        if self.line.ends_with(b"\n") {
             self.line.pop();
-            if self.line.ends_with("\r") {
+            if self.line.ends_with(b"\r") {
                 self.line.pop();
             }
         }
-        Ok(Some(mem::replace(&mut self.line, String::new())).into())
+        match String::from_utf8(mem::replace(&mut self.line, Vec::new())) {
+            Ok(line) => Ok(Some(line).into()),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+        }
     }
 }
@@ -0,0 +1,267 @@
+//! A mock I/O object for testing.
+//!
+//! The [`Mock`] type implements `AsyncRead` and `AsyncWrite` by following a
+//! script of steps, set up with a [`Builder`]. This allows testing codecs and
+//! protocol implementations without any actual I/O, including the cases which
+//! are hard to trigger with real sockets, such as partial reads, `WouldBlock`
+//! and errors.
+//!
+//! This module is only available with the `mock` feature enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate futures;
+//! # extern crate tokio_io;
+//! use futures::Future;
+//! use tokio_io::io::{read_exact, write_all};
+//! use tokio_io::mock::Builder;
+//!
+//! # fn main() {
+//! let mock = Builder::new()
+//!     .read(b"ping")
+//!     .wait()
+//!     .write(b"pong")
+//!     .build();
+//!
+//! let (mock, buf) = read_exact(mock, [0; 4]).wait().unwrap();
+//! assert_eq!(&buf, b"ping");
+//!
+//! write_all(mock, b"pong").wait().unwrap();
+//! # }
+//! ```
+//!
+//! [`Mock`]: struct.Mock.html
+//! [`Builder`]: struct.Builder.html
+
+use {AsyncRead, AsyncWrite};
+
+use futures::task::{self, Task};
+use futures::{Async, Poll};
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::{cmp, mem, thread};
+
+/// Builds a [`Mock`] from a script of steps.
+///
+/// Steps are performed in the order they are added. Reads and writes are
+/// checked against the script as they happen.
+///
+/// [`Mock`]: struct.Mock.html
+#[derive(Debug, Default)]
+pub struct Builder {
+    actions: VecDeque<Action>,
+}
+
+/// An I/O object following a script of reads, writes, waits and errors.
+///
+/// Reading yields the data of the next read steps, possibly split over
+/// several calls, and returns EOF once the script is over. Writing checks the
+/// data against the next write steps, and panics if the data differs or if
+/// the script has no write step left.
+///
+/// A read reaching a write step of the script, or a write reaching a read
+/// step, returns `WouldBlock` until the other direction has made progress.
+/// Likewise, a wait step makes the next read or write return `WouldBlock`
+/// once, and notifies the current task, if any, so that it is polled again.
+///
+/// Dropping a `Mock` panics if the script still holds data to read or to
+/// write, unless the thread is already panicking.
+///
+/// `Mock` values are created by [`Builder::build`].
+///
+/// [`Builder::build`]: struct.Builder.html#method.build
+#[derive(Debug)]
+pub struct Mock {
+    actions: VecDeque<Action>,
+
+    // The task blocked on a step of the other direction.
+    blocked: Option<Task>,
+}
+
+#[derive(Debug)]
+enum Action {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    Wait,
+    ReadError(Option<io::Error>),
+    WriteError(Option<io::Error>),
+}
+
+impl Builder {
+    /// Returns a new builder with an empty script.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Adds a step yielding `buf` to reads.
+    ///
+    /// The data may be read by one or more calls to `read`. It is never
+    /// merged with the data of a following read step.
+    pub fn read(&mut self, buf: &[u8]) -> &mut Self {
+        if !buf.is_empty() {
+            self.actions.push_back(Action::Read(buf.into()));
+        }
+        self
+    }
+
+    /// Adds a step expecting `buf` to be written.
+    ///
+    /// The data may be written by one or more calls to `write`.
+    pub fn write(&mut self, buf: &[u8]) -> &mut Self {
+        if !buf.is_empty() {
+            self.actions.push_back(Action::Write(buf.into()));
+        }
+        self
+    }
+
+    /// Adds a step making the next read or write return `WouldBlock`.
+    pub fn wait(&mut self) -> &mut Self {
+        self.actions.push_back(Action::Wait);
+        self
+    }
+
+    /// Adds a step making the next read fail with `error`.
+    pub fn read_error(&mut self, error: io::Error) -> &mut Self {
+        self.actions.push_back(Action::ReadError(Some(error)));
+        self
+    }
+
+    /// Adds a step making the next write fail with `error`.
+    pub fn write_error(&mut self, error: io::Error) -> &mut Self {
+        self.actions.push_back(Action::WriteError(Some(error)));
+        self
+    }
+
+    /// Creates a `Mock` following the script.
+    ///
+    /// The script is moved to the `Mock`, leaving the builder empty.
+    pub fn build(&mut self) -> Mock {
+        Mock {
+            actions: mem::replace(&mut self.actions, VecDeque::new()),
+            blocked: None,
+        }
+    }
+}
+
+impl Mock {
+    /// Moves on to the next step of the script.
+    fn next_action(&mut self) {
+        self.actions.pop_front();
+
+        if let Some(task) = self.blocked.take() {
+            task.notify();
+        }
+    }
+
+    /// Returns `WouldBlock`, waking up the task once the script moves on.
+    fn block(&mut self) -> io::Error {
+        if task::is_in_task() {
+            self.blocked = Some(task::current());
+        }
+
+        io::ErrorKind::WouldBlock.into()
+    }
+
+    /// Returns `WouldBlock`, waking up the task right away.
+    fn wait(&mut self) -> io::Error {
+        self.next_action();
+
+        if task::is_in_task() {
+            task::current().notify();
+        }
+
+        io::ErrorKind::WouldBlock.into()
+    }
+}
+
+impl Read for Mock {
+    fn read(&mut self, dst: &mut [u8]) -> io::Result<usize> {
+        let n = match self.actions.front_mut() {
+            Some(&mut Action::Read(ref mut data)) => {
+                let n = cmp::min(dst.len(), data.len());
+                dst[..n].copy_from_slice(&data[..n]);
+                data.drain(..n);
+                n
+            }
+            Some(&mut Action::ReadError(ref mut error)) => {
+                let error = error.take().unwrap();
+                self.next_action();
+                return Err(error);
+            }
+            Some(&mut Action::Wait) => return Err(self.wait()),
+            Some(_) => return Err(self.block()),
+            None => return Ok(0),
+        };
+
+        if let Some(&Action::Read(ref data)) = self.actions.front() {
+            if !data.is_empty() {
+                return Ok(n);
+            }
+        }
+
+        self.next_action();
+        Ok(n)
+    }
+}
+
+impl AsyncRead for Mock {}
+
+impl Write for Mock {
+    fn write(&mut self, src: &[u8]) -> io::Result<usize> {
+        let n = match self.actions.front_mut() {
+            Some(&mut Action::Write(ref mut expected)) => {
+                let n = cmp::min(src.len(), expected.len());
+                assert_eq!(&src[..n], &expected[..n], "mismatched write");
+                expected.drain(..n);
+                n
+            }
+            Some(&mut Action::WriteError(ref mut error)) => {
+                let error = error.take().unwrap();
+                self.next_action();
+                return Err(error);
+            }
+            Some(&mut Action::Wait) => return Err(self.wait()),
+            Some(_) => return Err(self.block()),
+            None => panic!("unexpected write of {} bytes", src.len()),
+        };
+
+        if let Some(&Action::Write(ref expected)) = self.actions.front() {
+            if !expected.is_empty() {
+                return Ok(n);
+            }
+        }
+
+        self.next_action();
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+
+        for action in &self.actions {
+            match *action {
+                Action::Read(_) => panic!("there is still data left to read"),
+                Action::Write(_) => panic!("there is still data left to write"),
+                _ => {}
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::io::{self, Read, Write};
 use bytes::{Buf, BufMut};
 use futures::sync::BiLock;
 use futures::{Async, Poll};
+use iovec::IoVec;
 
 use {AsyncRead, AsyncWrite};
 
@@ -69,6 +70,11 @@ impl<T: AsyncRead> Read for ReadHalf<T> {
 }
 
 impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        let mut l = try_ready!(wrap_as_io(self.handle.poll_lock()));
+        l.poll_read_vectored(bufs)
+    }
+
     fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
         let mut l = try_ready!(wrap_as_io(self.handle.poll_lock()));
         l.read_buf(buf)
@@ -92,6 +98,11 @@ impl<T: AsyncWrite> Write for WriteHalf<T> {
 }
 
 impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        let mut l = try_ready!(wrap_as_io(self.handle.poll_lock()));
+        l.poll_write_vectored(bufs)
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         let mut l = try_ready!(wrap_as_io(self.handle.poll_lock()));
         l.shutdown()
@@ -147,3 +147,140 @@ fn read_buf_translate_wouldblock_to_not_ready() {
     let mut buf = BytesMut::with_capacity(65);
     assert!(!R.read_buf(&mut buf).unwrap().is_ready());
 }
+
+#[test]
+fn poll_read_vectored_default() {
+    use tokio_io::IoVec;
+
+    let mut r = io::Cursor::new(b"hello world".to_vec());
+
+    let mut a = [0; 6];
+    let mut b = [0; 5];
+
+    let n = {
+        let mut bufs: [&mut IoVec; 2] = [(&mut a[..]).into(), (&mut b[..]).into()];
+
+        match r.poll_read_vectored(&mut bufs).unwrap() {
+            Async::Ready(n) => n,
+            _ => panic!(),
+        }
+    };
+
+    // Only the first buffer is filled
+    assert_eq!(6, n);
+    assert_eq!(&a, b"hello ");
+}
+
+#[test]
+fn take_copy_then_continue() {
+    use futures::Future;
+    use tokio_io::io::{copy, read_exact, read_to_end, take};
+
+    let reader: &[u8] = b"\x05helloworld";
+    let (reader, len) = read_exact(reader, [0; 1]).wait().unwrap();
+
+    let out = io::Cursor::new(vec![]);
+    let (n, payload, out) = copy(take(reader, len[0] as u64), out).wait().unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(out.into_inner(), b"hello");
+    assert_eq!(payload.limit(), 0);
+
+    let (_, rest) = read_to_end(payload.into_inner(), vec![]).wait().unwrap();
+    assert_eq!(rest, b"world");
+}
+
+#[test]
+fn chain_not_ready() {
+    use tokio_io::io::chain;
+
+    struct R {
+        blocked: bool,
+    }
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if !self.blocked {
+                self.blocked = true;
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+
+            buf[0..3].copy_from_slice(b"abc");
+            Ok(3)
+        }
+    }
+
+    impl AsyncRead for R {}
+
+    let first: &[u8] = b"xy";
+    let mut rd = chain(first, R { blocked: false });
+    let mut buf = [0; 8];
+
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(2));
+    assert_eq!(&buf[..2], b"xy");
+
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::NotReady);
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(3));
+    assert_eq!(&buf[..3], b"abc");
+}
+
+#[test]
+fn read_to_end_limit() {
+    use futures::Future;
+    use tokio_io::io::{read_to_end, LimitExceeded};
+
+    /// Reader returning one byte at a time, with a `WouldBlock` error between
+    /// each.
+    struct R {
+        data: &'static [u8],
+        blocked: bool,
+    }
+
+    impl Read for R {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.blocked = !self.blocked;
+
+            if self.blocked {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+
+            let n = self.data.len().min(1);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    impl AsyncRead for R {}
+
+    let rd = R {
+        data: b"hello",
+        blocked: false,
+    };
+    let mut fut = read_to_end(rd, b"> ".to_vec()).limit(5);
+
+    let buf = loop {
+        match fut.poll().unwrap() {
+            Async::Ready((_, buf)) => break buf,
+            Async::NotReady => {}
+        }
+    };
+    assert_eq!(buf, b"> hello");
+
+    let rd = R {
+        data: b"hello world",
+        blocked: false,
+    };
+    let mut fut = read_to_end(rd, vec![]).limit(5);
+
+    let err = loop {
+        match fut.poll() {
+            Ok(Async::Ready(_)) => panic!("limit not enforced"),
+            Ok(Async::NotReady) => {}
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let err = err.get_ref().unwrap();
+    assert_eq!(err.downcast_ref::<LimitExceeded>().unwrap().limit(), 5);
+}
@@ -0,0 +1,134 @@
+extern crate bytes;
+extern crate futures;
+extern crate tokio_io;
+
+use bytes::{Buf, IntoBuf};
+use futures::{Async, Poll};
+use tokio_io::{AsyncWrite, IoVec};
+
+use std::io::{self, Write};
+
+#[test]
+fn write_buf_vectored() {
+    struct W {
+        data: Vec<u8>,
+        calls: usize,
+    }
+
+    impl Write for W {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            unimplemented!();
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl AsyncWrite for W {
+        fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+            self.calls += 1;
+
+            let mut n = 0;
+
+            for buf in bufs {
+                self.data.extend_from_slice(buf);
+                n += buf.len();
+            }
+
+            Ok(Async::Ready(n))
+        }
+
+        fn shutdown(&mut self) -> Poll<(), io::Error> {
+            Ok(().into())
+        }
+    }
+
+    let mut w = W {
+        data: vec![],
+        calls: 0,
+    };
+    let mut buf = (&b"hello "[..]).into_buf().chain(&b"world"[..]);
+
+    let n = match w.write_buf(&mut buf).unwrap() {
+        Async::Ready(n) => n,
+        _ => panic!(),
+    };
+
+    assert_eq!(11, n);
+    assert!(!buf.has_remaining());
+    assert_eq!(w.data, b"hello world");
+    assert_eq!(w.calls, 1);
+}
+
+#[test]
+fn poll_write_vectored_default() {
+    let mut w = io::Cursor::new(vec![]);
+
+    let bufs: [&IoVec; 2] = [b"hello "[..].into(), b"world"[..].into()];
+
+    // Only the first buffer is written
+    match w.poll_write_vectored(&bufs).unwrap() {
+        Async::Ready(6) => {}
+        _ => panic!(),
+    }
+
+    assert_eq!(w.get_ref(), b"hello ");
+}
+
+/// Writer recording flushes and shutdowns, which only implements the
+/// required `AsyncWrite` methods and `poll_flush`.
+#[derive(Default)]
+struct Recorder {
+    flushes: usize,
+    shutdowns: usize,
+}
+
+impl Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        panic!("flush called instead of poll_flush");
+    }
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_flush(&mut self) -> Poll<(), io::Error> {
+        self.flushes += 1;
+        Ok(().into())
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.shutdowns += 1;
+        Ok(().into())
+    }
+}
+
+#[test]
+fn poll_shutdown_calls_shutdown() {
+    let mut w = Recorder::default();
+
+    assert!(w.poll_shutdown().unwrap().is_ready());
+    assert_eq!(w.shutdowns, 1);
+    assert_eq!(w.flushes, 0);
+}
+
+#[test]
+fn wrappers_forward_flush_and_shutdown() {
+    let mut w: Box<AsyncWrite> = Box::new(Recorder::default());
+    assert!(w.poll_flush().unwrap().is_ready());
+    assert!(w.poll_shutdown().unwrap().is_ready());
+
+    let mut w = tokio_io::io::BufWriter::new(Recorder::default());
+    w.write_all(b"hello").unwrap();
+
+    assert!(w.poll_flush().unwrap().is_ready());
+    assert_eq!(w.get_ref().flushes, 1);
+    assert_eq!(w.get_ref().shutdowns, 0);
+
+    assert!(w.poll_shutdown().unwrap().is_ready());
+    assert_eq!(w.get_ref().flushes, 1);
+    assert_eq!(w.get_ref().shutdowns, 1);
+}
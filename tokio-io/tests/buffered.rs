@@ -0,0 +1,288 @@
+extern crate futures;
+extern crate tokio_io;
+
+use futures::{Async, Future, Poll, Stream};
+use tokio_io::io::{chain, lines, read_line, read_until, take, BufReader, BufWriter};
+use tokio_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+use std::io::{self, BufRead, Read, Write};
+
+/// Reader returning its chunks one at a time, with a `WouldBlock` error
+/// between each.
+struct R {
+    chunks: Vec<&'static [u8]>,
+    blocked: bool,
+    reads: usize,
+}
+
+impl Read for R {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reads += 1;
+
+        if !self.blocked {
+            self.blocked = true;
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        self.blocked = false;
+
+        if self.chunks.is_empty() {
+            return Ok(0);
+        }
+
+        let chunk = self.chunks.remove(0);
+        buf[..chunk.len()].copy_from_slice(chunk);
+        Ok(chunk.len())
+    }
+}
+
+impl AsyncRead for R {}
+
+/// Writer accepting `limit` bytes before returning `WouldBlock`.
+struct W {
+    data: Vec<u8>,
+    limit: usize,
+    writes: usize,
+    shutdown: bool,
+}
+
+impl Write for W {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+
+        if self.limit == 0 {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+
+        let n = buf.len().min(self.limit);
+        self.data.extend_from_slice(&buf[..n]);
+        self.limit -= n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for W {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.shutdown = true;
+        Ok(().into())
+    }
+}
+
+#[test]
+fn buf_reader_serves_small_reads_from_buffer() {
+    let mut rd = BufReader::with_capacity(
+        16,
+        R {
+            chunks: vec![b"hello world", b"!"],
+            blocked: true,
+            reads: 0,
+        },
+    );
+
+    let mut buf = [0; 5];
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(5));
+    assert_eq!(&buf, b"hello");
+    assert_eq!(rd.buffer(), b" world");
+
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(5));
+    assert_eq!(&buf, b" worl");
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(1));
+    assert_eq!(rd.get_ref().reads, 1);
+
+    assert!(rd.poll_read(&mut buf).unwrap().is_not_ready());
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(1));
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::NotReady);
+    assert_eq!(rd.poll_read(&mut buf).unwrap(), Async::Ready(0));
+}
+
+#[test]
+fn buf_reader_fill_buf_and_consume() {
+    let mut rd = BufReader::new(R {
+        chunks: vec![b"one\ntwo\n"],
+        blocked: false,
+        reads: 0,
+    });
+
+    let err = rd.fill_buf().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    assert_eq!(rd.fill_buf().unwrap(), b"one\ntwo\n");
+    rd.consume(4);
+    assert_eq!(rd.fill_buf().unwrap(), b"two\n");
+
+    let (rd, line) = read_until(rd, b'\n', vec![]).wait().unwrap();
+    assert_eq!(line, b"two\n");
+    assert!(rd.buffer().is_empty());
+}
+
+#[test]
+fn buf_reader_poll_fill_buf() {
+    let mut rd = BufReader::new(R {
+        chunks: vec![b"hello"],
+        blocked: false,
+        reads: 0,
+    });
+
+    assert!(rd.poll_fill_buf().unwrap().is_not_ready());
+    assert_eq!(rd.poll_fill_buf().unwrap(), Async::Ready(&b"hello"[..]));
+    rd.consume(5);
+    assert!(rd.poll_fill_buf().unwrap().is_not_ready());
+    assert_eq!(rd.poll_fill_buf().unwrap(), Async::Ready(&b""[..]));
+}
+
+#[test]
+fn read_line_across_chunks() {
+    // The first chunk ends in the middle of a multi-byte character
+    let rd = BufReader::new(R {
+        chunks: vec![b"caf\xc3", b"\xa9\r\nnext"],
+        blocked: true,
+        reads: 0,
+    });
+
+    let mut fut = read_line(rd, "line: ".to_string());
+    assert!(fut.poll().unwrap().is_not_ready());
+
+    let (rd, line) = match fut.poll().unwrap() {
+        Async::Ready(res) => res,
+        Async::NotReady => panic!("line not read"),
+    };
+    assert_eq!(line, "line: caf\u{e9}\r\n");
+
+    // The rest of the stream is read as lines
+    let mut lines = lines(rd);
+    assert!(lines.poll().unwrap().is_not_ready());
+    assert_eq!(
+        lines.poll().unwrap(),
+        Async::Ready(Some("next".to_string()))
+    );
+    assert!(lines.poll().unwrap().is_not_ready());
+    assert_eq!(lines.poll().unwrap(), Async::Ready(None));
+}
+
+#[test]
+fn lines_across_chunks() {
+    let rd = BufReader::new(R {
+        chunks: vec![b"one\r\ntw", b"\xc3", b"\xa9\nthree\n", b"\xff\n"],
+        blocked: true,
+        reads: 0,
+    });
+
+    let mut lines = lines(rd);
+    assert_eq!(lines.poll().unwrap(), Async::Ready(Some("one".to_string())));
+    assert!(lines.poll().unwrap().is_not_ready());
+    assert!(lines.poll().unwrap().is_not_ready());
+    assert_eq!(
+        lines.poll().unwrap(),
+        Async::Ready(Some("tw\u{e9}".to_string()))
+    );
+    assert_eq!(
+        lines.poll().unwrap(),
+        Async::Ready(Some("three".to_string()))
+    );
+    assert!(lines.poll().unwrap().is_not_ready());
+
+    let err = lines.poll().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+/// Buffered reader that is only readable through `poll_fill_buf`.
+struct P {
+    data: &'static [u8],
+}
+
+impl Read for P {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        panic!("read called");
+    }
+}
+
+impl BufRead for P {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        panic!("fill_buf called");
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.data = &self.data[amt..];
+    }
+}
+
+impl AsyncRead for P {}
+
+impl AsyncBufRead for P {
+    fn poll_fill_buf(&mut self) -> Poll<&[u8], io::Error> {
+        Ok(Async::Ready(self.data))
+    }
+}
+
+#[test]
+fn poll_fill_buf_override_is_used() {
+    let (rd, line) = read_line(P { data: b"one\ntwo" }, String::new())
+        .wait()
+        .unwrap();
+    assert_eq!(line, "one\n");
+    assert_eq!(rd.data, b"two");
+
+    // The adapters forward to the override of the reader they wrap
+    let rd = chain(take(P { data: b"one\ntwo" }, 2), P { data: b"x\n" });
+    let (_, line) = read_line(Box::new(rd), String::new()).wait().unwrap();
+    assert_eq!(line, "onx\n");
+}
+
+#[test]
+fn buf_writer_buffers_small_writes() {
+    let mut wr = BufWriter::with_capacity(
+        8,
+        W {
+            data: vec![],
+            limit: 100,
+            writes: 0,
+            shutdown: false,
+        },
+    );
+
+    assert_eq!(wr.poll_write(b"abc").unwrap(), Async::Ready(3));
+    assert_eq!(wr.poll_write(b"def").unwrap(), Async::Ready(3));
+    assert_eq!(wr.get_ref().writes, 0);
+    assert_eq!(wr.buffer(), b"abcdef");
+
+    // Does not fit, the buffer is written out first
+    assert_eq!(wr.poll_write(b"ghi").unwrap(), Async::Ready(3));
+    assert_eq!(wr.get_ref().data, b"abcdef");
+    assert_eq!(wr.buffer(), b"ghi");
+
+    // Large writes bypass the buffer
+    assert_eq!(wr.poll_write(b"0123456789").unwrap(), Async::Ready(10));
+    assert_eq!(wr.get_ref().data, b"abcdefghi0123456789");
+    assert!(wr.buffer().is_empty());
+}
+
+#[test]
+fn buf_writer_flush_and_shutdown() {
+    let mut wr = BufWriter::new(W {
+        data: vec![],
+        limit: 4,
+        writes: 0,
+        shutdown: false,
+    });
+
+    assert_eq!(wr.poll_write(b"hello world").unwrap(), Async::Ready(11));
+
+    // The inner writer only takes part of the buffer
+    assert!(wr.poll_flush().unwrap().is_not_ready());
+    assert_eq!(wr.get_ref().data, b"hell");
+    assert_eq!(wr.buffer(), b"o world");
+
+    wr.get_mut().limit = 3;
+    assert!(wr.shutdown().unwrap().is_not_ready());
+    assert!(!wr.get_ref().shutdown);
+
+    wr.get_mut().limit = 100;
+    assert!(wr.shutdown().unwrap().is_ready());
+    assert!(wr.get_ref().shutdown);
+    assert_eq!(wr.get_ref().data, b"hello world");
+    assert!(wr.buffer().is_empty());
+}
@@ -0,0 +1,164 @@
+#![cfg(any(feature = "gzip", feature = "deflate", feature = "zstd"))]
+
+extern crate futures;
+extern crate tokio_io;
+
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use std::io::{self, Cursor, Read, Write};
+
+/// Transfers at most 7 bytes at a time, and returns `WouldBlock` every other
+/// read or write.
+#[derive(Debug)]
+struct Flaky {
+    inner: Cursor<Vec<u8>>,
+    block: bool,
+}
+
+impl Flaky {
+    fn new(data: Vec<u8>) -> Flaky {
+        Flaky {
+            inner: Cursor::new(data),
+            block: false,
+        }
+    }
+
+    fn would_block(&mut self) -> io::Result<()> {
+        self.block = !self.block;
+        if self.block {
+            Err(io::ErrorKind::WouldBlock.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Read for Flaky {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.would_block()?;
+        let n = buf.len().min(7);
+        self.inner.read(&mut buf[..n])
+    }
+}
+
+impl AsyncRead for Flaky {}
+
+impl Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.would_block()?;
+        let n = buf.len().min(7);
+        self.inner.write(&buf[..n])
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Flaky {
+    fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
+        Ok(().into())
+    }
+}
+
+fn retry<T, F: FnMut() -> io::Result<T>>(mut f: F) -> T {
+    loop {
+        match f() {
+            Ok(t) => return t,
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+fn data() -> Vec<u8> {
+    (0..10_000u32)
+        .flat_map(|i| (i % 251).to_string().into_bytes())
+        .collect()
+}
+
+fn compress<W: AsyncWrite>(mut wr: W) -> W {
+    let data = data();
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let end = (pos + 1000).min(data.len());
+        pos += retry(|| wr.write(&data[pos..end]));
+    }
+
+    while wr.shutdown().unwrap().is_not_ready() {}
+    wr
+}
+
+fn decompress<R: AsyncRead>(mut rd: R) {
+    let mut out = vec![];
+    let mut buf = [0; 256];
+
+    loop {
+        match retry(|| rd.read(&mut buf)) {
+            0 => break,
+            n => out.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    assert_eq!(out, data());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_round_trip() {
+    use tokio_io::io::{GzipDecoder, GzipEncoder};
+
+    let wr = compress(GzipEncoder::new(Flaky::new(vec![])));
+    let compressed = wr.get_ref().inner.get_ref().clone();
+    assert!(compressed.len() < data().len() / 2);
+    assert_eq!(&compressed[..2], b"\x1f\x8b");
+
+    decompress(GzipDecoder::new(Flaky::new(compressed)));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_flush() {
+    use tokio_io::io::{GzipDecoder, GzipEncoder};
+
+    let mut wr = GzipEncoder::new(Flaky::new(vec![]));
+    retry(|| wr.write(b"hello"));
+    retry(|| wr.flush());
+
+    // Everything written so far may be decompressed before the end of the
+    // stream
+    let compressed = wr.get_ref().inner.get_ref().clone();
+    let mut rd = GzipDecoder::new(Flaky::new(compressed));
+    let mut buf = [0; 5];
+    let mut pos = 0;
+    while pos < 5 {
+        pos += retry(|| rd.read(&mut buf[pos..]));
+    }
+    assert_eq!(&buf, b"hello");
+}
+
+#[cfg(feature = "deflate")]
+#[test]
+fn deflate_round_trip() {
+    use tokio_io::io::{DeflateDecoder, DeflateEncoder};
+
+    let wr = compress(DeflateEncoder::with_level(Flaky::new(vec![]), 9));
+    let compressed = wr.get_ref().inner.get_ref().clone();
+    assert!(compressed.len() < data().len() / 2);
+
+    decompress(DeflateDecoder::new(Flaky::new(compressed)));
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_round_trip() {
+    use tokio_io::io::{ZstdDecoder, ZstdEncoder};
+
+    let wr = compress(ZstdEncoder::new(Flaky::new(vec![])).unwrap());
+    let compressed = wr.get_ref().inner.get_ref().clone();
+    assert!(compressed.len() < data().len() / 2);
+    assert_eq!(&compressed[..4], b"\x28\xb5\x2f\xfd");
+
+    decompress(ZstdDecoder::new(Flaky::new(compressed)).unwrap());
+}
@@ -0,0 +1,46 @@
+extern crate futures;
+extern crate tokio_io;
+
+use futures::Future;
+use tokio_io::io::copy_buf;
+use tokio_io::AsyncRead;
+
+use std::io::{self, Cursor, Read};
+
+/// Reader recording the size of the buffers it is given.
+struct R {
+    data: Cursor<Vec<u8>>,
+    reads: Vec<usize>,
+}
+
+impl Read for R {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reads.push(buf.len());
+        self.data.read(buf)
+    }
+}
+
+impl AsyncRead for R {}
+
+#[test]
+fn copy_buf_uses_given_buffer() {
+    let reader = R {
+        data: Cursor::new(vec![7; 20_000]),
+        reads: vec![],
+    };
+
+    let (n, reader, writer, buf) = copy_buf(reader, Cursor::new(vec![]), vec![0; 8192])
+        .wait()
+        .unwrap();
+
+    assert_eq!(n, 20_000);
+    assert_eq!(writer.into_inner(), vec![7; 20_000]);
+    assert_eq!(reader.reads, [8192, 8192, 8192, 8192]);
+    assert_eq!(buf.len(), 8192);
+}
+
+#[test]
+#[should_panic]
+fn copy_buf_empty_buffer() {
+    let _ = copy_buf(Cursor::new(vec![1]), Cursor::new(vec![]), vec![]);
+}
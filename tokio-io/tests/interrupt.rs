@@ -0,0 +1,115 @@
+extern crate futures;
+extern crate tokio_io;
+
+use futures::Future;
+use tokio_io::io::{read_exact, write_all, Window};
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use std::io::{self, Read, Write};
+
+/// Transfers the given chunks, one per call, and would block once there are
+/// none left.
+#[derive(Debug)]
+struct Chunked {
+    chunks: Vec<Vec<u8>>,
+    written: Vec<u8>,
+}
+
+impl Chunked {
+    fn new(chunks: &[&[u8]]) -> Chunked {
+        Chunked {
+            chunks: chunks.iter().rev().map(|c| c.to_vec()).collect(),
+            written: vec![],
+        }
+    }
+}
+
+impl Read for Chunked {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.chunks.pop() {
+            Some(chunk) => {
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+            None => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl AsyncRead for Chunked {}
+
+impl Write for Chunked {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.chunks.pop() {
+            Some(chunk) => {
+                let n = chunk.len();
+                self.written.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+            None => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for Chunked {
+    fn shutdown(&mut self) -> futures::Poll<(), io::Error> {
+        Ok(().into())
+    }
+}
+
+#[test]
+fn read_exact_resume() {
+    let mut read = read_exact(Chunked::new(&[b"ab", b"c"]), vec![0; 5]);
+    assert!(read.poll().unwrap().is_not_ready());
+    assert_eq!(read.position(), 3);
+
+    let (mut rd, buf, pos) = read.into_inner();
+    assert_eq!(&buf[..pos], b"abc");
+
+    rd.chunks.push(b"de".to_vec());
+    let mut buf = Window::new(buf);
+    buf.set_start(pos);
+
+    let (_, buf) = read_exact(rd, buf).wait().unwrap();
+    assert_eq!(buf.into_inner(), b"abcde");
+}
+
+#[test]
+fn read_exact_progress_on_error() {
+    let mut read = read_exact(Chunked::new(&[b"ab", b""]), vec![0; 5]);
+    assert_eq!(
+        read.poll().unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+
+    let (_, buf, pos) = read.into_inner();
+    assert_eq!(&buf[..pos], b"ab");
+}
+
+#[test]
+fn write_all_resume() {
+    let mut write = write_all(Chunked::new(&[b"..", b"."]), b"abcde".to_vec());
+    assert!(write.poll().unwrap().is_not_ready());
+    assert_eq!(write.position(), 3);
+    assert_eq!(write.get_ref().written, b"abc");
+
+    let (mut wr, buf, pos) = write.into_inner();
+    wr.chunks.push(b"..".to_vec());
+    let mut buf = Window::new(buf);
+    buf.set_start(pos);
+
+    let (wr, _) = write_all(wr, buf).wait().unwrap();
+    assert_eq!(wr.written, b"abcde");
+}
+
+#[test]
+#[should_panic]
+fn into_inner_after_completion() {
+    let mut write = write_all(Chunked::new(&[b"."]), b"a".to_vec());
+    assert!(write.poll().unwrap().is_ready());
+    write.into_inner();
+}
@@ -0,0 +1,117 @@
+extern crate futures;
+extern crate tokio_io;
+
+use tokio_io::io::{read_to_end, write_all};
+use tokio_io::mock::Builder;
+
+use futures::Future;
+
+use std::io::{self, Read, Write};
+
+#[test]
+fn read() {
+    let mut mock = Builder::new().read(b"hello").read(b" world").build();
+    let mut buf = [0; 3];
+
+    assert_eq!(mock.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"hel");
+
+    // Steps are not merged
+    assert_eq!(mock.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"lo");
+    assert_eq!(mock.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b" wo");
+    assert_eq!(mock.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"rld");
+
+    // EOF
+    assert_eq!(mock.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn read_wait_and_error() {
+    let mut mock = Builder::new()
+        .read(b"hello")
+        .wait()
+        .read_error(io::Error::new(io::ErrorKind::Other, "boom"))
+        .read(b"world")
+        .build();
+    let mut buf = [0; 10];
+
+    assert_eq!(mock.read(&mut buf).unwrap(), 5);
+
+    let err = mock.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    let err = mock.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    assert_eq!(mock.read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"world");
+}
+
+#[test]
+fn read_in_task() {
+    let mock = Builder::new().read(b"hello").wait().read(b" world").build();
+
+    let (_, buf) = read_to_end(mock, vec![]).wait().unwrap();
+
+    assert_eq!(buf, b"hello world");
+}
+
+#[test]
+fn write() {
+    let mut mock = Builder::new()
+        .write(b"hello")
+        .wait()
+        .write_error(io::Error::new(io::ErrorKind::Other, "boom"))
+        .write(b" world")
+        .build();
+
+    assert_eq!(mock.write(b"hel").unwrap(), 3);
+    assert_eq!(mock.write(b"lo wor").unwrap(), 2);
+
+    let err = mock.write(b" world").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    let err = mock.write(b" world").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+
+    assert_eq!(mock.write(b" world").unwrap(), 6);
+}
+
+#[test]
+#[should_panic(expected = "mismatched write")]
+fn write_mismatch() {
+    let mut mock = Builder::new().write(b"hello").build();
+    let _ = mock.write(b"world");
+}
+
+#[test]
+#[should_panic(expected = "unexpected write")]
+fn write_unexpected() {
+    let mut mock = Builder::new().build();
+    let _ = mock.write(b"hello");
+}
+
+#[test]
+#[should_panic(expected = "still data left to write")]
+fn write_missing() {
+    let mut mock = Builder::new().write(b"hello").build();
+    mock.write(b"hel").unwrap();
+}
+
+#[test]
+fn read_then_write() {
+    let mut mock = Builder::new().write(b"ping").read(b"pong").build();
+    let mut buf = [0; 4];
+
+    // Reading blocks until the write step is done
+    let err = mock.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    let (mut mock, _) = write_all(mock, b"ping").wait().unwrap();
+
+    assert_eq!(mock.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"pong");
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "throttle")]
+
+extern crate futures;
+extern crate tokio_io;
+extern crate tokio_timer;
+
+use tokio_io::io::{RateLimit, Throttle};
+use tokio_io::AsyncRead;
+use tokio_timer::clock::MockClock;
+
+use std::io::{self, Cursor, Read, Write};
+use std::time::Duration;
+
+#[test]
+fn unlimited() {
+    let mut rd = Throttle::new(Cursor::new(vec![1; 100]));
+    let mut buf = [0; 100];
+    assert_eq!(rd.read(&mut buf).unwrap(), 100);
+}
+
+#[test]
+fn read_limit() {
+    let mock = MockClock::new();
+
+    mock.enter(|| {
+        let mut rd = Throttle::new(Cursor::new(vec![1; 1000]));
+        rd.set_read_limit(Some(RateLimit::new(100, 50)));
+        let mut buf = [0; 100];
+
+        // The bucket starts out full
+        assert_eq!(rd.read(&mut buf).unwrap(), 50);
+        assert!(rd.poll_read(&mut buf).unwrap().is_not_ready());
+
+        mock.advance(Duration::from_millis(100));
+        assert_eq!(rd.read(&mut buf).unwrap(), 10);
+        assert!(rd.poll_read(&mut buf).unwrap().is_not_ready());
+
+        // No more than a burst is saved up
+        mock.advance(Duration::from_secs(10));
+        assert_eq!(rd.read(&mut buf).unwrap(), 50);
+
+        rd.set_read_limit(None);
+        assert_eq!(rd.read(&mut buf).unwrap(), 100);
+    });
+}
+
+#[test]
+fn write_limit() {
+    let mock = MockClock::new();
+
+    mock.enter(|| {
+        let mut wr = Throttle::new(Vec::new());
+        wr.set_write_limit(Some(RateLimit::new(1000, 10)));
+
+        assert_eq!(wr.write(&[1; 20]).unwrap(), 10);
+        assert_eq!(
+            wr.write(&[1; 20]).unwrap_err().kind(),
+            io::ErrorKind::WouldBlock
+        );
+
+        mock.advance(Duration::from_millis(5));
+        assert_eq!(wr.write(&[1; 20]).unwrap(), 5);
+        assert_eq!(wr.get_ref().len(), 15);
+
+        // Reads are not limited
+        assert!(wr.read_limit().is_none());
+    });
+}
+
+#[test]
+#[should_panic]
+fn zero_rate() {
+    RateLimit::new(0, 1);
+}
@@ -0,0 +1,71 @@
+#![cfg(windows)]
+#![doc(html_root_url = "https://docs.rs/tokio-named-pipes/0.1.0")]
+#![deny(missing_docs, warnings, missing_debug_implementations)]
+
+//! Windows named pipes for Tokio.
+//!
+//! Named pipes are the usual way for processes on Windows to communicate
+//! locally, as Unix domain sockets do on Unix. This crate provides a
+//! [`NamedPipeServer`] accepting connections on a pipe name, and a
+//! [`NamedPipe`] representing one end of a connected pipe, which implements
+//! `AsyncRead` and `AsyncWrite`. Both are driven by the reactor's I/O
+//! completion port.
+//!
+//! The server keeps an instance of the pipe waiting for the next client at
+//! all times, so clients connecting in a burst do not find the name missing.
+//! A client connecting while all instances are busy is retried until an
+//! instance is available.
+//!
+//! [`NamedPipeServer`]: struct.NamedPipeServer.html
+//! [`NamedPipe`]: struct.NamedPipe.html
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # extern crate tokio;
+//! # extern crate tokio_named_pipes;
+//! use tokio::prelude::*;
+//! use tokio_named_pipes::{NamedPipe, NamedPipeServer};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let name = r"\\.\pipe\tokio-example";
+//!
+//! let server = NamedPipeServer::bind(name)?
+//!     .incoming()
+//!     .for_each(|pipe| {
+//!         let (reader, writer) = pipe.split();
+//!         let echo = tokio::io::copy(reader, writer)
+//!             .map(|_| ())
+//!             .map_err(|e| eprintln!("echo failed; error = {:?}", e));
+//!         tokio::spawn(echo);
+//!         Ok(())
+//!     })
+//!     .map_err(|e| eprintln!("accept failed; error = {:?}", e));
+//!
+//! let client = NamedPipe::connect(name)
+//!     .and_then(|pipe| tokio::io::write_all(pipe, b"hello"))
+//!     .map(|_| ())
+//!     .map_err(|e| eprintln!("client failed; error = {:?}", e));
+//!
+//! tokio::run(server.join(client).map(|_| ()));
+//! # Ok(())
+//! # }
+//! ```
+
+#[macro_use]
+extern crate futures;
+#[macro_use]
+extern crate log;
+extern crate mio;
+extern crate mio_named_pipes;
+extern crate miow;
+extern crate tokio_io;
+extern crate tokio_reactor;
+extern crate tokio_timer;
+extern crate winapi;
+
+mod pipe;
+mod server;
+
+pub use pipe::{ConnectFuture, NamedPipe};
+pub use server::{Builder, Incoming, NamedPipeServer};
@@ -0,0 +1,198 @@
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_reactor::{Handle, PollEvented};
+use tokio_timer::Delay;
+
+use futures::{Async, Future, Poll};
+use mio::Ready;
+use mio_named_pipes;
+use winapi::shared::winerror::ERROR_PIPE_BUSY;
+use winapi::um::winbase::FILE_FLAG_OVERLAPPED;
+
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::os::windows::fs::OpenOptionsExt;
+use std::os::windows::io::{AsRawHandle, FromRawHandle, IntoRawHandle, RawHandle};
+use std::time::{Duration, Instant};
+
+/// One end of a connected named pipe.
+///
+/// On the client side, a pipe is connected with [`NamedPipe::connect`]. On
+/// the server side, connected pipes are accepted from a
+/// [`NamedPipeServer`].
+///
+/// Reading and writing to a `NamedPipe` is usually done using the
+/// convenience functions found in the [`tokio_io::io`] module.
+///
+/// [`NamedPipe::connect`]: #method.connect
+/// [`NamedPipeServer`]: struct.NamedPipeServer.html
+/// [`tokio_io::io`]: https://docs.rs/tokio-io/0.1/tokio_io/io/index.html
+pub struct NamedPipe {
+    io: PollEvented<mio_named_pipes::NamedPipe>,
+}
+
+/// Future returned by `NamedPipe::connect` which will resolve to a
+/// `NamedPipe` when the pipe is connected.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct ConnectFuture {
+    name: OsString,
+    delay: Option<Delay>,
+}
+
+/// How long to wait before trying to connect again while all the instances
+/// of the pipe are busy.
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+impl NamedPipe {
+    /// Connects to the named pipe `name`, such as `\\.\pipe\my-pipe`.
+    ///
+    /// While all the instances of the pipe are connected to other clients,
+    /// connecting is retried until the server makes an instance available.
+    /// An error is returned if no server is listening on `name`.
+    ///
+    /// The returned pipe is associated with the default event loop's handle.
+    pub fn connect<A: AsRef<OsStr>>(name: A) -> ConnectFuture {
+        ConnectFuture {
+            name: name.as_ref().to_os_string(),
+            delay: None,
+        }
+    }
+
+    /// Creates a new `NamedPipe` from a pipe created by `mio-named-pipes`.
+    ///
+    /// The returned pipe will be associated with the given event loop
+    /// specified by `handle` and is ready to perform I/O.
+    pub fn from_mio(pipe: mio_named_pipes::NamedPipe, handle: &Handle) -> io::Result<NamedPipe> {
+        let io = PollEvented::new_with_handle(pipe, handle)?;
+        Ok(NamedPipe { io })
+    }
+
+    pub(crate) fn new(io: PollEvented<mio_named_pipes::NamedPipe>) -> NamedPipe {
+        NamedPipe { io }
+    }
+
+    /// Test whether this pipe is ready to be read or not.
+    pub fn poll_read_ready(&self, ready: Ready) -> Poll<Ready, io::Error> {
+        self.io.poll_read_ready(ready)
+    }
+
+    /// Test whether this pipe is ready to be written to or not.
+    pub fn poll_write_ready(&self) -> Poll<Ready, io::Error> {
+        self.io.poll_write_ready()
+    }
+
+    /// Disconnects the client from the server end of the pipe.
+    ///
+    /// The client then reads EOF. This is only valid on a pipe accepted
+    /// from a `NamedPipeServer`.
+    pub fn disconnect(&self) -> io::Result<()> {
+        self.io.get_ref().disconnect()
+    }
+}
+
+impl Read for NamedPipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.io.read(buf)
+    }
+}
+
+impl Write for NamedPipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.io.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.io.flush()
+    }
+}
+
+impl AsyncRead for NamedPipe {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl AsyncWrite for NamedPipe {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        <&NamedPipe>::shutdown(&mut &*self)
+    }
+}
+
+impl<'a> Read for &'a NamedPipe {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&self.io).read(buf)
+    }
+}
+
+impl<'a> Write for &'a NamedPipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&self.io).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&self.io).flush()
+    }
+}
+
+impl<'a> AsyncRead for &'a NamedPipe {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+}
+
+impl<'a> AsyncWrite for &'a NamedPipe {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        // Pipes cannot be shut down in one direction only, the peer reads EOF
+        // once the pipe is dropped or disconnected.
+        Ok(().into())
+    }
+}
+
+impl fmt::Debug for NamedPipe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.io.get_ref().fmt(f)
+    }
+}
+
+impl AsRawHandle for NamedPipe {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.io.get_ref().as_raw_handle()
+    }
+}
+
+impl Future for ConnectFuture {
+    type Item = NamedPipe;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<NamedPipe, io::Error> {
+        loop {
+            if let Some(ref mut delay) = self.delay {
+                try_ready!(delay
+                    .poll()
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e)));
+            }
+
+            let res = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(FILE_FLAG_OVERLAPPED)
+                .open(&self.name);
+
+            match res {
+                Ok(file) => {
+                    let pipe = unsafe {
+                        mio_named_pipes::NamedPipe::from_raw_handle(file.into_raw_handle())
+                    };
+                    return NamedPipe::from_mio(pipe, &Handle::default()).map(Async::Ready);
+                }
+                Err(ref e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) => {
+                    trace!("all instances of the pipe are busy, retrying");
+                    self.delay = Some(Delay::new(Instant::now() + BUSY_RETRY_DELAY));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use NamedPipe;
+
+use tokio_reactor::{Handle, PollEvented};
+
+use futures::{Async, Poll, Stream};
+use mio_named_pipes;
+use miow::pipe::NamedPipeBuilder;
+
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::mem;
+use std::os::windows::io::{FromRawHandle, IntoRawHandle};
+
+/// A named pipe server, accepting connections from clients.
+///
+/// Each connection uses its own instance of the pipe. The server keeps one
+/// instance waiting for a client: once a client connects to it, a new
+/// instance is created before the connected one is handed out, unless the
+/// maximum number of instances is reached.
+pub struct NamedPipeServer {
+    name: OsString,
+    builder: Builder,
+    pending: Option<PollEvented<mio_named_pipes::NamedPipe>>,
+    connecting: bool,
+}
+
+/// Stream returned by the `NamedPipeServer::incoming` function representing
+/// the stream of pipes connected to a server.
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct Incoming {
+    inner: NamedPipeServer,
+}
+
+/// Builds a named pipe server with custom configuration values.
+///
+/// Methods can be chained in order to set the configuration values. The
+/// server is created by calling [`bind`].
+///
+/// [`bind`]: #method.bind
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate tokio_named_pipes;
+/// use tokio_named_pipes::Builder;
+///
+/// # fn main() -> std::io::Result<()> {
+/// let server = Builder::new()
+///     .max_instances(16)
+///     .in_buffer_size(4096)
+///     .out_buffer_size(4096)
+///     .bind(r"\\.\pipe\tokio-example")?;
+/// # drop(server);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    max_instances: u8,
+    in_buffer_size: u32,
+    out_buffer_size: u32,
+    accept_remote: bool,
+}
+
+impl NamedPipeServer {
+    /// Creates a new server listening on the pipe `name`, such as
+    /// `\\.\pipe\my-pipe`, with the default configuration.
+    ///
+    /// An error is returned if a pipe with this name already exists. See
+    /// [`Builder`] to configure the server.
+    ///
+    /// [`Builder`]: struct.Builder.html
+    pub fn bind<A: AsRef<OsStr>>(name: A) -> io::Result<NamedPipeServer> {
+        Builder::new().bind(name)
+    }
+
+    /// Returns the name of the pipe.
+    pub fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    /// Attempts to accept a connection from a client, returning the
+    /// connected pipe.
+    ///
+    /// If no client is connected, `Async::NotReady` is returned and the
+    /// current task is notified once a client connects.
+    ///
+    /// An error is returned if no instance of the pipe is waiting for a
+    /// client and a new one cannot be created, such as when all the
+    /// instances allowed by [`Builder::max_instances`] are connected.
+    ///
+    /// [`Builder::max_instances`]: struct.Builder.html#method.max_instances
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from outside of a task context.
+    pub fn poll_accept(&mut self) -> Poll<NamedPipe, io::Error> {
+        if self.pending.is_none() {
+            self.pending = Some(self.builder.create(&self.name, false)?);
+        }
+
+        if !self.connecting {
+            match self.pending().get_ref().connect() {
+                Ok(()) => return Ok(Async::Ready(self.next_instance())),
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    self.connecting = true;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // The pipe becomes writable once the connection completes.
+        try_ready!(self.pending().poll_write_ready());
+        self.connecting = false;
+
+        if let Some(e) = self.pending().get_ref().take_error()? {
+            return Err(e);
+        }
+
+        Ok(Async::Ready(self.next_instance()))
+    }
+
+    /// Consumes this server, returning a stream of the pipes connected to
+    /// it.
+    pub fn incoming(self) -> Incoming {
+        Incoming { inner: self }
+    }
+
+    fn pending(&self) -> &PollEvented<mio_named_pipes::NamedPipe> {
+        self.pending.as_ref().expect("no pending instance")
+    }
+
+    /// Replaces the connected instance with a new one waiting for the next
+    /// client, returning the connected pipe.
+    ///
+    /// If the new instance cannot be created, creating it is retried on the
+    /// next call to `poll_accept`.
+    fn next_instance(&mut self) -> NamedPipe {
+        let next = match self.builder.create(&self.name, false) {
+            Ok(next) => Some(next),
+            Err(e) => {
+                debug!(
+                    "failed to create the next instance of {:?}; error = {:?}",
+                    self.name, e
+                );
+                None
+            }
+        };
+
+        let connected = mem::replace(&mut self.pending, next).expect("no pending instance");
+        trace!("client connected to {:?}", self.name);
+        NamedPipe::new(connected)
+    }
+}
+
+impl ::std::fmt::Debug for NamedPipeServer {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("NamedPipeServer")
+            .field("name", &self.name)
+            .field("pending", &self.pending.as_ref().map(|p| p.get_ref()))
+            .finish()
+    }
+}
+
+impl Stream for Incoming {
+    type Item = NamedPipe;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<NamedPipe>, io::Error> {
+        let pipe = try_ready!(self.inner.poll_accept());
+        Ok(Async::Ready(Some(pipe)))
+    }
+}
+
+impl Builder {
+    /// Returns a new server builder with default configuration values.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> Builder {
+        Builder {
+            max_instances: 255,
+            in_buffer_size: 65536,
+            out_buffer_size: 65536,
+            accept_remote: false,
+        }
+    }
+
+    /// Sets the maximum number of instances of the pipe, which bounds the
+    /// number of clients connected at the same time.
+    ///
+    /// The instance waiting for the next client counts towards the limit.
+    /// The default value, 255, means that the number of instances is not
+    /// limited.
+    pub fn max_instances(&mut self, max_instances: u8) -> &mut Self {
+        self.max_instances = max_instances;
+        self
+    }
+
+    /// Sets the number of bytes to reserve for the input buffer of each
+    /// instance.
+    ///
+    /// The default value is 64 KiB.
+    pub fn in_buffer_size(&mut self, size: u32) -> &mut Self {
+        self.in_buffer_size = size;
+        self
+    }
+
+    /// Sets the number of bytes to reserve for the output buffer of each
+    /// instance.
+    ///
+    /// The default value is 64 KiB.
+    pub fn out_buffer_size(&mut self, size: u32) -> &mut Self {
+        self.out_buffer_size = size;
+        self
+    }
+
+    /// Sets whether clients on remote machines may connect to the pipe.
+    ///
+    /// By default, only local clients are accepted.
+    pub fn accept_remote(&mut self, accept: bool) -> &mut Self {
+        self.accept_remote = accept;
+        self
+    }
+
+    /// Creates a new server listening on the pipe `name` with this
+    /// configuration.
+    ///
+    /// An error is returned if a pipe with this name already exists. The
+    /// server is associated with the default event loop's handle.
+    pub fn bind<A: AsRef<OsStr>>(&self, name: A) -> io::Result<NamedPipeServer> {
+        let name = name.as_ref().to_os_string();
+        let pending = self.create(&name, true)?;
+
+        Ok(NamedPipeServer {
+            name,
+            builder: self.clone(),
+            pending: Some(pending),
+            connecting: false,
+        })
+    }
+
+    fn create(
+        &self,
+        name: &OsStr,
+        first: bool,
+    ) -> io::Result<PollEvented<mio_named_pipes::NamedPipe>> {
+        let pipe = NamedPipeBuilder::new(name)
+            .first(first)
+            .inbound(true)
+            .outbound(true)
+            .max_instances(self.max_instances)
+            .in_buffer_size(self.in_buffer_size)
+            .out_buffer_size(self.out_buffer_size)
+            .accept_remote(self.accept_remote)
+            .create()?;
+
+        let pipe = unsafe { mio_named_pipes::NamedPipe::from_raw_handle(pipe.into_raw_handle()) };
+
+        // The pipe must be registered before a connection is issued.
+        PollEvented::new_with_handle(pipe, &Handle::default())
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
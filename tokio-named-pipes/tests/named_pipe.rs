@@ -0,0 +1,84 @@
+#![cfg(windows)]
+
+extern crate futures;
+extern crate tokio;
+extern crate tokio_named_pipes;
+
+use tokio_named_pipes::*;
+
+use tokio::io;
+use tokio::runtime::current_thread::Runtime;
+
+use futures::sync::oneshot;
+use futures::{future, Future, Stream};
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(e) => e,
+            Err(e) => panic!("{} failed with {:?}", stringify!($e), e),
+        }
+    };
+}
+
+#[test]
+fn echo() {
+    let name = r"\\.\pipe\tokio-named-pipes-echo";
+
+    let mut rt = Runtime::new().unwrap();
+
+    let server = t!(NamedPipeServer::bind(name));
+    let (tx, rx) = oneshot::channel();
+
+    rt.spawn({
+        server
+            .incoming()
+            .into_future()
+            .and_then(move |(pipe, _)| {
+                tx.send(pipe.unwrap()).unwrap();
+                Ok(())
+            })
+            .map_err(|e| panic!("err={:?}", e.0))
+    });
+
+    let client = t!(rt.block_on(NamedPipe::connect(name)));
+    let server = t!(rt.block_on(rx));
+
+    // Write to the client
+    let (client, _) = t!(rt.block_on(io::write_all(client, b"hello")));
+
+    // Read from the server
+    let (server, buf) = t!(rt.block_on(io::read_exact(server, [0; 5])));
+    assert_eq!(&buf, b"hello");
+
+    // And back the other way
+    t!(rt.block_on(io::write_all(server, b"world")));
+    let (_, buf) = t!(rt.block_on(io::read_exact(client, [0; 5])));
+    assert_eq!(&buf, b"world");
+}
+
+#[test]
+fn bind_existing_name() {
+    let name = r"\\.\pipe\tokio-named-pipes-existing";
+
+    let _server = t!(NamedPipeServer::bind(name));
+    assert!(NamedPipeServer::bind(name).is_err());
+}
+
+#[test]
+fn max_instances() {
+    let name = r"\\.\pipe\tokio-named-pipes-max-instances";
+
+    let mut rt = Runtime::new().unwrap();
+
+    let mut server = t!(Builder::new().max_instances(1).bind(name));
+
+    // A client can connect before the server accepts it.
+    let _client = t!(rt.block_on(NamedPipe::connect(name)));
+    let _pipe = t!(rt.block_on(future::poll_fn(|| server.poll_accept())));
+
+    // The only instance is connected, so no other can be created.
+    assert!(rt
+        .block_on(future::poll_fn(|| server.poll_accept()))
+        .is_err());
+}
@@ -0,0 +1,65 @@
+//! `SO_BINDTODEVICE` helpers shared by `tokio-tcp` and `tokio-udp`.
+//!
+//! This module is not part of the public API.
+
+use libc;
+
+use std::ffi::CString;
+use std::io;
+use std::os::unix::io::RawFd;
+
+/// Binds the socket `fd` to the network interface `interface`, or removes
+/// the binding if `None`.
+pub fn bind_device(fd: RawFd, interface: Option<&str>) -> io::Result<()> {
+    let interface = match interface {
+        Some(interface) => CString::new(interface).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "interface name contains a NUL byte",
+            )
+        })?,
+        None => CString::default(),
+    };
+    let name = interface.as_bytes();
+
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            name.as_ptr() as *const _,
+            name.len() as libc::socklen_t,
+        )
+    };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Returns the network interface the socket `fd` is bound to, if any.
+pub fn device(fd: RawFd) -> io::Result<Option<String>> {
+    let mut buf = [0u8; libc::IFNAMSIZ];
+    let mut len = buf.len() as libc::socklen_t;
+
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_BINDTODEVICE,
+            buf.as_mut_ptr() as *mut _,
+            &mut len,
+        )
+    };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let name = buf[..len as usize].split(|&b| b == 0).next().unwrap_or(&[]);
+    if name.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(String::from_utf8_lossy(name).into_owned()))
+}
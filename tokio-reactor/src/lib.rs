@@ -35,6 +35,8 @@ extern crate crossbeam_utils;
 extern crate futures;
 #[macro_use]
 extern crate lazy_static;
+#[cfg(target_os = "linux")]
+extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate mio;
@@ -46,6 +48,9 @@ extern crate tokio_io;
 extern crate tokio_sync;
 
 pub(crate) mod background;
+#[cfg(target_os = "linux")]
+#[doc(hidden)]
+pub mod device;
 mod poll_evented;
 mod registration;
 mod sharded_rwlock;
@@ -3,6 +3,7 @@ use {Handle, Registration};
 use futures::{task, Async, Poll};
 use mio;
 use mio::event::Evented;
+use tokio_executor::budget;
 use tokio_io::{AsyncRead, AsyncWrite};
 
 use std::fmt;
@@ -211,7 +212,12 @@ where
     /// The I/O resource will remain in a read-ready state until readiness is
     /// cleared by calling [`clear_read_ready`].
     ///
+    /// `Async::NotReady` is also returned when the current task has exhausted
+    /// its [cooperative scheduling budget][budget]. In this case, the task is
+    /// notified immediately so that it is scheduled again.
+    ///
     /// [`clear_read_ready`]: #method.clear_read_ready
+    /// [budget]: https://docs.rs/tokio-executor/0.1/tokio_executor/budget/index.html
     ///
     /// # Panics
     ///
@@ -221,6 +227,25 @@ where
     /// * called from outside of a task context.
     pub fn poll_read_ready(&self, mask: mio::Ready) -> Poll<mio::Ready, io::Error> {
         assert!(!mask.is_writable(), "cannot poll for write readiness");
+
+        // Cooperative scheduling, see `tokio_executor::budget`.
+        let proceed = match budget::poll_proceed() {
+            Async::Ready(proceed) => proceed,
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
+        let ret = self.poll_read_ready_unbudgeted(mask);
+
+        if let Ok(Async::Ready(_)) = ret {
+            proceed.made_progress();
+        }
+
+        ret
+    }
+
+    /// `poll_read_ready` without consuming the task's budget, so that
+    /// clearing readiness does not count as an operation.
+    fn poll_read_ready_unbudgeted(&self, mask: mio::Ready) -> Poll<mio::Ready, io::Error> {
         poll_ready!(
             self,
             mask,
@@ -254,7 +279,7 @@ where
             .read_readiness
             .fetch_and(!ready.as_usize(), Relaxed);
 
-        if self.poll_read_ready(ready)?.is_ready() {
+        if self.poll_read_ready_unbudgeted(ready)?.is_ready() {
             // Notify the current task
             task::current().notify();
         }
@@ -273,7 +298,11 @@ where
     /// The I/O resource will remain in a write-ready state until readiness is
     /// cleared by calling [`clear_write_ready`].
     ///
+    /// As with [`poll_read_ready`], `Async::NotReady` is also returned when the
+    /// current task has exhausted its cooperative scheduling budget.
+    ///
     /// [`clear_write_ready`]: #method.clear_write_ready
+    /// [`poll_read_ready`]: #method.poll_read_ready
     ///
     /// # Panics
     ///
@@ -282,6 +311,23 @@ where
     /// * `ready` contains bits besides `writable` and `hup`.
     /// * called from outside of a task context.
     pub fn poll_write_ready(&self) -> Poll<mio::Ready, io::Error> {
+        // Cooperative scheduling, see `tokio_executor::budget`.
+        let proceed = match budget::poll_proceed() {
+            Async::Ready(proceed) => proceed,
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
+        let ret = self.poll_write_ready_unbudgeted();
+
+        if let Ok(Async::Ready(_)) = ret {
+            proceed.made_progress();
+        }
+
+        ret
+    }
+
+    /// `poll_write_ready` without consuming the task's budget.
+    fn poll_write_ready_unbudgeted(&self) -> Poll<mio::Ready, io::Error> {
         poll_ready!(
             self,
             mio::Ready::writable(),
@@ -310,7 +356,7 @@ where
             .write_readiness
             .fetch_and(!ready.as_usize(), Relaxed);
 
-        if self.poll_write_ready()?.is_ready() {
+        if self.poll_write_ready_unbudgeted()?.is_ready() {
             // Notify the current task
             task::current().notify();
         }
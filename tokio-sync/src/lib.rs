@@ -9,6 +9,7 @@
 extern crate fnv;
 #[macro_use]
 extern crate futures;
+extern crate tokio_executor;
 
 macro_rules! debug {
     ($($t:tt)*) => {
@@ -1,5 +1,6 @@
 use super::list;
 use futures::Poll;
+use tokio_executor::budget;
 
 use loom::{
     futures::AtomicTask,
@@ -252,7 +253,13 @@ where
         use super::block::Read::*;
         use futures::Async::*;
 
-        self.inner.rx_fields.with_mut(|rx_fields_ptr| {
+        // Cooperative scheduling, see `tokio_executor::budget`.
+        let proceed = match budget::poll_proceed() {
+            Ready(proceed) => proceed,
+            NotReady => return Ok(NotReady),
+        };
+
+        let ret = self.inner.rx_fields.with_mut(|rx_fields_ptr| {
             let rx_fields = unsafe { &mut *rx_fields_ptr };
 
             macro_rules! try_recv {
@@ -297,7 +304,13 @@ where
             } else {
                 Ok(NotReady)
             }
-        })
+        });
+
+        if let Ok(Ready(_)) = ret {
+            proceed.made_progress();
+        }
+
+        ret
     }
 }
 
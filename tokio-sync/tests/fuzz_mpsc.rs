@@ -1,6 +1,7 @@
 extern crate futures;
 #[macro_use]
 extern crate loom;
+extern crate tokio_executor;
 
 macro_rules! if_fuzz {
     ($($t:tt)*) => {
@@ -0,0 +1,76 @@
+use super::{TcpListener, TcpStream};
+
+use std::io;
+use std::net::SocketAddr;
+
+use futures::{Future, Poll};
+
+/// Accepts a new connection on `listener`, returning a future type.
+///
+/// The returned future will resolve to the listener, along with the accepted
+/// stream and the address of the peer, so that the listener can be used to
+/// accept the next connection. This is an alternative to [`incoming`] when
+/// accepting connections one at a time, for example within a
+/// [`loop_fn`].
+///
+/// See [`TcpListener::poll_accept`] for more details.
+///
+/// [`incoming`]: struct.TcpListener.html#method.incoming
+/// [`loop_fn`]: https://docs.rs/futures/0.1/futures/future/fn.loop_fn.html
+/// [`TcpListener::poll_accept`]: struct.TcpListener.html#method.poll_accept
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::net::tcp::{self, TcpListener};
+/// use futures::future::{self, Loop};
+/// use futures::Future;
+/// use std::net::SocketAddr;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+/// let listener = TcpListener::bind(&addr)?;
+///
+/// let server = future::loop_fn(listener, |listener| {
+///     tcp::accept(listener).map(|(listener, _stream, addr)| {
+///         println!("accepted a connection from {}", addr);
+///         Loop::Continue::<(), _>(listener)
+///     })
+/// });
+/// # drop(server);
+/// # Ok(())
+/// # }
+/// ```
+pub fn accept(listener: TcpListener) -> Accept {
+    Accept {
+        listener: Some(listener),
+    }
+}
+
+/// A future which accepts a connection on a `TcpListener`.
+///
+/// Created by the [`accept`] function.
+///
+/// [`accept`]: fn.accept.html
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Accept {
+    listener: Option<TcpListener>,
+}
+
+impl Future for Accept {
+    type Item = (TcpListener, TcpStream, SocketAddr);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, io::Error> {
+        let (stream, addr) = match self.listener {
+            Some(ref mut listener) => try_ready!(listener.poll_accept()),
+            None => panic!("poll an Accept after it's done"),
+        };
+
+        let listener = self.listener.take().unwrap();
+        Ok((listener, stream, addr).into())
+    }
+}
@@ -0,0 +1,285 @@
+use super::keepalive::{self, TcpKeepalive};
+use super::{ConnectFuture, TcpListener, TcpStream};
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, RawSocket};
+
+use socket2::{Domain, Protocol, Socket, Type};
+#[cfg(target_os = "linux")]
+use tokio_reactor::device;
+use tokio_reactor::Handle;
+
+/// A TCP socket that has not yet been converted to a `TcpStream` or
+/// `TcpListener`.
+///
+/// `TcpBuilder` allows configuring the socket options which must be set
+/// before the socket is bound, connected or listening, such as
+/// `SO_REUSEADDR`, `SO_REUSEPORT` or the size of the socket buffers. The
+/// socket is then turned into a [`TcpStream`] with [`connect`], or into a
+/// [`TcpListener`] with [`listen`], both registered with the default reactor.
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`TcpListener`]: struct.TcpListener.html
+/// [`connect`]: #method.connect
+/// [`listen`]: #method.listen
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// use tokio::net::tcp::TcpBuilder;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:0".parse()?;
+///
+/// let socket = TcpBuilder::new_v4()?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_recv_buffer_size(64 * 1024)?;
+/// socket.bind(&addr)?;
+///
+/// let listener = socket.listen(1024)?;
+/// # drop(listener);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TcpBuilder {
+    inner: Socket,
+}
+
+impl TcpBuilder {
+    /// Creates a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<TcpBuilder> {
+        TcpBuilder::new(Domain::ipv4())
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<TcpBuilder> {
+        TcpBuilder::new(Domain::ipv6())
+    }
+
+    /// Creates a new TCP socket of the same address family as `addr`.
+    ///
+    /// The socket is not bound to `addr`, see [`bind`] for this.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn for_addr(addr: &SocketAddr) -> io::Result<TcpBuilder> {
+        match *addr {
+            SocketAddr::V4(..) => TcpBuilder::new_v4(),
+            SocketAddr::V6(..) => TcpBuilder::new_v6(),
+        }
+    }
+
+    fn new(domain: Domain) -> io::Result<TcpBuilder> {
+        let inner = Socket::new(domain, Type::stream(), Some(Protocol::tcp()))?;
+        Ok(TcpBuilder { inner })
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// This allows binding to an address still in the `TIME_WAIT` state,
+    /// such as the address of a listener which was just restarted.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.inner.set_reuse_address(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuseaddr`].
+    ///
+    /// [`set_reuseaddr`]: #method.set_reuseaddr
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.inner.reuse_address()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address, and on Linux
+    /// the incoming connections are balanced between the listeners.
+    ///
+    /// This function is only available on Unix platforms.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuseport`].
+    ///
+    /// [`set_reuseport`]: #method.set_reuseport
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    /// Enables keepalive messages on this socket, with the idle time, probe
+    /// interval and number of retries configured by `keepalive`.
+    ///
+    /// The configuration is kept by the connected stream, or by the streams
+    /// accepted by the listener. See [`TcpKeepalive`] for more details.
+    ///
+    /// [`TcpKeepalive`]: struct.TcpKeepalive.html
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> io::Result<()> {
+        #[cfg(unix)]
+        let socket = self.inner.as_raw_fd();
+        #[cfg(windows)]
+        let socket = self.inner.as_raw_socket();
+
+        keepalive::set(socket, keepalive)
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's send buffer associated
+    /// with the socket. The operating system may round or limit the size.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's receive buffer associated
+    /// with the socket. The operating system may round or limit the size.
+    ///
+    /// The receive buffer of a listening socket is inherited by the accepted
+    /// streams, and determines the TCP window scale they negotiate, so it
+    /// must be set before calling [`listen`].
+    ///
+    /// [`listen`]: #method.listen
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_BINDTODEVICE` option on this socket.
+    ///
+    /// Once bound to a network interface, such as `"eth0"`, the socket only
+    /// sends and receives packets through this interface. `None` removes the
+    /// binding. This usually requires the `CAP_NET_RAW` capability.
+    ///
+    /// This function is only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        device::bind_device(self.inner.as_raw_fd(), interface)
+    }
+
+    /// Gets the value of the `SO_BINDTODEVICE` option on this socket.
+    ///
+    /// For more information about this option, see [`bind_device`].
+    ///
+    /// This function is only available on Linux.
+    ///
+    /// [`bind_device`]: #method.bind_device
+    #[cfg(target_os = "linux")]
+    pub fn device(&self) -> io::Result<Option<String>> {
+        device::device(self.inner.as_raw_fd())
+    }
+
+    /// Binds the socket to the given address.
+    ///
+    /// Binding before connecting selects the local address of the
+    /// connection, and so the interface it goes through on multi-homed
+    /// hosts. Port 0 lets the operating system pick the port. Note that on
+    /// Windows, a socket must be bound before it can be connected, perhaps
+    /// to the unspecified address.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::net::tcp::TcpBuilder;
+    /// use futures::Future;
+    ///
+    /// # fn main() -> Result<(), Box<std::error::Error>> {
+    /// let source = "192.168.1.10:0".parse()?;
+    /// let addr = "192.168.1.1:80".parse()?;
+    ///
+    /// let socket = TcpBuilder::new_v4()?;
+    /// socket.bind(&source)?;
+    ///
+    /// let task = socket
+    ///     .connect(&addr)
+    ///     .map(|stream| println!("connected from {}", stream.local_addr().unwrap()))
+    ///     .map_err(|e| eprintln!("failed to connect; error = {:?}", e));
+    /// # drop(task);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        self.inner.bind(&(*addr).into())
+    }
+
+    /// Returns the local address that this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner
+            .local_addr()?
+            .as_std()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "socket is not an internet socket"))
+    }
+
+    /// Connects the socket to `addr`, returning a future resolving to the
+    /// connected `TcpStream`.
+    ///
+    /// The stream is registered with the default reactor, see
+    /// [`TcpStream::connect_std`] for the details of the connection.
+    ///
+    /// [`TcpStream::connect_std`]: struct.TcpStream.html#method.connect_std
+    pub fn connect(self, addr: &SocketAddr) -> ConnectFuture {
+        TcpStream::connect_std(self.inner.into_tcp_stream(), addr, &Handle::default())
+    }
+
+    /// Starts listening on the socket, returning a `TcpListener` accepting
+    /// connections.
+    ///
+    /// The listener is registered with the default reactor. `backlog` is
+    /// the maximum number of pending connections, which the operating system
+    /// may limit.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.inner.listen(backlog as i32)?;
+        TcpListener::from_std(self.inner.into_tcp_listener(), &Handle::default())
+    }
+}
+
+impl fmt::Debug for TcpBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TcpBuilder {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for TcpBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.inner.as_raw_socket()
+    }
+}
@@ -0,0 +1,220 @@
+use super::{ConnectFuture, TcpStream};
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use futures::{Async, Future, Poll};
+use tokio_timer::Delay;
+
+/// Configures dual-stack connections to a set of addresses, racing them as
+/// described by [RFC 8305] ("Happy Eyeballs").
+///
+/// The addresses are reordered to alternate between IPv6 and IPv4, starting
+/// with the family of the first address. A connection attempt is started to
+/// the first address, then a new attempt is started to the next address each
+/// time the [attempt delay] elapses, or as soon as an attempt fails. The first
+/// established stream is returned, and the other attempts are dropped.
+///
+/// The returned futures must be polled from within a runtime providing a
+/// timer.
+///
+/// [RFC 8305]: https://tools.ietf.org/html/rfc8305
+/// [attempt delay]: #method.attempt_delay
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use futures::Future;
+/// use tokio::net::tcp::HappyEyeballs;
+/// use std::net::ToSocketAddrs;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addrs = "localhost:34254".to_socket_addrs()?;
+/// let stream = HappyEyeballs::new()
+///     .attempt_delay(Duration::from_millis(100))
+///     .connect(addrs)
+///     .map(|stream|
+///         println!("successfully connected to {}", stream.peer_addr().unwrap()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HappyEyeballs {
+    attempt_delay: Duration,
+}
+
+/// Future returned by `TcpStream::connect_any` and `HappyEyeballs::connect`
+/// which will resolve to the first `TcpStream` to connect.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct ConnectAny {
+    attempt_delay: Duration,
+
+    // Addresses yet to be attempted, in order.
+    addrs: VecDeque<SocketAddr>,
+
+    // Attempts in progress.
+    attempts: Vec<ConnectFuture>,
+
+    // Elapses when the next attempt is due, set once the first attempt starts.
+    delay: Option<Delay>,
+
+    // Error of the last failed attempt.
+    error: Option<io::Error>,
+}
+
+impl HappyEyeballs {
+    /// Returns a new configuration with the default values.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> HappyEyeballs {
+        HappyEyeballs {
+            attempt_delay: Duration::from_millis(250),
+        }
+    }
+
+    /// Set the delay between the start of two connection attempts.
+    ///
+    /// The default value is 250 milliseconds, as recommended by RFC 8305.
+    pub fn attempt_delay(&mut self, delay: Duration) -> &mut Self {
+        self.attempt_delay = delay;
+        self
+    }
+
+    /// Connects to the first of `addrs` to accept a connection.
+    ///
+    /// The returned future fails with the error of the last attempt if none
+    /// succeeds, or with an error of kind `InvalidInput` if `addrs` is empty.
+    pub fn connect<I>(&self, addrs: I) -> ConnectAny
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        ConnectAny {
+            attempt_delay: self.attempt_delay,
+            addrs: interleave(addrs),
+            attempts: Vec::new(),
+            delay: None,
+            error: None,
+        }
+    }
+}
+
+impl Default for HappyEyeballs {
+    fn default() -> HappyEyeballs {
+        HappyEyeballs::new()
+    }
+}
+
+impl TcpStream {
+    /// Create a new TCP stream connected to the first of `addrs` to accept a
+    /// connection, racing the addresses as described by RFC 8305.
+    ///
+    /// This is a shortcut for `HappyEyeballs::new().connect(addrs)`, see
+    /// [`HappyEyeballs`] for more details.
+    ///
+    /// [`HappyEyeballs`]: struct.HappyEyeballs.html
+    pub fn connect_any<I>(addrs: I) -> ConnectAny
+    where
+        I: IntoIterator<Item = SocketAddr>,
+    {
+        HappyEyeballs::new().connect(addrs)
+    }
+}
+
+impl ConnectAny {
+    fn start_attempt(&mut self, addr: SocketAddr) {
+        let deadline = Instant::now() + self.attempt_delay;
+
+        match self.delay {
+            Some(ref mut delay) => delay.reset(deadline),
+            None => self.delay = Some(Delay::new(deadline)),
+        }
+
+        self.attempts.push(TcpStream::connect(&addr));
+    }
+}
+
+impl Future for ConnectAny {
+    type Item = TcpStream;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<TcpStream, io::Error> {
+        loop {
+            let mut i = 0;
+
+            while i < self.attempts.len() {
+                match self.attempts[i].poll() {
+                    Ok(Async::Ready(stream)) => return Ok(Async::Ready(stream)),
+                    Ok(Async::NotReady) => i += 1,
+                    Err(e) => {
+                        drop(self.attempts.swap_remove(i));
+                        self.error = Some(e);
+                    }
+                }
+            }
+
+            // Start the next attempt right away if the others failed, or once
+            // the delay elapses.
+            let due = self.attempts.is_empty()
+                || match self.delay {
+                    Some(ref mut delay) => delay
+                        .poll()
+                        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+                        .is_ready(),
+                    None => true,
+                };
+
+            if !due {
+                return Ok(Async::NotReady);
+            }
+
+            match self.addrs.pop_front() {
+                Some(addr) => self.start_attempt(addr),
+                None if self.attempts.is_empty() => {
+                    return Err(self.error.take().unwrap_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "could not resolve to any addresses",
+                        )
+                    }));
+                }
+                None => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+/// Orders the addresses to alternate between address families, starting with
+/// the family of the first address.
+fn interleave<I>(addrs: I) -> VecDeque<SocketAddr>
+where
+    I: IntoIterator<Item = SocketAddr>,
+{
+    let addrs: Vec<_> = addrs.into_iter().collect();
+
+    let first_v6 = match addrs.first() {
+        Some(addr) => addr.is_ipv6(),
+        None => return VecDeque::new(),
+    };
+
+    let (mut preferred, mut other): (VecDeque<_>, VecDeque<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_v6);
+
+    let mut ordered = VecDeque::with_capacity(preferred.len() + other.len());
+
+    loop {
+        match (preferred.pop_front(), other.pop_front()) {
+            (None, None) => return ordered,
+            (a, b) => {
+                ordered.extend(a);
+                ordered.extend(b);
+            }
+        }
+    }
+}
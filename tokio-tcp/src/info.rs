@@ -0,0 +1,284 @@
+use std::io;
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+
+/// Statistics of a TCP connection kept by the operating system, as returned
+/// by [`TcpStream::tcp_info`].
+///
+/// This is a snapshot taken when `tcp_info` is called, call it again to
+/// refresh the statistics.
+///
+/// # Platform-specific behavior
+///
+/// On Linux and Android, the statistics are read from the `TCP_INFO` socket
+/// option. On macOS and iOS, they are read from the `TCP_CONNECTION_INFO`
+/// socket option, which does not report some of the statistics.
+///
+/// [`TcpStream::tcp_info`]: struct.TcpStream.html#method.tcp_info
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::net::TcpStream;
+/// use futures::Future;
+/// use std::net::SocketAddr;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+/// let stream = TcpStream::connect(&addr);
+/// stream.map(|stream| {
+///     let info = stream.tcp_info().unwrap();
+///     println!("rtt = {:?}, cwnd = {}", info.rtt(), info.congestion_window());
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpInfo {
+    rtt: Duration,
+    rtt_var: Duration,
+    rto: Duration,
+    mss: u32,
+    cwnd: u32,
+    ssthresh: Option<u32>,
+    retransmits: u64,
+    unacked: Option<u32>,
+    lost: Option<u32>,
+}
+
+impl TcpInfo {
+    /// Returns the smoothed round-trip time of the connection.
+    pub fn rtt(&self) -> Duration {
+        self.rtt
+    }
+
+    /// Returns the variation of the round-trip time of the connection.
+    pub fn rtt_var(&self) -> Duration {
+        self.rtt_var
+    }
+
+    /// Returns the current retransmission timeout.
+    pub fn rto(&self) -> Duration {
+        self.rto
+    }
+
+    /// Returns the maximum segment size used to send data, in bytes.
+    pub fn mss(&self) -> u32 {
+        self.mss
+    }
+
+    /// Returns the congestion window, in segments.
+    pub fn congestion_window(&self) -> u32 {
+        self.cwnd
+    }
+
+    /// Returns the slow start threshold, in segments.
+    ///
+    /// `None` is returned while the connection is still in its initial slow
+    /// start, before the threshold is set.
+    pub fn slow_start_threshold(&self) -> Option<u32> {
+        self.ssthresh
+    }
+
+    /// Returns the total number of segments retransmitted on the connection.
+    pub fn retransmits(&self) -> u64 {
+        self.retransmits
+    }
+
+    /// Returns the number of segments sent and not yet acknowledged.
+    ///
+    /// This is only reported on Linux and Android.
+    pub fn unacked(&self) -> Option<u32> {
+        self.unacked
+    }
+
+    /// Returns the number of segments considered lost.
+    ///
+    /// This is only reported on Linux and Android.
+    pub fn lost(&self) -> Option<u32> {
+        self.lost
+    }
+}
+
+pub(crate) fn get(fd: RawFd) -> io::Result<TcpInfo> {
+    sys::get(fd)
+}
+
+/// Reads the socket option `name` of the TCP level into a `T`.
+///
+/// `T` must be a plain C struct, which the operating system may fill
+/// partially.
+fn getsockopt<T: Copy>(fd: RawFd, name: ::libc::c_int) -> io::Result<T> {
+    use std::mem;
+
+    unsafe {
+        let mut value: T = mem::zeroed();
+        let mut len = mem::size_of::<T>() as ::libc::socklen_t;
+        let ret = ::libc::getsockopt(
+            fd,
+            ::libc::IPPROTO_TCP,
+            name,
+            &mut value as *mut _ as *mut _,
+            &mut len,
+        );
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use super::TcpInfo;
+
+    use libc::c_int;
+
+    use std::io;
+    use std::os::unix::io::RawFd;
+    use std::time::Duration;
+
+    const TCP_INFO: c_int = 11;
+
+    /// The value of `tcpi_snd_ssthresh` until it is first set.
+    const INFINITE_SSTHRESH: u32 = 0x7fff_ffff;
+
+    /// The start of `struct tcp_info` of `linux/tcp.h`. Fields are only ever
+    /// appended to it, so the layout of this part is stable.
+    #[allow(dead_code, non_camel_case_types)]
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct tcp_info {
+        tcpi_state: u8,
+        tcpi_ca_state: u8,
+        tcpi_retransmits: u8,
+        tcpi_probes: u8,
+        tcpi_backoff: u8,
+        tcpi_options: u8,
+        tcpi_wscale: u8,
+        tcpi_flags: u8,
+
+        tcpi_rto: u32,
+        tcpi_ato: u32,
+        tcpi_snd_mss: u32,
+        tcpi_rcv_mss: u32,
+
+        tcpi_unacked: u32,
+        tcpi_sacked: u32,
+        tcpi_lost: u32,
+        tcpi_retrans: u32,
+        tcpi_fackets: u32,
+
+        tcpi_last_data_sent: u32,
+        tcpi_last_ack_sent: u32,
+        tcpi_last_data_recv: u32,
+        tcpi_last_ack_recv: u32,
+
+        tcpi_pmtu: u32,
+        tcpi_rcv_ssthresh: u32,
+        tcpi_rtt: u32,
+        tcpi_rttvar: u32,
+        tcpi_snd_ssthresh: u32,
+        tcpi_snd_cwnd: u32,
+        tcpi_advmss: u32,
+        tcpi_reordering: u32,
+
+        tcpi_rcv_rtt: u32,
+        tcpi_rcv_space: u32,
+
+        tcpi_total_retrans: u32,
+    }
+
+    pub(super) fn get(fd: RawFd) -> io::Result<TcpInfo> {
+        let info: tcp_info = super::getsockopt(fd, TCP_INFO)?;
+
+        // Times are in microseconds, sizes in segments.
+        Ok(TcpInfo {
+            rtt: Duration::from_micros(info.tcpi_rtt as u64),
+            rtt_var: Duration::from_micros(info.tcpi_rttvar as u64),
+            rto: Duration::from_micros(info.tcpi_rto as u64),
+            mss: info.tcpi_snd_mss,
+            cwnd: info.tcpi_snd_cwnd,
+            ssthresh: match info.tcpi_snd_ssthresh {
+                ssthresh if ssthresh >= INFINITE_SSTHRESH => None,
+                ssthresh => Some(ssthresh),
+            },
+            retransmits: info.tcpi_total_retrans as u64,
+            unacked: Some(info.tcpi_unacked),
+            lost: Some(info.tcpi_lost),
+        })
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod sys {
+    use super::TcpInfo;
+
+    use libc::c_int;
+
+    use std::cmp;
+    use std::io;
+    use std::os::unix::io::RawFd;
+    use std::time::Duration;
+
+    const TCP_CONNECTION_INFO: c_int = 0x106;
+
+    /// The value of `tcpi_snd_ssthresh` until it is first set.
+    const INFINITE_SSTHRESH: u32 = 65535 << 14;
+
+    /// `struct tcp_connection_info` of `netinet/tcp.h`.
+    #[allow(dead_code, non_camel_case_types)]
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct tcp_connection_info {
+        tcpi_state: u8,
+        tcpi_snd_wscale: u8,
+        tcpi_rcv_wscale: u8,
+        __pad1: u8,
+        tcpi_options: u32,
+        tcpi_flags: u32,
+        tcpi_rto: u32,
+        tcpi_maxseg: u32,
+        tcpi_snd_ssthresh: u32,
+        tcpi_snd_cwnd: u32,
+        tcpi_snd_wnd: u32,
+        tcpi_snd_sbbytes: u32,
+        tcpi_rcv_wnd: u32,
+        tcpi_rttcur: u32,
+        tcpi_srtt: u32,
+        tcpi_rttvar: u32,
+        tcpi_tfo: u32,
+        tcpi_txpackets: u64,
+        tcpi_txbytes: u64,
+        tcpi_txretransmitbytes: u64,
+        tcpi_rxpackets: u64,
+        tcpi_rxbytes: u64,
+        tcpi_rxoutoforderbytes: u64,
+        tcpi_txretransmitpackets: u64,
+    }
+
+    pub(super) fn get(fd: RawFd) -> io::Result<TcpInfo> {
+        let info: tcp_connection_info = super::getsockopt(fd, TCP_CONNECTION_INFO)?;
+
+        // Times are in milliseconds, sizes in bytes.
+        let mss = cmp::max(info.tcpi_maxseg, 1);
+        Ok(TcpInfo {
+            rtt: Duration::from_millis(info.tcpi_srtt as u64),
+            rtt_var: Duration::from_millis(info.tcpi_rttvar as u64),
+            rto: Duration::from_millis(info.tcpi_rto as u64),
+            mss: info.tcpi_maxseg,
+            cwnd: info.tcpi_snd_cwnd / mss,
+            ssthresh: match info.tcpi_snd_ssthresh {
+                ssthresh if ssthresh >= INFINITE_SSTHRESH => None,
+                ssthresh => Some(ssthresh / mss),
+            },
+            retransmits: info.tcpi_txretransmitpackets,
+            unacked: None,
+            lost: None,
+        })
+    }
+}
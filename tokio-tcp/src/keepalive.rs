@@ -0,0 +1,266 @@
+use std::io;
+use std::time::Duration;
+
+/// Configures the TCP keepalive probes sent on a socket.
+///
+/// Keepalive probes are sent once the connection has been idle for the
+/// keepalive [`time`], then every [`interval`] until the peer answers. The
+/// connection is dropped after [`retries`] unanswered probes. The options
+/// which are not set keep the default values of the operating system.
+///
+/// This is applied with [`TcpStream::set_tcp_keepalive`] or
+/// [`TcpBuilder::set_tcp_keepalive`].
+///
+/// # Platform-specific behavior
+///
+/// On Windows, the interval defaults to 1 second when only the time is set,
+/// and the number of retries cannot be configured. Some Unix platforms do
+/// not support configuring the interval and the number of retries either.
+/// Setting an option that is not supported makes applying the configuration
+/// fail with an error. Note that, this [may change in the future][changes].
+///
+/// [`time`]: #method.time
+/// [`interval`]: #method.interval
+/// [`retries`]: #method.retries
+/// [`TcpStream::set_tcp_keepalive`]: struct.TcpStream.html#method.set_tcp_keepalive
+/// [`TcpBuilder::set_tcp_keepalive`]: struct.TcpBuilder.html#method.set_tcp_keepalive
+/// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::net::tcp::{TcpKeepalive, TcpStream};
+/// use futures::Future;
+/// use std::net::SocketAddr;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+/// let stream = TcpStream::connect(&addr);
+/// stream.map(|stream| {
+///     let keepalive = TcpKeepalive::new()
+///         .time(Duration::from_secs(60))
+///         .interval(Duration::from_secs(10))
+///         .retries(5)
+///         .clone();
+///     stream.set_tcp_keepalive(&keepalive).expect("set_tcp_keepalive failed");
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Returns a new configuration enabling keepalive probes with the
+    /// default values of the operating system.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> TcpKeepalive {
+        TcpKeepalive::default()
+    }
+
+    /// Sets the amount of time the connection must be idle before the first
+    /// keepalive probe is sent.
+    ///
+    /// This corresponds to `TCP_KEEPIDLE` on most Unix platforms, and to
+    /// `TCP_KEEPALIVE` on macOS and iOS.
+    pub fn time(&mut self, time: Duration) -> &mut Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Sets the amount of time between two keepalive probes.
+    ///
+    /// This corresponds to `TCP_KEEPINTVL` on Unix platforms.
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets the number of unanswered keepalive probes after which the
+    /// connection is dropped.
+    ///
+    /// This corresponds to `TCP_KEEPCNT` on Unix platforms.
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = Some(retries);
+        self
+    }
+}
+
+fn unsupported(option: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!(
+            "setting the keepalive {} is not supported on this platform",
+            option
+        ),
+    )
+}
+
+#[cfg(unix)]
+pub(crate) fn set(fd: ::std::os::unix::io::RawFd, keepalive: &TcpKeepalive) -> io::Result<()> {
+    use libc::{c_int, SOL_SOCKET, SO_KEEPALIVE};
+
+    setsockopt(fd, SOL_SOCKET, SO_KEEPALIVE, 1)?;
+
+    if let Some(time) = keepalive.time {
+        setsockopt(fd, ::libc::IPPROTO_TCP, sys::KEEPALIVE_TIME, secs(time))?;
+    }
+
+    if let Some(interval) = keepalive.interval {
+        match sys::KEEPALIVE_INTERVAL {
+            Some(opt) => setsockopt(fd, ::libc::IPPROTO_TCP, opt, secs(interval))?,
+            None => return Err(unsupported("interval")),
+        }
+    }
+
+    if let Some(retries) = keepalive.retries {
+        match sys::KEEPALIVE_RETRIES {
+            Some(opt) => setsockopt(fd, ::libc::IPPROTO_TCP, opt, retries as c_int)?,
+            None => return Err(unsupported("retries")),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn setsockopt(
+    fd: ::std::os::unix::io::RawFd,
+    level: ::libc::c_int,
+    name: ::libc::c_int,
+    value: ::libc::c_int,
+) -> io::Result<()> {
+    use std::mem;
+
+    let ret = unsafe {
+        ::libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const _ as *const _,
+            mem::size_of_val(&value) as ::libc::socklen_t,
+        )
+    };
+
+    if ret == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts a duration to a number of seconds, rounding up so that a
+/// non-zero duration is not turned into zero.
+#[cfg(unix)]
+fn secs(duration: Duration) -> ::libc::c_int {
+    let secs = duration.as_secs() + (duration.subsec_nanos() > 0) as u64;
+    ::std::cmp::min(secs, ::libc::c_int::max_value() as u64) as ::libc::c_int
+}
+
+#[cfg(unix)]
+mod sys {
+    use libc::c_int;
+
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    pub const KEEPALIVE_TIME: c_int = ::libc::TCP_KEEPALIVE;
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    pub const KEEPALIVE_TIME: c_int = ::libc::TCP_KEEPIDLE;
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "fuchsia"
+    ))]
+    pub const KEEPALIVE_INTERVAL: Option<c_int> = Some(::libc::TCP_KEEPINTVL);
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "fuchsia"
+    )))]
+    pub const KEEPALIVE_INTERVAL: Option<c_int> = None;
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "fuchsia"
+    ))]
+    pub const KEEPALIVE_RETRIES: Option<c_int> = Some(::libc::TCP_KEEPCNT);
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "fuchsia"
+    )))]
+    pub const KEEPALIVE_RETRIES: Option<c_int> = None;
+}
+
+#[cfg(windows)]
+pub(crate) fn set(
+    socket: ::std::os::windows::io::RawSocket,
+    keepalive: &TcpKeepalive,
+) -> io::Result<()> {
+    use std::{mem, ptr};
+    use winapi::shared::minwindef::{DWORD, ULONG};
+    use winapi::shared::mstcpip::{tcp_keepalive, SIO_KEEPALIVE_VALS};
+    use winapi::um::winsock2::{WSAIoctl, SOCKET, SOCKET_ERROR};
+
+    if keepalive.retries.is_some() {
+        return Err(unsupported("retries"));
+    }
+
+    let millis = |duration: Duration| {
+        let millis = duration.as_secs() * 1000 + duration.subsec_nanos() as u64 / 1_000_000;
+        ::std::cmp::min(millis, ULONG::max_value() as u64) as ULONG
+    };
+
+    // Both values must be given, the defaults are those of the system.
+    let mut vals = tcp_keepalive {
+        onoff: 1,
+        keepalivetime: keepalive.time.map_or(2 * 60 * 60 * 1000, &millis),
+        keepaliveinterval: keepalive.interval.map_or(1000, &millis),
+    };
+    let mut returned: DWORD = 0;
+
+    let ret = unsafe {
+        WSAIoctl(
+            socket as SOCKET,
+            SIO_KEEPALIVE_VALS,
+            &mut vals as *mut _ as *mut _,
+            mem::size_of_val(&vals) as DWORD,
+            ptr::null_mut(),
+            0,
+            &mut returned,
+            ptr::null_mut(),
+            None,
+        )
+    };
+
+    if ret == SOCKET_ERROR {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
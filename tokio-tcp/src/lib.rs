@@ -26,15 +26,47 @@ extern crate bytes;
 #[macro_use]
 extern crate futures;
 extern crate iovec;
+#[cfg(unix)]
+extern crate libc;
 extern crate mio;
+extern crate socket2;
 extern crate tokio_io;
 extern crate tokio_reactor;
+extern crate tokio_timer;
+#[cfg(windows)]
+extern crate winapi;
 
+mod accept;
+mod builder;
+mod happy_eyeballs;
 mod incoming;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+mod info;
+mod keepalive;
 mod listener;
+mod peek;
+mod split;
 mod stream;
 
+pub use self::accept::{accept, Accept};
+pub use self::builder::TcpBuilder;
+pub use self::happy_eyeballs::{ConnectAny, HappyEyeballs};
 pub use self::incoming::Incoming;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+pub use self::info::TcpInfo;
+pub use self::keepalive::TcpKeepalive;
 pub use self::listener::TcpListener;
-pub use self::stream::ConnectFuture;
+pub use self::peek::{peek, Peek};
+pub use self::split::{OwnedReadHalf, OwnedWriteHalf, ReuniteError};
 pub use self::stream::TcpStream;
+pub use self::stream::{ConnectFuture, ConnectTimeout};
@@ -85,7 +85,9 @@ impl TcpListener {
     ///
     /// Note that typically for simple usage it's easier to treat incoming
     /// connections as a `Stream` of `TcpStream`s with the `incoming` method
-    /// below.
+    /// below, or to accept them one at a time with the [`accept`] function.
+    ///
+    /// [`accept`]: fn.accept.html
     ///
     /// # Return
     ///
@@ -370,13 +372,52 @@ impl fmt::Debug for TcpListener {
 #[cfg(unix)]
 mod sys {
     use super::TcpListener;
+    use mio;
+    use std::io;
+    use std::net;
     use std::os::unix::prelude::*;
 
+    impl TcpListener {
+        /// Consumes this listener, returning the `TcpListener` of the standard
+        /// library.
+        ///
+        /// The listener is deregistered from its event loop and is left in
+        /// nonblocking mode. Call `set_nonblocking(false)` on the returned
+        /// listener to use it for blocking I/O.
+        ///
+        /// This function is only available on Unix.
+        pub fn into_std(self) -> io::Result<net::TcpListener> {
+            let listener = self.io.into_inner()?;
+            Ok(unsafe { net::TcpListener::from_raw_fd(listener.into_raw_fd()) })
+        }
+    }
+
     impl AsRawFd for TcpListener {
         fn as_raw_fd(&self) -> RawFd {
             self.io.get_ref().as_raw_fd()
         }
     }
+
+    impl FromRawFd for TcpListener {
+        /// Creates a `TcpListener` from a raw file descriptor, such as one
+        /// inherited from systemd or launchd socket activation.
+        ///
+        /// The listener is switched to nonblocking mode and is lazily
+        /// associated with the default event loop's handle. Use [`from_std`]
+        /// to associate it with another event loop and handle errors.
+        ///
+        /// [`from_std`]: #method.from_std
+        ///
+        /// # Panics
+        ///
+        /// This function panics if the listener cannot be switched to
+        /// nonblocking mode.
+        unsafe fn from_raw_fd(fd: RawFd) -> TcpListener {
+            let listener = mio::net::TcpListener::from_std(net::TcpListener::from_raw_fd(fd))
+                .expect("failed to set the listener to nonblocking mode");
+            TcpListener::new(listener)
+        }
+    }
 }
 
 #[cfg(windows)]
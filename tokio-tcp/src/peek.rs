@@ -0,0 +1,88 @@
+use super::TcpStream;
+
+use std::io;
+use std::mem;
+
+use futures::{Future, Poll};
+
+#[derive(Debug)]
+enum State<T> {
+    Pending { stream: TcpStream, buf: T },
+    Empty,
+}
+
+/// Receives data from the stream into `buf` without removing it from the
+/// socket's queue, returning a future type.
+///
+/// The returned future will resolve to both the stream and the buffer as
+/// well as the number of bytes peeked once some data is available. The
+/// peeked data is returned again by the next read, which makes this useful
+/// to sniff the protocol spoken on a connection before handing it over.
+///
+/// See [`TcpStream::poll_peek`] for more details.
+///
+/// [`TcpStream::poll_peek`]: struct.TcpStream.html#method.poll_peek
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio;
+/// # extern crate futures;
+/// use tokio::net::tcp::{self, TcpStream};
+/// use futures::Future;
+/// use std::net::SocketAddr;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+/// let stream = TcpStream::connect(&addr)
+///     .and_then(|stream| tcp::peek(stream, [0; 1]))
+///     .map(|(_stream, buf, n)| {
+///         // A TLS handshake starts with a handshake record
+///         let is_tls = n == 1 && buf[0] == 0x16;
+///         println!("TLS: {}", is_tls);
+///     });
+/// # Ok(())
+/// # }
+/// ```
+pub fn peek<T>(stream: TcpStream, buf: T) -> Peek<T>
+where
+    T: AsMut<[u8]>,
+{
+    Peek {
+        state: State::Pending { stream, buf },
+    }
+}
+
+/// A future which peeks at the data available on a `TcpStream`.
+///
+/// Created by the [`peek`] function.
+///
+/// [`peek`]: fn.peek.html
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Peek<T> {
+    state: State<T>,
+}
+
+impl<T> Future for Peek<T>
+where
+    T: AsMut<[u8]>,
+{
+    type Item = (TcpStream, T, usize);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(TcpStream, T, usize), io::Error> {
+        let n = match self.state {
+            State::Pending {
+                ref mut stream,
+                ref mut buf,
+            } => try_ready!(stream.poll_peek(buf.as_mut())),
+            State::Empty => panic!("poll a Peek after it's done"),
+        };
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Pending { stream, buf } => Ok((stream, buf, n).into()),
+            State::Empty => panic!("invalid internal state"),
+        }
+    }
+}
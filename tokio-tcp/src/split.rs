@@ -0,0 +1,217 @@
+use super::TcpStream;
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr};
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut};
+use futures::Poll;
+use iovec::IoVec;
+use tokio_io::{AsyncRead, AsyncWrite};
+
+/// Owned read half of a [`TcpStream`], created by [`into_split`].
+///
+/// Reading from an `OwnedReadHalf` is usually done using the combinators in
+/// `tokio::io`, or by polling it directly as an [`AsyncRead`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`into_split`]: struct.TcpStream.html#method.into_split
+/// [`AsyncRead`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncRead.html
+#[derive(Debug)]
+pub struct OwnedReadHalf {
+    inner: Arc<TcpStream>,
+}
+
+/// Owned write half of a [`TcpStream`], created by [`into_split`].
+///
+/// Dropping the write half shuts down the write direction of the stream, so
+/// the peer reads EOF, unless it is [reunited] with its read half first.
+///
+/// Writing to an `OwnedWriteHalf` is usually done using the combinators in
+/// `tokio::io`, or by polling it directly as an [`AsyncWrite`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`into_split`]: struct.TcpStream.html#method.into_split
+/// [reunited]: struct.OwnedReadHalf.html#method.reunite
+/// [`AsyncWrite`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncWrite.html
+#[derive(Debug)]
+pub struct OwnedWriteHalf {
+    inner: Arc<TcpStream>,
+    shutdown_on_drop: bool,
+}
+
+/// Error returned by [`OwnedReadHalf::reunite`] when the halves do not come
+/// from the same stream.
+///
+/// The halves are handed back in the error.
+///
+/// [`OwnedReadHalf::reunite`]: struct.OwnedReadHalf.html#method.reunite
+#[derive(Debug)]
+pub struct ReuniteError(pub OwnedReadHalf, pub OwnedWriteHalf);
+
+pub(crate) fn split(stream: TcpStream) -> (OwnedReadHalf, OwnedWriteHalf) {
+    let inner = Arc::new(stream);
+
+    let read = OwnedReadHalf {
+        inner: inner.clone(),
+    };
+    let write = OwnedWriteHalf {
+        inner,
+        shutdown_on_drop: true,
+    };
+
+    (read, write)
+}
+
+impl OwnedReadHalf {
+    /// Attempts to put the two halves of a `TcpStream` back together and
+    /// recover the original stream.
+    ///
+    /// Fails with a [`ReuniteError`] if the halves come from different
+    /// streams.
+    ///
+    /// [`ReuniteError`]: struct.ReuniteError.html
+    pub fn reunite(self, other: OwnedWriteHalf) -> Result<TcpStream, ReuniteError> {
+        if !Arc::ptr_eq(&self.inner, &other.inner) {
+            return Err(ReuniteError(self, other));
+        }
+
+        let mut other = other;
+        other.shutdown_on_drop = false;
+
+        let inner = other.inner.clone();
+        drop(self);
+        drop(other);
+
+        Ok(Arc::try_unwrap(inner).expect("TcpStream: try_unwrap failed in reunite"))
+    }
+
+    /// Receives data on the socket without removing that data from the
+    /// queue.
+    ///
+    /// See [`TcpStream::poll_peek`] for more details.
+    ///
+    /// [`TcpStream::poll_peek`]: struct.TcpStream.html#method.poll_peek
+    pub fn poll_peek(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.inner.poll_peek_ref(buf)
+    }
+
+    /// Returns the remote address that this stream is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Returns the local address that this stream is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+}
+
+impl OwnedWriteHalf {
+    /// Attempts to put the two halves of a `TcpStream` back together and
+    /// recover the original stream.
+    ///
+    /// This is equivalent to [`OwnedReadHalf::reunite`].
+    ///
+    /// [`OwnedReadHalf::reunite`]: struct.OwnedReadHalf.html#method.reunite
+    pub fn reunite(self, other: OwnedReadHalf) -> Result<TcpStream, ReuniteError> {
+        other.reunite(self)
+    }
+
+    /// Drops the write half without shutting down the write direction of
+    /// the stream.
+    ///
+    /// The connection stays open for writing until the read half is dropped
+    /// too, at which point the socket is closed.
+    pub fn forget(mut self) {
+        self.shutdown_on_drop = false;
+    }
+
+    /// Returns the remote address that this stream is connected to.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Returns the local address that this stream is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+}
+
+impl AsRef<TcpStream> for OwnedReadHalf {
+    fn as_ref(&self) -> &TcpStream {
+        &self.inner
+    }
+}
+
+impl AsRef<TcpStream> for OwnedWriteHalf {
+    fn as_ref(&self) -> &TcpStream {
+        &self.inner
+    }
+}
+
+impl Read for OwnedReadHalf {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self.inner).read(buf)
+    }
+}
+
+impl AsyncRead for OwnedReadHalf {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        <&TcpStream>::poll_read_vectored(&mut &*self.inner, bufs)
+    }
+
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        <&TcpStream>::read_buf(&mut &*self.inner, buf)
+    }
+}
+
+impl Write for OwnedWriteHalf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.inner).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.inner).flush()
+    }
+}
+
+impl AsyncWrite for OwnedWriteHalf {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        <&TcpStream>::poll_write_vectored(&mut &*self.inner, bufs)
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        <&TcpStream>::shutdown(&mut &*self.inner)
+    }
+
+    fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        <&TcpStream>::write_buf(&mut &*self.inner, buf)
+    }
+}
+
+impl Drop for OwnedWriteHalf {
+    fn drop(&mut self) {
+        if self.shutdown_on_drop {
+            let _ = self.inner.shutdown(Shutdown::Write);
+        }
+    }
+}
+
+impl fmt::Display for ReuniteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.description())
+    }
+}
+
+impl Error for ReuniteError {
+    fn description(&self) -> &str {
+        "tried to reunite halves that are not from the same socket"
+    }
+}
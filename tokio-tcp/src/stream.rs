@@ -1,15 +1,27 @@
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+use super::info::{self, TcpInfo};
+use super::keepalive::{self, TcpKeepalive};
+use super::split::{self, OwnedReadHalf, OwnedWriteHalf};
+
 use std::fmt;
 use std::io::{self, Read, Write};
 use std::mem;
 use std::net::{self, Shutdown, SocketAddr};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bytes::{Buf, BufMut};
 use futures::{Async, Future, Poll};
 use iovec::IoVec;
 use mio;
+use tokio_io::io::HalfClose;
 use tokio_io::{AsyncRead, AsyncWrite};
 use tokio_reactor::{Handle, PollEvented};
+use tokio_timer::Delay;
 
 /// An I/O object representing a TCP stream connected to a remote endpoint.
 ///
@@ -52,6 +64,16 @@ pub struct ConnectFuture {
     inner: ConnectFutureState,
 }
 
+/// Future returned by `TcpStream::connect_timeout` and
+/// `TcpStream::connect_deadline` which will resolve to a `TcpStream` when the
+/// stream is connected, or fail once the deadline is reached.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct ConnectTimeout {
+    inner: ConnectFuture,
+    delay: Delay,
+}
+
 #[must_use = "futures do nothing unless polled"]
 #[derive(Debug)]
 enum ConnectFutureState {
@@ -96,6 +118,50 @@ impl TcpStream {
         ConnectFuture { inner }
     }
 
+    /// Create a new TCP stream connected to the specified address, failing if
+    /// the connection is not established within `timeout`.
+    ///
+    /// This behaves like [`connect`], except that the returned future fails
+    /// with an error of kind `TimedOut` once `timeout` has elapsed. It must be
+    /// polled from within a runtime providing a timer.
+    ///
+    /// [`connect`]: #method.connect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use futures::Future;
+    /// use tokio::net::TcpStream;
+    /// use std::net::SocketAddr;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), Box<std::error::Error>> {
+    /// let addr = "127.0.0.1:34254".parse::<SocketAddr>()?;
+    /// let stream = TcpStream::connect_timeout(&addr, Duration::from_secs(5))
+    ///     .map(|stream|
+    ///         println!("successfully connected to {}", stream.local_addr().unwrap()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_timeout(addr: &SocketAddr, timeout: Duration) -> ConnectTimeout {
+        TcpStream::connect_deadline(addr, Instant::now() + timeout)
+    }
+
+    /// Create a new TCP stream connected to the specified address, failing if
+    /// the connection is not established by `deadline`.
+    ///
+    /// See [`connect_timeout`] for more details.
+    ///
+    /// [`connect_timeout`]: #method.connect_timeout
+    pub fn connect_deadline(addr: &SocketAddr, deadline: Instant) -> ConnectTimeout {
+        ConnectTimeout {
+            inner: TcpStream::connect(addr),
+            delay: Delay::new(deadline),
+        }
+    }
+
     pub(crate) fn new(connected: mio::net::TcpStream) -> TcpStream {
         let io = PollEvented::new(connected);
         TcpStream { io }
@@ -176,7 +242,9 @@ impl TcpStream {
     /// returned and the current task is notified once a new event is received.
     ///
     /// The stream will remain in a read-ready state until calls to `poll_read`
-    /// return `NotReady`.
+    /// return `NotReady`, or calls to [`try_read`] fail with `WouldBlock`.
+    ///
+    /// [`try_read`]: #method.try_read
     ///
     /// # Panics
     ///
@@ -223,7 +291,10 @@ impl TcpStream {
     /// returned and the current task is notified once a new event is received.
     ///
     /// The I/O resource will remain in a write-ready state until calls to
-    /// `poll_write` return `NotReady`.
+    /// `poll_write` return `NotReady`, or calls to [`try_write`] fail with
+    /// `WouldBlock`.
+    ///
+    /// [`try_write`]: #method.try_write
     ///
     /// # Panics
     ///
@@ -256,6 +327,57 @@ impl TcpStream {
         self.io.poll_write_ready()
     }
 
+    /// Tries to read data from the stream into `buf`, without waiting for the
+    /// stream to be readable.
+    ///
+    /// This is meant to be used after [`poll_read_ready`] returns
+    /// `Async::Ready`, to issue as many reads as wanted for a single readiness
+    /// check. On success, returns the number of bytes read, zero meaning that
+    /// the peer closed its write half. If no data is available, an error of
+    /// kind `WouldBlock` is returned and the stream's read readiness is cleared,
+    /// so that the next call to [`poll_read_ready`] waits for data to arrive.
+    ///
+    /// [`poll_read_ready`]: #method.poll_read_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        match (&*self.io.get_ref()).read(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
+    /// Tries to write data from `buf` to the stream, without waiting for the
+    /// stream to be writable.
+    ///
+    /// This is meant to be used after [`poll_write_ready`] returns
+    /// `Async::Ready`. On success, returns the number of bytes written. If the
+    /// stream's send buffer is full, an error of kind `WouldBlock` is returned
+    /// and the stream's write readiness is cleared, so that the next call to
+    /// [`poll_write_ready`] waits for the stream to become writable again.
+    ///
+    /// [`poll_write_ready`]: #method.poll_write_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        match (&*self.io.get_ref()).write(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
     /// Returns the local address that this stream is bound to.
     ///
     /// # Examples
@@ -329,6 +451,10 @@ impl TcpStream {
     /// `Ok(Async::NotReady)` and arranges for the current task to receive a
     /// notification when the socket becomes readable or is closed.
     ///
+    /// See the [`peek`] function for a future peeking at a stream.
+    ///
+    /// [`peek`]: fn.peek.html
+    ///
     /// # Panics
     ///
     /// This function will panic if called from outside of a task context.
@@ -358,6 +484,10 @@ impl TcpStream {
     /// # }
     /// ```
     pub fn poll_peek(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.poll_peek_ref(buf)
+    }
+
+    pub(crate) fn poll_peek_ref(&self, buf: &mut [u8]) -> Poll<usize, io::Error> {
         try_ready!(self.io.poll_read_ready(mio::Ready::readable()));
 
         match self.io.get_ref().peek(buf) {
@@ -398,6 +528,45 @@ impl TcpStream {
         self.io.get_ref().shutdown(how)
     }
 
+    /// Splits the stream into an owned read half and an owned write half.
+    ///
+    /// Unlike `AsyncRead::split`, the halves do not lock each other, and can
+    /// be moved into separate tasks. Reading from the read half and writing to
+    /// the write half happen concurrently, without any synchronization.
+    ///
+    /// The halves can be put back together with [`OwnedReadHalf::reunite`].
+    /// Dropping the write half shuts down the write direction of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate tokio;
+    /// # extern crate futures;
+    /// use tokio::net::TcpStream;
+    /// use tokio::io;
+    /// use futures::Future;
+    /// use std::net::SocketAddr;
+    ///
+    /// # fn main() -> Result<(), Box<std::error::Error>> {
+    /// let addr = "127.0.0.1:8080".parse::<SocketAddr>()?;
+    /// let echo = TcpStream::connect(&addr)
+    ///     .and_then(|stream| {
+    ///         let (rd, wr) = stream.into_split();
+    ///         io::copy(rd, wr)
+    ///     })
+    ///     .map(|_| ())
+    ///     .map_err(|e| eprintln!("echo failed: {}", e));
+    ///
+    /// tokio::run(echo);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`OwnedReadHalf::reunite`]: struct.OwnedReadHalf.html#method.reunite
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        split::split(self)
+    }
+
     /// Gets the value of the `TCP_NODELAY` option on this socket.
     ///
     /// For more information about this option, see [`set_nodelay`].
@@ -634,6 +803,50 @@ impl TcpStream {
         self.io.get_ref().set_keepalive(keepalive)
     }
 
+    /// Enables keepalive messages on this socket, with the idle time, probe
+    /// interval and number of retries configured by `keepalive`.
+    ///
+    /// Keepalive messages can be disabled with [`set_keepalive`]. See
+    /// [`TcpKeepalive`] for more details.
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    /// [`TcpKeepalive`]: struct.TcpKeepalive.html
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> io::Result<()> {
+        #[cfg(unix)]
+        use std::os::unix::io::AsRawFd;
+        #[cfg(windows)]
+        use std::os::windows::io::AsRawSocket;
+
+        #[cfg(unix)]
+        let socket = self.io.get_ref().as_raw_fd();
+        #[cfg(windows)]
+        let socket = self.io.get_ref().as_raw_socket();
+
+        keepalive::set(socket, keepalive)
+    }
+
+    /// Returns the statistics of the connection kept by the operating
+    /// system, such as the round-trip time, the congestion window and the
+    /// number of retransmitted segments.
+    ///
+    /// Each call queries the operating system again, so the statistics can
+    /// be refreshed whenever needed. See [`TcpInfo`] for more details.
+    ///
+    /// This function is only available on Linux, Android, macOS and iOS.
+    ///
+    /// [`TcpInfo`]: struct.TcpInfo.html
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    ))]
+    pub fn tcp_info(&self) -> io::Result<TcpInfo> {
+        use std::os::unix::io::AsRawFd;
+
+        info::get(self.io.get_ref().as_raw_fd())
+    }
+
     /// Gets the value of the `IP_TTL` option for this socket.
     ///
     /// For more information about this option, see [`set_ttl`].
@@ -812,12 +1025,27 @@ impl AsyncRead for TcpStream {
         false
     }
 
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        <&TcpStream>::poll_read_vectored(&mut &*self, bufs)
+    }
+
     fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
         <&TcpStream>::read_buf(&mut &*self, buf)
     }
 }
 
+impl HalfClose for TcpStream {
+    fn poll_close_write(&mut self) -> Poll<(), io::Error> {
+        TcpStream::shutdown(self, Shutdown::Write)?;
+        Ok(().into())
+    }
+}
+
 impl AsyncWrite for TcpStream {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        <&TcpStream>::poll_write_vectored(&mut &*self, bufs)
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         <&TcpStream>::shutdown(&mut &*self)
     }
@@ -850,12 +1078,23 @@ impl<'a> AsyncRead for &'a TcpStream {
         false
     }
 
-    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
         if let Async::NotReady = self.io.poll_read_ready(mio::Ready::readable())? {
             return Ok(Async::NotReady);
         }
 
-        let r = unsafe {
+        match self.io.get_ref().read_bufs(bufs) {
+            Ok(n) => Ok(Async::Ready(n)),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        let n = unsafe {
             // The `IoVec` type can't have a 0-length size, so we create a bunch
             // of dummy versions on the stack with 1 length which we'll quickly
             // overwrite.
@@ -894,36 +1133,38 @@ impl<'a> AsyncRead for &'a TcpStream {
                 b16.into(),
             ];
             let n = buf.bytes_vec_mut(&mut bufs);
-            self.io.get_ref().read_bufs(&mut bufs[..n])
+            try_ready!(self.poll_read_vectored(&mut bufs[..n]))
         };
 
-        match r {
-            Ok(n) => {
-                unsafe {
-                    buf.advance_mut(n);
-                }
-                Ok(Async::Ready(n))
-            }
+        unsafe {
+            buf.advance_mut(n);
+        }
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<'a> AsyncWrite for &'a TcpStream {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        if let Async::NotReady = self.io.poll_write_ready()? {
+            return Ok(Async::NotReady);
+        }
+
+        match self.io.get_ref().write_bufs(bufs) {
+            Ok(n) => Ok(Async::Ready(n)),
             Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                self.io.clear_read_ready(mio::Ready::readable())?;
+                self.io.clear_write_ready()?;
                 Ok(Async::NotReady)
             }
             Err(e) => Err(e),
         }
     }
-}
 
-impl<'a> AsyncWrite for &'a TcpStream {
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         Ok(().into())
     }
 
     fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
-        if let Async::NotReady = self.io.poll_write_ready()? {
-            return Ok(Async::NotReady);
-        }
-
-        let r = {
+        let n = {
             // The `IoVec` type can't have a zero-length size, so create a dummy
             // version from a 1-length slice which we'll overwrite with the
             // `bytes_vec` method.
@@ -931,19 +1172,11 @@ impl<'a> AsyncWrite for &'a TcpStream {
             let iovec = <&IoVec>::from(DUMMY);
             let mut bufs = [iovec; 64];
             let n = buf.bytes_vec(&mut bufs);
-            self.io.get_ref().write_bufs(&bufs[..n])
+            try_ready!(self.poll_write_vectored(&bufs[..n]))
         };
-        match r {
-            Ok(n) => {
-                buf.advance(n);
-                Ok(Async::Ready(n))
-            }
-            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
-                self.io.clear_write_ready()?;
-                Ok(Async::NotReady)
-            }
-            Err(e) => Err(e),
-        }
+
+        buf.advance(n);
+        Ok(Async::Ready(n))
     }
 }
 
@@ -962,6 +1195,26 @@ impl Future for ConnectFuture {
     }
 }
 
+impl Future for ConnectTimeout {
+    type Item = TcpStream;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<TcpStream, io::Error> {
+        if let Async::Ready(stream) = self.inner.poll()? {
+            return Ok(Async::Ready(stream));
+        }
+
+        match self.delay.poll() {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Ok(Async::Ready(())) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "connection timed out",
+            )),
+            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+        }
+    }
+}
+
 impl ConnectFutureState {
     fn poll_inner<F>(&mut self, f: F) -> Poll<TcpStream, io::Error>
     where
@@ -1,6 +1,8 @@
 extern crate env_logger;
+#[macro_use]
 extern crate futures;
 extern crate mio;
+extern crate tokio;
 extern crate tokio_io;
 extern crate tokio_tcp;
 
@@ -133,3 +135,480 @@ mod unix {
         t.join().unwrap();
     }
 }
+
+#[test]
+fn read_write_vectored() {
+    use futures::future::poll_fn;
+    use std::io::{Read, Write};
+    use tokio_io::{AsyncRead, AsyncWrite, IoVec};
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+
+    let (tx, rx) = channel();
+    let t = thread::spawn(move || {
+        let mut s = t!(srv.accept()).0;
+        t!(s.write_all(b"hello world"));
+        tx.send(()).unwrap();
+
+        let mut buf = vec![];
+        t!(s.read_to_end(&mut buf));
+        buf
+    });
+
+    let mut stream = t!(TcpStream::connect(&addr).wait());
+
+    let n = t!(poll_fn(|| {
+        let bufs: [&IoVec; 2] = [b"hello "[..].into(), b"world"[..].into()];
+        stream.poll_write_vectored(&bufs)
+    })
+    .wait());
+    assert_eq!(n, 11);
+
+    rx.recv().unwrap();
+
+    let mut a = [0; 6];
+    let mut b = [0; 5];
+
+    let n = t!(poll_fn(|| {
+        let mut bufs: [&mut IoVec; 2] = [(&mut a[..]).into(), (&mut b[..]).into()];
+        stream.poll_read_vectored(&mut bufs)
+    })
+    .wait());
+    assert_eq!(n, 11);
+    assert_eq!(&a, b"hello ");
+    assert_eq!(&b, b"world");
+
+    t!(stream.shutdown(net::Shutdown::Write));
+    assert_eq!(t.join().unwrap(), b"hello world");
+}
+
+#[test]
+fn shutdown_then_write() {
+    use std::io::Read;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || {
+        let mut buf = [0; 5];
+        t!(t!(srv.accept()).0.read_exact(&mut buf));
+        buf
+    });
+
+    // `AsyncWrite::shutdown` only flushes the stream, which stays writable.
+    let stream = t!(TcpStream::connect(&addr).wait());
+    let stream = t!(tokio_io::io::shutdown(stream).wait());
+    t!(tokio_io::io::write_all(stream, b"hello").wait());
+
+    assert_eq!(&t.join().unwrap(), b"hello");
+}
+
+#[test]
+fn into_split() {
+    use std::io::{Read, Write};
+    use tokio_io::io;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+
+    let t = thread::spawn(move || {
+        let mut s = t!(srv.accept()).0;
+        let mut buf = vec![];
+        t!(s.read_to_end(&mut buf));
+        t!(s.write_all(&buf));
+    });
+
+    let stream = t!(TcpStream::connect(&addr).wait());
+    let (rd, wr) = stream.into_split();
+
+    let wr = thread::spawn(move || {
+        let (wr, _) = t!(io::write_all(wr, b"hello world").wait());
+        assert_eq!(t!(wr.peer_addr()), addr);
+        // Dropping the write half sends EOF to the peer
+    });
+
+    let (_, buf) = t!(io::read_to_end(rd, vec![]).wait());
+    assert_eq!(buf, b"hello world");
+
+    wr.join().unwrap();
+    t.join().unwrap();
+}
+
+#[test]
+fn reunite() {
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+
+    let t = thread::spawn(move || {
+        t!(srv.accept());
+        t!(srv.accept());
+    });
+
+    let (rd1, wr1) = t!(TcpStream::connect(&addr).wait()).into_split();
+    let (rd2, wr2) = t!(TcpStream::connect(&addr).wait()).into_split();
+
+    let err = rd1.reunite(wr2).unwrap_err();
+    let (rd1, wr2) = (err.0, err.1);
+
+    let stream = t!(rd1.reunite(wr1));
+    t!(stream.peer_addr());
+    t!(wr2.reunite(rd2));
+
+    t.join().unwrap();
+}
+
+#[test]
+fn forget_write_half() {
+    use std::io::Read;
+    use std::time::Duration;
+    use tokio_io::io;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || t!(srv.accept()).0);
+
+    let (rd, wr) = t!(TcpStream::connect(&addr).wait()).into_split();
+    let mut theirs = t.join().unwrap();
+
+    let (wr, _) = t!(io::write_all(wr, b"hi").wait());
+    wr.forget();
+
+    // The peer does not read EOF until the read half is dropped
+    let mut buf = [0; 2];
+    t!(theirs.read_exact(&mut buf));
+    t!(theirs.set_read_timeout(Some(Duration::from_millis(50))));
+    assert!(theirs.read(&mut buf).is_err());
+
+    drop(rd);
+    t!(theirs.set_read_timeout(None));
+    assert_eq!(t!(theirs.read(&mut buf)), 0);
+}
+
+#[test]
+fn connect_timeout() {
+    use std::time::{Duration, Instant};
+    use tokio::runtime::Runtime;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let listener = t!(srv.try_clone());
+    let t = thread::spawn(move || t!(listener.accept()).0);
+
+    let mut rt = t!(Runtime::new());
+
+    let connect = TcpStream::connect_timeout(&addr, Duration::from_secs(10));
+    let mine = t!(rt.block_on(connect));
+    let theirs = t.join().unwrap();
+    assert_eq!(t!(mine.local_addr()), t!(theirs.peer_addr()));
+
+    // The deadline has already passed
+    let deadline = Instant::now() - Duration::from_secs(1);
+    let connect = TcpStream::connect_deadline(&addr, deadline);
+    let err = rt.block_on(connect).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn connect_any() {
+    use std::time::Duration;
+    use tokio::runtime::Runtime;
+    use tokio_tcp::HappyEyeballs;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let listener = t!(srv.try_clone());
+    let t = thread::spawn(move || t!(listener.accept()).0);
+
+    // A port on which nothing listens
+    let closed = t!(t!(net::TcpListener::bind("127.0.0.1:0")).local_addr());
+
+    let mut rt = t!(Runtime::new());
+
+    // The failed attempt is followed by the next one right away
+    let connect = HappyEyeballs::new()
+        .attempt_delay(Duration::from_secs(60))
+        .connect(vec![closed, addr]);
+    let mine = t!(rt.block_on(connect));
+    let theirs = t.join().unwrap();
+    assert_eq!(t!(mine.local_addr()), t!(theirs.peer_addr()));
+
+    let err = rt
+        .block_on(TcpStream::connect_any(vec![closed]))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+
+    let err = rt.block_on(TcpStream::connect_any(vec![])).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn tcp_socket() {
+    use tokio::runtime::Runtime;
+    use tokio_tcp::TcpBuilder;
+
+    drop(env_logger::try_init());
+    let mut rt = t!(Runtime::new());
+
+    let socket = t!(TcpBuilder::new_v4());
+    t!(socket.set_reuseaddr(true));
+    assert!(t!(socket.reuseaddr()));
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+    t!(socket.bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(socket.local_addr());
+    let listener = t!(socket.listen(16));
+    assert_eq!(t!(listener.local_addr()), addr);
+
+    let socket = t!(TcpBuilder::for_addr(&addr));
+    t!(socket.set_send_buffer_size(64 * 1024));
+    t!(socket.bind(&t!("127.0.0.1:0".parse())));
+    let local = t!(socket.local_addr());
+
+    let accept = listener.incoming().into_future().map_err(|(e, _)| e);
+    let (mine, (theirs, _)) = t!(rt.block_on(socket.connect(&addr).join(accept)));
+    assert_eq!(t!(mine.local_addr()), local);
+    assert_eq!(t!(theirs.unwrap().peer_addr()), local);
+}
+
+#[cfg(unix)]
+#[test]
+fn tcp_socket_reuseport() {
+    use tokio::runtime::Runtime;
+    use tokio_tcp::TcpBuilder;
+
+    drop(env_logger::try_init());
+    let _rt = t!(Runtime::new());
+
+    let first = t!(TcpBuilder::new_v4());
+    t!(first.set_reuseport(true));
+    t!(first.bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(first.local_addr());
+    let _first = t!(first.listen(16));
+
+    let second = t!(TcpBuilder::new_v4());
+    t!(second.set_reuseport(true));
+    assert!(t!(second.reuseport()));
+    t!(second.bind(&addr));
+    let _second = t!(second.listen(16));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn tcp_socket_bind_device() {
+    use tokio_tcp::TcpBuilder;
+
+    drop(env_logger::try_init());
+
+    let socket = t!(TcpBuilder::new_v4());
+    assert_eq!(t!(socket.device()), None);
+
+    let err = socket.bind_device(Some("lo\0")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    // Binding to a device requires `CAP_NET_RAW`.
+    if socket.bind_device(Some("lo")).is_ok() {
+        assert_eq!(t!(socket.device()), Some("lo".to_string()));
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+#[test]
+fn tcp_info() {
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || {
+        let (mut stream, _) = t!(srv.accept());
+        t!(std::io::Write::write_all(&mut stream, b"hello"));
+        stream
+    });
+
+    let stream = t!(TcpStream::connect(&addr).wait());
+    let (stream, _) = t!(tokio_io::io::read_exact(stream, [0; 5]).wait());
+    let _theirs = t.join().unwrap();
+
+    let info = t!(stream.tcp_info());
+    assert!(info.mss() > 0);
+    assert!(info.congestion_window() > 0);
+    assert!(info.rto() > info.rtt());
+    assert_eq!(info.retransmits(), 0);
+}
+
+#[test]
+fn peek() {
+    use std::io::Write;
+    use tokio::runtime::Runtime;
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || t!(t!(srv.accept()).0.write_all(b"hello")));
+
+    let mut rt = t!(Runtime::new());
+
+    let stream = t!(rt.block_on(TcpStream::connect(&addr)));
+    t.join().unwrap();
+
+    let (stream, buf, n) = t!(rt.block_on(tokio_tcp::peek(stream, [0; 3])));
+    assert!(n > 0);
+    assert_eq!(&buf[..n], &b"hel"[..n]);
+
+    // The peeked data is read afterwards
+    let (read, _) = stream.into_split();
+    let (mut read, buf) = t!(rt.block_on(tokio_io::io::read_exact(read, [0; 5])));
+    assert_eq!(&buf, b"hello");
+
+    // The other side closed the connection
+    let mut buf = [0; 1];
+    let n = t!(rt.block_on(futures::future::poll_fn(move || read.poll_peek(&mut buf))));
+    assert_eq!(n, 0);
+}
+
+#[test]
+fn tcp_keepalive() {
+    use std::time::Duration;
+    use tokio::runtime::Runtime;
+    use tokio_tcp::{TcpBuilder, TcpKeepalive};
+
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || t!(srv.accept()).0);
+
+    let mut rt = t!(Runtime::new());
+
+    let mut keepalive = TcpKeepalive::new();
+    keepalive.time(Duration::from_secs(60));
+
+    let socket = t!(TcpBuilder::new_v4());
+    t!(socket.set_tcp_keepalive(&keepalive));
+    t!(socket.bind(&t!("127.0.0.1:0".parse())));
+    let stream = t!(rt.block_on(socket.connect(&addr)));
+    assert_eq!(t!(stream.keepalive()), Some(Duration::from_secs(60)));
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    keepalive.interval(Duration::from_secs(10)).retries(3);
+    keepalive.time(Duration::from_secs(2));
+    t!(stream.set_tcp_keepalive(&keepalive));
+    assert_eq!(t!(stream.keepalive()), Some(Duration::from_secs(2)));
+
+    t!(stream.set_keepalive(None));
+    assert_eq!(t!(stream.keepalive()), None);
+
+    t.join().unwrap();
+}
+
+#[test]
+fn accept_future() {
+    use tokio::runtime::Runtime;
+
+    drop(env_logger::try_init());
+    let mut rt = t!(Runtime::new());
+
+    let srv = t!(TcpListener::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(srv.local_addr());
+
+    let first = t!(net::TcpStream::connect(&addr));
+    let (srv, theirs, peer) = t!(rt.block_on(tokio_tcp::accept(srv)));
+    assert_eq!(peer, t!(first.local_addr()));
+    assert_eq!(t!(theirs.peer_addr()), peer);
+
+    // The listener is handed back to accept the next connection
+    let second = t!(net::TcpStream::connect(&addr));
+    let (_, _, peer) = t!(rt.block_on(tokio_tcp::accept(srv)));
+    assert_eq!(peer, t!(second.local_addr()));
+}
+
+#[cfg(unix)]
+#[test]
+fn listener_raw_fd_conversions() {
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+    drop(env_logger::try_init());
+    let std_listener = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(std_listener.local_addr());
+
+    // Inherited sockets are usually in blocking mode.
+    let listener = unsafe { TcpListener::from_raw_fd(std_listener.into_raw_fd()) };
+    assert_eq!(t!(listener.local_addr()), addr);
+
+    let t = thread::spawn(move || t!(net::TcpStream::connect(&addr)));
+    let (accepted, _) = t!(listener.incoming().into_future().map_err(|e| e.0).wait());
+    let mine = t.join().unwrap();
+    assert_eq!(t!(accepted.unwrap().peer_addr()), t!(mine.local_addr()));
+}
+
+#[cfg(unix)]
+#[test]
+fn listener_into_std() {
+    drop(env_logger::try_init());
+    let listener = t!(TcpListener::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(listener.local_addr());
+
+    let std_listener = t!(listener.into_std());
+    assert_eq!(t!(std_listener.local_addr()), addr);
+
+    // The listener is left in nonblocking mode.
+    assert_eq!(
+        std_listener.accept().unwrap_err().kind(),
+        std::io::ErrorKind::WouldBlock
+    );
+}
+
+#[test]
+fn try_read_write() {
+    drop(env_logger::try_init());
+    let srv = t!(net::TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(srv.local_addr());
+    let t = thread::spawn(move || t!(srv.accept()).0);
+
+    let stream = t!(TcpStream::connect(&addr).wait());
+    let mut theirs = t.join().unwrap();
+
+    t!(futures::future::poll_fn(|| {
+        try_ready!(stream.poll_write_ready());
+        assert_eq!(t!(stream.try_write(b"hello")), 5);
+        Ok::<_, std::io::Error>(().into())
+    })
+    .wait());
+
+    let mut buf = [0; 5];
+    t!(std::io::Read::read_exact(&mut theirs, &mut buf));
+    assert_eq!(&buf, b"hello");
+    t!(std::io::Write::write_all(&mut theirs, b"world"));
+
+    let mut received = Vec::new();
+    t!(futures::future::poll_fn(|| {
+        while received.len() < 5 {
+            try_ready!(stream.poll_read_ready(mio::Ready::readable()));
+            let mut buf = [0; 8];
+            match stream.try_read(&mut buf) {
+                Ok(n) => received.extend_from_slice(&buf[..n]),
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Everything was read, so the read readiness has been cleared.
+        let err = stream.try_read(&mut [0; 8]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+        assert!(stream
+            .poll_read_ready(mio::Ready::readable())?
+            .is_not_ready());
+        Ok::<_, std::io::Error>(().into())
+    })
+    .wait());
+    assert_eq!(received, b"world");
+}
@@ -0,0 +1,85 @@
+#![feature(test)]
+#![deny(warnings)]
+
+extern crate futures;
+extern crate test;
+extern crate tokio_threadpool;
+
+const NUM_MSGS: usize = 1_000;
+const NUM_YIELDING: usize = 16;
+
+mod threadpool {
+    use futures::future::{self, poll_fn};
+    use futures::sync::mpsc as chan;
+    use futures::{task, Async, Future, Stream};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{mpsc, Arc};
+    use test;
+    use tokio_threadpool::*;
+
+    fn ping_pong(b: &mut test::Bencher, lifo_slot: bool) {
+        let threadpool = Builder::new().lifo_slot(lifo_slot).build();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // Keep the run queues busy so that the messages have to wait behind
+        // other tasks unless they are run next.
+        for _ in 0..super::NUM_YIELDING {
+            let stop = stop.clone();
+
+            threadpool.spawn(poll_fn(move || {
+                if stop.load(Relaxed) {
+                    return Ok(Async::Ready(()));
+                }
+
+                task::current().notify();
+                Ok(Async::NotReady)
+            }));
+        }
+
+        b.iter(|| {
+            let (done_tx, done_rx) = mpsc::sync_channel(1);
+            let (ping_tx, ping_rx) = chan::unbounded::<usize>();
+            let (pong_tx, pong_rx) = chan::unbounded::<usize>();
+
+            threadpool.spawn(
+                pong_rx
+                    .for_each(move |n| {
+                        ping_tx.unbounded_send(n + 1).unwrap();
+                        Ok(())
+                    })
+                    .map_err(|_| ()),
+            );
+
+            let start = pong_tx.clone();
+
+            threadpool.spawn(
+                ping_rx
+                    .take_while(|&n| future::ok(n < super::NUM_MSGS))
+                    .for_each(move |n| {
+                        pong_tx.unbounded_send(n + 1).unwrap();
+                        Ok(())
+                    })
+                    .then(move |_| {
+                        done_tx.send(()).unwrap();
+                        Ok(())
+                    }),
+            );
+
+            start.unbounded_send(0).unwrap();
+            done_rx.recv().unwrap();
+        });
+
+        stop.store(true, Relaxed);
+    }
+
+    #[bench]
+    fn ping_pong_lifo_slot(b: &mut test::Bencher) {
+        ping_pong(b, true);
+    }
+
+    #[bench]
+    fn ping_pong_no_lifo_slot(b: &mut test::Bencher) {
+        ping_pong(b, false);
+    }
+}
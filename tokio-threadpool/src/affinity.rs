@@ -0,0 +1,180 @@
+use worker::WorkerId;
+
+use std::io;
+
+/// Core assignments computed from the user supplied callback when the pool is
+/// built.
+///
+/// Threads in the pool switch between running a worker and running a blocking
+/// section. Whenever a thread starts running a worker, it is pinned to the
+/// core assigned to that worker. Whenever a thread enters a blocking section,
+/// it is pinned to the cores that are not assigned to any worker so that
+/// blocking operations do not compete with the workers.
+#[derive(Debug)]
+pub(crate) struct Affinity {
+    /// Core assigned to each worker, indexed by worker id.
+    workers: Vec<Option<usize>>,
+
+    /// Cores available to threads running blocking sections.
+    blocking: Vec<usize>,
+
+    /// Cores the process is allowed to run on, used for workers without an
+    /// assignment.
+    all: Vec<usize>,
+}
+
+impl Affinity {
+    pub fn new<F>(pool_size: usize, f: F) -> Affinity
+    where
+        F: Fn(&WorkerId) -> Option<usize>,
+    {
+        let all = allowed();
+
+        // Assignments to cores the process may not run on are ignored.
+        let workers: Vec<_> = (0..pool_size)
+            .map(|i| f(&WorkerId::new(i)).filter(|core| all.contains(core)))
+            .collect();
+
+        let mut blocking: Vec<usize> = all
+            .iter()
+            .cloned()
+            .filter(|core| !workers.contains(&Some(*core)))
+            .collect();
+
+        if blocking.is_empty() {
+            // Every core is reserved for a worker. Rather than starving the
+            // blocking sections, let them run anywhere.
+            blocking = all.clone();
+        }
+
+        Affinity {
+            workers,
+            blocking,
+            all,
+        }
+    }
+
+    /// Pin the current thread to the core assigned to the worker `id`.
+    pub fn pin_worker(&self, id: &WorkerId) {
+        let res = match self.workers[id.0] {
+            Some(core) => set_current(&[core]),
+            None => set_current(&self.all),
+        };
+
+        if let Err(e) = res {
+            warn!("failed to set worker thread affinity; idx={}; err={:?}", id.0, e);
+        }
+    }
+
+    /// Pin the current thread to the cores reserved for blocking sections.
+    pub fn pin_blocking(&self) {
+        if let Err(e) = set_current(&self.blocking) {
+            warn!("failed to set blocking thread affinity; err={:?}", e);
+        }
+    }
+}
+
+/// Returns the cores the process is allowed to run on.
+#[cfg(target_os = "linux")]
+fn allowed() -> Vec<usize> {
+    use libc;
+    use std::mem;
+
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+
+        if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            warn!(
+                "failed to get process affinity; err={:?}",
+                io::Error::last_os_error()
+            );
+            return fallback();
+        }
+
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|&core| libc::CPU_ISSET(core, &set))
+            .collect()
+    }
+}
+
+#[cfg(windows)]
+fn allowed() -> Vec<usize> {
+    use std::mem;
+    use winapi::shared::basetsd::DWORD_PTR;
+    use winapi::um::processthreadsapi::GetCurrentProcess;
+    use winapi::um::winbase::GetProcessAffinityMask;
+
+    let mut process: DWORD_PTR = 0;
+    let mut system: DWORD_PTR = 0;
+
+    if unsafe { GetProcessAffinityMask(GetCurrentProcess(), &mut process, &mut system) } == 0 {
+        warn!(
+            "failed to get process affinity; err={:?}",
+            io::Error::last_os_error()
+        );
+        return fallback();
+    }
+
+    (0..mem::size_of::<DWORD_PTR>() * 8)
+        .filter(|&core| process & (1 << core) != 0)
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn allowed() -> Vec<usize> {
+    fallback()
+}
+
+/// Assumes the process may run on every core.
+fn fallback() -> Vec<usize> {
+    (0..::num_cpus::get()).collect()
+}
+
+#[cfg(target_os = "linux")]
+fn set_current(cores: &[usize]) -> io::Result<()> {
+    use libc;
+    use std::mem;
+
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+
+        // `CPU_SET` does not check its index against the size of the set.
+        for &core in cores.iter().filter(|&&core| core < libc::CPU_SETSIZE as usize) {
+            libc::CPU_SET(core, &mut set);
+        }
+
+        if libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn set_current(cores: &[usize]) -> io::Result<()> {
+    use std::mem;
+    use winapi::shared::basetsd::DWORD_PTR;
+    use winapi::um::processthreadsapi::GetCurrentThread;
+    use winapi::um::winbase::SetThreadAffinityMask;
+
+    let bits = mem::size_of::<DWORD_PTR>() * 8;
+
+    let mask = cores
+        .iter()
+        .filter(|&&core| core < bits)
+        .fold(0 as DWORD_PTR, |mask, &core| mask | (1 << core));
+
+    if unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Thread affinity is not supported on this platform, the assignment is
+/// ignored.
+#[cfg(not(any(target_os = "linux", windows)))]
+fn set_current(_cores: &[usize]) -> io::Result<()> {
+    Ok(())
+}
@@ -7,7 +7,45 @@ use std::fmt;
 
 /// Error raised by `blocking`.
 pub struct BlockingError {
-    _p: (),
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Kind {
+    NotOnPool,
+    AtCapacity,
+}
+
+/// What `blocking` does when the maximum number of concurrent blocking
+/// sections has been reached.
+///
+/// The policy is set with [`Builder::blocking_policy`].
+///
+/// [`Builder::blocking_policy`]: struct.Builder.html#method.blocking_policy
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BlockingPolicy {
+    /// Return `NotReady` and notify the task once capacity becomes available.
+    ///
+    /// This is the default policy.
+    Queue,
+
+    /// Return an error for which [`BlockingError::is_at_capacity`] returns
+    /// `true`.
+    ///
+    /// [`BlockingError::is_at_capacity`]: struct.BlockingError.html#method.is_at_capacity
+    FailFast,
+}
+
+/// Snapshot of the blocking capacity usage of a thread pool.
+///
+/// Obtained with [`ThreadPool::blocking_stats`].
+///
+/// [`ThreadPool::blocking_stats`]: struct.ThreadPool.html#method.blocking_stats
+#[derive(Debug, Clone, Copy)]
+pub struct BlockingStats {
+    pub(crate) max: usize,
+    pub(crate) in_use: usize,
+    pub(crate) waiting: usize,
 }
 
 /// Enter a blocking section of code.
@@ -129,7 +167,7 @@ where
         let worker = match worker {
             Some(worker) => worker,
             None => {
-                return Err(BlockingError { _p: () });
+                return Err(BlockingError::new(Kind::NotOnPool));
             }
         };
 
@@ -156,6 +194,48 @@ where
     Ok(ret.into())
 }
 
+impl BlockingError {
+    fn new(kind: Kind) -> BlockingError {
+        BlockingError { kind }
+    }
+
+    pub(crate) fn at_capacity() -> BlockingError {
+        BlockingError::new(Kind::AtCapacity)
+    }
+
+    /// Returns `true` if the error was caused by the thread pool having no
+    /// capacity left for blocking sections.
+    ///
+    /// This only happens when the pool uses [`BlockingPolicy::FailFast`].
+    ///
+    /// [`BlockingPolicy::FailFast`]: enum.BlockingPolicy.html#variant.FailFast
+    pub fn is_at_capacity(&self) -> bool {
+        self.kind == Kind::AtCapacity
+    }
+}
+
+impl BlockingStats {
+    /// Returns the maximum number of concurrent blocking sections.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the number of blocking sections that currently hold capacity,
+    /// i.e. the number of threads in use for blocking operations.
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    /// Returns the number of tasks waiting for blocking capacity.
+    ///
+    /// This is always zero when the pool uses [`BlockingPolicy::FailFast`].
+    ///
+    /// [`BlockingPolicy::FailFast`]: enum.BlockingPolicy.html#variant.FailFast
+    pub fn waiting(&self) -> usize {
+        self.waiting
+    }
+}
+
 impl fmt::Display for BlockingError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(fmt, "{}", self.description())
@@ -172,6 +252,11 @@ impl fmt::Debug for BlockingError {
 
 impl Error for BlockingError {
     fn description(&self) -> &str {
-        "`blocking` annotation used from outside the context of a thread pool"
+        match self.kind {
+            Kind::NotOnPool => {
+                "`blocking` annotation used from outside the context of a thread pool"
+            }
+            Kind::AtCapacity => "thread pool has no capacity left for blocking sections",
+        }
     }
 }
@@ -1,10 +1,12 @@
+use affinity::Affinity;
 use callback::Callback;
 use config::{Config, MAX_WORKERS};
 use park::{BoxPark, BoxedPark, DefaultPark};
-use pool::{Pool, MAX_BACKUP};
+use pool::{GlobalQueue, Pool, MAX_BACKUP};
 use shutdown::ShutdownTrigger;
 use thread_pool::ThreadPool;
-use worker::{self, Worker, WorkerId};
+use worker::{self, Worker, WorkerContext, WorkerId};
+use BlockingPolicy;
 
 use std::cmp::max;
 use std::error::Error;
@@ -12,7 +14,6 @@ use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
-use crossbeam_deque::Injector;
 use num_cpus;
 use tokio_executor::park::Park;
 use tokio_executor::Enter;
@@ -61,12 +62,21 @@ pub struct Builder {
     /// Number of workers to spawn
     pool_size: usize,
 
+    /// Number of workers the pool can be resized to
+    max_pool_size: Option<usize>,
+
     /// Maximum number of futures that can be in a blocking section
     /// concurrently.
     max_blocking: usize,
 
+    /// Maximum number of spawned futures that have not yet been polled.
+    max_queued: Option<usize>,
+
     /// Generates the `Park` instances
     new_park: Box<Fn(&WorkerId) -> BoxPark>,
+
+    /// Assigns cores to workers
+    core_affinity: Option<Box<Fn(&WorkerId) -> Option<usize>>>,
 }
 
 impl Builder {
@@ -98,7 +108,9 @@ impl Builder {
 
         Builder {
             pool_size: num_cpus,
+            max_pool_size: None,
             max_blocking: 100,
+            max_queued: None,
             config: Config {
                 keep_alive: None,
                 name_prefix: None,
@@ -106,8 +118,18 @@ impl Builder {
                 around_worker: None,
                 after_start: None,
                 before_stop: None,
+                on_park: None,
+                on_unpark: None,
+                affinity: None,
+                blocking_policy: BlockingPolicy::Queue,
+                task_dump: false,
+                lifo_slot: true,
+                global_queue_interval: 32,
+                #[cfg(feature = "trace")]
+                dispatch: ::tokio_trace::Dispatch::none(),
             },
             new_park,
+            core_affinity: None,
         }
     }
 
@@ -139,6 +161,45 @@ impl Builder {
         self
     }
 
+    /// Set the maximum number of worker threads the thread pool can be resized
+    /// to with [`ThreadPool::resize`].
+    ///
+    /// The pool starts with [`pool_size`] workers. Resources for the additional
+    /// workers, such as the `park` instances created by [`custom_park`], are
+    /// allocated when the pool is built.
+    ///
+    /// This must be a number between 1 and 32,768. Values smaller than the
+    /// pool size are ignored. The default value is the pool size, which only
+    /// allows the pool to shrink and grow back to its initial size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .pool_size(4)
+    ///     .max_pool_size(16)
+    ///     .build();
+    ///
+    /// thread_pool.resize(8);
+    /// # }
+    /// ```
+    ///
+    /// [`ThreadPool::resize`]: struct.ThreadPool.html#method.resize
+    /// [`pool_size`]: #method.pool_size
+    /// [`custom_park`]: #method.custom_park
+    pub fn max_pool_size(&mut self, val: usize) -> &mut Self {
+        assert!(val >= 1, "at least one thread required");
+        assert!(val <= MAX_WORKERS, "max value is {}", MAX_WORKERS);
+
+        self.max_pool_size = Some(val);
+        self
+    }
+
     /// Set the maximum number of concurrent blocking sections.
     ///
     /// When the maximum concurrent `blocking` calls is reached, any further
@@ -169,6 +230,72 @@ impl Builder {
         self
     }
 
+    /// Set what happens when `blocking` is called while the maximum number of
+    /// concurrent blocking sections has been reached.
+    ///
+    /// With [`BlockingPolicy::Queue`], the task is queued and notified once
+    /// capacity becomes available. With [`BlockingPolicy::FailFast`],
+    /// `blocking` returns an error right away, letting the caller shed load.
+    ///
+    /// The default value is [`BlockingPolicy::Queue`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::{Builder, BlockingPolicy};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .max_blocking(16)
+    ///     .blocking_policy(BlockingPolicy::FailFast)
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`BlockingPolicy::Queue`]: enum.BlockingPolicy.html#variant.Queue
+    /// [`BlockingPolicy::FailFast`]: enum.BlockingPolicy.html#variant.FailFast
+    pub fn blocking_policy(&mut self, val: BlockingPolicy) -> &mut Self {
+        self.config.blocking_policy = val;
+        self
+    }
+
+    /// Set the maximum number of spawned futures that have not yet started
+    /// executing.
+    ///
+    /// Once this many futures are queued, spawning a new future fails with an
+    /// "at capacity" error until a worker picks up one of the queued futures.
+    /// [`Sender::try_spawn`] hands the future back to the caller in that case
+    /// and [`Sender::spawn_when_ready`] waits for capacity to become available.
+    ///
+    /// Futures that have already been polled once do not count towards this
+    /// limit, regardless of how many times they are scheduled afterwards.
+    ///
+    /// By default, the number of queued futures is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .max_queued(1_024)
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`Sender::try_spawn`]: struct.Sender.html#method.try_spawn
+    /// [`Sender::spawn_when_ready`]: struct.Sender.html#method.spawn_when_ready
+    pub fn max_queued(&mut self, val: usize) -> &mut Self {
+        assert!(val > 0, "max queued must be greater than zero");
+        self.max_queued = Some(val);
+        self
+    }
+
     /// Set the thread keep alive duration
     ///
     /// If set, a thread that has completed a `blocking` call will wait for up
@@ -199,6 +326,106 @@ impl Builder {
         self
     }
 
+    /// Track the live tasks of the thread pool so that they can be dumped.
+    ///
+    /// When enabled, [`ThreadPool::dump`] returns a snapshot of the tasks
+    /// that are live on the pool, which helps finding out what the pool is
+    /// doing when an application hangs. Tracking tasks adds some overhead to
+    /// spawning and completing tasks.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .task_dump(true)
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`ThreadPool::dump`]: struct.ThreadPool.html#method.dump
+    pub fn task_dump(&mut self, val: bool) -> &mut Self {
+        self.config.task_dump = val;
+        self
+    }
+
+    /// Run tasks notified by a worker next on that worker.
+    ///
+    /// When a task running on a worker notifies another task, for example by
+    /// sending it a message over a channel, the notified task is stored in a
+    /// single slot of the worker and runs as soon as the current task yields,
+    /// instead of waiting for the tasks already queued on the worker. This
+    /// greatly reduces latency of message-passing workloads. To prevent two
+    /// tasks notifying each other from starving the rest of the queue, only a
+    /// few tasks are run from the slot in a row.
+    ///
+    /// Tasks in the slot cannot be stolen by other workers.
+    ///
+    /// The default value is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .lifo_slot(false)
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn lifo_slot(&mut self, val: bool) -> &mut Self {
+        self.config.lifo_slot = val;
+        self
+    }
+
+    /// Set how many tasks a worker runs between two checks of the global
+    /// queue.
+    ///
+    /// Futures spawned from outside of the pool are pushed onto a global
+    /// queue, while futures notified from a worker stay on that worker. A busy
+    /// worker only picks up futures from the global queue every `val` tasks
+    /// it runs, at which point it also polls its park (the reactor, when used
+    /// by the runtime) for events.
+    ///
+    /// A lower value gives newly spawned futures and I/O events a fairer
+    /// share of the workers, at the cost of more contention on the global
+    /// queue and more frequent calls to the park. A higher value favors
+    /// throughput of the futures already running on the pool.
+    ///
+    /// The default value is 32.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `val` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .global_queue_interval(8)
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn global_queue_interval(&mut self, val: usize) -> &mut Self {
+        assert!(val > 0, "global queue interval must be greater than zero");
+        self.config.global_queue_interval = val;
+        self
+    }
+
     /// Set name prefix of threads spawned by the scheduler
     ///
     /// Thread name prefix is used for generating thread names. For example, if
@@ -288,7 +515,12 @@ impl Builder {
     /// Execute function `f` after each thread is started but before it starts
     /// doing work.
     ///
-    /// This is intended for bookkeeping and monitoring use cases.
+    /// This is intended for bookkeeping and monitoring use cases. Use
+    /// [`on_worker_start`] if the callback needs to know which worker is
+    /// starting. Only one start callback is kept, so calling this function
+    /// replaces any callback previously set with [`on_worker_start`].
+    ///
+    /// [`on_worker_start`]: #method.on_worker_start
     ///
     /// # Examples
     ///
@@ -308,6 +540,40 @@ impl Builder {
     pub fn after_start<F>(&mut self, f: F) -> &mut Self
     where
         F: Fn() + Send + Sync + 'static,
+    {
+        self.config.after_start = Some(Arc::new(move |_: &WorkerContext| f()));
+        self
+    }
+
+    /// Execute function `f` after each thread is started but before it starts
+    /// doing work, passing it the identity of the worker being started.
+    ///
+    /// This is intended for configuring the worker thread, for example setting
+    /// its priority, or for registering per-worker monitoring. Only one start
+    /// callback is kept, so calling this function replaces any callback
+    /// previously set with [`after_start`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .on_worker_start(|cx| {
+    ///         println!("worker {} started on thread {:?}",
+    ///                  cx.worker_id().to_usize(), cx.thread_id());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`after_start`]: #method.after_start
+    pub fn on_worker_start<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerContext) + Send + Sync + 'static,
     {
         self.config.after_start = Some(Arc::new(f));
         self
@@ -315,7 +581,12 @@ impl Builder {
 
     /// Execute function `f` before each thread stops.
     ///
-    /// This is intended for bookkeeping and monitoring use cases.
+    /// This is intended for bookkeeping and monitoring use cases. Use
+    /// [`on_worker_stop`] if the callback needs to know which worker is
+    /// stopping. Only one stop callback is kept, so calling this function
+    /// replaces any callback previously set with [`on_worker_stop`].
+    ///
+    /// [`on_worker_stop`]: #method.on_worker_stop
     ///
     /// # Examples
     ///
@@ -335,11 +606,148 @@ impl Builder {
     pub fn before_stop<F>(&mut self, f: F) -> &mut Self
     where
         F: Fn() + Send + Sync + 'static,
+    {
+        self.config.before_stop = Some(Arc::new(move |_: &WorkerContext| f()));
+        self
+    }
+
+    /// Execute function `f` before each thread stops, passing it the identity
+    /// of the last worker that ran on the thread.
+    ///
+    /// Only one stop callback is kept, so calling this function replaces any
+    /// callback previously set with [`before_stop`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .on_worker_stop(|cx| {
+    ///         println!("worker {} stopping", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`before_stop`]: #method.before_stop
+    pub fn on_worker_stop<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerContext) + Send + Sync + 'static,
     {
         self.config.before_stop = Some(Arc::new(f));
         self
     }
 
+    /// Execute function `f` each time a worker runs out of work and is about
+    /// to go to sleep.
+    ///
+    /// The callback is invoked on the worker thread right before it is parked.
+    /// It is not invoked for the short, non-blocking polls of the `park`
+    /// instance that workers perform while busy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .on_park(|cx| {
+    ///         println!("worker {} parking", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    pub fn on_park<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerContext) + Send + Sync + 'static,
+    {
+        self.config.on_park = Some(Arc::new(f));
+        self
+    }
+
+    /// Execute function `f` each time a sleeping worker is woken up to process
+    /// work.
+    ///
+    /// Every call to the [`on_park`] callback is followed by a call to this
+    /// callback once the worker wakes up, including when it is woken up to
+    /// shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .on_unpark(|cx| {
+    ///         println!("worker {} unparked", cx.worker_id().to_usize());
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`on_park`]: #method.on_park
+    pub fn on_unpark<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerContext) + Send + Sync + 'static,
+    {
+        self.config.on_unpark = Some(Arc::new(f));
+        self
+    }
+
+    /// Pin worker threads to CPU cores.
+    ///
+    /// The provided closure `f` is called once per worker when the pool is
+    /// built and returns the index of the core that the worker should run on,
+    /// or `None` to let the worker run on any core. Whenever a thread starts
+    /// running a worker, it is pinned to the worker's core before any other
+    /// callback is invoked.
+    ///
+    /// Cores are numbered by the operating system. Only the cores the process
+    /// is allowed to run on are used, an assignment to any other core is
+    /// ignored as if `f` returned `None`.
+    ///
+    /// Threads that enter a [`blocking`] section are moved to the allowed
+    /// cores that are not assigned to any worker. If every allowed core is
+    /// assigned to a worker, blocking sections may run on any of them.
+    ///
+    /// Thread affinity is supported on Linux and Windows. On other platforms,
+    /// the assignment is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    ///
+    /// # pub fn main() {
+    /// // Pin worker N to core N.
+    /// let thread_pool = Builder::new()
+    ///     .pool_size(2)
+    ///     .core_affinity(|id| Some(id.to_usize()))
+    ///     .build();
+    /// # }
+    /// ```
+    ///
+    /// [`blocking`]: fn.blocking.html
+    pub fn core_affinity<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&WorkerId) -> Option<usize> + 'static,
+    {
+        self.core_affinity = Some(Box::new(f));
+        self
+    }
+
     /// Customize the `park` instance used by each worker thread.
     ///
     /// The provided closure `f` is called once per worker and returns a `Park`
@@ -403,7 +811,7 @@ impl Builder {
         let workers: Arc<[worker::Entry]> = {
             let mut workers = vec![];
 
-            for i in 0..self.pool_size {
+            for i in 0..self.num_workers() {
                 let id = WorkerId::new(i);
                 let park = (self.new_park)(&id);
                 let unpark = park.unpark();
@@ -414,7 +822,7 @@ impl Builder {
             workers.into()
         };
 
-        let queue = Arc::new(Injector::new());
+        let queue = Arc::new(GlobalQueue::new());
 
         // Create a trigger that will clean up resources on shutdown.
         //
@@ -422,17 +830,36 @@ impl Builder {
         // strong references.
         let trigger = Arc::new(ShutdownTrigger::new(workers.clone(), queue.clone()));
 
+        let mut config = self.config.clone();
+
+        // Worker threads report events to the subscriber of the thread
+        // building the pool.
+        #[cfg(feature = "trace")]
+        {
+            config.dispatch = ::tokio_trace::dispatcher::with(|d| d.clone());
+        }
+
+        if let Some(ref f) = self.core_affinity {
+            config.affinity = Some(Arc::new(Affinity::new(self.num_workers(), f)));
+        }
+
         // Create the pool
         let pool = Arc::new(Pool::new(
             workers,
+            self.pool_size,
             Arc::downgrade(&trigger),
             self.max_blocking,
-            self.config.clone(),
+            self.max_queued,
+            config,
             queue,
         ));
 
         ThreadPool::new2(pool, trigger)
     }
+
+    fn num_workers(&self) -> usize {
+        max(self.pool_size, self.max_pool_size.unwrap_or(0))
+    }
 }
 
 impl fmt::Debug for Builder {
@@ -440,6 +867,8 @@ impl fmt::Debug for Builder {
         fmt.debug_struct("Builder")
             .field("config", &self.config)
             .field("pool_size", &self.pool_size)
+            .field("max_pool_size", &self.max_pool_size)
+            .field("max_queued", &self.max_queued)
             .field("new_park", &"Box<Fn() -> BoxPark>")
             .finish()
     }
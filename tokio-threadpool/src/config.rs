@@ -1,4 +1,7 @@
+use affinity::Affinity;
 use callback::Callback;
+use BlockingPolicy;
+use worker::WorkerContext;
 
 use std::fmt;
 use std::sync::Arc;
@@ -12,8 +15,18 @@ pub(crate) struct Config {
     pub name_prefix: Option<String>,
     pub stack_size: Option<usize>,
     pub around_worker: Option<Callback>,
-    pub after_start: Option<Arc<Fn() + Send + Sync>>,
-    pub before_stop: Option<Arc<Fn() + Send + Sync>>,
+    pub after_start: Option<Arc<Fn(&WorkerContext) + Send + Sync>>,
+    pub before_stop: Option<Arc<Fn(&WorkerContext) + Send + Sync>>,
+    pub on_park: Option<Arc<Fn(&WorkerContext) + Send + Sync>>,
+    pub on_unpark: Option<Arc<Fn(&WorkerContext) + Send + Sync>>,
+    pub affinity: Option<Arc<Affinity>>,
+    pub blocking_policy: BlockingPolicy,
+    pub task_dump: bool,
+    pub lifo_slot: bool,
+    pub global_queue_interval: usize,
+    // The `tokio-trace` dispatcher of the thread that built the pool
+    #[cfg(feature = "trace")]
+    pub dispatch: ::tokio_trace::Dispatch,
 }
 
 /// Max number of workers that can be part of a pool. This is the most that can
@@ -21,12 +34,37 @@ pub(crate) struct Config {
 /// threads. There can be more standby threads.
 pub(crate) const MAX_WORKERS: usize = 1 << 15;
 
+impl Config {
+    /// Run `f` with the pool's `tokio-trace` dispatcher set as the default.
+    #[cfg(feature = "trace")]
+    pub fn with_dispatch<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        ::tokio_trace::dispatcher::with_default(self.dispatch.clone(), f)
+    }
+
+    /// Run `f`, tracing is disabled.
+    #[cfg(not(feature = "trace"))]
+    pub fn with_dispatch<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        f()
+    }
+}
+
 impl fmt::Debug for Config {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Config")
             .field("keep_alive", &self.keep_alive)
             .field("name_prefix", &self.name_prefix)
             .field("stack_size", &self.stack_size)
+            .field("affinity", &self.affinity)
+            .field("blocking_policy", &self.blocking_policy)
+            .field("task_dump", &self.task_dump)
+            .field("lifo_slot", &self.lifo_slot)
+            .field("global_queue_interval", &self.global_queue_interval)
             .finish()
     }
 }
@@ -0,0 +1,220 @@
+use task::Task;
+
+use slab::Slab;
+
+use std::fmt;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::{Arc, Mutex, Weak};
+use std::usize;
+
+/// A snapshot of the tasks that are live on a thread pool.
+///
+/// A dump is returned by [`ThreadPool::dump`] when task dumps are enabled with
+/// [`Builder::task_dump`]. Each task is described by a [`TaskDump`]. The
+/// `Display` implementation prints one line per task, which is handy to log
+/// when diagnosing a hang.
+///
+/// [`ThreadPool::dump`]: struct.ThreadPool.html#method.dump
+/// [`Builder::task_dump`]: struct.Builder.html#method.task_dump
+/// [`TaskDump`]: struct.TaskDump.html
+#[derive(Debug, Clone)]
+pub struct Dump {
+    tasks: Vec<TaskDump>,
+}
+
+/// Describes a single task of a [`Dump`].
+///
+/// [`Dump`]: struct.Dump.html
+#[derive(Debug, Clone)]
+pub struct TaskDump {
+    name: Option<String>,
+    location: Option<SpawnLocation>,
+    state: TaskState,
+    polls: usize,
+}
+
+/// The state of a task at the time it was dumped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// The task is waiting to be notified.
+    Idle,
+
+    /// The task has been notified and is queued to be polled.
+    Scheduled,
+
+    /// The task is being polled by a worker.
+    Running,
+
+    /// The task is waiting for capacity to enter a blocking section.
+    Blocked,
+}
+
+/// The location in the source code a task was spawned from.
+///
+/// Locations are captured by the [`spawn!`] macro.
+///
+/// [`spawn!`]: macro.spawn.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpawnLocation {
+    file: &'static str,
+    line: u32,
+}
+
+/// Tracks the live tasks of a pool when task dumps are enabled.
+///
+/// Tasks are held weakly, so that a task which is dropped without completing,
+/// such as when the pool shuts down, is not kept alive by the registry.
+#[derive(Debug)]
+pub(crate) struct Registry {
+    tasks: Mutex<Slab<Weak<Task>>>,
+}
+
+/// Key of a task that is not in the registry.
+pub(crate) const UNREGISTERED: usize = usize::MAX;
+
+// ===== impl Dump =====
+
+impl Dump {
+    pub(crate) fn new(tasks: Vec<TaskDump>) -> Dump {
+        Dump { tasks }
+    }
+
+    /// Returns the dumped tasks, in no particular order.
+    pub fn tasks(&self) -> &[TaskDump] {
+        &self.tasks
+    }
+}
+
+impl fmt::Display for Dump {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for task in &self.tasks {
+            writeln!(fmt, "{}", task)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ===== impl TaskDump =====
+
+impl TaskDump {
+    pub(crate) fn new(
+        name: Option<String>,
+        location: Option<SpawnLocation>,
+        state: TaskState,
+        polls: usize,
+    ) -> TaskDump {
+        TaskDump {
+            name,
+            location,
+            state,
+            polls,
+        }
+    }
+
+    /// Returns the name the task was spawned with, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| &name[..])
+    }
+
+    /// Returns the location the task was spawned from, if it was captured.
+    pub fn location(&self) -> Option<SpawnLocation> {
+        self.location
+    }
+
+    /// Returns the state of the task.
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    /// Returns the number of times the task has been polled.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+}
+
+impl fmt::Display for TaskDump {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(fmt, "task {:?}", name)?,
+            None => write!(fmt, "unnamed task")?,
+        }
+
+        if let Some(location) = self.location {
+            write!(fmt, " spawned at {}", location)?;
+        }
+
+        write!(fmt, ": {:?}, polled {} times", self.state, self.polls)
+    }
+}
+
+// ===== impl SpawnLocation =====
+
+impl SpawnLocation {
+    /// Create a new `SpawnLocation`.
+    ///
+    /// This is usually called by the [`spawn!`] macro with the values of the
+    /// `file!` and `line!` macros.
+    ///
+    /// [`spawn!`]: macro.spawn.html
+    pub fn new(file: &'static str, line: u32) -> SpawnLocation {
+        SpawnLocation { file, line }
+    }
+
+    /// Returns the name of the source file.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Returns the line in the source file.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+}
+
+impl fmt::Display for SpawnLocation {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}:{}", self.file, self.line)
+    }
+}
+
+// ===== impl Registry =====
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry {
+            tasks: Mutex::new(Slab::new()),
+        }
+    }
+
+    /// Track `task` until it is unregistered.
+    pub fn register(&self, task: &Arc<Task>) {
+        let key = self.tasks.lock().unwrap().insert(Arc::downgrade(task));
+        task.dump_key.store(key, Relaxed);
+    }
+
+    /// Stop tracking `task`, called once the task has completed.
+    pub fn unregister(&self, task: &Task) {
+        let key = task.dump_key.swap(UNREGISTERED, Relaxed);
+
+        if key != UNREGISTERED {
+            self.tasks.lock().unwrap().remove(key);
+        }
+    }
+
+    pub fn dump(&self) -> Dump {
+        let mut tasks = self.tasks.lock().unwrap();
+        let mut dumps = vec![];
+
+        // Tasks dropped without being unregistered are removed on the way.
+        tasks.retain(|_, task| match task.upgrade() {
+            Some(task) => {
+                dumps.extend(task.dump());
+                true
+            }
+            None => false,
+        });
+
+        Dump::new(dumps)
+    }
+}
@@ -74,7 +74,16 @@
 //! thread has no additional work and is inserted into the backup pool. This
 //! makes it available to other workers that encounter a [`blocking`] call.
 //!
+//! ## Tracing
+//!
+//! When the `trace` feature is enabled, the pool emits [`tokio-trace`] events
+//! as workers start and stop, park and unpark, attempt to steal tasks, and
+//! transition in and out of blocking sections. Each event has a `worker` field
+//! holding the worker index. Worker threads report events to the subscriber
+//! that was the default on the thread that built the pool.
+//!
 //! [`blocking`]: fn.blocking.html
+//! [`tokio-trace`]: https://github.com/tokio-rs/tokio/tree/master/tokio-trace
 //! [`runtime`]: https://docs.rs/tokio/0.1/tokio/runtime/
 
 extern crate tokio_executor;
@@ -91,6 +100,18 @@ extern crate slab;
 #[macro_use]
 extern crate log;
 
+#[cfg(feature = "trace")]
+#[macro_use(event, is_enabled, valueset)]
+extern crate tokio_trace;
+#[cfg(feature = "trace")]
+#[macro_use(metadata, identify_callsite)]
+extern crate tokio_trace_core;
+
+#[cfg(target_os = "linux")]
+extern crate libc;
+#[cfg(windows)]
+extern crate winapi;
+
 // ## Crate layout
 //
 // The primary type, `Pool`, holds the majority of a thread pool's state,
@@ -141,23 +162,31 @@ extern crate log;
 //
 // [Treiber stack]: https://en.wikipedia.org/wiki/Treiber_Stack
 
+#[macro_use]
+mod macros;
+
 pub mod park;
 
+mod affinity;
 mod blocking;
 mod builder;
 mod callback;
 mod config;
+mod dump;
 mod notifier;
 mod pool;
+mod priority;
 mod sender;
 mod shutdown;
 mod task;
 mod thread_pool;
 mod worker;
 
-pub use blocking::{blocking, BlockingError};
+pub use blocking::{blocking, BlockingError, BlockingPolicy, BlockingStats};
 pub use builder::Builder;
-pub use sender::Sender;
+pub use dump::{Dump, SpawnLocation, TaskDump, TaskState};
+pub use priority::Priority;
+pub use sender::{Sender, SpawnWhenReady};
 pub use shutdown::Shutdown;
 pub use thread_pool::{SpawnHandle, ThreadPool};
-pub use worker::{Worker, WorkerId};
+pub use worker::{Worker, WorkerContext, WorkerId, WorkerStats};
@@ -0,0 +1,63 @@
+/// Emit a `tokio-trace` event describing the scheduler's behavior.
+///
+/// The event is only emitted when the `trace` feature is enabled, in
+/// which case it is recorded at the `TRACE` level, with the fields and message
+/// given as in `tokio_trace::event!`. Otherwise, the macro expands to nothing.
+#[cfg(feature = "trace")]
+macro_rules! trace_event {
+    ($($arg:tt)+) => {
+        event!(::tokio_trace::Level::TRACE, $($arg)+)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_event {
+    ($($arg:tt)+) => {};
+}
+
+/// Spawn a future, recording the location of the call for task dumps.
+///
+/// `spawn!(executor, future)` spawns `future` with `executor.spawn_at`, and
+/// `spawn!(executor, name, future)` spawns it with `executor.spawn_named_at`,
+/// passing the file and line the macro is invoked from. This works with
+/// [`ThreadPool`] and [`Sender`], as well as with any other executor providing
+/// these functions.
+///
+/// The location is reported by [`ThreadPool::dump`].
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate tokio_threadpool;
+/// extern crate futures;
+///
+/// use tokio_threadpool::ThreadPool;
+/// use futures::future::{Future, lazy};
+///
+/// # pub fn main() {
+/// let thread_pool = ThreadPool::new();
+///
+/// spawn!(thread_pool, lazy(|| {
+///     println!("called from a worker thread");
+///     Ok(())
+/// }));
+///
+/// spawn!(thread_pool.sender(), "named", lazy(|| Ok(()))).unwrap();
+///
+/// thread_pool.shutdown_on_idle().wait().unwrap();
+/// # }
+/// ```
+///
+/// [`ThreadPool`]: struct.ThreadPool.html
+/// [`Sender`]: struct.Sender.html
+/// [`ThreadPool::dump`]: struct.ThreadPool.html#method.dump
+#[macro_export]
+macro_rules! spawn {
+    ($executor:expr, $future:expr) => {
+        $executor.spawn_at($crate::SpawnLocation::new(file!(), line!()), $future)
+    };
+    ($executor:expr, $name:expr, $future:expr) => {
+        $executor.spawn_named_at($crate::SpawnLocation::new(file!(), line!()), $name, $future)
+    };
+}
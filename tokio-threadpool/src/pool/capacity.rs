@@ -0,0 +1,113 @@
+use futures::task::{self, Task};
+use futures::Async;
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{AcqRel, Acquire};
+use std::sync::Mutex;
+
+/// Limits the number of tasks that have been spawned but not yet polled.
+///
+/// A slot is acquired when a future is spawned onto the pool and released the
+/// first time a worker polls the resulting task. Producers that need to wait
+/// for a slot register themselves in `waiters` and are notified as slots get
+/// released.
+#[derive(Debug)]
+pub(crate) struct SpawnCapacity {
+    /// Maximum number of queued tasks, `None` if unbounded.
+    max: Option<usize>,
+
+    /// Number of spawned tasks that have not been polled yet.
+    queued: AtomicUsize,
+
+    /// Tasks waiting for a slot to become available.
+    waiters: Mutex<VecDeque<Task>>,
+}
+
+impl SpawnCapacity {
+    pub fn new(max: Option<usize>) -> SpawnCapacity {
+        SpawnCapacity {
+            max,
+            queued: AtomicUsize::new(0),
+            waiters: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns `true` if the pool cannot accept any more tasks.
+    pub fn is_full(&self) -> bool {
+        match self.max {
+            Some(max) => self.queued.load(Acquire) >= max,
+            None => false,
+        }
+    }
+
+    /// Attempt to acquire a slot for a new task.
+    pub fn try_acquire(&self) -> bool {
+        let max = match self.max {
+            Some(max) => max,
+            None => return true,
+        };
+
+        let mut queued = self.queued.load(Acquire);
+
+        loop {
+            if queued >= max {
+                return false;
+            }
+
+            let actual = self.queued.compare_and_swap(queued, queued + 1, AcqRel);
+
+            if actual == queued {
+                return true;
+            }
+
+            queued = actual;
+        }
+    }
+
+    /// Release a slot acquired by `try_acquire`, notifying a waiting producer.
+    pub fn release(&self) {
+        if self.max.is_none() {
+            return;
+        }
+
+        self.queued.fetch_sub(1, AcqRel);
+
+        if let Some(task) = self.waiters.lock().unwrap().pop_front() {
+            task.notify();
+        }
+    }
+
+    /// Returns `Ready` once a slot is available, registering the current task
+    /// to be notified otherwise.
+    pub fn poll_ready(&self) -> Async<()> {
+        if !self.is_full() {
+            return Async::Ready(());
+        }
+
+        let mut waiters = self.waiters.lock().unwrap();
+
+        // Check again while holding the lock. A slot released after this point
+        // will see the task in the waiter list.
+        if !self.is_full() {
+            return Async::Ready(());
+        }
+
+        let task = task::current();
+
+        if !waiters.iter().any(|t| t.will_notify_current()) {
+            waiters.push_back(task);
+        }
+
+        Async::NotReady
+    }
+
+    /// Notify all waiting producers, used when the pool shuts down.
+    pub fn notify_all(&self) {
+        let waiters: Vec<_> = self.waiters.lock().unwrap().drain(..).collect();
+
+        for task in waiters {
+            task.notify();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use pool::State;
+
+use futures::task::AtomicTask;
+use futures::Async;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Acquire;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Tracks how long the pool has been without any futures.
+///
+/// This is updated whenever the number of futures transitions between zero and
+/// one, and lets a shutdown wait until the pool has stayed idle for a grace
+/// period.
+#[derive(Debug)]
+pub(crate) struct Idle {
+    /// When the pool became idle, `None` if there are futures on the pool.
+    since: Mutex<Option<Instant>>,
+
+    /// Notified when the pool becomes idle.
+    task: AtomicTask,
+}
+
+impl Idle {
+    pub fn new() -> Idle {
+        Idle {
+            since: Mutex::new(Some(Instant::now())),
+            task: AtomicTask::new(),
+        }
+    }
+
+    /// Called after the number of futures on the pool transitioned to or from
+    /// zero.
+    ///
+    /// The pool state is loaded while holding the lock, so concurrent
+    /// transitions cannot leave a stale value behind.
+    pub fn transition(&self, state: &AtomicUsize) {
+        let mut since = self.since.lock().unwrap();
+        let state: State = state.load(Acquire).into();
+
+        if state.num_futures() == 0 {
+            if since.is_none() {
+                *since = Some(Instant::now());
+                self.task.notify();
+            }
+        } else {
+            *since = None;
+        }
+    }
+
+    /// Returns when the pool became idle, or registers the current task to be
+    /// notified once it does.
+    pub fn poll(&self) -> Async<Instant> {
+        self.task.register();
+
+        match *self.since.lock().unwrap() {
+            Some(since) => Async::Ready(since),
+            None => Async::NotReady,
+        }
+    }
+}
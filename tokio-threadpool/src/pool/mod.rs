@@ -1,29 +1,35 @@
 mod backup;
 mod backup_stack;
+mod capacity;
+mod idle;
+mod queue;
 mod state;
 
 pub(crate) use self::backup::{Backup, BackupId};
 pub(crate) use self::backup_stack::MAX_BACKUP;
+pub(crate) use self::queue::GlobalQueue;
 pub(crate) use self::state::{Lifecycle, State, MAX_FUTURES};
 
 use self::backup::Handoff;
 use self::backup_stack::BackupStack;
+use self::capacity::SpawnCapacity;
+use self::idle::Idle;
 
 use config::Config;
+use dump::{Dump, Registry};
 use shutdown::ShutdownTrigger;
 use task::{Blocking, Task};
-use worker::{self, Worker, WorkerId};
+use worker::{self, Worker, WorkerContext, WorkerId};
 
 use futures::Poll;
 
 use std::cell::Cell;
 use std::num::Wrapping;
 use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::{AcqRel, Acquire};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
 use std::sync::{Arc, Weak};
 use std::thread;
 
-use crossbeam_deque::Injector;
 use crossbeam_utils::CachePadded;
 use rand;
 
@@ -50,11 +56,15 @@ pub(crate) struct Pool {
     // The number of workers will *usually* be small.
     pub workers: Arc<[worker::Entry]>,
 
-    // The global MPMC queue of tasks.
+    // Number of active workers. Workers with an index greater or equal to
+    // this value retire once they notice it.
+    pool_size: AtomicUsize,
+
+    // The global MPMC queues of tasks, one per priority.
     //
-    // Spawned tasks are pushed into this queue. Although worker threads have their own dedicated
-    // task queues, they periodically steal tasks from this global queue, too.
-    pub queue: Arc<Injector<Arc<Task>>>,
+    // Spawned tasks are pushed into these queues. Although worker threads have their own dedicated
+    // task queues, they periodically steal tasks from the global queues, too.
+    pub queue: Arc<GlobalQueue>,
 
     // Completes the shutdown process when the `ThreadPool` and all `Worker`s get dropped.
     //
@@ -76,6 +86,15 @@ pub(crate) struct Pool {
     // are pending blocking capacity.
     blocking: Blocking,
 
+    // Limits the number of spawned tasks that have not been polled yet.
+    pub capacity: SpawnCapacity,
+
+    // Tracks how long the pool has been without futures.
+    pub idle: Idle,
+
+    // Tracks the live tasks, `None` unless task dumps are enabled.
+    pub registry: Option<Registry>,
+
     // Configuration
     pub config: Config,
 }
@@ -84,18 +103,22 @@ impl Pool {
     /// Create a new `Pool`
     pub fn new(
         workers: Arc<[worker::Entry]>,
+        pool_size: usize,
         trigger: Weak<ShutdownTrigger>,
         max_blocking: usize,
+        max_queued: Option<usize>,
         config: Config,
-        queue: Arc<Injector<Arc<Task>>>,
+        queue: Arc<GlobalQueue>,
     ) -> Pool {
-        let pool_size = workers.len();
-        let total_size = max_blocking + pool_size;
+        let max_pool_size = workers.len();
+        let total_size = max_blocking + max_pool_size;
+
+        debug_assert!(pool_size <= max_pool_size);
 
         // Create the set of backup entries
         //
-        // This is `backup + pool_size` because the core thread pool running the
-        // workers is spawned from backup as well.
+        // This is `backup + max_pool_size` because the core thread pool running
+        // the workers is spawned from backup as well.
         let backup = (0..total_size)
             .map(|_| Backup::new())
             .collect::<Vec<_>>()
@@ -114,11 +137,19 @@ impl Pool {
             state: CachePadded::new(AtomicUsize::new(State::new().into())),
             sleep_stack: CachePadded::new(worker::Stack::new()),
             workers,
+            pool_size: AtomicUsize::new(pool_size),
             queue,
             trigger,
             backup,
             backup_stack,
             blocking,
+            capacity: SpawnCapacity::new(max_queued),
+            idle: Idle::new(),
+            registry: if config.task_dump {
+                Some(Registry::new())
+            } else {
+                None
+            },
             config,
         };
 
@@ -127,9 +158,65 @@ impl Pool {
             ret.sleep_stack.push(&ret.workers, i).unwrap();
         }
 
+        // Workers beyond the initial pool size are not on the sleeper stack
+        // until the pool is resized.
+        for i in pool_size..max_pool_size {
+            ret.workers[i].fetch_unset_pushed(Relaxed);
+        }
+
         ret
     }
 
+    /// Returns the number of active workers.
+    pub fn pool_size(&self) -> usize {
+        self.pool_size.load(Acquire)
+    }
+
+    /// Returns `true` if the worker at `idx` should retire.
+    pub fn is_retired(&self, idx: usize) -> bool {
+        idx >= self.pool_size()
+    }
+
+    /// Change the number of active workers.
+    ///
+    /// New workers are made available to `signal_work`, which spawns their
+    /// threads. Workers beyond the new size are woken up so that they retire.
+    pub fn resize(&self, pool_size: usize, pool: &Arc<Pool>) {
+        debug_assert_eq!(*self, **pool);
+
+        assert!(pool_size >= 1, "at least one thread required");
+        assert!(
+            pool_size <= self.workers.len(),
+            "max value is {}",
+            self.workers.len()
+        );
+
+        let prev = self.pool_size.swap(pool_size, AcqRel);
+
+        trace!("resize; prev={}; next={}", prev, pool_size);
+
+        if pool_size > prev {
+            for idx in prev..pool_size {
+                let state = self.workers[idx].fetch_set_pushed(AcqRel);
+
+                if !state.is_pushed() {
+                    // The push fails if the pool is terminated, in which case
+                    // the worker is not needed anyway.
+                    let _ = self.sleep_stack.push(&self.workers, idx);
+                }
+            }
+
+            for _ in prev..pool_size {
+                self.signal_work(pool);
+            }
+        } else {
+            for idx in pool_size..prev {
+                let entry = &self.workers[idx];
+                entry.signal_stop(entry.state.load(Acquire).into());
+            }
+        }
+    }
+
     /// Start shutting down the pool. This means that no new futures will be
     /// accepted.
     pub fn shutdown(&self, now: bool, purge_queue: bool) {
@@ -185,6 +272,11 @@ impl Pool {
 
         trace!("  -> transitioned to shutdown");
 
+        if state.lifecycle() == Lifecycle::ShutdownNow {
+            // Producers waiting for capacity must observe the shutdown.
+            self.capacity.notify_all();
+        }
+
         // Only transition to terminate if there are no futures currently on the
         // pool
         if state.num_futures() != 0 {
@@ -225,6 +317,28 @@ impl Pool {
         self.blocking.poll_blocking_capacity(task)
     }
 
+    pub fn try_claim_blocking_capacity(&self) -> bool {
+        self.blocking.try_claim_capacity()
+    }
+
+    pub fn blocking_stats(&self) -> ::BlockingStats {
+        self.blocking.stats()
+    }
+
+    /// Returns a snapshot of the scheduling counters of each worker.
+    pub fn worker_stats(&self) -> Vec<worker::WorkerStats> {
+        self.workers.iter().map(|entry| entry.stats.snapshot()).collect()
+    }
+
+    /// Returns a snapshot of the live tasks, empty unless task dumps are
+    /// enabled.
+    pub fn dump(&self) -> Dump {
+        match self.registry {
+            Some(ref registry) => registry.dump(),
+            None => Dump::new(vec![]),
+        }
+    }
+
     /// Submit a task to the scheduler.
     ///
     /// Called from either inside or outside of the scheduler. If currently on
@@ -247,7 +361,7 @@ impl Pool {
 
                     trace!("    -> submit internal; idx={}", idx);
 
-                    worker.pool.workers[idx].submit_internal(task);
+                    worker.pool.workers[idx].submit_internal(task, self.config.lifo_slot);
                     worker.pool.signal_work(pool);
                     return;
                 }
@@ -323,9 +437,17 @@ impl Pool {
 
         let pool = pool.clone();
 
-        let res = th.spawn(move || {
+        let dispatch_pool = pool.clone();
+
+        let run = move || {
+            if let Some(ref affinity) = pool.config.affinity {
+                affinity.pin_worker(&id);
+            }
+
+            trace_event!({ worker = id.0 }, "worker started");
+
             if let Some(ref f) = pool.config.after_start {
-                f();
+                f(&WorkerContext::current(id.clone()));
             }
 
             let mut worker_id = id;
@@ -337,7 +459,12 @@ impl Pool {
                 debug_assert!(pool.backup[backup_id.0].is_running());
 
                 // TODO: Avoid always cloning
-                let worker = Worker::new(worker_id, backup_id, pool.clone(), trigger.clone());
+                let worker = Worker::new(
+                    worker_id.clone(),
+                    backup_id,
+                    pool.clone(),
+                    trigger.clone(),
+                );
 
                 // Run the worker. If the worker transitioned to a "blocking"
                 // state, then `is_blocking` will be true.
@@ -346,6 +473,8 @@ impl Pool {
                     break;
                 }
 
+                trace_event!({ worker = worker_id.0 }, "blocking section completed");
+
                 debug_assert!(!pool.backup[backup_id.0].is_pushed());
 
                 // Push the thread back onto the backup stack. This makes it
@@ -371,6 +500,13 @@ impl Pool {
                 match handoff {
                     Handoff::Worker(id) => {
                         debug_assert!(pool.backup[backup_id.0].is_running());
+
+                        if let Some(ref affinity) = pool.config.affinity {
+                            affinity.pin_worker(&id);
+                        }
+
+                        trace_event!({ worker = id.0 }, "worker handed off to thread");
+
                         worker_id = id;
                     }
                     Handoff::Idle | Handoff::Terminated => {
@@ -379,10 +515,14 @@ impl Pool {
                 }
             }
 
+            trace_event!({ worker = worker_id.0 }, "worker stopped");
+
             if let Some(ref f) = pool.config.before_stop {
-                f();
+                f(&WorkerContext::current(worker_id));
             }
-        });
+        };
+
+        let res = th.spawn(move || dispatch_pool.config.with_dispatch(run));
 
         if let Err(e) = res {
             error!("failed to spawn worker thread; err={:?}", e);
@@ -397,9 +537,16 @@ impl Pool {
 
         use worker::Lifecycle::Signaled;
 
-        if let Some((idx, worker_state)) = self.sleep_stack.pop(&self.workers, Signaled, false) {
+        while let Some((idx, worker_state)) = self.sleep_stack.pop(&self.workers, Signaled, false) {
             let entry = &self.workers[idx];
 
+            if self.is_retired(idx) {
+                // The worker is retired or about to retire, make sure it
+                // notices and try the next sleeper.
+                entry.signal_stop(worker_state);
+                continue;
+            }
+
             debug_assert!(
                 worker_state.lifecycle() != Signaled,
                 "actual={:?}",
@@ -412,6 +559,8 @@ impl Pool {
                 trace!("signal_work -- spawn; idx={}", idx);
                 self.spawn_thread(WorkerId(idx), pool);
             }
+
+            return;
         }
     }
 
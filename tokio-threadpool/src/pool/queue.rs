@@ -0,0 +1,62 @@
+use priority::Priority;
+use task::Task;
+use worker;
+
+use std::sync::Arc;
+
+use crossbeam_deque::{Injector, Steal};
+
+/// The global queues of tasks, one per priority.
+///
+/// Spawned tasks are pushed into these queues. Although worker threads have
+/// their own dedicated task queues, they periodically steal tasks from the
+/// global queues, too.
+#[derive(Debug)]
+pub(crate) struct GlobalQueue {
+    high: Injector<Arc<Task>>,
+    normal: Injector<Arc<Task>>,
+}
+
+impl GlobalQueue {
+    pub fn new() -> GlobalQueue {
+        GlobalQueue {
+            high: Injector::new(),
+            normal: Injector::new(),
+        }
+    }
+
+    pub fn push(&self, task: Arc<Task>) {
+        match task.priority() {
+            Priority::High => self.high.push(task),
+            Priority::Normal => self.normal.push(task),
+        }
+    }
+
+    /// Returns `true` if high priority tasks are waiting in the global queue.
+    pub fn has_high(&self) -> bool {
+        !self.high.is_empty()
+    }
+
+    /// Steal a batch of high priority tasks into `dest`.
+    pub fn steal_high(&self, dest: &worker::Entry) -> Steal<()> {
+        self.high.steal_batch(dest.queue(Priority::High))
+    }
+
+    /// Steal a batch of normal priority tasks into `dest`.
+    pub fn steal_normal(&self, dest: &worker::Entry) -> Steal<()> {
+        self.normal.steal_batch(dest.queue(Priority::Normal))
+    }
+
+    /// Steal a batch of tasks into `dest`, high priority tasks first.
+    pub fn steal_batch(&self, dest: &worker::Entry) -> Steal<()> {
+        self.high
+            .steal_batch(dest.queue(Priority::High))
+            .or_else(|| self.normal.steal_batch(dest.queue(Priority::Normal)))
+    }
+
+    /// Drain (and drop) all queued tasks.
+    pub fn drain(&self) {
+        while !self.high.steal().is_empty() {}
+        while !self.normal.steal().is_empty() {}
+    }
+}
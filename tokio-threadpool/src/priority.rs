@@ -0,0 +1,45 @@
+/// Scheduling priority of a spawned future.
+///
+/// Workers always run `High` priority tasks before `Normal` priority tasks
+/// that are queued on the same worker. To prevent a steady stream of high
+/// priority work from starving everything else, a worker that has run a
+/// number of `High` priority tasks in a row while `Normal` priority tasks are
+/// waiting runs one `Normal` priority task before going back to the `High`
+/// priority queue.
+///
+/// The priority of a task is set when it is spawned and is kept every time the
+/// task is scheduled again after being notified.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_threadpool;
+/// # extern crate futures;
+/// # use tokio_threadpool::{Priority, ThreadPool};
+/// use futures::future::{Future, lazy};
+///
+/// # pub fn main() {
+/// let thread_pool = ThreadPool::new();
+///
+/// thread_pool.spawn_with_priority(lazy(|| {
+///     println!("health check");
+///     Ok(())
+/// }), Priority::High);
+///
+/// thread_pool.shutdown().wait().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Priority {
+    /// Latency sensitive work, scheduled ahead of `Normal` priority work.
+    High,
+
+    /// Regular work. This is the priority used by `spawn`.
+    Normal,
+}
+
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority::Normal
+    }
+}
@@ -1,10 +1,15 @@
+use dump::{Dump, SpawnLocation};
 use pool::{self, Lifecycle, Pool, MAX_FUTURES};
+use priority::Priority;
+use BlockingStats;
 use task::Task;
+use worker::WorkerStats;
 
 use std::sync::atomic::Ordering::{AcqRel, Acquire};
 use std::sync::Arc;
 
-use futures::{future, Future};
+use futures::future::{self, ExecuteError, ExecuteErrorKind};
+use futures::{Async, Future, Poll};
 use tokio_executor::{self, SpawnError};
 
 /// Submit futures to the associated thread pool for execution.
@@ -84,8 +89,292 @@ impl Sender {
         tokio_executor::Executor::spawn(&mut s, Box::new(future))
     }
 
+    /// Spawn a future onto the thread pool with the given scheduling priority.
+    ///
+    /// This behaves like [`spawn`], which uses [`Priority::Normal`], except
+    /// that the spawned task is scheduled according to `priority`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::{Priority, ThreadPool};
+    /// use futures::future::{Future, lazy};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = ThreadPool::new();
+    ///
+    /// thread_pool.sender().spawn_with_priority(lazy(|| {
+    ///     println!("heartbeat");
+    ///     Ok(())
+    /// }), Priority::High).unwrap();
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn`]: #method.spawn
+    /// [`Priority::Normal`]: enum.Priority.html#variant.Normal
+    pub fn spawn_with_priority<F>(&self, future: F, priority: Priority) -> Result<(), SpawnError>
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.prepare_for_spawn()?;
+        self.submit(Box::new(future), priority, None, None);
+        Ok(())
+    }
+
+    /// Spawn a future onto the thread pool, giving the task a name.
+    ///
+    /// This behaves like [`spawn`], except that `name` is attached to the
+    /// spawned task. The name is included in the pool's log and trace events
+    /// about the task, which helps telling tasks apart when diagnosing the
+    /// behavior of the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::ThreadPool;
+    /// use futures::future::{Future, lazy};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = ThreadPool::new();
+    ///
+    /// thread_pool.sender().spawn_named("accept loop", lazy(|| {
+    ///     println!("accepting connections");
+    ///     Ok(())
+    /// })).unwrap();
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn`]: #method.spawn
+    pub fn spawn_named<N, F>(&self, name: N, future: F) -> Result<(), SpawnError>
+    where
+        N: Into<String>,
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.prepare_for_spawn()?;
+        self.submit(Box::new(future), Priority::Normal, Some(name.into()), None);
+        Ok(())
+    }
+
+    /// Spawn a future onto the thread pool, recording where it was spawned
+    /// from.
+    ///
+    /// The location is reported by task dumps, see [`ThreadPool::dump`]. This
+    /// is usually called through the [`spawn!`] macro, which captures the
+    /// location of the call.
+    ///
+    /// [`ThreadPool::dump`]: struct.ThreadPool.html#method.dump
+    /// [`spawn!`]: macro.spawn.html
+    pub fn spawn_at<F>(&self, location: SpawnLocation, future: F) -> Result<(), SpawnError>
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.prepare_for_spawn()?;
+        self.submit(Box::new(future), Priority::Normal, None, Some(location));
+        Ok(())
+    }
+
+    /// Spawn a future onto the thread pool, giving the task a name and
+    /// recording where it was spawned from.
+    ///
+    /// See [`spawn_named`] and [`spawn_at`].
+    ///
+    /// [`spawn_named`]: #method.spawn_named
+    /// [`spawn_at`]: #method.spawn_at
+    pub fn spawn_named_at<N, F>(
+        &self,
+        location: SpawnLocation,
+        name: N,
+        future: F,
+    ) -> Result<(), SpawnError>
+    where
+        N: Into<String>,
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.prepare_for_spawn()?;
+        self.submit(Box::new(future), Priority::Normal, Some(name.into()), Some(location));
+        Ok(())
+    }
+
+    /// Attempt to spawn a future onto the thread pool, handing it back if the
+    /// pool cannot accept it.
+    ///
+    /// This behaves like [`spawn`], except that on failure the returned
+    /// [`ExecuteError`] contains the future. Use [`ExecuteError::kind`] to
+    /// determine whether the pool is at capacity, see [`Builder::max_queued`],
+    /// or shutdown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::ThreadPool;
+    /// use futures::future::{Future, lazy};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = ThreadPool::new();
+    ///
+    /// let res = thread_pool.sender().try_spawn(lazy(|| {
+    ///     println!("called from a worker thread");
+    ///     Ok(())
+    /// }));
+    ///
+    /// if let Err(e) = res {
+    ///     // Try again later with the same future
+    ///     let _future = e.into_future();
+    /// }
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn`]: #method.spawn
+    /// [`ExecuteError`]: https://docs.rs/futures/0.1/futures/future/struct.ExecuteError.html
+    /// [`ExecuteError::kind`]: https://docs.rs/futures/0.1/futures/future/struct.ExecuteError.html#method.kind
+    /// [`Builder::max_queued`]: struct.Builder.html#method.max_queued
+    pub fn try_spawn<F>(&self, future: F) -> Result<(), ExecuteError<F>>
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        if let Err(e) = self.prepare_for_spawn() {
+            let kind = if e.is_at_capacity() {
+                ExecuteErrorKind::NoCapacity
+            } else {
+                ExecuteErrorKind::Shutdown
+            };
+
+            return Err(ExecuteError::new(kind, future));
+        }
+
+        self.submit(Box::new(future), Priority::Normal, None, None);
+        Ok(())
+    }
+
+    /// Check if the thread pool can accept a new future, registering the
+    /// current task to be notified once it can.
+    ///
+    /// Returns `Ready` if a call to `spawn` will not fail due to the pool being
+    /// at capacity. Another producer may still use up the capacity before
+    /// `spawn` is called. Returns an error if the pool is shutdown.
+    pub fn poll_spawn_ready(&self) -> Poll<(), SpawnError> {
+        let state: pool::State = self.pool.state.load(Acquire).into();
+
+        if state.lifecycle() == Lifecycle::ShutdownNow {
+            return Err(SpawnError::shutdown());
+        }
+
+        Ok(self.pool.capacity.poll_ready())
+    }
+
+    /// Spawn a future onto the thread pool once it has capacity for it.
+    ///
+    /// Unlike [`spawn`], which fails immediately when the pool is at capacity,
+    /// the returned future waits until the pool can accept `future`. It
+    /// resolves with an error if the pool shuts down first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    /// use futures::future::{Future, lazy};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .max_queued(16)
+    ///     .build();
+    ///
+    /// let sender = thread_pool.sender().clone();
+    ///
+    /// thread_pool.spawn(lazy(move || {
+    ///     sender.spawn_when_ready(lazy(|| {
+    ///         println!("spawned once the pool had capacity");
+    ///         Ok(())
+    ///     }))
+    ///     .map_err(|e| panic!("pool shutdown; err={:?}", e))
+    /// }));
+    ///
+    /// thread_pool.shutdown_on_idle().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn`]: #method.spawn
+    pub fn spawn_when_ready<F>(&self, future: F) -> SpawnWhenReady<F>
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        SpawnWhenReady {
+            sender: self.clone(),
+            future: Some(future),
+        }
+    }
+
+    /// Returns a snapshot of the thread pool's blocking capacity usage.
+    ///
+    /// See [`ThreadPool::blocking_stats`] for more details.
+    ///
+    /// [`ThreadPool::blocking_stats`]: struct.ThreadPool.html#method.blocking_stats
+    pub fn blocking_stats(&self) -> BlockingStats {
+        self.pool.blocking_stats()
+    }
+
+    /// Returns a snapshot of the scheduling counters of each worker.
+    ///
+    /// See [`ThreadPool::worker_stats`] for more details.
+    ///
+    /// [`ThreadPool::worker_stats`]: struct.ThreadPool.html#method.worker_stats
+    pub fn worker_stats(&self) -> Vec<WorkerStats> {
+        self.pool.worker_stats()
+    }
+
+    /// Returns a snapshot of the tasks that are live on the thread pool.
+    ///
+    /// See [`ThreadPool::dump`] for more details.
+    ///
+    /// [`ThreadPool::dump`]: struct.ThreadPool.html#method.dump
+    pub fn dump(&self) -> Dump {
+        self.pool.dump()
+    }
+
+    /// Change the number of worker threads of the thread pool.
+    ///
+    /// See [`ThreadPool::resize`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `pool_size` is zero or greater than the
+    /// maximum pool size set with [`Builder::max_pool_size`].
+    ///
+    /// [`ThreadPool::resize`]: struct.ThreadPool.html#method.resize
+    /// [`Builder::max_pool_size`]: struct.Builder.html#method.max_pool_size
+    pub fn resize(&self, pool_size: usize) {
+        self.pool.resize(pool_size, &self.pool);
+    }
+
     /// Logic to prepare for spawning
     fn prepare_for_spawn(&self) -> Result<(), SpawnError> {
+        if !self.pool.capacity.try_acquire() {
+            return Err(SpawnError::at_capacity());
+        }
+
+        if let Err(e) = self.inc_num_futures() {
+            self.pool.capacity.release();
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn inc_num_futures(&self) -> Result<(), SpawnError> {
         let mut state: pool::State = self.pool.state.load(Acquire).into();
 
         // Increment the number of futures spawned on the pool as well as
@@ -113,6 +402,11 @@ impl Sender {
 
             if actual == state {
                 trace!("execute; count={:?}", next.num_futures());
+
+                if state.num_futures() == 0 {
+                    self.pool.idle.transition(&self.pool.state);
+                }
+
                 break;
             }
 
@@ -121,6 +415,28 @@ impl Sender {
 
         Ok(())
     }
+
+    /// Schedule a future that has been accepted by `prepare_for_spawn`.
+    fn submit(
+        &self,
+        future: Box<Future<Item = (), Error = ()> + Send>,
+        priority: Priority,
+        name: Option<String>,
+        location: Option<SpawnLocation>,
+    ) {
+        // Create a new task for the future
+        let task = Arc::new(Task::new(future, priority, name, location));
+
+        // Register the task before it can run, and thus complete.
+        if let Some(ref registry) = self.pool.registry {
+            registry.register(&task);
+        }
+
+        // Call `submit_external()` in order to place the task into the global
+        // queue. This way all workers have equal chance of running this task,
+        // which means IO handles will be assigned to reactors more evenly.
+        self.pool.submit_external(task, &self.pool);
+    }
 }
 
 impl tokio_executor::Executor for Sender {
@@ -142,7 +458,7 @@ impl<'a> tokio_executor::Executor for &'a Sender {
     fn status(&self) -> Result<(), tokio_executor::SpawnError> {
         let state: pool::State = self.pool.state.load(Acquire).into();
 
-        if state.num_futures() == MAX_FUTURES {
+        if state.num_futures() == MAX_FUTURES || self.pool.capacity.is_full() {
             // No capacity
             return Err(SpawnError::at_capacity());
         }
@@ -163,14 +479,7 @@ impl<'a> tokio_executor::Executor for &'a Sender {
 
         // At this point, the pool has accepted the future, so schedule it for
         // execution.
-
-        // Create a new task for the future
-        let task = Arc::new(Task::new(future));
-
-        // Call `submit_external()` in order to place the task into the global
-        // queue. This way all workers have equal chance of running this task,
-        // which means IO handles will be assigned to reactors more evenly.
-        self.pool.submit_external(task, &self.pool);
+        self.submit(future, Priority::Normal, None, None);
 
         Ok(())
     }
@@ -203,3 +512,40 @@ impl Clone for Sender {
         Sender { pool }
     }
 }
+
+/// Future returned by [`Sender::spawn_when_ready`].
+///
+/// Resolves once the future has been spawned onto the thread pool.
+///
+/// [`Sender::spawn_when_ready`]: struct.Sender.html#method.spawn_when_ready
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SpawnWhenReady<F> {
+    sender: Sender,
+    future: Option<F>,
+}
+
+impl<F> Future for SpawnWhenReady<F>
+where
+    F: Future<Item = (), Error = ()> + Send + 'static,
+{
+    type Item = ();
+    type Error = SpawnError;
+
+    fn poll(&mut self) -> Poll<(), SpawnError> {
+        loop {
+            try_ready!(self.sender.poll_spawn_ready());
+
+            let future = self.future.take().expect("polled after completion");
+
+            match self.sender.try_spawn(future) {
+                Ok(()) => return Ok(Async::Ready(())),
+                Err(e) => match e.kind() {
+                    // Another producer used up the capacity, wait again.
+                    ExecuteErrorKind::NoCapacity => self.future = Some(e.into_future()),
+                    _ => return Err(SpawnError::shutdown()),
+                },
+            }
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use task::Task;
+use pool::GlobalQueue;
 use worker;
 
-use crossbeam_deque::Injector;
 use futures::task::AtomicTask;
 use futures::{Async, Future, Poll};
 
@@ -63,7 +62,7 @@ impl Future for Shutdown {
 pub(crate) struct ShutdownTrigger {
     inner: Arc<Mutex<Inner>>,
     workers: Arc<[worker::Entry]>,
-    queue: Arc<Injector<Arc<Task>>>,
+    queue: Arc<GlobalQueue>,
 }
 
 unsafe impl Send for ShutdownTrigger {}
@@ -72,7 +71,7 @@ unsafe impl Sync for ShutdownTrigger {}
 impl ShutdownTrigger {
     pub(crate) fn new(
         workers: Arc<[worker::Entry]>,
-        queue: Arc<Injector<Arc<Task>>>,
+        queue: Arc<GlobalQueue>,
     ) -> ShutdownTrigger {
         ShutdownTrigger {
             inner: Arc::new(Mutex::new(Inner {
@@ -88,7 +87,7 @@ impl ShutdownTrigger {
 impl Drop for ShutdownTrigger {
     fn drop(&mut self) {
         // Drain the global task queue.
-        while !self.queue.steal().is_empty() {}
+        self.queue.drain();
 
         // Drop the remaining incomplete tasks and parkers assosicated with workers.
         for worker in self.workers.iter() {
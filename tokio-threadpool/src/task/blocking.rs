@@ -45,6 +45,16 @@ pub(crate) struct Blocking {
     /// queue. If a thread loses the race, instead of waiting to pop a task, it
     /// signals to the winning thread that it should pop an additional task.
     lock: AtomicUsize,
+
+    /// Maximum number of concurrent blocking sections.
+    capacity: usize,
+
+    /// Number of allocations of blocking capacity that have not been released
+    /// yet.
+    in_use: AtomicUsize,
+
+    /// Number of tasks queued pending blocking capacity.
+    waiting: AtomicUsize,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -103,6 +113,44 @@ impl Blocking {
             tail: UnsafeCell::new(ptr),
             stub: stub,
             lock: AtomicUsize::new(0),
+            capacity,
+            in_use: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a snapshot of the blocking capacity usage.
+    pub fn stats(&self) -> ::BlockingStats {
+        ::BlockingStats {
+            max: self.capacity,
+            in_use: self.in_use.load(Relaxed),
+            waiting: self.waiting.load(Relaxed),
+        }
+    }
+
+    /// Attempt to acquire blocking capacity without queuing the task if none
+    /// is available.
+    pub fn try_claim_capacity(&self) -> bool {
+        let mut curr: State = self.state.load(Acquire).into();
+
+        loop {
+            let mut next = curr;
+
+            if !next.claim_capacity(&self.stub) {
+                return false;
+            }
+
+            let actual = self
+                .state
+                .compare_and_swap(curr.into(), next.into(), AcqRel)
+                .into();
+
+            if curr == actual {
+                self.in_use.fetch_add(1, Relaxed);
+                return true;
+            }
+
+            curr = actual;
         }
     }
 
@@ -193,6 +241,8 @@ impl Blocking {
                     (*prev).next_blocking.store(ptr as *mut _, Release);
                 }
 
+                self.waiting.fetch_add(1, Relaxed);
+
                 // The node was queued to be notified once capacity is made
                 // available.
                 Ok(Async::NotReady)
@@ -209,6 +259,8 @@ impl Blocking {
                     debug_assert!(prev.is_queued());
                 }
 
+                self.in_use.fetch_add(1, Relaxed);
+
                 // Capacity has been obtained
                 Ok(().into())
             }
@@ -242,6 +294,10 @@ impl Blocking {
     }
 
     pub fn notify_task(&self, pool: &Arc<Pool>) {
+        // Each call releases one allocation of blocking capacity, which is
+        // either returned to the pool or handed to a queued task below.
+        self.in_use.fetch_sub(1, Relaxed);
+
         let prev = self.lock.fetch_add(1, AcqRel);
 
         if prev != 0 {
@@ -262,6 +318,9 @@ impl Blocking {
                     None => break,
                 };
 
+                self.waiting.fetch_sub(1, Relaxed);
+                self.in_use.fetch_add(1, Relaxed);
+
                 Task::notify_blocking(task, pool);
             }
 
@@ -6,8 +6,12 @@ pub(crate) use self::blocking::{Blocking, CanBlock};
 use self::blocking_state::BlockingState;
 use self::state::State;
 
+use dump::{SpawnLocation, TaskDump, TaskState, UNREGISTERED};
 use notifier::Notifier;
 use pool::Pool;
+use priority::Priority;
+
+use tokio_executor::budget;
 
 use futures::executor::{self, Spawn};
 use futures::{self, Async, Future};
@@ -32,6 +36,21 @@ pub(crate) struct Task {
     /// Next pointer in the queue of tasks pending blocking capacity.
     next_blocking: AtomicPtr<Task>,
 
+    /// Scheduling priority, used to pick the run queue the task is pushed to.
+    priority: Priority,
+
+    /// Name given to the task when it was spawned, used for diagnostics.
+    name: Option<String>,
+
+    /// Where the task was spawned from, used for diagnostics.
+    location: Option<SpawnLocation>,
+
+    /// Number of times the task has been polled.
+    polls: AtomicUsize,
+
+    /// The key associated with this task in the pool's task dump registry.
+    pub dump_key: AtomicUsize,
+
     /// ID of the worker that polled this task first.
     ///
     /// This field can be a `Cell` because it's only accessed by the worker thread that is
@@ -66,7 +85,12 @@ type BoxFuture = Box<Future<Item = (), Error = ()> + Send + 'static>;
 
 impl Task {
     /// Create a new `Task` as a harness for `future`.
-    pub fn new(future: BoxFuture) -> Task {
+    pub fn new(
+        future: BoxFuture,
+        priority: Priority,
+        name: Option<String>,
+        location: Option<SpawnLocation>,
+    ) -> Task {
         // Wrap the future with an execution context.
         let task_fut = executor::spawn(future);
 
@@ -74,6 +98,11 @@ impl Task {
             state: AtomicUsize::new(State::new().into()),
             blocking: AtomicUsize::new(BlockingState::new().into()),
             next_blocking: AtomicPtr::new(ptr::null_mut()),
+            priority,
+            name,
+            location,
+            polls: AtomicUsize::new(0),
+            dump_key: AtomicUsize::new(UNREGISTERED),
             reg_worker: Cell::new(None),
             reg_index: Cell::new(0),
             future: UnsafeCell::new(Some(task_fut)),
@@ -90,6 +119,11 @@ impl Task {
             state: AtomicUsize::new(State::stub().into()),
             blocking: AtomicUsize::new(BlockingState::new().into()),
             next_blocking: AtomicPtr::new(ptr::null_mut()),
+            priority: Priority::Normal,
+            name: None,
+            location: None,
+            polls: AtomicUsize::new(0),
+            dump_key: AtomicUsize::new(UNREGISTERED),
             reg_worker: Cell::new(None),
             reg_index: Cell::new(0),
             future: UnsafeCell::new(Some(task_fut)),
@@ -113,9 +147,12 @@ impl Task {
             _ => panic!("unexpected task state; {:?}", actual),
         }
 
+        self.polls.fetch_add(1, Relaxed);
+
         trace!(
-            "Task::run; state={:?}",
-            State::from(self.state.load(Relaxed))
+            "Task::run; state={:?}; name={:?}",
+            State::from(self.state.load(Relaxed)),
+            self.name
         );
 
         // The transition to `Running` done above ensures that a lock on the
@@ -141,10 +178,11 @@ impl Task {
 
             let mut g = Guard(fut, true);
 
-            let ret =
+            let ret = budget::with_budget(|| {
                 g.0.as_mut()
                     .unwrap()
-                    .poll_future_notify(unpark, self as *const _ as usize);
+                    .poll_future_notify(unpark, self as *const _ as usize)
+            });
 
             g.1 = false;
 
@@ -227,6 +265,42 @@ impl Task {
         }
     }
 
+    /// Returns the scheduling priority of the task.
+    #[inline]
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Returns the name the task was spawned with, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|name| &name[..])
+    }
+
+    /// Returns a description of the task, or `None` if it is no longer live.
+    pub fn dump(&self) -> Option<TaskDump> {
+        use self::State::*;
+
+        let state: State = self.state.load(Acquire).into();
+
+        let state = match state {
+            Idle if BlockingState::from(self.blocking.load(Acquire)).is_queued() => {
+                TaskState::Blocked
+            }
+            Idle => TaskState::Idle,
+            Scheduled => TaskState::Scheduled,
+            Running | Notified => TaskState::Running,
+            Complete | Aborted => return None,
+        };
+
+        Some(TaskDump::new(
+            self.name.clone(),
+            self.location,
+            state,
+            self.polls.load(Relaxed),
+        ))
+    }
+
     /// Notify the task
     pub fn notify(me: Arc<Task>, pool: &Arc<Pool>) {
         if me.schedule() {
@@ -296,6 +370,8 @@ impl fmt::Debug for Task {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Task")
             .field("state", &self.state)
+            .field("priority", &self.priority)
+            .field("name", &self.name)
             .field("future", &"Spawn<BoxFuture>")
             .finish()
     }
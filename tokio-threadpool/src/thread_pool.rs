@@ -1,12 +1,18 @@
 use builder::Builder;
+use dump::{Dump, SpawnLocation};
 use pool::Pool;
+use priority::Priority;
+use BlockingStats;
 use sender::Sender;
 use shutdown::{Shutdown, ShutdownTrigger};
+use worker::WorkerStats;
 
+use futures::future::ExecuteError;
 use futures::sync::oneshot;
-use futures::{Future, Poll};
+use futures::{Async, Future, Poll};
 
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Work-stealing based thread pool for executing futures.
 ///
@@ -83,6 +89,244 @@ impl ThreadPool {
         self.sender().spawn(future).unwrap();
     }
 
+    /// Spawn a future onto the thread pool with the given scheduling priority.
+    ///
+    /// See [`Priority`] for details on how tasks of different priorities are
+    /// scheduled.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Use
+    /// [`Sender::spawn_with_priority`] for a version that returns a `Result`
+    /// instead of panicking.
+    ///
+    /// [`Priority`]: enum.Priority.html
+    /// [`Sender::spawn_with_priority`]: struct.Sender.html#method.spawn_with_priority
+    pub fn spawn_with_priority<F>(&self, future: F, priority: Priority)
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.sender().spawn_with_priority(future, priority).unwrap();
+    }
+
+    /// Spawn a future onto the thread pool, giving the task a name.
+    ///
+    /// The name is included in the pool's diagnostics about the task.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Use [`Sender::spawn_named`]
+    /// for a version that returns a `Result` instead of panicking.
+    ///
+    /// [`Sender::spawn_named`]: struct.Sender.html#method.spawn_named
+    pub fn spawn_named<N, F>(&self, name: N, future: F)
+    where
+        N: Into<String>,
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.sender().spawn_named(name, future).unwrap();
+    }
+
+    /// Spawn a future onto the thread pool, recording where it was spawned
+    /// from.
+    ///
+    /// This is usually called through the [`spawn!`] macro.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Use [`Sender::spawn_at`]
+    /// for a version that returns a `Result` instead of panicking.
+    ///
+    /// [`spawn!`]: macro.spawn.html
+    /// [`Sender::spawn_at`]: struct.Sender.html#method.spawn_at
+    pub fn spawn_at<F>(&self, location: SpawnLocation, future: F)
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.sender().spawn_at(location, future).unwrap();
+    }
+
+    /// Spawn a future onto the thread pool, giving the task a name and
+    /// recording where it was spawned from.
+    ///
+    /// This is usually called through the [`spawn!`] macro.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the spawn fails. Use
+    /// [`Sender::spawn_named_at`] for a version that returns a `Result`
+    /// instead of panicking.
+    ///
+    /// [`spawn!`]: macro.spawn.html
+    /// [`Sender::spawn_named_at`]: struct.Sender.html#method.spawn_named_at
+    pub fn spawn_named_at<N, F>(&self, location: SpawnLocation, name: N, future: F)
+    where
+        N: Into<String>,
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.sender().spawn_named_at(location, name, future).unwrap();
+    }
+
+    /// Returns a snapshot of the thread pool's blocking capacity usage.
+    ///
+    /// The snapshot reports how many [`blocking`] sections currently hold
+    /// capacity and how many tasks are waiting for capacity to become
+    /// available. A steadily growing number of waiting tasks indicates that
+    /// [`Builder::max_blocking`] is too low for the workload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::ThreadPool;
+    /// use futures::Future;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = ThreadPool::new();
+    ///
+    /// let stats = thread_pool.blocking_stats();
+    /// assert_eq!(stats.in_use(), 0);
+    /// assert_eq!(stats.waiting(), 0);
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`blocking`]: fn.blocking.html
+    /// [`Builder::max_blocking`]: struct.Builder.html#method.max_blocking
+    pub fn blocking_stats(&self) -> BlockingStats {
+        self.sender().blocking_stats()
+    }
+
+    /// Returns a snapshot of the scheduling counters of each worker, indexed
+    /// by worker ID.
+    ///
+    /// The counters track how often workers steal tasks from each other, how
+    /// often they take tasks from the pool's global queue, and how deep their
+    /// local queues get, which helps tuning the size of the pool. A pool where
+    /// workers steal a lot may benefit from fewer workers, while workers
+    /// with deep local queues and little stealing may benefit from more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    /// use futures::Future;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new().pool_size(4).build();
+    ///
+    /// for (id, stats) in thread_pool.worker_stats().iter().enumerate() {
+    ///     println!("worker {}: stole {} times", id, stats.steal_count());
+    /// }
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    pub fn worker_stats(&self) -> Vec<WorkerStats> {
+        self.sender().worker_stats()
+    }
+
+    /// Returns a snapshot of the tasks that are live on the thread pool.
+    ///
+    /// Each task is reported with its name, see [`spawn_named`], the location
+    /// it was spawned from, if it was spawned with the [`spawn!`] macro, its
+    /// state, and the number of times it has been polled. Tasks are only
+    /// tracked when enabled with [`Builder::task_dump`], otherwise the dump is
+    /// always empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[macro_use]
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    /// use futures::future::{self, Future};
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .task_dump(true)
+    ///     .build();
+    ///
+    /// spawn!(thread_pool, "stuck", future::empty());
+    ///
+    /// // Prints something like:
+    /// // task "stuck" spawned at src/main.rs:12: Idle, polled 1 times
+    /// println!("{}", thread_pool.dump());
+    /// # thread_pool.shutdown_now().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`spawn_named`]: #method.spawn_named
+    /// [`spawn!`]: macro.spawn.html
+    /// [`Builder::task_dump`]: struct.Builder.html#method.task_dump
+    pub fn dump(&self) -> Dump {
+        self.sender().dump()
+    }
+
+    /// Change the number of worker threads of the thread pool.
+    ///
+    /// When growing, new workers are started and immediately take part in
+    /// processing tasks. When shrinking, the workers with the highest
+    /// identifiers are retired: each of them finishes the task it is currently
+    /// polling, hands the tasks left in its local queue over to the remaining
+    /// workers, and stops its thread.
+    ///
+    /// Threads that are in a [`blocking`] section when their worker retires
+    /// complete the blocking section before stopping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate tokio_threadpool;
+    /// # extern crate futures;
+    /// # use tokio_threadpool::Builder;
+    /// use futures::Future;
+    ///
+    /// # pub fn main() {
+    /// let thread_pool = Builder::new()
+    ///     .pool_size(2)
+    ///     .max_pool_size(8)
+    ///     .build();
+    ///
+    /// // Scale up to handle more load
+    /// thread_pool.resize(8);
+    ///
+    /// // ... and back down once the load is gone.
+    /// thread_pool.resize(1);
+    ///
+    /// thread_pool.shutdown().wait().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `pool_size` is zero or greater than the
+    /// maximum pool size set with [`Builder::max_pool_size`].
+    ///
+    /// [`blocking`]: fn.blocking.html
+    /// [`Builder::max_pool_size`]: struct.Builder.html#method.max_pool_size
+    pub fn resize(&self, pool_size: usize) {
+        self.sender().resize(pool_size);
+    }
+
+    /// Attempt to spawn a future onto the thread pool, handing it back if the
+    /// pool cannot accept it.
+    ///
+    /// See [`Sender::try_spawn`] for more details.
+    ///
+    /// [`Sender::try_spawn`]: struct.Sender.html#method.try_spawn
+    pub fn try_spawn<F>(&self, future: F) -> Result<(), ExecuteError<F>>
+    where
+        F: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        self.sender().try_spawn(future)
+    }
+
     /// Spawn a future on to the thread pool, return a future representing
     /// the produced value.
     ///
@@ -137,6 +381,23 @@ impl ThreadPool {
         &mut self.inner.as_mut().unwrap().sender
     }
 
+    /// Returns the instant at which the pool became idle.
+    ///
+    /// The pool is idle when no futures spawned onto it are left to complete.
+    /// If futures are still running, `NotReady` is returned and the current
+    /// task is notified once the pool becomes idle again.
+    ///
+    /// This can be used to only shut the pool down once it has stayed idle
+    /// for a grace period: wait for the returned instant plus the grace
+    /// period, then poll again to check that no future was spawned meanwhile.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a task context.
+    pub fn poll_idle(&self) -> Async<Instant> {
+        self.sender().pool.idle.poll()
+    }
+
     /// Shutdown the pool once it becomes idle.
     ///
     /// Idle is defined as the completion of all futures that have been spawned
@@ -147,6 +408,11 @@ impl ThreadPool {
     /// handle will result in an error. All worker threads are signaled and will
     /// shutdown. The returned future completes once all worker threads have
     /// completed the shutdown process.
+    ///
+    /// To only shut down once the pool has stayed idle for a while, wait on
+    /// [`poll_idle`] first.
+    ///
+    /// [`poll_idle`]: #method.poll_idle
     pub fn shutdown_on_idle(mut self) -> Shutdown {
         let inner = self.inner.take().unwrap();
         inner.sender.pool.shutdown(false, false);
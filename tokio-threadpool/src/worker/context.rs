@@ -0,0 +1,52 @@
+use worker::WorkerId;
+
+use std::thread::{self, ThreadId};
+
+/// Identifies the worker and thread that a lifecycle callback is invoked for.
+///
+/// A `WorkerContext` is passed to the callbacks registered with
+/// [`Builder::on_worker_start`], [`Builder::on_worker_stop`],
+/// [`Builder::on_park`], and [`Builder::on_unpark`]. The callbacks are always
+/// invoked on the thread that the context describes, so it is safe to use them
+/// to configure thread-level settings such as priorities or affinities.
+///
+/// Note that a thread may run different workers over its lifetime. When a
+/// worker enters a [`blocking`] section, the worker is handed off to another
+/// thread while the original thread continues running the blocking
+/// operation.
+///
+/// [`Builder::on_worker_start`]: struct.Builder.html#method.on_worker_start
+/// [`Builder::on_worker_stop`]: struct.Builder.html#method.on_worker_stop
+/// [`Builder::on_park`]: struct.Builder.html#method.on_park
+/// [`Builder::on_unpark`]: struct.Builder.html#method.on_unpark
+/// [`blocking`]: fn.blocking.html
+#[derive(Debug, Clone)]
+pub struct WorkerContext {
+    worker_id: WorkerId,
+    thread_id: ThreadId,
+}
+
+impl WorkerContext {
+    /// Returns a context for `worker_id` running on the current thread.
+    pub(crate) fn current(worker_id: WorkerId) -> WorkerContext {
+        WorkerContext {
+            worker_id,
+            thread_id: thread::current().id(),
+        }
+    }
+
+    /// Returns the identifier of the worker.
+    ///
+    /// Worker identifiers correspond to integers in the range `0..pool_size`,
+    /// see [`WorkerId::to_usize`].
+    ///
+    /// [`WorkerId::to_usize`]: struct.WorkerId.html#method.to_usize
+    pub fn worker_id(&self) -> &WorkerId {
+        &self.worker_id
+    }
+
+    /// Returns the identifier of the thread the worker is running on.
+    pub fn thread_id(&self) -> ThreadId {
+        self.thread_id
+    }
+}
@@ -1,6 +1,8 @@
 use park::{BoxPark, BoxUnpark};
+use priority::Priority;
 use task::Task;
 use worker::state::{State, PUSHED_MASK};
+use worker::stats::Stats;
 
 use std::cell::UnsafeCell;
 use std::fmt;
@@ -29,11 +31,21 @@ pub(crate) struct WorkerEntry {
     next_sleeper: UnsafeCell<usize>,
 
     // Worker half of deque
-    pub worker: Worker<Arc<Task>>,
+    worker: Worker<Arc<Task>>,
 
     // Stealer half of deque
     stealer: Stealer<Arc<Task>>,
 
+    // Worker half of the high priority deque
+    high: Worker<Arc<Task>>,
+
+    // Stealer half of the high priority deque
+    high_stealer: Stealer<Arc<Task>>,
+
+    // Task notified by the task running on this worker, to be run next. Only
+    // accessed by the thread owning the worker.
+    next: UnsafeCell<Option<Arc<Task>>>,
+
     // Thread parker
     park: UnsafeCell<Option<BoxPark>>,
 
@@ -49,23 +61,32 @@ pub(crate) struct WorkerEntry {
     // Set to `true` when `remotely_completed_tasks` has tasks that need to be removed from
     // `running_tasks`.
     needs_drain: AtomicBool,
+
+    // Scheduling counters, exposed through `ThreadPool::worker_stats`.
+    pub stats: Stats,
 }
 
 impl WorkerEntry {
     pub fn new(park: BoxPark, unpark: BoxUnpark) -> Self {
         let w = Worker::new_fifo();
         let s = w.stealer();
+        let h = Worker::new_fifo();
+        let hs = h.stealer();
 
         WorkerEntry {
             state: CachePadded::new(AtomicUsize::new(State::default().into())),
             next_sleeper: UnsafeCell::new(0),
             worker: w,
             stealer: s,
+            high: h,
+            high_stealer: hs,
+            next: UnsafeCell::new(None),
             park: UnsafeCell::new(Some(park)),
             unpark: UnsafeCell::new(Some(unpark)),
             running_tasks: UnsafeCell::new(Slab::new()),
             remotely_completed_tasks: SegQueue::new(),
             needs_drain: AtomicBool::new(false),
+            stats: Stats::new(),
         }
     }
 
@@ -82,11 +103,33 @@ impl WorkerEntry {
         self.state.fetch_and(!PUSHED_MASK, ordering).into()
     }
 
+    /// Atomically set the pushed flag.
+    ///
+    /// # Return
+    ///
+    /// The state *before* the push flag is set.
+    pub fn fetch_set_pushed(&self, ordering: Ordering) -> State {
+        self.state.fetch_or(PUSHED_MASK, ordering).into()
+    }
+
     /// Submit a task to this worker while currently on the same thread that is
     /// running the worker.
+    ///
+    /// When `lifo` is set, normal priority tasks are stored in the LIFO slot,
+    /// pushing the task previously stored there onto the queue.
     #[inline]
-    pub fn submit_internal(&self, task: Arc<Task>) {
-        self.push_internal(task);
+    pub fn submit_internal(&self, task: Arc<Task>, lifo: bool) {
+        if lifo && task.priority() == Priority::Normal {
+            let next = unsafe { &mut *self.next.get() };
+
+            if let Some(prev) = next.take() {
+                self.push_internal(prev);
+            }
+
+            *next = Some(task);
+        } else {
+            self.push_internal(task);
+        }
     }
 
     /// Notifies the worker and returns `false` if it needs to be spawned.
@@ -186,13 +229,27 @@ impl WorkerEntry {
         self.unpark();
     }
 
-    /// Pop a task
+    /// Pop a task from the queue of the given priority
     ///
     /// This **must** only be called by the thread that owns the worker entry.
     /// This function is not `Sync`.
     #[inline]
-    pub fn pop_task(&self) -> Option<Arc<Task>> {
-        self.worker.pop()
+    pub fn pop_task(&self, priority: Priority) -> Option<Arc<Task>> {
+        self.queue(priority).pop()
+    }
+
+    /// Take the task stored in the LIFO slot.
+    ///
+    /// This **must** only be called by the thread that owns the worker entry.
+    #[inline]
+    pub fn take_next(&self) -> Option<Arc<Task>> {
+        unsafe { (*self.next.get()).take() }
+    }
+
+    /// Returns `true` if the queue of the given priority has no tasks.
+    #[inline]
+    pub fn is_empty(&self, priority: Priority) -> bool {
+        self.queue(priority).is_empty()
     }
 
     /// Steal tasks
@@ -202,18 +259,31 @@ impl WorkerEntry {
     ///
     /// At the same time, this method steals some additional tasks and moves
     /// them into `dest` in order to balance the work distribution among
-    /// workers.
+    /// workers. High priority tasks are stolen first.
     pub fn steal_tasks(&self, dest: &Self) -> Steal<Arc<Task>> {
-        self.stealer.steal_batch_and_pop(&dest.worker)
+        self.high_stealer
+            .steal_batch_and_pop(&dest.high)
+            .or_else(|| self.stealer.steal_batch_and_pop(&dest.worker))
     }
 
     /// Drain (and drop) all tasks that are queued for work.
     ///
     /// This is called when the pool is shutting down.
     pub fn drain_tasks(&self) {
+        drop(self.take_next());
+        while self.high.pop().is_some() {}
         while self.worker.pop().is_some() {}
     }
 
+    /// Returns the worker half of the deque for the given priority.
+    #[inline]
+    pub fn queue(&self, priority: Priority) -> &Worker<Arc<Task>> {
+        match priority {
+            Priority::High => &self.high,
+            Priority::Normal => &self.worker,
+        }
+    }
+
     /// Parks the worker thread.
     pub fn park(&self) {
         if let Some(park) = unsafe { (*self.park.get()).as_mut() } {
@@ -300,7 +370,9 @@ impl WorkerEntry {
 
     #[inline]
     pub fn push_internal(&self, task: Arc<Task>) {
-        self.worker.push(task);
+        let queue = self.queue(task.priority());
+        self.stats.push_local(queue.is_empty());
+        queue.push(task);
     }
 
     #[inline]
@@ -323,6 +395,8 @@ impl fmt::Debug for WorkerEntry {
             .field("next_sleeper", &"UnsafeCell<usize>")
             .field("worker", &self.worker)
             .field("stealer", &self.stealer)
+            .field("high", &self.high)
+            .field("high_stealer", &self.high_stealer)
             .field("park", &"UnsafeCell<BoxPark>")
             .field("unpark", &"BoxUnpark")
             .finish()
@@ -1,13 +1,19 @@
+mod context;
 mod entry;
 mod stack;
 mod state;
+mod stats;
 
+pub use self::context::WorkerContext;
 pub(crate) use self::entry::WorkerEntry as Entry;
 pub(crate) use self::stack::Stack;
 pub(crate) use self::state::{Lifecycle, State};
+pub use self::stats::WorkerStats;
 
 use notifier::Notifier;
 use pool::{self, BackupId, Pool};
+use priority::Priority;
+use BlockingPolicy;
 use sender::Sender;
 use shutdown::ShutdownTrigger;
 use task::{self, CanBlock, Task};
@@ -56,6 +62,12 @@ pub struct Worker {
     // Set when the worker should finalize on drop
     should_finalize: Cell<bool>,
 
+    // Number of high priority tasks run in a row.
+    high_streak: Cell<usize>,
+
+    // Number of tasks run from the LIFO slot in a row.
+    lifo_streak: Cell<usize>,
+
     // Completes the shutdown process when the `ThreadPool` and all `Worker`s get dropped.
     trigger: Arc<ShutdownTrigger>,
 
@@ -98,6 +110,8 @@ impl Worker {
             current_task: CurrentTask::new(),
             is_blocking: Cell::new(false),
             should_finalize: Cell::new(false),
+            high_streak: Cell::new(0),
+            lifo_streak: Cell::new(0),
             trigger,
             _p: PhantomData,
         }
@@ -165,6 +179,15 @@ impl Worker {
             // none yet available.
             NoCapacity => return Ok(Async::NotReady),
 
+            // The task has yet to ask for capacity, and must not wait for it.
+            CanRequest if self.pool.config.blocking_policy == BlockingPolicy::FailFast => {
+                if !self.pool.try_claim_blocking_capacity() {
+                    return Err(::BlockingError::at_capacity());
+                }
+
+                self.current_task.set_can_block(Allocated);
+            }
+
             // The task has yet to ask for capacity
             CanRequest => {
                 // Atomically attempt to acquire blocking capacity, and if none
@@ -193,6 +216,7 @@ impl Worker {
         }
 
         trace!("transition to blocking state");
+        trace_event!({ worker = self.id.0 }, "entering blocking section");
 
         // Transitioning to blocking requires handing over the worker state to
         // another thread so that the work queue can continue to be processed.
@@ -202,6 +226,11 @@ impl Worker {
         // Track that the thread has now fully entered the blocking state.
         self.is_blocking.set(true);
 
+        // Move the thread off of the cores reserved for workers.
+        if let Some(ref affinity) = self.pool.config.affinity {
+            affinity.pin_blocking();
+        }
+
         Ok(().into())
     }
 
@@ -218,12 +247,21 @@ impl Worker {
         &self.id
     }
 
+    /// Returns a `Sender` spawning futures onto the pool this worker belongs
+    /// to.
+    pub fn sender(&self) -> Sender {
+        Sender {
+            pool: self.pool.clone(),
+        }
+    }
+
     /// Run the worker
     ///
     /// This function blocks until the worker is shutting down.
     pub fn run(&self) {
         const MAX_SPINS: usize = 3;
-        const LIGHT_SLEEP_INTERVAL: usize = 32;
+
+        let global_queue_interval = self.pool.config.global_queue_interval;
 
         // Get the notifier.
         let notify = Arc::new(Notifier {
@@ -237,6 +275,11 @@ impl Worker {
         while self.check_run_state(first) {
             first = false;
 
+            // The pool has been resized and this worker is no longer needed.
+            if self.pool.is_retired(self.id.0) && self.retire() {
+                break;
+            }
+
             // Run the next available task
             if self.try_run_task(&notify) {
                 if self.is_blocking.get() {
@@ -246,7 +289,7 @@ impl Worker {
 
                 // Poll the reactor and the global queue every now and then to
                 // ensure no task gets left behind.
-                if tick % LIGHT_SLEEP_INTERVAL == 0 {
+                if tick % global_queue_interval == 0 {
                     self.sleep_light();
                 }
 
@@ -301,6 +344,57 @@ impl Worker {
         self.try_steal_task(notify)
     }
 
+    /// Hands the worker's queued tasks over to the global queue and
+    /// transitions the worker to the shutdown state.
+    ///
+    /// Returns `false` if the pool has grown again in the meantime, in which
+    /// case the worker keeps running.
+    fn retire(&self) -> bool {
+        use self::Lifecycle::*;
+
+        trace!("Worker::retire; idx={}", self.id.0);
+
+        let entry = self.entry();
+
+        if let Some(task) = entry.take_next() {
+            self.pool.queue.push(task);
+        }
+
+        for &priority in &[Priority::High, Priority::Normal] {
+            while let Some(task) = entry.pop_task(priority) {
+                self.pool.queue.push(task);
+            }
+        }
+
+        let mut state: State = entry.state.load(Acquire).into();
+
+        loop {
+            if !self.pool.is_retired(self.id.0) {
+                return false;
+            }
+
+            let mut next = state;
+            next.set_lifecycle(Shutdown);
+
+            let actual = entry
+                .state
+                .compare_and_swap(state.into(), next.into(), AcqRel)
+                .into();
+
+            if actual == state {
+                break;
+            }
+
+            state = actual;
+        }
+
+        // Other workers must pick up the tasks that were handed over as well
+        // as any notification this worker received before retiring.
+        self.pool.signal_work(&self.pool);
+
+        true
+    }
+
     /// Checks the worker's current state, updating it as needed.
     ///
     /// Returns `true` if the worker should run.
@@ -384,8 +478,72 @@ impl Worker {
     ///
     /// Returns `true` if work was found.
     fn try_run_owned_task(&self, notify: &Arc<Notifier>) -> bool {
+        use crossbeam_deque::Steal;
+
+        // Maximum number of high priority tasks to run in a row before giving
+        // a normal priority task a chance to run.
+        const MAX_HIGH_STREAK: usize = 16;
+
+        // Maximum number of tasks to run from the LIFO slot in a row before
+        // giving the tasks queued on the worker a chance to run.
+        const MAX_LIFO_STREAK: usize = 3;
+
+        // High priority tasks spawned from outside of the pool should not wait
+        // for the next light sleep to be picked up.
+        if self.pool.queue.has_high() {
+            if let Steal::Success(()) = self.pool.queue.steal_high(self.entry()) {
+                self.entry().stats.inc_global_queue_count();
+                self.pool.signal_work(&self.pool);
+            }
+        }
+
+        let entry = self.entry();
+
+        // The task notified last runs next, unless high priority tasks are
+        // waiting.
+        if entry.is_empty(Priority::High) {
+            if let Some(task) = entry.take_next() {
+                let lifo_streak = self.lifo_streak.get();
+
+                if lifo_streak < MAX_LIFO_STREAK {
+                    self.lifo_streak.set(lifo_streak + 1);
+                    self.run_task(task, notify);
+                    return true;
+                }
+
+                // Tasks notifying each other would otherwise starve the
+                // queue.
+                entry.push_internal(task);
+            }
+        }
+
+        self.lifo_streak.set(0);
+
+        let streak = self.high_streak.get();
+
+        let task = if streak >= MAX_HIGH_STREAK {
+            self.high_streak.set(0);
+
+            if entry.is_empty(Priority::Normal) {
+                // Normal priority tasks may be waiting in the global queue.
+                if let Steal::Success(()) = self.pool.queue.steal_normal(entry) {
+                    entry.stats.inc_global_queue_count();
+                }
+            }
+
+            entry
+                .pop_task(Priority::Normal)
+                .or_else(|| entry.pop_task(Priority::High))
+        } else if let Some(task) = entry.pop_task(Priority::High) {
+            self.high_streak.set(streak + 1);
+            Some(task)
+        } else {
+            self.high_streak.set(0);
+            entry.pop_task(Priority::Normal)
+        };
+
         // Poll the internal queue for a task to run
-        match self.entry().pop_task() {
+        match task {
             Some(task) => {
                 self.run_task(task, notify);
                 true
@@ -409,7 +567,16 @@ impl Worker {
 
         loop {
             if idx < len {
-                match self.pool.workers[idx].steal_tasks(self.entry()) {
+                let res = self.pool.workers[idx].steal_tasks(self.entry());
+
+                self.entry().stats.inc_steal_operations(res.is_success());
+
+                trace_event!(
+                    { worker = self.id.0, victim = idx, success = res.is_success() },
+                    "steal attempt"
+                );
+
+                match res {
                     Steal::Success(task) => {
                         trace!("stole task from another worker");
 
@@ -454,10 +621,22 @@ impl Worker {
         if task.reg_worker.get().is_none() {
             task.reg_worker.set(Some(self.id.0 as u32));
             self.entry().register_task(&task);
+
+            // The task is no longer queued, make room for a new one.
+            self.pool.capacity.release();
         }
 
         let run = self.run_task2(&task, notify);
 
+        trace_event!(
+            {
+                worker = self.id.0,
+                task = task.name().unwrap_or(""),
+                complete = match run { Complete => true, _ => false }
+            },
+            "task polled"
+        );
+
         // TODO: Try to claim back the worker state in case the backup thread
         // did not start up fast enough. This is a performance optimization.
 
@@ -474,6 +653,7 @@ impl Worker {
                     //
                     // We have to call `submit_external` instead of `submit`
                     // here because `self` is still set as the current worker.
+                    self.pool.workers[self.id.0].stats.inc_overflow_count();
                     self.pool.submit_external(task, &self.pool);
                 } else {
                     self.entry().push_internal(task);
@@ -495,7 +675,13 @@ impl Worker {
                     if actual == state {
                         trace!("task complete; state={:?}", next);
 
+                        if let Some(ref registry) = self.pool.registry {
+                            registry.unregister(&task);
+                        }
+
                         if state.num_futures() == 1 {
+                            self.pool.idle.transition(&self.pool.state);
+
                             // If the thread pool has been flagged as shutdown,
                             // start terminating workers. This involves waking
                             // up any sleeping worker so that they can notice
@@ -651,6 +837,12 @@ impl Worker {
         // only after that go to sleep.
         self.sleep_light();
 
+        trace_event!({ worker = self.id.0 }, "worker parked");
+
+        if let Some(ref f) = self.pool.config.on_park {
+            f(&WorkerContext::current(self.id.clone()));
+        }
+
         // The state has been transitioned to sleeping, we can now wait by
         // calling the parker. This is done in a loop as condvars can wakeup
         // spuriously.
@@ -676,6 +868,12 @@ impl Worker {
                             .into();
 
                         if actual == state {
+                            trace_event!({ worker = self.id.0 }, "worker unparked");
+
+                            if let Some(ref f) = self.pool.config.on_unpark {
+                                f(&WorkerContext::current(self.id.clone()));
+                            }
+
                             return true;
                         }
 
@@ -706,8 +904,9 @@ impl Worker {
 
         use crossbeam_deque::Steal;
         loop {
-            match self.pool.queue.steal_batch(&self.entry().worker) {
+            match self.pool.queue.steal_batch(self.entry()) {
                 Steal::Success(()) => {
+                    self.entry().stats.inc_global_queue_count();
                     self.pool.signal_work(&self.pool);
                     break;
                 }
@@ -0,0 +1,127 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+/// Scheduling counters of a single worker.
+///
+/// Obtained with [`ThreadPool::worker_stats`]. The counters are updated with
+/// relaxed atomic operations, so a snapshot may be slightly out of date.
+///
+/// [`ThreadPool::worker_stats`]: struct.ThreadPool.html#method.worker_stats
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerStats {
+    steal_count: usize,
+    steal_operations: usize,
+    global_queue_count: usize,
+    overflow_count: usize,
+    max_queue_depth: usize,
+}
+
+/// Counters updated by a worker, stored in its entry.
+#[derive(Debug)]
+pub(crate) struct Stats {
+    steal_count: AtomicUsize,
+    steal_operations: AtomicUsize,
+    global_queue_count: AtomicUsize,
+    overflow_count: AtomicUsize,
+
+    // Tasks pushed onto the local queue since it was last seen empty. Only
+    // written by the thread owning the worker.
+    queue_depth: AtomicUsize,
+    max_queue_depth: AtomicUsize,
+}
+
+impl WorkerStats {
+    /// Returns the number of times the worker successfully stole tasks from
+    /// another worker.
+    ///
+    /// Each steal moves a batch of tasks onto the worker's local queue.
+    pub fn steal_count(&self) -> usize {
+        self.steal_count
+    }
+
+    /// Returns the number of times the worker attempted to steal tasks from
+    /// another worker, whether or not it found any.
+    pub fn steal_operations(&self) -> usize {
+        self.steal_operations
+    }
+
+    /// Returns the number of times the worker took a batch of tasks from the
+    /// pool's global queue.
+    pub fn global_queue_count(&self) -> usize {
+        self.global_queue_count
+    }
+
+    /// Returns the number of tasks the worker pushed onto the pool's global
+    /// queue because it could not keep them on its local queue.
+    ///
+    /// This happens when a task is notified while the worker has handed off
+    /// its local queue to run a `blocking` section.
+    pub fn overflow_count(&self) -> usize {
+        self.overflow_count
+    }
+
+    /// Returns the largest number of tasks the worker pushed onto its local
+    /// queue between two times the queue was empty.
+    ///
+    /// Other workers may steal from the queue in the meantime, so this is an
+    /// upper bound of the actual maximum depth of the queue.
+    pub fn max_queue_depth(&self) -> usize {
+        self.max_queue_depth
+    }
+}
+
+impl Stats {
+    pub fn new() -> Stats {
+        Stats {
+            steal_count: AtomicUsize::new(0),
+            steal_operations: AtomicUsize::new(0),
+            global_queue_count: AtomicUsize::new(0),
+            overflow_count: AtomicUsize::new(0),
+            queue_depth: AtomicUsize::new(0),
+            max_queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn inc_steal_operations(&self, success: bool) {
+        self.steal_operations.fetch_add(1, Relaxed);
+
+        if success {
+            self.steal_count.fetch_add(1, Relaxed);
+        }
+    }
+
+    pub fn inc_global_queue_count(&self) {
+        self.global_queue_count.fetch_add(1, Relaxed);
+    }
+
+    pub fn inc_overflow_count(&self) {
+        self.overflow_count.fetch_add(1, Relaxed);
+    }
+
+    /// Track a task being pushed onto the local queue.
+    ///
+    /// This must only be called by the thread owning the worker.
+    pub fn push_local(&self, was_empty: bool) {
+        let depth = if was_empty {
+            1
+        } else {
+            self.queue_depth.load(Relaxed) + 1
+        };
+
+        self.queue_depth.store(depth, Relaxed);
+
+        if depth > self.max_queue_depth.load(Relaxed) {
+            self.max_queue_depth.store(depth, Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> WorkerStats {
+        WorkerStats {
+            steal_count: self.steal_count.load(Relaxed),
+            steal_operations: self.steal_operations.load(Relaxed),
+            global_queue_count: self.global_queue_count.load(Relaxed),
+            overflow_count: self.overflow_count.load(Relaxed),
+            max_queue_depth: self.max_queue_depth.load(Relaxed),
+        }
+    }
+}
@@ -396,3 +396,100 @@ fn hammer() {
         }
     }
 }
+
+#[test]
+fn fail_fast_when_at_capacity() {
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new()
+        .pool_size(2)
+        .max_blocking(1)
+        .blocking_policy(BlockingPolicy::FailFast)
+        .build();
+
+    let (entered_tx, entered_rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+
+    pool.spawn(lazy(move || {
+        blocking(|| {
+            entered_tx.send(()).unwrap();
+            unblock_rx.recv().unwrap();
+        })
+        .unwrap();
+
+        Ok(())
+    }));
+
+    entered_rx.recv().unwrap();
+
+    let stats = pool.blocking_stats();
+    assert_eq!(stats.max(), 1);
+    assert_eq!(stats.in_use(), 1);
+    assert_eq!(stats.waiting(), 0);
+
+    let (tx, rx) = mpsc::channel();
+
+    pool.spawn(lazy(move || {
+        let err = blocking(|| ()).unwrap_err();
+        tx.send(err.is_at_capacity()).unwrap();
+        Ok(())
+    }));
+
+    assert!(rx.recv().unwrap());
+
+    unblock_tx.send(()).unwrap();
+    pool.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn blocking_stats_track_waiting_tasks() {
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(2).max_blocking(1).build();
+
+    let (entered_tx, entered_rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+
+    pool.spawn(lazy(move || {
+        blocking(|| {
+            entered_tx.send(()).unwrap();
+            unblock_rx.recv().unwrap();
+        })
+        .unwrap();
+
+        Ok(())
+    }));
+
+    entered_rx.recv().unwrap();
+
+    let (done_tx, done_rx) = mpsc::channel();
+
+    pool.spawn(poll_fn(move || {
+        let res = blocking(|| ()).map_err(|e| panic!("blocking err {:?}", e));
+
+        if res?.is_ready() {
+            done_tx.send(()).unwrap();
+            return Ok(().into());
+        }
+
+        Ok(Async::NotReady)
+    }));
+
+    while pool.blocking_stats().waiting() == 0 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(pool.blocking_stats().in_use(), 1);
+
+    unblock_tx.send(()).unwrap();
+    done_rx.recv().unwrap();
+
+    // Capacity is released once the blocking threads are done
+    while pool.blocking_stats().in_use() != 0 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(pool.blocking_stats().waiting(), 0);
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
@@ -1,6 +1,10 @@
 extern crate env_logger;
+#[macro_use]
 extern crate futures;
+#[cfg(target_os = "linux")]
+extern crate libc;
 extern crate tokio_executor;
+#[macro_use]
 extern crate tokio_threadpool;
 
 use tokio_executor::park::{Park, Unpark};
@@ -534,3 +538,515 @@ fn eagerly_drops_futures() {
     // Ensure `task` lives until after the test completes.
     drop(task);
 }
+
+#[test]
+fn worker_lifecycle_hooks() {
+    let _ = ::env_logger::try_init();
+
+    let started = Arc::new(::std::sync::Mutex::new(vec![]));
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let parked = Arc::new(AtomicUsize::new(0));
+    let unparked = Arc::new(AtomicUsize::new(0));
+
+    let pool = {
+        let started = started.clone();
+        let stopped = stopped.clone();
+        let parked = parked.clone();
+        let unparked = unparked.clone();
+
+        Builder::new()
+            .pool_size(1)
+            .on_worker_start(move |cx| {
+                assert_eq!(cx.thread_id(), ::std::thread::current().id());
+                started
+                    .lock()
+                    .unwrap()
+                    .push((cx.worker_id().to_usize(), cx.thread_id()));
+            })
+            .on_worker_stop(move |cx| {
+                assert_eq!(cx.worker_id().to_usize(), 0);
+                stopped.fetch_add(1, Relaxed);
+            })
+            .on_park(move |_| {
+                parked.fetch_add(1, Relaxed);
+            })
+            .on_unpark(move |_| {
+                unparked.fetch_add(1, Relaxed);
+            })
+            .build()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let tx2 = tx.clone();
+
+    pool.spawn(lazy(move || {
+        tx.send(::std::thread::current().id()).unwrap();
+        Ok(())
+    }));
+
+    let thread_id = rx.recv().unwrap();
+
+    // Wait for the worker to run out of work
+    while parked.load(Relaxed) == 0 {
+        ::std::thread::sleep(Duration::from_millis(10));
+    }
+
+    pool.spawn(lazy(move || {
+        tx2.send(::std::thread::current().id()).unwrap();
+        Ok(())
+    }));
+
+    assert_eq!(thread_id, rx.recv().unwrap());
+    assert!(unparked.load(Relaxed) > 0);
+
+    pool.shutdown().wait().unwrap();
+
+    assert_eq!(*started.lock().unwrap(), vec![(0, thread_id)]);
+    assert_eq!(stopped.load(Relaxed), 1);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn core_affinity_pins_workers() {
+    use std::mem;
+
+    fn current_cores() -> Vec<usize> {
+        unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            let size = mem::size_of::<libc::cpu_set_t>();
+            assert_eq!(0, libc::sched_getaffinity(0, size, &mut set));
+
+            (0..libc::CPU_SETSIZE as usize)
+                .filter(|&core| libc::CPU_ISSET(core, &set))
+                .collect()
+        }
+    }
+
+    let _ = ::env_logger::try_init();
+
+    // The process may not be allowed to run on every core, such as in a
+    // container, so pick one it is allowed on.
+    let core = current_cores()[0];
+
+    let pool = Builder::new()
+        .pool_size(1)
+        .core_affinity(move |_| Some(core))
+        .build();
+
+    let (tx, rx) = mpsc::channel();
+
+    pool.spawn(lazy(move || {
+        tx.send(current_cores()).unwrap();
+        Ok(())
+    }));
+
+    assert_eq!(vec![core], rx.recv().unwrap());
+
+    pool.shutdown().wait().unwrap();
+}
+
+#[test]
+fn max_queued_applies_backpressure() {
+    use futures::future::ExecuteErrorKind;
+
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(1).max_queued(2).build();
+
+    // Occupy the only worker so that spawned futures stay queued.
+    let (started_tx, started_rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+
+    pool.spawn(lazy(move || {
+        started_tx.send(()).unwrap();
+        unblock_rx.recv().unwrap();
+        Ok(())
+    }));
+
+    started_rx.recv().unwrap();
+
+    let num_run = Arc::new(AtomicUsize::new(0));
+
+    let run = {
+        let num_run = num_run.clone();
+        move || {
+            let num_run = num_run.clone();
+            lazy(move || {
+                num_run.fetch_add(1, Relaxed);
+                Ok(())
+            })
+        }
+    };
+
+    pool.try_spawn(run()).unwrap();
+    pool.sender().spawn(run()).unwrap();
+
+    let err = pool.try_spawn(run()).unwrap_err();
+    assert_eq!(err.kind(), ExecuteErrorKind::NoCapacity);
+    assert!(tokio_executor::Executor::status(pool.sender()).unwrap_err().is_at_capacity());
+
+    // Waits for capacity, which becomes available once the worker is released.
+    let sender = pool.sender().clone();
+    let th = ::std::thread::spawn(move || sender.spawn_when_ready(err.into_future()).wait());
+
+    ::std::thread::sleep(Duration::from_millis(50));
+    unblock_tx.send(()).unwrap();
+
+    th.join().unwrap().unwrap();
+
+    pool.shutdown_on_idle().wait().unwrap();
+
+    assert_eq!(num_run.load(Relaxed), 3);
+}
+
+#[test]
+fn high_priority_tasks_run_first() {
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(1).build();
+
+    // Occupy the only worker so that spawned futures stay queued.
+    let (started_tx, started_rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+
+    pool.spawn(lazy(move || {
+        started_tx.send(()).unwrap();
+        unblock_rx.recv().unwrap();
+        Ok(())
+    }));
+
+    started_rx.recv().unwrap();
+
+    let order = Arc::new(::std::sync::Mutex::new(vec![]));
+
+    let record = |priority| {
+        let order = order.clone();
+        lazy(move || {
+            order.lock().unwrap().push(priority);
+            Ok(())
+        })
+    };
+
+    pool.spawn(record(Priority::Normal));
+
+    for _ in 0..40 {
+        pool.spawn_with_priority(record(Priority::High), Priority::High);
+    }
+
+    unblock_tx.send(()).unwrap();
+    pool.shutdown_on_idle().wait().unwrap();
+
+    let order = order.lock().unwrap();
+    assert_eq!(order.len(), 41);
+    assert_eq!(order[0], Priority::High);
+
+    // The normal priority task is not starved by the high priority ones.
+    let pos = order.iter().position(|&p| p == Priority::Normal).unwrap();
+    assert!(pos < 40, "normal priority task starved; pos={}", pos);
+}
+
+#[test]
+fn resize_starts_and_retires_workers() {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    let _ = ::env_logger::try_init();
+
+    fn wait_for<F: Fn() -> bool>(f: F) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while !f() {
+            assert!(Instant::now() < deadline, "timed out");
+            ::std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    let started = Arc::new(Mutex::new(HashSet::new()));
+    let stopped = Arc::new(Mutex::new(HashSet::new()));
+
+    let pool = {
+        let started = started.clone();
+        let stopped = stopped.clone();
+
+        Builder::new()
+            .pool_size(1)
+            .max_pool_size(4)
+            .on_worker_start(move |cx| {
+                started.lock().unwrap().insert(cx.worker_id().to_usize());
+            })
+            .on_worker_stop(move |cx| {
+                stopped.lock().unwrap().insert(cx.worker_id().to_usize());
+            })
+            .build()
+    };
+
+    pool.resize(4);
+    wait_for(|| started.lock().unwrap().is_superset(&[1, 2, 3].iter().cloned().collect()));
+
+    pool.resize(1);
+    wait_for(|| *stopped.lock().unwrap() == [1, 2, 3].iter().cloned().collect());
+
+    // The remaining worker keeps processing tasks
+    let (tx, rx) = mpsc::channel();
+
+    for i in 0..10 {
+        let tx = tx.clone();
+        pool.spawn(lazy(move || {
+            tx.send(i).unwrap();
+            Ok(())
+        }));
+    }
+
+    let mut values: Vec<_> = rx.iter().take(10).collect();
+    values.sort();
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+
+    // Retired workers can be started again
+    stopped.lock().unwrap().clear();
+    started.lock().unwrap().clear();
+
+    pool.resize(3);
+    wait_for(|| started.lock().unwrap().is_superset(&[1, 2].iter().cloned().collect()));
+
+    pool.shutdown().wait().unwrap();
+}
+
+#[test]
+fn poll_idle_reports_when_pool_became_idle() {
+    use futures::future::poll_fn;
+    use std::time::Instant;
+
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(2).build();
+    let (tx, rx) = mpsc::channel();
+    let (unblock_tx, unblock_rx) = mpsc::channel::<()>();
+
+    pool.spawn(lazy(move || {
+        tx.send(()).unwrap();
+        unblock_rx.recv().unwrap();
+        Ok(())
+    }));
+
+    rx.recv().unwrap();
+
+    // The pool is busy, the polling task is notified once it becomes idle.
+    let busy = poll_fn(|| Ok::<_, ()>(Async::Ready(pool.poll_idle())));
+    assert!(busy.wait().unwrap().is_not_ready());
+
+    let before = Instant::now();
+    unblock_tx.send(()).unwrap();
+
+    let since = poll_fn(|| Ok::<_, ()>(pool.poll_idle())).wait().unwrap();
+    assert!(since >= before);
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn dump_live_tasks() {
+    use futures::sync::oneshot;
+    use std::thread;
+
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(1).task_dump(true).build();
+
+    let (tx, rx) = oneshot::channel::<()>();
+    let line = line!() + 1;
+    spawn!(pool, "waiting", rx.map_err(|_| ()));
+
+    // Wait for the task to be polled
+    let task = loop {
+        let dump = pool.dump();
+
+        if let Some(task) = dump.tasks().iter().find(|task| task.polls() > 0) {
+            break task.clone();
+        }
+
+        thread::yield_now();
+    };
+
+    assert_eq!(task.name(), Some("waiting"));
+    assert_eq!(task.state(), TaskState::Idle);
+
+    let location = task.location().unwrap();
+    assert!(location.file().ends_with("threadpool.rs"));
+    assert_eq!(location.line(), line);
+
+    tx.send(()).unwrap();
+
+    while !pool.dump().tasks().is_empty() {
+        thread::yield_now();
+    }
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn worker_stats() {
+    use futures::future::poll_fn;
+    use futures::sync::oneshot;
+
+    let _ = ::env_logger::try_init();
+
+    // Keep notified tasks out of the LIFO slot so that they are all queued
+    let pool = Builder::new().pool_size(1).lifo_slot(false).build();
+    assert_eq!(pool.worker_stats().len(), 1);
+
+    let (polled_tx, polled_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+    let mut txs = vec![];
+
+    // Spawned tasks are pushed onto the global queue
+    for _ in 0..10 {
+        let (tx, mut rx) = oneshot::channel::<()>();
+        let polled_tx = polled_tx.clone();
+        let done_tx = done_tx.clone();
+
+        txs.push(tx);
+
+        pool.spawn(poll_fn(move || {
+            let _ = polled_tx.send(());
+            try_ready!(rx.poll().map_err(|_| ()));
+            done_tx.send(()).unwrap();
+            Ok(().into())
+        }));
+    }
+
+    for _ in 0..10 {
+        polled_rx.recv().unwrap();
+    }
+
+    // Tasks notified from a worker are pushed onto its local queue, there is
+    // no other worker to steal them.
+    pool.spawn(lazy(move || {
+        for tx in txs {
+            tx.send(()).unwrap();
+        }
+
+        Ok(())
+    }));
+
+    for _ in 0..10 {
+        done_rx.recv().unwrap();
+    }
+
+    let stats = pool.worker_stats();
+
+    assert!(stats[0].global_queue_count() >= 1);
+    assert_eq!(stats[0].max_queue_depth(), 10);
+    assert_eq!(stats[0].steal_count(), 0);
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn lifo_slot_does_not_starve_queue() {
+    use futures::sync::{mpsc as chan, oneshot};
+
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new().pool_size(1).build();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let (done_tx, done_rx) = mpsc::channel();
+    let (queued_tx, queued_rx) = oneshot::channel::<()>();
+    let (ping_tx, ping_rx) = chan::unbounded::<usize>();
+    let (pong_tx, pong_rx) = chan::unbounded::<usize>();
+
+    // Queued behind the two tasks below once they start notifying each other
+    let stop2 = stop.clone();
+    pool.spawn(queued_rx.then(move |_| {
+        stop2.store(true, Relaxed);
+        done_tx.send(()).unwrap();
+        Ok(())
+    }));
+
+    let stop2 = stop.clone();
+    pool.spawn(ping_rx.for_each(move |n| {
+        if stop2.load(Relaxed) {
+            return Err(());
+        }
+
+        pong_tx.unbounded_send(n + 1).unwrap();
+        Ok(())
+    }));
+
+    let ping_tx2 = ping_tx.clone();
+    pool.spawn(
+        pong_rx
+            .for_each(move |n| {
+                let _ = ping_tx2.unbounded_send(n + 1);
+                Ok(())
+            })
+            .map_err(|_| ()),
+    );
+
+    pool.spawn(lazy(move || {
+        queued_tx.send(()).unwrap();
+        ping_tx.unbounded_send(0).unwrap();
+        Ok(())
+    }));
+
+    done_rx
+        .recv_timeout(Duration::from_secs(10))
+        .expect("queued task starved");
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
+
+#[test]
+fn global_queue_interval() {
+    use futures::future::poll_fn;
+    use futures::task;
+
+    let _ = ::env_logger::try_init();
+
+    let pool = Builder::new()
+        .pool_size(1)
+        .global_queue_interval(4)
+        .build();
+
+    let polls = Arc::new(AtomicUsize::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let (polled_tx, polled_rx) = mpsc::channel();
+    let (spawned_tx, spawned_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+
+    // Keeps the worker busy with its local queue. The second poll waits for
+    // the other task to be in the global queue, so the number of polls
+    // before it runs only depends on the interval.
+    let polls2 = polls.clone();
+    let stop2 = stop.clone();
+    pool.spawn(poll_fn(move || {
+        if polls2.fetch_add(1, Relaxed) == 1 {
+            polled_tx.send(()).unwrap();
+            spawned_rx.recv().unwrap();
+        }
+
+        if stop2.load(Relaxed) {
+            return Ok(().into());
+        }
+
+        task::current().notify();
+        Ok(Async::NotReady)
+    }));
+
+    polled_rx.recv().unwrap();
+
+    let polls2 = polls.clone();
+    pool.spawn(lazy(move || {
+        stop.store(true, Relaxed);
+        done_tx.send(polls2.load(Relaxed)).unwrap();
+        Ok(())
+    }));
+    spawned_tx.send(()).unwrap();
+
+    // The global queue is next checked after the fifth poll, the task taken
+    // from it then runs after the poll the busy task already queued.
+    assert_eq!(done_rx.recv().unwrap(), 6);
+
+    pool.shutdown_on_idle().wait().unwrap();
+}
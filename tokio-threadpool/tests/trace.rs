@@ -0,0 +1,133 @@
+#![cfg(feature = "trace")]
+
+extern crate futures;
+extern crate tokio_threadpool;
+extern crate tokio_trace_core;
+
+use tokio_threadpool::*;
+use tokio_trace_core::field::{Field, Record};
+use tokio_trace_core::{dispatcher, Dispatch, Event, Metadata, Span, Subscriber};
+
+use futures::future::{lazy, Future};
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Records the message, `worker`, and `task` fields of every event.
+struct Recorder {
+    events: Arc<Mutex<Vec<(String, Option<u64>, Option<String>)>>>,
+}
+
+struct Fields {
+    message: String,
+    worker: Option<u64>,
+    task: Option<String>,
+}
+
+impl Record for Fields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "worker" {
+            self.worker = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "task" {
+            self.task = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Metadata, _: &tokio_trace_core::field::ValueSet) -> Span {
+        Span::from_u64(1)
+    }
+
+    fn record(&self, _: &Span, _: &tokio_trace_core::field::ValueSet) {}
+
+    fn record_follows_from(&self, _: &Span, _: &Span) {}
+
+    fn event(&self, event: &Event) {
+        let mut fields = Fields {
+            message: String::new(),
+            worker: None,
+            task: None,
+        };
+
+        event.record(&mut fields);
+        self.events
+            .lock()
+            .unwrap()
+            .push((fields.message, fields.worker, fields.task));
+    }
+
+    fn enter(&self, _: &Span) {}
+
+    fn exit(&self, _: &Span) {}
+}
+
+#[test]
+fn worker_lifecycle_events() {
+    let events = Arc::new(Mutex::new(vec![]));
+
+    let recorder = Recorder {
+        events: events.clone(),
+    };
+
+    // Workers inherit the dispatcher of the thread building the pool
+    let pool = dispatcher::with_default(Dispatch::new(recorder), || {
+        Builder::new().pool_size(1).build()
+    });
+
+    pool.spawn(lazy(|| {
+        blocking(|| ()).unwrap();
+        Ok(())
+    }));
+
+    pool.shutdown_on_idle().wait().unwrap();
+
+    let events = events.lock().unwrap();
+
+    for msg in &["worker started", "entering blocking section", "worker stopped"] {
+        assert!(
+            events.iter().any(|&(ref m, w, _)| m == msg && w == Some(0)),
+            "missing {:?} in {:?}",
+            msg,
+            *events
+        );
+    }
+}
+
+#[test]
+fn named_task_events() {
+    let events = Arc::new(Mutex::new(vec![]));
+
+    let recorder = Recorder {
+        events: events.clone(),
+    };
+
+    let pool = dispatcher::with_default(Dispatch::new(recorder), || {
+        Builder::new().pool_size(1).build()
+    });
+
+    pool.spawn_named("my-task", lazy(|| Ok(())));
+    pool.shutdown_on_idle().wait().unwrap();
+
+    let events = events.lock().unwrap();
+
+    let named = events.iter().any(|&(ref m, _, ref t)| {
+        m == "task polled" && t.as_ref().map(|t| &t[..]) == Some("my-task")
+    });
+
+    assert!(named, "missing named task event in {:?}", *events);
+}
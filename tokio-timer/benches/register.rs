@@ -0,0 +1,72 @@
+#![feature(test)]
+#![deny(warnings)]
+
+extern crate test;
+extern crate tokio_executor;
+extern crate tokio_timer;
+
+const NUM_DELAYS: usize = 1_000;
+
+mod register {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::sync::{mpsc, Arc};
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use test::Bencher;
+    use tokio_executor::park::ParkThread;
+    use tokio_timer::timer::Timer;
+
+    /// Delays created on the thread driving the timer are staged, and handed
+    /// to the timer in one batch when it parks.
+    #[bench]
+    fn timer_thread(b: &mut Bencher) {
+        let mut timer = Timer::new(ParkThread::new());
+        let handle = timer.handle();
+        let deadline = Instant::now() + Duration::from_secs(60);
+
+        timer.turn(Some(Duration::from_millis(0))).unwrap();
+
+        b.iter(|| {
+            let delays: Vec<_> = (0..super::NUM_DELAYS)
+                .map(|_| handle.delay(deadline))
+                .collect();
+
+            timer.turn(Some(Duration::from_millis(0))).unwrap();
+            drop(delays);
+        });
+    }
+
+    /// Delays created on another thread are pushed onto the timer's queue one
+    /// at a time, each unparking the timer.
+    #[bench]
+    fn other_thread(b: &mut Bencher) {
+        let deadline = Instant::now() + Duration::from_secs(60);
+
+        let done = Arc::new(AtomicBool::new(false));
+        let done2 = done.clone();
+        let (tx, rx) = mpsc::channel();
+
+        let th = thread::spawn(move || {
+            let mut timer = Timer::new(ParkThread::new());
+            tx.send(timer.handle()).unwrap();
+
+            while !done2.load(SeqCst) {
+                timer.turn(Some(Duration::from_millis(1))).unwrap();
+            }
+        });
+
+        let handle = rx.recv().unwrap();
+
+        b.iter(|| {
+            let delays: Vec<_> = (0..super::NUM_DELAYS)
+                .map(|_| handle.delay(deadline))
+                .collect();
+
+            drop(delays);
+        });
+
+        done.store(true, SeqCst);
+        th.join().unwrap();
+    }
+}
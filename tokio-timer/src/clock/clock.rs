@@ -39,9 +39,14 @@ thread_local! {
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// # use tokio_timer::clock;
 /// let now = clock::now();
 /// ```
+#[deprecated(
+    since = "0.2.11",
+    note = "use `timer::Handle::now`, which reads the clock of the timer instead of the thread's"
+)]
 pub fn now() -> Instant {
     CLOCK.with(|current| match current.get() {
         Some(ptr) => unsafe { (*ptr).now() },
@@ -89,6 +94,10 @@ impl timer::Now for Clock {
     fn now(&mut self) -> Instant {
         Clock::now(self)
     }
+
+    fn clock(&self) -> Option<Clock> {
+        Some(self.clone())
+    }
 }
 
 impl fmt::Debug for Clock {
@@ -0,0 +1,290 @@
+use clock::{self, Clock, Now};
+use timer::{self, Timer};
+
+use tokio_executor;
+use tokio_executor::park::{Park, Unpark};
+
+use futures::future::{self, Future};
+
+use std::cell::RefCell;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A clock whose time can be frozen and advanced by hand, for use in tests.
+///
+/// A `MockClock` owns a [`Timer`] driven by its source of time. Within
+/// [`enter`], the mock clock and its timer are set as the defaults for the
+/// current execution context, so [`Handle::current`] and [`Delay`] use them.
+///
+/// Time starts out paused. While paused, time only moves forward when
+/// [`advance`] is called, and all timeouts that are due at the new time fire
+/// before `advance` returns.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_timer;
+/// # use futures::Future;
+/// use tokio_timer::Delay;
+/// use tokio_timer::clock::MockClock;
+/// use tokio_timer::timer::Handle;
+///
+/// use std::time::Duration;
+///
+/// # fn main() {
+/// let mock = MockClock::new();
+///
+/// mock.enter(|| {
+///     let mut delay = Delay::new(Handle::current().now() + Duration::from_secs(60));
+///     assert!(delay.poll().unwrap().is_not_ready());
+///
+///     mock.advance(Duration::from_secs(60));
+///     assert!(delay.poll().unwrap().is_ready());
+/// });
+/// # }
+/// ```
+///
+/// [`Timer`]: ../timer/struct.Timer.html
+/// [`enter`]: #method.enter
+/// [`Handle::current`]: ../timer/struct.Handle.html#method.current
+/// [`Delay`]: ../struct.Delay.html
+/// [`advance`]: #method.advance
+#[derive(Debug)]
+pub struct MockClock {
+    /// Timer processing the timeouts as time is advanced
+    timer: RefCell<Timer<MockPark, Clock>>,
+
+    /// Clock reading `now`
+    clock: Clock,
+
+    /// Source of time of `clock`
+    now: MockNow,
+}
+
+/// Source of time which can be paused and advanced by hand, following
+/// another clock while running.
+///
+/// This is the source of time of a `MockClock`, and of runtimes allowing time
+/// to be paused. It is not part of the public API.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct MockNow {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// The clock time passes by while not paused
+    clock: Clock,
+
+    /// Set while time is paused
+    paused: bool,
+
+    /// The time when time was last paused or resumed
+    base: Instant,
+
+    /// The value of `clock` when time was last resumed
+    clock_base: Instant,
+}
+
+/// `Park` implementation that never blocks, so turning the timer only fires
+/// the timeouts that are already due.
+#[derive(Debug)]
+struct MockPark;
+
+#[derive(Debug)]
+struct MockUnpark;
+
+impl MockClock {
+    /// Create a new `MockClock`, paused at the current system time.
+    pub fn new() -> MockClock {
+        let now = MockNow::new(Clock::system(), true);
+        let clock = Clock::new_with_now(now.clone());
+        let timer = Timer::new_with_now(MockPark, clock.clone());
+
+        MockClock {
+            timer: RefCell::new(timer),
+            clock,
+            now,
+        }
+    }
+
+    /// Returns a `Clock` reading the time of the mock clock.
+    pub fn clock(&self) -> Clock {
+        self.clock.clone()
+    }
+
+    /// Returns a handle to the timer driven by the mock clock.
+    pub fn handle(&self) -> timer::Handle {
+        self.timer.borrow().handle()
+    }
+
+    /// Returns the current time of the mock clock.
+    pub fn now(&self) -> Instant {
+        self.now.now()
+    }
+
+    /// Returns `true` if time is paused.
+    pub fn is_paused(&self) -> bool {
+        self.now.is_paused()
+    }
+
+    /// Freeze time at its current value.
+    ///
+    /// Calling `pause` while time is already paused has no effect.
+    pub fn pause(&self) {
+        self.now.pause();
+    }
+
+    /// Let time pass at the rate of the system clock again, starting from its
+    /// current value.
+    ///
+    /// Timeouts are only fired when time is advanced or when the timer is
+    /// turned, so a timeout that becomes due while time is running fires on
+    /// the next call to [`advance`] or [`turn`].
+    ///
+    /// [`advance`]: #method.advance
+    /// [`turn`]: #method.turn
+    pub fn resume(&self) {
+        self.now.resume();
+    }
+
+    /// Move time forward by `duration`, firing all timeouts that are due at
+    /// the new time.
+    ///
+    /// The tasks waiting on the fired timeouts are notified before this
+    /// function returns.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if time is not paused.
+    pub fn advance(&self, duration: Duration) {
+        self.now.advance(duration);
+        self.turn();
+    }
+
+    /// Fire all timeouts that are due at the current time, without moving
+    /// time forward.
+    pub fn turn(&self) {
+        self.timer
+            .borrow_mut()
+            .turn(Some(Duration::from_millis(0)))
+            .expect("failed to turn the mock timer");
+    }
+
+    /// Run `f` with the mock clock and its timer set as the defaults for the
+    /// current execution context.
+    ///
+    /// `f` runs within a task, so futures may be polled directly from the
+    /// closure.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from within an execution context.
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let mut enter =
+            tokio_executor::enter().expect("mock clock entered from an execution context");
+        let handle = self.handle();
+
+        clock::with_default(&self.clock, &mut enter, |enter| {
+            timer::with_default(&handle, enter, |_| {
+                future::lazy(|| Ok::<_, ()>(f())).wait().unwrap()
+            })
+        })
+    }
+}
+
+impl MockNow {
+    /// Create a new `MockNow` starting at the current time of `clock`.
+    pub fn new(clock: Clock, paused: bool) -> MockNow {
+        let now = clock.now();
+
+        MockNow {
+            inner: Arc::new(Mutex::new(State {
+                clock,
+                paused,
+                base: now,
+                clock_base: now,
+            })),
+        }
+    }
+
+    /// Returns `true` if time is paused.
+    pub fn is_paused(&self) -> bool {
+        self.inner.lock().unwrap().paused
+    }
+
+    /// Freeze time at its current value.
+    pub fn pause(&self) {
+        let mut state = self.inner.lock().unwrap();
+
+        if !state.paused {
+            state.base = state.now();
+            state.paused = true;
+        }
+    }
+
+    /// Let time follow the clock again, starting from its current value.
+    pub fn resume(&self) {
+        let mut state = self.inner.lock().unwrap();
+
+        if state.paused {
+            state.clock_base = state.clock.now();
+            state.paused = false;
+        }
+    }
+
+    /// Move time forward by `duration`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if time is not paused.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.inner.lock().unwrap();
+
+        assert!(state.paused, "time can only be advanced while paused");
+        state.base += duration;
+    }
+}
+
+impl Now for MockNow {
+    fn now(&self) -> Instant {
+        self.inner.lock().unwrap().now()
+    }
+}
+
+impl State {
+    fn now(&self) -> Instant {
+        if self.paused {
+            self.base
+        } else {
+            self.base + (self.clock.now() - self.clock_base)
+        }
+    }
+}
+
+impl Park for MockPark {
+    type Unpark = MockUnpark;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        MockUnpark
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn park_timeout(&mut self, _: Duration) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Unpark for MockUnpark {
+    fn unpark(&self) {}
+}
@@ -15,9 +15,30 @@
 //! [`Instant`]: https://doc.rust-lang.org/std/time/struct.Instant.html
 //! [`Instant::now`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now
 //! [`with_default`]: fn.with_default.html
+//!
+//! In tests, a [`MockClock`] freezes time and advances it by hand, firing the
+//! timeouts that become due.
+//!
+//! # Timers
+//!
+//! A timer captures its clock when it is created, and all the timeouts it
+//! drives compute their deadlines with that clock, whatever thread they are
+//! polled from. [`Handle::now`] returns the current time according to the
+//! clock of a timer. Computing deadlines for a timer with [`now`][n] relies on
+//! the thread-local clock matching the timer's one, which is why [`now`][n] is
+//! deprecated.
+//!
+//! [`MockClock`]: struct.MockClock.html
+//! [`Handle::now`]: ../timer/struct.Handle.html#method.now
 
 mod clock;
+mod mock;
 mod now;
 
-pub use self::clock::{now, with_default, Clock};
+#[allow(deprecated)]
+pub use self::clock::now;
+pub use self::clock::{with_default, Clock};
+pub use self::mock::MockClock;
+#[doc(hidden)]
+pub use self::mock::MockNow;
 pub use self::now::Now;
@@ -1,7 +1,8 @@
 use timer::{HandlePriv, Registration};
 use Error;
 
-use futures::{Future, Poll};
+use futures::{Async, Future, Poll};
+use tokio_executor::budget;
 
 use std::time::{Duration, Instant};
 
@@ -74,6 +75,12 @@ impl Delay {
         self.registration.reset(deadline);
     }
 
+    /// Returns the current instant according to the clock of the timer
+    /// driving the `Delay`.
+    pub(crate) fn now(&self) -> Instant {
+        self.registration.now()
+    }
+
     pub(crate) fn reset_timeout(&mut self) {
         self.registration.reset_timeout();
     }
@@ -90,9 +97,21 @@ impl Future for Delay {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        // Cooperative scheduling, see `tokio_executor::budget`.
+        let proceed = match budget::poll_proceed() {
+            Async::Ready(proceed) => proceed,
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
         // Ensure the `Delay` instance is associated with a timer.
         self.register();
 
-        self.registration.poll_elapsed()
+        let ret = self.registration.poll_elapsed();
+
+        if let Ok(Async::Ready(_)) = ret {
+            proceed.made_progress();
+        }
+
+        ret
     }
 }
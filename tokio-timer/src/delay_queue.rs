@@ -4,13 +4,13 @@
 //!
 //! [`DelayQueue`]: struct.DelayQueue.html
 
-use clock::now;
 use timer::Handle;
 use wheel::{self, Wheel};
 use {Delay, Error};
 
-use futures::{Future, Poll, Stream};
+use futures::{Async, Future, Poll, Stream};
 use slab::Slab;
+use tokio_executor::budget;
 
 use std::cmp;
 use std::marker::PhantomData;
@@ -174,7 +174,7 @@ pub struct Expired<T> {
 ///
 /// [`DelayQueue`]: struct.DelayQueue.html
 /// [`DelayQueue::insert`]: struct.DelayQueue.html#method.insert
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Key {
     index: usize,
 }
@@ -244,7 +244,7 @@ impl<T> DelayQueue<T> {
             expired: Stack::default(),
             delay: None,
             poll: wheel::Poll::new(0),
-            start: now(),
+            start: handle.now(),
         }
     }
 
@@ -402,7 +402,8 @@ impl<T> DelayQueue<T> {
     /// [`Key`]: struct.Key.html
     /// [type]: #
     pub fn insert(&mut self, value: T, timeout: Duration) -> Key {
-        self.insert_at(value, now() + timeout)
+        let now = self.handle.now();
+        self.insert_at(value, now + timeout)
     }
 
     fn insert_idx(&mut self, when: u64, key: usize) {
@@ -502,11 +503,23 @@ impl<T> DelayQueue<T> {
     /// # }
     /// ```
     pub fn reset_at(&mut self, key: &Key, when: Instant) {
-        self.wheel.remove(&key.index, &mut self.slab);
+        use self::wheel::Stack;
 
         // Normalize the deadline. Values cannot be set to expire in the past.
         let when = self.normalize_deadline(when);
 
+        if self.slab[key.index].expired {
+            self.expired.remove(&key.index, &mut self.slab);
+            self.slab[key.index].expired = false;
+        } else if self.wheel.is_same_slot(self.slab[key.index].when, when) {
+            // The entry stays in its slot, so neither the wheel nor the delay
+            // need to be updated.
+            self.slab[key.index].when = when;
+            return;
+        } else {
+            self.wheel.remove(&key.index, &mut self.slab);
+        }
+
         self.slab[key.index].when = when;
         self.insert_idx(when, key.index);
 
@@ -559,7 +572,8 @@ impl<T> DelayQueue<T> {
     /// # }
     /// ```
     pub fn reset(&mut self, key: &Key, timeout: Duration) {
-        self.reset_at(key, now() + timeout);
+        let now = self.handle.now();
+        self.reset_at(key, now + timeout);
     }
 
     /// Clears the queue, removing all items.
@@ -640,6 +654,89 @@ impl<T> DelayQueue<T> {
         self.slab.reserve(additional);
     }
 
+    /// Shrink the capacity of the queue as much as possible.
+    ///
+    /// The queue may still be able to hold more items than it contains without
+    /// allocating, as the storage of removed items cannot always be released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_timer::DelayQueue;
+    /// # use std::time::Duration;
+    /// let mut delay_queue = DelayQueue::with_capacity(100);
+    /// delay_queue.insert("hello", Duration::from_secs(10));
+    ///
+    /// delay_queue.shrink_to_fit();
+    /// assert!(delay_queue.capacity() < 100);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.slab.shrink_to_fit();
+    }
+
+    /// Returns the number of items in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_timer::DelayQueue;
+    /// # use std::time::Duration;
+    /// let mut delay_queue = DelayQueue::new();
+    /// assert_eq!(delay_queue.len(), 0);
+    ///
+    /// delay_queue.insert("hello", Duration::from_secs(5));
+    /// assert_eq!(delay_queue.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.slab.len()
+    }
+
+    /// Returns the key of the item that expires first, without removing it
+    /// from the queue.
+    ///
+    /// Returns `None` if the queue is empty. Items that have already expired
+    /// are returned first, in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio_timer::DelayQueue;
+    /// # use std::time::Duration;
+    /// let mut delay_queue = DelayQueue::new();
+    /// assert!(delay_queue.peek().is_none());
+    ///
+    /// delay_queue.insert("later", Duration::from_secs(10));
+    /// delay_queue.insert("sooner", Duration::from_secs(5));
+    ///
+    /// let next = delay_queue.peek().unwrap();
+    /// assert_eq!(*delay_queue.remove(&next).get_ref(), "sooner");
+    /// ```
+    pub fn peek(&self) -> Option<Key> {
+        if let Some(idx) = self.expired.head {
+            return Some(Key::new(idx));
+        }
+
+        // The first item to expire is stored in the next slot of the wheel
+        let stack = self.wheel.next_entries()?;
+        let mut next = stack.head;
+        let mut first: Option<usize> = None;
+
+        while let Some(idx) = next {
+            let is_first = match first {
+                Some(first) => self.slab[idx].when < self.slab[first].when,
+                None => true,
+            };
+
+            if is_first {
+                first = Some(idx);
+            }
+
+            next = self.slab[idx].next;
+        }
+
+        first.map(Key::new)
+    }
+
     /// Returns `true` if there are no items in the queue.
     ///
     /// Note that this function returns `false` even if all items have not yet
@@ -714,6 +811,12 @@ impl<T> Stream for DelayQueue<T> {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Error> {
+        // Cooperative scheduling, see `tokio_executor::budget`.
+        let proceed = match budget::poll_proceed() {
+            Async::Ready(proceed) => proceed,
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+
         let item = try_ready!(self.poll_idx()).map(|idx| {
             let data = self.slab.remove(idx);
             debug_assert!(data.next.is_none());
@@ -726,6 +829,8 @@ impl<T> Stream for DelayQueue<T> {
             }
         });
 
+        proceed.made_progress();
+
         Ok(item.into())
     }
 }
@@ -842,4 +947,14 @@ impl<T> Expired<T> {
     pub fn into_inner(self) -> T {
         self.data
     }
+
+    /// Returns the instant at which the item expired.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the key the item was stored under.
+    pub fn key(&self) -> Key {
+        self.key.clone()
+    }
 }
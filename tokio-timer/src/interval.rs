@@ -1,6 +1,6 @@
 use Delay;
 
-use clock;
+use timer;
 
 use futures::{Future, Poll, Stream};
 
@@ -14,6 +14,44 @@ pub struct Interval {
 
     /// The duration between values yielded by `Interval`.
     duration: Duration,
+
+    /// How to catch up when ticks are missed.
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+/// Defines the behavior of an [`Interval`] when it misses ticks.
+///
+/// Ticks are missed when the interval is not polled for longer than its
+/// period, for example because the task polling it was busy. The behavior is
+/// set with [`Interval::set_missed_tick_behavior`].
+///
+/// The examples below assume an interval with a period of 100ms that yields
+/// its tick at `t`, then is only polled again at `t + 350ms`.
+///
+/// [`Interval`]: struct.Interval.html
+/// [`Interval::set_missed_tick_behavior`]: struct.Interval.html#method.set_missed_tick_behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissedTickBehavior {
+    /// Yield all the missed ticks as fast as possible, then keep the original
+    /// schedule.
+    ///
+    /// The interval yields `t + 100ms`, `t + 200ms` and `t + 300ms` right
+    /// away, then `t + 400ms` when that time is reached. This is the default.
+    Burst,
+
+    /// Yield a single tick, then schedule the next tick a full period after
+    /// the time the tick was yielded.
+    ///
+    /// The interval yields `t + 100ms` right away, then `t + 450ms` when that
+    /// time is reached.
+    Delay,
+
+    /// Yield a single tick, then skip the missed ticks and keep the original
+    /// schedule.
+    ///
+    /// The interval yields `t + 100ms` right away, then `t + 400ms` when that
+    /// time is reached.
+    Skip,
 }
 
 impl Interval {
@@ -46,11 +84,51 @@ impl Interval {
     ///
     /// This function panics if `duration` is zero.
     pub fn new_interval(duration: Duration) -> Interval {
-        Interval::new(clock::now() + duration, duration)
+        Interval::new(timer::now() + duration, duration)
     }
 
     pub(crate) fn new_with_delay(delay: Delay, duration: Duration) -> Interval {
-        Interval { delay, duration }
+        Interval {
+            delay,
+            duration,
+            missed_tick_behavior: MissedTickBehavior::Burst,
+        }
+    }
+
+    /// Returns the behavior of the interval when it misses ticks.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Sets the behavior of the interval when it misses ticks.
+    ///
+    /// Defaults to [`MissedTickBehavior::Burst`].
+    ///
+    /// [`MissedTickBehavior::Burst`]: enum.MissedTickBehavior.html#variant.Burst
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    /// Returns the instant of the tick following the one at `deadline`.
+    fn next_tick(&self, deadline: Instant) -> Instant {
+        let next = deadline + self.duration;
+        let now = self.delay.now();
+
+        if next > now {
+            // No tick was missed
+            return next;
+        }
+
+        match self.missed_tick_behavior {
+            MissedTickBehavior::Burst => next,
+            MissedTickBehavior::Delay => now + self.duration,
+            MissedTickBehavior::Skip => {
+                // Round up to the first tick of the original schedule that is
+                // after `now`.
+                let behind = ::nanos(now - deadline) % ::nanos(self.duration);
+                now + self.duration - Duration::from_nanos(behind)
+            }
+        }
     }
 }
 
@@ -66,8 +144,9 @@ impl Stream for Interval {
         let now = self.delay.deadline();
 
         // The next interval value is `duration` after the one that just
-        // yielded.
-        self.delay.reset(now + self.duration);
+        // yielded, unless ticks were missed.
+        let next = self.next_tick(now);
+        self.delay.reset(next);
 
         // Return the current instant
         Ok(Some(now).into())
@@ -57,16 +57,16 @@ pub use self::delay::Delay;
 #[doc(inline)]
 pub use self::delay_queue::DelayQueue;
 pub use self::error::Error;
-pub use self::interval::Interval;
+pub use self::interval::{Interval, MissedTickBehavior};
 #[doc(inline)]
 pub use self::timeout::Timeout;
 pub use self::timer::{with_default, Timer};
 
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 /// Create a Future that completes in `duration` from now.
 pub fn sleep(duration: Duration) -> Delay {
-    Delay::new(Instant::now() + duration)
+    Delay::new(timer::now() + duration)
 }
 
 // ===== Internal utils =====
@@ -97,3 +97,14 @@ fn ms(duration: Duration, round: Round) -> u64 {
         .saturating_mul(MILLIS_PER_SEC)
         .saturating_add(millis as u64)
 }
+
+/// Convert a `Duration` to nanoseconds, saturating at `u64::MAX`.
+#[inline]
+fn nanos(duration: Duration) -> u64 {
+    const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+    duration
+        .as_secs()
+        .saturating_mul(NANOS_PER_SEC)
+        .saturating_add(duration.subsec_nanos() as u64)
+}
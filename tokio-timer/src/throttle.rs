@@ -1,6 +1,6 @@
 //! Slow down a stream by enforcing a delay between items.
 
-use {clock, Delay, Error};
+use {timer, Delay, Error};
 
 use futures::future::Either;
 use futures::{Async, Future, Poll, Stream};
@@ -72,7 +72,7 @@ impl<T: Stream> Stream for Throttle<T> {
         let value = try_ready!({ self.stream.poll().map_err(ThrottleError::from_stream_err) });
 
         if value.is_some() {
-            self.delay = Some(Delay::new(clock::now() + self.duration));
+            self.delay = Some(Delay::new(timer::now() + self.duration));
         }
 
         Ok(Async::Ready(value))
@@ -1,30 +1,37 @@
-//! Allows a future or stream to execute for a maximum amount of time.
+//! Allows a future, stream or sink to execute for a maximum amount of time.
 //!
 //! See [`Timeout`] documentation for more details.
 //!
 //! [`Timeout`]: struct.Timeout.html
 
-use clock::now;
+use timer::now;
 use Delay;
 
-use futures::{Async, Future, Poll, Stream};
+use futures::{Async, AsyncSink, Future, Poll, Sink, StartSend, Stream};
 
 use std::error;
 use std::fmt;
 use std::time::{Duration, Instant};
 
-/// Allows a `Future` or `Stream` to execute for a limited amount of time.
+/// Allows a `Future`, `Stream` or `Sink` to execute for a limited amount of
+/// time.
 ///
 /// If the future or stream completes before the timeout has expired, then
 /// `Timeout` returns the completed value. Otherwise, `Timeout` returns an
 /// [`Error`].
 ///
-/// # Futures and Streams
+/// # Futures, Streams and Sinks
 ///
-/// The exact behavor depends on if the inner value is a `Future` or a `Stream`.
-/// In the case of a `Future`, `Timeout` will require the future to complete by
-/// a fixed deadline. In the case of a `Stream`, `Timeout` will allow each item
-/// to take the entire timeout before returning an error.
+/// The exact behavor depends on if the inner value is a `Future`, a `Stream`
+/// or a `Sink`. In the case of a `Future`, `Timeout` will require the future to
+/// complete by a fixed deadline. In the case of a `Stream`, `Timeout` will
+/// allow each item to take the entire timeout before returning an error.
+///
+/// In the case of a `Sink`, each send is allowed to take the entire timeout.
+/// The timeout starts when the sink first applies backpressure or when an item
+/// is accepted, and stops once the sink is flushed, so an idle sink never
+/// times out. If the inner value is both a `Stream` and a `Sink`, progress in
+/// either direction resets the timeout.
 ///
 /// In order to set an upper bound on the processing of the *entire* stream,
 /// then a timeout should be set on the future that processes the stream. For
@@ -73,12 +80,42 @@ use std::time::{Duration, Instant};
 pub struct Timeout<T> {
     value: T,
     delay: Delay,
+
+    /// Set while the inner sink has a send in progress
+    sending: bool,
 }
 
 /// Error returned by `Timeout`.
 #[derive(Debug)]
 pub struct Error<T>(Kind<T>);
 
+/// Future returned by [`Timeout::recoverable`].
+///
+/// Behaves like [`Timeout`], except that the wrapped future is handed back
+/// with the error when the timeout elapses.
+///
+/// [`Timeout`]: struct.Timeout.html
+/// [`Timeout::recoverable`]: struct.Timeout.html#method.recoverable
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Recoverable<T> {
+    timeout: Option<Timeout<T>>,
+}
+
+/// Error returned by [`Recoverable`].
+///
+/// When the timeout elapsed, the error holds the unfinished future, which can
+/// be obtained with [`into_inner`].
+///
+/// [`Recoverable`]: struct.Recoverable.html
+/// [`into_inner`]: #method.into_inner
+pub struct RecoverableError<T, E> {
+    error: Error<E>,
+
+    /// The unfinished future, set when the timeout elapsed
+    value: Option<T>,
+}
+
 /// Timeout error variants
 #[derive(Debug)]
 enum Kind<T> {
@@ -127,7 +164,11 @@ impl<T> Timeout<T> {
     pub fn new(value: T, timeout: Duration) -> Timeout<T> {
         let delay = Delay::new_timeout(now() + timeout, timeout);
 
-        Timeout { value, delay }
+        Timeout {
+            value,
+            delay,
+            sending: false,
+        }
     }
 
     /// Gets a reference to the underlying value in this timeout.
@@ -146,6 +187,45 @@ impl<T> Timeout<T> {
     }
 }
 
+impl<T: Future> Timeout<T> {
+    /// Returns a future that hands back the wrapped future when the timeout
+    /// elapses, instead of dropping it.
+    ///
+    /// This allows retrying the future with a new timeout, or extracting the
+    /// state it accumulated so far. Streams do not need this, as a `Timeout`
+    /// wrapping a stream can still be polled after an item timed out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate futures;
+    /// # extern crate tokio;
+    /// use tokio::timer::Timeout;
+    /// use futures::Future;
+    /// use futures::sync::oneshot;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() {
+    /// let (tx, rx) = oneshot::channel::<()>();
+    ///
+    /// let retry = Timeout::new(rx, Duration::from_millis(10))
+    ///     .recoverable()
+    ///     .or_else(|err| {
+    ///         // Give the receiver some more time
+    ///         let rx = err.into_inner().unwrap();
+    ///         Timeout::new(rx, Duration::from_millis(10))
+    ///     });
+    /// # let _ = tokio::runtime::current_thread::block_on_all(retry);
+    /// # drop(tx);
+    /// # }
+    /// ```
+    pub fn recoverable(self) -> Recoverable<T> {
+        Recoverable {
+            timeout: Some(self),
+        }
+    }
+}
+
 impl<T: Future> Timeout<T> {
     /// Create a new `Timeout` that completes when `future` completes or when
     /// `deadline` is reached.
@@ -160,6 +240,7 @@ impl<T: Future> Timeout<T> {
         Timeout {
             value: future,
             delay,
+            sending: false,
         }
     }
 }
@@ -220,6 +301,157 @@ where
     }
 }
 
+impl<T> Sink for Timeout<T>
+where
+    T: Sink,
+{
+    type SinkItem = T::SinkItem;
+    type SinkError = Error<T::SinkError>;
+
+    fn start_send(&mut self, item: Self::SinkItem) -> StartSend<Self::SinkItem, Self::SinkError> {
+        match self.value.start_send(item) {
+            Ok(AsyncSink::Ready) => {
+                // The item is accepted, it now has the entire timeout to be
+                // flushed.
+                self.sending = true;
+                self.delay.reset_timeout();
+                Ok(AsyncSink::Ready)
+            }
+            Ok(AsyncSink::NotReady(item)) => {
+                self.poll_send_timeout()?;
+                Ok(AsyncSink::NotReady(item))
+            }
+            Err(e) => Err(Error::inner(e)),
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+        match self.value.poll_complete() {
+            Ok(Async::Ready(())) => {
+                self.sending = false;
+                Ok(Async::Ready(()))
+            }
+            Ok(Async::NotReady) => {
+                self.poll_send_timeout()?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(Error::inner(e)),
+        }
+    }
+
+    fn close(&mut self) -> Poll<(), Self::SinkError> {
+        match self.value.close() {
+            Ok(Async::Ready(())) => {
+                self.sending = false;
+                Ok(Async::Ready(()))
+            }
+            Ok(Async::NotReady) => {
+                self.poll_send_timeout()?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(Error::inner(e)),
+        }
+    }
+}
+
+impl<T> Timeout<T> {
+    /// Check the timeout of the send in progress, starting it if the sink was
+    /// idle until now.
+    fn poll_send_timeout<E>(&mut self) -> Result<(), Error<E>> {
+        if !self.sending {
+            self.sending = true;
+            self.delay.reset_timeout();
+        }
+
+        match self.delay.poll() {
+            Ok(Async::NotReady) => Ok(()),
+            Ok(Async::Ready(_)) => {
+                self.delay.reset_timeout();
+                Err(Error::elapsed())
+            }
+            Err(e) => Err(Error::timer(e)),
+        }
+    }
+}
+
+impl<T> Future for Recoverable<T>
+where
+    T: Future,
+{
+    type Item = T::Item;
+    type Error = RecoverableError<T, T::Error>;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let res = self
+            .timeout
+            .as_mut()
+            .expect("cannot poll Recoverable twice")
+            .poll();
+
+        match res {
+            Ok(v) => Ok(v),
+            Err(error) => {
+                let timeout = self.timeout.take().unwrap();
+
+                let value = if error.is_elapsed() {
+                    Some(timeout.into_inner())
+                } else {
+                    None
+                };
+
+                Err(RecoverableError { error, value })
+            }
+        }
+    }
+}
+
+// ===== impl RecoverableError =====
+
+impl<T, E> RecoverableError<T, E> {
+    /// Returns the error of the timeout.
+    pub fn get_ref(&self) -> &Error<E> {
+        &self.error
+    }
+
+    /// Returns `true` if the timeout elapsed before the future completed.
+    pub fn is_elapsed(&self) -> bool {
+        self.error.is_elapsed()
+    }
+
+    /// Consumes `self`, returning the error of the timeout.
+    pub fn into_error(self) -> Error<E> {
+        self.error
+    }
+
+    /// Consumes `self`, returning the unfinished future if the timeout
+    /// elapsed.
+    ///
+    /// Returns `None` if the error was caused by the future or by the timer.
+    pub fn into_inner(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T, E: fmt::Debug> fmt::Debug for RecoverableError<T, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("RecoverableError")
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<T, E: fmt::Display> fmt::Display for RecoverableError<T, E> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.error.fmt(fmt)
+    }
+}
+
+impl<T, E: error::Error> error::Error for RecoverableError<T, E> {
+    fn description(&self) -> &str {
+        error::Error::description(&self.error)
+    }
+}
+
 // ===== impl Error =====
 
 impl<T> Error<T> {
@@ -0,0 +1,171 @@
+use timer::{Now, SystemNow, Timer};
+use wheel;
+
+use tokio_executor::park::Park;
+
+use std::time::Duration;
+
+/// Builds a timer with custom configuration values.
+///
+/// Methods can be chained in order to set the configuration values. The timer
+/// is constructed by calling [`build`].
+///
+/// # Precision and performance
+///
+/// The timer rounds every deadline up to its resolution, and fires all the
+/// timeouts due within a tick at once. A finer resolution makes timeouts fire
+/// closer to their deadline, but the timer wakes up more often when many
+/// timeouts are pending, and the levels of the wheel cover a shorter span of
+/// time. Note that the timer cannot be more precise than the `Park`
+/// implementation it blocks with; parking the thread usually has a precision
+/// in the order of a millisecond.
+///
+/// A coarser resolution batches timeouts that are close to each other, which
+/// reduces the number of wake ups and the work done per timeout, at the cost of
+/// firing timeouts later.
+///
+/// Each level of the wheel covers 64 times the span of the level below it,
+/// starting at 64 ticks for the lowest level. The levels bound how far in the
+/// future a timeout can be set: with `n` levels, deadlines must be less than
+/// `64^n` ticks away. Additional levels cost a bit of memory and make finding
+/// the next timeout slightly slower.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_executor;
+/// # extern crate tokio_timer;
+/// use tokio_executor::park::ParkThread;
+/// use tokio_timer::timer::Builder;
+///
+/// use std::time::Duration;
+///
+/// # pub fn main() {
+/// // A coarse timer, with 10ms ticks
+/// let timer = Builder::new()
+///     .resolution(Duration::from_millis(10))
+///     .build(ParkThread::new());
+/// # drop(timer);
+/// # }
+/// ```
+///
+/// [`build`]: #method.build
+#[derive(Debug, Clone)]
+pub struct Builder {
+    /// Duration of a tick of the wheel
+    resolution: Duration,
+
+    /// Number of levels of the wheel
+    num_levels: usize,
+
+    /// Store timeouts in buckets instead of the wheel
+    coarse: bool,
+}
+
+impl Builder {
+    /// Returns a new timer builder.
+    ///
+    /// Configuration methods can be chained on the return value.
+    pub fn new() -> Builder {
+        Builder {
+            resolution: Duration::from_millis(1),
+            num_levels: wheel::NUM_LEVELS,
+            coarse: false,
+        }
+    }
+
+    /// Set the duration of a tick of the timer.
+    ///
+    /// Deadlines are rounded up to a multiple of the resolution.
+    ///
+    /// The default value is one millisecond.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `val` is zero.
+    pub fn resolution(&mut self, val: Duration) -> &mut Self {
+        assert!(val > Duration::new(0, 0), "resolution must be non-zero");
+        self.resolution = val;
+        self
+    }
+
+    /// Set the number of levels of the timing wheel.
+    ///
+    /// With `val` levels, the timer supports deadlines up to `64^val` ticks in
+    /// the future.
+    ///
+    /// The default value is 6, which supports deadlines about 2 years in the
+    /// future with the default resolution.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `val` is zero or greater than 10.
+    pub fn levels(&mut self, val: usize) -> &mut Self {
+        assert!(
+            val > 0 && val <= wheel::MAX_LEVELS,
+            "the number of levels must be between 1 and {}",
+            wheel::MAX_LEVELS
+        );
+        self.num_levels = val;
+        self
+    }
+
+    /// Enable or disable the coarse mode of the timer.
+    ///
+    /// In coarse mode, timeouts are grouped in buckets, one per tick, instead
+    /// of being stored in the timing wheel. Cancelling a timeout, by dropping
+    /// it, only flags it as canceled: the timer is not notified and skips the
+    /// timeout when its bucket expires.
+    ///
+    /// This makes creating and cancelling timeouts cheaper, which suits
+    /// servers setting a timeout on every request, most of which complete
+    /// before the timeout fires. In exchange, the memory of a canceled timeout
+    /// is only released once its deadline is reached, and finding the next
+    /// bucket to expire gets slower as the number of distinct deadlines grows.
+    /// Coarse mode is best combined with a coarse [`resolution`], which
+    /// reduces the number of buckets.
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate tokio_executor;
+    /// # extern crate tokio_timer;
+    /// use tokio_executor::park::ParkThread;
+    /// use tokio_timer::timer::Builder;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// # pub fn main() {
+    /// let timer = Builder::new()
+    ///     .resolution(Duration::from_millis(100))
+    ///     .coarse(true)
+    ///     .build(ParkThread::new());
+    /// # drop(timer);
+    /// # }
+    /// ```
+    ///
+    /// [`resolution`]: #method.resolution
+    pub fn coarse(&mut self, val: bool) -> &mut Self {
+        self.coarse = val;
+        self
+    }
+
+    /// Create a `Timer` using `park` to block the current thread.
+    pub fn build<T: Park>(&self, park: T) -> Timer<T> {
+        self.build_with_now(park, SystemNow::new())
+    }
+
+    /// Create a `Timer` using `park` to block the current thread and `now` to
+    /// get the current `Instant`.
+    pub fn build_with_now<T: Park, N: Now>(&self, park: T, now: N) -> Timer<T, N> {
+        Timer::from_config(park, now, self.resolution, self.num_levels, self.coarse)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
@@ -191,7 +191,7 @@ impl Entry {
             me.state.store(when, Relaxed);
         }
 
-        if inner.queue(me).is_err() {
+        if inner.register(me).is_err() {
             // The timer has shutdown, transition the entry to the error state.
             me.error();
         }
@@ -230,12 +230,21 @@ impl Entry {
         is_elapsed(state)
     }
 
-    pub fn fire(&self, when: u64) {
+    /// Fire the entry if its deadline is `when` or earlier.
+    ///
+    /// If the deadline was extended past `when`, the entry is not fired and
+    /// the new deadline is returned. The timer must then add the entry back at
+    /// that deadline.
+    pub fn fire(&self, when: u64) -> Option<u64> {
         let mut curr = self.state.load(SeqCst);
 
         loop {
-            if is_elapsed(curr) || curr > when {
-                return;
+            if is_elapsed(curr) {
+                return None;
+            }
+
+            if curr > when {
+                return Some(curr);
             }
 
             let next = ELAPSED | curr;
@@ -249,6 +258,7 @@ impl Entry {
         }
 
         self.task.notify();
+        None
     }
 
     pub fn error(&self) {
@@ -288,6 +298,12 @@ impl Entry {
             None => return,
         };
 
+        // A coarse timer skips canceled entries when their bucket is fired,
+        // there is no need to notify it.
+        if inner.coarse {
+            return;
+        }
+
         let _ = inner.queue(entry);
     }
 
@@ -353,7 +369,12 @@ impl Entry {
                 notify = !is_elapsed(curr);
             } else {
                 next = when;
-                notify = true;
+
+                // When the deadline is extended, the timer keeps the entry at
+                // its old deadline. Once that deadline is reached, the timer
+                // sees the new one and adds the entry back. This avoids going
+                // through the timer for every extension.
+                notify = is_elapsed(curr) || when < curr;
             }
 
             let actual = entry.state.compare_and_swap(curr, next, SeqCst);
@@ -370,6 +391,15 @@ impl Entry {
         }
     }
 
+    /// Returns the current instant according to the clock of the timer,
+    /// falling back to the current timer if the entry is not registered.
+    pub fn now(&self) -> Instant {
+        match self.upgrade_inner() {
+            Some(inner) => inner.clock.now(),
+            None => ::timer::now(),
+        }
+    }
+
     fn upgrade_inner(&self) -> Option<Arc<Inner>> {
         self.inner.as_ref().and_then(|inner| inner.upgrade())
     }
@@ -1,3 +1,4 @@
+use clock::Clock;
 use timer::Inner;
 use {Deadline, Delay, Error, Interval};
 
@@ -150,6 +151,32 @@ impl Handle {
         Interval::new_with_delay(self.delay(at), duration)
     }
 
+    /// Returns the clock of this handle's associated `Timer`.
+    ///
+    /// All the timeouts driven by a timer compute their deadlines using its
+    /// clock, regardless of the thread they are polled from. If the handle
+    /// does not reference a timer, the clock of the current execution context
+    /// is returned.
+    pub fn clock(&self) -> Clock {
+        match self.inner {
+            Some(ref handle_priv) => handle_priv.clock(),
+            None => match HandlePriv::try_current() {
+                Ok(handle_priv) => handle_priv.clock(),
+                Err(_) => Clock::new(),
+            },
+        }
+    }
+
+    /// Returns the current instant according to the clock of this handle's
+    /// associated `Timer`.
+    ///
+    /// See [`clock`] for more details.
+    ///
+    /// [`clock`]: #method.clock
+    pub fn now(&self) -> Instant {
+        self.clock().now()
+    }
+
     fn as_priv(&self) -> Option<&HandlePriv> {
         self.inner.as_ref()
     }
@@ -172,6 +199,15 @@ impl HandlePriv {
         })
     }
 
+    /// Returns the clock of the timer, or the clock of the current execution
+    /// context if the timer is gone.
+    pub(crate) fn clock(&self) -> Clock {
+        match self.inner() {
+            Some(inner) => inner.clock.clone(),
+            None => Clock::new(),
+        }
+    }
+
     /// Try to return a strong ref to the inner
     pub(crate) fn inner(&self) -> Option<Arc<Inner>> {
         self.inner.upgrade()
@@ -183,6 +219,20 @@ impl HandlePriv {
     }
 }
 
+/// Returns the current instant according to the clock of the current timer.
+///
+/// Falls back to the clock of the current execution context when no timer is
+/// set.
+pub(crate) fn now() -> Instant {
+    CURRENT_TIMER.with(|current| match *current.borrow() {
+        Some(ref handle) => match handle.inner() {
+            Some(inner) => inner.clock.now(),
+            None => Clock::new().now(),
+        },
+        None => Clock::new().now(),
+    })
+}
+
 impl fmt::Debug for HandlePriv {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "HandlePriv")
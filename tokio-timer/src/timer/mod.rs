@@ -34,6 +34,7 @@
 #![allow(deprecated)]
 
 mod atomic_stack;
+mod builder;
 mod entry;
 mod handle;
 mod now;
@@ -44,17 +45,21 @@ use self::atomic_stack::AtomicStack;
 use self::entry::Entry;
 use self::stack::Stack;
 
-pub(crate) use self::handle::HandlePriv;
+pub use self::builder::Builder;
+pub(crate) use self::handle::{now, HandlePriv};
 pub use self::handle::{with_default, Handle};
 pub use self::now::{Now, SystemNow};
 pub(crate) use self::registration::Registration;
 
 use atomic::AtomicU64;
+use clock::Clock;
 use wheel;
 use Error;
 
 use tokio_executor::park::{Park, ParkThread, Unpark};
 
+use std::collections::BTreeMap;
+use std::mem;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
 use std::sync::Arc;
@@ -71,6 +76,10 @@ use std::{cmp, fmt};
 /// [`Delay`] instances. The `Timer` implementation is thread-safe and, as such,
 /// is able to handle callers from across threads.
 ///
+/// [`Delay`] instances registered from the thread driving the timer, as is the
+/// case for tasks running on the Tokio runtime, are queued without unparking
+/// the timer, as it processes its queue the next time it parks anyway.
+///
 /// Callers do not use `Timer` directly to create [`Delay`] instances.  Instead,
 /// [`Handle`][Handle.struct] is used. A handle for the timer instance is obtained by calling
 /// [`handle`]. [`Handle`][Handle.struct] is the type that implements `Clone` and is `Send +
@@ -80,8 +89,10 @@ use std::{cmp, fmt};
 /// [`turn`]. The timer will perform no work unless [`turn`] is called
 /// repeatedly.
 ///
-/// The `Timer` has a resolution of one millisecond. Any unit of time that falls
-/// between milliseconds are rounded up to the next millisecond.
+/// By default, the `Timer` has a resolution of one millisecond. Any unit of
+/// time that falls between milliseconds are rounded up to the next
+/// millisecond. The resolution and the number of levels of the timing wheel
+/// can be configured with [`Builder`].
 ///
 /// When the `Timer` instance is dropped, any outstanding [`Delay`] instance that
 /// has not elapsed will be notified with an error. At this point, calling
@@ -118,9 +129,14 @@ use std::{cmp, fmt};
 /// either be canceled (dropped) or their associated entries will reach level
 /// zero and be notified.
 ///
+/// The resolution and the number of levels given above are the defaults. With
+/// a different resolution, each level zero slot represents one unit of that
+/// resolution instead of one millisecond.
+///
 /// [`Delay`]: ../struct.Delay.html
 /// [`Interval`]: ../struct.Interval.html
 /// [`Timeout`]: ../struct.Timeout.html
+/// [`Builder`]: struct.Builder.html
 /// [paper]: http://www.cs.columbia.edu/~nahum/w6998/papers/ton97-timing-wheels.pdf
 /// [`handle`]: #method.handle
 /// [`turn`]: #method.turn
@@ -133,6 +149,10 @@ pub struct Timer<T, N = SystemNow> {
     /// Timer wheel
     wheel: wheel::Wheel<Stack>,
 
+    /// Timeouts of a coarse timer, grouped by the tick they expire at. These
+    /// are not stored in the wheel.
+    buckets: BTreeMap<u64, Vec<Arc<Entry>>>,
+
     /// Thread parker. The `Timer` park implementation delegates to this.
     park: T,
 
@@ -152,6 +172,16 @@ pub(crate) struct Inner {
     /// The instant at which the timer started running.
     start: Instant,
 
+    /// The clock of the timer, used to compute deadlines relative to now for
+    /// all the timeouts driven by the timer.
+    clock: Clock,
+
+    /// The duration of a tick of the wheel, in nanoseconds.
+    resolution: u64,
+
+    /// Set when timeouts are stored in buckets instead of the wheel.
+    coarse: bool,
+
     /// The last published timer `elapsed` value.
     elapsed: AtomicU64,
 
@@ -161,6 +191,9 @@ pub(crate) struct Inner {
     /// Head of the "process" linked list.
     process: AtomicStack,
 
+    /// Identifies the thread the timer was last parked on, see `thread_id`.
+    driver: AtomicUsize,
+
     /// Unparks the timer thread.
     unpark: Box<Unpark>,
 }
@@ -210,12 +243,25 @@ where
     /// thread and `now` to get the current `Instant`.
     ///
     /// Specifying the source of time is useful when testing.
-    pub fn new_with_now(park: T, mut now: N) -> Self {
+    pub fn new_with_now(park: T, now: N) -> Self {
+        Builder::new().build_with_now(park, now)
+    }
+
+    pub(crate) fn from_config(
+        park: T,
+        mut now: N,
+        resolution: Duration,
+        num_levels: usize,
+        coarse: bool,
+    ) -> Self {
         let unpark = Box::new(park.unpark());
+        let clock = now.clock().unwrap_or_else(Clock::new);
+        let inner = Inner::new(now.now(), clock, ::nanos(resolution), coarse, unpark);
 
         Timer {
-            inner: Arc::new(Inner::new(now.now(), unpark)),
-            wheel: wheel::Wheel::new(),
+            inner: Arc::new(inner),
+            wheel: wheel::Wheel::with_levels(num_levels),
+            buckets: BTreeMap::new(),
             park,
             now,
         }
@@ -261,22 +307,35 @@ where
 
     /// Converts an `Expiration` to an `Instant`.
     fn expiration_instant(&self, when: u64) -> Instant {
-        self.inner.start + Duration::from_millis(when)
+        let nanos = when.saturating_mul(self.inner.resolution);
+        self.inner.start + Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
     }
 
     /// Run timer related logic
     fn process(&mut self) {
-        let now = ::ms(self.now.now() - self.inner.start, ::Round::Down);
+        let elapsed = self.now.now() - self.inner.start;
+        let now = self.inner.ticks(elapsed, ::Round::Down);
         let mut poll = wheel::Poll::new(now);
 
         while let Some(entry) = self.wheel.poll(&mut poll, &mut ()) {
             let when = entry.when_internal().expect("invalid internal entry state");
 
             // Fire the entry
-            entry.fire(when);
+            self.fire_entry(entry, when);
+        }
+
+        // Fire the coarse timeouts that are due. Canceled timeouts are skipped
+        // by `fire` as they are already flagged as elapsed.
+        while let Some(&when) = self.buckets.keys().next() {
+            if when > now {
+                break;
+            }
 
-            // Track that the entry has been fired
-            entry.set_when_internal(None);
+            for entry in self.buckets.remove(&when).unwrap() {
+                if let Some(next) = entry.fire(when) {
+                    self.add_coarse_entry(entry, next);
+                }
+            }
         }
 
         // Update the elapsed cache
@@ -287,7 +346,21 @@ where
     ///
     /// This handles adding and canceling timeouts.
     fn process_queue(&mut self) {
+        // Entries registered from this thread from now on are picked up the
+        // next time the timer parks, without unparking it.
+        self.inner.driver.store(thread_id(), SeqCst);
+
         for entry in self.inner.process.take() {
+            if self.inner.coarse {
+                // Stale copies of the entry in other buckets are skipped when
+                // they are fired.
+                if let Some(when) = entry.load_state() {
+                    self.add_coarse_entry(entry, when);
+                }
+
+                continue;
+            }
+
             match (entry.when_internal(), entry.load_state()) {
                 (None, None) => {
                     // Nothing to do
@@ -326,8 +399,7 @@ where
             Err((entry, InsertError::Elapsed)) => {
                 // The entry's deadline has elapsed, so fire it and update the
                 // internal state accordingly.
-                entry.set_when_internal(None);
-                entry.fire(when);
+                self.fire_entry(entry, when);
             }
             Err((entry, InsertError::Invalid)) => {
                 // The entry's deadline is invalid, so error it and update the
@@ -337,6 +409,46 @@ where
             }
         }
     }
+
+    /// Store the entry in the bucket of `when`, firing it if `when` has already
+    /// elapsed.
+    fn add_coarse_entry(&mut self, entry: Arc<Entry>, when: u64) {
+        if when <= self.wheel.elapsed() {
+            if let Some(next) = entry.fire(when) {
+                self.add_coarse_entry(entry, next);
+            }
+
+            return;
+        }
+
+        self.buckets
+            .entry(when)
+            .or_insert_with(Vec::new)
+            .push(entry);
+    }
+
+    /// Returns the tick at which the next timeout expires.
+    fn poll_at(&self) -> Option<u64> {
+        let bucket = self.buckets.keys().next().cloned();
+
+        match (self.wheel.poll_at(), bucket) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Fire an entry that is no longer stored in the wheel.
+    ///
+    /// If the entry's deadline was extended since it was added to the wheel,
+    /// it is added back at its new deadline instead.
+    fn fire_entry(&mut self, entry: Arc<Entry>, when: u64) {
+        // Track that the entry has been removed from the wheel
+        entry.set_when_internal(None);
+
+        if let Some(next) = entry.fire(when) {
+            self.add_entry(entry, next);
+        }
+    }
 }
 
 impl Default for Timer<ParkThread, SystemNow> {
@@ -360,7 +472,7 @@ where
     fn park(&mut self) -> Result<(), Self::Error> {
         self.process_queue();
 
-        match self.wheel.poll_at() {
+        match self.poll_at() {
             Some(when) => {
                 let now = self.now.now();
                 let deadline = self.expiration_instant(when);
@@ -384,7 +496,7 @@ where
     fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
         self.process_queue();
 
-        match self.wheel.poll_at() {
+        match self.poll_at() {
             Some(when) => {
                 let now = self.now.now();
                 let deadline = self.expiration_instant(when);
@@ -420,18 +532,34 @@ impl<T, N> Drop for Timer<T, N> {
         while let Some(entry) = self.wheel.poll(&mut poll, &mut ()) {
             entry.error();
         }
+
+        for (_, entries) in mem::replace(&mut self.buckets, BTreeMap::new()) {
+            for entry in entries {
+                entry.error();
+            }
+        }
     }
 }
 
 // ===== impl Inner =====
 
 impl Inner {
-    fn new(start: Instant, unpark: Box<Unpark>) -> Inner {
+    fn new(
+        start: Instant,
+        clock: Clock,
+        resolution: u64,
+        coarse: bool,
+        unpark: Box<Unpark>,
+    ) -> Inner {
         Inner {
             num: AtomicUsize::new(0),
             elapsed: AtomicU64::new(0),
             process: AtomicStack::new(),
             start,
+            clock,
+            resolution,
+            coarse,
+            driver: AtomicUsize::new(0),
             unpark,
         }
     }
@@ -474,12 +602,43 @@ impl Inner {
         Ok(())
     }
 
+    /// Queue a newly registered entry.
+    ///
+    /// The timer is not unparked when the entry is registered from the thread
+    /// driving it, as the timer cannot be parked and processes its queue the
+    /// next time it parks.
+    fn register(&self, entry: &Arc<Entry>) -> Result<(), Error> {
+        let id = thread_id();
+
+        if id == 0 || self.driver.load(SeqCst) != id {
+            return self.queue(entry);
+        }
+
+        if self.process.push(entry)? && self.driver.load(SeqCst) != id {
+            // The timer was handed to another thread, which may have parked
+            // it before the entry was pushed.
+            self.unpark.unpark();
+        }
+
+        Ok(())
+    }
+
     fn normalize_deadline(&self, deadline: Instant) -> u64 {
         if deadline < self.start {
             return 0;
         }
 
-        ::ms(deadline - self.start, ::Round::Up)
+        self.ticks(deadline - self.start, ::Round::Up)
+    }
+
+    /// Convert a `Duration` to a number of ticks of the wheel.
+    fn ticks(&self, duration: Duration, round: ::Round) -> u64 {
+        let nanos = ::nanos(duration);
+
+        match round {
+            ::Round::Up => nanos / self.resolution + (nanos % self.resolution != 0) as u64,
+            ::Round::Down => nanos / self.resolution,
+        }
     }
 }
 
@@ -488,3 +647,16 @@ impl fmt::Debug for Inner {
         fmt.debug_struct("Inner").finish()
     }
 }
+
+/// Returns an identifier of the current thread, never reused by another
+/// thread, unlike the address of a thread-local.
+///
+/// Zero is returned while the thread is exiting, and never identifies a
+/// thread driving a timer.
+fn thread_id() -> usize {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+    thread_local!(static ID: usize = NEXT_ID.fetch_add(1, SeqCst));
+
+    ID.try_with(|id| *id).unwrap_or(0)
+}
@@ -1,3 +1,5 @@
+use clock::Clock;
+
 use std::time::Instant;
 
 #[doc(hidden)]
@@ -5,6 +7,11 @@ use std::time::Instant;
 pub trait Now {
     /// Returns an instant corresponding to "now".
     fn now(&mut self) -> Instant;
+
+    /// Returns the `Clock` this source of time reads, if it is one.
+    fn clock(&self) -> Option<Clock> {
+        None
+    }
 }
 
 pub use clock::Clock as SystemNow;
@@ -1,4 +1,3 @@
-use clock::now;
 use timer::{Entry, HandlePriv};
 use Error;
 
@@ -46,11 +45,17 @@ impl Registration {
     }
 
     pub fn reset_timeout(&mut self) {
-        let deadline = now() + self.entry.time_ref().duration;
+        let deadline = self.now() + self.entry.time_ref().duration;
         self.entry.time_mut().deadline = deadline;
         Entry::reset(&mut self.entry);
     }
 
+    /// Returns the current instant according to the clock of the timer the
+    /// entry is registered with.
+    pub fn now(&self) -> Instant {
+        self.entry.now()
+    }
+
     pub fn is_elapsed(&self) -> bool {
         self.entry.is_elapsed()
     }
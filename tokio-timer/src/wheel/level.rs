@@ -194,6 +194,16 @@ impl<T: Stack> Level<T> {
         }
     }
 
+    /// Returns the entries stored in `slot`.
+    pub fn entries(&self, slot: usize) -> &T {
+        &self.slot[slot]
+    }
+
+    /// Returns the slot storing the entries that fire at `when`.
+    pub fn slot_for(&self, when: u64) -> usize {
+        slot_for(when, self.level)
+    }
+
     pub fn pop_entry_slot(&mut self, slot: usize, store: &mut T::Store) -> Option<T::Owned> {
         let ret = self.slot[slot].pop(store);
 
@@ -6,6 +6,7 @@ use self::level::Level;
 pub(crate) use self::stack::Stack;
 
 use std::borrow::Borrow;
+use std::cmp;
 use std::usize;
 
 /// Timing wheel implementation.
@@ -21,9 +22,12 @@ use std::usize;
 /// See `Timer` documentation for some implementation notes.
 #[derive(Debug)]
 pub(crate) struct Wheel<T> {
-    /// The number of milliseconds elapsed since the wheel started.
+    /// The number of ticks elapsed since the wheel started.
     elapsed: u64,
 
+    /// The maximum number of ticks an entry can be stored in the future.
+    max_duration: u64,
+
     /// Timer wheel.
     ///
     /// Levels:
@@ -37,13 +41,13 @@ pub(crate) struct Wheel<T> {
     levels: Vec<Level<T>>,
 }
 
-/// Number of levels. Each level has 64 slots. By using 6 levels with 64 slots
-/// each, the timer is able to track time up to 2 years into the future with a
-/// precision of 1 millisecond.
-const NUM_LEVELS: usize = 6;
+/// Default number of levels. Each level has 64 slots. By using 6 levels with
+/// 64 slots each, the timer is able to track time up to 2 years into the future
+/// with a precision of 1 millisecond.
+pub(crate) const NUM_LEVELS: usize = 6;
 
-/// The maximum duration of a delay
-const MAX_DURATION: u64 = 1 << (6 * NUM_LEVELS);
+/// Maximum number of levels, bounded by the number of bits in a tick count.
+pub(crate) const MAX_LEVELS: usize = 10;
 
 #[derive(Debug)]
 pub(crate) enum InsertError {
@@ -64,13 +68,24 @@ where
 {
     /// Create a new timing wheel
     pub fn new() -> Wheel<T> {
-        let levels = (0..NUM_LEVELS).map(Level::new).collect();
+        Wheel::with_levels(NUM_LEVELS)
+    }
+
+    /// Create a new timing wheel with `num_levels` levels
+    pub fn with_levels(num_levels: usize) -> Wheel<T> {
+        assert!(num_levels > 0 && num_levels <= MAX_LEVELS);
+
+        let levels = (0..num_levels).map(Level::new).collect();
 
-        Wheel { elapsed: 0, levels }
+        Wheel {
+            elapsed: 0,
+            max_duration: 1 << (6 * num_levels),
+            levels,
+        }
     }
 
-    /// Return the number of milliseconds that have elapsed since the timing
-    /// wheel's creation.
+    /// Return the number of ticks that have elapsed since the timing wheel's
+    /// creation.
     pub fn elapsed(&self) -> u64 {
         self.elapsed
     }
@@ -104,7 +119,7 @@ where
     ) -> Result<(), (T::Owned, InsertError)> {
         if when <= self.elapsed {
             return Err((item, InsertError::Elapsed));
-        } else if when - self.elapsed > MAX_DURATION {
+        } else if when - self.elapsed > self.max_duration {
             return Err((item, InsertError::Invalid));
         }
 
@@ -131,6 +146,30 @@ where
         self.levels[level].remove_entry(when, item, store);
     }
 
+    /// Returns `true` if entries firing at `a` and at `b` are stored in the
+    /// same slot.
+    ///
+    /// The deadline of an entry can be changed to any instant stored in the
+    /// same slot without moving the entry.
+    pub fn is_same_slot(&self, a: u64, b: u64) -> bool {
+        if a <= self.elapsed || b <= self.elapsed {
+            return false;
+        }
+
+        let level = self.level_for(a);
+
+        level == self.level_for(b)
+            && self.levels[level].slot_for(a) == self.levels[level].slot_for(b)
+    }
+
+    /// Returns the slot that will be processed next, if any.
+    ///
+    /// The entry firing first is stored in that slot.
+    pub fn next_entries(&self) -> Option<&T> {
+        self.next_expiration()
+            .map(|expiration| self.levels[expiration.level].entries(expiration.slot))
+    }
+
     /// Instant at which to poll
     pub fn poll_at(&self) -> Option<u64> {
         self.next_expiration().map(|expiration| expiration.deadline)
@@ -169,14 +208,14 @@ where
     /// Returns the instant at which the next timeout expires.
     fn next_expiration(&self) -> Option<Expiration> {
         // Check all levels
-        for level in 0..NUM_LEVELS {
+        for level in 0..self.levels.len() {
             if let Some(expiration) = self.levels[level].next_expiration(self.elapsed) {
                 // There cannot be any expirations at a higher level that happen
                 // before this one.
                 debug_assert!({
                     let mut res = true;
 
-                    for l2 in (level + 1)..NUM_LEVELS {
+                    for l2 in (level + 1)..self.levels.len() {
                         if let Some(e2) = self.levels[l2].next_expiration(self.elapsed) {
                             if e2.deadline < expiration.deadline {
                                 res = false;
@@ -234,7 +273,8 @@ where
     }
 
     fn level_for(&self, when: u64) -> usize {
-        level_for(self.elapsed, when)
+        // Entries that do not fit in the lower levels wrap around the top one
+        cmp::min(level_for(self.elapsed, when), self.levels.len() - 1)
     }
 }
 
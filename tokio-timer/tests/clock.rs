@@ -1,10 +1,17 @@
+#![allow(deprecated)]
+
+extern crate futures;
 extern crate tokio_executor;
 extern crate tokio_timer;
 
 use tokio_timer::clock;
 use tokio_timer::clock::*;
+use tokio_timer::{Delay, Timeout};
+
+use futures::{future, Future};
 
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
 
 struct ConstNow(Instant);
 
@@ -49,3 +56,81 @@ fn execution_context() {
         assert!(b <= a);
     });
 }
+
+#[test]
+fn mock_clock_pause_and_resume() {
+    let mock = MockClock::new();
+    assert!(mock.is_paused());
+
+    let start = mock.now();
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(mock.now(), start);
+
+    mock.resume();
+    thread::sleep(Duration::from_millis(10));
+    assert!(mock.now() >= start + Duration::from_millis(10));
+
+    mock.pause();
+    let paused = mock.now();
+    thread::sleep(Duration::from_millis(10));
+    assert_eq!(mock.now(), paused);
+
+    mock.enter(|| assert_eq!(clock::now(), paused));
+}
+
+#[test]
+fn mock_clock_advance_fires_delays() {
+    let mock = MockClock::new();
+
+    mock.enter(|| {
+        let start = clock::now();
+        let mut short = Delay::new(start + Duration::from_millis(100));
+        let mut long = Delay::new(start + Duration::from_secs(3600));
+
+        assert!(short.poll().unwrap().is_not_ready());
+        assert!(long.poll().unwrap().is_not_ready());
+
+        mock.advance(Duration::from_millis(99));
+        assert!(short.poll().unwrap().is_not_ready());
+
+        mock.advance(Duration::from_millis(1));
+        assert_eq!(clock::now(), start + Duration::from_millis(100));
+        assert!(short.poll().unwrap().is_ready());
+        assert!(long.poll().unwrap().is_not_ready());
+
+        mock.advance(Duration::from_secs(3600));
+        assert!(long.poll().unwrap().is_ready());
+    });
+}
+
+#[test]
+#[should_panic]
+fn mock_clock_advance_while_running() {
+    let mock = MockClock::new();
+    mock.resume();
+    mock.advance(Duration::from_millis(1));
+}
+
+#[test]
+fn timer_uses_its_own_clock() {
+    let mock = MockClock::new();
+    mock.advance(Duration::from_secs(3600));
+
+    let handle = mock.handle();
+    assert_eq!(handle.now(), mock.now());
+
+    // Only the timer is set for the execution context, the thread-local clock
+    // is the system one.
+    let mut enter = tokio_executor::enter().unwrap();
+    let mut timeout = tokio_timer::timer::with_default(&handle, &mut enter, |_| {
+        Timeout::new(future::empty::<(), ()>(), Duration::from_millis(100))
+    });
+    drop(enter);
+
+    mock.enter(|| {
+        assert!(timeout.poll().unwrap().is_not_ready());
+
+        mock.advance(Duration::from_millis(100));
+        assert!(timeout.poll().unwrap_err().is_elapsed());
+    });
+}
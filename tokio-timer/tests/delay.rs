@@ -6,7 +6,8 @@ extern crate tokio_timer;
 mod support;
 use support::*;
 
-use tokio_timer::timer::Handle;
+use tokio_executor::park::ParkThread;
+use tokio_timer::timer::{Builder, Handle, Timer};
 use tokio_timer::*;
 
 use futures::Future;
@@ -413,6 +414,29 @@ fn reset_future_delay_before_fire() {
     });
 }
 
+#[test]
+fn extend_registered_delay() {
+    mocked(|timer, time| {
+        let mut delay = Delay::new(time.now() + ms(10));
+
+        assert_not_ready!(delay);
+        turn(timer, ms(1));
+
+        // Extend the deadline a few times after the timer saw the delay
+        delay.reset(time.now() + ms(20));
+        delay.reset(time.now() + ms(30));
+
+        // The timer wakes up at the original deadline, then moves the delay
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(10));
+        assert_not_ready!(delay);
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(31));
+        assert_ready!(delay);
+    });
+}
+
 #[test]
 fn reset_past_delay_before_turn() {
     mocked(|timer, time| {
@@ -497,3 +521,155 @@ fn delay_with_default_handle() {
         assert_ready!(delay);
     });
 }
+
+#[test]
+fn coarse_resolution() {
+    let mut builder = Builder::new();
+    builder.resolution(ms(10));
+
+    mocked_with_builder(Instant::now(), &builder, |timer, time| {
+        let mut delay = Delay::new(time.now() + ms(15));
+        assert_not_ready!(delay);
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(20));
+
+        assert_ready!(delay);
+    });
+}
+
+#[test]
+fn fine_resolution() {
+    let mut builder = Builder::new();
+    builder.resolution(Duration::from_micros(100));
+
+    mocked_with_builder(Instant::now(), &builder, |timer, time| {
+        let mut delay = Delay::new(time.now() + Duration::from_micros(1_500));
+        assert_not_ready!(delay);
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), Duration::from_micros(1_500));
+
+        assert_ready!(delay);
+    });
+}
+
+#[test]
+fn deadline_beyond_levels() {
+    let mut builder = Builder::new();
+    builder.levels(2);
+
+    mocked_with_builder(Instant::now(), &builder, |timer, time| {
+        // Two levels cover 64 * 64 ms
+        let mut near = Delay::new(time.now() + ms(4_000));
+        let mut far = Delay::new(time.now() + ms(5_000));
+
+        assert_not_ready!(near);
+        assert_not_ready!(far);
+
+        turn(timer, ms(0));
+
+        assert_not_ready!(near);
+        assert!(far.poll().is_err());
+    });
+}
+
+#[test]
+fn coarse_timer() {
+    let mut builder = Builder::new();
+    builder.resolution(ms(10)).coarse(true);
+
+    mocked_with_builder(Instant::now(), &builder, |timer, time| {
+        let mut first = Delay::new(time.now() + ms(15));
+        let mut second = Delay::new(time.now() + ms(18));
+        let mut canceled = Delay::new(time.now() + ms(5));
+
+        assert_not_ready!(first);
+        assert_not_ready!(second);
+        assert_not_ready!(canceled);
+        turn(timer, ms(0));
+
+        // The canceled delay stays in its bucket until it expires
+        drop(canceled);
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(10));
+
+        // Both delays share the bucket expiring at 20ms
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(20));
+
+        assert_ready!(first);
+        assert_ready!(second);
+    });
+}
+
+#[test]
+fn coarse_timer_reset() {
+    let mut builder = Builder::new();
+    builder.resolution(ms(10)).coarse(true);
+
+    mocked_with_builder(Instant::now(), &builder, |timer, time| {
+        let mut delay = Delay::new(time.now() + ms(10));
+        assert_not_ready!(delay);
+        turn(timer, ms(0));
+
+        // Extending the deadline moves the delay to a later bucket once the
+        // first one expires.
+        delay.reset(time.now() + ms(30));
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(10));
+        assert_not_ready!(delay);
+
+        // Moving it earlier goes through the timer
+        delay.reset(time.now() + ms(10));
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(20));
+        assert_ready!(delay);
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(30));
+    });
+}
+
+#[test]
+fn many_delays_registered_between_turns() {
+    mocked(|timer, time| {
+        turn(timer, ms(0));
+
+        // Registered from the thread driving the timer, the delays are only
+        // handed to the timer when it parks.
+        let mut delays: Vec<_> = (1..100).map(|i| Delay::new(time.now() + ms(i))).collect();
+
+        for delay in &mut delays {
+            assert_not_ready!(delay);
+        }
+
+        turn(timer, None);
+        assert_eq!(time.advanced(), ms(1));
+
+        assert_ready!(delays[0]);
+        assert_not_ready!(delays[1]);
+
+        advance(timer, ms(98));
+
+        for delay in &mut delays {
+            assert_ready!(delay);
+        }
+    });
+}
+
+#[test]
+fn staged_delay_errors_on_timer_drop() {
+    let mut timer = Timer::new(ParkThread::new());
+    timer.turn(Some(ms(0))).unwrap();
+
+    let mut delay = timer.handle().delay(Instant::now() + ms(100));
+
+    drop(timer);
+
+    assert!(delay.poll().is_err());
+}
@@ -44,3 +44,69 @@ fn usage() {
         assert_not_ready!(int);
     });
 }
+
+#[test]
+fn missed_ticks_burst() {
+    mocked(|timer, time| {
+        let start = time.now();
+        let mut int = Interval::new(start, ms(100));
+        assert_eq!(int.missed_tick_behavior(), MissedTickBehavior::Burst);
+
+        assert_ready_eq!(int, Some(start));
+
+        advance(timer, ms(350));
+        assert_ready_eq!(int, Some(start + ms(100)));
+        assert_ready_eq!(int, Some(start + ms(200)));
+        assert_ready_eq!(int, Some(start + ms(300)));
+        assert_not_ready!(int);
+
+        advance(timer, ms(50));
+        assert_ready_eq!(int, Some(start + ms(400)));
+        assert_not_ready!(int);
+    });
+}
+
+#[test]
+fn missed_ticks_delay() {
+    mocked(|timer, time| {
+        let start = time.now();
+        let mut int = Interval::new(start, ms(100));
+        int.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        assert_ready_eq!(int, Some(start));
+
+        advance(timer, ms(350));
+        assert_ready_eq!(int, Some(start + ms(100)));
+        assert_not_ready!(int);
+
+        advance(timer, ms(50));
+        assert_not_ready!(int);
+
+        advance(timer, ms(50));
+        assert_ready_eq!(int, Some(start + ms(450)));
+        assert_not_ready!(int);
+    });
+}
+
+#[test]
+fn missed_ticks_skip() {
+    mocked(|timer, time| {
+        let start = time.now();
+        let mut int = Interval::new(start, ms(100));
+        int.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        assert_ready_eq!(int, Some(start));
+
+        advance(timer, ms(350));
+        assert_ready_eq!(int, Some(start + ms(100)));
+        assert_not_ready!(int);
+
+        advance(timer, ms(50));
+        assert_ready_eq!(int, Some(start + ms(400)));
+        assert_not_ready!(int);
+
+        advance(timer, ms(100));
+        assert_ready_eq!(int, Some(start + ms(500)));
+        assert_not_ready!(int);
+    });
+}
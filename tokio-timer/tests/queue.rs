@@ -186,7 +186,7 @@ fn reset_entry() {
             assert_not_ready!(queue);
         });
 
-        turn(timer, ms(7));
+        advance(timer, ms(7));
 
         assert!(!task.is_notified());
 
@@ -194,7 +194,7 @@ fn reset_entry() {
             assert_not_ready!(queue);
         });
 
-        turn(timer, ms(3));
+        advance(timer, ms(3));
 
         assert!(task.is_notified());
 
@@ -404,3 +404,93 @@ fn reset_first_expiring_item_to_expire_later() {
         assert_eq!(entry, "bar");
     })
 }
+
+#[test]
+fn reset_within_same_slot() {
+    mocked(|timer, time| {
+        let mut queue = DelayQueue::new();
+        let mut task = MockTask::new();
+
+        let now = time.now();
+
+        // Both deadlines are stored in the same slot of the second level
+        let key = queue.insert_at("foo", now + ms(100));
+        queue.reset_at(&key, now + ms(110));
+
+        task.enter(|| {
+            assert_not_ready!(queue);
+        });
+
+        advance(timer, ms(105));
+
+        task.enter(|| {
+            assert_not_ready!(queue);
+        });
+
+        advance(timer, ms(10));
+
+        let entry = assert_ready!(queue).unwrap();
+        assert_eq!(*entry.get_ref(), "foo");
+        assert_eq!(entry.deadline(), now + ms(110));
+    });
+}
+
+#[test]
+fn reset_expired_entry() {
+    mocked(|timer, time| {
+        let mut queue = DelayQueue::new();
+        let mut task = MockTask::new();
+
+        let now = time.now();
+        let key = queue.insert_at("foo", now);
+
+        queue.reset_at(&key, now + ms(10));
+
+        task.enter(|| {
+            assert_not_ready!(queue);
+        });
+
+        turn(timer, ms(10));
+
+        let entry = assert_ready!(queue).unwrap();
+        assert_eq!(*entry.get_ref(), "foo");
+    });
+}
+
+#[test]
+fn peek_and_len() {
+    mocked(|_timer, time| {
+        let mut queue = DelayQueue::new();
+
+        assert_eq!(queue.len(), 0);
+        assert!(queue.peek().is_none());
+
+        let now = time.now();
+        queue.insert_at("c", now + ms(300));
+        queue.insert_at("a", now + ms(100));
+        queue.insert_at("b", now + ms(120));
+        assert_eq!(queue.len(), 3);
+
+        let next = queue.peek().unwrap();
+        assert_eq!(*queue.remove(&next).get_ref(), "a");
+
+        let next = queue.peek().unwrap();
+        assert_eq!(*queue.remove(&next).get_ref(), "b");
+        assert_eq!(queue.len(), 1);
+
+        let key = queue.insert_at("now", now);
+        assert_eq!(queue.peek(), Some(key));
+    });
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut queue = DelayQueue::with_capacity(128);
+    assert!(queue.capacity() >= 128);
+
+    queue.insert("foo", ms(10));
+    queue.shrink_to_fit();
+
+    assert!(queue.capacity() < 128);
+    assert_eq!(queue.len(), 1);
+}
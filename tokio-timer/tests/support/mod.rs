@@ -2,7 +2,7 @@
 
 use tokio_executor::park::{Park, Unpark};
 use tokio_timer::clock::Now;
-use tokio_timer::timer::Timer;
+use tokio_timer::timer::{Builder, Timer};
 
 use futures::future::{lazy, Future};
 
@@ -135,6 +135,13 @@ where
 }
 
 pub fn mocked_with_now<F, R>(now: Instant, f: F) -> R
+where
+    F: FnOnce(&mut Timer<MockPark>, &mut MockTime) -> R,
+{
+    mocked_with_builder(now, &Builder::new(), f)
+}
+
+pub fn mocked_with_builder<F, R>(now: Instant, builder: &Builder, f: F) -> R
 where
     F: FnOnce(&mut Timer<MockPark>, &mut MockTime) -> R,
 {
@@ -145,7 +152,7 @@ where
     let mut enter = ::tokio_executor::enter().unwrap();
 
     ::tokio_timer::clock::with_default(&now, &mut enter, |enter| {
-        let mut timer = Timer::new(park);
+        let mut timer = builder.build(park);
         let handle = timer.handle();
 
         ::tokio_timer::with_default(&handle, enter, |_| {
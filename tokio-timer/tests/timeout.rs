@@ -9,7 +9,7 @@ use support::*;
 use tokio_timer::*;
 
 use futures::sync::{mpsc, oneshot};
-use futures::{future, Future, Stream};
+use futures::{future, AsyncSink, Future, Sink, Stream};
 
 #[test]
 fn simultaneous_deadline_future_completion() {
@@ -177,3 +177,96 @@ fn idle_stream_timesout_periodically() {
         assert_elapsed!(stream);
     });
 }
+
+#[test]
+fn sink_send_times_out() {
+    mocked(|timer, _time| {
+        let (tx, mut rx) = mpsc::channel::<u32>(1);
+        let mut sink = Timeout::new(tx, ms(100));
+
+        // Fill the channel
+        assert_eq!(sink.start_send(1).unwrap(), AsyncSink::Ready);
+        assert_eq!(sink.start_send(2).unwrap(), AsyncSink::Ready);
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::NotReady(3));
+
+        advance(timer, ms(90));
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::NotReady(3));
+
+        advance(timer, ms(10));
+        assert!(sink.start_send(3).unwrap_err().is_elapsed());
+
+        // Receiving makes room, the send completes
+        assert_ready_eq!(rx, Some(1));
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::Ready);
+    });
+}
+
+#[test]
+fn idle_sink_does_not_time_out() {
+    mocked(|timer, _time| {
+        let (tx, mut rx) = mpsc::channel::<u32>(1);
+        let mut sink = Timeout::new(tx, ms(100));
+
+        assert_eq!(sink.start_send(1).unwrap(), AsyncSink::Ready);
+        assert!(sink.poll_complete().unwrap().is_ready());
+
+        // The sink stays idle for longer than the timeout
+        advance(timer, ms(500));
+
+        assert_eq!(sink.start_send(2).unwrap(), AsyncSink::Ready);
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::NotReady(3));
+
+        // The timeout starts when the sink applies backpressure
+        advance(timer, ms(90));
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::NotReady(3));
+
+        assert_ready_eq!(rx, Some(1));
+        assert_eq!(sink.start_send(3).unwrap(), AsyncSink::Ready);
+    });
+}
+
+#[test]
+fn sink_error_is_inner() {
+    mocked(|_timer, _time| {
+        let (tx, rx) = mpsc::channel::<u32>(0);
+        let mut sink = Timeout::new(tx, ms(100));
+
+        drop(rx);
+
+        let err = sink.start_send(1).unwrap_err();
+        assert!(err.is_inner());
+        assert!(!err.is_elapsed());
+    });
+}
+
+#[test]
+fn recoverable_returns_future_on_elapse() {
+    mocked(|timer, _time| {
+        let (tx, rx) = oneshot::channel();
+
+        let mut fut = Timeout::new(rx, ms(100)).recoverable();
+        assert_not_ready!(fut);
+
+        advance(timer, ms(100));
+
+        let err = fut.poll().unwrap_err();
+        assert!(err.is_elapsed());
+
+        // The receiver is handed back and still usable
+        let mut rx = err.into_inner().unwrap();
+        tx.send(7).unwrap();
+        assert_ready_eq!(rx, 7);
+    });
+}
+
+#[test]
+fn recoverable_inner_error() {
+    mocked(|_timer, _time| {
+        let mut fut = Timeout::new(future::err::<(), _>("boom"), ms(100)).recoverable();
+
+        let err = fut.poll().unwrap_err();
+        assert!(!err.is_elapsed());
+        assert!(err.get_ref().is_inner());
+        assert!(err.into_inner().is_none());
+    });
+}
@@ -0,0 +1,61 @@
+//! Attributing the work of futures to spans.
+//!
+//! Available with the `futures` feature enabled.
+
+use span::Context;
+
+use futures::{Future, Poll};
+
+/// Enters the span that was executing when the future was created around each
+/// poll of the future.
+///
+/// A future spawned onto an executor is polled outside of the code that
+/// spawned it, and so outside of its span. Wrapping the future in
+/// `InCurrentSpan` before spawning it attributes the work done by the task to
+/// the span that spawned it, with that span's subscriber.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// #[macro_use]
+/// extern crate tokio_trace;
+/// use futures::Future;
+/// use tokio_trace::future::InCurrentSpan;
+///
+/// # fn main() {
+/// let mut span = span!("request");
+///
+/// let future = span.enter(|| InCurrentSpan::new(futures::future::lazy(|| {
+///     // Runs in `request`, wherever the future is polled.
+///     Ok::<(), ()>(())
+/// })));
+///
+/// future.wait().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct InCurrentSpan<F> {
+    future: F,
+    span: Context,
+}
+
+impl<F> InCurrentSpan<F> {
+    /// Wraps `future`, capturing the span that is currently executing.
+    pub fn new(future: F) -> InCurrentSpan<F> {
+        InCurrentSpan {
+            future,
+            span: Context::current(),
+        }
+    }
+}
+
+impl<F: Future> Future for InCurrentSpan<F> {
+    type Item = F::Item;
+    type Error = F::Error;
+
+    fn poll(&mut self) -> Poll<F::Item, F::Error> {
+        let future = &mut self.future;
+        self.span.enter(|| future.poll())
+    }
+}
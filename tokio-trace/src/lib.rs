@@ -293,6 +293,8 @@
 //! [`enabled`]: subscriber/trait.Subscriber.html#tymethod.enabled
 //! [metadata]: struct.Metadata.html
 //! [`tokio-trace-nursery`]: https://github.com/tokio-rs/tokio-trace-nursery
+#[cfg(feature = "futures")]
+extern crate futures;
 extern crate tokio_trace_core;
 
 // Somehow this `use` statement is necessary for us to re-export the `core`
@@ -975,6 +977,8 @@ macro_rules! valueset {
     (@val $k:ident) => { None };
 }
 pub mod field;
+#[cfg(feature = "futures")]
+pub mod future;
 pub mod span;
 pub mod subscriber;
 
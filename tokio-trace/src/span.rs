@@ -140,6 +140,7 @@ pub use tokio_trace_core::span::Span as Id;
 
 use std::{
     borrow::Borrow,
+    cell::RefCell,
     cmp, fmt,
     hash::{Hash, Hasher},
 };
@@ -192,6 +193,34 @@ pub(crate) struct Inner<'a> {
     meta: &'a Metadata<'a>,
 }
 
+/// An owned reference to the span that was executing when it was captured,
+/// which can be entered again later.
+///
+/// Spans are entered around code that runs to completion on a single thread.
+/// Work that is deferred, such as a future spawned onto an executor, runs
+/// outside of that code, and so outside of the span. Capturing a `Context`
+/// with [`Context::current`] and entering it around the deferred work
+/// attributes that work to the span again, possibly on another thread.
+///
+/// A `Context` keeps the span open until it is dropped.
+///
+/// [`Context::current`]: ::span::Context::current
+pub struct Context {
+    /// `None` if no span was executing when the context was captured.
+    inner: Option<ContextInner>,
+}
+
+struct ContextInner {
+    id: Id,
+    subscriber: Dispatch,
+}
+
+thread_local! {
+    /// The IDs of the spans executing on this thread, innermost last, along
+    /// with the subscribers that tagged them.
+    static CURRENT: RefCell<Vec<(Id, Dispatch)>> = RefCell::new(Vec::new());
+}
+
 /// A guard representing a span which has been entered and is currently
 /// executing.
 ///
@@ -399,6 +428,7 @@ impl<'a> Inner<'a> {
     /// when entering a span.
     fn enter(self) -> Entered<'a> {
         self.subscriber.enter(&self.id);
+        push_current(&self.id, &self.subscriber);
         Entered { inner: self }
     }
 
@@ -483,6 +513,7 @@ impl<'a> Entered<'a> {
     /// to re-enter the span, or `None` if the span closed while performing the
     /// exit.
     fn exit(self) -> Option<Inner<'a>> {
+        pop_current();
         self.inner.subscriber.exit(&self.inner.id);
         if self.inner.closed {
             // Dropping `inner` will allow it to perform the closure if
@@ -493,3 +524,89 @@ impl<'a> Entered<'a> {
         }
     }
 }
+
+// ===== impl Context =====
+
+impl Context {
+    /// Captures the span that is currently executing on this thread, along
+    /// with the subscriber that tagged it.
+    ///
+    /// If no span is executing, entering the returned context does nothing.
+    pub fn current() -> Context {
+        let current = CURRENT
+            .try_with(|current| current.borrow().last().cloned())
+            .ok()
+            .and_then(|current| current);
+
+        let inner = current.map(|(id, subscriber)| ContextInner {
+            id: subscriber.clone_span(&id),
+            subscriber,
+        });
+
+        Context { inner }
+    }
+
+    /// Executes the given function in the context of the captured span.
+    ///
+    /// The span's subscriber is set as the default for the duration of `f`.
+    /// If no span was captured, `f` is invoked in the context of the
+    /// currently-executing span (if there is one).
+    pub fn enter<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        struct Exit<'a>(&'a ContextInner);
+
+        impl<'a> Drop for Exit<'a> {
+            fn drop(&mut self) {
+                pop_current();
+                self.0.subscriber.exit(&self.0.id);
+            }
+        }
+
+        match self.inner {
+            Some(ref inner) => dispatcher::with_default(inner.subscriber.clone(), || {
+                inner.subscriber.enter(&inner.id);
+                push_current(&inner.id, &inner.subscriber);
+                let _exit = Exit(inner);
+                f()
+            }),
+            None => f(),
+        }
+    }
+
+    /// Returns the captured span's `Id`, if a span was executing.
+    pub fn id(&self) -> Option<Id> {
+        self.inner.as_ref().map(|inner| inner.id.clone())
+    }
+}
+
+impl Clone for Context {
+    fn clone(&self) -> Self {
+        let inner = self.inner.as_ref().map(|inner| ContextInner {
+            id: inner.subscriber.clone_span(&inner.id),
+            subscriber: inner.subscriber.clone(),
+        });
+
+        Context { inner }
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            inner.subscriber.drop_span(inner.id);
+        }
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Context").field("id", &self.id()).finish()
+    }
+}
+
+fn push_current(id: &Id, subscriber: &Dispatch) {
+    let _ = CURRENT.try_with(|current| current.borrow_mut().push((id.clone(), subscriber.clone())));
+}
+
+fn pop_current() {
+    let _ = CURRENT.try_with(|current| current.borrow_mut().pop());
+}
@@ -0,0 +1,132 @@
+extern crate futures;
+extern crate tokio;
+#[macro_use]
+extern crate tokio_trace;
+
+use tokio::prelude::*;
+use tokio::runtime::Runtime;
+use tokio_trace::future::InCurrentSpan;
+use tokio_trace::span::Context;
+use tokio_trace::{dispatcher, field, Dispatch, Event, Id, Level, Metadata, Subscriber};
+
+use futures::sync::oneshot;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::Arc;
+
+/// Accepts all spans, only counting the events.
+struct Spans {
+    next_id: AtomicUsize,
+    events: Arc<AtomicUsize>,
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Metadata, _: &field::ValueSet) -> Id {
+        Id::from_u64(self.next_id.fetch_add(1, SeqCst) as u64 + 1)
+    }
+
+    fn record(&self, _: &Id, _: &field::ValueSet) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event) {
+        self.events.fetch_add(1, SeqCst);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Returns a new `Spans` subscriber, and its count of events.
+fn spans() -> (Dispatch, Arc<AtomicUsize>) {
+    let events = Arc::new(AtomicUsize::new(0));
+    let dispatch = Dispatch::new(Spans {
+        next_id: AtomicUsize::new(0),
+        events: events.clone(),
+    });
+
+    (dispatch, events)
+}
+
+#[test]
+fn spawned_task_runs_in_span() {
+    let mut rt = Runtime::new().unwrap();
+
+    let (dispatch, events) = spans();
+
+    let (spawned, span) = dispatcher::with_default(dispatch, || {
+        let mut span = span!("parent");
+        let id = span.id();
+
+        let spawned = rt.block_on(future::lazy(move || {
+            let (tx, rx) = oneshot::channel();
+
+            span.enter(|| {
+                tokio::spawn(InCurrentSpan::new(future::lazy(move || {
+                    // Reported to the subscriber of the span, which is not
+                    // the default one of the worker thread.
+                    event!(Level::INFO, spawned = true);
+                    tx.send(Context::current().id()).unwrap();
+                    Ok(())
+                })));
+            });
+
+            rx
+        }));
+
+        (spawned.unwrap(), id)
+    });
+
+    assert!(span.is_some());
+    assert_eq!(spawned, span);
+    assert_eq!(events.load(SeqCst), 1);
+}
+
+#[test]
+fn spawned_task_outside_of_span() {
+    let mut rt = Runtime::new().unwrap();
+
+    let spawned = rt
+        .block_on(future::lazy(|| {
+            let (tx, rx) = oneshot::channel();
+
+            tokio::spawn(InCurrentSpan::new(future::lazy(move || {
+                tx.send(Context::current().id()).unwrap();
+                Ok(())
+            })));
+
+            rx
+        }))
+        .unwrap();
+
+    assert!(spawned.is_none());
+}
+
+#[test]
+fn context_keeps_subscriber_of_span() {
+    use std::thread;
+
+    let (dispatch, events) = spans();
+    let (other, other_events) = spans();
+
+    // The context is captured while another subscriber is the default.
+    let context = dispatcher::with_default(dispatch, || {
+        let mut span = span!("parent");
+        span.enter(|| dispatcher::with_default(other, Context::current))
+    });
+
+    thread::spawn(move || {
+        context.enter(|| event!(Level::INFO, entered = true));
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(events.load(SeqCst), 1);
+    assert_eq!(other_events.load(SeqCst), 0);
+}
@@ -476,3 +476,35 @@ fn new_span_with_target_and_log_level() {
 
     handle.assert_finished();
 }
+
+#[test]
+fn context_reenters_span_on_other_thread() {
+    use tokio_trace::span::Context;
+
+    let (subscriber, handle) = subscriber::mock()
+        .enter(span::mock().named("foo"))
+        .clone_span(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .drop_span(span::mock().named("foo"))
+        .enter(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .drop_span(span::mock().named("foo"))
+        .done()
+        .run_with_handle();
+
+    let context = dispatcher::with_default(Dispatch::new(subscriber), || {
+        span!("foo").enter(|| Context::current())
+    });
+
+    assert!(context.id().is_some());
+
+    thread::spawn(move || {
+        context.enter(|| {
+            assert!(Context::current().id().is_some());
+        });
+    })
+    .join()
+    .unwrap();
+
+    handle.assert_finished();
+}
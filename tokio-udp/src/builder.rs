@@ -0,0 +1,210 @@
+use super::UdpSocket;
+
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, RawSocket};
+
+use socket2::{Domain, Protocol, Socket, Type};
+#[cfg(target_os = "linux")]
+use tokio_reactor::device;
+use tokio_reactor::Handle;
+
+/// A UDP socket that has not yet been bound and converted to a `UdpSocket`.
+///
+/// `UdpBuilder` allows configuring the socket options which must be set
+/// before the socket is bound, such as `SO_REUSEADDR`, `SO_BINDTODEVICE` or
+/// the size of the socket buffers. The socket is then turned into a
+/// [`UdpSocket`] with [`bind`], registered with the default reactor.
+///
+/// [`UdpSocket`]: struct.UdpSocket.html
+/// [`bind`]: #method.bind
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_udp;
+/// use tokio_udp::UdpBuilder;
+///
+/// # fn main() -> Result<(), Box<std::error::Error>> {
+/// let addr = "127.0.0.1:0".parse()?;
+///
+/// let builder = UdpBuilder::new_v4()?;
+/// builder.set_reuseaddr(true)?;
+/// builder.set_recv_buffer_size(64 * 1024)?;
+///
+/// let socket = builder.bind(&addr)?;
+/// # drop(socket);
+/// # Ok(())
+/// # }
+/// ```
+pub struct UdpBuilder {
+    inner: Socket,
+}
+
+impl UdpBuilder {
+    /// Creates a new IPv4 UDP socket.
+    pub fn new_v4() -> io::Result<UdpBuilder> {
+        UdpBuilder::new(Domain::ipv4())
+    }
+
+    /// Creates a new IPv6 UDP socket.
+    pub fn new_v6() -> io::Result<UdpBuilder> {
+        UdpBuilder::new(Domain::ipv6())
+    }
+
+    /// Creates a new UDP socket of the same address family as `addr`.
+    ///
+    /// The socket is not bound to `addr`, see [`bind`] for this.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn for_addr(addr: &SocketAddr) -> io::Result<UdpBuilder> {
+        match *addr {
+            SocketAddr::V4(..) => UdpBuilder::new_v4(),
+            SocketAddr::V6(..) => UdpBuilder::new_v6(),
+        }
+    }
+
+    fn new(domain: Domain) -> io::Result<UdpBuilder> {
+        let inner = Socket::new(domain, Type::dgram(), Some(Protocol::udp()))?;
+        Ok(UdpBuilder { inner })
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same multicast address.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.inner.set_reuse_address(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuseaddr`].
+    ///
+    /// [`set_reuseaddr`]: #method.set_reuseaddr
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.inner.reuse_address()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address, and on Linux
+    /// the incoming datagrams are balanced between the sockets.
+    ///
+    /// This function is only available on Unix platforms.
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_reuseport`].
+    ///
+    /// [`set_reuseport`]: #method.set_reuseport
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+    ///
+    /// If this is set to `false`, an IPv6 socket can also send and receive
+    /// datagrams to and from IPv4-mapped IPv6 addresses.
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.inner.set_only_v6(only_v6)
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's send buffer associated
+    /// with the socket. The operating system may round or limit the size.
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// Changes the size of the operating system's receive buffer associated
+    /// with the socket. The operating system may round or limit the size.
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_BINDTODEVICE` option on this socket.
+    ///
+    /// Once bound to a network interface, such as `"eth0"`, the socket only
+    /// sends and receives datagrams through this interface. `None` removes
+    /// the binding. This usually requires the `CAP_NET_RAW` capability.
+    ///
+    /// This function is only available on Linux.
+    #[cfg(target_os = "linux")]
+    pub fn bind_device(&self, interface: Option<&str>) -> io::Result<()> {
+        device::bind_device(self.inner.as_raw_fd(), interface)
+    }
+
+    /// Gets the value of the `SO_BINDTODEVICE` option on this socket.
+    ///
+    /// For more information about this option, see [`bind_device`].
+    ///
+    /// This function is only available on Linux.
+    ///
+    /// [`bind_device`]: #method.bind_device
+    #[cfg(target_os = "linux")]
+    pub fn device(&self) -> io::Result<Option<String>> {
+        device::device(self.inner.as_raw_fd())
+    }
+
+    /// Binds the socket to the given address, returning a `UdpSocket`.
+    ///
+    /// The address is the source address of the datagrams sent by the
+    /// socket, which selects the interface they go through on multi-homed
+    /// hosts. Port 0 lets the operating system pick the port. The socket is
+    /// registered with the default reactor.
+    pub fn bind(self, addr: &SocketAddr) -> io::Result<UdpSocket> {
+        self.inner.bind(&(*addr).into())?;
+        UdpSocket::from_std(self.inner.into_udp_socket(), &Handle::default())
+    }
+}
+
+impl fmt::Debug for UdpBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for UdpBuilder {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawSocket for UdpBuilder {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.inner.as_raw_socket()
+    }
+}
@@ -22,19 +22,26 @@
 extern crate bytes;
 #[macro_use]
 extern crate futures;
+#[cfg(target_os = "linux")]
+extern crate libc;
 extern crate mio;
+extern crate socket2;
 #[macro_use]
 extern crate log;
 extern crate tokio_codec;
 extern crate tokio_io;
 extern crate tokio_reactor;
 
+mod builder;
 mod frame;
+mod mmsg;
 mod recv_dgram;
 mod send_dgram;
 mod socket;
 
+pub use self::builder::UdpBuilder;
 pub use self::frame::UdpFramed;
+pub use self::mmsg::RecvMeta;
 pub use self::recv_dgram::RecvDgram;
 pub use self::send_dgram::SendDgram;
 pub use self::socket::UdpSocket;
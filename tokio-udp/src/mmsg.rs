@@ -0,0 +1,613 @@
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+
+use mio;
+
+/// The length and source address of a datagram received by
+/// [`UdpSocket::poll_recv_mmsg`] or [`UdpSocket::poll_recv_segments`].
+///
+/// [`UdpSocket::poll_recv_mmsg`]: struct.UdpSocket.html#method.poll_recv_mmsg
+/// [`UdpSocket::poll_recv_segments`]: struct.UdpSocket.html#method.poll_recv_segments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvMeta {
+    len: usize,
+    addr: SocketAddr,
+    segment_size: usize,
+}
+
+impl RecvMeta {
+    /// Returns the number of bytes of the datagram written to the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the address the datagram was received from.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the size of the datagrams which were coalesced into the
+    /// buffer by generic receive offload.
+    ///
+    /// The buffer holds consecutive datagrams of this size sent by the same
+    /// peer, the last one of which may be shorter. This is equal to `len`
+    /// when the datagram was not coalesced.
+    pub fn segment_size(&self) -> usize {
+        self.segment_size
+    }
+}
+
+impl Default for RecvMeta {
+    fn default() -> RecvMeta {
+        RecvMeta {
+            len: 0,
+            addr: SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), 0)),
+            segment_size: 0,
+        }
+    }
+}
+
+/// Sends the datagrams in `msgs` until the socket would block, returning the
+/// number of datagrams sent.
+pub(crate) fn send_mmsg(
+    socket: &mio::net::UdpSocket,
+    msgs: &[(&[u8], SocketAddr)],
+) -> io::Result<usize> {
+    sys::send_mmsg(socket, msgs)
+}
+
+/// Receives datagrams into `bufs` until the socket would block, returning
+/// the number of datagrams received.
+pub(crate) fn recv_mmsg(
+    socket: &mio::net::UdpSocket,
+    bufs: &mut [&mut [u8]],
+    meta: &mut [RecvMeta],
+) -> io::Result<usize> {
+    sys::recv_mmsg(socket, bufs, meta)
+}
+
+/// Tracks whether a socket supports generic segmentation offload, which is
+/// detected the first time it is needed.
+#[derive(Debug)]
+pub(crate) struct Gso {
+    state: AtomicUsize,
+}
+
+const GSO_UNKNOWN: usize = 0;
+const GSO_SUPPORTED: usize = 1;
+const GSO_UNSUPPORTED: usize = 2;
+
+/// Maximum number of segments the kernel accepts in one send, on older
+/// kernels.
+const UDP_MAX_SEGMENTS: usize = 64;
+
+impl Gso {
+    pub fn new() -> Gso {
+        Gso {
+            state: AtomicUsize::new(GSO_UNKNOWN),
+        }
+    }
+
+    pub fn supported(&self, socket: &mio::net::UdpSocket) -> bool {
+        match self.state.load(Relaxed) {
+            GSO_SUPPORTED => true,
+            GSO_UNSUPPORTED => false,
+            _ => {
+                let supported = sys::gso_supported(socket);
+                let state = if supported {
+                    GSO_SUPPORTED
+                } else {
+                    GSO_UNSUPPORTED
+                };
+                self.state.store(state, Relaxed);
+                supported
+            }
+        }
+    }
+
+    /// Sends `buf` to `target` as datagrams of `segment_size` bytes,
+    /// returning the number of bytes sent.
+    ///
+    /// The datagrams are split by the kernel or the network card when
+    /// possible, and are otherwise sent as a batch of datagrams.
+    pub fn send(
+        &self,
+        socket: &mio::net::UdpSocket,
+        buf: &[u8],
+        segment_size: usize,
+        target: &SocketAddr,
+    ) -> io::Result<usize> {
+        if buf.len() > segment_size && self.supported(socket) {
+            match send_offloaded(socket, buf, segment_size, target) {
+                Err(ref e) if sys::is_offload_error(e) => {
+                    // The device cannot offload the segmentation, so stop
+                    // using it for this socket.
+                    self.state.store(GSO_UNSUPPORTED, Relaxed);
+                }
+                res => return res,
+            }
+        }
+
+        let msgs: Vec<_> = buf
+            .chunks(segment_size)
+            .map(|segment| (segment, *target))
+            .collect();
+
+        let sent = send_mmsg(socket, &msgs)?;
+        Ok(::std::cmp::min(sent * segment_size, buf.len()))
+    }
+}
+
+/// Sends `buf` with segmentation offload, at most `UDP_MAX_SEGMENTS` segments
+/// at a time.
+///
+/// An error is only returned if nothing was sent.
+fn send_offloaded(
+    socket: &mio::net::UdpSocket,
+    buf: &[u8],
+    segment_size: usize,
+    target: &SocketAddr,
+) -> io::Result<usize> {
+    let mut sent = 0;
+
+    for chunk in buf.chunks(segment_size * UDP_MAX_SEGMENTS) {
+        match sys::send_segments(socket, chunk, segment_size as u16, target) {
+            Ok(n) => {
+                sent += n;
+
+                if n < chunk.len() {
+                    break;
+                }
+            }
+            Err(e) => {
+                if sent == 0 {
+                    return Err(e);
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Receives a datagram, or several datagrams coalesced by generic receive
+/// offload.
+pub(crate) fn recv_segments(socket: &mio::net::UdpSocket, buf: &mut [u8]) -> io::Result<RecvMeta> {
+    sys::recv_segments(socket, buf)
+}
+
+pub(crate) fn set_segment_size(socket: &mio::net::UdpSocket, size: u16) -> io::Result<()> {
+    sys::set_segment_size(socket, size)
+}
+
+pub(crate) fn segment_size(socket: &mio::net::UdpSocket) -> io::Result<u16> {
+    sys::segment_size(socket)
+}
+
+pub(crate) fn set_gro(socket: &mio::net::UdpSocket, gro: bool) -> io::Result<()> {
+    sys::set_gro(socket, gro)
+}
+
+pub(crate) fn gro(socket: &mio::net::UdpSocket) -> io::Result<bool> {
+    sys::gro(socket)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use super::RecvMeta;
+
+    use libc::{self, c_int};
+
+    use std::io;
+    use std::mem;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use std::os::unix::io::AsRawFd;
+    use std::ptr;
+
+    use mio;
+
+    pub fn send_mmsg(
+        socket: &mio::net::UdpSocket,
+        msgs: &[(&[u8], SocketAddr)],
+    ) -> io::Result<usize> {
+        let mut addrs = Vec::with_capacity(msgs.len());
+        let mut iovecs = Vec::with_capacity(msgs.len());
+
+        for &(buf, ref addr) in msgs {
+            let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+            let len = to_sockaddr(addr, &mut storage);
+            addrs.push((storage, len));
+            iovecs.push(libc::iovec {
+                iov_base: buf.as_ptr() as *mut _,
+                iov_len: buf.len(),
+            });
+        }
+
+        // The headers point into `addrs` and `iovecs`, which are not
+        // modified anymore.
+        let mut hdrs = Vec::with_capacity(msgs.len());
+        for (&mut (ref mut storage, len), iovec) in addrs.iter_mut().zip(iovecs.iter_mut()) {
+            let mut hdr: libc::mmsghdr = unsafe { mem::zeroed() };
+            hdr.msg_hdr.msg_name = storage as *mut _ as *mut _;
+            hdr.msg_hdr.msg_namelen = len;
+            hdr.msg_hdr.msg_iov = iovec;
+            hdr.msg_hdr.msg_iovlen = 1;
+            hdrs.push(hdr);
+        }
+
+        let ret =
+            unsafe { libc::sendmmsg(socket.as_raw_fd(), hdrs.as_mut_ptr(), hdrs.len() as _, 0) };
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    pub fn recv_mmsg(
+        socket: &mio::net::UdpSocket,
+        bufs: &mut [&mut [u8]],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        let n = ::std::cmp::min(bufs.len(), meta.len());
+
+        let mut addrs: Vec<libc::sockaddr_storage> = Vec::with_capacity(n);
+        let mut iovecs = Vec::with_capacity(n);
+
+        for buf in bufs[..n].iter_mut() {
+            addrs.push(unsafe { mem::zeroed() });
+            iovecs.push(libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut _,
+                iov_len: buf.len(),
+            });
+        }
+
+        let mut hdrs = Vec::with_capacity(n);
+        for (storage, iovec) in addrs.iter_mut().zip(iovecs.iter_mut()) {
+            let mut hdr: libc::mmsghdr = unsafe { mem::zeroed() };
+            hdr.msg_hdr.msg_name = storage as *mut _ as *mut _;
+            hdr.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as _;
+            hdr.msg_hdr.msg_iov = iovec;
+            hdr.msg_hdr.msg_iovlen = 1;
+            hdrs.push(hdr);
+        }
+
+        let ret = unsafe {
+            libc::recvmmsg(
+                socket.as_raw_fd(),
+                hdrs.as_mut_ptr(),
+                n as _,
+                0,
+                ptr::null_mut(),
+            )
+        };
+
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let received = ret as usize;
+        for i in 0..received {
+            let len = hdrs[i].msg_len as usize;
+            meta[i] = RecvMeta {
+                len,
+                addr: from_sockaddr(&addrs[i])?,
+                segment_size: len,
+            };
+        }
+
+        Ok(received)
+    }
+
+    // Not yet defined by the libc crate, see `linux/udp.h`.
+    const UDP_SEGMENT: c_int = 103;
+    const UDP_GRO: c_int = 104;
+
+    pub fn gso_supported(socket: &mio::net::UdpSocket) -> bool {
+        // Kernels older than 4.18 do not know about the option, and would
+        // ignore the control message, sending a single oversized datagram.
+        udp_option(socket, UDP_SEGMENT).is_ok()
+    }
+
+    pub fn is_offload_error(e: &io::Error) -> bool {
+        match e.raw_os_error() {
+            // Returned when the device cannot compute the checksums of the
+            // segments.
+            Some(libc::EIO) => true,
+            // Returned when the socket or the kernel does not support
+            // segmentation for this send, such as with some socket options.
+            Some(libc::EINVAL) | Some(libc::EOPNOTSUPP) | Some(libc::ENOPROTOOPT) => true,
+            _ => false,
+        }
+    }
+
+    pub fn send_segments(
+        socket: &mio::net::UdpSocket,
+        buf: &[u8],
+        segment_size: u16,
+        target: &SocketAddr,
+    ) -> io::Result<usize> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let len = to_sockaddr(target, &mut storage);
+        let mut iovec = libc::iovec {
+            iov_base: buf.as_ptr() as *mut _,
+            iov_len: buf.len(),
+        };
+
+        // Aligned space for the control message holding the segment size.
+        let mut control = [0u64; 4];
+
+        let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+        hdr.msg_name = &mut storage as *mut _ as *mut _;
+        hdr.msg_namelen = len;
+        hdr.msg_iov = &mut iovec;
+        hdr.msg_iovlen = 1;
+        hdr.msg_control = control.as_mut_ptr() as *mut _;
+        hdr.msg_controllen = unsafe { libc::CMSG_SPACE(mem::size_of::<u16>() as _) } as _;
+
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&hdr);
+            (*cmsg).cmsg_level = libc::SOL_UDP;
+            (*cmsg).cmsg_type = UDP_SEGMENT;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<u16>() as _) as _;
+            ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut u16, segment_size);
+        }
+
+        let ret = unsafe { libc::sendmsg(socket.as_raw_fd(), &hdr, 0) };
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    pub fn recv_segments(socket: &mio::net::UdpSocket, buf: &mut [u8]) -> io::Result<RecvMeta> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut iovec = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut _,
+            iov_len: buf.len(),
+        };
+        let mut control = [0u64; 8];
+
+        let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+        hdr.msg_name = &mut storage as *mut _ as *mut _;
+        hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as _;
+        hdr.msg_iov = &mut iovec;
+        hdr.msg_iovlen = 1;
+        hdr.msg_control = control.as_mut_ptr() as *mut _;
+        hdr.msg_controllen = mem::size_of_val(&control) as _;
+
+        let ret = unsafe { libc::recvmsg(socket.as_raw_fd(), &mut hdr, 0) };
+
+        if ret == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let len = ret as usize;
+        let mut segment_size = len;
+
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&hdr);
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == libc::SOL_UDP && (*cmsg).cmsg_type == UDP_GRO {
+                    segment_size =
+                        ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const c_int) as usize;
+                }
+                cmsg = libc::CMSG_NXTHDR(&hdr, cmsg);
+            }
+        }
+
+        Ok(RecvMeta {
+            len,
+            addr: from_sockaddr(&storage)?,
+            segment_size,
+        })
+    }
+
+    pub fn set_segment_size(socket: &mio::net::UdpSocket, size: u16) -> io::Result<()> {
+        set_udp_option(socket, UDP_SEGMENT, size as c_int)
+    }
+
+    pub fn segment_size(socket: &mio::net::UdpSocket) -> io::Result<u16> {
+        udp_option(socket, UDP_SEGMENT).map(|size| size as u16)
+    }
+
+    pub fn set_gro(socket: &mio::net::UdpSocket, gro: bool) -> io::Result<()> {
+        set_udp_option(socket, UDP_GRO, gro as c_int)
+    }
+
+    pub fn gro(socket: &mio::net::UdpSocket) -> io::Result<bool> {
+        udp_option(socket, UDP_GRO).map(|gro| gro != 0)
+    }
+
+    fn set_udp_option(socket: &mio::net::UdpSocket, name: c_int, value: c_int) -> io::Result<()> {
+        let ret = unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_UDP,
+                name,
+                &value as *const _ as *const _,
+                mem::size_of_val(&value) as libc::socklen_t,
+            )
+        };
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn udp_option(socket: &mio::net::UdpSocket, name: c_int) -> io::Result<c_int> {
+        let mut value: c_int = 0;
+        let mut len = mem::size_of_val(&value) as libc::socklen_t;
+
+        let ret = unsafe {
+            libc::getsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_UDP,
+                name,
+                &mut value as *mut _ as *mut _,
+                &mut len,
+            )
+        };
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn to_sockaddr(addr: &SocketAddr, storage: &mut libc::sockaddr_storage) -> libc::socklen_t {
+        match *addr {
+            SocketAddr::V4(ref addr) => {
+                let sin = unsafe { &mut *(storage as *mut _ as *mut libc::sockaddr_in) };
+                sin.sin_family = libc::AF_INET as libc::sa_family_t;
+                sin.sin_port = addr.port().to_be();
+                sin.sin_addr = libc::in_addr {
+                    s_addr: u32::from(*addr.ip()).to_be(),
+                };
+                mem::size_of::<libc::sockaddr_in>() as libc::socklen_t
+            }
+            SocketAddr::V6(ref addr) => {
+                let sin6 = unsafe { &mut *(storage as *mut _ as *mut libc::sockaddr_in6) };
+                sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                sin6.sin6_port = addr.port().to_be();
+                sin6.sin6_flowinfo = addr.flowinfo();
+                sin6.sin6_addr = libc::in6_addr {
+                    s6_addr: addr.ip().octets(),
+                };
+                sin6.sin6_scope_id = addr.scope_id();
+                mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t
+            }
+        }
+    }
+
+    fn from_sockaddr(storage: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+        match storage.ss_family as libc::c_int {
+            libc::AF_INET => {
+                let sin = unsafe { &*(storage as *const _ as *const libc::sockaddr_in) };
+                let ip = Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr));
+                Ok(SocketAddr::V4(SocketAddrV4::new(
+                    ip,
+                    u16::from_be(sin.sin_port),
+                )))
+            }
+            libc::AF_INET6 => {
+                let sin6 = unsafe { &*(storage as *const _ as *const libc::sockaddr_in6) };
+                Ok(SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from(sin6.sin6_addr.s6_addr),
+                    u16::from_be(sin6.sin6_port),
+                    sin6.sin6_flowinfo,
+                    sin6.sin6_scope_id,
+                )))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid address family",
+            )),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use super::RecvMeta;
+
+    use std::io;
+    use std::net::SocketAddr;
+
+    use mio;
+
+    // Without batch system calls, the datagrams are sent and received one
+    // at a time, until the socket would block or an error occurs.
+
+    pub fn send_mmsg(
+        socket: &mio::net::UdpSocket,
+        msgs: &[(&[u8], SocketAddr)],
+    ) -> io::Result<usize> {
+        for (i, &(buf, ref addr)) in msgs.iter().enumerate() {
+            if let Err(e) = socket.send_to(buf, addr) {
+                return if i == 0 { Err(e) } else { Ok(i) };
+            }
+        }
+
+        Ok(msgs.len())
+    }
+
+    pub fn recv_mmsg(
+        socket: &mio::net::UdpSocket,
+        bufs: &mut [&mut [u8]],
+        meta: &mut [RecvMeta],
+    ) -> io::Result<usize> {
+        for (i, (buf, meta)) in bufs.iter_mut().zip(meta.iter_mut()).enumerate() {
+            match socket.recv_from(buf) {
+                Ok((len, addr)) => {
+                    *meta = RecvMeta {
+                        len,
+                        addr,
+                        segment_size: len,
+                    }
+                }
+                Err(e) => return if i == 0 { Err(e) } else { Ok(i) },
+            }
+        }
+
+        Ok(::std::cmp::min(bufs.len(), meta.len()))
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Other,
+            "segmentation offload is not supported on this platform",
+        )
+    }
+
+    pub fn gso_supported(_: &mio::net::UdpSocket) -> bool {
+        false
+    }
+
+    pub fn is_offload_error(_: &io::Error) -> bool {
+        false
+    }
+
+    pub fn send_segments(
+        _: &mio::net::UdpSocket,
+        _: &[u8],
+        _: u16,
+        _: &SocketAddr,
+    ) -> io::Result<usize> {
+        Err(unsupported())
+    }
+
+    pub fn recv_segments(socket: &mio::net::UdpSocket, buf: &mut [u8]) -> io::Result<RecvMeta> {
+        let (len, addr) = socket.recv_from(buf)?;
+        Ok(RecvMeta {
+            len,
+            addr,
+            segment_size: len,
+        })
+    }
+
+    pub fn set_segment_size(_: &mio::net::UdpSocket, _: u16) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn segment_size(_: &mio::net::UdpSocket) -> io::Result<u16> {
+        Err(unsupported())
+    }
+
+    pub fn set_gro(_: &mio::net::UdpSocket, _: bool) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn gro(_: &mio::net::UdpSocket) -> io::Result<bool> {
+        Err(unsupported())
+    }
+}
@@ -1,3 +1,4 @@
+use super::mmsg::{self, Gso, RecvMeta};
 use super::{RecvDgram, SendDgram};
 
 use std::fmt;
@@ -12,6 +13,7 @@ use tokio_reactor::{Handle, PollEvented};
 /// An I/O object representing a UDP socket.
 pub struct UdpSocket {
     io: PollEvented<mio::net::UdpSocket>,
+    gso: Gso,
 }
 
 impl UdpSocket {
@@ -23,7 +25,10 @@ impl UdpSocket {
 
     fn new(socket: mio::net::UdpSocket) -> UdpSocket {
         let io = PollEvented::new(socket);
-        UdpSocket { io: io }
+        UdpSocket {
+            io: io,
+            gso: Gso::new(),
+        }
     }
 
     /// Creates a new `UdpSocket` from the previously bound socket provided.
@@ -40,7 +45,10 @@ impl UdpSocket {
     pub fn from_std(socket: net::UdpSocket, handle: &Handle) -> io::Result<UdpSocket> {
         let io = mio::net::UdpSocket::from_socket(socket)?;
         let io = PollEvented::new_with_handle(io, handle)?;
-        Ok(UdpSocket { io })
+        Ok(UdpSocket {
+            io,
+            gso: Gso::new(),
+        })
     }
 
     /// Returns the local address that this socket is bound to.
@@ -140,6 +148,144 @@ impl UdpSocket {
         }
     }
 
+    /// Check whether the socket is ready for sending.
+    ///
+    /// Once this returns `Async::Ready`, datagrams can be sent with
+    /// [`try_send`] until it fails with `WouldBlock`, which lets the caller
+    /// send several datagrams for a single readiness check.
+    ///
+    /// If the socket is not ready for sending then `Async::NotReady` is
+    /// returned and the current task is notified once the socket becomes
+    /// writable.
+    ///
+    /// [`try_send`]: #method.try_send
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
+    pub fn poll_send_ready(&self) -> Poll<(), io::Error> {
+        try_ready!(self.io.poll_write_ready());
+        Ok(Async::Ready(()))
+    }
+
+    /// Check whether the socket is ready for receiving.
+    ///
+    /// Once this returns `Async::Ready`, datagrams can be received with
+    /// [`try_recv`] until it fails with `WouldBlock`, which lets the caller
+    /// receive several datagrams for a single readiness check.
+    ///
+    /// If the socket is not ready for receiving then `Async::NotReady` is
+    /// returned and the current task is notified once the socket becomes
+    /// readable.
+    ///
+    /// [`try_recv`]: #method.try_recv
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
+    pub fn poll_recv_ready(&self) -> Poll<(), io::Error> {
+        try_ready!(self.io.poll_read_ready(mio::Ready::readable()));
+        Ok(Async::Ready(()))
+    }
+
+    /// Tries to send data on the socket to the remote address to which it is
+    /// connected, without waiting for the socket to be writable.
+    ///
+    /// On success, returns the number of bytes written. If the socket's send
+    /// buffer is full, an error of kind `WouldBlock` is returned and the
+    /// socket's write readiness is cleared, so that the next call to
+    /// [`poll_send_ready`] waits for the socket to become writable again.
+    ///
+    /// [`poll_send_ready`]: #method.poll_send_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_send(&self, buf: &[u8]) -> io::Result<usize> {
+        match self.io.get_ref().send(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
+    /// Tries to receive a single datagram on the socket from the remote
+    /// address to which it is connected, without waiting for the socket to
+    /// be readable.
+    ///
+    /// On success, returns the number of bytes read. If no datagram is
+    /// available, an error of kind `WouldBlock` is returned and the socket's
+    /// read readiness is cleared, so that the next call to
+    /// [`poll_recv_ready`] waits for a datagram to arrive.
+    ///
+    /// [`poll_recv_ready`]: #method.poll_recv_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.io.get_ref().recv(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
+    /// Tries to send data on the socket to the given address, without
+    /// waiting for the socket to be writable.
+    ///
+    /// On success, returns the number of bytes written. If the socket's send
+    /// buffer is full, an error of kind `WouldBlock` is returned and the
+    /// socket's write readiness is cleared, so that the next call to
+    /// [`poll_send_ready`] waits for the socket to become writable again.
+    ///
+    /// [`poll_send_ready`]: #method.poll_send_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_send_to(&self, buf: &[u8], target: &SocketAddr) -> io::Result<usize> {
+        match self.io.get_ref().send_to(buf, target) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
+    /// Tries to receive a single datagram on the socket, without waiting for
+    /// the socket to be readable.
+    ///
+    /// On success, returns the number of bytes read and the address the
+    /// datagram came from. If no datagram is available, an error of kind
+    /// `WouldBlock` is returned and the socket's read readiness is cleared,
+    /// so that the next call to [`poll_recv_ready`] waits for a datagram to
+    /// arrive.
+    ///
+    /// [`poll_recv_ready`]: #method.poll_recv_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        match self.io.get_ref().recv_from(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
     #[deprecated(since = "0.1.2", note = "use poll_send_to instead")]
     #[doc(hidden)]
     pub fn send_to(&mut self, buf: &[u8], target: &SocketAddr) -> io::Result<usize> {
@@ -249,17 +395,272 @@ impl UdpSocket {
         RecvDgram::new(self, buf)
     }
 
+    /// Sends a batch of datagrams, each of them to its own address. On
+    /// success, returns the number of datagrams sent.
+    ///
+    /// The datagrams are sent in order until the socket's send buffer is
+    /// full, so only the first datagrams of `msgs` may be sent. The others
+    /// can be sent by calling this method again with the rest of `msgs`.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, the datagrams are sent with a single `sendmmsg` system call.
+    /// On other platforms, they are sent one at a time. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+    ///
+    /// # Return
+    ///
+    /// On success, returns `Ok(Async::Ready(num_datagrams_sent))`.
+    ///
+    /// If the socket is not ready for writing, the method returns
+    /// `Ok(Async::NotReady)` and arranges for the current task to receive a
+    /// notification when the socket becomes writable.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from outside of a task context.
+    pub fn poll_send_mmsg(&mut self, msgs: &[(&[u8], SocketAddr)]) -> Poll<usize, io::Error> {
+        if msgs.is_empty() {
+            return Ok(Async::Ready(0));
+        }
+
+        try_ready!(self.io.poll_write_ready());
+
+        match mmsg::send_mmsg(self.io.get_ref(), msgs) {
+            Ok(n) => Ok(n.into()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Receives a batch of datagrams. On success, returns the number of
+    /// datagrams received.
+    ///
+    /// Each datagram is written to the buffer of `bufs` with the same index,
+    /// and its length and source address to the element of `meta` with the
+    /// same index. Up to as many datagrams as the shorter of `bufs` and
+    /// `meta` are received, as long as they are available without waiting.
+    /// A datagram longer than its buffer is truncated.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux, the datagrams are received with a single `recvmmsg` system
+    /// call. On other platforms, they are received one at a time. Note that,
+    /// this [may change in the future][changes].
+    ///
+    /// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside the context of a future's
+    /// task.
+    pub fn poll_recv_mmsg(
+        &mut self,
+        bufs: &mut [&mut [u8]],
+        meta: &mut [RecvMeta],
+    ) -> Poll<usize, io::Error> {
+        if bufs.is_empty() || meta.is_empty() {
+            return Ok(Async::Ready(0));
+        }
+
+        try_ready!(self.io.poll_read_ready(mio::Ready::readable()));
+
+        match mmsg::recv_mmsg(self.io.get_ref(), bufs, meta) {
+            Ok(n) => Ok(n.into()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Sends the data of `buf` to the given address as consecutive datagrams
+    /// of `segment_size` bytes, the last of which may be shorter. On success,
+    /// returns the number of bytes sent.
+    ///
+    /// When the socket supports generic segmentation offload, `buf` is
+    /// handed to the kernel at once and split into datagrams by the kernel
+    /// or the network card, which is much cheaper than sending each datagram
+    /// separately. Otherwise, the datagrams are sent as a batch, as with
+    /// [`poll_send_mmsg`]. Only the first datagrams may be sent if the
+    /// socket's send buffer is full, the rest of `buf` can then be sent by
+    /// calling this method again.
+    ///
+    /// The kernel limits the number of datagrams sent in one call, to 64 on
+    /// older kernels, and the size of `buf` to the maximum size of a
+    /// datagram.
+    ///
+    /// [`poll_send_mmsg`]: #method.poll_send_mmsg
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Segmentation offload is only supported on Linux 4.18 and later, see
+    /// [`gso_supported`]. Note that, this [may change in the
+    /// future][changes].
+    ///
+    /// [`gso_supported`]: #method.gso_supported
+    /// [changes]: https://doc.rust-lang.org/std/io/index.html#platform-specific-behavior
+    ///
+    /// # Return
+    ///
+    /// On success, returns `Ok(Async::Ready(num_bytes_written))`.
+    ///
+    /// If the socket is not ready for writing, the method returns
+    /// `Ok(Async::NotReady)` and arranges for the current task to receive a
+    /// notification when the socket becomes writable.
+    ///
+    /// An error of kind `InvalidInput` is returned if `segment_size` is zero
+    /// or larger than `u16::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from outside of a task context.
+    pub fn poll_send_segments_to(
+        &mut self,
+        buf: &[u8],
+        segment_size: usize,
+        target: &SocketAddr,
+    ) -> Poll<usize, io::Error> {
+        if segment_size == 0 || segment_size > u16::max_value() as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid segment size",
+            ));
+        }
+
+        if buf.is_empty() {
+            return self.poll_send_to(buf, target);
+        }
+
+        try_ready!(self.io.poll_write_ready());
+
+        match self.gso.send(self.io.get_ref(), buf, segment_size, target) {
+            Ok(n) => Ok(n.into()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Receives a single datagram, or several datagrams coalesced by generic
+    /// receive offload, into `buf`.
+    ///
+    /// Once generic receive offload is enabled with [`set_gro`], consecutive
+    /// datagrams of the same size sent by the same peer may be received at
+    /// once. The returned [`RecvMeta`] gives the total length of the data
+    /// written to `buf` and the [`segment_size`] of the datagrams it is made
+    /// of. `buf` should be large enough to hold the largest datagram,
+    /// 65535 bytes, otherwise the coalesced datagrams are truncated.
+    ///
+    /// [`set_gro`]: #method.set_gro
+    /// [`RecvMeta`]: struct.RecvMeta.html
+    /// [`segment_size`]: struct.RecvMeta.html#method.segment_size
+    ///
+    /// # Return
+    ///
+    /// On success, returns `Ok(Async::Ready(meta))`.
+    ///
+    /// If no data is available for reading, the method returns
+    /// `Ok(Async::NotReady)` and arranges for the current task to receive a
+    /// notification when the socket becomes receivable or is closed.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called outside the context of a future's
+    /// task.
+    pub fn poll_recv_segments(&mut self, buf: &mut [u8]) -> Poll<RecvMeta, io::Error> {
+        try_ready!(self.io.poll_read_ready(mio::Ready::readable()));
+
+        match mmsg::recv_segments(self.io.get_ref(), buf) {
+            Ok(meta) => Ok(meta.into()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(mio::Ready::readable())?;
+                Ok(Async::NotReady)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns whether the socket supports generic segmentation offload.
+    ///
+    /// When it does not, [`poll_send_segments_to`] falls back to sending the
+    /// datagrams as a batch. This is detected once for each socket, and
+    /// also becomes `false` if the network device turns out not to support
+    /// the offload when sending.
+    ///
+    /// [`poll_send_segments_to`]: #method.poll_send_segments_to
+    pub fn gso_supported(&self) -> bool {
+        self.gso.supported(self.io.get_ref())
+    }
+
+    /// Sets the value of the `UDP_SEGMENT` option on this socket.
+    ///
+    /// When set to a non-zero size, the data of every send larger than `size`
+    /// is split into datagrams of `size` bytes by the kernel, as with
+    /// [`poll_send_segments_to`]. Unlike [`poll_send_segments_to`], there is
+    /// no fallback when the socket does not support the offload.
+    ///
+    /// This function is only supported on Linux, an error is returned on
+    /// other platforms.
+    ///
+    /// [`poll_send_segments_to`]: #method.poll_send_segments_to
+    pub fn set_segment_size(&self, size: u16) -> io::Result<()> {
+        mmsg::set_segment_size(self.io.get_ref(), size)
+    }
+
+    /// Gets the value of the `UDP_SEGMENT` option on this socket.
+    ///
+    /// For more information about this option, see [`set_segment_size`].
+    ///
+    /// [`set_segment_size`]: #method.set_segment_size
+    pub fn segment_size(&self) -> io::Result<u16> {
+        mmsg::segment_size(self.io.get_ref())
+    }
+
+    /// Sets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// When enabled, consecutive datagrams may be coalesced when received,
+    /// which requires receiving them with [`poll_recv_segments`] to know
+    /// where each datagram ends.
+    ///
+    /// This function is only supported on Linux 5.0 and later, an error is
+    /// returned on other platforms.
+    ///
+    /// [`poll_recv_segments`]: #method.poll_recv_segments
+    pub fn set_gro(&self, gro: bool) -> io::Result<()> {
+        mmsg::set_gro(self.io.get_ref(), gro)
+    }
+
+    /// Gets the value of the `UDP_GRO` option on this socket.
+    ///
+    /// For more information about this option, see [`set_gro`].
+    ///
+    /// [`set_gro`]: #method.set_gro
+    pub fn gro(&self) -> io::Result<bool> {
+        mmsg::gro(self.io.get_ref())
+    }
+
     /// Check the UDP socket's read readiness state.
     ///
     /// The mask argument allows specifying what readiness to notify on. This
     /// can be any value, including platform specific readiness, **except**
-    /// `writable`.
+    /// `writable`. HUP is always implicitly included on platforms that support
+    /// it.
     ///
     /// If the socket is not ready for receiving then `Async::NotReady` is
     /// returned and the current task is notified once a new event is received.
     ///
     /// The socket will remain in a read-ready state until calls to `poll_recv`
-    /// return `NotReady`.
+    /// return `NotReady`, or calls to the `try_recv` functions fail with
+    /// `WouldBlock`.
     ///
     /// # Panics
     ///
@@ -277,7 +678,8 @@ impl UdpSocket {
     /// returned and the current task is notified once a new event is received.
     ///
     /// The I/O resource will remain in a write-ready state until calls to
-    /// `poll_send` return `NotReady`.
+    /// `poll_send` return `NotReady`, or calls to the `try_send` functions
+    /// fail with `WouldBlock`.
     ///
     /// # Panics
     ///
@@ -430,13 +832,52 @@ impl fmt::Debug for UdpSocket {
 #[cfg(all(unix))]
 mod sys {
     use super::UdpSocket;
+    use mio;
+    use std::io;
+    use std::net;
     use std::os::unix::prelude::*;
 
+    impl UdpSocket {
+        /// Consumes this socket, returning the `UdpSocket` of the standard
+        /// library.
+        ///
+        /// The socket is deregistered from its event loop and is left in
+        /// nonblocking mode. Call `set_nonblocking(false)` on the returned
+        /// socket to use it for blocking I/O.
+        ///
+        /// This function is only available on Unix.
+        pub fn into_std(self) -> io::Result<net::UdpSocket> {
+            let socket = self.io.into_inner()?;
+            Ok(unsafe { net::UdpSocket::from_raw_fd(socket.into_raw_fd()) })
+        }
+    }
+
     impl AsRawFd for UdpSocket {
         fn as_raw_fd(&self) -> RawFd {
             self.io.get_ref().as_raw_fd()
         }
     }
+
+    impl FromRawFd for UdpSocket {
+        /// Creates a `UdpSocket` from a raw file descriptor, such as one
+        /// inherited from systemd or launchd socket activation.
+        ///
+        /// The socket is switched to nonblocking mode and is lazily associated
+        /// with the default event loop's handle. Use [`from_std`] to associate
+        /// it with another event loop and handle errors.
+        ///
+        /// [`from_std`]: #method.from_std
+        ///
+        /// # Panics
+        ///
+        /// This function panics if the socket cannot be switched to
+        /// nonblocking mode.
+        unsafe fn from_raw_fd(fd: RawFd) -> UdpSocket {
+            let socket = mio::net::UdpSocket::from_socket(net::UdpSocket::from_raw_fd(fd))
+                .expect("failed to set the socket to nonblocking mode");
+            UdpSocket::new(socket)
+        }
+    }
 }
 
 #[cfg(windows)]
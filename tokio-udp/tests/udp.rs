@@ -1,3 +1,4 @@
+#[macro_use]
 extern crate futures;
 extern crate tokio_codec;
 extern crate tokio_udp;
@@ -9,10 +10,10 @@ extern crate env_logger;
 use std::io;
 use std::net::SocketAddr;
 
-use futures::{Future, Poll, Sink, Stream};
+use futures::{future, Future, Poll, Sink, Stream};
 
 use bytes::{BufMut, BytesMut};
-use tokio_codec::{Decoder, Encoder};
+use tokio_codec::{Decoder, EncodeRef, Encoder};
 use tokio_udp::{UdpFramed, UdpSocket};
 
 macro_rules! t {
@@ -240,8 +241,14 @@ impl Encoder for ByteCodec {
     type Error = io::Error;
 
     fn encode(&mut self, data: Vec<u8>, buf: &mut BytesMut) -> Result<(), io::Error> {
+        self.encode_ref(&data, buf)
+    }
+}
+
+impl EncodeRef for ByteCodec {
+    fn encode_ref(&mut self, data: &Vec<u8>, buf: &mut BytesMut) -> Result<(), io::Error> {
         buf.reserve(data.len());
-        buf.put(data);
+        buf.put_slice(data);
         Ok(())
     }
 }
@@ -288,3 +295,418 @@ fn send_framed() {
         assert_eq!(a_addr, addr);
     }
 }
+
+#[test]
+fn send_framed_ref() {
+    drop(env_logger::try_init());
+
+    let a_soc = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let b_soc = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let a_addr = t!(a_soc.local_addr());
+    let b_addr = t!(b_soc.local_addr());
+
+    let mut a = UdpFramed::new(a_soc, ByteCodec);
+    let b = UdpFramed::new(b_soc, ByteCodec);
+
+    let msg = b"4567".to_vec();
+
+    let send = future::poll_fn(|| {
+        try_ready!(a.start_send_ref(&msg, b_addr));
+        a.poll_complete()
+    });
+    let recv = b.into_future().map_err(|e| e.0);
+    let received = t!(send.join(recv).wait()).1;
+
+    // The message is still owned by the sender
+    let (data, addr) = received.0.unwrap();
+    assert_eq!(msg, data);
+    assert_eq!(a_addr, addr);
+}
+
+#[test]
+fn multicast_membership() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    drop(env_logger::try_init());
+
+    let socket = t!(UdpSocket::bind(&t!("0.0.0.0:0".parse())));
+    let group = Ipv4Addr::new(239, 255, 42, 98);
+    let interface = Ipv4Addr::new(127, 0, 0, 1);
+
+    t!(socket.join_multicast_v4(&group, &interface));
+    // Joining twice on the same interface fails
+    assert!(socket.join_multicast_v4(&group, &interface).is_err());
+    t!(socket.leave_multicast_v4(&group, &interface));
+    assert!(socket.leave_multicast_v4(&group, &interface).is_err());
+
+    t!(socket.set_multicast_loop_v4(false));
+    assert!(!t!(socket.multicast_loop_v4()));
+    t!(socket.set_multicast_ttl_v4(4));
+    assert_eq!(t!(socket.multicast_ttl_v4()), 4);
+
+    // Not a multicast address
+    assert!(socket.join_multicast_v4(&interface, &interface).is_err());
+
+    if let Ok(socket) = UdpSocket::bind(&t!("[::]:0".parse())) {
+        t!(socket.set_multicast_loop_v6(false));
+        assert!(!t!(socket.multicast_loop_v6()));
+        assert!(socket.join_multicast_v6(&Ipv6Addr::LOCALHOST, 0).is_err());
+    }
+}
+
+struct Utf8Codec;
+
+impl Decoder for Utf8Codec {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<String>, io::Error> {
+        let s = String::from_utf8(buf.take().to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Some(s))
+    }
+}
+
+#[test]
+fn skip_decode_errors() {
+    drop(env_logger::try_init());
+
+    let sender = t!(std::net::UdpSocket::bind("127.0.0.1:0"));
+    let socket = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(socket.local_addr());
+
+    t!(sender.send_to(b"\xff", &addr));
+    t!(sender.send_to(b"hello", &addr));
+    t!(sender.send_to(b"\xfe", &addr));
+
+    // The stream returns the error by default
+    let framed = UdpFramed::new(socket, Utf8Codec);
+    let (err, mut framed) = match framed.into_future().wait() {
+        Err(e) => e,
+        Ok(_) => panic!("expected a decode error"),
+    };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    framed.set_skip_decode_errors(true);
+    let (frame, framed) = t!(framed.into_future().map_err(|e| e.0).wait());
+    let (frame, _) = frame.unwrap();
+    assert_eq!(frame, "hello");
+    assert_eq!(framed.skipped_datagrams(), 0);
+
+    t!(sender.send_to(b"world", &addr));
+    let (frame, framed) = t!(framed.into_future().map_err(|e| e.0).wait());
+    assert_eq!(frame.unwrap().0, "world");
+    assert_eq!(framed.skipped_datagrams(), 1);
+}
+
+#[test]
+fn skip_decode_errors_yields() {
+    drop(env_logger::try_init());
+
+    let sender = t!(std::net::UdpSocket::bind("127.0.0.1:0"));
+    let socket = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let addr = t!(socket.local_addr());
+
+    for _ in 0..100 {
+        t!(sender.send_to(b"\xff", &addr));
+    }
+    t!(sender.send_to(b"hello", &addr));
+
+    let mut framed = UdpFramed::new(socket, Utf8Codec);
+    framed.set_skip_decode_errors(true);
+
+    // The malformed datagrams are skipped over several polls
+    let mut polls = 0;
+    let frame = t!(future::poll_fn(|| {
+        polls += 1;
+        framed.poll()
+    })
+    .wait());
+    assert_eq!(frame.unwrap().0, "hello");
+    assert_eq!(framed.skipped_datagrams(), 100);
+    assert!(polls > 1);
+}
+
+#[test]
+fn send_recv_mmsg() {
+    use tokio_udp::RecvMeta;
+
+    drop(env_logger::try_init());
+
+    let mut a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let mut b = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let a_addr = t!(a.local_addr());
+    let b_addr = t!(b.local_addr());
+
+    let msgs: [(&[u8], SocketAddr); 3] = [(b"one", b_addr), (b"two", b_addr), (b"three", b_addr)];
+
+    let mut sent = 0;
+    t!(future::poll_fn(|| {
+        while sent < msgs.len() {
+            sent += try_ready!(a.poll_send_mmsg(&msgs[sent..]));
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    let mut bufs = vec![[0u8; 4]; 4];
+    let mut meta = vec![RecvMeta::default(); 4];
+    let mut received = 0;
+    t!(future::poll_fn(|| {
+        while received < msgs.len() {
+            let mut slices: Vec<&mut [u8]> =
+                bufs[received..].iter_mut().map(|b| &mut b[..]).collect();
+            received += try_ready!(b.poll_recv_mmsg(&mut slices, &mut meta[received..]));
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    assert_eq!(received, 3);
+    for (i, expected) in [&b"one"[..], b"two", b"thre"].iter().enumerate() {
+        assert_eq!(&bufs[i][..meta[i].len()], *expected);
+        assert_eq!(meta[i].addr(), a_addr);
+    }
+}
+
+#[test]
+fn try_send_recv() {
+    drop(env_logger::try_init());
+
+    let a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let b = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    t!(a.connect(&t!(b.local_addr())));
+    t!(b.connect(&t!(a.local_addr())));
+
+    t!(future::poll_fn(|| {
+        try_ready!(a.poll_send_ready());
+        for msg in &[&b"one"[..], b"two", b"three"] {
+            assert_eq!(t!(a.try_send(msg)), msg.len());
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    let mut received = Vec::new();
+    t!(future::poll_fn(|| {
+        while received.len() < 3 {
+            try_ready!(b.poll_recv_ready());
+            let mut buf = [0; 8];
+            match b.try_recv(&mut buf) {
+                Ok(n) => received.push(buf[..n].to_vec()),
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        // The queue is empty, so the read readiness has been cleared.
+        let mut buf = [0; 8];
+        let err = b.try_recv(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert!(b.poll_recv_ready()?.is_not_ready());
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    assert_eq!(
+        received,
+        vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]
+    );
+}
+
+#[test]
+fn send_segments() {
+    drop(env_logger::try_init());
+
+    let mut a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let mut b = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let a_addr = t!(a.local_addr());
+    let b_addr = t!(b.local_addr());
+
+    // The datagrams are either segmented by the kernel, or sent as a batch.
+    let data = b"aaaabbbbcc";
+    let mut sent = 0;
+    t!(future::poll_fn(|| {
+        while sent < data.len() {
+            sent += try_ready!(a.poll_send_segments_to(&data[sent..], 4, &b_addr));
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    let mut received = Vec::new();
+    t!(future::poll_fn(|| {
+        while received.len() < 3 {
+            let mut buf = [0; 8];
+            let meta = try_ready!(b.poll_recv_segments(&mut buf));
+            assert_eq!(meta.addr(), a_addr);
+            assert_eq!(meta.segment_size(), meta.len());
+            received.push(buf[..meta.len()].to_vec());
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    assert_eq!(
+        received,
+        vec![b"aaaa".to_vec(), b"bbbb".to_vec(), b"cc".to_vec()]
+    );
+
+    let err = future::poll_fn(|| a.poll_send_segments_to(data, 0, &b_addr))
+        .wait()
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn send_more_segments_than_the_kernel_limit() {
+    drop(env_logger::try_init());
+
+    let mut a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let mut b = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let b_addr = t!(b.local_addr());
+
+    // Older kernels accept at most 64 segments in one send.
+    let data: Vec<u8> = (0..100u8).flat_map(|i| vec![i; 4]).collect();
+    let mut sent = 0;
+    t!(future::poll_fn(|| {
+        while sent < data.len() {
+            sent += try_ready!(a.poll_send_segments_to(&data[sent..], 4, &b_addr));
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    let mut received = Vec::new();
+    t!(future::poll_fn(|| {
+        while received.len() < data.len() {
+            let mut buf = [0; 4];
+            let meta = try_ready!(b.poll_recv_segments(&mut buf));
+            received.extend_from_slice(&buf[..meta.len()]);
+        }
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    assert_eq!(received, data);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn segmentation_options() {
+    let a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+
+    // Segmentation offload requires a recent kernel.
+    if !a.gso_supported() {
+        return;
+    }
+
+    t!(a.set_segment_size(1200));
+    assert_eq!(t!(a.segment_size()), 1200);
+    t!(a.set_segment_size(0));
+    assert_eq!(t!(a.segment_size()), 0);
+
+    if a.set_gro(true).is_ok() {
+        assert!(t!(a.gro()));
+    }
+}
+
+#[test]
+fn try_send_to_recv_from() {
+    drop(env_logger::try_init());
+
+    let a = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let b = t!(UdpSocket::bind(&t!("127.0.0.1:0".parse())));
+    let a_addr = t!(a.local_addr());
+    let b_addr = t!(b.local_addr());
+
+    t!(future::poll_fn(|| {
+        try_ready!(a.poll_send_ready());
+        assert_eq!(t!(a.try_send_to(b"hello", &b_addr)), 5);
+        Ok::<_, io::Error>(().into())
+    })
+    .wait());
+
+    let (n, from) = t!(future::poll_fn(|| {
+        try_ready!(b.poll_recv_ready());
+        let mut buf = [0; 8];
+        match b.try_recv_from(&mut buf) {
+            Ok(res) => Ok(res.into()),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => Ok(futures::Async::NotReady),
+            Err(e) => Err(e),
+        }
+    })
+    .wait());
+    assert_eq!(n, 5);
+    assert_eq!(from, a_addr);
+}
+
+#[test]
+fn builder() {
+    use tokio_udp::UdpBuilder;
+
+    drop(env_logger::try_init());
+
+    let builder = t!(UdpBuilder::new_v4());
+    t!(builder.set_reuseaddr(true));
+    assert!(t!(builder.reuseaddr()));
+    t!(builder.set_recv_buffer_size(64 * 1024));
+    assert!(t!(builder.recv_buffer_size()) >= 64 * 1024);
+    let a = t!(builder.bind(&t!("127.0.0.1:0".parse())));
+    let a_addr = t!(a.local_addr());
+
+    let b = t!(t!(UdpBuilder::for_addr(&a_addr)).bind(&t!("127.0.0.1:0".parse())));
+    let b_addr = t!(b.local_addr());
+
+    let (_, _) = t!(a.send_dgram(b"hello", &b_addr).wait());
+    let (_, buf, n, from) = t!(b.recv_dgram(vec![0; 8]).wait());
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(from, a_addr);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn builder_bind_device() {
+    use tokio_udp::UdpBuilder;
+
+    drop(env_logger::try_init());
+
+    let builder = t!(UdpBuilder::new_v4());
+    assert_eq!(t!(builder.device()), None);
+
+    let err = builder.bind_device(Some("lo\0")).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    // Binding to a device requires `CAP_NET_RAW`.
+    if builder.bind_device(Some("lo")).is_ok() {
+        assert_eq!(t!(builder.device()), Some("lo".to_string()));
+        t!(builder.bind(&t!("127.0.0.1:0".parse())));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn raw_fd_conversions() {
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+    let std_socket = t!(std::net::UdpSocket::bind("127.0.0.1:0"));
+    let addr = t!(std_socket.local_addr());
+
+    // Inherited sockets are usually in blocking mode.
+    let socket = unsafe { UdpSocket::from_raw_fd(std_socket.into_raw_fd()) };
+    assert_eq!(t!(socket.local_addr()), addr);
+
+    let sender = t!(std::net::UdpSocket::bind("127.0.0.1:0"));
+    t!(sender.send_to(b"hello", &addr));
+    let (socket, buf, n, from) = t!(socket.recv_dgram(vec![0; 16]).wait());
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(from, t!(sender.local_addr()));
+
+    // The socket is left in nonblocking mode.
+    let std_socket = t!(socket.into_std());
+    assert_eq!(t!(std_socket.local_addr()), addr);
+    assert_eq!(
+        std_socket.recv(&mut [0; 16]).unwrap_err().kind(),
+        io::ErrorKind::WouldBlock
+    );
+}
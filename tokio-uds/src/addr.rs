@@ -0,0 +1,210 @@
+use libc;
+use socket2::SockAddr;
+
+use std::ffi::OsStr;
+use std::fmt;
+use std::io;
+use std::mem;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::RawFd;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+/// An address of a Unix socket, which a socket can be bound or connected to.
+///
+/// Besides a path in the file system, this can name a socket in the abstract
+/// namespace of Linux. Such a socket is not visible in the file system, and
+/// its name is released once the socket is closed. Abstract names start with
+/// a NUL byte, so they cannot be given as a `Path`.
+///
+/// The address of a socket which is not bound, such as the sender of a
+/// datagram from an unbound socket, is unnamed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate tokio_uds;
+/// use tokio_uds::{UnixListener, UnixSocketAddr};
+///
+/// # #[cfg(target_os = "linux")]
+/// # fn main() -> std::io::Result<()> {
+/// let addr = UnixSocketAddr::from_abstract_name("tokio-uds-example")?;
+/// let listener = UnixListener::bind_addr(&addr)?;
+/// # drop(listener);
+/// # Ok(())
+/// # }
+/// # #[cfg(not(target_os = "linux"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct UnixSocketAddr {
+    kind: Kind,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Kind {
+    Pathname(PathBuf),
+    Abstract(Vec<u8>),
+    Unnamed,
+}
+
+/// Signature of `getsockname` and `getpeername`.
+type GetName =
+    unsafe extern "C" fn(libc::c_int, *mut libc::sockaddr, *mut libc::socklen_t) -> libc::c_int;
+
+impl UnixSocketAddr {
+    /// Creates an address naming the socket at `path` in the file system.
+    pub fn from_pathname<P: AsRef<Path>>(path: P) -> UnixSocketAddr {
+        UnixSocketAddr {
+            kind: Kind::Pathname(path.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Creates an address naming a socket in the abstract namespace.
+    ///
+    /// `name` is given without the leading NUL byte, and may contain any
+    /// bytes. An error is returned if it is too long to fit in a socket
+    /// address.
+    ///
+    /// This function is only available on Linux and Android.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_abstract_name<N: AsRef<[u8]>>(name: N) -> io::Result<UnixSocketAddr> {
+        let name = name.as_ref();
+        let addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+
+        if name.len() >= addr.sun_path.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "abstract name is too long",
+            ));
+        }
+
+        Ok(UnixSocketAddr {
+            kind: Kind::Abstract(name.to_vec()),
+        })
+    }
+
+    /// Returns the path of the socket, if the address is a path in the file
+    /// system.
+    pub fn as_pathname(&self) -> Option<&Path> {
+        match self.kind {
+            Kind::Pathname(ref path) => Some(path),
+            Kind::Abstract(..) | Kind::Unnamed => None,
+        }
+    }
+
+    /// Returns the name of the socket without the leading NUL byte, if the
+    /// address is in the abstract namespace.
+    pub fn as_abstract_name(&self) -> Option<&[u8]> {
+        match self.kind {
+            Kind::Abstract(ref name) => Some(name),
+            Kind::Pathname(..) | Kind::Unnamed => None,
+        }
+    }
+
+    /// Returns `true` if the address is unnamed.
+    pub fn is_unnamed(&self) -> bool {
+        match self.kind {
+            Kind::Unnamed => true,
+            Kind::Pathname(..) | Kind::Abstract(..) => false,
+        }
+    }
+
+    /// Returns the local address of the socket `fd`.
+    pub(crate) fn local(fd: RawFd) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::get(fd, libc::getsockname)
+    }
+
+    /// Returns the address of the peer of the socket `fd`.
+    pub(crate) fn peer(fd: RawFd) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::get(fd, libc::getpeername)
+    }
+
+    fn get(fd: RawFd, f: GetName) -> io::Result<UnixSocketAddr> {
+        let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+        let mut len = mem::size_of::<libc::sockaddr_un>() as libc::socklen_t;
+
+        if unsafe { f(fd, &mut addr as *mut _ as *mut _, &mut len) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(UnixSocketAddr::from_raw(&addr, len))
+    }
+
+    /// Converts an address filled in by the operating system, of `len`
+    /// bytes.
+    pub(crate) fn from_raw(addr: &libc::sockaddr_un, len: libc::socklen_t) -> UnixSocketAddr {
+        let offset = &addr.sun_path as *const _ as usize - addr as *const _ as usize;
+        let len = ::std::cmp::min((len as usize).saturating_sub(offset), addr.sun_path.len());
+        let path = unsafe { slice::from_raw_parts(addr.sun_path.as_ptr() as *const u8, len) };
+
+        let kind = match path.first() {
+            None => Kind::Unnamed,
+            Some(&0) if cfg!(any(target_os = "linux", target_os = "android")) => {
+                Kind::Abstract(path[1..].to_vec())
+            }
+            Some(&0) => Kind::Unnamed,
+            Some(_) => {
+                // The path may be NUL terminated.
+                let end = path.iter().position(|&b| b == 0).unwrap_or(len);
+                Kind::Pathname(PathBuf::from(OsStr::from_bytes(&path[..end])))
+            }
+        };
+
+        UnixSocketAddr { kind }
+    }
+
+    pub(crate) fn to_sockaddr(&self) -> io::Result<SockAddr> {
+        let name = match self.kind {
+            Kind::Pathname(ref path) => return SockAddr::unix(path),
+            Kind::Abstract(ref name) => name,
+            Kind::Unnamed => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cannot use an unnamed address",
+                ))
+            }
+        };
+
+        let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+        addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+        // The name follows a NUL byte, and is not NUL terminated.
+        for (dst, src) in addr.sun_path[1..].iter_mut().zip(name) {
+            *dst = *src as libc::c_char;
+        }
+
+        let offset = &addr.sun_path as *const _ as usize - &addr as *const _ as usize;
+        let len = offset + 1 + name.len();
+
+        Ok(unsafe {
+            SockAddr::from_raw_parts(&addr as *const _ as *const _, len as libc::socklen_t)
+        })
+    }
+}
+
+impl<'a> From<&'a Path> for UnixSocketAddr {
+    fn from(path: &'a Path) -> UnixSocketAddr {
+        UnixSocketAddr::from_pathname(path)
+    }
+}
+
+impl From<PathBuf> for UnixSocketAddr {
+    fn from(path: PathBuf) -> UnixSocketAddr {
+        UnixSocketAddr {
+            kind: Kind::Pathname(path),
+        }
+    }
+}
+
+impl fmt::Debug for UnixSocketAddr {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            Kind::Pathname(ref path) => write!(fmt, "{:?} (pathname)", path),
+            Kind::Abstract(ref name) => {
+                write!(fmt, "{:?} (abstract)", String::from_utf8_lossy(name))
+            }
+            Kind::Unnamed => write!(fmt, "(unnamed)"),
+        }
+    }
+}
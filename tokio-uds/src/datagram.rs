@@ -1,15 +1,18 @@
-use {RecvDgram, SendDgram};
+use {RecvDgram, SendDgram, UnixSocketAddr};
 
 use tokio_reactor::{Handle, PollEvented};
 
 use futures::{Async, Poll};
+use libc;
 use mio::Ready;
 use mio_uds;
+use socket2::{Domain, Socket, Type};
 
 use std::fmt;
 use std::io;
+use std::mem;
 use std::net::Shutdown;
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 
@@ -28,6 +31,20 @@ impl UnixDatagram {
         Ok(UnixDatagram::new(socket))
     }
 
+    /// Creates a new `UnixDatagram` bound to the specified address.
+    ///
+    /// Unlike [`bind`], this can bind the socket to a name in the abstract
+    /// namespace on Linux.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn bind_addr(addr: &UnixSocketAddr) -> io::Result<UnixDatagram> {
+        let socket = Socket::new(Domain::unix(), Type::dgram(), None)?;
+        socket.bind(&addr.to_sockaddr()?)?;
+
+        let socket = mio_uds::UnixDatagram::from_datagram(socket.into_unix_datagram())?;
+        Ok(UnixDatagram::new(socket))
+    }
+
     /// Creates an unnamed pair of connected sockets.
     ///
     /// This function will create a pair of interconnected Unix sockets for
@@ -52,6 +69,16 @@ impl UnixDatagram {
         Ok(UnixDatagram { io })
     }
 
+    /// Consumes this socket, returning the `UnixDatagram` of the standard library.
+    ///
+    /// The socket is deregistered from its event loop and is left in
+    /// nonblocking mode. Call `set_nonblocking(false)` on the returned socket
+    /// to use it for blocking I/O.
+    pub fn into_std(self) -> io::Result<net::UnixDatagram> {
+        let socket = self.io.into_inner()?;
+        Ok(unsafe { net::UnixDatagram::from_raw_fd(socket.into_raw_fd()) })
+    }
+
     fn new(socket: mio_uds::UnixDatagram) -> UnixDatagram {
         let io = PollEvented::new(socket);
         UnixDatagram { io }
@@ -71,6 +98,23 @@ impl UnixDatagram {
         self.io.get_ref().connect(path)
     }
 
+    /// Connects the socket to the specified address.
+    ///
+    /// Unlike [`connect`], this can connect the socket to a name in the
+    /// abstract namespace on Linux.
+    ///
+    /// [`connect`]: #method.connect
+    pub fn connect_addr(&self, addr: &UnixSocketAddr) -> io::Result<()> {
+        let addr = addr.to_sockaddr()?;
+        let ret = unsafe { libc::connect(self.as_raw_fd(), addr.as_ptr(), addr.len()) };
+
+        if ret == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Test whether this socket is ready to be read or not.
     pub fn poll_read_ready(&self, ready: Ready) -> Poll<Ready, io::Error> {
         self.io.poll_read_ready(ready)
@@ -93,6 +137,26 @@ impl UnixDatagram {
         self.io.get_ref().peer_addr()
     }
 
+    /// Returns the local address that this socket is bound to.
+    ///
+    /// Unlike [`local_addr`], this returns names in the abstract namespace
+    /// on Linux.
+    ///
+    /// [`local_addr`]: #method.local_addr
+    pub fn local_socket_addr(&self) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::local(self.as_raw_fd())
+    }
+
+    /// Returns the address of this socket's peer.
+    ///
+    /// Unlike [`peer_addr`], this returns names in the abstract namespace on
+    /// Linux.
+    ///
+    /// [`peer_addr`]: #method.peer_addr
+    pub fn peer_socket_addr(&self) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::peer(self.as_raw_fd())
+    }
+
     /// Receives data from the socket.
     ///
     /// On success, returns the number of bytes read and the address from
@@ -110,6 +174,48 @@ impl UnixDatagram {
         }
     }
 
+    /// Receives data from the socket.
+    ///
+    /// Unlike [`poll_recv_from`], the address the data came from may be in
+    /// the abstract namespace on Linux, so that it can be replied to with
+    /// [`poll_send_to_addr`].
+    ///
+    /// On success, returns the number of bytes read and the address from
+    /// whence the data came.
+    ///
+    /// [`poll_recv_from`]: #method.poll_recv_from
+    /// [`poll_send_to_addr`]: #method.poll_send_to_addr
+    pub fn poll_recv_from_addr(&self, buf: &mut [u8]) -> Poll<(usize, UnixSocketAddr), io::Error> {
+        try_ready!(self.io.poll_read_ready(Ready::readable()));
+
+        let mut addr: libc::sockaddr_un = unsafe { mem::zeroed() };
+        let mut len = mem::size_of::<libc::sockaddr_un>() as libc::socklen_t;
+
+        let ret = unsafe {
+            libc::recvfrom(
+                self.as_raw_fd(),
+                buf.as_mut_ptr() as *mut _,
+                buf.len(),
+                0,
+                &mut addr as *mut _ as *mut _,
+                &mut len,
+            )
+        };
+
+        if ret >= 0 {
+            let addr = UnixSocketAddr::from_raw(&addr, len);
+            return Ok((ret as usize, addr).into());
+        }
+
+        match io::Error::last_os_error() {
+            ref e if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(Ready::readable())?;
+                Ok(Async::NotReady)
+            }
+            e => Err(e),
+        }
+    }
+
     /// Receives data from the socket.
     ///
     /// On success, returns the number of bytes read.
@@ -153,6 +259,43 @@ impl UnixDatagram {
         }
     }
 
+    /// Sends data on the socket to the specified address.
+    ///
+    /// Unlike [`poll_send_to`], this can send data to a name in the abstract
+    /// namespace on Linux.
+    ///
+    /// On success, returns the number of bytes written.
+    ///
+    /// [`poll_send_to`]: #method.poll_send_to
+    pub fn poll_send_to_addr(&self, buf: &[u8], addr: &UnixSocketAddr) -> Poll<usize, io::Error> {
+        let addr = addr.to_sockaddr()?;
+
+        try_ready!(self.io.poll_write_ready());
+
+        let ret = unsafe {
+            libc::sendto(
+                self.as_raw_fd(),
+                buf.as_ptr() as *const _,
+                buf.len(),
+                0,
+                addr.as_ptr(),
+                addr.len(),
+            )
+        };
+
+        if ret >= 0 {
+            return Ok((ret as usize).into());
+        }
+
+        match io::Error::last_os_error() {
+            ref e if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Ok(Async::NotReady)
+            }
+            e => Err(e),
+        }
+    }
+
     /// Sends data on the socket to the socket's peer.
     ///
     /// The peer address may be set by the `connect` method, and this method
@@ -207,3 +350,24 @@ impl AsRawFd for UnixDatagram {
         self.io.get_ref().as_raw_fd()
     }
 }
+
+impl FromRawFd for UnixDatagram {
+    /// Creates a `UnixDatagram` from a raw file descriptor, such as one inherited
+    /// from systemd or launchd socket activation.
+    ///
+    /// The socket is switched to nonblocking mode and is lazily associated
+    /// with the default event loop's handle. Use [`from_std`] to associate it
+    /// with another event loop and handle errors.
+    ///
+    /// [`from_std`]: #method.from_std
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the socket cannot be switched to nonblocking
+    /// mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixDatagram {
+        let socket = mio_uds::UnixDatagram::from_datagram(net::UnixDatagram::from_raw_fd(fd))
+            .expect("failed to set the socket to nonblocking mode");
+        UnixDatagram::new(socket)
+    }
+}
@@ -2,12 +2,13 @@ use std::io;
 use std::os::unix::net::SocketAddr;
 use std::path::Path;
 
-use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use futures::{task, Async, AsyncSink, Poll, Sink, StartSend, Stream};
 
 use super::UnixDatagram;
 
 use bytes::{BufMut, BytesMut};
-use tokio_codec::{Decoder, Encoder};
+use tokio_codec::_datagram::MAX_SKIPPED_PER_POLL;
+use tokio_codec::{Decoder, EncodeRef, Encoder};
 
 /// A unified `Stream` and `Sink` interface to an underlying `UnixDatagram`, using
 /// the `Encoder` and `Decoder` traits to encode and decode frames.
@@ -25,6 +26,15 @@ use tokio_codec::{Decoder, Encoder};
 /// If you want to work more directly with the streams and sink, consider
 /// calling `split` on the `UnixDatagramFramed` returned by this method, which will break
 /// them into separate objects, allowing them to interact more easily.
+///
+/// Incoming frames are yielded along with the address of the peer which sent
+/// them, and outgoing frames are sent to the path given with each frame. As
+/// with `UdpFramed`, decoding errors can be [skipped], and frames can be
+/// encoded from a reference with [`start_send_ref`] when the codec
+/// implements `EncodeRef`.
+///
+/// [skipped]: #method.set_skip_decode_errors
+/// [`start_send_ref`]: #method.start_send_ref
 #[must_use = "sinks do nothing unless polled"]
 #[derive(Debug)]
 pub struct UnixDatagramFramed<A, C> {
@@ -34,6 +44,8 @@ pub struct UnixDatagramFramed<A, C> {
     wr: BytesMut,
     out_addr: Option<A>,
     flushed: bool,
+    skip_decode_errors: bool,
+    skipped: u64,
 }
 
 impl<A, C: Decoder> Stream for UnixDatagramFramed<A, C> {
@@ -41,20 +53,40 @@ impl<A, C: Decoder> Stream for UnixDatagramFramed<A, C> {
     type Error = C::Error;
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
-        self.rd.reserve(INITIAL_RD_CAPACITY);
+        let mut skipped = 0;
 
-        let (n, addr) = unsafe {
-            let (n, addr) = try_ready!(self.socket.poll_recv_from(self.rd.bytes_mut()));
-            self.rd.advance_mut(n);
-            (n, addr)
-        };
-        trace!("received {} bytes, decoding", n);
-        let frame_res = self.codec.decode(&mut self.rd);
-        self.rd.clear();
-        let frame = frame_res?;
-        let result = frame.map(|frame| (frame, addr));
-        trace!("frame decoded from buffer");
-        Ok(Async::Ready(result))
+        loop {
+            self.rd.reserve(INITIAL_RD_CAPACITY);
+
+            let (n, addr) = unsafe {
+                let (n, addr) = try_ready!(self.socket.poll_recv_from(self.rd.bytes_mut()));
+                self.rd.advance_mut(n);
+                (n, addr)
+            };
+            trace!("received {} bytes, decoding", n);
+            let frame_res = self.codec.decode(&mut self.rd);
+            self.rd.clear();
+            let frame = match frame_res {
+                Ok(frame) => frame,
+                Err(_) if self.skip_decode_errors => {
+                    trace!("failed to decode datagram from {:?}, skipping it", addr);
+                    self.skipped += 1;
+                    skipped += 1;
+
+                    if skipped == MAX_SKIPPED_PER_POLL {
+                        // Yield, the task is polled again right away.
+                        task::current().notify();
+                        return Ok(Async::NotReady);
+                    }
+
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let result = frame.map(|frame| (frame, addr));
+            trace!("frame decoded from buffer");
+            return Ok(Async::Ready(result));
+        }
     }
 }
 
@@ -125,6 +157,30 @@ impl<A: AsRef<Path>, C: Encoder> Sink for UnixDatagramFramed<A, C> {
     }
 }
 
+impl<A: AsRef<Path>, C: EncodeRef> UnixDatagramFramed<A, C> {
+    /// Begins the process of sending a frame to `out_addr`, encoding it from
+    /// a reference so that the caller keeps ownership of it.
+    ///
+    /// This behaves as `Sink::start_send`, except that `Async::NotReady` is
+    /// returned while the previous frame is not sent yet, as the item does
+    /// not need to be handed back. The frame is sent by
+    /// `Sink::poll_complete`.
+    pub fn start_send_ref(&mut self, frame: &C::Item, out_addr: A) -> Poll<(), C::Error> {
+        trace!("sending frame");
+
+        if !self.flushed {
+            try_ready!(self.poll_complete());
+        }
+
+        self.codec.encode_ref(frame, &mut self.wr)?;
+        self.out_addr = Some(out_addr);
+        self.flushed = false;
+        trace!("frame encoded; length={}", self.wr.len());
+
+        Ok(Async::Ready(()))
+    }
+}
+
 const INITIAL_RD_CAPACITY: usize = 64 * 1024;
 const INITIAL_WR_CAPACITY: usize = 8 * 1024;
 
@@ -140,9 +196,35 @@ impl<A, C> UnixDatagramFramed<A, C> {
             rd: BytesMut::with_capacity(INITIAL_RD_CAPACITY),
             wr: BytesMut::with_capacity(INITIAL_WR_CAPACITY),
             flushed: true,
+            skip_decode_errors: false,
+            skipped: 0,
         }
     }
 
+    /// Sets whether datagrams which fail to decode are skipped.
+    ///
+    /// By default, a datagram which the codec fails to decode makes the
+    /// stream return the error. When skipping them, such datagrams are
+    /// dropped instead, and the stream goes on with the next datagram. The
+    /// number of datagrams skipped so far is returned by
+    /// [`skipped_datagrams`]. Errors from the socket itself are always
+    /// returned.
+    ///
+    /// [`skipped_datagrams`]: #method.skipped_datagrams
+    pub fn set_skip_decode_errors(&mut self, skip: bool) {
+        self.skip_decode_errors = skip;
+    }
+
+    /// Returns the number of datagrams which were skipped because they
+    /// failed to decode.
+    ///
+    /// For more information, see [`set_skip_decode_errors`].
+    ///
+    /// [`set_skip_decode_errors`]: #method.set_skip_decode_errors
+    pub fn skipped_datagrams(&self) -> u64 {
+        self.skipped
+    }
+
     /// Returns a reference to the underlying I/O stream wrapped by `Framed`.
     ///
     /// # Note
@@ -165,4 +247,9 @@ impl<A, C> UnixDatagramFramed<A, C> {
     pub fn get_mut(&mut self) -> &mut UnixDatagram {
         &mut self.socket
     }
+
+    /// Consumes the `Framed`, returning its underlying I/O stream.
+    pub fn into_inner(self) -> UnixDatagram {
+        self.socket
+    }
 }
@@ -15,24 +15,29 @@ extern crate libc;
 extern crate log;
 extern crate mio;
 extern crate mio_uds;
+extern crate socket2;
 extern crate tokio_codec;
 extern crate tokio_io;
 extern crate tokio_reactor;
 
+mod addr;
 mod datagram;
 mod frame;
 mod incoming;
 mod listener;
 mod recv_dgram;
 mod send_dgram;
+mod split;
 mod stream;
 mod ucred;
 
+pub use addr::UnixSocketAddr;
 pub use datagram::UnixDatagram;
 pub use frame::UnixDatagramFramed;
 pub use incoming::Incoming;
 pub use listener::UnixListener;
 pub use recv_dgram::RecvDgram;
 pub use send_dgram::SendDgram;
+pub use split::{OwnedReadHalf, OwnedWriteHalf, ReuniteError};
 pub use stream::{ConnectFuture, UnixStream};
 pub use ucred::UCred;
@@ -1,14 +1,15 @@
-use {Incoming, UnixStream};
+use {Incoming, UnixSocketAddr, UnixStream};
 
 use tokio_reactor::{Handle, PollEvented};
 
 use futures::{Async, Poll};
 use mio::Ready;
 use mio_uds;
+use socket2::{Domain, Socket, Type};
 
 use std::fmt;
 use std::io;
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 
@@ -28,6 +29,22 @@ impl UnixListener {
         Ok(UnixListener { io })
     }
 
+    /// Creates a new `UnixListener` bound to the specified address.
+    ///
+    /// Unlike [`bind`], this can bind the listener to a name in the abstract
+    /// namespace on Linux.
+    ///
+    /// [`bind`]: #method.bind
+    pub fn bind_addr(addr: &UnixSocketAddr) -> io::Result<UnixListener> {
+        let socket = Socket::new(Domain::unix(), Type::stream(), None)?;
+        socket.bind(&addr.to_sockaddr()?)?;
+        socket.listen(128)?;
+
+        let listener = mio_uds::UnixListener::from_listener(socket.into_unix_listener())?;
+        let io = PollEvented::new(listener);
+        Ok(UnixListener { io })
+    }
+
     /// Consumes a `UnixListener` in the standard library and returns a
     /// nonblocking `UnixListener` from this crate.
     ///
@@ -39,11 +56,31 @@ impl UnixListener {
         Ok(UnixListener { io })
     }
 
+    /// Consumes this listener, returning the `UnixListener` of the standard library.
+    ///
+    /// The listener is deregistered from its event loop and is left in
+    /// nonblocking mode. Call `set_nonblocking(false)` on the returned listener
+    /// to use it for blocking I/O.
+    pub fn into_std(self) -> io::Result<net::UnixListener> {
+        let listener = self.io.into_inner()?;
+        Ok(unsafe { net::UnixListener::from_raw_fd(listener.into_raw_fd()) })
+    }
+
     /// Returns the local socket address of this listener.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.io.get_ref().local_addr()
     }
 
+    /// Returns the local address of this listener.
+    ///
+    /// Unlike [`local_addr`], this returns names in the abstract namespace
+    /// on Linux.
+    ///
+    /// [`local_addr`]: #method.local_addr
+    pub fn local_socket_addr(&self) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::local(self.as_raw_fd())
+    }
+
     /// Test whether this socket is ready to be read or not.
     pub fn poll_read_ready(&self, ready: Ready) -> Poll<Ready, io::Error> {
         self.io.poll_read_ready(ready)
@@ -144,3 +181,25 @@ impl AsRawFd for UnixListener {
         self.io.get_ref().as_raw_fd()
     }
 }
+
+impl FromRawFd for UnixListener {
+    /// Creates a `UnixListener` from a raw file descriptor, such as one inherited
+    /// from systemd or launchd socket activation.
+    ///
+    /// The listener is switched to nonblocking mode and is lazily associated
+    /// with the default event loop's handle. Use [`from_std`] to associate it
+    /// with another event loop and handle errors.
+    ///
+    /// [`from_std`]: #method.from_std
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the listener cannot be switched to nonblocking
+    /// mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixListener {
+        let listener = mio_uds::UnixListener::from_listener(net::UnixListener::from_raw_fd(fd))
+            .expect("failed to set the listener to nonblocking mode");
+        let io = PollEvented::new(listener);
+        UnixListener { io }
+    }
+}
@@ -0,0 +1,200 @@
+use {UCred, UnixStream};
+
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use bytes::{Buf, BufMut};
+use futures::Poll;
+use iovec::IoVec;
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::net::SocketAddr;
+use std::sync::Arc;
+
+/// Owned read half of a [`UnixStream`], created by [`into_split`].
+///
+/// Reading from an `OwnedReadHalf` is usually done using the combinators in
+/// `tokio::io`, or by polling it directly as an [`AsyncRead`].
+///
+/// [`UnixStream`]: struct.UnixStream.html
+/// [`into_split`]: struct.UnixStream.html#method.into_split
+/// [`AsyncRead`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncRead.html
+#[derive(Debug)]
+pub struct OwnedReadHalf {
+    inner: Arc<UnixStream>,
+}
+
+/// Owned write half of a [`UnixStream`], created by [`into_split`].
+///
+/// Dropping the write half shuts down the write direction of the stream, so
+/// the peer reads EOF, unless it is [reunited] with its read half first.
+///
+/// Writing to an `OwnedWriteHalf` is usually done using the combinators in
+/// `tokio::io`, or by polling it directly as an [`AsyncWrite`].
+///
+/// [`UnixStream`]: struct.UnixStream.html
+/// [`into_split`]: struct.UnixStream.html#method.into_split
+/// [reunited]: struct.OwnedReadHalf.html#method.reunite
+/// [`AsyncWrite`]: https://docs.rs/tokio-io/0.1/tokio_io/trait.AsyncWrite.html
+#[derive(Debug)]
+pub struct OwnedWriteHalf {
+    inner: Arc<UnixStream>,
+    shutdown_on_drop: bool,
+}
+
+/// Error returned by [`OwnedReadHalf::reunite`] when the halves do not come
+/// from the same stream.
+///
+/// The halves are handed back in the error.
+///
+/// [`OwnedReadHalf::reunite`]: struct.OwnedReadHalf.html#method.reunite
+#[derive(Debug)]
+pub struct ReuniteError(pub OwnedReadHalf, pub OwnedWriteHalf);
+
+pub(crate) fn split(stream: UnixStream) -> (OwnedReadHalf, OwnedWriteHalf) {
+    let inner = Arc::new(stream);
+
+    let read = OwnedReadHalf {
+        inner: inner.clone(),
+    };
+    let write = OwnedWriteHalf {
+        inner,
+        shutdown_on_drop: true,
+    };
+
+    (read, write)
+}
+
+impl OwnedReadHalf {
+    /// Attempts to put the two halves of a `UnixStream` back together and
+    /// recover the original stream.
+    ///
+    /// Fails with a [`ReuniteError`] if the halves come from different
+    /// streams.
+    ///
+    /// [`ReuniteError`]: struct.ReuniteError.html
+    pub fn reunite(self, other: OwnedWriteHalf) -> Result<UnixStream, ReuniteError> {
+        if !Arc::ptr_eq(&self.inner, &other.inner) {
+            return Err(ReuniteError(self, other));
+        }
+
+        let mut other = other;
+        other.shutdown_on_drop = false;
+
+        let inner = other.inner.clone();
+        drop(self);
+        drop(other);
+
+        Ok(Arc::try_unwrap(inner).expect("UnixStream: try_unwrap failed in reunite"))
+    }
+
+    /// Returns the socket address of the remote half of this connection.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns effective credentials of the process which called `connect` or `pair`.
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        self.inner.peer_cred()
+    }
+}
+
+impl OwnedWriteHalf {
+    /// Returns the socket address of the remote half of this connection.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+
+    /// Returns the socket address of the local half of this connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Returns effective credentials of the process which called `connect` or `pair`.
+    pub fn peer_cred(&self) -> io::Result<UCred> {
+        self.inner.peer_cred()
+    }
+}
+
+impl AsRef<UnixStream> for OwnedReadHalf {
+    fn as_ref(&self) -> &UnixStream {
+        &self.inner
+    }
+}
+
+impl AsRef<UnixStream> for OwnedWriteHalf {
+    fn as_ref(&self) -> &UnixStream {
+        &self.inner
+    }
+}
+
+impl Read for OwnedReadHalf {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self.inner).read(buf)
+    }
+}
+
+impl AsyncRead for OwnedReadHalf {
+    unsafe fn prepare_uninitialized_buffer(&self, _: &mut [u8]) -> bool {
+        false
+    }
+
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        <&UnixStream>::poll_read_vectored(&mut &*self.inner, bufs)
+    }
+
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        <&UnixStream>::read_buf(&mut &*self.inner, buf)
+    }
+}
+
+impl Write for OwnedWriteHalf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.inner).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.inner).flush()
+    }
+}
+
+impl AsyncWrite for OwnedWriteHalf {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        <&UnixStream>::poll_write_vectored(&mut &*self.inner, bufs)
+    }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        <&UnixStream>::shutdown(&mut &*self.inner)
+    }
+
+    fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        <&UnixStream>::write_buf(&mut &*self.inner, buf)
+    }
+}
+
+impl Drop for OwnedWriteHalf {
+    fn drop(&mut self) {
+        if self.shutdown_on_drop {
+            let _ = self.inner.shutdown(Shutdown::Write);
+        }
+    }
+}
+
+impl fmt::Display for ReuniteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.description())
+    }
+}
+
+impl Error for ReuniteError {
+    fn description(&self) -> &str {
+        "tried to reunite halves that are not from the same socket"
+    }
+}
@@ -1,5 +1,8 @@
+use split::{self, OwnedReadHalf, OwnedWriteHalf};
 use ucred::{self, UCred};
+use UnixSocketAddr;
 
+use tokio_io::io::HalfClose;
 use tokio_io::{AsyncRead, AsyncWrite};
 use tokio_reactor::{Handle, PollEvented};
 
@@ -9,11 +12,12 @@ use iovec::{self, IoVec};
 use libc;
 use mio::Ready;
 use mio_uds;
+use socket2::{Domain, Socket, Type};
 
 use std::fmt;
 use std::io::{self, Read, Write};
 use std::net::Shutdown;
-use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::os::unix::net::{self, SocketAddr};
 use std::path::Path;
 
@@ -60,6 +64,23 @@ impl UnixStream {
         ConnectFuture { inner }
     }
 
+    /// Connects to the socket named by `addr`.
+    ///
+    /// Unlike [`connect`], this can connect to a name in the abstract
+    /// namespace on Linux.
+    ///
+    /// [`connect`]: #method.connect
+    pub fn connect_addr(addr: &UnixSocketAddr) -> ConnectFuture {
+        let res = connect_addr(addr).map(UnixStream::new);
+
+        let inner = match res {
+            Ok(stream) => State::Waiting(stream),
+            Err(e) => State::Error(e),
+        };
+
+        ConnectFuture { inner }
+    }
+
     /// Consumes a `UnixStream` in the standard library and returns a
     /// nonblocking `UnixStream` from this crate.
     ///
@@ -72,6 +93,16 @@ impl UnixStream {
         Ok(UnixStream { io })
     }
 
+    /// Consumes this stream, returning the `UnixStream` of the standard library.
+    ///
+    /// The stream is deregistered from its event loop and is left in
+    /// nonblocking mode. Call `set_nonblocking(false)` on the returned stream
+    /// to use it for blocking I/O.
+    pub fn into_std(self) -> io::Result<net::UnixStream> {
+        let stream = self.io.into_inner()?;
+        Ok(unsafe { net::UnixStream::from_raw_fd(stream.into_raw_fd()) })
+    }
+
     /// Creates an unnamed pair of connected sockets.
     ///
     /// This function will create a pair of interconnected Unix sockets for
@@ -91,15 +122,96 @@ impl UnixStream {
     }
 
     /// Test whether this socket is ready to be read or not.
+    ///
+    /// The mask argument allows specifying what readiness to notify on. This
+    /// can be any value, including platform specific readiness such as
+    /// `UnixReady::hup()`, **except** `writable`. HUP is always implicitly
+    /// included on platforms that support it.
+    ///
+    /// The socket will remain in a read-ready state until calls to `poll_read`
+    /// return `NotReady`, or calls to [`try_read`] fail with `WouldBlock`.
+    ///
+    /// [`try_read`]: #method.try_read
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    ///
+    /// * `ready` includes writable.
+    /// * called from outside of a task context.
     pub fn poll_read_ready(&self, ready: Ready) -> Poll<Ready, io::Error> {
         self.io.poll_read_ready(ready)
     }
 
     /// Test whether this socket is ready to be written to or not.
+    ///
+    /// This always checks for writable readiness and also checks for HUP
+    /// readiness on platforms that support it.
+    ///
+    /// The socket will remain in a write-ready state until calls to
+    /// `poll_write` return `NotReady`, or calls to [`try_write`] fail with
+    /// `WouldBlock`.
+    ///
+    /// [`try_write`]: #method.try_write
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called from outside of a task context.
     pub fn poll_write_ready(&self) -> Poll<Ready, io::Error> {
         self.io.poll_write_ready()
     }
 
+    /// Tries to read data from the socket into `buf`, without waiting for the
+    /// socket to be readable.
+    ///
+    /// This is meant to be used after [`poll_read_ready`] returns
+    /// `Async::Ready`, to issue as many reads as wanted for a single readiness
+    /// check. On success, returns the number of bytes read, zero meaning that
+    /// the peer closed its write half. If no data is available, an error of
+    /// kind `WouldBlock` is returned and the socket's read readiness is cleared,
+    /// so that the next call to [`poll_read_ready`] waits for data to arrive.
+    ///
+    /// [`poll_read_ready`]: #method.poll_read_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        match (&*self.io.get_ref()).read(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_read_ready(Ready::readable())?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
+    /// Tries to write data from `buf` to the socket, without waiting for the
+    /// socket to be writable.
+    ///
+    /// This is meant to be used after [`poll_write_ready`] returns
+    /// `Async::Ready`. On success, returns the number of bytes written. If the
+    /// socket's send buffer is full, an error of kind `WouldBlock` is returned
+    /// and the socket's write readiness is cleared, so that the next call to
+    /// [`poll_write_ready`] waits for the socket to become writable again.
+    ///
+    /// [`poll_write_ready`]: #method.poll_write_ready
+    ///
+    /// # Panics
+    ///
+    /// This function panics if it returns `WouldBlock` when called from
+    /// outside of a task context.
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        match (&*self.io.get_ref()).write(buf) {
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.io.clear_write_ready()?;
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+            res => res,
+        }
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         self.io.get_ref().local_addr()
@@ -110,6 +222,26 @@ impl UnixStream {
         self.io.get_ref().peer_addr()
     }
 
+    /// Returns the address of the local half of this connection.
+    ///
+    /// Unlike [`local_addr`], this returns names in the abstract namespace
+    /// on Linux.
+    ///
+    /// [`local_addr`]: #method.local_addr
+    pub fn local_socket_addr(&self) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::local(self.as_raw_fd())
+    }
+
+    /// Returns the address of the remote half of this connection.
+    ///
+    /// Unlike [`peer_addr`], this returns names in the abstract namespace on
+    /// Linux.
+    ///
+    /// [`peer_addr`]: #method.peer_addr
+    pub fn peer_socket_addr(&self) -> io::Result<UnixSocketAddr> {
+        UnixSocketAddr::peer(self.as_raw_fd())
+    }
+
     /// Returns effective credentials of the process which called `connect` or `pair`.
     pub fn peer_cred(&self) -> io::Result<UCred> {
         ucred::get_peer_cred(self)
@@ -128,6 +260,19 @@ impl UnixStream {
     pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
         self.io.get_ref().shutdown(how)
     }
+
+    /// Splits the stream into an owned read half and an owned write half.
+    ///
+    /// Unlike `AsyncRead::split`, the halves do not lock each other, and can
+    /// be moved into separate tasks.
+    ///
+    /// The halves can be put back together with [`OwnedReadHalf::reunite`].
+    /// Dropping the write half shuts down the write direction of the stream.
+    ///
+    /// [`OwnedReadHalf::reunite`]: struct.OwnedReadHalf.html#method.reunite
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        split::split(self)
+    }
 }
 
 impl Read for UnixStream {
@@ -150,12 +295,27 @@ impl AsyncRead for UnixStream {
         false
     }
 
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
+        <&UnixStream>::poll_read_vectored(&mut &*self, bufs)
+    }
+
     fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
         <&UnixStream>::read_buf(&mut &*self, buf)
     }
 }
 
+impl HalfClose for UnixStream {
+    fn poll_close_write(&mut self) -> Poll<(), io::Error> {
+        UnixStream::shutdown(self, Shutdown::Write)?;
+        Ok(().into())
+    }
+}
+
 impl AsyncWrite for UnixStream {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
+        <&UnixStream>::poll_write_vectored(&mut &*self, bufs)
+    }
+
     fn shutdown(&mut self) -> Poll<(), io::Error> {
         <&UnixStream>::shutdown(&mut &*self)
     }
@@ -186,12 +346,12 @@ impl<'a> AsyncRead for &'a UnixStream {
         false
     }
 
-    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+    fn poll_read_vectored(&mut self, bufs: &mut [&mut IoVec]) -> Poll<usize, io::Error> {
         if let Async::NotReady = <UnixStream>::poll_read_ready(self, Ready::readable())? {
             return Ok(Async::NotReady);
         }
         unsafe {
-            let r = read_ready(buf, self.as_raw_fd());
+            let r = read_ready_vecs(bufs, self.as_raw_fd());
             if r == -1 {
                 let e = io::Error::last_os_error();
                 if e.kind() == io::ErrorKind::WouldBlock {
@@ -201,25 +361,27 @@ impl<'a> AsyncRead for &'a UnixStream {
                     Err(e)
                 }
             } else {
-                let r = r as usize;
-                buf.advance_mut(r);
-                Ok(r.into())
+                Ok((r as usize).into())
             }
         }
     }
-}
 
-impl<'a> AsyncWrite for &'a UnixStream {
-    fn shutdown(&mut self) -> Poll<(), io::Error> {
-        Ok(().into())
+    fn read_buf<B: BufMut>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        unsafe {
+            let n = try_ready!(with_bufs_mut(buf, |bufs| self.poll_read_vectored(bufs)));
+            buf.advance_mut(n);
+            Ok(n.into())
+        }
     }
+}
 
-    fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+impl<'a> AsyncWrite for &'a UnixStream {
+    fn poll_write_vectored(&mut self, bufs: &[&IoVec]) -> Poll<usize, io::Error> {
         if let Async::NotReady = <UnixStream>::poll_write_ready(self)? {
             return Ok(Async::NotReady);
         }
         unsafe {
-            let r = write_ready(buf, self.as_raw_fd());
+            let r = write_ready_vecs(bufs, self.as_raw_fd());
             if r == -1 {
                 let e = io::Error::last_os_error();
                 if e.kind() == io::ErrorKind::WouldBlock {
@@ -229,12 +391,20 @@ impl<'a> AsyncWrite for &'a UnixStream {
                     Err(e)
                 }
             } else {
-                let r = r as usize;
-                buf.advance(r);
-                Ok(r.into())
+                Ok((r as usize).into())
             }
         }
     }
+
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        Ok(().into())
+    }
+
+    fn write_buf<B: Buf>(&mut self, buf: &mut B) -> Poll<usize, io::Error> {
+        let n = try_ready!(with_bufs(buf, |bufs| self.poll_write_vectored(bufs)));
+        buf.advance(n);
+        Ok(n.into())
+    }
 }
 
 impl fmt::Debug for UnixStream {
@@ -249,6 +419,27 @@ impl AsRawFd for UnixStream {
     }
 }
 
+impl FromRawFd for UnixStream {
+    /// Creates a `UnixStream` from a raw file descriptor, such as one inherited
+    /// from systemd or launchd socket activation.
+    ///
+    /// The stream is switched to nonblocking mode and is lazily associated
+    /// with the default event loop's handle. Use [`from_std`] to associate it
+    /// with another event loop and handle errors.
+    ///
+    /// [`from_std`]: #method.from_std
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the stream cannot be switched to nonblocking
+    /// mode.
+    unsafe fn from_raw_fd(fd: RawFd) -> UnixStream {
+        let stream = mio_uds::UnixStream::from_stream(net::UnixStream::from_raw_fd(fd))
+            .expect("failed to set the stream to nonblocking mode");
+        UnixStream::new(stream)
+    }
+}
+
 impl Future for ConnectFuture {
     type Item = UnixStream;
     type Error = io::Error;
@@ -284,7 +475,26 @@ impl Future for ConnectFuture {
     }
 }
 
-unsafe fn read_ready<B: BufMut>(buf: &mut B, raw_fd: RawFd) -> isize {
+fn connect_addr(addr: &UnixSocketAddr) -> io::Result<mio_uds::UnixStream> {
+    let addr = addr.to_sockaddr()?;
+    let socket = Socket::new(Domain::unix(), Type::stream(), None)?;
+    socket.set_nonblocking(true)?;
+
+    match socket.connect(&addr) {
+        Ok(()) => {}
+        Err(ref e) if e.raw_os_error() == Some(libc::EINPROGRESS) => {}
+        Err(e) => return Err(e),
+    }
+
+    mio_uds::UnixStream::from_stream(socket.into_unix_stream())
+}
+
+/// Calls `f` with the chunks of `buf`.
+unsafe fn with_bufs_mut<B, F, R>(buf: &mut B, f: F) -> R
+where
+    B: BufMut,
+    F: FnOnce(&mut [&mut IoVec]) -> R,
+{
     // The `IoVec` type can't have a 0-length size, so we create a bunch
     // of dummy versions on the stack with 1 length which we'll quickly
     // overwrite.
@@ -324,7 +534,7 @@ unsafe fn read_ready<B: BufMut>(buf: &mut B, raw_fd: RawFd) -> isize {
     ];
 
     let n = buf.bytes_vec_mut(&mut bufs);
-    read_ready_vecs(&mut bufs[..n], raw_fd)
+    f(&mut bufs[..n])
 }
 
 unsafe fn read_ready_vecs(bufs: &mut [&mut IoVec], raw_fd: RawFd) -> isize {
@@ -333,7 +543,12 @@ unsafe fn read_ready_vecs(bufs: &mut [&mut IoVec], raw_fd: RawFd) -> isize {
     libc::readv(raw_fd, iovecs.as_ptr(), iovecs.len() as i32)
 }
 
-unsafe fn write_ready<B: Buf>(buf: &mut B, raw_fd: RawFd) -> isize {
+/// Calls `f` with the chunks of `buf`.
+fn with_bufs<B, F, R>(buf: &B, f: F) -> R
+where
+    B: Buf,
+    F: FnOnce(&[&IoVec]) -> R,
+{
     // The `IoVec` type can't have a zero-length size, so create a dummy
     // version from a 1-length slice which we'll overwrite with the
     // `bytes_vec` method.
@@ -345,7 +560,7 @@ unsafe fn write_ready<B: Buf>(buf: &mut B, raw_fd: RawFd) -> isize {
     ];
 
     let n = buf.bytes_vec(&mut bufs);
-    write_ready_vecs(&bufs[..n], raw_fd)
+    f(&bufs[..n])
 }
 
 unsafe fn write_ready_vecs(bufs: &[&IoVec], raw_fd: RawFd) -> isize {
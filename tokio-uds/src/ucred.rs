@@ -1,4 +1,4 @@
-use libc::{gid_t, uid_t};
+use libc::{gid_t, pid_t, uid_t};
 
 /// Credentials of a process
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -7,6 +7,17 @@ pub struct UCred {
     pub uid: uid_t,
     /// GID (group ID) of the process
     pub gid: gid_t,
+    pid: Option<pid_t>,
+}
+
+impl UCred {
+    /// Gets PID (process ID) of the process.
+    ///
+    /// This is only available on Linux, Android, macOS, iOS and Solaris, and
+    /// is `None` on other platforms, or if the PID could not be retrieved.
+    pub fn pid(&self) -> Option<pid_t> {
+        self.pid
+    }
 }
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -63,6 +74,7 @@ pub mod impl_linux {
                 Ok(super::UCred {
                     uid: ucred.uid,
                     gid: ucred.gid,
+                    pid: Some(ucred.pid),
                 })
             } else {
                 Err(io::Error::last_os_error())
@@ -81,25 +93,64 @@ pub mod impl_linux {
 ))]
 pub mod impl_macos {
     use libc::getpeereid;
+    use std::io;
     use std::os::unix::io::AsRawFd;
-    use std::{io, mem};
     use UnixStream;
 
     pub fn get_peer_cred(sock: &UnixStream) -> io::Result<super::UCred> {
         unsafe {
             let raw_fd = sock.as_raw_fd();
 
-            let mut cred: super::UCred = mem::uninitialized();
+            let mut cred = super::UCred {
+                uid: 0,
+                gid: 0,
+                pid: None,
+            };
 
             let ret = getpeereid(raw_fd, &mut cred.uid, &mut cred.gid);
 
             if ret == 0 {
+                cred.pid = get_peer_pid(raw_fd);
                 Ok(cred)
             } else {
                 Err(io::Error::last_os_error())
             }
         }
     }
+
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    unsafe fn get_peer_pid(raw_fd: ::std::os::unix::io::RawFd) -> Option<::libc::pid_t> {
+        use libc::{c_int, c_void, getsockopt, pid_t, socklen_t};
+        use std::mem;
+
+        // See `sys/un.h`.
+        const SOL_LOCAL: c_int = 0;
+        const LOCAL_PEERPID: c_int = 0x002;
+
+        let mut pid: pid_t = 0;
+        let mut pid_size = mem::size_of::<pid_t>() as socklen_t;
+
+        let ret = getsockopt(
+            raw_fd,
+            SOL_LOCAL,
+            LOCAL_PEERPID,
+            &mut pid as *mut pid_t as *mut c_void,
+            &mut pid_size,
+        );
+
+        // The credentials are still useful without the PID, which older
+        // versions of macOS do not report.
+        if ret == 0 && pid_size as usize == mem::size_of::<pid_t>() {
+            Some(pid)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    unsafe fn get_peer_pid(_: ::std::os::unix::io::RawFd) -> Option<::libc::pid_t> {
+        None
+    }
 }
 
 #[cfg(any(target_os = "solaris"))]
@@ -116,6 +167,7 @@ pub mod impl_solaris {
         fn ucred_free(cred: *mut ucred_t);
         fn ucred_geteuid(cred: *const ucred_t) -> super::uid_t;
         fn ucred_getegid(cred: *const ucred_t) -> super::gid_t;
+        fn ucred_getpid(cred: *const ucred_t) -> super::pid_t;
 
         fn getpeerucred(
             fd: ::std::os::raw::c_int,
@@ -134,10 +186,15 @@ pub mod impl_solaris {
             if ret == 0 {
                 let uid = ucred_geteuid(cred);
                 let gid = ucred_getegid(cred);
+                let pid = ucred_getpid(cred);
 
                 ucred_free(cred);
 
-                Ok(super::UCred { uid, gid })
+                Ok(super::UCred {
+                    uid,
+                    gid,
+                    pid: Some(pid),
+                })
             } else {
                 Err(io::Error::last_os_error())
             }
@@ -151,6 +208,13 @@ pub mod impl_solaris {
 mod test {
     use libc::getegid;
     use libc::geteuid;
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    ))]
+    use libc::getpid;
     use UnixStream;
 
     #[test]
@@ -173,5 +237,13 @@ mod test {
 
         assert_eq!(cred_a.uid, uid);
         assert_eq!(cred_a.gid, gid);
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "ios"
+        ))]
+        assert_eq!(cred_a.pid(), Some(unsafe { getpid() }));
     }
 }
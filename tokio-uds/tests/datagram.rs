@@ -86,3 +86,151 @@ fn framed_echo() {
         assert_eq!(response[0].0, "ECHO");
     }
 }
+
+#[test]
+fn framed_skip_decode_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let server_path = dir.path().join("server.sock");
+    let client_path = dir.path().join("client.sock");
+
+    let mut rt = Runtime::new().unwrap();
+
+    let socket = UnixDatagram::bind(&server_path).unwrap();
+    let mut server = UnixDatagramFramed::<&std::path::Path, _>::new(socket, StringDatagramCodec);
+    server.set_skip_decode_errors(true);
+
+    let client = UnixDatagram::bind(&client_path).unwrap();
+    let client = rt
+        .block_on(client.send_dgram(&b"\xff\xfe"[..], &server_path))
+        .unwrap()
+        .0;
+    rt.block_on(client.send_dgram(&b"valid"[..], &server_path))
+        .unwrap();
+
+    let (frame, server) = rt
+        .block_on(server.into_future())
+        .map_err(|(e, _)| e)
+        .unwrap();
+    let (msg, addr) = frame.unwrap();
+    assert_eq!(msg, "valid");
+    assert_eq!(addr.as_pathname(), Some(client_path.as_path()));
+    assert_eq!(server.skipped_datagrams(), 1);
+}
+
+#[test]
+fn framed_skip_many_decode_errors() {
+    use std::os::unix::net;
+    use std::thread;
+
+    let dir = tempfile::tempdir().unwrap();
+    let server_path = dir.path().join("server.sock");
+
+    let mut rt = Runtime::new().unwrap();
+
+    let socket = UnixDatagram::bind(&server_path).unwrap();
+    let mut server = UnixDatagramFramed::<&std::path::Path, _>::new(socket, StringDatagramCodec);
+    server.set_skip_decode_errors(true);
+
+    // More malformed datagrams than are skipped in a single poll
+    let client = net::UnixDatagram::unbound().unwrap();
+    let sender = thread::spawn(move || {
+        for _ in 0..100 {
+            client.send_to(b"\xff\xfe", &server_path).unwrap();
+        }
+        client.send_to(b"valid", &server_path).unwrap();
+    });
+
+    let (frame, server) = rt
+        .block_on(server.into_future())
+        .map_err(|(e, _)| e)
+        .unwrap();
+    assert_eq!(frame.unwrap().0, "valid");
+    assert_eq!(server.skipped_datagrams(), 100);
+
+    sender.join().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn abstract_namespace() {
+    use futures::future::poll_fn;
+
+    let server_addr =
+        UnixSocketAddr::from_abstract_name(format!("tokio-uds-server-{}", std::process::id()))
+            .unwrap();
+    let client_addr =
+        UnixSocketAddr::from_abstract_name(format!("tokio-uds-client-{}", std::process::id()))
+            .unwrap();
+
+    let mut rt = Runtime::new().unwrap();
+
+    let server = UnixDatagram::bind_addr(&server_addr).unwrap();
+    let client = UnixDatagram::bind_addr(&client_addr).unwrap();
+
+    rt.block_on(poll_fn(|| client.poll_send_to_addr(b"ping", &server_addr)))
+        .unwrap();
+    let (server, buf, n, _) = rt.block_on(server.recv_dgram(vec![0; 8])).unwrap();
+    assert_eq!(&buf[..n], b"ping");
+
+    server.connect_addr(&client_addr).unwrap();
+    rt.block_on(poll_fn(|| server.poll_send(b"pong"))).unwrap();
+    let (_, buf, n, _) = rt.block_on(client.recv_dgram(vec![0; 8])).unwrap();
+    assert_eq!(&buf[..n], b"pong");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn reply_to_abstract_peer() {
+    use futures::future::poll_fn;
+
+    let server_addr = UnixSocketAddr::from_abstract_name(format!(
+        "tokio-uds-reply-server-{}",
+        std::process::id()
+    ))
+    .unwrap();
+    let client_addr = UnixSocketAddr::from_abstract_name(format!(
+        "tokio-uds-reply-client-{}",
+        std::process::id()
+    ))
+    .unwrap();
+
+    let mut rt = Runtime::new().unwrap();
+
+    let server = UnixDatagram::bind_addr(&server_addr).unwrap();
+    let client = UnixDatagram::bind_addr(&client_addr).unwrap();
+    assert_eq!(client.local_socket_addr().unwrap(), client_addr);
+
+    rt.block_on(poll_fn(|| client.poll_send_to_addr(b"ping", &server_addr)))
+        .unwrap();
+
+    let mut buf = [0; 8];
+    let (n, peer) = rt
+        .block_on(poll_fn(|| server.poll_recv_from_addr(&mut buf)))
+        .unwrap();
+    assert_eq!(&buf[..n], b"ping");
+    assert_eq!(peer, client_addr);
+
+    rt.block_on(poll_fn(|| server.poll_send_to_addr(b"pong", &peer)))
+        .unwrap();
+    let (_, buf, n, _) = rt.block_on(client.recv_dgram(vec![0; 8])).unwrap();
+    assert_eq!(&buf[..n], b"pong");
+}
+
+#[test]
+fn recv_from_unnamed_peer() {
+    use futures::future::poll_fn;
+
+    let mut rt = Runtime::new().unwrap();
+
+    let (a, b) = UnixDatagram::pair().unwrap();
+    assert!(a.local_socket_addr().unwrap().is_unnamed());
+
+    rt.block_on(poll_fn(|| a.poll_send(b"ping"))).unwrap();
+
+    let mut buf = [0; 8];
+    let (n, peer) = rt
+        .block_on(poll_fn(|| b.poll_recv_from_addr(&mut buf)))
+        .unwrap();
+    assert_eq!(&buf[..n], b"ping");
+    assert!(peer.is_unnamed());
+}
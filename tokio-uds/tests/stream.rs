@@ -1,6 +1,8 @@
 #![cfg(unix)]
 
+#[macro_use]
 extern crate futures;
+extern crate mio;
 extern crate tokio;
 extern crate tokio_uds;
 
@@ -56,3 +58,139 @@ fn echo() {
 
     assert_eq!(buf, b"hello");
 }
+
+#[test]
+fn into_split() {
+    let mut rt = Runtime::new().unwrap();
+
+    let (a, b) = t!(UnixStream::pair());
+    let (a_rd, a_wr) = a.into_split();
+    let (b_rd, b_wr) = b.into_split();
+
+    // Write on one end and drop the write half, which sends EOF
+    let (a_wr, _) = t!(rt.block_on(io::write_all(a_wr, b"ping")));
+    drop(a_wr);
+
+    let (b_rd, buf) = t!(rt.block_on(io::read_to_end(b_rd, vec![])));
+    assert_eq!(buf, b"ping");
+
+    // The other direction is still open
+    let (b_wr, _) = t!(rt.block_on(io::write_all(b_wr, b"pong")));
+
+    let mut buf = [0; 4];
+    let (a_rd, _) = t!(rt.block_on(io::read_exact(a_rd, &mut buf)));
+    assert_eq!(&buf, b"pong");
+
+    // Only halves of the same stream can be reunited
+    let err = a_rd.reunite(b_wr).unwrap_err();
+    let b_wr = err.1;
+
+    t!(b_rd.reunite(b_wr));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn abstract_namespace() {
+    let name = format!("tokio-uds-tests-{}", std::process::id());
+    let addr = t!(UnixSocketAddr::from_abstract_name(&name));
+    assert_eq!(addr.as_abstract_name(), Some(name.as_bytes()));
+    assert_eq!(addr.as_pathname(), None);
+
+    let mut rt = Runtime::new().unwrap();
+
+    let server = t!(UnixListener::bind_addr(&addr));
+    let (tx, rx) = oneshot::channel();
+
+    rt.spawn({
+        server
+            .incoming()
+            .into_future()
+            .and_then(move |(sock, _)| {
+                tx.send(sock.unwrap()).unwrap();
+                Ok(())
+            })
+            .map_err(|e| panic!("err={:?}", e))
+    });
+
+    let client = rt.block_on(UnixStream::connect_addr(&addr)).unwrap();
+    let server = rt.block_on(rx).unwrap();
+
+    rt.block_on(io::write_all(client, b"hello")).unwrap();
+    let (_, buf) = rt.block_on(io::read_to_end(server, vec![])).unwrap();
+    assert_eq!(buf, b"hello");
+
+    // The name is not a path in the file system.
+    assert!(!std::path::Path::new(&name).exists());
+}
+
+#[test]
+fn std_conversions() {
+    use std::io::{Read, Write};
+    use std::os::unix::io::{FromRawFd, IntoRawFd};
+    use std::os::unix::net;
+
+    let dir = Builder::new().prefix("tokio-uds-tests").tempdir().unwrap();
+    let sock_path = dir.path().join("conversions.sock");
+
+    let mut rt = Runtime::new().unwrap();
+
+    // Inherited sockets are usually in blocking mode.
+    let std_listener = t!(net::UnixListener::bind(&sock_path));
+    let listener = unsafe { UnixListener::from_raw_fd(std_listener.into_raw_fd()) };
+
+    let client = t!(rt.block_on(UnixStream::connect(&sock_path)));
+    let (server, _) = t!(rt.block_on(listener.incoming().into_future().map_err(|e| e.0)));
+    let server = server.unwrap();
+
+    let mut client = t!(client.into_std());
+    t!(client.set_nonblocking(false));
+    t!(client.write_all(b"hello"));
+
+    let (server, buf) = t!(rt.block_on(io::read_exact(server, [0; 5])));
+    assert_eq!(&buf, b"hello");
+
+    // The stream is left in nonblocking mode.
+    let mut server = t!(server.into_std());
+    assert_eq!(
+        server.read(&mut [0; 5]).unwrap_err().kind(),
+        std::io::ErrorKind::WouldBlock
+    );
+}
+
+#[test]
+fn try_read_write() {
+    use futures::future::poll_fn;
+    use futures::Async;
+
+    let mut rt = Runtime::new().unwrap();
+    let (a, b) = t!(UnixStream::pair());
+
+    t!(rt.block_on(poll_fn(|| {
+        try_ready!(a.poll_write_ready());
+        assert_eq!(t!(a.try_write(b"hello")), 5);
+        Ok::<_, std::io::Error>(Async::Ready(()))
+    })));
+
+    let buf = t!(rt.block_on(poll_fn(|| {
+        try_ready!(b.poll_read_ready(mio::Ready::readable()));
+        let mut buf = [0; 8];
+        match b.try_read(&mut buf) {
+            Ok(n) => Ok(Async::Ready(buf[..n].to_vec())),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(Async::NotReady),
+            Err(e) => Err(e),
+        }
+    })));
+    assert_eq!(buf, b"hello");
+
+    // The peer closing the socket is reported as HUP, then EOF.
+    drop(a);
+    let n = t!(rt.block_on(poll_fn(|| {
+        try_ready!(b.poll_read_ready(mio::Ready::readable()));
+        match b.try_read(&mut [0; 8]) {
+            Ok(n) => Ok(Async::Ready(n)),
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(Async::NotReady),
+            Err(e) => Err(e),
+        }
+    })));
+    assert_eq!(n, 0);
+}